@@ -1,3 +1,4 @@
+use crate::agent::TaskListItem;
 use crate::permissions::ToolPermissionDescriptor;
 use crate::tools::todo_write::TodoItem;
 use crate::tools::{CategoryPhrasing, ToolRender};
@@ -26,6 +27,8 @@ pub enum AgentEvent {
         tool_call_id: String,
         tool_name: String,
         summary: String,
+        /// Estimated size of the result as it enters the conversation.
+        tokens: usize,
     },
     ToolExecutionStarted {
         tool_call_id: String,
@@ -38,14 +41,30 @@ pub enum AgentEvent {
     AllToolsComplete,
     FinalResponse(String),
     Error(String),
+    /// The backend refused or failed the request, with what the user might
+    /// do about it.
+    BackendError {
+        message: String,
+        hint: Option<String>,
+    },
     MaxStepsReached(usize),
+    /// The turn stopped early because the user interrupted it.
+    TurnInterrupted,
     ToolPermissionRequest {
-        descriptor: ToolPermissionDescriptor,
+        descriptor: Box<ToolPermissionDescriptor>,
         request_id: String,
     },
     ApprovalRequest {
         tool_call_id: String,
         tool_name: String,
+        /// The unified diff the tool would apply, shown in the dialog.
+        preview: Option<String>,
+    },
+    /// A persisted permission rule decided a tool call without asking.
+    PermissionRuleMatched {
+        tool: String,
+        rule: String,
+        allowed: bool,
     },
     UserRejection(Vec<String>),
     PermissionDenied(Vec<String>),
@@ -59,6 +78,23 @@ pub enum AgentEvent {
         message: String,
         is_success: bool,
     },
+    /// The request overflowed the context window, so the conversation was
+    /// compacted before retrying it.
+    /// A backend request is waiting on the configured request limits.
+    RequestQueued {
+        reason: String,
+    },
+    ContextAutoCompacted {
+        tokens_before: usize,
+        tokens_after: usize,
+    },
+    /// A file read (tool call or `@` mention) over the configured warning
+    /// size; `truncated` when it was also cut to a head window.
+    LargeFileRead {
+        path: String,
+        bytes: usize,
+        truncated: bool,
+    },
     TokenPressureWarning {
         current_pressure: f32,
         threshold: f32,
@@ -104,4 +140,35 @@ pub enum AgentEvent {
         model: Option<String>,
         save: bool,
     },
+    /// Change the reasoning effort of the active backend; `None` turns
+    /// reasoning off. Handled on the main task like `SwitchBackend`.
+    SetReasoningEffort {
+        effort: Option<crate::config::ReasoningEffort>,
+        save: bool,
+    },
+    /// Set `key` to `value` on the active backend's config, as
+    /// `AppConfig::update_backend_setting` would, and rebuild the backend.
+    /// Handled on the main task like `SwitchBackend`.
+    SetBackendSetting {
+        key: String,
+        value: String,
+        save: bool,
+    },
+    /// Make `name` the active agent, moving to its configured backend/model
+    /// (or back to the global default). Handled on the main task like
+    /// `SwitchBackend`.
+    SwitchAgent {
+        name: String,
+    },
+    /// Show assistant text verbatim (`Some(true)`) or as markdown
+    /// (`Some(false)`); `None` toggles.
+    SetRawOutput(Option<bool>),
+    /// Show a unified diff in the transcript, e.g. from `/diff`.
+    ShowDiff(String),
+    /// Open the task-list picker for `items` from the assistant message at
+    /// `message_index` in the conversation.
+    ShowTaskList {
+        message_index: usize,
+        items: Vec<TaskListItem>,
+    },
 }
@@ -1,9 +1,13 @@
+use crate::agent::retry_budget::RetryBudget;
+use crate::agent::task_list::{TaskListItem, parse_task_list, set_task_checked};
 use crate::console;
-use crate::storage::{ConversationMetadata, ConversationStorage};
+use crate::secret_redaction::SecretRedactor;
+use crate::storage::{ConversationMetadata, ConversationStorage, UsageRecord, UsageTotals};
 use crate::tools::error::ToolError;
 use crate::tools::{ListDirectoryTool, ReadFileTool};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +44,20 @@ pub struct ConversationMessage {
     pub attachments: Vec<Attachment>,
 }
 
+/// Marks the message `/compact` leaves in place of the history it summarized.
+/// Messages starting with it are pinned: context management never drops them.
+pub const SUMMARY_PREFIX: &str = "[Conversation summary]";
+
+impl ConversationMessage {
+    pub fn is_pinned_summary(&self) -> bool {
+        self.role == Role::User
+            && self
+                .content
+                .as_deref()
+                .is_some_and(|c| c.starts_with(SUMMARY_PREFIX))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AttachmentKind {
@@ -179,6 +197,15 @@ impl FileMention {
         }
     }
 
+    pub fn redact_secrets(&mut self, redactor: &SecretRedactor) {
+        let (FileMention::File { result, .. } | FileMention::Directory { result, .. }) = self;
+        if let Ok(output) = result
+            && let Cow::Owned(redacted) = redactor.redact(output)
+        {
+            *output = redacted;
+        }
+    }
+
     pub fn tool_name(&self) -> &'static str {
         match self {
             FileMention::File { .. } => ReadFileTool::NAME,
@@ -214,9 +241,54 @@ pub struct Conversation {
     pub metadata: ConversationMetadata,
     pub messages: Vec<ConversationMessage>,
     pub thinking_budget_override: Option<u32>,
+    /// Retries left in the turn in progress. The agent sets it for the
+    /// duration of a turn; backends and tools draw their retries from it.
+    pub retry_budget: Option<Arc<RetryBudget>>,
     storage: Option<Arc<ConversationStorage>>,
+    /// Number of lines in the on-disk message log. Grows with every append and
+    /// only shrinks on a rewrite, so it drifts above `messages.len()` as
+    /// context management drops messages from memory.
+    persisted_len: usize,
+    /// An append failed, so the on-disk log is missing messages until the
+    /// next rewrite; see [`Conversation::flush`].
+    unsaved: bool,
+    /// Alternate replies from `/regenerate`, kept in memory only.
+    variants: Option<ReplyVariants>,
 }
 
+/// Alternate replies to the latest prompt. Only the selected one is in
+/// `messages` and storage; the others wait here until the next prompt
+/// commits the choice.
+#[derive(Clone)]
+struct ReplyVariants {
+    /// Index in `messages` where the reply starts.
+    start: usize,
+    replies: Vec<Vec<ConversationMessage>>,
+    selected: usize,
+}
+
+/// Prefix of the ids of the synthetic tool calls that carry @-mentions.
+const MENTION_ID_PREFIX: &str = "mention_";
+
+fn is_mention(message: &ConversationMessage) -> bool {
+    match message.role {
+        Role::Assistant => message.tool_calls.as_ref().is_some_and(|calls| {
+            calls
+                .iter()
+                .all(|call| call.id.starts_with(MENTION_ID_PREFIX))
+        }),
+        Role::Tool => message
+            .tool_call_id
+            .as_ref()
+            .is_some_and(|id| id.starts_with(MENTION_ID_PREFIX)),
+        _ => false,
+    }
+}
+
+/// How many dropped messages the on-disk log may carry before
+/// [`Conversation::compact_storage_if_needed`] rewrites it.
+pub const STORAGE_COMPACTION_SLACK: usize = 32;
+
 /// Outcome of [`Conversation::cancel_in_flight_turn`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CancelKind {
@@ -244,7 +316,11 @@ impl Conversation {
             metadata: ConversationMetadata::new(id),
             messages: Vec::new(),
             thinking_budget_override: None,
+            retry_budget: None,
             storage: None,
+            persisted_len: 0,
+            unsaved: false,
+            variants: None,
         }
     }
 
@@ -254,7 +330,11 @@ impl Conversation {
             metadata,
             messages: Vec::new(),
             thinking_budget_override: None,
+            retry_budget: None,
             storage: Some(storage),
+            persisted_len: 0,
+            unsaved: false,
+            variants: None,
         })
     }
 
@@ -263,8 +343,12 @@ impl Conversation {
         let messages = storage.load_messages(id)?;
         Ok(Self {
             metadata,
+            persisted_len: messages.len(),
+            unsaved: false,
+            variants: None,
             messages,
             thinking_budget_override: None,
+            retry_budget: None,
             storage: Some(storage),
         })
     }
@@ -280,7 +364,11 @@ impl Conversation {
             metadata,
             messages: Vec::new(),
             thinking_budget_override: None,
+            retry_budget: None,
             storage: Some(storage),
+            persisted_len: 0,
+            unsaved: false,
+            variants: None,
         })
     }
 
@@ -324,6 +412,8 @@ impl Conversation {
         attachments: Vec<Attachment>,
         mentions: Vec<FileMention>,
     ) {
+        // A new prompt commits whichever reply variant is selected.
+        self.variants = None;
         self.add_user_message_with_attachments(content, attachments);
         for mention in mentions {
             self.add_file_mention(mention);
@@ -331,7 +421,7 @@ impl Conversation {
     }
 
     fn add_file_mention(&mut self, mention: FileMention) {
-        let tool_call_id = format!("mention_{}", uuid::Uuid::new_v4());
+        let tool_call_id = format!("{}{}", MENTION_ID_PREFIX, uuid::Uuid::new_v4());
         let tool_name = mention.tool_name();
         let display_name = mention.display_name();
         let args = mention.tool_args();
@@ -386,6 +476,7 @@ impl Conversation {
 
     pub fn clear(&mut self) {
         self.messages.clear();
+        self.variants = None;
     }
 
     pub fn clear_turn_history(&mut self) {
@@ -402,6 +493,58 @@ impl Conversation {
             .take_while(|m| m.role == Role::System)
             .count();
         self.messages.truncate(keep);
+        self.variants = None;
+    }
+
+    /// Replace every message after the leading system messages with one
+    /// pinned summary message, persisting the result. Returns how many
+    /// messages were replaced.
+    pub fn replace_with_summary(&mut self, summary: &str) -> usize {
+        let keep = self
+            .messages
+            .iter()
+            .take_while(|m| m.role == Role::System)
+            .count();
+        let replaced = self.messages.len() - keep;
+
+        self.messages.truncate(keep);
+        self.variants = None;
+        self.messages.push(ConversationMessage {
+            role: Role::User,
+            content: Some(format!("{}\n{}", SUMMARY_PREFIX, summary.trim())),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            attachments: Vec::new(),
+        });
+
+        if let Err(e) = self.rewrite_storage() {
+            eprintln!("Warning: failed to persist compacted conversation: {e}");
+        }
+        self.metadata.message_count = self.messages.len();
+        replaced
+    }
+
+    /// Content of the leading system message, which holds the agent prompt.
+    pub fn system_prompt(&self) -> Option<&str> {
+        self.messages
+            .first()
+            .filter(|m| m.role == Role::System)
+            .and_then(|m| m.content.as_deref())
+    }
+
+    /// Replace the agent prompt in place, persisting the result. Returns
+    /// false when the conversation doesn't start with a system message.
+    pub fn set_system_prompt(&mut self, content: String) -> bool {
+        let Some(message) = self.messages.first_mut().filter(|m| m.role == Role::System) else {
+            return false;
+        };
+        message.content = Some(content);
+
+        if let Err(e) = self.rewrite_storage() {
+            eprintln!("Warning: failed to persist updated system prompt: {e}");
+        }
+        true
     }
 
     pub fn get_messages_for_api(&self) -> &Vec<ConversationMessage> {
@@ -413,7 +556,7 @@ impl Conversation {
         self.metadata.update();
 
         if let Some(storage) = &self.storage
-            && let Err(e) = storage.save_metadata(&self.metadata)
+            && let Err(e) = storage.update_title(&self.metadata.id, title)
         {
             console().error(&format!("Warning: Failed to persist title update: {}", e))
         }
@@ -436,27 +579,255 @@ impl Conversation {
         self.metadata.update();
 
         if let Some(storage) = &self.storage
-            && let Err(e) = storage.save_metadata(&self.metadata)
+            && let Err(e) = storage.update_name(&self.metadata.id, self.metadata.name.clone())
         {
             console().error(&format!("Warning: Failed to persist name update: {}", e))
         }
     }
 
+    /// Record the directory, backend and model this conversation runs with.
+    pub fn set_origin(&mut self, working_dir: &std::path::Path, backend: &str, model: &str) {
+        self.metadata = self
+            .metadata
+            .clone()
+            .with_origin(working_dir, backend, model);
+
+        if let Some(storage) = &self.storage
+            && let Err(e) = storage.update_origin(&self.metadata.id, working_dir, backend, model)
+        {
+            console().error(&format!("Warning: Failed to persist origin update: {}", e))
+        }
+    }
+
+    /// Record a mid-session switch of backend or model, so resuming the
+    /// conversation picks it up again.
+    pub fn set_backend(&mut self, backend: &str, model: &str) {
+        self.metadata = self.metadata.clone().with_backend(backend, model);
+
+        if let Some(storage) = &self.storage
+            && let Err(e) = storage.update_backend(&self.metadata.id, backend, model)
+        {
+            console().error(&format!("Warning: Failed to persist backend update: {}", e))
+        }
+    }
+
+    /// Record the tokens and cost of one backend call. With storage, the
+    /// record is persisted and the in-memory history refreshed from disk so it
+    /// also reflects sub-agent usage attributed to this conversation.
+    pub fn record_usage(&mut self, record: UsageRecord) {
+        let Some(storage) = &self.storage else {
+            self.metadata.usage.push(record);
+            return;
+        };
+        match storage.record_usage(&self.metadata.id, record.clone()) {
+            Ok(metadata) => self.metadata.usage = metadata.usage,
+            Err(e) => {
+                eprintln!("Warning: Failed to persist token usage: {}", e);
+                self.metadata.usage.push(record);
+            }
+        }
+    }
+
+    pub fn usage_totals(&self) -> UsageTotals {
+        self.metadata.usage_totals()
+    }
+
     pub fn has_storage(&self) -> bool {
         self.storage.is_some()
     }
 
+    /// Task-list items from the most recent assistant message, with that
+    /// message's index. `None` if the latest reply has no task list.
+    pub fn latest_task_list(&self) -> Option<(usize, Vec<TaskListItem>)> {
+        let (index, message) = self
+            .messages
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, m)| m.role == Role::Assistant && m.content.is_some())?;
+        let items = parse_task_list(message.content.as_deref()?);
+        (!items.is_empty()).then_some((index, items))
+    }
+
+    /// Check or uncheck a task-list item in place so the model sees the new
+    /// state on its next turn. Returns false if the item no longer exists.
+    pub fn set_task_item_checked(
+        &mut self,
+        message_index: usize,
+        line: usize,
+        checked: bool,
+    ) -> bool {
+        let Some(content) = self
+            .messages
+            .get_mut(message_index)
+            .and_then(|m| m.content.as_mut())
+        else {
+            return false;
+        };
+        let Some(updated) = set_task_checked(content, line, checked) else {
+            return false;
+        };
+        *content = updated;
+
+        if let Err(e) = self.rewrite_storage() {
+            eprintln!("Warning: failed to persist task list update: {e}");
+        }
+        true
+    }
+
     fn persist_message(&mut self, message: &ConversationMessage) {
         if let Some(storage) = &self.storage {
             if let Err(e) = storage.append_message(&self.metadata.id, message) {
                 eprintln!("Warning: Failed to persist message: {}", e);
+                self.unsaved = true;
             } else {
+                self.persisted_len += 1;
                 self.metadata.message_count = self.messages.len();
                 self.metadata.update();
             }
         }
     }
 
+    /// Replace the on-disk message log with the in-memory messages.
+    fn rewrite_storage(&mut self) -> Result<()> {
+        if let Some(storage) = &self.storage {
+            storage.rewrite_messages(&self.metadata.id, &self.messages)?;
+            self.persisted_len = self.messages.len();
+            self.unsaved = false;
+        }
+        Ok(())
+    }
+
+    /// Make sure storage holds every message in memory. Appends normally
+    /// keep it current; after a failed one the whole log is rewritten. Called
+    /// on the way out of a crash, so it never blocks on anything but disk.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.unsaved {
+            self.rewrite_storage()?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the on-disk log so it holds only the messages still in memory,
+    /// dropping whatever context management has pruned since the last rewrite.
+    /// Returns true if the log was rewritten.
+    pub fn compact_storage(&mut self) -> bool {
+        if self.storage.is_none() {
+            return false;
+        }
+        match self.rewrite_storage() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Warning: failed to compact conversation log: {e}");
+                false
+            }
+        }
+    }
+
+    /// Compact the on-disk log once it carries at least
+    /// [`STORAGE_COMPACTION_SLACK`] messages that are no longer in memory.
+    /// Appends stay O(1) per message; this bounds how far the log can drift.
+    pub fn compact_storage_if_needed(&mut self) -> bool {
+        if self.persisted_len < self.messages.len() + STORAGE_COMPACTION_SLACK {
+            return false;
+        }
+        self.compact_storage()
+    }
+
+    /// Set the latest reply aside and cut the conversation back to the
+    /// prompt, so the agent can answer it again. Earlier variants are kept.
+    /// Returns false if the latest prompt has no reply yet.
+    pub fn begin_regeneration(&mut self) -> bool {
+        // Replies can contain user messages the agent adds itself, so a
+        // prompt already being regenerated keeps its recorded start.
+        let start = match &self.variants {
+            Some(variants) if variants.start <= self.messages.len() => variants.start,
+            _ => match self.reply_start() {
+                Some(start) => start,
+                None => return false,
+            },
+        };
+        if start >= self.messages.len() {
+            return false;
+        }
+
+        let reply = self.messages.split_off(start);
+        let mut variants = match self.variants.take() {
+            Some(mut variants) if variants.start == start => {
+                variants.replies[variants.selected] = reply;
+                variants
+            }
+            _ => ReplyVariants {
+                start,
+                replies: vec![reply],
+                selected: 0,
+            },
+        };
+        variants.replies.push(Vec::new());
+        variants.selected = variants.replies.len() - 1;
+        self.variants = Some(variants);
+
+        if let Err(e) = self.rewrite_storage() {
+            eprintln!("Warning: failed to rewrite conversation log: {e}");
+        }
+        self.metadata.message_count = self.messages.len();
+        true
+    }
+
+    /// Swap in the next (or previous) reply variant, so it is the one the
+    /// next request sees. Returns its 1-based position and the variant
+    /// count, or None if there is nothing to switch between.
+    pub fn cycle_reply_variant(&mut self, forward: bool) -> Option<(usize, usize)> {
+        let mut variants = self.variants.take()?;
+        if variants.start > self.messages.len() {
+            return None;
+        }
+
+        let current = self.messages.split_off(variants.start);
+        if current.is_empty() {
+            // The regeneration never produced a reply; forget the empty slot.
+            variants.replies.remove(variants.selected);
+            let count = variants.replies.len();
+            variants.selected = if forward {
+                variants.selected % count
+            } else {
+                (variants.selected + count - 1) % count
+            };
+        } else {
+            variants.replies[variants.selected] = current;
+            let count = variants.replies.len();
+            variants.selected = if forward {
+                (variants.selected + 1) % count
+            } else {
+                (variants.selected + count - 1) % count
+            };
+        }
+
+        self.messages
+            .extend(variants.replies[variants.selected].iter().cloned());
+        if let Err(e) = self.rewrite_storage() {
+            eprintln!("Warning: failed to rewrite conversation log: {e}");
+        }
+        self.metadata.message_count = self.messages.len();
+
+        let position = (variants.selected + 1, variants.replies.len());
+        if variants.replies.len() > 1 {
+            self.variants = Some(variants);
+        }
+        Some(position)
+    }
+
+    /// Index just past the latest user prompt and the @-mention reads
+    /// attached to it, where the assistant's reply starts.
+    fn reply_start(&self) -> Option<usize> {
+        let user_idx = self.messages.iter().rposition(|m| m.role == Role::User)?;
+        let mentions = self.messages[user_idx + 1..]
+            .iter()
+            .take_while(|m| is_mention(m))
+            .count();
+        Some(user_idx + 1 + mentions)
+    }
+
     pub fn has_pending_tool_calls(&self) -> bool {
         // Check last message first (assistant with tool_calls)
         if let Some(last_message) = self.messages.last()
@@ -589,9 +960,7 @@ impl Conversation {
             CancelKind::Thinking
         };
 
-        if let Some(storage) = &self.storage
-            && let Err(e) = storage.rewrite_messages(&self.metadata.id, &self.messages)
-        {
+        if let Err(e) = self.rewrite_storage() {
             eprintln!("Warning: failed to rewrite cancelled conversation log: {e}");
         }
         self.metadata.message_count = self.messages.len();
@@ -680,9 +1049,7 @@ impl Conversation {
 
         if changed {
             // Rewrite the persisted log so the sanitization survives reloads.
-            if let Some(storage) = &self.storage
-                && let Err(e) = storage.rewrite_messages(&self.metadata.id, &self.messages)
-            {
+            if let Err(e) = self.rewrite_storage() {
                 eprintln!("Warning: failed to rewrite sanitized conversation log: {e}");
             }
         }
@@ -758,7 +1125,11 @@ impl Clone for Conversation {
             metadata: self.metadata.clone(),
             messages: self.messages.clone(),
             thinking_budget_override: self.thinking_budget_override,
+            retry_budget: self.retry_budget.clone(),
             storage: self.storage.clone(),
+            persisted_len: self.persisted_len,
+            unsaved: self.unsaved,
+            variants: self.variants.clone(),
         }
     }
 }
@@ -808,6 +1179,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_latest_task_list_uses_last_assistant_reply() {
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("plan it".to_string());
+        conversation.add_assistant_message(Some("- [ ] old".to_string()), None);
+        conversation.add_user_message("again".to_string());
+        conversation.add_assistant_message(Some("Steps:\n- [ ] a\n- [x] b".to_string()), None);
+
+        let (index, items) = conversation.latest_task_list().unwrap();
+        assert_eq!(index, 3);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "a");
+        assert!(items[1].checked);
+    }
+
+    #[test]
+    fn test_latest_task_list_none_when_reply_has_no_tasks() {
+        let mut conversation = Conversation::new();
+        conversation.add_assistant_message(Some("- [ ] earlier".to_string()), None);
+        conversation.add_assistant_message(Some("No checklist here".to_string()), None);
+
+        assert!(conversation.latest_task_list().is_none());
+    }
+
+    #[test]
+    fn test_set_task_item_checked_edits_message() {
+        let mut conversation = Conversation::new();
+        conversation.add_assistant_message(Some("- [ ] a\n- [ ] b".to_string()), None);
+
+        assert!(conversation.set_task_item_checked(0, 1, true));
+        assert_eq!(
+            conversation.messages[0].content.as_deref(),
+            Some("- [ ] a\n- [x] b")
+        );
+        assert!(!conversation.set_task_item_checked(0, 7, true));
+        assert!(!conversation.set_task_item_checked(9, 0, true));
+    }
+
     #[test]
     fn test_tool_call_flow() {
         let mut conversation = Conversation::new();
@@ -1490,4 +1899,130 @@ mod tests {
         assert_eq!(conv.messages[2].tool_call_id.as_ref(), Some(id1));
         assert_eq!(conv.messages[4].tool_call_id.as_ref(), Some(id2));
     }
+
+    fn stored_conversation() -> (Conversation, Arc<ConversationStorage>, tempfile::TempDir) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let index = crate::storage::IndexStorage::new(temp.path().join("index.json"));
+        let storage = Arc::new(ConversationStorage::new_with_index(temp.path(), index));
+        let conversation = Conversation::with_storage("conv".to_string(), storage.clone()).unwrap();
+        (conversation, storage, temp)
+    }
+
+    fn add_tool_turn(conversation: &mut Conversation, n: usize) {
+        let id = format!("call_{n}");
+        conversation.add_user_message(format!("step {n}"));
+        conversation.add_assistant_message(
+            None,
+            Some(vec![ToolCall {
+                id: id.clone(),
+                r#type: "function".to_string(),
+                function: ToolFunction {
+                    name: "read_file".to_string(),
+                    arguments: "{}".to_string(),
+                },
+            }]),
+        );
+        conversation.add_tool_result(ToolCallResponse::success(
+            id,
+            "read_file".to_string(),
+            "Read".to_string(),
+            format!("output {n}"),
+        ));
+    }
+
+    fn as_json(messages: &[ConversationMessage]) -> Vec<serde_json::Value> {
+        messages
+            .iter()
+            .map(|m| serde_json::to_value(m).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_appended_messages_reload_identically() {
+        let (mut conversation, storage, _temp) = stored_conversation();
+        conversation.add_system_message("system".to_string());
+        add_tool_turn(&mut conversation, 1);
+        conversation.add_assistant_message(Some("done".to_string()), None);
+
+        let reloaded = Conversation::load("conv", storage).unwrap();
+        assert_eq!(as_json(&reloaded.messages), as_json(&conversation.messages));
+    }
+
+    #[test]
+    fn test_compaction_waits_for_enough_dropped_messages() {
+        let (mut conversation, storage, _temp) = stored_conversation();
+        add_tool_turn(&mut conversation, 1);
+        add_tool_turn(&mut conversation, 2);
+        conversation.messages.drain(..3);
+
+        assert!(!conversation.compact_storage_if_needed());
+        assert_eq!(storage.load_messages("conv").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_compaction_drops_pruned_messages_and_keeps_tool_pairs() {
+        let (mut conversation, storage, _temp) = stored_conversation();
+        conversation.add_system_message("system".to_string());
+        for n in 0..STORAGE_COMPACTION_SLACK {
+            add_tool_turn(&mut conversation, n);
+        }
+
+        // Mimic a sliding window: keep the system message and the last two turns.
+        let tail = conversation
+            .messages
+            .split_off(conversation.messages.len() - 6);
+        conversation.messages.truncate(1);
+        conversation.messages.extend(tail);
+
+        assert!(conversation.compact_storage_if_needed());
+        add_tool_turn(&mut conversation, 99);
+
+        let reloaded = Conversation::load("conv", storage).unwrap();
+        assert_eq!(as_json(&reloaded.messages), as_json(&conversation.messages));
+        assert_eq!(reloaded.messages.len(), 10);
+        for (i, message) in reloaded.messages.iter().enumerate() {
+            if let Some(calls) = &message.tool_calls {
+                let result = &reloaded.messages[i + 1];
+                assert_eq!(result.role, Role::Tool);
+                assert_eq!(result.tool_call_id.as_ref(), Some(&calls[0].id));
+            }
+        }
+    }
+
+    #[test]
+    fn test_replace_with_summary_keeps_system_and_pins_summary() {
+        let mut conversation = Conversation::new();
+        conversation.add_system_message("system".to_string());
+        add_tool_turn(&mut conversation, 1);
+        conversation.add_assistant_message(Some("done".to_string()), None);
+
+        let replaced = conversation.replace_with_summary("did the thing\n");
+
+        assert_eq!(replaced, 4);
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[0].role, Role::System);
+        assert!(conversation.messages[1].is_pinned_summary());
+        assert_eq!(
+            conversation.messages[1].content.as_deref(),
+            Some("[Conversation summary]\ndid the thing")
+        );
+    }
+
+    #[test]
+    fn test_usage_survives_reload() {
+        let (mut conversation, storage, _temp) = stored_conversation();
+        conversation.record_usage(UsageRecord {
+            input_tokens: 120,
+            output_tokens: 30,
+            cost: Some(0.002),
+            subagent: None,
+        });
+        conversation.set_title("usage".to_string());
+
+        let reloaded = Conversation::load("conv", storage).unwrap();
+        let totals = reloaded.usage_totals();
+        assert_eq!(totals.total_tokens(), 150);
+        assert_eq!(totals.calls, 1);
+        assert_eq!(reloaded.title(), "usage");
+    }
 }
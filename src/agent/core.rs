@@ -4,14 +4,50 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::mpsc;
 
 use crate::agent::agent_events::AgentEvent;
-use crate::agent::{Conversation, Role, ToolCall, ToolCallResponse};
-use crate::backends::{LlmBackend, LlmResponse};
-use crate::context_management::ContextManager;
+use crate::agent::retry_budget::RetryBudget;
+use crate::agent::title;
+use crate::agent::{Conversation, ToolCall, ToolCallResponse};
+use crate::backends::{LlmBackend, LlmError, LlmResponse, RequestLimiter, RequestPermit};
+use crate::context_management::{ContextManager, TokenUsageRecord};
 use crate::permissions::PermissionScope;
+use crate::storage::UsageRecord;
 use crate::system_reminders::{ReminderContext, SideEffectResult, SystemReminder};
+use crate::telemetry;
 use crate::tool_executor::ToolExecutor;
 use crate::tools::{ToolRegistry, ToolRender};
 
+/// Left in the conversation when the user interrupts a turn, so the model
+/// knows on the next turn why its work stopped.
+pub const INTERRUPTED_BY_USER_MESSAGE: &str =
+    "[The user interrupted this turn. Stop the current task and wait for their next instruction.]";
+
+/// Left in the conversation when a turn hits the step limit, so the model
+/// knows on the next turn that its work was cut off rather than finished.
+pub fn max_steps_reached_message(max_steps: usize) -> String {
+    format!(
+        "[This turn stopped after reaching the limit of {} steps. The task may be unfinished; \
+         wait for the user's next instruction before continuing.]",
+        max_steps
+    )
+}
+
+/// Result recorded for tool calls that were requested but never run because
+/// the turn was interrupted first.
+const INTERRUPTED_TOOL_RESULT: &str = "[not run: interrupted by user]";
+
+/// Rough size of a response the backend reported no usage for, at the same
+/// four bytes per token as `Conversation::estimate_token`.
+fn estimate_output_tokens(response: &LlmResponse) -> usize {
+    let content = response.content.as_deref().map_or(0, str::len);
+    let tool_calls: usize = response
+        .tool_calls
+        .iter()
+        .flatten()
+        .map(|call| call.function.name.len() + call.function.arguments.len())
+        .sum();
+    (content + tool_calls).div_ceil(4)
+}
+
 #[derive(Debug, Clone)]
 pub struct PermissionResponse {
     pub request_id: String,
@@ -35,7 +71,10 @@ pub struct Agent {
     context_manager: Option<Arc<ContextManager>>,
     system_reminder: Option<Arc<SystemReminder>>,
     cancellation_token: Option<Arc<AtomicBool>>,
+    interrupt: Option<Arc<AtomicBool>>,
     thinking_budget_override: Option<u32>,
+    title_backend: Option<Arc<dyn LlmBackend>>,
+    request_limiter: Option<Arc<RequestLimiter>>,
 }
 
 impl Agent {
@@ -53,7 +92,10 @@ impl Agent {
             context_manager: None,
             system_reminder: None,
             cancellation_token: None,
+            interrupt: None,
             thinking_budget_override: None,
+            title_backend: None,
+            request_limiter: None,
         }
     }
 
@@ -87,43 +129,74 @@ impl Agent {
         self
     }
 
-    pub async fn generate_title(&self, first_user_message: &str) -> Result<String> {
-        let prompt = format!(
-            "Generate a short title (5-8 words) for a conversation starting with: {}",
-            first_user_message
-        );
+    /// Set by the user to stop the turn gracefully: the in-flight backend
+    /// request or tool batch finishes, then the turn ends with every tool call
+    /// answered and [`INTERRUPTED_BY_USER_MESSAGE`] appended.
+    pub fn with_interrupt(mut self, interrupt: Arc<AtomicBool>) -> Self {
+        self.interrupt = Some(interrupt);
+        self
+    }
+
+    /// Backend used for conversation titles instead of the main one, usually
+    /// a cheaper model.
+    pub fn with_title_backend(mut self, backend: Option<Arc<dyn LlmBackend>>) -> Self {
+        self.title_backend = backend;
+        self
+    }
+
+    /// Limiter shared with other agents on the same backend; requests wait
+    /// for a slot instead of going out immediately.
+    pub fn with_request_limiter(mut self, limiter: Option<Arc<RequestLimiter>>) -> Self {
+        self.request_limiter = limiter;
+        self
+    }
+
+    async fn request_slot(&self) -> Option<RequestPermit> {
+        let limiter = self.request_limiter.as_ref()?;
+        Some(
+            limiter
+                .acquire(|reason| self.send_event(AgentEvent::RequestQueued { reason }))
+                .await,
+        )
+    }
+
+    fn interrupted(&self) -> bool {
+        self.interrupt
+            .as_ref()
+            .is_some_and(|i| i.load(Ordering::Relaxed))
+    }
 
-        let title = self.backend.send_message(&prompt).await?;
-        let title = title.trim().trim_matches('"').to_string();
+    fn title_backend(&self) -> &dyn LlmBackend {
+        self.title_backend
+            .as_deref()
+            .unwrap_or(self.backend.as_ref())
+    }
 
-        Ok(title)
+    pub async fn generate_title(&self, first_user_message: &str) -> Result<String> {
+        title::generate_title(self.title_backend(), first_user_message).await
     }
 
     async fn ensure_title(&self, conversation: &mut Conversation) {
-        if conversation.title().is_empty()
-            && let Some(first_user_msg) = conversation
-                .messages
-                .iter()
-                .find(|m| m.role == Role::User)
-                .and_then(|m| m.content.as_ref())
-        {
-            match self.generate_title(first_user_msg).await {
-                Ok(title) => {
-                    conversation.set_title(title.clone());
-                    self.send_event(AgentEvent::DebugMessage(format!(
-                        "Started: {} ({})",
-                        title,
-                        conversation.id()
-                    )));
-                }
-                Err(e) => {
-                    self.send_event(AgentEvent::DebugMessage(format!(
-                        "Warning: Failed to generate title: {}",
-                        e
-                    )));
-                }
-            }
+        if !conversation.title().is_empty() {
+            return;
         }
+        let Some(first_user_msg) = title::first_user_message(conversation) else {
+            return;
+        };
+
+        let (title, error) = title::title_or_fallback(self.title_backend(), first_user_msg).await;
+        if let Some(e) = error {
+            self.send_event(AgentEvent::DebugMessage(format!(
+                "Warning: Failed to generate title, using the first message: {}",
+                e
+            )));
+        }
+        conversation.set_title(title.clone());
+        self.send_event(AgentEvent::DebugMessage(format!(
+            "Started: {} ({})",
+            title,
+            conversation.id()
+        )));
     }
 
     fn send_event(&self, event: AgentEvent) {
@@ -133,6 +206,17 @@ impl Agent {
     }
 
     pub async fn handle_turn(&self, conversation: &mut Conversation) -> Result<()> {
+        conversation.retry_budget = self
+            .tool_executor
+            .turn_retry_budget()
+            .map(|limit| Arc::new(RetryBudget::new(limit)));
+        let result =
+            telemetry::agent_turn(self.backend.backend_name(), self.run_turn(conversation)).await;
+        conversation.retry_budget = None;
+        result
+    }
+
+    async fn run_turn(&self, conversation: &mut Conversation) -> Result<()> {
         self.send_event(AgentEvent::Thinking);
 
         if self.thinking_budget_override.is_some() {
@@ -177,6 +261,12 @@ impl Agent {
                 return Ok(());
             }
 
+            if self.interrupted() {
+                conversation.add_user_message(INTERRUPTED_BY_USER_MESSAGE.to_string());
+                self.send_event(AgentEvent::TurnInterrupted);
+                return Ok(());
+            }
+
             self.send_event(AgentEvent::StepStarted { step });
 
             let reminder_result = self.apply_system_reminders(conversation, step).await?;
@@ -208,15 +298,7 @@ impl Agent {
                 return Ok(());
             }
 
-            let response = match self
-                .backend
-                .send_message_with_tools_and_events(
-                    conversation,
-                    &self.tool_registry,
-                    self.event_sender.clone(),
-                )
-                .await
-            {
+            let response = match self.send_with_overflow_retry(conversation).await {
                 Ok(response) => response,
                 Err(e) if e.should_send_to_llm() => {
                     // Add error as user message so LLM can adjust
@@ -225,7 +307,22 @@ impl Agent {
                     continue;
                 }
                 Err(e) => {
-                    self.send_event(AgentEvent::Error(e.user_message()));
+                    let hint = if conversation
+                        .retry_budget
+                        .as_ref()
+                        .is_some_and(|budget| budget.is_exhausted())
+                    {
+                        Some(
+                            "This turn used up its retries; raise turn_retry_budget to allow more"
+                                .to_string(),
+                        )
+                    } else {
+                        e.hint().map(str::to_string)
+                    };
+                    self.send_event(AgentEvent::BackendError {
+                        message: e.user_message(),
+                        hint,
+                    });
                     return Err(anyhow::Error::new(e));
                 }
             };
@@ -239,6 +336,7 @@ impl Agent {
             }
         }
 
+        conversation.add_user_message(max_steps_reached_message(self.max_steps));
         self.send_event(AgentEvent::MaxStepsReached(self.max_steps));
         self.ensure_title(conversation).await;
         Ok(())
@@ -249,10 +347,13 @@ impl Agent {
         conversation: &mut Conversation,
         context_manager: &ContextManager,
     ) -> Result<()> {
-        context_manager
-            .apply_strategies(conversation)
-            .await
-            .expect("error applying context management");
+        if context_manager.should_apply(conversation) {
+            context_manager
+                .apply_strategies(conversation)
+                .await
+                .expect("error applying context management");
+            conversation.compact_storage_if_needed();
+        }
 
         let pressure_after = context_manager.get_token_pressure(conversation);
 
@@ -266,6 +367,66 @@ impl Agent {
         Ok(())
     }
 
+    /// Send the conversation, and if the provider says it no longer fits the
+    /// context window, compact once and resend. A second overflow is returned
+    /// as is, so this can't loop.
+    async fn send_with_overflow_retry(
+        &self,
+        conversation: &mut Conversation,
+    ) -> Result<LlmResponse, LlmError> {
+        let slot = self.request_slot().await;
+        let result = self
+            .backend
+            .send_message_with_tools_and_events(
+                conversation,
+                &self.tool_registry,
+                self.event_sender.clone(),
+            )
+            .await;
+        drop(slot);
+        match result {
+            Err(LlmError::ContextLengthExceeded { .. })
+                if self.compact_after_overflow(conversation).await =>
+            {
+                let _slot = self.request_slot().await;
+                self.backend
+                    .send_message_with_tools_and_events(
+                        conversation,
+                        &self.tool_registry,
+                        self.event_sender.clone(),
+                    )
+                    .await
+            }
+            other => other,
+        }
+    }
+
+    /// Run every compaction strategy regardless of the trigger. Returns false
+    /// when there is no context manager or nothing could be dropped, in which
+    /// case a retry would only fail the same way.
+    async fn compact_after_overflow(&self, conversation: &mut Conversation) -> bool {
+        let Some(context_manager) = &self.context_manager else {
+            return false;
+        };
+        let tokens_before = conversation.estimate_token();
+        if let Err(e) = context_manager.apply_strategies(conversation).await {
+            self.send_event(AgentEvent::DebugMessage(format!(
+                "Compaction after context overflow failed: {e}"
+            )));
+            return false;
+        }
+        let tokens_after = conversation.estimate_token();
+        if tokens_after >= tokens_before {
+            return false;
+        }
+        conversation.compact_storage_if_needed();
+        self.send_event(AgentEvent::ContextAutoCompacted {
+            tokens_before,
+            tokens_after,
+        });
+        true
+    }
+
     async fn apply_system_reminders(
         &self,
         conversation: &mut Conversation,
@@ -284,17 +445,38 @@ impl Agent {
         conversation: &mut Conversation,
         response: LlmResponse,
     ) -> Result<TurnStatus> {
-        // Record token usage in context manager if available
-        if let (Some(input_tokens), Some(output_tokens)) =
-            (response.input_tokens, response.output_tokens)
-        {
-            if let Some(context_manager) = &self.context_manager {
-                context_manager.record_token_usage(input_tokens, output_tokens);
-            }
+        // Record token usage in context manager if available, estimating it
+        // for backends that don't report any
+        let reported = response.input_tokens.zip(response.output_tokens);
+        if let Some(context_manager) = &self.context_manager {
+            let record = match reported {
+                Some((input_tokens, output_tokens)) => {
+                    TokenUsageRecord::from_backend(input_tokens, output_tokens)
+                }
+                None => TokenUsageRecord::from_estimate(
+                    conversation.estimate_token(),
+                    estimate_output_tokens(&response),
+                ),
+            };
+            context_manager.record_token_usage(record, conversation);
+        }
+        if let Some((input_tokens, output_tokens)) = reported {
             let cost = self
                 .backend
                 .pricing()
                 .map(|p| p.calculate_cost(input_tokens, output_tokens));
+            telemetry::record_usage(
+                self.backend.backend_name(),
+                input_tokens,
+                output_tokens,
+                cost,
+            );
+            conversation.record_usage(UsageRecord {
+                input_tokens,
+                output_tokens,
+                cost,
+                subagent: None,
+            });
             self.send_event(AgentEvent::TokenUsage {
                 input_tokens,
                 output_tokens,
@@ -336,6 +518,20 @@ impl Agent {
 
         conversation.add_assistant_message(response.content.clone(), Some(tool_calls.clone()));
 
+        // Interrupted while the model was answering: answer its tool calls
+        // without running them so the history stays well-formed.
+        if self.interrupted() {
+            for tool_call in &tool_calls {
+                conversation.add_tool_result(ToolCallResponse::success(
+                    tool_call.id.clone(),
+                    tool_call.function.name.clone(),
+                    tool_call.function.name.clone(),
+                    INTERRUPTED_TOOL_RESULT.to_string(),
+                ));
+            }
+            return Ok(TurnStatus::Continue);
+        }
+
         // Phase 1: Emit tool call events
         if let Some(ref content) = response.content {
             self.send_event(AgentEvent::AssistantThought(content.clone()));
@@ -346,7 +542,11 @@ impl Agent {
         let conversation_id = Some(conversation.id());
         let tool_results = self
             .tool_executor
-            .execute_tool_calls(&tool_calls, conversation_id)
+            .execute_tool_calls(
+                &tool_calls,
+                conversation_id,
+                conversation.retry_budget.as_deref(),
+            )
             .await;
 
         // Phase 3: Check for rejections and permission denials
@@ -161,34 +161,52 @@ async fn agent_continues_after_successful_tool_call() {
 
 #[tokio::test]
 async fn agent_respects_max_steps_limit() {
-    let backend = Arc::new(MockBackend::new(vec![]));
-    let agent = Agent::new(
-        backend,
-        Arc::new(ToolRegistry::new()),
-        Arc::new(ToolExecutor::new(
-            Arc::new(ToolRegistry::new()),
-            Arc::new(
-                PermissionManager::new(
-                    {
-                        let (tx, _) = mpsc::unbounded_channel();
-                        tx
-                    },
-                    {
-                        let (_, rx) = mpsc::unbounded_channel();
-                        rx
+    // A model that never stops asking for another tool call.
+    let responses = (0..10)
+        .map(|i| {
+            LlmResponse::with_tool_calls(
+                None,
+                vec![ToolCall {
+                    id: format!("call_{i}"),
+                    r#type: "function".to_string(),
+                    function: ToolFunction {
+                        name: "test_tool".to_string(),
+                        arguments: "{}".to_string(),
                     },
-                )
-                .with_skip_permissions(true),
-            ),
-        )),
-    )
-    .with_max_steps(2);
+                }],
+            )
+        })
+        .collect();
+    let backend = MockBackend::new(responses);
+    let call_count = Arc::clone(&backend.call_count);
+
+    let (agent, _, _, _) = create_test_agent(Arc::new(backend));
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let agent = agent.with_event_sender(event_tx).with_max_steps(3);
 
     let mut conversation = Conversation::new();
+    // Titled up front so no title request counts against the backend calls.
+    conversation.set_title("Looping".to_string());
     conversation.add_user_message("Test".to_string());
 
     let result = agent.handle_turn(&mut conversation).await;
-    assert!(result.is_err() || result.is_ok());
+
+    assert!(result.is_ok());
+    assert_eq!(call_count.load(Ordering::SeqCst), 3);
+    let last = conversation.messages.last().unwrap();
+    assert_eq!(last.role, Role::User);
+    assert_eq!(
+        last.content.as_deref(),
+        Some(max_steps_reached_message(3).as_str())
+    );
+
+    let mut limit_events = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        if let AgentEvent::MaxStepsReached(max_steps) = event {
+            limit_events.push(max_steps);
+        }
+    }
+    assert_eq!(limit_events, vec![3]);
 }
 
 #[tokio::test]
@@ -291,6 +309,39 @@ async fn agent_strips_quotes_from_title() {
     assert!(!title.contains('"'));
 }
 
+#[tokio::test]
+async fn agent_falls_back_to_first_message_when_title_generation_fails() {
+    // One response for the turn; the title request then finds none and errors.
+    let backend = Arc::new(MockBackend::new(vec![LlmResponse::content_only(
+        "Sure.".to_string(),
+    )]));
+
+    let (agent, _, _, _) = create_test_agent(backend);
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Refactor the storage index\nand add tests".to_string());
+    agent.handle_turn(&mut conversation).await.unwrap();
+
+    assert_eq!(conversation.title(), "Refactor the storage index");
+}
+
+#[tokio::test]
+async fn agent_uses_title_backend_when_configured() {
+    let main = Arc::new(MockBackend::new(vec![LlmResponse::content_only(
+        "Sure.".to_string(),
+    )]));
+    let titles: Arc<dyn LlmBackend> = Arc::new(MockBackend::new(vec![LlmResponse::content_only(
+        "Storage Index Refactor".to_string(),
+    )]));
+
+    let (agent, _, _, _) = create_test_agent(main);
+    let agent = agent.with_title_backend(Some(titles));
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Refactor the storage index".to_string());
+    agent.handle_turn(&mut conversation).await.unwrap();
+
+    assert_eq!(conversation.title(), "Storage Index Refactor");
+}
+
 #[tokio::test]
 async fn permission_response_creation() {
     let response = PermissionResponse {
@@ -669,3 +720,369 @@ async fn agent_handles_budget_exhaustion() {
     let result = agent.handle_turn(&mut conversation).await;
     assert!(result.is_ok());
 }
+
+/// Raises `interrupt` when the request is made, as if the user pressed Esc
+/// while the model was answering.
+struct InterruptingBackend {
+    inner: MockBackend,
+    interrupt: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl LlmBackend for InterruptingBackend {
+    async fn send_message(&self, message: &str) -> Result<String> {
+        self.inner.send_message(message).await
+    }
+
+    async fn send_message_with_tools(
+        &self,
+        conversation: &Conversation,
+        tools: &ToolRegistry,
+    ) -> Result<LlmResponse, LlmError> {
+        self.interrupt.store(true, Ordering::SeqCst);
+        self.inner
+            .send_message_with_tools(conversation, tools)
+            .await
+    }
+
+    fn backend_name(&self) -> &str {
+        "mock"
+    }
+
+    fn model_name(&self) -> &str {
+        "mock-model"
+    }
+}
+
+#[tokio::test]
+async fn interrupt_answers_pending_tool_calls_and_ends_turn() {
+    let interrupt = Arc::new(AtomicBool::new(false));
+    let tool_call = ToolCall {
+        id: "call_interrupted".to_string(),
+        r#type: "function".to_string(),
+        function: ToolFunction {
+            name: "test_tool".to_string(),
+            arguments: "{}".to_string(),
+        },
+    };
+    let backend = Arc::new(InterruptingBackend {
+        inner: MockBackend::new(vec![
+            LlmResponse::with_tool_calls(None, vec![tool_call]),
+            LlmResponse::content_only("Picking up again".to_string()),
+        ]),
+        interrupt: Arc::clone(&interrupt),
+    });
+    let call_count = Arc::clone(&backend.inner.call_count);
+
+    let (agent, _, _, _) = create_test_agent(backend);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let agent = agent
+        .with_event_sender(event_tx)
+        .with_interrupt(Arc::clone(&interrupt));
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Refactor everything".to_string());
+
+    agent.handle_turn(&mut conversation).await.unwrap();
+
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    let roles: Vec<Role> = conversation.messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        vec![Role::User, Role::Assistant, Role::Tool, Role::User]
+    );
+    assert_eq!(
+        conversation.messages[2].tool_call_id.as_deref(),
+        Some("call_interrupted")
+    );
+    assert_eq!(
+        conversation.messages[3].content.as_deref(),
+        Some(INTERRUPTED_BY_USER_MESSAGE)
+    );
+
+    let mut saw_interrupted = false;
+    while let Ok(event) = event_rx.try_recv() {
+        saw_interrupted |= matches!(event, AgentEvent::TurnInterrupted);
+    }
+    assert!(saw_interrupted);
+
+    // The next prompt runs normally once the flag is cleared.
+    interrupt.store(false, Ordering::SeqCst);
+    conversation.add_user_message("Just fix the tests".to_string());
+    agent.handle_turn(&mut conversation).await.unwrap();
+    assert_eq!(
+        conversation.messages.last().unwrap().content.as_deref(),
+        Some("Picking up again")
+    );
+}
+
+/// Fails with a context overflow while the conversation is estimated above
+/// `limit` tokens, and answers normally once it fits.
+struct SmallWindowBackend {
+    limit: usize,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl LlmBackend for SmallWindowBackend {
+    async fn send_message(&self, _message: &str) -> Result<String> {
+        Ok("Title".to_string())
+    }
+
+    async fn send_message_with_tools(
+        &self,
+        conversation: &Conversation,
+        _tools: &ToolRegistry,
+    ) -> Result<LlmResponse, LlmError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if conversation.estimate_token() > self.limit {
+            return Err(LlmError::ContextLengthExceeded {
+                message: "prompt is too long".to_string(),
+            });
+        }
+        Ok(LlmResponse::content_only("Fits now".to_string()))
+    }
+
+    fn backend_name(&self) -> &str {
+        "mock"
+    }
+
+    fn model_name(&self) -> &str {
+        "mock-model"
+    }
+}
+
+fn long_conversation() -> Conversation {
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Refactor the parser".to_string());
+    for _ in 0..6 {
+        conversation.add_assistant_message(Some("x".repeat(4_000)), None);
+        conversation.add_user_message("keep going".to_string());
+    }
+    conversation
+}
+
+fn sliding_window_manager(max_tokens: usize) -> Arc<ContextManager> {
+    use crate::context_management::{
+        CompactionTrigger, ContextManagerConfig, SlidingWindowConfig, SlidingWindowStrategy,
+        TokenAccountant,
+    };
+
+    let config = ContextManagerConfig::default().with_trigger(CompactionTrigger::Manual);
+    Arc::new(
+        ContextManager::new(config, Arc::new(TokenAccountant::new())).add_strategy(Box::new(
+            SlidingWindowStrategy::new(SlidingWindowConfig {
+                max_tokens,
+                ..Default::default()
+            }),
+        )),
+    )
+}
+
+#[tokio::test]
+async fn context_overflow_compacts_once_and_retries() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let backend = Arc::new(SmallWindowBackend {
+        limit: 3_000,
+        calls: Arc::clone(&calls),
+    });
+    let (agent, _, _, _) = create_test_agent(backend);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let agent = agent
+        .with_event_sender(event_tx)
+        .with_context_manager(sliding_window_manager(2_000));
+
+    let mut conversation = long_conversation();
+    agent.handle_turn(&mut conversation).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        conversation.messages.last().unwrap().content.as_deref(),
+        Some("Fits now")
+    );
+    let mut compacted = None;
+    while let Ok(event) = event_rx.try_recv() {
+        if let AgentEvent::ContextAutoCompacted {
+            tokens_before,
+            tokens_after,
+        } = event
+        {
+            compacted = Some((tokens_before, tokens_after));
+        }
+    }
+    let (before, after) = compacted.expect("expected a compaction event");
+    assert!(after < before);
+}
+
+#[tokio::test]
+async fn context_overflow_is_retried_at_most_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let backend = Arc::new(SmallWindowBackend {
+        limit: 100,
+        calls: Arc::clone(&calls),
+    });
+    let (agent, _, _, _) = create_test_agent(backend);
+    let agent = agent.with_context_manager(sliding_window_manager(2_000));
+
+    let mut conversation = long_conversation();
+    let result = agent.handle_turn(&mut conversation).await;
+
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Nothing left to drop: the error surfaces without a retry.
+    calls.store(0, Ordering::SeqCst);
+    assert!(agent.handle_turn(&mut conversation).await.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+/// Asks for one flaky tool call, then fails every request with a rate limit
+/// that a RequestExecutor would retry five times.
+struct FlakyBackend {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl LlmBackend for FlakyBackend {
+    async fn send_message(&self, _message: &str) -> Result<String> {
+        anyhow::bail!("unused")
+    }
+
+    async fn send_message_with_tools(
+        &self,
+        conversation: &Conversation,
+        tools: &ToolRegistry,
+    ) -> Result<LlmResponse, LlmError> {
+        self.send_message_with_tools_and_events(conversation, tools, None)
+            .await
+    }
+
+    async fn send_message_with_tools_and_events(
+        &self,
+        conversation: &Conversation,
+        _tools: &ToolRegistry,
+        event_sender: Option<mpsc::UnboundedSender<AgentEvent>>,
+    ) -> Result<LlmResponse, LlmError> {
+        crate::backends::RequestExecutor::new(5, "flaky".to_string())
+            .execute(
+                || async {
+                    if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Ok(LlmResponse::with_tool_calls(
+                            None,
+                            vec![ToolCall {
+                                id: "call_1".to_string(),
+                                r#type: "function".to_string(),
+                                function: ToolFunction {
+                                    name: "locked_read".to_string(),
+                                    arguments: "{}".to_string(),
+                                },
+                            }],
+                        ));
+                    }
+                    Err(LlmError::RateLimit {
+                        retry_after: Some(0),
+                        message: "slow down".to_string(),
+                    })
+                },
+                event_sender,
+                conversation.retry_budget.clone(),
+            )
+            .await
+    }
+
+    fn backend_name(&self) -> &str {
+        "flaky"
+    }
+
+    fn model_name(&self) -> &str {
+        "flaky-model"
+    }
+
+    fn pricing(&self) -> Option<crate::backends::TokenPricing> {
+        None
+    }
+}
+
+struct LockedReadTool {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl crate::tools::Tool for LockedReadTool {
+    fn name(&self) -> &'static str {
+        "locked_read"
+    }
+    fn display_name(&self) -> &'static str {
+        "locked read"
+    }
+    fn description(&self) -> &'static str {
+        "always finds its file locked"
+    }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+    fn parameter_schema(&self) -> serde_json::Value {
+        serde_json::json!({"type": "object", "properties": {}, "required": []})
+    }
+    async fn execute(
+        &self,
+        _args: &serde_json::Value,
+        _ctx: &crate::tools::ToolExecutionContext,
+    ) -> crate::tools::error::ToolResult<String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Err(crate::tools::error::ToolError::ReadFailed {
+            path: "locked.txt".into(),
+        })
+    }
+    fn describe_permission(&self, target: Option<&str>) -> crate::ToolPermissionDescriptor {
+        crate::ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_read_only()
+            .build()
+            .unwrap()
+    }
+}
+
+#[tokio::test]
+async fn retry_budget_is_shared_across_tool_and_backend_retries() {
+    let backend_calls = Arc::new(AtomicUsize::new(0));
+    let tool_calls = Arc::new(AtomicUsize::new(0));
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_tool(Arc::new(LockedReadTool {
+            calls: Arc::clone(&tool_calls),
+        }))
+        .unwrap();
+    let registry = Arc::new(registry);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let (_, response_rx) = mpsc::unbounded_channel();
+    let permission_manager =
+        Arc::new(PermissionManager::new(event_tx.clone(), response_rx).with_skip_permissions(true));
+    let tool_executor = Arc::new(
+        ToolExecutor::new(Arc::clone(&registry), permission_manager)
+            .with_tool_retries(2)
+            .with_retry_backoff(std::time::Duration::from_millis(1))
+            .with_turn_retry_budget(3),
+    );
+    let backend = Arc::new(FlakyBackend {
+        calls: Arc::clone(&backend_calls),
+    });
+    let agent = Agent::new(backend, registry, tool_executor).with_event_sender(event_tx);
+
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Read the locked file".to_string());
+    let result = agent.handle_turn(&mut conversation).await;
+
+    assert!(result.is_err());
+    // The tool takes its two retries, which leaves one for the backend: the
+    // request that would have been tried five times is tried twice.
+    assert_eq!(tool_calls.load(Ordering::SeqCst), 3);
+    assert_eq!(backend_calls.load(Ordering::SeqCst), 1 + 2);
+
+    let mut hint = None;
+    while let Ok(event) = event_rx.try_recv() {
+        if let AgentEvent::BackendError { hint: h, .. } = event {
+            hint = h;
+        }
+    }
+    assert!(hint.is_some_and(|h| h.contains("turn_retry_budget")));
+}
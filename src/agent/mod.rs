@@ -1,10 +1,15 @@
 mod agent_events;
 mod conversation;
 mod core;
+pub mod retry_budget;
+mod task_list;
+pub mod title;
 
 pub use agent_events::{AgentEvent, PendingToolCall};
 pub use conversation::{
     Attachment, AttachmentKind, CancelKind, Conversation, ConversationMessage, FileMention, Role,
-    ToolCall, ToolCallResponse, ToolExecutionContext, ToolFunction,
+    SUMMARY_PREFIX, ToolCall, ToolCallResponse, ToolExecutionContext, ToolFunction,
 };
 pub use core::{Agent, ApprovalResponse, PermissionResponse};
+pub use retry_budget::RetryBudget;
+pub use task_list::{TaskListItem, parse_task_list, set_task_checked};
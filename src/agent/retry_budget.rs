@@ -0,0 +1,60 @@
+//! A cap on retries across one agent turn.
+//!
+//! Backend requests and tool calls each retry on their own, so a turn where
+//! many of them flake can retry dozens of times. The agent puts a
+//! [`RetryBudget`] on the conversation for the turn, and hands it to every
+//! retry site, which takes from it with [`RetryBudget::take_retry`]; once it
+//! is spent, failures are returned instead.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Debug)]
+pub struct RetryBudget {
+    limit: u32,
+    used: AtomicU32,
+}
+
+impl RetryBudget {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            used: AtomicU32::new(0),
+        }
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn used(&self) -> u32 {
+        self.used.load(Ordering::SeqCst).min(self.limit)
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.used.load(Ordering::SeqCst) >= self.limit
+    }
+
+    /// Take one retry, if any are left.
+    pub fn take_retry(&self) -> bool {
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                (used < self.limit).then_some(used + 1)
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_caps_retries() {
+        let budget = RetryBudget::new(2);
+        let taken = (0..5).filter(|_| budget.take_retry()).count();
+
+        assert_eq!(taken, 2);
+        assert!(budget.is_exhausted());
+        assert_eq!(budget.used(), 2);
+    }
+}
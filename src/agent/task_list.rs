@@ -0,0 +1,156 @@
+/// A GitHub-style task-list item (`- [ ] text`) found in message markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskListItem {
+    /// Zero-based line index within the message content.
+    pub line: usize,
+    pub checked: bool,
+    pub text: String,
+}
+
+/// Byte offset of the `[ ]`/`[x]` marker on a task-list line, if it is one.
+fn marker_offset(line: &str) -> Option<usize> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+
+    let after_bullet = if let Some(r) = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))
+    {
+        r
+    } else {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        rest[digits..]
+            .strip_prefix(". ")
+            .or_else(|| rest[digits..].strip_prefix(") "))?
+    };
+
+    let marker = after_bullet.get(..3)?;
+    if matches!(marker, "[ ]" | "[x]" | "[X]")
+        && after_bullet[3..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+    {
+        Some(line.len() - after_bullet.len())
+    } else {
+        None
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Collect task-list items, skipping anything inside fenced code blocks.
+pub fn parse_task_list(markdown: &str) -> Vec<TaskListItem> {
+    let mut items = Vec::new();
+    let mut in_fence = false;
+
+    for (idx, line) in markdown.lines().enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(offset) = marker_offset(line) {
+            items.push(TaskListItem {
+                line: idx,
+                checked: &line[offset..offset + 3] != "[ ]",
+                text: line[offset + 3..].trim().to_string(),
+            });
+        }
+    }
+
+    items
+}
+
+/// Rewrite the marker on `line` to `checked`. Returns `None` if that line is
+/// not a task-list item. Line endings are preserved.
+pub fn set_task_checked(markdown: &str, line: usize, checked: bool) -> Option<String> {
+    let mut output = String::with_capacity(markdown.len());
+    let mut found = false;
+
+    for (idx, segment) in markdown.split_inclusive('\n').enumerate() {
+        if idx == line {
+            let offset = marker_offset(segment)?;
+            output.push_str(&segment[..offset]);
+            output.push_str(if checked { "[x]" } else { "[ ]" });
+            output.push_str(&segment[offset + 3..]);
+            found = true;
+        } else {
+            output.push_str(segment);
+        }
+    }
+
+    found.then_some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unchecked_and_checked_items() {
+        let md = "Plan:\n- [ ] write tests\n- [x] fix bug\n* [X] ship\n1. [ ] review\n";
+        let items = parse_task_list(md);
+
+        assert_eq!(
+            items,
+            vec![
+                TaskListItem {
+                    line: 1,
+                    checked: false,
+                    text: "write tests".to_string()
+                },
+                TaskListItem {
+                    line: 2,
+                    checked: true,
+                    text: "fix bug".to_string()
+                },
+                TaskListItem {
+                    line: 3,
+                    checked: true,
+                    text: "ship".to_string()
+                },
+                TaskListItem {
+                    line: 4,
+                    checked: false,
+                    text: "review".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_plain_list_items_and_code_blocks() {
+        let md = "- plain item\n- [link](x)\n```\n- [ ] not a task\n```\n  - [ ] nested task";
+        let items = parse_task_list(md);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].line, 5);
+        assert_eq!(items[0].text, "nested task");
+    }
+
+    #[test]
+    fn set_task_checked_toggles_only_the_marker() {
+        let md = "- [ ] one\r\n- [ ] two\r\n";
+        let updated = set_task_checked(md, 1, true).unwrap();
+        assert_eq!(updated, "- [ ] one\r\n- [x] two\r\n");
+
+        let reverted = set_task_checked(&updated, 1, false).unwrap();
+        assert_eq!(reverted, md);
+    }
+
+    #[test]
+    fn set_task_checked_rejects_non_task_lines() {
+        assert!(set_task_checked("just text\n- [ ] task", 0, true).is_none());
+        assert!(set_task_checked("- [ ] task", 5, true).is_none());
+    }
+}
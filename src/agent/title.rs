@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+use crate::agent::{Conversation, Role};
+use crate::backends::LlmBackend;
+
+/// Longest fallback title, in characters, cut from the first user message.
+pub const FALLBACK_TITLE_CHARS: usize = 60;
+
+/// Ask `backend` for a short title for a conversation that opens with
+/// `first_user_message`.
+pub async fn generate_title(backend: &dyn LlmBackend, first_user_message: &str) -> Result<String> {
+    let prompt = format!(
+        "Generate a short title (5-8 words) for a conversation starting with: {}\n\n\
+         Reply with the title only.",
+        first_user_message
+    );
+
+    let title = backend.send_message(&prompt).await?;
+    let title = title.trim().trim_matches('"').to_string();
+    if title.is_empty() {
+        anyhow::bail!("backend returned an empty title");
+    }
+
+    Ok(title)
+}
+
+/// Title used when generation fails: the first line of the first user
+/// message, cut to [`FALLBACK_TITLE_CHARS`].
+pub fn fallback_title(first_user_message: &str) -> String {
+    let line = first_user_message.trim().lines().next().unwrap_or_default();
+    let mut chars = line.chars();
+    let mut title: String = chars.by_ref().take(FALLBACK_TITLE_CHARS).collect();
+    if chars.next().is_some() {
+        title = format!("{}…", title.trim_end());
+    }
+    title
+}
+
+/// Content of the first user message, the one a title describes.
+pub fn first_user_message(conversation: &Conversation) -> Option<&str> {
+    conversation
+        .messages
+        .iter()
+        .find(|m| m.role == Role::User)
+        .and_then(|m| m.content.as_deref())
+}
+
+/// Generate a title, falling back to [`fallback_title`] if the backend fails.
+pub async fn title_or_fallback(
+    backend: &dyn LlmBackend,
+    first_user_message: &str,
+) -> (String, Option<anyhow::Error>) {
+    match generate_title(backend, first_user_message).await {
+        Ok(title) => (title, None),
+        Err(e) => (fallback_title(first_user_message), Some(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_uses_first_line() {
+        assert_eq!(
+            fallback_title("  Fix the login bug\nmore detail"),
+            "Fix the login bug"
+        );
+    }
+
+    #[test]
+    fn fallback_truncates_long_messages() {
+        let title = fallback_title(&"word ".repeat(40));
+        assert!(title.ends_with('…'));
+        assert!(title.chars().count() <= FALLBACK_TITLE_CHARS + 1);
+    }
+}
@@ -15,6 +15,8 @@ pub struct AgentDefinition {
     pub tags: Vec<String>,
     #[serde(skip)]
     pub core_instructions: String,
+    pub backend: Option<String>,
+    pub model: Option<String>,
 }
 
 pub struct AgentDefinitionManager {
@@ -35,8 +37,15 @@ impl AgentDefinition {
             description: config.description,
             tags: config.tags,
             core_instructions,
+            backend: config.backend,
+            model: config.model,
         }
     }
+
+    /// Whether this agent picks its own backend or model.
+    pub fn overrides_backend(&self) -> bool {
+        self.backend.is_some() || self.model.is_some()
+    }
 }
 
 impl AgentDefinitionManager {
@@ -1,6 +1,7 @@
-use super::{LlmBackend, LlmResponse, RequestExecutor};
+use super::{LlmBackend, LlmResponse, RequestExecutor, RetryPolicy};
 use crate::agent::{Conversation, ConversationMessage, Role, ToolCall};
 use crate::backends::llm_error::LlmError;
+use crate::backends::stop_sequences;
 use crate::tools::ToolRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -12,6 +13,9 @@ pub struct AnthropicConfig {
     pub api_key: String,
     pub model: String,
     pub base_url: String,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop: Vec<String>,
     pub thinking_budget: Option<u32>,
     pub streaming: bool,
 }
@@ -22,6 +26,9 @@ impl Default for AnthropicConfig {
             api_key: String::new(),
             model: "claude-sonnet-4.5".to_string(),
             base_url: "https://api.anthropic.com/v1".to_string(),
+            temperature: None,
+            top_p: None,
+            stop: Vec::new(),
             thinking_budget: None,
             streaming: true,
         }
@@ -51,6 +58,10 @@ struct MessagesRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     thinking: Option<ThinkingConfig>,
@@ -141,7 +152,8 @@ impl AnthropicBackend {
             .build()
             .context("Failed to build HTTP client")?;
 
-        let default_executor = RequestExecutor::new(3, "Anthropic API request".to_string());
+        let default_executor = RequestExecutor::new(3, "Anthropic API request".to_string())
+            .with_backend_name("anthropic");
 
         Ok(Self {
             client,
@@ -150,6 +162,12 @@ impl AnthropicBackend {
         })
     }
 
+    /// Retries the failures `policy` lists on top of the built-in ones.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.default_executor = self.default_executor.with_retry_policy(policy);
+        self
+    }
+
     fn convert_messages(
         &self,
         messages: &[ConversationMessage],
@@ -265,7 +283,7 @@ impl AnthropicBackend {
 
     fn create_request(&self, message: &str) -> MessagesRequest {
         let (max_tokens, temperature, thinking) =
-            self.thinking_request_overrides(8092, Some(0.7), None);
+            self.thinking_request_overrides(8092, self.config.temperature, None);
         MessagesRequest {
             model: self.config.model.clone(),
             messages: vec![AnthropicMessage {
@@ -275,6 +293,8 @@ impl AnthropicBackend {
             max_tokens,
             system: None,
             temperature,
+            top_p: self.sampling_top_p(&thinking),
+            stop_sequences: stop_sequences(&self.config.stop),
             tools: None,
             thinking,
             stream: false,
@@ -305,6 +325,12 @@ impl AnthropicBackend {
         }
     }
 
+    /// Extended thinking only accepts top_p close to 1, so it is left to the
+    /// API default whenever thinking is on.
+    fn sampling_top_p(&self, thinking: &Option<ThinkingConfig>) -> Option<f32> {
+        self.config.top_p.filter(|_| thinking.is_none())
+    }
+
     fn convert_tool_schemas(&self, tool_schemas: Vec<Value>) -> Vec<Value> {
         // Convert from OpenAI format to Anthropic format
         tool_schemas
@@ -335,14 +361,19 @@ impl AnthropicBackend {
         let tool_schemas = self.convert_tool_schemas(tools.get_tool_schemas());
         let has_tools = !tool_schemas.is_empty();
 
-        let (max_tokens, temperature, thinking) =
-            self.thinking_request_overrides(8092, Some(0.7), conversation.thinking_budget_override);
+        let (max_tokens, temperature, thinking) = self.thinking_request_overrides(
+            8092,
+            self.config.temperature,
+            conversation.thinking_budget_override,
+        );
         MessagesRequest {
             model: self.config.model.clone(),
             messages,
             max_tokens,
             system: system_prompt,
             temperature,
+            top_p: self.sampling_top_p(&thinking),
+            stop_sequences: stop_sequences(&self.config.stop),
             tools: if has_tools { Some(tool_schemas) } else { None },
             thinking,
             stream: false,
@@ -350,31 +381,7 @@ impl AnthropicBackend {
     }
 
     fn http_error_to_llm_error(status: reqwest::StatusCode, error_text: String) -> LlmError {
-        let status_code = status.as_u16();
-
-        match status_code {
-            429 => {
-                // Try to parse retry-after header
-                // We can't access the header here, but we'll handle it in the request method
-                LlmError::RateLimit {
-                    retry_after: None,
-                    message: error_text,
-                }
-            }
-            400 => LlmError::InvalidRequest {
-                message: error_text,
-            },
-            500..=599 => LlmError::ServerError {
-                status: status_code,
-                message: error_text,
-            },
-            401 | 403 => LlmError::AuthenticationError {
-                message: error_text,
-            },
-            _ => LlmError::Other {
-                message: format!("API error {}: {}", status_code, error_text),
-            },
-        }
+        LlmError::from_http_status(status.as_u16(), error_text)
     }
 
     async fn send_request_with_error_handling(
@@ -392,9 +399,7 @@ impl AnthropicBackend {
             .json(request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -425,6 +430,23 @@ impl AnthropicBackend {
         Ok(response_data)
     }
 
+    /// Preflight: list models to confirm the key and base_url work.
+    async fn check_connection(&self) -> Result<()> {
+        if self.config.api_key.is_empty() {
+            anyhow::bail!(
+                "Anthropic API key not configured. Set it with: hoosh config set anthropic_api_key <your_key>"
+            );
+        }
+        let url = format!("{}/models", self.config.base_url);
+        let request = self
+            .client
+            .get(&url)
+            .header("x-api-key", &self.config.api_key)
+            .header("anthropic-version", "2023-06-01");
+        super::preflight::check("anthropic", &url, request).await?;
+        Ok(())
+    }
+
     async fn send_message_attempt(&self, message: &str) -> Result<String, LlmError> {
         if self.config.api_key.is_empty() {
             return Err(LlmError::AuthenticationError {
@@ -486,9 +508,7 @@ impl AnthropicBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -507,19 +527,7 @@ impl AnthropicBackend {
             return Err(Self::http_error_to_llm_error(status, error_text));
         }
 
-        let mut reader = crate::backends::stream::LineReader::new(response.bytes_stream());
-        let mut acc = StreamAccumulator::default();
-        while let Some(line) = reader.next_line().await? {
-            let Some(data) = crate::backends::stream::sse_data(&line) else {
-                continue;
-            };
-            let Ok(event) = serde_json::from_str::<StreamEvent>(data) else {
-                continue;
-            };
-            acc.apply(event, event_tx);
-        }
-
-        Ok(acc.into_response())
+        read_stream(response.bytes_stream(), event_tx).await
     }
 
     fn extract_text_from_response(&self, response: MessagesResponse) -> Option<String> {
@@ -589,6 +597,32 @@ impl AnthropicBackend {
     }
 }
 
+/// Fold an SSE response body into one response, however its events are
+/// split across network chunks.
+async fn read_stream<S, B, E>(
+    stream: S,
+    event_tx: &tokio::sync::mpsc::UnboundedSender<crate::agent::AgentEvent>,
+) -> Result<LlmResponse, LlmError>
+where
+    S: futures::Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    let mut reader = crate::backends::stream::LineReader::new(stream);
+    let mut acc = StreamAccumulator::default();
+    while let Some(line) = reader.next_line().await? {
+        let Some(data) = crate::backends::stream::sse_data(&line) else {
+            continue;
+        };
+        let Ok(event) = serde_json::from_str::<StreamEvent>(data) else {
+            continue;
+        };
+        acc.apply(event, event_tx);
+    }
+
+    Ok(acc.into_response())
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum StreamEvent {
@@ -603,6 +637,9 @@ enum StreamEvent {
         index: usize,
         delta: StreamDelta,
     },
+    ContentBlockStop {
+        index: usize,
+    },
     MessageDelta {
         usage: StreamUsage,
     },
@@ -655,14 +692,41 @@ enum StreamDelta {
 enum BlockAcc {
     Text(String),
     Thinking(String),
+    /// A tool call whose `input_json_delta` fragments are still arriving.
     ToolUse {
         id: String,
         name: String,
         json: String,
     },
+    /// A tool call whose block has stopped, with its arguments complete.
+    Call(ToolCall),
     Ignored,
 }
 
+impl BlockAcc {
+    /// Turn a finished tool-use block into its call; other blocks are kept.
+    fn finish(self) -> Self {
+        let BlockAcc::ToolUse { id, name, json } = self else {
+            return self;
+        };
+        let arguments = if json.trim().is_empty() {
+            "{}".to_string()
+        } else {
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(&json) {
+                // Passed on as-is: the tool reports the bad arguments to the
+                // model, which can retry the call.
+                tracing::warn!("tool_use block {} has malformed input: {}", id, e);
+            }
+            json
+        };
+        BlockAcc::Call(ToolCall {
+            id,
+            r#type: "function".to_string(),
+            function: crate::agent::ToolFunction { name, arguments },
+        })
+    }
+}
+
 #[derive(Default)]
 struct StreamAccumulator {
     blocks: std::collections::BTreeMap<usize, BlockAcc>,
@@ -716,6 +780,11 @@ impl StreamAccumulator {
                 }
                 StreamDelta::Other => {}
             },
+            StreamEvent::ContentBlockStop { index } => {
+                if let Some(block) = self.blocks.remove(&index) {
+                    self.blocks.insert(index, block.finish());
+                }
+            }
             StreamEvent::MessageDelta { usage } => {
                 self.output_tokens = usage.output_tokens as usize;
             }
@@ -728,22 +797,12 @@ impl StreamAccumulator {
         let mut thinking_parts = Vec::new();
         let mut tool_calls = Vec::new();
 
+        // A block the stream never stopped is finished with what arrived.
         for (_, block) in self.blocks {
-            match block {
+            match block.finish() {
                 BlockAcc::Text(t) if !t.is_empty() => text_parts.push(t),
                 BlockAcc::Thinking(t) if !t.is_empty() => thinking_parts.push(t),
-                BlockAcc::ToolUse { id, name, json } => {
-                    let arguments = if json.trim().is_empty() {
-                        "{}".to_string()
-                    } else {
-                        json
-                    };
-                    tool_calls.push(ToolCall {
-                        id,
-                        r#type: "function".to_string(),
-                        function: crate::agent::ToolFunction { name, arguments },
-                    });
-                }
+                BlockAcc::Call(call) => tool_calls.push(call),
                 _ => {}
             }
         }
@@ -773,9 +832,17 @@ impl StreamAccumulator {
 
 #[async_trait]
 impl LlmBackend for AnthropicBackend {
+    async fn initialize(&self) -> Result<()> {
+        self.check_connection().await
+    }
+
     async fn send_message(&self, message: &str) -> Result<String> {
         self.default_executor
-            .execute(|| async { self.send_message_attempt(message).await }, None)
+            .execute(
+                || async { self.send_message_attempt(message).await },
+                None,
+                None,
+            )
             .await
             .map_err(anyhow::Error::new)
     }
@@ -792,6 +859,7 @@ impl LlmBackend for AnthropicBackend {
                         .await
                 },
                 None,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -805,6 +873,7 @@ impl LlmBackend for AnthropicBackend {
             .execute(
                 || async { self.send_message_attempt(message).await },
                 event_tx,
+                None,
             )
             .await
             .map_err(anyhow::Error::new)
@@ -827,6 +896,7 @@ impl LlmBackend for AnthropicBackend {
                             .await
                     },
                     event_tx,
+                    conversation.retry_budget.clone(),
                 )
                 .await;
         }
@@ -838,6 +908,7 @@ impl LlmBackend for AnthropicBackend {
                         .await
                 },
                 event_tx,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -938,6 +1009,52 @@ mod tests {
         assert_eq!(thinking.budget_tokens, 5000);
     }
 
+    #[test]
+    fn sampling_params_omitted_unless_configured() {
+        let request = backend().create_request("hi");
+        assert_eq!(request.temperature, None);
+        assert_eq!(request.top_p, None);
+        let body = serde_json::to_value(&request).unwrap();
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("top_p").is_none());
+
+        let request = AnthropicBackend::new(AnthropicConfig {
+            temperature: Some(0.3),
+            top_p: Some(0.8),
+            ..AnthropicConfig::default()
+        })
+        .expect("backend")
+        .create_request("hi");
+        assert_eq!(request.temperature, Some(0.3));
+        assert_eq!(request.top_p, Some(0.8));
+    }
+
+    #[test]
+    fn thinking_drops_top_p() {
+        let request = AnthropicBackend::new(AnthropicConfig {
+            top_p: Some(0.8),
+            thinking_budget: Some(2048),
+            ..AnthropicConfig::default()
+        })
+        .expect("backend")
+        .create_request("hi");
+        assert_eq!(request.top_p, None);
+        assert_eq!(request.temperature, Some(1.0));
+    }
+
+    #[test]
+    fn configured_stops_are_sent_as_stop_sequences() {
+        let request = AnthropicBackend::new(AnthropicConfig {
+            stop: vec!["</answer>".to_string()],
+            ..AnthropicConfig::default()
+        })
+        .expect("backend")
+        .create_request("hi");
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["stop_sequences"], serde_json::json!(["</answer>"]));
+        assert!(body.get("stop").is_none());
+    }
+
     #[test]
     fn thinking_zero_budget_treated_as_disabled() {
         let (max_tokens, temperature, thinking) =
@@ -988,6 +1105,55 @@ mod tests {
         assert_eq!(calls[0].function.arguments, "{\"path\":\"a.txt\"}");
     }
 
+    #[tokio::test]
+    async fn streaming_reassembles_interleaved_tool_calls_from_fragmented_frames() {
+        let frames = [
+            r#"{"type":"message_start","message":{"usage":{"input_tokens":12,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Reading both."}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_a","name":"read_file","input":{}}}"#,
+            r#"{"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_b","name":"grep","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"pa"}}"#,
+            r#"{"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":""}}"#,
+            r#"{"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"pattern\": \"fn \\\\w+\""}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"th\": \"src/é.rs\"}"}}"#,
+            r#"{"type":"content_block_stop","index":1}"#,
+            r#"{"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":", \"limit\": 5}"}}"#,
+            r#"{"type":"content_block_stop","index":2}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":40}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        let body: String = frames
+            .iter()
+            .map(|data| format!("event: x\ndata: {}\n\n", data))
+            .collect();
+        // Cut the body into small chunks that split lines, and even
+        // characters, at arbitrary points.
+        let chunks: Vec<Result<Vec<u8>, std::convert::Infallible>> = body
+            .as_bytes()
+            .chunks(7)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect();
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let resp = read_stream(futures::stream::iter(chunks), &tx)
+            .await
+            .expect("stream");
+
+        assert_eq!(resp.content.as_deref(), Some("Reading both."));
+        assert_eq!(resp.output_tokens, Some(40));
+        let calls = resp.tool_calls.expect("tool calls");
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "toolu_a");
+        assert_eq!(calls[0].function.name, "read_file");
+        let a: serde_json::Value = serde_json::from_str(&calls[0].function.arguments).unwrap();
+        assert_eq!(a, serde_json::json!({"path": "src/é.rs"}));
+        assert_eq!(calls[1].id, "toolu_b");
+        let b: serde_json::Value = serde_json::from_str(&calls[1].function.arguments).unwrap();
+        assert_eq!(b, serde_json::json!({"pattern": "fn \\w+", "limit": 5}));
+    }
+
     #[test]
     fn thinking_block_extracted_into_llm_response() {
         let json = r#"{
@@ -35,10 +35,15 @@ impl BackendFactory for TogetherAiBackend {
             api_key,
             model,
             base_url,
+            temperature: config.temperature,
+            top_p: config.top_p,
+            stop: config.stop.clone().unwrap_or_default(),
             streaming: config.streaming.unwrap_or(true),
         };
 
-        Ok(Box::new(TogetherAiBackend::new(together_config)?))
+        let backend = TogetherAiBackend::new(together_config)?
+            .with_retry_policy(config.retry.clone().unwrap_or_default());
+        Ok(Box::new(backend))
     }
 }
 
@@ -63,11 +68,16 @@ impl BackendFactory for AnthropicBackend {
             api_key,
             model,
             base_url,
+            temperature: config.temperature,
+            top_p: config.top_p,
+            stop: config.stop.clone().unwrap_or_default(),
             thinking_budget: config.thinking_budget,
             streaming: config.streaming.unwrap_or(true),
         };
 
-        Ok(Box::new(AnthropicBackend::new(anthropic_config)?))
+        let backend = AnthropicBackend::new(anthropic_config)?
+            .with_retry_policy(config.retry.clone().unwrap_or_default());
+        Ok(Box::new(backend))
     }
 }
 
@@ -101,14 +111,19 @@ impl BackendFactory for OpenAICompatibleBackend {
             base_url,
             chat_api,
             temperature: config.temperature,
+            top_p: config.top_p,
+            stop: config.stop.clone().unwrap_or_default(),
             pricing_endpoint: config.pricing_endpoint.clone(),
             thinking_budget: config.thinking_budget,
             reasoning_effort: config.reasoning_effort,
             reasoning_display: config.reasoning_display,
             streaming: config.streaming.unwrap_or(true),
+            tool_api: config.tool_api.unwrap_or_default(),
         };
 
-        Ok(Box::new(OpenAICompatibleBackend::new(openai_config)?))
+        let backend = OpenAICompatibleBackend::new(openai_config)?
+            .with_retry_policy(config.retry.clone().unwrap_or_default());
+        Ok(Box::new(backend))
     }
 }
 
@@ -132,10 +147,14 @@ impl BackendFactory for OllamaBackend {
             model,
             base_url,
             temperature: config.temperature,
+            top_p: config.top_p,
+            stop: config.stop.clone().unwrap_or_default(),
             streaming: config.streaming.unwrap_or(true),
         };
 
-        Ok(Box::new(OllamaBackend::new(ollama_config)?))
+        let backend = OllamaBackend::new(ollama_config)?
+            .with_retry_policy(config.retry.clone().unwrap_or_default());
+        Ok(Box::new(backend))
     }
 }
 pub fn create_backend(backend_name: &str, config: &AppConfig) -> Result<Box<dyn LlmBackend>> {
@@ -165,6 +184,39 @@ pub fn create_backend(backend_name: &str, config: &AppConfig) -> Result<Box<dyn
     }
 }
 
+/// `backend_name` as configured, but running `model`.
+pub fn create_backend_with_model(
+    backend_name: &str,
+    config: &AppConfig,
+    model: &str,
+) -> Result<Box<dyn LlmBackend>> {
+    let mut staged = config.clone();
+    staged
+        .backends
+        .get_mut(backend_name)
+        .ok_or_else(|| anyhow::anyhow!("Backend '{}' not found in config", backend_name))?
+        .model = Some(model.to_string());
+    create_backend(backend_name, &staged)
+}
+
+/// Backend for conversation titles: `backend_name` with its `title_model`.
+/// `None` when no title model is configured or the backend can't be built, in
+/// which case titles come from the main backend.
+pub fn create_title_backend(backend_name: &str, config: &AppConfig) -> Option<Box<dyn LlmBackend>> {
+    let title_model = config
+        .get_backend_config(backend_name)?
+        .title_model
+        .clone()?;
+
+    match create_backend_with_model(backend_name, config, &title_model) {
+        Ok(backend) => Some(backend),
+        Err(e) => {
+            tracing::warn!("Title model unavailable, using the main model: {}", e);
+            None
+        }
+    }
+}
+
 fn unknown_backend_error(backend_name: &str) -> anyhow::Error {
     let available: Vec<&str> = BackendKind::available()
         .iter()
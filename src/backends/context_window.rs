@@ -0,0 +1,102 @@
+//! Context window sizes for well-known models.
+//!
+//! Matching is by prefix on the lowercased model name with any provider
+//! namespace (`anthropic/`, `meta-llama/`) stripped, so dated snapshots and
+//! Ollama tags (`llama3.1:8b`) resolve to their family. More specific prefixes
+//! come first.
+
+/// Used when neither the backend config nor the table knows the model. Small
+/// on purpose: overestimating the window lets a conversation outgrow it.
+pub const FALLBACK_CONTEXT_WINDOW: usize = 32_000;
+
+const KNOWN_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    // Anthropic
+    ("claude-2", 100_000),
+    ("claude-instant", 100_000),
+    ("claude-", 200_000),
+    // OpenAI
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4-32k", 32_768),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1-mini", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    // Open-weight models, as named by Ollama and hosted providers
+    ("llama3.1", 128_000),
+    ("llama3.2", 128_000),
+    ("llama3.3", 128_000),
+    ("llama-3.1", 128_000),
+    ("llama-3.2", 128_000),
+    ("llama-3.3", 128_000),
+    ("meta-llama-3.1", 128_000),
+    ("llama3", 8_192),
+    ("llama-3", 8_192),
+    ("meta-llama-3", 8_192),
+    ("qwen3", 40_960),
+    ("qwen2.5", 32_768),
+    ("deepseek-r1", 128_000),
+    ("deepseek-v3", 128_000),
+    ("deepseek-coder-v2", 128_000),
+    ("mistral-large", 128_000),
+    ("mistral", 32_768),
+    ("mixtral", 32_768),
+    ("codellama", 16_384),
+    ("gemma3", 128_000),
+    ("gemma2", 8_192),
+    ("phi4", 16_384),
+];
+
+/// The context window of `model` in tokens, if it is a known model.
+pub fn known_context_window(model: &str) -> Option<usize> {
+    let model = model.to_ascii_lowercase();
+    let model = model.rsplit('/').next().unwrap_or(&model);
+
+    KNOWN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|&(_, window)| window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_model_families() {
+        let cases = [
+            ("claude-sonnet-4-5", Some(200_000)),
+            ("claude-2.1", Some(100_000)),
+            ("gpt-4o-mini", Some(128_000)),
+            ("gpt-4", Some(8_192)),
+            ("gpt-4.1-mini", Some(1_047_576)),
+            ("llama3.1:8b", Some(128_000)),
+            ("llama3:latest", Some(8_192)),
+            ("qwen2.5-coder:7b", Some(32_768)),
+            ("some-private-finetune", None),
+        ];
+        for (model, expected) in cases {
+            assert_eq!(known_context_window(model), expected, "{model}");
+        }
+    }
+
+    #[test]
+    fn ignores_provider_namespace_and_case() {
+        assert_eq!(
+            known_context_window("anthropic/claude-opus-4-1"),
+            Some(200_000)
+        );
+        assert_eq!(
+            known_context_window("meta-llama/Llama-3.3-70B-Instruct-Turbo"),
+            Some(128_000)
+        );
+        assert_eq!(
+            known_context_window("meta-llama/Meta-Llama-3.1-405B-Instruct-Turbo"),
+            Some(128_000)
+        );
+    }
+}
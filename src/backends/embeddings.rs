@@ -0,0 +1,178 @@
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+
+use crate::backends::BackendKind;
+use crate::config::AppConfig;
+
+/// Turns text into vectors for similarity search. Separate from `LlmBackend`
+/// because the embedding model is usually not the chat model, and only some
+/// providers offer one.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Model name, stored with the index so vectors from different models
+    /// are never compared.
+    fn model(&self) -> &str;
+
+    /// One vector per input, in order.
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .connect_timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Ollama's `/api/embed` endpoint, e.g. with `nomic-embed-text`.
+pub struct OllamaEmbedder {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+impl OllamaEmbedder {
+    pub fn new(base_url: &str, model: &str) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/api/embed", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({ "model": self.model, "input": inputs }))
+            .send()
+            .await
+            .context("Failed to reach Ollama for embeddings")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!("Ollama embeddings request failed ({}): {}", status, body);
+        }
+        let parsed: OllamaEmbedResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama embeddings response")?;
+        Ok(parsed.embeddings)
+    }
+}
+
+/// The OpenAI-style `/embeddings` endpoint, also served by Together AI and
+/// most OpenAI-compatible gateways.
+pub struct OpenAiEmbedder {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    embedding: Vec<f32>,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(base_url: &str, api_key: &str, model: &str) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/embeddings", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&json!({ "model": self.model, "input": inputs }))
+            .send()
+            .await
+            .context("Failed to reach the embeddings endpoint")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!("Embeddings request failed ({}): {}", status, body);
+        }
+        let parsed: OpenAiEmbedResponse = response
+            .json()
+            .await
+            .context("Failed to parse embeddings response")?;
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+/// Build the embedder named by `[recall]`. The base URL and API key come from
+/// the matching `[backends.<name>]` entry, falling back to the backend's
+/// defaults.
+pub fn create_embedder(config: &AppConfig) -> Result<Box<dyn Embedder>> {
+    let recall = config
+        .recall_config()
+        .context("[recall] is not configured")?;
+    let kind = BackendKind::from_str(&recall.backend)
+        .map_err(|_| anyhow!("Unknown embeddings backend '{}'", recall.backend))?;
+    let backend_config = config.get_backend_config(&recall.backend);
+    let base_url = backend_config
+        .and_then(|b| b.base_url.clone())
+        .or_else(|| kind.default_base_url().map(str::to_string));
+
+    match kind {
+        BackendKind::Ollama => {
+            let base_url = base_url.context("No base URL for Ollama embeddings")?;
+            Ok(Box::new(OllamaEmbedder::new(&base_url, &recall.model)?))
+        }
+        BackendKind::OpenAi | BackendKind::Groq | BackendKind::TogetherAi => {
+            let base_url = base_url.with_context(|| {
+                format!("No base URL configured for backend '{}'", recall.backend)
+            })?;
+            let api_key = backend_config
+                .and_then(|b| b.api_key.clone())
+                .with_context(|| {
+                    format!("No API key configured for backend '{}'", recall.backend)
+                })?;
+            Ok(Box::new(OpenAiEmbedder::new(
+                &base_url,
+                &api_key,
+                &recall.model,
+            )?))
+        }
+        BackendKind::Anthropic | BackendKind::Mock => {
+            bail!("Backend '{}' does not provide embeddings", recall.backend)
+        }
+    }
+}
@@ -1,12 +1,16 @@
-use super::strategy::RetryStrategy;
-use crate::agent::AgentEvent;
+use super::strategy::{RetryPolicy, RetryStrategy};
+use crate::agent::{AgentEvent, RetryBudget};
 use crate::backends::llm_error::LlmError;
+use crate::telemetry;
+use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
 
 #[derive(Clone)]
 pub struct RequestExecutor {
     max_attempts: u32,
     operation_name: String,
+    backend_name: Option<String>,
+    policy: RetryPolicy,
 }
 
 impl RequestExecutor {
@@ -14,36 +18,103 @@ impl RequestExecutor {
         Self {
             max_attempts,
             operation_name,
+            backend_name: None,
+            policy: RetryPolicy::default(),
         }
     }
 
+    /// Names the backend in telemetry; defaults to the operation name.
+    pub fn with_backend_name(mut self, backend_name: impl Into<String>) -> Self {
+        self.backend_name = Some(backend_name.into());
+        self
+    }
+
+    /// Retries the failures `policy` lists as well as the built-in ones.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Run `operation`, retrying its failures up to `max_attempts`. Requests
+    /// made during an agent turn pass the turn's `retry_budget`, which every
+    /// retry also draws from.
     pub async fn execute<F, Fut, T>(
         &self,
         operation: F,
         event_tx: Option<UnboundedSender<AgentEvent>>,
+        retry_budget: Option<Arc<RetryBudget>>,
     ) -> Result<T, LlmError>
     where
         F: FnMut() -> Fut,
         Fut: Future<Output = Result<T, LlmError>>,
     {
-        let strategy = RetryStrategy::new(self.max_attempts, self.operation_name.clone(), event_tx);
-        strategy.execute(operation).await
+        let strategy = RetryStrategy::new(self.max_attempts, self.operation_name.clone(), event_tx)
+            .with_policy(self.policy.clone())
+            .with_retry_budget(retry_budget);
+        let backend = self.backend_name.as_deref().unwrap_or(&self.operation_name);
+        telemetry::backend_request(backend, strategy.execute(operation)).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn attempts_for_status(executor: &RequestExecutor, status: u16) -> u32 {
+        let attempts = AtomicU32::new(0);
+        let result = executor
+            .execute(
+                || async {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Err::<String, _>(LlmError::from_http_status(status, "gateway".to_string()))
+                },
+                None,
+                None,
+            )
+            .await;
+        assert!(result.is_err());
+        attempts.load(Ordering::SeqCst)
+    }
 
     #[tokio::test]
     async fn test_executor_delegates_to_strategy() {
         let executor = RequestExecutor::new(3, "test".to_string());
 
         let result = executor
-            .execute(|| async { Ok("test".to_string()) }, None)
+            .execute(|| async { Ok("test".to_string()) }, None, None)
             .await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "test");
     }
+
+    #[tokio::test]
+    async fn test_configured_status_is_retried_but_unconfigured_400_is_not() {
+        let executor = RequestExecutor::new(2, "test".to_string()).with_retry_policy(RetryPolicy {
+            statuses: vec![499],
+            messages: Vec::new(),
+        });
+
+        assert_eq!(attempts_for_status(&executor, 499).await, 2);
+        assert_eq!(attempts_for_status(&executor, 400).await, 1);
+    }
+
+    #[test]
+    fn test_retry_policy_matches_message_fragments() {
+        let policy = RetryPolicy {
+            statuses: Vec::new(),
+            messages: vec!["Upstream Reset".to_string()],
+        };
+
+        assert!(policy.is_retryable(&LlmError::from_http_status(
+            400,
+            "gateway: upstream reset by peer".to_string()
+        )));
+        assert!(!policy.is_retryable(&LlmError::from_http_status(
+            400,
+            "bad model name".to_string()
+        )));
+        assert!(policy.is_retryable(&LlmError::from_http_status(503, "unavailable".to_string())));
+    }
 }
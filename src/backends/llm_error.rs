@@ -1,5 +1,16 @@
 use std::fmt;
 
+/// Lower-cased fragments providers use for an overflowing prompt when the
+/// body carries no machine-readable code.
+const CONTEXT_LENGTH_PHRASES: &[&str] = &[
+    "prompt is too long",
+    "maximum context length",
+    "context length exceeded",
+    "context_length_exceeded",
+    "context window",
+    "too many tokens",
+];
+
 #[derive(Debug, Clone)]
 pub enum LlmError {
     RateLimit {
@@ -17,11 +28,28 @@ pub enum LlmError {
         message: String,
     },
     InvalidRequest {
+        status: u16,
+        message: String,
+    },
+    /// The provider is temporarily out of capacity (Anthropic's 529).
+    Overloaded {
+        message: String,
+    },
+    /// The conversation no longer fits the model's context window.
+    ContextLengthExceeded {
+        message: String,
+    },
+    Timeout {
         message: String,
     },
     RecoverableByLlm {
         message: String,
     },
+    /// A non-success HTTP status none of the other variants covers.
+    UnexpectedStatus {
+        status: u16,
+        message: String,
+    },
     Other {
         message: String,
     },
@@ -34,9 +62,135 @@ impl LlmError {
             LlmError::RateLimit { .. }
                 | LlmError::ServerError { .. }
                 | LlmError::NetworkError { .. }
+                | LlmError::Overloaded { .. }
+                | LlmError::Timeout { .. }
         )
     }
 
+    /// HTTP status of the failed response, for the variants that keep it.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            LlmError::ServerError { status, .. }
+            | LlmError::InvalidRequest { status, .. }
+            | LlmError::UnexpectedStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The provider's or transport's own wording, before any formatting.
+    pub fn message(&self) -> &str {
+        match self {
+            LlmError::RateLimit { message, .. }
+            | LlmError::ServerError { message, .. }
+            | LlmError::AuthenticationError { message }
+            | LlmError::NetworkError { message }
+            | LlmError::InvalidRequest { message, .. }
+            | LlmError::Overloaded { message }
+            | LlmError::ContextLengthExceeded { message }
+            | LlmError::Timeout { message }
+            | LlmError::RecoverableByLlm { message }
+            | LlmError::UnexpectedStatus { message, .. }
+            | LlmError::Other { message } => message,
+        }
+    }
+
+    /// Classify a non-success HTTP response from any provider. The body is
+    /// checked before the status because providers disagree on statuses: an
+    /// overflowing prompt is a 400 everywhere, but only the body says so.
+    /// Phrases only count on client-error statuses, so a rate limit or
+    /// server error that happens to mention tokens stays retriable.
+    pub fn from_http_status(status: u16, body: String) -> Self {
+        let error_kind = Self::error_kind(&body);
+        let lowered = body.to_lowercase();
+
+        if error_kind.as_deref() == Some("context_length_exceeded")
+            || (matches!(status, 400 | 413 | 422)
+                && CONTEXT_LENGTH_PHRASES.iter().any(|p| lowered.contains(p)))
+        {
+            return LlmError::ContextLengthExceeded { message: body };
+        }
+        if status == 529 || error_kind.as_deref() == Some("overloaded_error") {
+            return LlmError::Overloaded { message: body };
+        }
+
+        match status {
+            429 => LlmError::RateLimit {
+                retry_after: None,
+                message: body,
+            },
+            401 | 403 => LlmError::AuthenticationError { message: body },
+            408 | 504 => LlmError::Timeout { message: body },
+            400 | 404 | 413 | 422 => LlmError::InvalidRequest {
+                status,
+                message: body,
+            },
+            500..=599 => LlmError::ServerError {
+                status,
+                message: body,
+            },
+            _ => LlmError::UnexpectedStatus {
+                status,
+                message: body,
+            },
+        }
+    }
+
+    /// A failure to get any response at all, e.g. from `reqwest`.
+    pub fn from_transport(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            LlmError::Timeout {
+                message: error.to_string(),
+            }
+        } else {
+            LlmError::NetworkError {
+                message: error.to_string(),
+            }
+        }
+    }
+
+    /// What the user can do about the error, shown under it in the TUI.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            LlmError::RateLimit { .. } => {
+                Some("Wait a moment and resend, or switch model with /model")
+            }
+            LlmError::AuthenticationError { .. } => Some(
+                "Check the API key with `hoosh config show`, then set it with `hoosh config set`",
+            ),
+            LlmError::Overloaded { .. } => {
+                Some("The provider is busy; resend shortly or switch model with /model")
+            }
+            LlmError::ContextLengthExceeded { .. } => {
+                Some("Try /compact to summarize the conversation, or /clear to start over")
+            }
+            LlmError::NetworkError { .. } => {
+                Some("Check your connection or proxy settings, then resend")
+            }
+            LlmError::Timeout { .. } => {
+                Some("Resend, or ask for a smaller step if the request is large")
+            }
+            LlmError::InvalidRequest { .. } => {
+                Some("The provider rejected the request; check the model name and backend settings")
+            }
+            LlmError::ServerError { .. }
+            | LlmError::RecoverableByLlm { .. }
+            | LlmError::UnexpectedStatus { .. }
+            | LlmError::Other { .. } => None,
+        }
+    }
+
+    /// `error.type` (Anthropic) or `error.code` (OpenAI-compatible) from a
+    /// JSON error body.
+    fn error_kind(raw: &str) -> Option<String> {
+        let json = serde_json::from_str::<serde_json::Value>(raw).ok()?;
+        let error = json.get("error")?;
+        ["code", "type"]
+            .iter()
+            .filter_map(|key| error.get(key).and_then(|v| v.as_str()))
+            .find(|kind| !kind.is_empty())
+            .map(str::to_string)
+    }
+
     pub fn user_message(&self) -> String {
         match self {
             LlmError::RateLimit { message, .. } => Self::extract_error_message(message)
@@ -53,11 +207,25 @@ impl LlmError {
             LlmError::NetworkError { message } => {
                 format!("Network error: {}", message)
             }
-            LlmError::InvalidRequest { message } => {
+            LlmError::InvalidRequest { message, .. } => {
                 let error_msg = Self::extract_error_message(message).unwrap_or(message.clone());
                 format!("Invalid request: {}", error_msg)
             }
+            LlmError::Overloaded { message } => {
+                let error_msg = Self::extract_error_message(message).unwrap_or(message.clone());
+                format!("Provider overloaded: {}", error_msg)
+            }
+            LlmError::ContextLengthExceeded { message } => {
+                let error_msg = Self::extract_error_message(message).unwrap_or(message.clone());
+                format!("Context window exceeded: {}", error_msg)
+            }
+            LlmError::Timeout { message } => {
+                format!("Request timed out: {}", message)
+            }
             LlmError::RecoverableByLlm { message } => message.clone(),
+            LlmError::UnexpectedStatus { status, message } => {
+                format!("Error: API error {}: {}", status, message)
+            }
             LlmError::Other { message } => {
                 format!("Error: {}", message)
             }
@@ -82,7 +250,11 @@ impl LlmError {
             LlmError::AuthenticationError { .. } => "Authentication error".to_string(),
             LlmError::NetworkError { .. } => "Network error".to_string(),
             LlmError::InvalidRequest { .. } => "Invalid request".to_string(),
+            LlmError::Overloaded { .. } => "Provider overloaded".to_string(),
+            LlmError::ContextLengthExceeded { .. } => "Context window exceeded".to_string(),
+            LlmError::Timeout { .. } => "Request timed out".to_string(),
             LlmError::RecoverableByLlm { .. } => "Response truncated".to_string(),
+            LlmError::UnexpectedStatus { status, .. } => format!("API error ({})", status),
             LlmError::Other { .. } => "Error occurred".to_string(),
         }
     }
@@ -99,3 +271,108 @@ impl fmt::Display for LlmError {
 }
 
 impl std::error::Error for LlmError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anthropic_errors_map_to_variants() {
+        let overloaded =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(529, overloaded.to_string()),
+            LlmError::Overloaded { .. }
+        ));
+
+        let too_long = r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 210000 tokens > 200000 maximum"}}"#;
+        let error = LlmError::from_http_status(400, too_long.to_string());
+        assert!(matches!(error, LlmError::ContextLengthExceeded { .. }));
+        assert_eq!(
+            error.user_message(),
+            "Context window exceeded: prompt is too long: 210000 tokens > 200000 maximum"
+        );
+        assert!(error.hint().unwrap().contains("/compact"));
+
+        let auth = r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(401, auth.to_string()),
+            LlmError::AuthenticationError { .. }
+        ));
+
+        let bad = r#"{"type":"error","error":{"type":"invalid_request_error","message":"max_tokens: must be positive"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(400, bad.to_string()),
+            LlmError::InvalidRequest { .. }
+        ));
+    }
+
+    #[test]
+    fn openai_compatible_errors_map_to_variants() {
+        let too_long = r#"{"error":{"message":"This model's maximum context length is 128000 tokens. However, your messages resulted in 130000 tokens.","type":"invalid_request_error","param":"messages","code":"context_length_exceeded"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(400, too_long.to_string()),
+            LlmError::ContextLengthExceeded { .. }
+        ));
+
+        let rate = r#"{"error":{"message":"Rate limit reached for gpt-4o","type":"requests","code":"rate_limit_exceeded"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(429, rate.to_string()),
+            LlmError::RateLimit { .. }
+        ));
+
+        let key = r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        assert!(matches!(
+            LlmError::from_http_status(401, key.to_string()),
+            LlmError::AuthenticationError { .. }
+        ));
+
+        assert!(matches!(
+            LlmError::from_http_status(504, "upstream timed out".to_string()),
+            LlmError::Timeout { .. }
+        ));
+        assert!(matches!(
+            LlmError::from_http_status(503, "unavailable".to_string()),
+            LlmError::ServerError { status: 503, .. }
+        ));
+    }
+
+    #[test]
+    fn plain_text_bodies_are_classified_by_phrase() {
+        let ollama = r#"{"error":"input length exceeds maximum context length"}"#;
+        assert!(matches!(
+            LlmError::from_http_status(400, ollama.to_string()),
+            LlmError::ContextLengthExceeded { .. }
+        ));
+    }
+
+    #[test]
+    fn phrases_in_rate_limits_and_server_errors_stay_retriable() {
+        let rate = "Rate limit: too many tokens per minute, retry shortly";
+        assert!(matches!(
+            LlmError::from_http_status(429, rate.to_string()),
+            LlmError::RateLimit { .. }
+        ));
+        let server = "upstream failed while checking the context window";
+        assert!(matches!(
+            LlmError::from_http_status(502, server.to_string()),
+            LlmError::ServerError { status: 502, .. }
+        ));
+    }
+
+    #[test]
+    fn overloaded_and_timeout_are_retried_but_context_length_is_not() {
+        let overloaded = LlmError::Overloaded {
+            message: String::new(),
+        };
+        let timeout = LlmError::Timeout {
+            message: String::new(),
+        };
+        let context = LlmError::ContextLengthExceeded {
+            message: String::new(),
+        };
+        assert!(overloaded.is_retryable());
+        assert!(timeout.is_retryable());
+        assert!(!context.is_retryable());
+    }
+}
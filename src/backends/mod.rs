@@ -89,6 +89,14 @@ pub trait LlmBackend: Send + Sync {
         None
     }
 
+    /// Context window of the configured model in tokens, when known. The
+    /// default consults the table in [`context_window`]; a `context_window`
+    /// key in the backend config takes precedence over this (see
+    /// `AppConfig::context_window`).
+    fn context_window(&self) -> Option<usize> {
+        context_window::known_context_window(self.model_name())
+    }
+
     /// Whether the backend's currently configured model accepts image input.
     /// Defaults to false — backends override when they can resolve the answer
     /// from their own model catalogue (hardcoded for Anthropic, /models for
@@ -121,11 +129,14 @@ pub trait LlmBackend: Send + Sync {
 pub mod anthropic;
 pub mod backend_factory;
 pub mod backend_kind;
+pub mod context_window;
+pub mod embeddings;
 pub mod llm_error;
 pub mod mock;
 pub mod ollama;
 #[cfg(feature = "openai-compatible")]
 pub mod openai_compatible;
+pub(crate) mod preflight;
 #[cfg(feature = "together-ai")]
 pub mod together_ai;
 
@@ -152,7 +163,15 @@ pub use together_ai::TogetherAiConfig;
 pub mod executor;
 pub use executor::RequestExecutor;
 
+pub mod rate_limit;
+pub use rate_limit::{RequestLimiter, RequestLimiters, RequestPermit};
+
 pub mod stream;
 
 pub mod strategy;
-pub use strategy::RetryStrategy;
+pub use strategy::{RetryPolicy, RetryStrategy};
+
+/// Configured stop sequences as a request field, left out when empty.
+pub(crate) fn stop_sequences(stop: &[String]) -> Option<Vec<String>> {
+    (!stop.is_empty()).then(|| stop.to_vec())
+}
@@ -1,6 +1,7 @@
-use super::{LlmBackend, LlmResponse, RequestExecutor};
+use super::{LlmBackend, LlmResponse, RequestExecutor, RetryPolicy};
 use crate::agent::{Conversation, ConversationMessage, Role, ToolCall};
 use crate::backends::llm_error::LlmError;
+use crate::backends::stop_sequences;
 use crate::tools::ToolRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -18,6 +19,8 @@ pub struct OllamaConfig {
     pub model: String,
     pub base_url: String,
     pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop: Vec<String>,
     pub streaming: bool,
 }
 
@@ -28,6 +31,8 @@ impl Default for OllamaConfig {
             model: DEFAULT_OLLAMA_MODEL.to_string(),
             base_url: DEFAULT_OLLAMA_BASE_URL.to_string(),
             temperature: None,
+            top_p: None,
+            stop: Vec::new(),
             streaming: true,
         }
     }
@@ -203,7 +208,8 @@ impl OllamaBackend {
             .build()
             .context("Failed to build HTTP client")?;
 
-        let default_executor = RequestExecutor::new(3, "Ollama API request".to_string());
+        let default_executor = RequestExecutor::new(3, "Ollama API request".to_string())
+            .with_backend_name(config.name.clone());
 
         Ok(Self {
             client,
@@ -212,6 +218,12 @@ impl OllamaBackend {
         })
     }
 
+    /// Retries the failures `policy` lists on top of the built-in ones.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.default_executor = self.default_executor.with_retry_policy(policy);
+        self
+    }
+
     async fn send_message_attempt(&self, message: &str) -> Result<String, LlmError> {
         let request = self.create_request(message);
         let url = format!("{}/api/chat", self.config.base_url);
@@ -222,16 +234,12 @@ impl OllamaBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(LlmError::Other {
-                message: format!("Ollama API error {}:", status.as_u16()) + &error_text,
-            });
+            return Err(LlmError::from_http_status(status.as_u16(), error_text));
         }
 
         // Read response as text first to provide better error messages
@@ -266,16 +274,12 @@ impl OllamaBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(LlmError::Other {
-                message: format!("Ollama API error {}:", status.as_u16()) + &error_text,
-            });
+            return Err(LlmError::from_http_status(status.as_u16(), error_text));
         }
 
         let response_text = response.text().await.map_err(|e| LlmError::Other {
@@ -332,16 +336,12 @@ impl OllamaBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(LlmError::Other {
-                message: format!("Ollama API error {}:", status.as_u16()) + &error_text,
-            });
+            return Err(LlmError::from_http_status(status.as_u16(), error_text));
         }
 
         let mut reader = crate::backends::stream::LineReader::new(response.bytes_stream());
@@ -434,20 +434,69 @@ impl OllamaBackend {
             seed: None,
             temperature: self.config.temperature,
             top_k: None,
-            top_p: None,
+            top_p: self.config.top_p,
             min_p: None,
-            stop: None,
+            stop: stop_sequences(&self.config.stop),
             num_ctx: None,
             num_predict: Some(DEFAULT_MAX_TOKENS as i32),
         }
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagInfo {
+    name: String,
+}
+
+/// Whether `model` is among the pulled models. Ollama lists untagged models
+/// as `<name>:latest`.
+fn model_is_pulled(pulled: &[TagInfo], model: &str) -> bool {
+    pulled
+        .iter()
+        .any(|tag| tag.name == model || tag.name == format!("{}:latest", model))
+}
+
+impl OllamaBackend {
+    /// Preflight: list local models to confirm the server is up and the
+    /// configured model has been pulled.
+    async fn check_connection(&self) -> Result<()> {
+        let url = format!("{}/api/tags", self.config.base_url);
+        let request = self.client.get(&url);
+        let response = super::preflight::check(&self.config.name, &url, request).await?;
+        let tags: TagsResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama model list")?;
+        if !model_is_pulled(&tags.models, &self.config.model) {
+            anyhow::bail!(
+                "Ollama model '{}' is not available locally. Pull it with: ollama pull {}",
+                self.config.model,
+                self.config.model
+            );
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl LlmBackend for OllamaBackend {
+    async fn initialize(&self) -> Result<()> {
+        self.check_connection().await
+    }
+
     async fn send_message(&self, message: &str) -> Result<String> {
         self.default_executor
-            .execute(|| async { self.send_message_attempt(message).await }, None)
+            .execute(
+                || async { self.send_message_attempt(message).await },
+                None,
+                None,
+            )
             .await
             .map_err(anyhow::Error::new)
     }
@@ -464,6 +513,7 @@ impl LlmBackend for OllamaBackend {
                         .await
                 },
                 None,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -477,6 +527,7 @@ impl LlmBackend for OllamaBackend {
             .execute(
                 || async { self.send_message_attempt(message).await },
                 event_tx,
+                None,
             )
             .await
             .map_err(anyhow::Error::new)
@@ -499,6 +550,7 @@ impl LlmBackend for OllamaBackend {
                             .await
                     },
                     event_tx,
+                    conversation.retry_budget.clone(),
                 )
                 .await;
         }
@@ -510,6 +562,7 @@ impl LlmBackend for OllamaBackend {
                         .await
                 },
                 event_tx,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -526,3 +579,26 @@ impl LlmBackend for OllamaBackend {
         &self.config.model
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<TagInfo> {
+        names
+            .iter()
+            .map(|n| TagInfo {
+                name: n.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn untagged_model_matches_latest() {
+        let pulled = tags(&["llama3.1:latest", "qwen2.5-coder:7b"]);
+        assert!(model_is_pulled(&pulled, "llama3.1"));
+        assert!(model_is_pulled(&pulled, "qwen2.5-coder:7b"));
+        assert!(!model_is_pulled(&pulled, "qwen2.5-coder"));
+        assert!(!model_is_pulled(&pulled, "mistral"));
+    }
+}
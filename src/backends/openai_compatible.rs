@@ -1,8 +1,9 @@
-use super::{LlmBackend, LlmResponse, RequestExecutor};
+use super::{LlmBackend, LlmResponse, RequestExecutor, RetryPolicy};
 use crate::agent::{Conversation, ConversationMessage, ToolCall};
 use crate::backends::llm_error::LlmError;
+use crate::backends::stop_sequences;
 use crate::backends::stream::StreamOptions;
-use crate::config::{ReasoningDisplay, ReasoningEffort};
+use crate::config::{ReasoningDisplay, ReasoningEffort, ToolApi};
 use crate::tools::ToolRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -16,12 +17,15 @@ pub struct OpenAICompatibleConfig {
     pub model: String,
     pub base_url: String,
     pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop: Vec<String>,
     pub chat_api: String,
     pub pricing_endpoint: Option<String>,
     pub thinking_budget: Option<u32>,
     pub reasoning_effort: Option<ReasoningEffort>,
     pub reasoning_display: Option<ReasoningDisplay>,
     pub streaming: bool,
+    pub tool_api: ToolApi,
 }
 
 impl Default for OpenAICompatibleConfig {
@@ -33,11 +37,14 @@ impl Default for OpenAICompatibleConfig {
             base_url: "https://api.openai.com/v1".to_string(),
             chat_api: "/chat/completions".to_string(),
             temperature: None,
+            top_p: None,
+            stop: Vec::new(),
             pricing_endpoint: None,
             thinking_budget: None,
             reasoning_effort: None,
             reasoning_display: None,
             streaming: true,
+            tool_api: ToolApi::Auto,
         }
     }
 }
@@ -62,6 +69,7 @@ struct OutputConfig {
 struct ReasoningParams {
     max_tokens: u32,
     temperature: Option<f32>,
+    top_p: Option<f32>,
     reasoning: Option<ReasoningConfig>,
     reasoning_effort: Option<ReasoningEffort>,
     thinking: Option<ThinkingConfig>,
@@ -74,6 +82,8 @@ pub struct OpenAICompatibleBackend {
     default_executor: RequestExecutor,
     cached_pricing: std::sync::Arc<tokio::sync::RwLock<Option<crate::backends::TokenPricing>>>,
     cached_supports_images: std::sync::Arc<tokio::sync::RwLock<Option<bool>>>,
+    /// Set once a gateway under `ToolApi::Auto` has rejected `tools`.
+    legacy_functions: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -84,10 +94,18 @@ struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    functions: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function_call: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     reasoning: Option<ReasoningConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reasoning_effort: Option<ReasoningEffort>,
@@ -114,11 +132,39 @@ struct OpenAIWireMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_calls: Option<Vec<ToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    function_call: Option<LegacyFunctionCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
 }
 
+/// A call in the legacy `functions` API, which allows one per message.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct LegacyFunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+impl LegacyFunctionCall {
+    /// The legacy API has no call ids; results are matched by name instead.
+    fn into_tool_call(self) -> ToolCall {
+        ToolCall {
+            id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+            r#type: "function".to_string(),
+            function: crate::agent::ToolFunction {
+                name: self.name,
+                arguments: if self.arguments.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    self.arguments
+                },
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 enum OpenAIContent {
@@ -175,6 +221,7 @@ fn to_openai_wire(messages: &[ConversationMessage]) -> Vec<OpenAIWireMessage> {
                 role: m.role.as_str().to_string(),
                 content,
                 tool_calls: m.tool_calls.clone(),
+                function_call: None,
                 tool_call_id: m.tool_call_id.clone(),
                 name: m.name.clone(),
             }
@@ -182,6 +229,48 @@ fn to_openai_wire(messages: &[ConversationMessage]) -> Vec<OpenAIWireMessage> {
         .collect()
 }
 
+/// Rewrite tool traffic for the legacy `functions` API: each tool call
+/// becomes an assistant message with one `function_call`, and tool results
+/// become `function` messages named after the tool.
+fn to_legacy_functions(messages: Vec<OpenAIWireMessage>) -> Vec<OpenAIWireMessage> {
+    let mut legacy = Vec::with_capacity(messages.len());
+    for mut message in messages {
+        if message.role == "tool" {
+            message.role = "function".to_string();
+            message.tool_call_id = None;
+            legacy.push(message);
+            continue;
+        }
+        let Some(tool_calls) = message.tool_calls.take() else {
+            legacy.push(message);
+            continue;
+        };
+        let mut content = message.content.take();
+        for call in tool_calls {
+            legacy.push(OpenAIWireMessage {
+                role: message.role.clone(),
+                content: content.take(),
+                tool_calls: None,
+                function_call: Some(LegacyFunctionCall {
+                    name: call.function.name,
+                    arguments: call.function.arguments,
+                }),
+                tool_call_id: None,
+                name: None,
+            });
+        }
+    }
+    legacy
+}
+
+/// A `tools` entry as a legacy `functions` entry.
+fn to_legacy_function_schema(schema: Value) -> Value {
+    match schema.get("function") {
+        Some(function) => function.clone(),
+        None => schema,
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ChatCompletionResponse {
     #[serde(default)]
@@ -237,6 +326,8 @@ struct ResponseMessage {
     content: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default)]
+    function_call: Option<LegacyFunctionCall>,
     #[serde(default, alias = "reasoning_content")]
     reasoning: Option<String>,
 }
@@ -290,7 +381,8 @@ impl OpenAICompatibleBackend {
             .build()
             .context("Failed to build HTTP client")?;
 
-        let default_executor = RequestExecutor::new(3, "OpenAI-compatible API request".to_string());
+        let default_executor = RequestExecutor::new(3, "OpenAI-compatible API request".to_string())
+            .with_backend_name(config.name.clone());
 
         Ok(Self {
             client,
@@ -298,9 +390,60 @@ impl OpenAICompatibleBackend {
             default_executor,
             cached_pricing: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
             cached_supports_images: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+            legacy_functions: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
     }
 
+    /// The tool API requests are sent with, after any fallback under `auto`.
+    fn tool_api(&self) -> ToolApi {
+        match self.config.tool_api {
+            ToolApi::Auto
+                if self
+                    .legacy_functions
+                    .load(std::sync::atomic::Ordering::Relaxed) =>
+            {
+                ToolApi::Functions
+            }
+            ToolApi::Auto => ToolApi::Tools,
+            api => api,
+        }
+    }
+
+    /// Under `auto`, treat a 400/422 that complains about the `tools` field
+    /// as the gateway only knowing `functions`, and switch for the session.
+    /// Returns whether the request should be resent.
+    fn fall_back_to_functions(
+        &self,
+        status: reqwest::StatusCode,
+        error_text: &str,
+        tools: &ToolRegistry,
+    ) -> bool {
+        let rejected_tools = matches!(status.as_u16(), 400 | 422) && {
+            let text = error_text.to_lowercase();
+            text.contains("tools") || text.contains("tool_choice")
+        };
+        if self.config.tool_api != ToolApi::Auto
+            || self.tool_api() != ToolApi::Tools
+            || tools.get_tool_schemas().is_empty()
+            || !rejected_tools
+        {
+            return false;
+        }
+        tracing::info!(
+            "{} rejected the tools field, switching to legacy functions",
+            self.config.name
+        );
+        self.legacy_functions
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        true
+    }
+
+    /// Retries the failures `policy` lists on top of the built-in ones.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.default_executor = self.default_executor.with_retry_policy(policy);
+        self
+    }
+
     async fn fetch_and_cache_model_info(&self) -> Result<()> {
         // Only fetch if pricing endpoint is configured
         let pricing_endpoint = match &self.config.pricing_endpoint {
@@ -359,29 +502,26 @@ impl OpenAICompatibleBackend {
         Ok(())
     }
 
-    fn http_error_to_llm_error(status: reqwest::StatusCode, error_text: String) -> LlmError {
-        let status_code = status.as_u16();
-
-        match status_code {
-            429 => {
-                // Try to parse retry-after header
-                // We can't access the header here, but we'll handle it in the request method
-                LlmError::RateLimit {
-                    retry_after: None,
-                    message: error_text,
-                }
-            }
-            500..=599 => LlmError::ServerError {
-                status: status_code,
-                message: error_text,
-            },
-            401 | 403 => LlmError::AuthenticationError {
-                message: error_text,
-            },
-            _ => LlmError::Other {
-                message: format!("API error {}: {}", status_code, error_text),
-            },
+    /// Preflight: list models to confirm the key and base_url work.
+    async fn check_connection(&self) -> Result<()> {
+        if self.config.api_key.is_empty() {
+            anyhow::bail!(
+                "{} API key not configured. Set it with: hoosh config set {}_api_key <your_key>",
+                self.config.name,
+                self.config.name
+            );
         }
+        let url = format!("{}/models", self.config.base_url);
+        let request = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.config.api_key));
+        super::preflight::check(&self.config.name, &url, request).await?;
+        Ok(())
+    }
+
+    fn http_error_to_llm_error(status: reqwest::StatusCode, error_text: String) -> LlmError {
+        LlmError::from_http_status(status.as_u16(), error_text)
     }
 
     async fn send_message_attempt(&self, message: &str) -> Result<String, LlmError> {
@@ -404,9 +544,7 @@ impl OpenAICompatibleBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -471,9 +609,7 @@ impl OpenAICompatibleBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -481,6 +617,10 @@ impl OpenAICompatibleBackend {
             let headers = response.headers().clone();
             let error_text = response.text().await.unwrap_or_default();
 
+            if self.fall_back_to_functions(status, &error_text, tools) {
+                return Box::pin(self.send_message_with_tools_attempt(conversation, tools)).await;
+            }
+
             // Handle rate limit with retry-after header
             if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
                 let retry_after = headers
@@ -575,18 +715,20 @@ impl OpenAICompatibleBackend {
         }
 
         // Fall back to traditional chat completion format
-        if let Some(choice) = response_data.choices.first()
-            && let Some(message) = &choice.message
+        if let Some(choice) = response_data.choices.into_iter().next()
+            && let Some(message) = choice.message
         {
             let thinking = message.reasoning.clone();
-            if let Some(tool_calls) = &message.tool_calls {
+            let tool_calls = message
+                .tool_calls
+                .or_else(|| Some(vec![message.function_call?.into_tool_call()]));
+            if let Some(tool_calls) = tool_calls {
                 // Response contains tool calls
-                return Ok(LlmResponse::with_tool_calls(
-                    message.content.clone(),
-                    tool_calls.clone(),
-                )
-                .with_tokens(input_tokens, output_tokens)
-                .with_thinking(thinking));
+                return Ok(
+                    LlmResponse::with_tool_calls(message.content.clone(), tool_calls)
+                        .with_tokens(input_tokens, output_tokens)
+                        .with_thinking(thinking),
+                );
             } else if let Some(content) = &message.content {
                 // Response contains only content
                 return Ok(LlmResponse::content_only(content.clone())
@@ -632,14 +774,20 @@ impl OpenAICompatibleBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
             let headers = response.headers().clone();
             let error_text = response.text().await.unwrap_or_default();
+            if self.fall_back_to_functions(status, &error_text, tools) {
+                return Box::pin(self.send_message_with_tools_streaming_attempt(
+                    conversation,
+                    tools,
+                    event_tx,
+                ))
+                .await;
+            }
             if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
                 let retry_after = headers
                     .get("retry-after")
@@ -689,13 +837,18 @@ impl OpenAICompatibleBackend {
                 role: "user".to_string(),
                 content: Some(OpenAIContent::Text(message.to_string())),
                 tool_calls: None,
+                function_call: None,
                 tool_call_id: None,
                 name: None,
             }],
             max_completion_tokens: r.max_tokens,
             temperature: r.temperature,
+            top_p: r.top_p,
+            stop: stop_sequences(&self.config.stop),
             tools: None,
             tool_choice: None,
+            functions: None,
+            function_call: None,
             reasoning: r.reasoning,
             reasoning_effort: r.reasoning_effort,
             thinking: r.thinking,
@@ -718,17 +871,33 @@ impl OpenAICompatibleBackend {
             conversation.thinking_budget_override,
         );
 
+        let messages = to_openai_wire(conversation.get_messages_for_api());
+        let (messages, tools, functions) = match self.tool_api() {
+            ToolApi::Functions => (
+                to_legacy_functions(messages),
+                None,
+                has_tools.then(|| {
+                    tool_schemas
+                        .into_iter()
+                        .map(to_legacy_function_schema)
+                        .collect()
+                }),
+            ),
+            _ => (messages, has_tools.then_some(tool_schemas), None),
+        };
+        let auto = |enabled: bool| enabled.then(|| "auto".to_string());
+
         ChatCompletionRequest {
             model: self.config.model.clone(),
-            messages: to_openai_wire(conversation.get_messages_for_api()),
+            messages,
             max_completion_tokens: r.max_tokens,
             temperature: r.temperature,
-            tools: if has_tools { Some(tool_schemas) } else { None },
-            tool_choice: if has_tools {
-                Some("auto".to_string())
-            } else {
-                None
-            },
+            top_p: r.top_p,
+            stop: stop_sequences(&self.config.stop),
+            tool_choice: auto(tools.is_some()),
+            tools,
+            function_call: auto(functions.is_some()),
+            functions,
             reasoning: r.reasoning,
             reasoning_effort: r.reasoning_effort,
             thinking: r.thinking,
@@ -751,6 +920,7 @@ impl OpenAICompatibleBackend {
             return ReasoningParams {
                 max_tokens: base_max_tokens,
                 temperature: Some(1.0),
+                top_p: None,
                 reasoning: None,
                 reasoning_effort: None,
                 thinking: Some(ThinkingConfig {
@@ -768,6 +938,7 @@ impl OpenAICompatibleBackend {
             return ReasoningParams {
                 max_tokens: base_max_tokens,
                 temperature: Some(1.0),
+                top_p: None,
                 reasoning: None,
                 reasoning_effort: Some(effort),
                 thinking: None,
@@ -780,6 +951,7 @@ impl OpenAICompatibleBackend {
             Some(budget) if budget > 0 => ReasoningParams {
                 max_tokens: base_max_tokens.max(budget.saturating_add(4096)),
                 temperature: Some(1.0),
+                top_p: None,
                 reasoning: Some(ReasoningConfig { max_tokens: budget }),
                 reasoning_effort: None,
                 thinking: None,
@@ -788,6 +960,7 @@ impl OpenAICompatibleBackend {
             _ => ReasoningParams {
                 max_tokens: base_max_tokens,
                 temperature: base_temperature,
+                top_p: self.config.top_p,
                 reasoning: None,
                 reasoning_effort: None,
                 thinking: None,
@@ -801,7 +974,11 @@ impl OpenAICompatibleBackend {
 impl LlmBackend for OpenAICompatibleBackend {
     async fn send_message(&self, message: &str) -> Result<String> {
         self.default_executor
-            .execute(|| async { self.send_message_attempt(message).await }, None)
+            .execute(
+                || async { self.send_message_attempt(message).await },
+                None,
+                None,
+            )
             .await
             .map_err(anyhow::Error::new)
     }
@@ -818,6 +995,7 @@ impl LlmBackend for OpenAICompatibleBackend {
                         .await
                 },
                 None,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -835,9 +1013,12 @@ impl LlmBackend for OpenAICompatibleBackend {
     }
 
     async fn initialize(&self) -> Result<()> {
-        // Fetch pricing + capability info at initialization if endpoint is configured
-        self.fetch_and_cache_model_info().await?;
-        Ok(())
+        // The preflight and the pricing + capability fetch are independent, so a
+        // failed connection check (which --no-preflight may downgrade to a
+        // warning) must not leave the model info uncached.
+        let connection = self.check_connection().await;
+        let model_info = self.fetch_and_cache_model_info().await;
+        connection.and(model_info)
     }
 
     async fn supports_images(&self) -> bool {
@@ -886,6 +1067,7 @@ impl LlmBackend for OpenAICompatibleBackend {
             .execute(
                 || async { self.send_message_attempt(message).await },
                 event_tx,
+                None,
             )
             .await
             .map_err(anyhow::Error::new)
@@ -908,6 +1090,7 @@ impl LlmBackend for OpenAICompatibleBackend {
                             .await
                     },
                     event_tx,
+                    conversation.retry_budget.clone(),
                 )
                 .await;
         }
@@ -919,6 +1102,7 @@ impl LlmBackend for OpenAICompatibleBackend {
                         .await
                 },
                 event_tx,
+                conversation.retry_budget.clone(),
             )
             .await
     }
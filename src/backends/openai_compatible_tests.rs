@@ -3,7 +3,7 @@ use crate::agent::Conversation;
 use crate::backends::{LlmBackend, LlmError};
 use crate::tools::ToolRegistry;
 use serde_json::json;
-use wiremock::matchers::{header, header_exists, method, path};
+use wiremock::matchers::{body_string_contains, header, header_exists, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn create_test_config() -> OpenAICompatibleConfig {
@@ -14,11 +14,14 @@ fn create_test_config() -> OpenAICompatibleConfig {
         base_url: "http://localhost".to_string(),
         chat_api: "/v1/chat/completions".to_string(),
         temperature: Some(0.7),
+        top_p: None,
+        stop: Vec::new(),
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: true,
+        tool_api: ToolApi::Auto,
     }
 }
 
@@ -105,6 +108,7 @@ async fn backend_handles_rate_limit_error() {
         default_executor,
         cached_pricing: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
         cached_supports_images: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+        legacy_functions: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
     };
 
     let result = backend.send_message("test").await;
@@ -135,6 +139,7 @@ async fn backend_handles_server_error() {
         default_executor,
         cached_pricing: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
         cached_supports_images: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+        legacy_functions: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
     };
 
     let result = backend.send_message("test").await;
@@ -164,6 +169,7 @@ async fn backend_handles_authentication_error() {
         default_executor,
         cached_pricing: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
         cached_supports_images: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+        legacy_functions: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
     };
 
     let result = backend.send_message("test").await;
@@ -413,6 +419,7 @@ async fn backend_handles_multiple_retries_on_rate_limit() {
         default_executor,
         cached_pricing: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
         cached_supports_images: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+        legacy_functions: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
     };
 
     let result = backend.send_message("test").await;
@@ -467,11 +474,14 @@ async fn backend_configuration_with_custom_values() {
         base_url: "https://custom.api".to_string(),
         chat_api: "/custom/chat".to_string(),
         temperature: Some(0.9),
+        top_p: None,
+        stop: Vec::new(),
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: true,
+        tool_api: ToolApi::Auto,
     };
 
     let backend = OpenAICompatibleBackend::new(config).unwrap();
@@ -655,6 +665,44 @@ fn reasoning_display_serializes_thinking_and_output_config() {
     assert!(wire.get("reasoning_effort").is_none());
 }
 
+#[test]
+fn sampling_params_omitted_unless_configured() {
+    let config = OpenAICompatibleConfig {
+        temperature: None,
+        ..create_test_config()
+    };
+    let wire = serde_json::to_value(
+        OpenAICompatibleBackend::new(config)
+            .unwrap()
+            .create_request("hi"),
+    )
+    .unwrap();
+    assert!(wire.get("temperature").is_none());
+    assert!(wire.get("top_p").is_none());
+
+    let config = OpenAICompatibleConfig {
+        top_p: Some(0.5),
+        ..create_test_config()
+    };
+    let request = OpenAICompatibleBackend::new(config)
+        .unwrap()
+        .create_request("hi");
+    assert_eq!(request.temperature, Some(0.7));
+    assert_eq!(request.top_p, Some(0.5));
+}
+
+#[test]
+fn reasoning_drops_top_p() {
+    let config = OpenAICompatibleConfig {
+        top_p: Some(0.5),
+        thinking_budget: Some(2048),
+        ..create_test_config()
+    };
+    let backend = OpenAICompatibleBackend::new(config).unwrap();
+    let r = backend.reasoning_request_overrides(4096, Some(0.7), None);
+    assert!(r.top_p.is_none());
+}
+
 #[test]
 fn reasoning_effort_serializes_lowercase() {
     let json = serde_json::to_string(&crate::config::ReasoningEffort::High).unwrap();
@@ -681,3 +729,246 @@ fn reasoning_field_in_response_is_ignored() {
     let msg = resp.choices[0].message.as_ref().unwrap();
     assert_eq!(msg.content.as_deref(), Some("The answer is 42."));
 }
+
+fn registry_with_read_file() -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_tool(std::sync::Arc::new(
+            crate::tools::file_ops::ReadFileTool::new(),
+        ))
+        .unwrap();
+    registry
+}
+
+fn backend_with_tool_api(base_url: String, tool_api: ToolApi) -> OpenAICompatibleBackend {
+    OpenAICompatibleBackend::new(OpenAICompatibleConfig {
+        base_url,
+        tool_api,
+        streaming: false,
+        ..create_test_config()
+    })
+    .unwrap()
+}
+
+fn conversation_with_tool_round() -> Conversation {
+    use crate::agent::{ToolCall, ToolCallResponse, ToolFunction};
+
+    let mut conversation = Conversation::new();
+    conversation.add_user_message("Read a.txt".to_string());
+    conversation.add_assistant_message(
+        Some("Reading it".to_string()),
+        Some(vec![ToolCall {
+            id: "call_1".to_string(),
+            r#type: "function".to_string(),
+            function: ToolFunction {
+                name: "read_file".to_string(),
+                arguments: "{\"path\":\"a.txt\"}".to_string(),
+            },
+        }]),
+    );
+    conversation.add_tool_result(ToolCallResponse::success(
+        "call_1".to_string(),
+        "read_file".to_string(),
+        "Read(a.txt)".to_string(),
+        "hello".to_string(),
+    ));
+    conversation
+}
+
+#[test]
+fn tools_api_serializes_registry_as_tools() {
+    let backend = backend_with_tool_api("http://localhost".to_string(), ToolApi::Tools);
+    let request = backend
+        .create_request_with_tools(&conversation_with_tool_round(), &registry_with_read_file());
+    let json = serde_json::to_value(&request).unwrap();
+
+    assert_eq!(json["tools"][0]["type"], "function");
+    assert_eq!(json["tools"][0]["function"]["name"], "read_file");
+    assert_eq!(json["tool_choice"], "auto");
+    assert!(json.get("functions").is_none());
+    assert_eq!(json["messages"][1]["tool_calls"][0]["id"], "call_1");
+    assert_eq!(json["messages"][2]["role"], "tool");
+    assert_eq!(json["messages"][2]["tool_call_id"], "call_1");
+}
+
+#[test]
+fn functions_api_serializes_registry_as_legacy_functions() {
+    let backend = backend_with_tool_api("http://localhost".to_string(), ToolApi::Functions);
+    let request = backend
+        .create_request_with_tools(&conversation_with_tool_round(), &registry_with_read_file());
+    let json = serde_json::to_value(&request).unwrap();
+
+    assert!(json.get("tools").is_none());
+    assert!(json.get("tool_choice").is_none());
+    assert_eq!(json["functions"][0]["name"], "read_file");
+    assert!(json["functions"][0]["parameters"].is_object());
+    assert_eq!(json["function_call"], "auto");
+
+    let messages = &json["messages"];
+    assert_eq!(messages[1]["content"], "Reading it");
+    assert_eq!(messages[1]["function_call"]["name"], "read_file");
+    assert_eq!(
+        messages[1]["function_call"]["arguments"],
+        "{\"path\":\"a.txt\"}"
+    );
+    assert!(messages[1].get("tool_calls").is_none());
+    assert_eq!(messages[2]["role"], "function");
+    assert_eq!(messages[2]["name"], "read_file");
+    assert!(messages[2].get("tool_call_id").is_none());
+}
+
+#[test]
+fn legacy_functions_split_parallel_calls_into_messages() {
+    use crate::agent::{ConversationMessage, Role, ToolCall, ToolFunction};
+
+    let call = |name: &str| ToolCall {
+        id: format!("call_{name}"),
+        r#type: "function".to_string(),
+        function: ToolFunction {
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+        },
+    };
+    let wire = to_legacy_functions(to_openai_wire(&[ConversationMessage {
+        role: Role::Assistant,
+        content: Some("two at once".to_string()),
+        tool_calls: Some(vec![call("list"), call("grep")]),
+        tool_call_id: None,
+        name: None,
+        attachments: Vec::new(),
+    }]));
+    let json = serde_json::to_value(&wire).unwrap();
+
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert_eq!(json[0]["content"], "two at once");
+    assert_eq!(json[0]["function_call"]["name"], "list");
+    assert!(json[1].get("content").is_none());
+    assert_eq!(json[1]["function_call"]["name"], "grep");
+}
+
+#[tokio::test]
+async fn backend_parses_legacy_function_call_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{
+                "message": {
+                    "content": null,
+                    "function_call": {
+                        "name": "read_file",
+                        "arguments": "{\"path\":\"a.txt\"}"
+                    }
+                },
+                "finish_reason": "function_call"
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend_with_tool_api(server.uri(), ToolApi::Functions);
+    let response = backend
+        .send_message_with_tools(&conversation_with_tool_round(), &registry_with_read_file())
+        .await
+        .unwrap();
+
+    let calls = response.tool_calls.expect("tool calls");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function.name, "read_file");
+    assert_eq!(calls[0].function.arguments, "{\"path\":\"a.txt\"}");
+    assert!(!calls[0].id.is_empty());
+}
+
+#[tokio::test]
+async fn auto_tool_api_falls_back_to_functions_when_tools_are_rejected() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(body_string_contains("\"tools\""))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .set_body_string("Unrecognized request argument supplied: tools"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(body_string_contains("\"functions\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{
+                "message": {
+                    "function_call": { "name": "read_file", "arguments": "{}" }
+                }
+            }]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let backend = backend_with_tool_api(server.uri(), ToolApi::Auto);
+    let conversation = conversation_with_tool_round();
+    let tools = registry_with_read_file();
+    // The first request learns the gateway's flavor; the second goes
+    // straight to functions.
+    for _ in 0..2 {
+        let response = backend
+            .send_message_with_tools(&conversation, &tools)
+            .await
+            .unwrap();
+        assert_eq!(response.tool_calls.unwrap()[0].function.name, "read_file");
+    }
+}
+
+#[test]
+fn configured_stops_appear_in_request() {
+    let backend = OpenAICompatibleBackend::new(OpenAICompatibleConfig {
+        stop: vec!["###".to_string(), "\nUser:".to_string()],
+        ..create_test_config()
+    })
+    .unwrap();
+    let request = backend
+        .create_request_with_tools(&conversation_with_tool_round(), &registry_with_read_file());
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["stop"], json!(["###", "\nUser:"]));
+
+    let plain = serde_json::to_value(
+        create_backend_with_url("http://localhost".to_string()).create_request("hi"),
+    )
+    .unwrap();
+    assert!(plain.get("stop").is_none());
+}
+
+#[tokio::test]
+async fn failed_preflight_still_caches_model_info() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "id": "gpt-4",
+                "pricing": { "prompt": "0.000002", "completion": "0.000004" },
+                "architecture": { "input_modalities": ["text", "image"] }
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let backend = OpenAICompatibleBackend::new(OpenAICompatibleConfig {
+        base_url: server.uri(),
+        pricing_endpoint: Some("/api/v1/models".to_string()),
+        ..create_test_config()
+    })
+    .unwrap();
+
+    assert!(backend.initialize().await.is_err());
+    let pricing = backend.pricing().unwrap();
+    assert!((pricing.input_per_million - 2.0).abs() < 1e-9);
+    assert!(backend.supports_images().await);
+}
@@ -0,0 +1,109 @@
+//! Startup checks that a backend is reachable and accepts its credentials,
+//! so a bad key or base_url is reported before the first prompt.
+
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Send a lightweight request (a models list) and turn any failure into an
+/// actionable error. Returns the response on success so callers can inspect
+/// the body.
+pub(crate) async fn check(
+    backend_name: &str,
+    url: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response> {
+    let response = request
+        .timeout(PREFLIGHT_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| request_error(backend_name, url, &e))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(status_error(backend_name, url, status.as_u16(), &body))
+}
+
+fn request_error(backend_name: &str, url: &str, error: &reqwest::Error) -> anyhow::Error {
+    let reason = if error.is_timeout() {
+        "timed out"
+    } else if error.is_connect() {
+        "connection failed"
+    } else {
+        "request failed"
+    };
+    anyhow!(
+        "Could not reach {} at {} ({}: {}).\n\
+         Check that the service is running, or fix the URL with: hoosh config set {}_base_url <url>",
+        backend_name,
+        url,
+        reason,
+        error,
+        backend_name
+    )
+}
+
+pub(crate) fn status_error(
+    backend_name: &str,
+    url: &str,
+    status: u16,
+    body: &str,
+) -> anyhow::Error {
+    match status {
+        401 | 403 => anyhow!(
+            "{} rejected the API key (HTTP {}).\n\
+             Set a valid key with: hoosh config set {}_api_key <your_key>",
+            backend_name,
+            status,
+            backend_name
+        ),
+        404 => anyhow!(
+            "{} returned 404 for {}.\n\
+             The base_url is probably wrong; fix it with: hoosh config set {}_base_url <url>",
+            backend_name,
+            url,
+            backend_name
+        ),
+        _ => {
+            let detail: String = body.trim().chars().take(200).collect();
+            anyhow!(
+                "{} health check failed (HTTP {} from {}): {}",
+                backend_name,
+                status,
+                url,
+                detail
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unauthorized_points_at_the_api_key() {
+        let msg = status_error("anthropic", "https://x/v1/models", 401, "").to_string();
+        assert!(msg.contains("rejected the API key"));
+        assert!(msg.contains("hoosh config set anthropic_api_key"));
+    }
+
+    #[test]
+    fn not_found_points_at_base_url() {
+        let msg = status_error("openai", "https://x/models", 404, "").to_string();
+        assert!(msg.contains("base_url is probably wrong"));
+        assert!(msg.contains("https://x/models"));
+    }
+
+    #[test]
+    fn other_statuses_include_a_short_body() {
+        let body = "x".repeat(500);
+        let msg = status_error("ollama", "http://localhost:11434/api/tags", 503, &body).to_string();
+        assert!(msg.contains("HTTP 503"));
+        assert!(msg.len() < 400);
+    }
+}
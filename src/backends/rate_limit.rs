@@ -0,0 +1,188 @@
+//! Client-side limits on LLM requests. One limiter per backend is shared by
+//! the main loop and every sub-agent task, so their combined traffic stays
+//! under the provider's limits. Requests over a limit wait rather than fail.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::config::AppConfig;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+pub struct RequestLimiter {
+    max_concurrent: Option<usize>,
+    in_flight: Option<Arc<Semaphore>>,
+    requests_per_minute: Option<usize>,
+    started: tokio::sync::Mutex<VecDeque<Instant>>,
+}
+
+/// Held while a request is in flight; dropping it frees the slot.
+pub struct RequestPermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+impl RequestLimiter {
+    pub fn new(max_concurrent: Option<usize>, requests_per_minute: Option<usize>) -> Self {
+        Self {
+            max_concurrent,
+            in_flight: max_concurrent.map(|n| Arc::new(Semaphore::new(n.max(1)))),
+            requests_per_minute: requests_per_minute.map(|n| n.max(1)),
+            started: tokio::sync::Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Wait until a request may go out. `on_queued` is called once, with the
+    /// reason, if the request has to wait at all.
+    pub async fn acquire(&self, on_queued: impl Fn(String)) -> RequestPermit {
+        let mut queued = false;
+
+        let slot = match (&self.in_flight, self.max_concurrent) {
+            (Some(in_flight), Some(max)) => Some(match Arc::clone(in_flight).try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    on_queued(format!("{} requests already in flight", max));
+                    queued = true;
+                    Arc::clone(in_flight)
+                        .acquire_owned()
+                        .await
+                        .expect("request semaphore is never closed")
+                }
+            }),
+            _ => None,
+        };
+
+        if let Some(limit) = self.requests_per_minute {
+            while let Some(wait) = self.start_or_wait(limit).await {
+                if !queued {
+                    on_queued(format!("{} requests per minute reached", limit));
+                    queued = true;
+                }
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        RequestPermit { _slot: slot }
+    }
+
+    /// Record a request start if the window has room, otherwise return how
+    /// long until the oldest start leaves the window.
+    async fn start_or_wait(&self, limit: usize) -> Option<Duration> {
+        let mut started = self.started.lock().await;
+        let now = Instant::now();
+        while started
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            started.pop_front();
+        }
+        if started.len() < limit {
+            started.push_back(now);
+            return None;
+        }
+        started
+            .front()
+            .map(|oldest| RATE_WINDOW - now.duration_since(*oldest))
+    }
+}
+
+/// Limiters for every backend with limits configured, created on first use
+/// and shared from then on.
+#[derive(Default)]
+pub struct RequestLimiters {
+    limits: HashMap<String, (Option<usize>, Option<usize>)>,
+    limiters: Mutex<HashMap<String, Arc<RequestLimiter>>>,
+}
+
+impl RequestLimiters {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let limits = config
+            .backends
+            .iter()
+            .filter(|(_, backend)| {
+                backend.max_concurrent_requests.is_some() || backend.requests_per_minute.is_some()
+            })
+            .map(|(name, backend)| {
+                (
+                    name.clone(),
+                    (backend.max_concurrent_requests, backend.requests_per_minute),
+                )
+            })
+            .collect();
+        Self {
+            limits,
+            limiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The shared limiter for `backend_name`, or `None` when it has no limits.
+    pub fn for_backend(&self, backend_name: &str) -> Option<Arc<RequestLimiter>> {
+        let (max_concurrent, per_minute) = *self.limits.get(backend_name)?;
+        let mut limiters = self.limiters.lock().ok()?;
+        let limiter = limiters
+            .entry(backend_name.to_string())
+            .or_insert_with(|| Arc::new(RequestLimiter::new(max_concurrent, per_minute)));
+        Some(Arc::clone(limiter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    #[tokio::test]
+    async fn concurrency_limit_queues_until_a_slot_frees() {
+        let limiter = RequestLimiter::new(Some(1), None);
+        let reasons = StdMutex::new(Vec::new());
+
+        let first = limiter.acquire(|r| reasons.lock().unwrap().push(r)).await;
+        assert!(reasons.lock().unwrap().is_empty());
+
+        let second = limiter.acquire(|r| reasons.lock().unwrap().push(r));
+        tokio::pin!(second);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), &mut second)
+                .await
+                .is_err()
+        );
+        drop(first);
+        second.await;
+        assert_eq!(
+            *reasons.lock().unwrap(),
+            vec!["1 requests already in flight".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limit_waits_for_the_window_to_move() {
+        let limiter = RequestLimiter::new(None, Some(2));
+
+        assert!(limiter.start_or_wait(2).await.is_none());
+        assert!(limiter.start_or_wait(2).await.is_none());
+        let wait = limiter.start_or_wait(2).await.unwrap();
+        assert!(wait > Duration::from_secs(59) && wait <= RATE_WINDOW);
+    }
+
+    #[test]
+    fn limiters_are_shared_per_backend_and_absent_without_limits() {
+        let mut config = AppConfig::default();
+        config.backends.insert(
+            "openai".to_string(),
+            toml::from_str("max_concurrent_requests = 2").unwrap(),
+        );
+        config.backends.insert(
+            "anthropic".to_string(),
+            toml::from_str("model = \"x\"").unwrap(),
+        );
+        let limiters = RequestLimiters::from_config(&config);
+
+        let a = limiters.for_backend("openai").unwrap();
+        let b = limiters.for_backend("openai").unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(limiters.for_backend("anthropic").is_none());
+        assert!(limiters.for_backend("mock").is_none());
+    }
+}
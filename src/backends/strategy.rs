@@ -1,5 +1,8 @@
 use crate::agent::AgentEvent;
+use crate::agent::RetryBudget;
 use crate::backends::llm_error::LlmError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::sleep;
@@ -25,10 +28,44 @@ fn format_duration(d: Duration) -> String {
     }
 }
 
+/// Failures to retry on top of the built-in transient ones (rate limits,
+/// 5xx, overload, timeouts and network errors), for gateways that report
+/// transient trouble in their own way. Entries only ever add to that set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// HTTP statuses to retry, e.g. a gateway's 499 or 520.
+    #[serde(default)]
+    pub statuses: Vec<u16>,
+    /// Case-insensitive substrings of the error body to retry on.
+    #[serde(default)]
+    pub messages: Vec<String>,
+}
+
+impl RetryPolicy {
+    pub fn is_retryable(&self, error: &LlmError) -> bool {
+        if error.is_retryable() {
+            return true;
+        }
+        if error
+            .status()
+            .is_some_and(|status| self.statuses.contains(&status))
+        {
+            return true;
+        }
+        let message = error.message().to_lowercase();
+        self.messages
+            .iter()
+            .filter(|fragment| !fragment.is_empty())
+            .any(|fragment| message.contains(&fragment.to_lowercase()))
+    }
+}
+
 pub struct RetryStrategy {
     pub max_attempts: u32,
     pub operation_name: String,
     pub event_tx: Option<UnboundedSender<AgentEvent>>,
+    pub policy: RetryPolicy,
+    pub retry_budget: Option<Arc<RetryBudget>>,
 }
 
 impl RetryStrategy {
@@ -41,9 +78,22 @@ impl RetryStrategy {
             max_attempts,
             operation_name,
             event_tx,
+            policy: RetryPolicy::default(),
+            retry_budget: None,
         }
     }
 
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Draw retries from the turn's budget as well as `max_attempts`.
+    pub fn with_retry_budget(mut self, retry_budget: Option<Arc<RetryBudget>>) -> Self {
+        self.retry_budget = retry_budget;
+        self
+    }
+
     fn send_event(&self, event: AgentEvent) {
         if let Some(ref tx) = self.event_tx {
             let _ = tx.send(event);
@@ -77,7 +127,14 @@ impl RetryStrategy {
                     }
                     return Ok(result);
                 }
-                Err(e) if e.is_retryable() && attempts + 1 < self.max_attempts => {
+                Err(e)
+                    if self.policy.is_retryable(&e)
+                        && attempts + 1 < self.max_attempts
+                        && self
+                            .retry_budget
+                            .as_ref()
+                            .is_none_or(|budget| budget.take_retry()) =>
+                {
                     attempts += 1;
 
                     let actual_delay = if let LlmError::RateLimit {
@@ -115,10 +172,21 @@ impl RetryStrategy {
                 Err(e) => {
                     // Only send retry event if we actually attempted retries
                     if attempts > 0 {
+                        let budget_spent = attempts + 1 < self.max_attempts
+                            && self.policy.is_retryable(&e)
+                            && self
+                                .retry_budget
+                                .as_ref()
+                                .is_some_and(|budget| budget.is_exhausted());
                         let final_message = format!(
-                            "{} failed after {} attempts: {}",
+                            "{} failed after {} attempts{}: {}",
                             self.operation_name,
                             attempts + 1,
+                            if budget_spent {
+                                " (retry budget for this turn used up)"
+                            } else {
+                                ""
+                            },
                             e.short_message()
                         );
 
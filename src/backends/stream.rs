@@ -114,6 +114,9 @@ struct OpenAiStreamDelta {
     reasoning: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<OpenAiStreamToolCall>>,
+    /// Legacy `functions` API: at most one call, streamed like a tool call.
+    #[serde(default)]
+    function_call: Option<OpenAiStreamFunction>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -153,6 +156,19 @@ struct OpenAiToolCallAcc {
     arguments: String,
 }
 
+impl OpenAiToolCallAcc {
+    fn apply(&mut self, func: OpenAiStreamFunction) {
+        if let Some(name) = func.name
+            && !name.is_empty()
+        {
+            self.name = name;
+        }
+        if let Some(args) = func.arguments {
+            self.arguments.push_str(&args);
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct OpenAiStreamAccumulator {
     text: String,
@@ -214,17 +230,13 @@ impl OpenAiStreamAccumulator {
                         entry.id = id;
                     }
                     if let Some(func) = tc.function {
-                        if let Some(name) = func.name
-                            && !name.is_empty()
-                        {
-                            entry.name = name;
-                        }
-                        if let Some(args) = func.arguments {
-                            entry.arguments.push_str(&args);
-                        }
+                        entry.apply(func);
                     }
                 }
             }
+            if let Some(func) = delta.function_call {
+                self.tool_calls.entry(0).or_default().apply(func);
+            }
         }
         hit_length_limit
     }
@@ -241,7 +253,12 @@ impl OpenAiStreamAccumulator {
             .into_values()
             .filter(|tc| !tc.name.is_empty())
             .map(|tc| ToolCall {
-                id: tc.id,
+                // Legacy function calls come without an id.
+                id: if tc.id.is_empty() {
+                    format!("call_{}", uuid::Uuid::new_v4().simple())
+                } else {
+                    tc.id
+                },
                 r#type: "function".to_string(),
                 function: crate::agent::ToolFunction {
                     name: tc.name,
@@ -362,6 +379,20 @@ mod tests {
         assert_eq!(calls[0].function.arguments, "{\"path\":\"a.txt\"}");
     }
 
+    #[test]
+    fn openai_accumulator_assembles_legacy_function_call() {
+        let resp = apply_chunks(&[
+            r#"{"choices":[{"delta":{"function_call":{"name":"read","arguments":""}}}]}"#,
+            r#"{"choices":[{"delta":{"function_call":{"arguments":"{\"path\":\"a.txt\"}"}}}]}"#,
+            r#"{"choices":[{"delta":{},"finish_reason":"function_call"}]}"#,
+        ]);
+        let calls = resp.tool_calls.expect("tool calls");
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].id.starts_with("call_"));
+        assert_eq!(calls[0].function.name, "read");
+        assert_eq!(calls[0].function.arguments, "{\"path\":\"a.txt\"}");
+    }
+
     #[test]
     fn openai_accumulator_collects_reasoning() {
         let resp = apply_chunks(&[
@@ -1,6 +1,7 @@
-use super::{LlmBackend, LlmResponse, RequestExecutor};
+use super::{LlmBackend, LlmResponse, RequestExecutor, RetryPolicy};
 use crate::agent::{Conversation, ConversationMessage, Role, ToolCall};
 use crate::backends::llm_error::LlmError;
+use crate::backends::stop_sequences;
 use crate::tools::ToolRegistry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -14,6 +15,9 @@ pub struct TogetherAiConfig {
     pub api_key: String,
     pub model: String,
     pub base_url: String,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop: Vec<String>,
     pub streaming: bool,
 }
 
@@ -23,6 +27,9 @@ impl Default for TogetherAiConfig {
             api_key: String::new(),
             model: "meta-llama/Llama-2-7b-chat-hf".to_string(),
             base_url: "https://api.together.xyz/v1".to_string(),
+            temperature: None,
+            top_p: None,
+            stop: Vec::new(),
             streaming: true,
         }
     }
@@ -40,8 +47,13 @@ struct ChatCompletionRequest {
     model: String,
     messages: Vec<ConversationMessage>,
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
@@ -114,7 +126,8 @@ impl TogetherAiBackend {
             .build()
             .context("Failed to build HTTP client")?;
 
-        let default_executor = RequestExecutor::new(3, "Together AI API request".to_string());
+        let default_executor = RequestExecutor::new(3, "Together AI API request".to_string())
+            .with_backend_name("together_ai");
 
         Ok(Self {
             client,
@@ -124,6 +137,12 @@ impl TogetherAiBackend {
         })
     }
 
+    /// Retries the failures `policy` lists on top of the built-in ones.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.default_executor = self.default_executor.with_retry_policy(policy);
+        self
+    }
+
     async fn fetch_and_cache_pricing(&self) -> Result<()> {
         let url = format!("{}/models", self.config.base_url);
 
@@ -156,28 +175,7 @@ impl TogetherAiBackend {
     }
 
     fn http_error_to_llm_error(status: reqwest::StatusCode, error_text: String) -> LlmError {
-        let status_code = status.as_u16();
-
-        match status_code {
-            429 => {
-                // Try to parse retry-after header
-                // We can't access the header here, but we'll handle it in the request method
-                LlmError::RateLimit {
-                    retry_after: None,
-                    message: error_text,
-                }
-            }
-            500..=599 => LlmError::ServerError {
-                status: status_code,
-                message: error_text,
-            },
-            401 | 403 => LlmError::AuthenticationError {
-                message: error_text,
-            },
-            _ => LlmError::Other {
-                message: format!("API error {}: {}", status_code, error_text),
-            },
-        }
+        LlmError::from_http_status(status.as_u16(), error_text)
     }
 
     async fn send_message_attempt(&self, message: &str) -> Result<String, LlmError> {
@@ -197,9 +195,7 @@ impl TogetherAiBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -263,9 +259,7 @@ impl TogetherAiBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -340,7 +334,9 @@ impl TogetherAiBackend {
                 attachments: Vec::new(),
             }],
             max_tokens: Some(8192),
-            temperature: Some(0.7),
+            temperature: self.config.temperature,
+            top_p: self.config.top_p,
+            stop: stop_sequences(&self.config.stop),
             tools: None,
             tool_choice: None,
             stream: false,
@@ -360,7 +356,9 @@ impl TogetherAiBackend {
             model: self.config.model.clone(),
             messages: conversation.get_messages_for_api().clone(),
             max_tokens: Some(8192),
-            temperature: Some(0.7),
+            temperature: self.config.temperature,
+            top_p: self.config.top_p,
+            stop: stop_sequences(&self.config.stop),
             tools: if has_tools { Some(tool_schemas) } else { None },
             tool_choice: if has_tools {
                 Some("auto".to_string())
@@ -401,9 +399,7 @@ impl TogetherAiBackend {
             .json(&request)
             .send()
             .await
-            .map_err(|e| LlmError::NetworkError {
-                message: e.to_string(),
-            })?;
+            .map_err(LlmError::from_transport)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -447,7 +443,11 @@ impl TogetherAiBackend {
 impl LlmBackend for TogetherAiBackend {
     async fn send_message(&self, message: &str) -> Result<String> {
         self.default_executor
-            .execute(|| async { self.send_message_attempt(message).await }, None)
+            .execute(
+                || async { self.send_message_attempt(message).await },
+                None,
+                None,
+            )
             .await
             .map_err(anyhow::Error::new)
     }
@@ -464,6 +464,7 @@ impl LlmBackend for TogetherAiBackend {
                         .await
                 },
                 None,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -477,6 +478,7 @@ impl LlmBackend for TogetherAiBackend {
             .execute(
                 || async { self.send_message_attempt(message).await },
                 event_tx,
+                None,
             )
             .await
             .map_err(anyhow::Error::new)
@@ -499,6 +501,7 @@ impl LlmBackend for TogetherAiBackend {
                             .await
                     },
                     event_tx,
+                    conversation.retry_budget.clone(),
                 )
                 .await;
         }
@@ -510,6 +513,7 @@ impl LlmBackend for TogetherAiBackend {
                         .await
                 },
                 event_tx,
+                conversation.retry_budget.clone(),
             )
             .await
     }
@@ -11,6 +11,7 @@ use crate::{
     AppConfig, BuiltinToolProvider, ConversationStorage, LlmBackend, MessageParser, ToolRegistry,
     console,
 };
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -19,10 +20,13 @@ pub async fn handle_agent(
     backend_name: Option<String>,
     add_dirs: Vec<String>,
     skip_permissions: bool,
+    yolo: bool,
+    dry_run: bool,
     continue_last: bool,
     resume: Option<String>,
     name: Option<String>,
     no_session_persistence: bool,
+    no_preflight: bool,
     mode: Option<String>,
     memory_mode: Option<String>,
     output_format: Option<String>,
@@ -43,21 +47,33 @@ pub async fn handle_agent(
         }
         config.conversation_storage = Some(crate::storage::ConversationStorageMode::Off);
     }
+    config.dry_run = dry_run;
     let config = &config;
+    crate::notification::init(config.notification_mode());
 
     let backend_name = backend_name.unwrap_or_else(|| config.default_backend.clone());
 
     let backend: Box<dyn LlmBackend> = create_backend(&backend_name, config)?;
-    backend.initialize().await?;
+    if let Err(e) = backend.initialize().await {
+        if !no_preflight {
+            anyhow::bail!(
+                "Backend '{}' failed its startup check:\n{:#}\n\nPass --no-preflight to start anyway.",
+                backend_name,
+                e
+            );
+        }
+        console().warning(&format!(
+            "Backend '{}' failed its startup check, continuing (--no-preflight): {:#}",
+            backend_name, e
+        ));
+    }
 
-    let working_dir = if !add_dirs.is_empty() {
+    let mut working_dir = if !add_dirs.is_empty() {
         PathBuf::from(&add_dirs[0])
     } else {
-        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+        AppConfig::project_root().unwrap_or_else(|_| PathBuf::from("."))
     };
 
-    let parser = MessageParser::with_working_directory(working_dir.clone());
-
     let backend_arc = Arc::from(backend);
 
     // Create shared todo state for the session
@@ -75,19 +91,142 @@ pub async fn handle_agent(
         .and_then(|s| s.parse::<MemoryMode>().ok())
         .unwrap_or_else(|| config.memory_mode.unwrap_or_default());
 
+    let storage_mode = config.conversation_storage_mode();
+    let storage_enabled = storage_mode.is_enabled();
+
+    let storage_root = config.conversation_storage_root(&working_dir)?;
+
+    if name.is_some() && !storage_enabled {
+        anyhow::bail!("--name requires conversation_storage to be enabled in config");
+    }
+
+    let continue_conversation_id = if let Some(ref id_or_name) = resume {
+        if !storage_enabled {
+            anyhow::bail!("--resume requires conversation_storage to be enabled in config");
+        }
+        let root = storage_root
+            .clone()
+            .expect("storage enabled implies a storage root");
+        let storage = ConversationStorage::with_root(&root);
+        if storage.conversation_exists(id_or_name) {
+            Some(id_or_name.clone())
+        } else if let Some(meta) = storage.find_by_name(id_or_name)? {
+            Some(meta.id)
+        } else {
+            anyhow::bail!("No conversation found with id or name: {}", id_or_name);
+        }
+    } else if continue_last {
+        if !storage_enabled {
+            console().warning(
+                "Conversation storage is disabled — --continue has no effect. Starting fresh.",
+            );
+            None
+        } else {
+            let root = storage_root
+                .clone()
+                .expect("storage enabled implies a storage root");
+            let storage = ConversationStorage::with_root(&root);
+            let conversations = storage.list_conversations()?;
+
+            if let Some(latest) = conversations.first() {
+                Some(latest.id.clone())
+            } else {
+                console().warning("No previous conversations found. Starting new conversation.");
+                None
+            }
+        }
+    } else if storage_enabled
+        && terminal_mode != TerminalMode::Tagged
+        && std::io::stdin().is_terminal()
+    {
+        let root = storage_root
+            .clone()
+            .expect("storage enabled implies a storage root");
+        offer_stale_session(&ConversationStorage::with_root(&root))?
+    } else {
+        None
+    };
+
+    // A resumed conversation's file references are relative to the directory
+    // it was started in; offer to switch back when that differs from here.
+    let mut conversation_root = None;
+    if let (Some(conv_id), Some(root)) = (&continue_conversation_id, &storage_root)
+        && let Ok(metadata) = ConversationStorage::with_root(root).load_metadata(conv_id)
+        && let Some(original) = metadata.moved_from(&working_dir)
+    {
+        console().warning(&format!(
+            "This conversation was started in {}, not {}.",
+            original.display(),
+            working_dir.display()
+        ));
+        if original.is_dir()
+            && terminal_mode != TerminalMode::Tagged
+            && std::io::stdin().is_terminal()
+            && crate::text_prompts::prompt_yes_no("Switch to the original directory?")?
+        {
+            working_dir = original.to_path_buf();
+            conversation_root = Some(root.clone());
+        }
+    }
+
+    let parser = MessageParser::with_working_directory(working_dir.clone())
+        .with_read_limits(config.read_limits());
+
     let mut tool_registry = ToolRegistry::new().with_provider(Arc::new(
-        BuiltinToolProvider::with_todo_state(working_dir.clone(), todo_state.clone()),
+        BuiltinToolProvider::with_todo_state(working_dir.clone(), todo_state.clone())
+            .with_command_blocklist(Arc::new(config.command_blocklist()))
+            .with_bash_config(Arc::new(config.bash_config()))
+            .with_read_limits(config.read_limits()),
     ));
 
     if resolved_memory_mode == MemoryMode::Summary {
         let _ = tool_registry.register_tool(Arc::new(UpdateSessionFileTool));
     }
 
+    let skill_roots = config.skill_roots(&working_dir)?;
+    if !skill_roots.is_empty() {
+        let _ = tool_registry.register_tool(Arc::new(crate::skill_management::UseSkillTool::new(
+            crate::SkillManager::with_roots(skill_roots),
+        )));
+    }
+
     if let Some(memory_root) = config.memory_storage_root(&working_dir)? {
         let _ =
             tool_registry.register_tool(Arc::new(crate::memory::SaveMemoryTool::new(memory_root)));
     }
 
+    if let (Some(recall), Some(root)) = (config.recall_config(), &storage_root) {
+        match crate::backends::embeddings::create_embedder(config) {
+            Ok(embedder) => {
+                let _ = tool_registry.register_tool(Arc::new(crate::tools::RecallTool::new(
+                    Arc::new(ConversationStorage::with_root(root)),
+                    root.join(crate::storage::EmbeddingIndex::FILE_NAME),
+                    embedder,
+                    recall.top_k(),
+                )));
+            }
+            Err(e) => console().warning(&format!("Recall tool disabled: {}", e)),
+        }
+    }
+
+    // Commit messages are drafted by the title model when one is configured.
+    let commit_message_backend: Arc<dyn LlmBackend> =
+        crate::backends::backend_factory::create_title_backend(&backend_name, config)
+            .map(Arc::from)
+            .unwrap_or_else(|| Arc::clone(&backend_arc));
+    let _ = tool_registry.register_tool(Arc::new(
+        crate::tools::GitCommitTool::new(working_dir.clone())
+            .with_message_backend(commit_message_backend),
+    ));
+
+    #[cfg(feature = "github")]
+    if let Some(token) = config.github_token() {
+        let _ = tool_registry.register_tool(Arc::new(crate::tools::GitHubTool::new(
+            working_dir.clone(),
+            token,
+        )));
+    }
+
     // Handle permissions based on mode
     if !skip_permissions {
         match terminal_mode {
@@ -135,54 +274,6 @@ pub async fn handle_agent(
         .and_then(|s| s.parse::<OutputFormat>().ok())
         .unwrap_or_default();
 
-    let storage_mode = config.conversation_storage_mode();
-    let storage_enabled = storage_mode.is_enabled();
-
-    let storage_root = config.conversation_storage_root(&working_dir)?;
-
-    if name.is_some() && !storage_enabled {
-        anyhow::bail!("--name requires conversation_storage to be enabled in config");
-    }
-
-    let continue_conversation_id = if let Some(ref id_or_name) = resume {
-        if !storage_enabled {
-            anyhow::bail!("--resume requires conversation_storage to be enabled in config");
-        }
-        let root = storage_root
-            .clone()
-            .expect("storage enabled implies a storage root");
-        let storage = ConversationStorage::with_root(&root);
-        if storage.conversation_exists(id_or_name) {
-            Some(id_or_name.clone())
-        } else if let Some(meta) = storage.find_by_name(id_or_name)? {
-            Some(meta.id)
-        } else {
-            anyhow::bail!("No conversation found with id or name: {}", id_or_name);
-        }
-    } else if continue_last {
-        if !storage_enabled {
-            console().warning(
-                "Conversation storage is disabled — --continue has no effect. Starting fresh.",
-            );
-            None
-        } else {
-            let root = storage_root
-                .clone()
-                .expect("storage enabled implies a storage root");
-            let storage = ConversationStorage::with_root(&root);
-            let conversations = storage.list_conversations()?;
-
-            if let Some(latest) = conversations.first() {
-                Some(latest.id.clone())
-            } else {
-                console().warning("No previous conversations found. Starting new conversation.");
-                None
-            }
-        }
-    } else {
-        None
-    };
-
     // Initialize session with all resources
     let session_config = SessionConfig::new(
         Arc::clone(&backend_arc),
@@ -196,10 +287,24 @@ pub async fn handle_agent(
     .with_working_dir(working_dir)
     .with_terminal_mode(Some(terminal_mode))
     .with_memory_mode(resolved_memory_mode)
-    .with_conversation_name(name);
+    .with_conversation_name(name)
+    .with_conversation_root(conversation_root)
+    .with_yolo(yolo);
 
     let session = initialize_session(session_config).await?;
 
+    // Save the conversation and restore the terminal if hoosh panics or is killed
+    crate::session_files::crash::install(session.terminal_mode != TerminalMode::Tagged);
+    let conversation_state = &session.event_loop_context.conversation_state;
+    let _crash_flush = if conversation_state.conversation.lock().await.has_storage() {
+        Some(crate::session_files::crash::guard_conversation(
+            Arc::clone(&conversation_state.conversation),
+            &conversation_state.conversation_id,
+        ))
+    } else {
+        None
+    };
+
     // Prepare message for tagged mode (join all args into single string)
     let message_text = if !message.is_empty() {
         Some(message.join(" "))
@@ -235,3 +340,29 @@ pub async fn handle_agent(
 
     Ok(())
 }
+
+/// Offer to resume a conversation left behind by a session that crashed in a
+/// terminal that has since closed. Conversations stored elsewhere are left for
+/// the project they belong to.
+fn offer_stale_session(storage: &ConversationStorage) -> anyhow::Result<Option<String>> {
+    let stale = crate::session_files::find_stale_sessions().unwrap_or_default();
+    for session in stale {
+        let Ok(metadata) = storage.load_metadata(&session.conversation_id) else {
+            continue;
+        };
+        console().warning(&format!(
+            "A previous session ended unexpectedly in conversation \"{}\" ({}).",
+            metadata.title, metadata.id
+        ));
+        let resume = crate::text_prompts::prompt_yes_no("Resume it?")?;
+        let _ = session.discard();
+        if resume {
+            return Ok(Some(metadata.id));
+        }
+        console().info(&format!(
+            "Starting a new conversation. Resume that one later with `hoosh --resume {}`.",
+            metadata.id
+        ));
+    }
+    Ok(None)
+}
@@ -41,6 +41,8 @@ fn create_custom_agent(name: &str, description: Option<String>) -> Result<()> {
             description,
             tags: vec![],
             core_instructions_file: None,
+            backend: None,
+            model: None,
         },
     );
     config.save()?;
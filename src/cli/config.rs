@@ -25,6 +25,7 @@ impl ConfigKey {
             ("_base_url", "base_url"),
             ("_chat_api", "chat_api"),
             ("_temperature", "temperature"),
+            ("_top_p", "top_p"),
             ("_model", "model"),
             ("_pricing_endpoint", "pricing_endpoint"),
         ];
@@ -43,7 +44,7 @@ impl ConfigKey {
         Err(format!(
             "Unknown config key: {}. Use format: <backend>_<setting> where backend is one of \
              [openai, together_ai, ollama, anthropic] and setting is one of \
-             [api_key, model, base_url, temperature, chat_api, pricing_endpoint]",
+             [api_key, model, base_url, temperature, top_p, chat_api, pricing_endpoint]",
             key
         ))
     }
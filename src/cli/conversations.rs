@@ -1,20 +1,21 @@
 use crate::cli::ConversationsAction;
+use crate::storage::{ConversationMetadata, ExportFormat, Transcript, UsageTotals};
 use crate::{AppConfig, ConversationStorage, console};
+use anyhow::Context;
 use std::path::PathBuf;
 
 pub fn handle_conversations(action: ConversationsAction, config: &AppConfig) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let storage = match config.conversation_storage_root(&cwd)? {
+        Some(root) => ConversationStorage::with_root(&root),
+        None => {
+            console().plain("Conversation storage is disabled (conversation_storage = \"off\").");
+            return Ok(());
+        }
+    };
+
     match action {
         ConversationsAction::List => {
-            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-            let storage = match config.conversation_storage_root(&cwd)? {
-                Some(root) => ConversationStorage::with_root(&root),
-                None => {
-                    console().plain(
-                        "Conversation storage is disabled (conversation_storage = \"off\").",
-                    );
-                    return Ok(());
-                }
-            };
             let conversations = storage.list_conversations()?;
 
             if conversations.is_empty() {
@@ -28,9 +29,139 @@ pub fn handle_conversations(action: ConversationsAction, config: &AppConfig) ->
                     .as_deref()
                     .map(|n| format!("[{}]", n))
                     .unwrap_or_default();
-                console().plain(&format!("{:<25} {:<20} {}", conv.id, label, conv.title));
+                console().plain(&format_list_entry(&conv, &label));
+            }
+        }
+        ConversationsAction::Search { query } => {
+            let hits = storage.search(&query)?;
+            if hits.is_empty() {
+                console().plain(&format!("No conversations mention \"{}\".", query));
+                return Ok(());
+            }
+            for conv in hits {
+                let label = conv
+                    .name
+                    .as_deref()
+                    .map(|n| format!("[{}]", n))
+                    .unwrap_or_default();
+                console().plain(&format_list_entry(&conv, &label));
+            }
+        }
+        ConversationsAction::Stats { id } => {
+            if !storage.conversation_exists(&id) {
+                anyhow::bail!("Conversation '{}' not found", id);
+            }
+            let metadata = storage.load_metadata(&id)?;
+            console().plain(&format_stats(&metadata.id, &metadata.usage_totals()));
+        }
+        ConversationsAction::Export { id, format, output } => {
+            let format: ExportFormat = format.parse()?;
+            let rendered = Transcript::load(&storage, &id)?.render(format)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    console().plain(&format!("Exported {} to {}", id, path.display()));
+                }
+                None => println!("{}", rendered),
             }
         }
+        ConversationsAction::Import { path } => {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let id = Transcript::from_json(&json)?.import(&storage)?;
+            console().plain(&format!("Imported conversation {}", id));
+        }
     }
     Ok(())
 }
+
+fn format_list_entry(conv: &ConversationMetadata, label: &str) -> String {
+    let mut entry = format!("{:<25} {:<20} {}", conv.id, label, conv.title);
+    let model = match (&conv.backend, &conv.model) {
+        (Some(backend), Some(model)) => Some(format!("{} ({})", model, backend)),
+        (Some(name), None) | (None, Some(name)) => Some(name.clone()),
+        (None, None) => None,
+    };
+    let origin: Vec<String> = conv
+        .working_dir
+        .as_ref()
+        .map(|dir| dir.display().to_string())
+        .into_iter()
+        .chain(model)
+        .collect();
+    if !origin.is_empty() {
+        entry.push_str(&format!("\n{:<25} {}", "", origin.join(" · ")));
+    }
+    entry
+}
+
+fn format_stats(id: &str, totals: &UsageTotals) -> String {
+    let cost = if totals.calls == 0 {
+        "$0.0000".to_string()
+    } else if totals.cost_incomplete && totals.cost == 0.0 {
+        "unknown (no pricing for this backend)".to_string()
+    } else if totals.cost_incomplete {
+        format!("at least ${:.4} (some calls had no pricing)", totals.cost)
+    } else {
+        format!("${:.4}", totals.cost)
+    };
+
+    format!(
+        "Conversation: {}\nCalls:        {} ({} by sub-agents)\nTokens:       {} ({} in, {} out)\nCost:         {}",
+        id,
+        totals.calls,
+        totals.subagent_calls,
+        totals.total_tokens(),
+        totals.input_tokens,
+        totals.output_tokens,
+        cost
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_stats_reports_totals_and_cost() {
+        let totals = UsageTotals {
+            input_tokens: 1000,
+            output_tokens: 250,
+            cost: 0.0125,
+            calls: 3,
+            subagent_calls: 1,
+            cost_incomplete: false,
+        };
+        let out = format_stats("conv_1", &totals);
+        assert!(out.contains("Conversation: conv_1"));
+        assert!(out.contains("3 (1 by sub-agents)"));
+        assert!(out.contains("1250 (1000 in, 250 out)"));
+        assert!(out.contains("$0.0125"));
+    }
+
+    #[test]
+    fn format_list_entry_shows_where_and_with_what_it_ran() {
+        let conv = ConversationMetadata::new("conv_1".to_string())
+            .with_title("Fix the parser".to_string())
+            .with_origin(std::path::Path::new("/work/app"), "openai", "gpt-4o");
+        let out = format_list_entry(&conv, "");
+        assert!(out.contains("Fix the parser"));
+        assert!(out.contains("/work/app · gpt-4o (openai)"), "got: {out}");
+
+        let bare = ConversationMetadata::new("conv_2".to_string());
+        assert!(!format_list_entry(&bare, "").contains('\n'));
+    }
+
+    #[test]
+    fn format_stats_flags_unpriced_calls() {
+        let totals = UsageTotals {
+            input_tokens: 10,
+            output_tokens: 5,
+            calls: 1,
+            cost_incomplete: true,
+            ..Default::default()
+        };
+        assert!(format_stats("c", &totals).contains("unknown"));
+    }
+}
@@ -66,13 +66,16 @@ async fn daemon_start(daemon_config: DaemonConfig, app_config: &AppConfig) -> Re
         .with_context(|| format!("Daemon agent '{}' not found", daemon_config.daemon_agent))?;
 
     let config = Arc::new(daemon_config);
-    let executor = Arc::new(JobExecutor::new(
-        Arc::clone(&store),
-        Arc::clone(&config),
-        backend,
-        agent.content,
-        agent.core_instructions,
-    ));
+    let executor = Arc::new(
+        JobExecutor::new(
+            Arc::clone(&store),
+            Arc::clone(&config),
+            backend,
+            agent.content,
+            agent.core_instructions,
+        )
+        .with_tool_config(app_config),
+    );
 
     let server = DaemonServer::new(config, store, executor);
     server.start().await
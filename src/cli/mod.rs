@@ -7,10 +7,12 @@ mod conversations;
 pub mod daemon;
 mod setup;
 pub mod shell_setup;
+mod skills;
 
 use crate::console::VerbosityLevel;
 use crate::memory_mode::MemoryMode;
 use crate::output_format::OutputFormat;
+use crate::storage::ExportFormat;
 use crate::terminal_mode::TerminalMode;
 use clap::builder::PossibleValuesParser;
 use clap::{Parser, Subcommand};
@@ -24,6 +26,7 @@ pub use config::handle_config;
 pub use conversations::handle_conversations;
 pub use daemon::handle_daemon;
 pub use setup::handle_setup;
+pub use skills::handle_skills;
 
 #[derive(Parser)]
 #[command(author, version, about)]
@@ -56,6 +59,16 @@ pub struct Cli {
     #[arg(long)]
     pub skip_permissions: bool,
 
+    /// With --skip-permissions, also run destructive commands without the
+    /// last-chance confirmation
+    #[arg(long, requires = "skip_permissions")]
+    pub yolo: bool,
+
+    /// Simulate tools that would change files or run mutating commands,
+    /// returning their preview to the model instead
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Continue the last conversation
     #[arg(long = "continue")]
     pub continue_last: bool,
@@ -85,6 +98,10 @@ pub struct Cli {
     #[arg(long = "no-session-persistence")]
     pub no_session_persistence: bool,
 
+    /// Start even if the backend health check fails (offline or mock use)
+    #[arg(long = "no-preflight")]
+    pub no_preflight: bool,
+
     /// Message to send (for tagged mode non-interactive use)
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     pub message: Vec<String>,
@@ -115,6 +132,10 @@ pub enum Commands {
         #[command(subcommand)]
         action: AliasAction,
     },
+    Skills {
+        #[command(subcommand)]
+        action: SkillsAction,
+    },
     Setup,
     Daemon {
         #[command(subcommand)]
@@ -152,6 +173,27 @@ pub enum DaemonAction {
 #[derive(Subcommand)]
 pub enum ConversationsAction {
     List,
+    /// Find conversations whose title or messages mention a phrase
+    Search {
+        query: String,
+    },
+    /// Print total tokens and cost recorded for a conversation
+    Stats {
+        id: String,
+    },
+    /// Export a conversation as a JSON transcript or readable markdown
+    Export {
+        id: String,
+        #[arg(long, value_parser = PossibleValuesParser::new(ExportFormat::VARIANTS), default_value = "json")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Recreate a conversation from a JSON transcript
+    Import {
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -175,6 +217,11 @@ pub enum CommandAction {
     ReinstallBuiltins,
 }
 
+#[derive(Subcommand)]
+pub enum SkillsAction {
+    List,
+}
+
 #[derive(Subcommand)]
 pub enum AliasAction {
     Install,
@@ -221,4 +268,15 @@ mod tests {
         let cli = Cli::try_parse_from(["hoosh", "hello"]).unwrap();
         assert!(!cli.no_session_persistence);
     }
+
+    #[test]
+    fn skills_list_subcommand_parses() {
+        let cli = Cli::try_parse_from(["hoosh", "skills", "list"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Skills {
+                action: SkillsAction::List
+            })
+        ));
+    }
 }
@@ -0,0 +1,35 @@
+use crate::cli::SkillsAction;
+use crate::{AppConfig, SkillManager, console};
+use std::path::PathBuf;
+
+pub fn handle_skills(action: SkillsAction, config: &AppConfig) -> anyhow::Result<()> {
+    match action {
+        SkillsAction::List => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            let roots = config.skill_roots(&cwd)?;
+            if roots.is_empty() {
+                console().plain("Skill discovery is disabled (skill_mode = \"off\").");
+                return Ok(());
+            }
+
+            let manager = SkillManager::with_roots(roots);
+            let skills = manager.discover_skills()?;
+
+            if skills.is_empty() {
+                console().plain("No skills found.");
+                return Ok(());
+            }
+
+            for skill in skills {
+                let description = if skill.description.is_empty() {
+                    "(no description)"
+                } else {
+                    skill.description.as_str()
+                };
+                console().plain(&format!("{:<25} {}", skill.name, description));
+                console().plain(&format!("{:<25} {}", "", skill.entry_point().display()));
+            }
+        }
+    }
+    Ok(())
+}
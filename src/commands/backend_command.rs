@@ -174,11 +174,19 @@ mod tests {
                 base_url: None,
                 chat_api: None,
                 temperature: None,
+                top_p: None,
                 pricing_endpoint: None,
                 thinking_budget: None,
                 reasoning_effort: None,
                 reasoning_display: None,
                 streaming: None,
+                context_window: None,
+                title_model: None,
+                max_concurrent_requests: None,
+                requests_per_minute: None,
+                retry: None,
+                tool_api: None,
+                stop: None,
             },
         );
         let mut ctx = CommandContext::new().with_config(config);
@@ -209,11 +217,19 @@ mod tests {
                 base_url: None,
                 chat_api: None,
                 temperature: None,
+                top_p: None,
                 pricing_endpoint: None,
                 thinking_budget: None,
                 reasoning_effort: None,
                 reasoning_display: None,
                 streaming: None,
+                context_window: None,
+                title_model: None,
+                max_concurrent_requests: None,
+                requests_per_minute: None,
+                retry: None,
+                tool_api: None,
+                stop: None,
             },
         );
         config.backends.insert(
@@ -224,11 +240,19 @@ mod tests {
                 base_url: None,
                 chat_api: None,
                 temperature: None,
+                top_p: None,
                 pricing_endpoint: None,
                 thinking_budget: None,
                 reasoning_effort: None,
                 reasoning_display: None,
                 streaming: None,
+                context_window: None,
+                title_model: None,
+                max_concurrent_requests: None,
+                requests_per_minute: None,
+                retry: None,
+                tool_api: None,
+                stop: None,
             },
         );
         let mut ctx = CommandContext::new().with_config(config);
@@ -0,0 +1,238 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::{ConversationMessage, Role};
+
+const DEFAULT_INSTRUCTIONS: &str = "Summarize the conversation below so it can replace the \
+     full history. Keep the user's goals, decisions made and why, files touched, open \
+     questions, and the next steps. Be concise; omit pleasantries and tool output that no \
+     longer matters.";
+
+/// Tool results longer than this are cut before being sent to the summarizer.
+const MAX_TOOL_RESULT_CHARS: usize = 2000;
+
+/// Runs the configured context management strategies instead of summarizing,
+/// e.g. with `trigger = "manual"`.
+const STRATEGIES_FLAG: &str = "--strategies";
+
+pub struct CompactCommand;
+
+fn build_transcript(messages: &[ConversationMessage]) -> String {
+    let mut transcript = String::new();
+    for message in messages.iter().filter(|m| m.role != Role::System) {
+        let content = message.content.as_deref().unwrap_or("");
+        match message.role {
+            Role::User => transcript.push_str(&format!("User: {content}\n\n")),
+            Role::Assistant => {
+                if !content.is_empty() {
+                    transcript.push_str(&format!("Assistant: {content}\n\n"));
+                }
+                for call in message.tool_calls.iter().flatten() {
+                    transcript.push_str(&format!(
+                        "Assistant called {}({})\n\n",
+                        call.function.name, call.function.arguments
+                    ));
+                }
+            }
+            Role::Tool => {
+                let mut result: String = content.chars().take(MAX_TOOL_RESULT_CHARS).collect();
+                if result.len() < content.len() {
+                    result.push_str("\n[truncated]");
+                }
+                let name = message.name.as_deref().unwrap_or("tool");
+                transcript.push_str(&format!("Result of {name}: {result}\n\n"));
+            }
+            Role::System => {}
+        }
+    }
+    transcript
+}
+
+fn build_prompt(transcript: &str, focus: &str) -> String {
+    let mut prompt = DEFAULT_INSTRUCTIONS.to_string();
+    if !focus.is_empty() {
+        prompt.push_str(&format!("\n\nAdditional focus from the user: {focus}"));
+    }
+    prompt.push_str(&format!("\n\n<conversation>\n{transcript}</conversation>"));
+    prompt
+}
+
+#[async_trait]
+impl Command for CompactCommand {
+    fn name(&self) -> &str {
+        "compact"
+    }
+
+    fn description(&self) -> &str {
+        "Summarize the conversation so far to free up context"
+    }
+
+    fn usage(&self) -> &str {
+        "/compact [focus instructions]\n\n\
+         Replaces the conversation history with a summary written by the current backend.\n\
+         Anything after the command tells the summarizer what to keep, e.g.\n\
+         `/compact keep all decisions about the auth module`.\n\
+         The summary is pinned: context management never drops it.\n\
+         `/compact --strategies` instead runs the [context_manager] strategies now, in\n\
+         their configured order; use it with `trigger = \"manual\"`."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("Conversation not available"))?;
+
+        if args.first().map(String::as_str) == Some(STRATEGIES_FLAG) {
+            let context_manager = context
+                .context_manager
+                .as_ref()
+                .ok_or_else(|| anyhow!("Context management is not enabled"))?;
+            let mut conversation = conversation.lock().await;
+            let before = conversation.estimate_token();
+            context_manager.apply_strategies(&mut conversation).await?;
+            conversation.compact_storage_if_needed();
+            let after = conversation.estimate_token();
+            return Ok(CommandResult::Success(format!(
+                "Context strategies applied: ~{before} → ~{after} tokens."
+            )));
+        }
+
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("Backend not available"))?;
+
+        let transcript = build_transcript(&conversation.lock().await.messages);
+        if transcript.is_empty() {
+            return Ok(CommandResult::Success(
+                "Nothing to compact yet.".to_string(),
+            ));
+        }
+
+        let prompt = build_prompt(&transcript, args.join(" ").trim());
+        let summary = backend
+            .send_message(&prompt)
+            .await
+            .map_err(|e| anyhow!("Failed to summarize conversation: {e}"))?;
+
+        let replaced = conversation.lock().await.replace_with_summary(&summary);
+        Ok(CommandResult::Success(format!(
+            "Compacted {replaced} message{} into a summary.",
+            if replaced == 1 { "" } else { "s" }
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use crate::backends::mock::MockBackend;
+    use std::sync::Arc;
+
+    fn context_with(conversation: Conversation) -> CommandContext {
+        CommandContext::new()
+            .with_conversation(Arc::new(tokio::sync::Mutex::new(conversation)))
+            .with_backend(Arc::new(MockBackend::new()))
+    }
+
+    #[test]
+    fn prompt_includes_focus_only_when_given() {
+        assert!(!build_prompt("User: hi\n\n", "").contains("Additional focus"));
+        assert!(
+            build_prompt("User: hi\n\n", "keep auth decisions")
+                .contains("Additional focus from the user: keep auth decisions")
+        );
+    }
+
+    #[tokio::test]
+    async fn compacts_into_pinned_summary_with_focus() {
+        let mut conversation = Conversation::new();
+        conversation.add_system_message("system".to_string());
+        conversation.add_user_message("refactor the auth module".to_string());
+        conversation.add_assistant_message(Some("done".to_string()), None);
+        let mut ctx = context_with(conversation);
+
+        let result = CompactCommand
+            .execute(
+                vec!["keep".into(), "auth".into(), "decisions".into()],
+                &mut ctx,
+            )
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(result, CommandResult::Success(msg) if msg == "Compacted 2 messages into a summary.")
+        );
+        let conv = ctx.conversation.unwrap();
+        let conv = conv.lock().await;
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[0].role, Role::System);
+        assert!(conv.messages[1].is_pinned_summary());
+        let summary = conv.messages[1].content.as_deref().unwrap();
+        assert!(summary.contains("Additional focus from the user: keep auth decisions"));
+        assert!(summary.contains("User: refactor the auth module"));
+    }
+
+    #[tokio::test]
+    async fn strategies_flag_runs_the_context_manager_instead_of_summarizing() {
+        use crate::context_management::{
+            CompactionTrigger, ContextManager, ContextManagerConfig, TokenAccountant,
+            ToolOutputTruncationConfig, ToolOutputTruncationStrategy,
+        };
+
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("run it".to_string());
+        conversation.messages.push(ConversationMessage {
+            role: Role::Tool,
+            content: Some("x".repeat(10_000)),
+            tool_calls: None,
+            tool_call_id: Some("call_1".to_string()),
+            name: Some("bash".to_string()),
+            attachments: Vec::new(),
+        });
+        let manager = ContextManager::new(
+            ContextManagerConfig::default().with_trigger(CompactionTrigger::Manual),
+            Arc::new(TokenAccountant::new()),
+        )
+        .add_strategy(Box::new(ToolOutputTruncationStrategy::new(
+            ToolOutputTruncationConfig {
+                preserve_last_tool_result: false,
+                ..Default::default()
+            },
+        )));
+        let mut ctx = context_with(conversation).with_context_manager(Arc::new(manager));
+
+        let result = CompactCommand
+            .execute(vec![STRATEGIES_FLAG.to_string()], &mut ctx)
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(result, CommandResult::Success(msg) if msg.starts_with("Context strategies applied"))
+        );
+        let conv = ctx.conversation.unwrap();
+        let conv = conv.lock().await;
+        assert_eq!(conv.messages.len(), 2);
+        assert!(conv.messages[1].content.as_ref().unwrap().len() < 10_000);
+        assert!(!conv.messages.iter().any(|m| m.is_pinned_summary()));
+    }
+
+    #[tokio::test]
+    async fn empty_conversation_is_left_alone() {
+        let mut conversation = Conversation::new();
+        conversation.add_system_message("system".to_string());
+        let mut ctx = context_with(conversation);
+
+        let result = CompactCommand.execute(vec![], &mut ctx).await.unwrap();
+
+        assert!(matches!(result, CommandResult::Success(msg) if msg == "Nothing to compact yet."));
+        assert_eq!(ctx.conversation.unwrap().lock().await.messages.len(), 1);
+    }
+}
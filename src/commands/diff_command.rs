@@ -0,0 +1,180 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+use crate::tools::file_ops::diff_preview::{run_git, working_tree_diff};
+
+pub struct DiffCommand;
+
+#[async_trait]
+impl Command for DiffCommand {
+    fn name(&self) -> &str {
+        "diff"
+    }
+
+    fn description(&self) -> &str {
+        "Show uncommitted changes"
+    }
+
+    fn usage(&self) -> &str {
+        "/diff [path]\n\n\
+         Shows every change since the last commit, staged or not, including new untracked\n\
+         files. Give a file or directory to limit the diff to it."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let working_dir = if context.working_directory.is_empty() {
+            PathBuf::from(".")
+        } else {
+            PathBuf::from(&context.working_directory)
+        };
+        let scope = args.first().map(String::as_str);
+
+        if run_git(&working_dir, &["rev-parse", "--is-inside-work-tree"])
+            .await
+            .is_err()
+        {
+            return Ok(CommandResult::Success(
+                "Not in a git repository, so there is nothing to compare against.".to_string(),
+            ));
+        }
+
+        let diff = working_tree_diff(&working_dir, scope.as_slice()).await?;
+        if diff.trim().is_empty() {
+            return Ok(CommandResult::Success(match scope {
+                Some(path) => format!("No uncommitted changes to {}.", path),
+                None => "No uncommitted changes.".to_string(),
+            }));
+        }
+
+        let files = diff
+            .lines()
+            .filter(|line| line.starts_with("diff --git "))
+            .count();
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::ShowDiff(diff))
+            .map_err(|e| anyhow!("Failed to show diff: {e}"))?;
+
+        Ok(CommandResult::Success(format!(
+            "{} file{} changed.",
+            files,
+            if files == 1 { "" } else { "s" }
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+    }
+
+    fn repo_with_commit() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-b", "main"]);
+        std::fs::write(dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "alpha\nbeta\ngamma\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Initial commit"]);
+        dir
+    }
+
+    async fn run(dir: &Path, args: &[&str]) -> (CommandResult, Option<String>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_working_directory(dir.to_string_lossy().into_owned())
+            .with_event_sender(tx);
+        let args = args.iter().map(|a| a.to_string()).collect();
+        let result = DiffCommand.execute(args, &mut ctx).await.unwrap();
+        let diff = match rx.try_recv() {
+            Ok(AgentEvent::ShowDiff(diff)) => Some(diff),
+            _ => None,
+        };
+        (result, diff)
+    }
+
+    fn message(result: CommandResult) -> String {
+        match result {
+            CommandResult::Success(msg) => msg,
+            _ => panic!("expected success"),
+        }
+    }
+
+    #[tokio::test]
+    async fn shows_modified_untracked_and_binary_files() {
+        let dir = repo_with_commit();
+        std::fs::write(dir.path().join("a.txt"), "one\nzwei\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "fresh\n").unwrap();
+        std::fs::write(dir.path().join("logo.png"), [0x89, b'P', b'N', b'G', 0, 1]).unwrap();
+
+        let (result, diff) = run(dir.path(), &[]).await;
+        let diff = diff.expect("expected a diff event");
+
+        assert!(diff.contains("-two\n+zwei\n"));
+        assert!(diff.contains("--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1 @@\n+fresh\n"));
+        assert!(diff.contains("Binary files /dev/null and b/logo.png differ\n"));
+        assert_eq!(message(result), "3 files changed.");
+    }
+
+    #[tokio::test]
+    async fn scopes_to_a_path() {
+        let dir = repo_with_commit();
+        std::fs::write(dir.path().join("a.txt"), "one\nzwei\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "alpha\n").unwrap();
+
+        let (result, diff) = run(dir.path(), &["b.txt"]).await;
+        let diff = diff.expect("expected a diff event");
+
+        assert!(diff.contains("b/b.txt"));
+        assert!(!diff.contains("a.txt"));
+        assert_eq!(message(result), "1 file changed.");
+    }
+
+    #[tokio::test]
+    async fn reports_renames() {
+        let dir = repo_with_commit();
+        git(dir.path(), &["mv", "b.txt", "c.txt"]);
+
+        let (_, diff) = run(dir.path(), &[]).await;
+        let diff = diff.expect("expected a diff event");
+
+        assert!(diff.contains("rename from b.txt\nrename to c.txt"));
+    }
+
+    #[tokio::test]
+    async fn clean_tree_and_no_repository_send_no_diff() {
+        let dir = repo_with_commit();
+        let (result, diff) = run(dir.path(), &["a.txt"]).await;
+        assert_eq!(message(result), "No uncommitted changes to a.txt.");
+        assert!(diff.is_none());
+
+        let plain = TempDir::new().unwrap();
+        let (result, diff) = run(plain.path(), &[]).await;
+        assert!(message(result).starts_with("Not in a git repository"));
+        assert!(diff.is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::str::FromStr;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+use crate::config::ReasoningEffort;
+
+pub struct EffortCommand;
+
+fn level_names() -> String {
+    let mut names: Vec<&str> = ReasoningEffort::ALL.iter().map(|e| e.as_str()).collect();
+    names.push("off");
+    names.join(", ")
+}
+
+/// `Ok(None)` for `off`, `Err` for anything that isn't a level.
+fn parse_level(arg: &str) -> Result<Option<ReasoningEffort>, ()> {
+    match arg.to_ascii_lowercase().as_str() {
+        "off" | "none" => Ok(None),
+        other => ReasoningEffort::from_str(other).map(Some),
+    }
+}
+
+#[async_trait]
+impl Command for EffortCommand {
+    fn name(&self) -> &str {
+        "effort"
+    }
+
+    fn description(&self) -> &str {
+        "Show or change how hard the model reasons"
+    }
+
+    fn usage(&self) -> &str {
+        "/effort [level] [--save]\n\n\
+         With no argument: prints the active backend's reasoning settings.\n\
+         With a level (low, medium, high, xhigh, max, off): applies it for the rest of the session.\n\
+         OpenAI-compatible backends get it as `reasoning_effort`; Anthropic gets a\n\
+         matching extended-thinking budget. Other backends ignore it.\n\
+         With --save: also writes the choice to the config file."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active backend"))?;
+
+        let save = args.iter().any(|a| a == "--save");
+        let Some(level) = args.iter().find(|a| *a != "--save") else {
+            let backend_config = context
+                .config
+                .as_ref()
+                .and_then(|c| c.get_backend_config(backend.backend_name()));
+            let effort = backend_config
+                .and_then(|c| c.reasoning_effort)
+                .map_or("unset", |e| e.as_str());
+            let budget = backend_config
+                .and_then(|c| c.thinking_budget)
+                .map_or("unset".to_string(), |b| format!("{b} tokens"));
+            return Ok(CommandResult::Success(format!(
+                "Backend: {}\nReasoning effort: {}\nThinking budget: {}\n\nUsage: /effort <{}> [--save]",
+                backend.backend_name(),
+                effort,
+                budget,
+                level_names()
+            )));
+        };
+
+        let Ok(effort) = parse_level(level) else {
+            return Ok(CommandResult::Success(format!(
+                "Unknown effort level '{}'. Choose one of: {}",
+                level,
+                level_names()
+            )));
+        };
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::SetReasoningEffort { effort, save })
+            .map_err(|e| anyhow!("Failed to dispatch effort change: {e}"))?;
+
+        Ok(CommandResult::Success(format!(
+            "Setting reasoning effort to {}…",
+            effort.map_or("off", |e| e.as_str())
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels_and_off() {
+        assert_eq!(parse_level("high"), Ok(Some(ReasoningEffort::High)));
+        assert_eq!(parse_level("Low"), Ok(Some(ReasoningEffort::Low)));
+        assert_eq!(parse_level("off"), Ok(None));
+        assert_eq!(parse_level("extreme"), Err(()));
+    }
+
+    #[tokio::test]
+    async fn dispatches_effort_change() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(std::sync::Arc::new(crate::backends::MockBackend::new()))
+            .with_event_sender(tx);
+
+        EffortCommand
+            .execute(vec!["medium".into(), "--save".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AgentEvent::SetReasoningEffort {
+                effort: Some(ReasoningEffort::Medium),
+                save: true
+            }
+        ));
+    }
+}
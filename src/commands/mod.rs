@@ -1,15 +1,27 @@
 mod agents_command;
 mod backend_command;
 mod clear_command;
+mod compact_command;
 pub mod custom;
+mod diff_command;
+mod effort_command;
 mod exit_command;
 mod help_command;
 mod model_command;
 mod permissions_command;
+mod raw_command;
+mod regenerate_command;
 mod register;
 mod registry;
 mod rename_command;
+mod run_command;
+mod sampling_command;
 mod status_command;
+mod stop_command;
+mod switch_agent_command;
+mod tasks_command;
+mod title_command;
+mod tokens_command;
 mod tools_command;
 mod untrust_command;
 
@@ -2,6 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 
 use super::registry::{Command, CommandContext, CommandResult};
+use crate::permissions::storage::PermissionRule;
 
 pub struct PermissionsCommand;
 
@@ -16,7 +17,7 @@ impl Command for PermissionsCommand {
     }
 
     fn usage(&self) -> &str {
-        "/permissions [list|reset] - View or manage permissions"
+        "/permissions [list|remove <n>|toggle <n>|reset] - View or manage permissions"
     }
 
     async fn execute(
@@ -35,14 +36,13 @@ impl Command for PermissionsCommand {
             "list" => {
                 let mut output = String::from("Current permissions:\n\n");
 
-                // Check if there's a permissions file loaded
-                let perms_info = permission_manager.get_permissions_info();
-                if perms_info.allow_count > 0 || perms_info.deny_count > 0 {
-                    output.push_str("✓ Permissions loaded from storage\n");
-                    output.push_str(&format!("  Allow rules: {}\n", perms_info.allow_count));
-                    output.push_str(&format!("  Deny rules: {}\n", perms_info.deny_count));
-                } else {
+                let (allow, deny) = permission_manager.rules();
+                if allow.is_empty() && deny.is_empty() {
                     output.push_str("✗ No persistent permissions saved\n");
+                } else {
+                    output.push_str("✓ Permissions loaded from storage\n");
+                    output.push_str(&format_rules("Deny", &deny, 1));
+                    output.push_str(&format_rules("Allow", &allow, deny.len() + 1));
                 }
 
                 if permission_manager.skip_permissions() {
@@ -53,7 +53,24 @@ impl Command for PermissionsCommand {
 
                 Ok(CommandResult::Success(output))
             }
-            "reset" => {
+            "remove" | "toggle" => {
+                let Some(index) = args.get(1).and_then(|n| n.parse::<usize>().ok()) else {
+                    return Ok(CommandResult::Success(format!(
+                        "Usage: /permissions {} <n> (rule numbers are shown by /permissions list)",
+                        subcommand
+                    )));
+                };
+                let message = if subcommand == "remove" {
+                    let (rule, _) = permission_manager.remove_rule(index)?;
+                    format!("Removed rule {}: {}", index, rule)
+                } else {
+                    let (rule, allowed) = permission_manager.toggle_rule(index)?;
+                    let now = if allowed { "allow" } else { "deny" };
+                    format!("Rule {} now set to {}", rule, now)
+                };
+                Ok(CommandResult::Success(message))
+            }
+            "reset" | "clear" => {
                 permission_manager.clear_all_permissions()?;
                 Ok(CommandResult::Success(
                     "All permissions cleared. Future operations will require approval.".to_string(),
@@ -67,3 +84,34 @@ impl Command for PermissionsCommand {
         }
     }
 }
+
+/// Rules are listed in the order they are checked, deny before allow, and
+/// numbered for `remove` and `toggle`.
+fn format_rules(heading: &str, rules: &[PermissionRule], first_index: usize) -> String {
+    let mut out = format!("\n{} rules ({}):\n", heading, rules.len());
+    for (index, rule) in (first_index..).zip(rules) {
+        match &rule.reason {
+            Some(reason) => out.push_str(&format!("  {}. {} - {}\n", index, rule, reason)),
+            None => out.push_str(&format!("  {}. {}\n", index, rule)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_listed_with_patterns_and_reasons() {
+        let rules = vec![
+            PermissionRule::ops_rule("bash", "git status"),
+            PermissionRule::ops_rule("write_file", "/secrets/**").with_reason("never"),
+        ];
+
+        assert_eq!(
+            format_rules("Deny", &rules, 3),
+            "\nDeny rules (2):\n  3. bash(git status)\n  4. write_file(/secrets/**) - never\n"
+        );
+    }
+}
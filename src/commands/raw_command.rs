@@ -0,0 +1,95 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+
+pub struct RawCommand;
+
+#[async_trait]
+impl Command for RawCommand {
+    fn name(&self) -> &str {
+        "raw"
+    }
+
+    fn description(&self) -> &str {
+        "Toggle plain-text display of assistant replies"
+    }
+
+    fn usage(&self) -> &str {
+        "/raw [on|off]\n\n\
+         Shows assistant replies verbatim instead of rendering markdown, for\n\
+         faithful copy-paste or output the renderer mangles. With no argument,\n\
+         toggles. In fullview mode earlier replies switch too; in inline mode\n\
+         text already in the scrollback stays as it was printed.\n\
+         Set `raw_output = true` in the config to start in this mode."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let enabled = match args.first().map(|a| a.to_ascii_lowercase()).as_deref() {
+            None => None,
+            Some("on") => Some(true),
+            Some("off") => Some(false),
+            Some(other) => {
+                return Ok(CommandResult::Success(format!(
+                    "Unknown argument '{}'. Usage: /raw [on|off]",
+                    other
+                )));
+            }
+        };
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::SetRawOutput(enabled))
+            .map_err(|e| anyhow!("Failed to toggle raw output: {e}"))?;
+
+        Ok(CommandResult::Success(
+            match enabled {
+                Some(true) => "Assistant replies are now shown as raw text.",
+                Some(false) => "Assistant replies are now rendered as markdown.",
+                None => "Toggled raw output. Use `/raw on` or `/raw off` to set it explicitly.",
+            }
+            .to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn dispatched(args: Vec<String>) -> Option<AgentEvent> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new().with_event_sender(tx);
+        RawCommand.execute(args, &mut ctx).await.unwrap();
+        rx.try_recv().ok()
+    }
+
+    #[tokio::test]
+    async fn toggles_without_argument() {
+        assert!(matches!(
+            dispatched(vec![]).await,
+            Some(AgentEvent::SetRawOutput(None))
+        ));
+    }
+
+    #[tokio::test]
+    async fn explicit_on_and_off() {
+        assert!(matches!(
+            dispatched(vec!["on".into()]).await,
+            Some(AgentEvent::SetRawOutput(Some(true)))
+        ));
+        assert!(matches!(
+            dispatched(vec!["OFF".into()]).await,
+            Some(AgentEvent::SetRawOutput(Some(false)))
+        ));
+        assert!(dispatched(vec!["maybe".into()]).await.is_none());
+    }
+}
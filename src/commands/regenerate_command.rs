@@ -0,0 +1,98 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+
+pub struct RegenerateCommand;
+
+#[async_trait]
+impl Command for RegenerateCommand {
+    fn name(&self) -> &str {
+        "regenerate"
+    }
+
+    fn description(&self) -> &str {
+        "Generate an alternate answer to the last prompt"
+    }
+
+    fn usage(&self) -> &str {
+        "/regenerate\n\n\
+         Asks the agent to answer the last prompt again, keeping the earlier answer.\n\
+         alt+left / alt+right switch between the answers; whichever is shown when you\n\
+         send the next prompt is the one the conversation keeps. The others are not saved."
+    }
+
+    async fn execute(
+        &self,
+        _args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("Conversation not available"))?;
+
+        if !conversation.lock().await.begin_regeneration() {
+            return Ok(CommandResult::Success(
+                "Nothing to regenerate yet.".to_string(),
+            ));
+        }
+        Ok(CommandResult::RunAgent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use std::sync::Arc;
+
+    fn contents(conversation: &Conversation) -> Vec<Option<&str>> {
+        conversation
+            .messages
+            .iter()
+            .map(|m| m.content.as_deref())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn selected_variant_is_what_the_next_prompt_follows() {
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("name a colour".to_string());
+        conversation.add_assistant_message(Some("red".to_string()), None);
+        let conversation = Arc::new(tokio::sync::Mutex::new(conversation));
+        let mut ctx = CommandContext::new().with_conversation(Arc::clone(&conversation));
+
+        let result = RegenerateCommand.execute(vec![], &mut ctx).await.unwrap();
+        assert!(matches!(result, CommandResult::RunAgent));
+
+        let mut conv = conversation.lock().await;
+        assert_eq!(contents(&conv), vec![Some("name a colour")]);
+        // The agent's second answer.
+        conv.add_assistant_message(Some("blue".to_string()), None);
+
+        assert_eq!(conv.cycle_reply_variant(false), Some((1, 2)));
+        assert_eq!(contents(&conv), vec![Some("name a colour"), Some("red")]);
+
+        conv.add_user_message_with_file_mentions("why?".to_string(), vec![], vec![]);
+        assert_eq!(
+            contents(&conv),
+            vec![Some("name a colour"), Some("red"), Some("why?")]
+        );
+        // The choice is committed; the other answer is gone.
+        assert_eq!(conv.cycle_reply_variant(true), None);
+    }
+
+    #[tokio::test]
+    async fn nothing_to_regenerate_without_a_reply() {
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("hello".to_string());
+        let mut ctx = CommandContext::new()
+            .with_conversation(Arc::new(tokio::sync::Mutex::new(conversation)));
+
+        let result = RegenerateCommand.execute(vec![], &mut ctx).await.unwrap();
+        assert!(
+            matches!(result, CommandResult::Success(msg) if msg == "Nothing to regenerate yet.")
+        );
+    }
+}
@@ -4,14 +4,26 @@ use std::sync::Arc;
 use super::agents_command::AgentsCommand;
 use super::backend_command::BackendCommand;
 use super::clear_command::ClearCommand;
+use super::compact_command::CompactCommand;
 use super::custom::CustomCommandManager;
+use super::diff_command::DiffCommand;
+use super::effort_command::EffortCommand;
 use super::exit_command::ExitCommand;
 use super::help_command::HelpCommand;
 use super::model_command::ModelCommand;
 use super::permissions_command::PermissionsCommand;
+use super::raw_command::RawCommand;
+use super::regenerate_command::RegenerateCommand;
 use super::registry::CommandRegistry;
 use super::rename_command::RenameCommand;
+use super::run_command::RunCommand;
+use super::sampling_command::SamplingCommand;
 use super::status_command::StatusCommand;
+use super::stop_command::StopCommand;
+use super::switch_agent_command::SwitchAgentCommand;
+use super::tasks_command::TasksCommand;
+use super::title_command::TitleCommand;
+use super::tokens_command::TokensCommand;
 use super::tools_command::ToolsCommand;
 use super::untrust_command::UntrustCommand;
 
@@ -21,12 +33,25 @@ pub fn register_default_commands(registry: &mut CommandRegistry) -> Result<()> {
     registry.register(Arc::new(StatusCommand))?;
     registry.register(Arc::new(ToolsCommand))?;
     registry.register(Arc::new(AgentsCommand))?;
+    registry.register(Arc::new(SwitchAgentCommand))?;
     registry.register(Arc::new(ExitCommand))?;
     registry.register(Arc::new(UntrustCommand))?;
     registry.register(Arc::new(PermissionsCommand))?;
     registry.register(Arc::new(RenameCommand))?;
+    registry.register(Arc::new(TitleCommand))?;
     registry.register(Arc::new(BackendCommand))?;
     registry.register(Arc::new(ModelCommand))?;
+    registry.register(Arc::new(EffortCommand))?;
+    registry.register(Arc::new(SamplingCommand::temperature()))?;
+    registry.register(Arc::new(SamplingCommand::top_p()))?;
+    registry.register(Arc::new(StopCommand))?;
+    registry.register(Arc::new(TasksCommand))?;
+    registry.register(Arc::new(CompactCommand))?;
+    registry.register(Arc::new(TokensCommand))?;
+    registry.register(Arc::new(RawCommand))?;
+    registry.register(Arc::new(DiffCommand))?;
+    registry.register(Arc::new(RunCommand))?;
+    registry.register(Arc::new(RegenerateCommand))?;
     Ok(())
 }
 
@@ -7,6 +7,7 @@ use crate::agent::Conversation;
 use crate::agent_definition::AgentDefinitionManager;
 use crate::config::AppConfig;
 use crate::context_management::ContextManager;
+use crate::tool_executor::ToolExecutor;
 use crate::tools::ToolRegistry;
 
 #[derive(Debug, Clone)]
@@ -29,6 +30,7 @@ pub struct CommandContext {
     pub config: Option<AppConfig>,
     pub backend: Option<Arc<dyn crate::backends::LlmBackend>>,
     pub context_manager: Option<Arc<ContextManager>>,
+    pub tool_executor: Option<Arc<ToolExecutor>>,
 }
 
 impl CommandContext {
@@ -45,6 +47,7 @@ impl CommandContext {
             config: None,
             backend: None,
             context_manager: None,
+            tool_executor: None,
         }
     }
 
@@ -108,6 +111,11 @@ impl CommandContext {
         self.context_manager = Some(context_manager);
         self
     }
+
+    pub fn with_tool_executor(mut self, tool_executor: Arc<ToolExecutor>) -> Self {
+        self.tool_executor = Some(tool_executor);
+        self
+    }
 }
 
 impl Default for CommandContext {
@@ -124,6 +132,11 @@ pub trait Command: Send + Sync {
         Vec::new()
     }
     fn usage(&self) -> &str;
+    /// Pass everything after the command name to `execute` as one argument,
+    /// with its spacing and quotes intact, instead of splitting it into words.
+    fn takes_raw_args(&self) -> bool {
+        false
+    }
     async fn execute(
         &self,
         args: Vec<String>,
@@ -166,25 +179,29 @@ impl CommandRegistry {
             return Err(anyhow!("Command must start with '/'"));
         }
 
-        let parts: Vec<String> = input[1..]
+        let body = input[1..].trim_start();
+        let cmd_name = body
             .split_whitespace()
-            .map(|s| s.to_string())
-            .collect();
+            .next()
+            .ok_or_else(|| anyhow!("Empty command"))?
+            .to_string();
+        let rest = body[cmd_name.len()..].trim_start();
 
-        if parts.is_empty() {
-            return Err(anyhow!("Empty command"));
-        }
-
-        let cmd_name = &parts[0];
-        let args = parts[1..].to_vec();
-
-        let resolved_name = self.aliases.get(cmd_name).unwrap_or(cmd_name);
+        let resolved_name = self.aliases.get(&cmd_name).unwrap_or(&cmd_name);
 
         let command = self
             .commands
             .get(resolved_name)
             .ok_or_else(|| anyhow!("Unknown command: {}", cmd_name))?;
 
+        let args = if !command.takes_raw_args() {
+            rest.split_whitespace().map(|s| s.to_string()).collect()
+        } else if rest.is_empty() {
+            Vec::new()
+        } else {
+            vec![rest.to_string()]
+        };
+
         command.execute(args, context).await
     }
 
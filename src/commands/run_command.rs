@@ -0,0 +1,211 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::{ToolCall, ToolFunction};
+use crate::tools::ToolError;
+
+/// `/run`: run a shell command and add its output to the conversation as
+/// context, without starting a turn.
+pub struct RunCommand;
+
+const USAGE: &str = "/run <command>\n\n\
+                     Runs the command with the bash tool and adds its output to the conversation\n\
+                     as context from you. The agent doesn't reply until your next message.\n\
+                     The command goes through the usual permission checks, and output larger\n\
+                     than read_limits.max_bytes keeps only its last lines.";
+
+/// Keep the last whole lines of `output` that fit in `max_bytes`, noting
+/// how much was dropped. Command output usually ends with what matters
+/// (the failing test, the exit code).
+fn cap_output(output: &str, max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return output.to_string();
+    }
+    let mut start = output.len() - max_bytes;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    if let Some(newline) = output[start..].find('\n') {
+        start += newline + 1;
+    }
+    format!("[{} earlier bytes omitted]\n{}", start, &output[start..])
+}
+
+#[async_trait]
+impl Command for RunCommand {
+    fn name(&self) -> &str {
+        "run"
+    }
+
+    fn description(&self) -> &str {
+        "Run a shell command and add its output as context"
+    }
+
+    fn usage(&self) -> &str {
+        USAGE
+    }
+
+    fn takes_raw_args(&self) -> bool {
+        true
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        if args.is_empty() {
+            return Ok(CommandResult::Success(format!(
+                "Usage: {}",
+                USAGE.lines().next().unwrap_or_default()
+            )));
+        }
+        // The registry passes the command line as typed, in one piece.
+        let command = args.join(" ");
+
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("Conversation not available"))?;
+        let tool_executor = context
+            .tool_executor
+            .as_ref()
+            .ok_or_else(|| anyhow!("Tool executor not available"))?;
+
+        let call = ToolCall {
+            id: format!("run_{}", uuid::Uuid::new_v4()),
+            r#type: "function".to_string(),
+            function: ToolFunction {
+                name: "bash".to_string(),
+                arguments: serde_json::json!({
+                    "command": command,
+                    "description": "Runs a command the user asked for with /run",
+                })
+                .to_string(),
+            },
+        };
+        let output = match tool_executor
+            .execute_tool_call(&call, None, None)
+            .await
+            .result
+        {
+            Ok(output) => output,
+            Err(ToolError::PermissionDenied { .. }) => {
+                return Ok(CommandResult::Success(format!(
+                    "Not run: permission denied for `{command}`."
+                )));
+            }
+            Err(e) => {
+                return Ok(CommandResult::Success(format!(
+                    "`{command}` failed: {e}\nNothing was added to the conversation."
+                )));
+            }
+        };
+
+        let max_bytes = context
+            .config
+            .clone()
+            .unwrap_or_default()
+            .read_limits()
+            .max_bytes;
+        let output = cap_output(&output, max_bytes);
+        let fence = if output.contains("```") { "~~~" } else { "```" };
+        conversation.lock().await.add_user_message(format!(
+            "[Context from the user: output of `{command}`, run with /run]\n\
+             {fence}\n{}\n{fence}",
+            output.trim_end()
+        ));
+
+        Ok(CommandResult::Success(format!(
+            "Added the output of `{command}` to the conversation:\n\n{}",
+            output.trim_end()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use crate::permissions::PermissionManager;
+    use crate::tool_executor::ToolExecutor;
+    use crate::tools::{BashTool, ToolRegistry};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    fn context_with_bash(skip_permissions: bool) -> (CommandContext, Arc<Mutex<Conversation>>) {
+        let mut registry = ToolRegistry::new();
+        registry.register_tool(Arc::new(BashTool::new())).unwrap();
+        let (event_tx, _) = tokio::sync::mpsc::unbounded_channel();
+        let (_, response_rx) = tokio::sync::mpsc::unbounded_channel();
+        let permission_manager =
+            PermissionManager::new(event_tx, response_rx).with_skip_permissions(skip_permissions);
+        let executor = ToolExecutor::new(Arc::new(registry), Arc::new(permission_manager));
+
+        let conversation = Arc::new(Mutex::new(Conversation::new()));
+        let context = CommandContext::new()
+            .with_conversation(Arc::clone(&conversation))
+            .with_tool_executor(Arc::new(executor));
+        (context, conversation)
+    }
+
+    #[tokio::test]
+    async fn output_lands_in_the_conversation() {
+        let (mut context, conversation) = context_with_bash(true);
+
+        let result = RunCommand
+            .execute(vec!["echo".into(), "seeded-context".into()], &mut context)
+            .await
+            .unwrap();
+
+        assert!(matches!(result, CommandResult::Success(msg) if msg.contains("seeded-context")));
+        let conversation = conversation.lock().await;
+        assert_eq!(conversation.messages.len(), 1);
+        let message = &conversation.messages[0];
+        assert_eq!(message.role, crate::agent::Role::User);
+        let content = message.content.as_deref().unwrap();
+        assert!(content.starts_with("[Context from the user: output of `echo seeded-context`"));
+        assert!(content.contains("seeded-context\nExit code: 0"));
+    }
+
+    #[tokio::test]
+    async fn command_line_reaches_bash_as_typed() {
+        let (mut context, conversation) = context_with_bash(true);
+        let mut registry = crate::commands::CommandRegistry::new();
+        registry.register(Arc::new(RunCommand)).unwrap();
+
+        registry
+            .execute("/run  echo 'a   b'\tc", &mut context)
+            .await
+            .unwrap();
+
+        let conversation = conversation.lock().await;
+        let content = conversation.messages[0].content.as_deref().unwrap();
+        assert!(content.starts_with("[Context from the user: output of `echo 'a   b'\tc`"));
+        assert!(content.contains("a   b c\n"));
+    }
+
+    #[tokio::test]
+    async fn denied_command_adds_nothing() {
+        let (mut context, conversation) = context_with_bash(false);
+
+        // Nobody answers the prompt, so the permission check fails.
+        RunCommand
+            .execute(vec!["touch".into(), "denied.txt".into()], &mut context)
+            .await
+            .unwrap();
+
+        assert!(conversation.lock().await.messages.is_empty());
+    }
+
+    #[test]
+    fn cap_output_keeps_the_last_lines() {
+        let output = "first line\nsecond line\nthird line\n";
+        assert_eq!(cap_output(output, 100), output);
+        assert_eq!(
+            cap_output(output, 16),
+            "[23 earlier bytes omitted]\nthird line\n"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+
+/// `/temp` and `/top_p`: adjust one sampling parameter of the active backend.
+pub struct SamplingCommand {
+    name: &'static str,
+    key: &'static str,
+    description: &'static str,
+    usage: &'static str,
+}
+
+impl SamplingCommand {
+    pub fn temperature() -> Self {
+        Self {
+            name: "temp",
+            key: "temperature",
+            description: "Show or change the sampling temperature",
+            usage: "/temp [value] [--save]\n\n\
+                    With no argument: prints the active backend's temperature.\n\
+                    With a value from 0 to 2: applies it to the following turns.\n\
+                    With --save: also writes it to the config file.\n\
+                    Ignored while extended thinking or a reasoning effort is active.",
+        }
+    }
+
+    pub fn top_p() -> Self {
+        Self {
+            name: "top_p",
+            key: "top_p",
+            description: "Show or change nucleus sampling (top_p)",
+            usage: "/top_p [value] [--save]\n\n\
+                    With no argument: prints the active backend's top_p.\n\
+                    With a value from 0 to 1: applies it to the following turns.\n\
+                    With --save: also writes it to the config file.\n\
+                    Ignored while extended thinking or a reasoning effort is active.",
+        }
+    }
+}
+
+#[async_trait]
+impl Command for SamplingCommand {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn usage(&self) -> &str {
+        self.usage
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active backend"))?;
+        let backend_name = backend.backend_name().to_string();
+        let config = context.config.clone().unwrap_or_default();
+
+        let save = args.iter().any(|a| a == "--save");
+        let Some(value) = args.iter().find(|a| *a != "--save") else {
+            let current = config
+                .get_backend_config(&backend_name)
+                .and_then(|c| match self.key {
+                    "temperature" => c.temperature,
+                    _ => c.top_p,
+                });
+            return Ok(CommandResult::Success(format!(
+                "Backend: {}\n{}: {}\n\nUsage: {}",
+                backend_name,
+                self.key,
+                current.map_or("provider default".to_string(), |v| v.to_string()),
+                self.usage.lines().next().unwrap_or_default()
+            )));
+        };
+
+        // Validate against a scratch copy so bad input is reported right away.
+        if let Err(e) =
+            config
+                .clone()
+                .update_backend_setting(&backend_name, self.key, value.clone())
+        {
+            return Ok(CommandResult::Success(e.to_string()));
+        }
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::SetBackendSetting {
+                key: self.key.to_string(),
+                value: value.clone(),
+                save,
+            })
+            .map_err(|e| anyhow!("Failed to dispatch {} change: {e}", self.key))?;
+
+        Ok(CommandResult::Success(format!(
+            "Setting {} to {}…",
+            self.key, value
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MockBackend;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn dispatches_valid_value() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_event_sender(tx);
+
+        SamplingCommand::top_p()
+            .execute(vec!["0.9".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            AgentEvent::SetBackendSetting { key, value, save } => {
+                assert_eq!(key, "top_p");
+                assert_eq!(value, "0.9");
+                assert!(!save);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_value() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_event_sender(tx);
+
+        SamplingCommand::temperature()
+            .execute(vec!["3".into(), "--save".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        assert!(rx.try_recv().is_err());
+    }
+}
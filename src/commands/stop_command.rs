@@ -0,0 +1,192 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+use crate::config::{MAX_STOP_SEQUENCES, validate_stop_sequences};
+
+/// `/stop`: set the active backend's stop sequences for the session.
+pub struct StopCommand;
+
+const USAGE: &str = "/stop [sequence...] [--clear] [--save]\n\n\
+                     With no argument: prints the active backend's stop sequences.\n\
+                     With sequences: generation ends when the model emits any of them.\n\
+                     Write \\n for a newline and \\t for a tab. At most four sequences.\n\
+                     With --clear: removes all stop sequences.\n\
+                     With --save: also writes the change to the config file.";
+
+/// Expand `\n`, `\t` and `\\`, since arguments are split on whitespace.
+fn unescape(arg: &str) -> String {
+    let mut out = String::with_capacity(arg.len());
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => {
+                if other != '\\' {
+                    out.push('\\');
+                }
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[async_trait]
+impl Command for StopCommand {
+    fn name(&self) -> &str {
+        "stop"
+    }
+
+    fn description(&self) -> &str {
+        "Show or change the stop sequences"
+    }
+
+    fn usage(&self) -> &str {
+        USAGE
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active backend"))?;
+        let backend_name = backend.backend_name().to_string();
+        let config = context.config.clone().unwrap_or_default();
+
+        let save = args.iter().any(|a| a == "--save");
+        let clear = args.iter().any(|a| a == "--clear");
+        let stop: Vec<String> = args
+            .iter()
+            .filter(|a| *a != "--save" && *a != "--clear")
+            .map(|a| unescape(a))
+            .collect();
+
+        if stop.is_empty() && !clear {
+            let current = config
+                .get_backend_config(&backend_name)
+                .and_then(|c| c.stop.clone())
+                .unwrap_or_default();
+            let listed = if current.is_empty() {
+                "none".to_string()
+            } else {
+                current
+                    .iter()
+                    .map(|s| format!("{s:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            return Ok(CommandResult::Success(format!(
+                "Backend: {}\nstop: {}\n\nUsage: {}",
+                backend_name,
+                listed,
+                USAGE.lines().next().unwrap_or_default()
+            )));
+        }
+
+        if clear && !stop.is_empty() {
+            return Ok(CommandResult::Success(
+                "Pass either sequences or --clear, not both".to_string(),
+            ));
+        }
+        if validate_stop_sequences(&stop).is_err() {
+            return Ok(CommandResult::Success(format!(
+                "Too many stop sequences: {} given, at most {MAX_STOP_SEQUENCES} allowed",
+                stop.len()
+            )));
+        }
+
+        let value = serde_json::to_string(&stop)?;
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::SetBackendSetting {
+                key: "stop".to_string(),
+                value: value.clone(),
+                save,
+            })
+            .map_err(|e| anyhow!("Failed to dispatch stop change: {e}"))?;
+
+        Ok(CommandResult::Success(if clear {
+            "Clearing stop sequences…".to_string()
+        } else {
+            format!("Setting stop to {value}…")
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MockBackend;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn dispatches_unescaped_sequences() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_event_sender(tx);
+
+        StopCommand
+            .execute(vec!["</answer>".into(), r"\nUser:".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            AgentEvent::SetBackendSetting { key, value, save } => {
+                assert_eq!(key, "stop");
+                assert_eq!(value, r#"["</answer>","\nUser:"]"#);
+                assert!(!save);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_too_many_sequences() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_event_sender(tx);
+
+        let args = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        StopCommand.execute(args, &mut ctx).await.unwrap();
+
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn clear_dispatches_empty_list() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = CommandContext::new()
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_event_sender(tx);
+
+        StopCommand
+            .execute(vec!["--clear".into(), "--save".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            AgentEvent::SetBackendSetting { value, save, .. } => {
+                assert_eq!(value, "[]");
+                assert!(save);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+
+pub struct SwitchAgentCommand;
+
+#[async_trait]
+impl Command for SwitchAgentCommand {
+    fn name(&self) -> &str {
+        "switch-agent"
+    }
+
+    fn description(&self) -> &str {
+        "Switch to another agent, and to its backend and model"
+    }
+
+    fn usage(&self) -> &str {
+        "/switch-agent <name>\n\n\
+         Makes <name> the active agent for the rest of the session. If the agent\n\
+         sets `backend` or `model` in its config, the session moves to them;\n\
+         otherwise it uses the global default backend and model.\n\
+         Use /agents to list the available agents."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let config = context.config.clone().unwrap_or_default();
+
+        let Some(name) = args.first() else {
+            return Ok(CommandResult::Success(format!(
+                "Current agent: {}\n\nUsage: /switch-agent <name>",
+                context.current_agent_name.as_deref().unwrap_or("none")
+            )));
+        };
+
+        if !config.agents.contains_key(name) {
+            let mut available: Vec<&str> = config.agents.keys().map(|s| s.as_str()).collect();
+            available.sort_unstable();
+            return Ok(CommandResult::Success(format!(
+                "Unknown agent '{}'. Available agents: {}",
+                name,
+                available.join(", ")
+            )));
+        }
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        event_tx
+            .send(AgentEvent::SwitchAgent { name: name.clone() })
+            .map_err(|e| anyhow!("Failed to dispatch agent switch: {e}"))?;
+
+        Ok(CommandResult::Success(format!(
+            "Switching to agent '{}'…",
+            name
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    fn context_with_agents(tx: tokio::sync::mpsc::UnboundedSender<AgentEvent>) -> CommandContext {
+        let config: AppConfig = toml::from_str(
+            "default_backend = \"mock\"\n\
+             [agents.reviewer]\nfile = \"reviewer.txt\"\nmodel = \"strong-model\"",
+        )
+        .unwrap();
+        CommandContext::new()
+            .with_config(config)
+            .with_event_sender(tx)
+    }
+
+    #[tokio::test]
+    async fn dispatches_switch_for_known_agent() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = context_with_agents(tx);
+
+        SwitchAgentCommand
+            .execute(vec!["reviewer".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            AgentEvent::SwitchAgent { name } => assert_eq!(name, "reviewer"),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_agent() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut ctx = context_with_agents(tx);
+
+        let result = SwitchAgentCommand
+            .execute(vec!["nobody".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        assert!(rx.try_recv().is_err());
+        match result {
+            CommandResult::Success(msg) => assert!(msg.contains("reviewer")),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::AgentEvent;
+
+pub struct TasksCommand;
+
+#[async_trait]
+impl Command for TasksCommand {
+    fn name(&self) -> &str {
+        "tasks"
+    }
+
+    fn description(&self) -> &str {
+        "Check off task-list items in the latest response"
+    }
+
+    fn usage(&self) -> &str {
+        "/tasks\n\n\
+         Opens the `- [ ]` items from the assistant's latest response.\n\
+         Up/Down to move, Space to toggle, Esc or Enter to close.\n\
+         Toggles edit the message itself, so the model sees them next turn."
+    }
+
+    async fn execute(
+        &self,
+        _args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("Conversation not available"))?;
+
+        let Some((message_index, items)) = conversation.lock().await.latest_task_list() else {
+            return Ok(CommandResult::Success(
+                "The latest response has no task list.".to_string(),
+            ));
+        };
+
+        let event_tx = context
+            .event_tx
+            .as_ref()
+            .ok_or_else(|| anyhow!("Event channel not available"))?;
+        let count = items.len();
+        event_tx
+            .send(AgentEvent::ShowTaskList {
+                message_index,
+                items,
+            })
+            .map_err(|e| anyhow!("Failed to open task list: {e}"))?;
+
+        Ok(CommandResult::Success(format!(
+            "Opened {count} task-list item{}.",
+            if count == 1 { "" } else { "s" }
+        )))
+    }
+}
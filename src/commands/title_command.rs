@@ -0,0 +1,112 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::title;
+use crate::backends::LlmBackend;
+
+pub struct TitleCommand;
+
+#[async_trait]
+impl Command for TitleCommand {
+    fn name(&self) -> &str {
+        "title"
+    }
+
+    fn description(&self) -> &str {
+        "Regenerate or set the title of the current conversation"
+    }
+
+    fn usage(&self) -> &str {
+        "/title\n/title <text>\n\nWith no argument: generates a new title from the first message, \
+         using the backend's `title_model` if one is configured. With text: uses it as the title."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active conversation"))?;
+
+        if !args.is_empty() {
+            let new_title = args.join(" ");
+            conversation.lock().await.set_title(new_title.clone());
+            return Ok(CommandResult::Success(format!("Title: {}", new_title)));
+        }
+
+        let Some(first_message) =
+            title::first_user_message(&*conversation.lock().await).map(str::to_string)
+        else {
+            return Ok(CommandResult::Success(
+                "Nothing to title yet: the conversation has no messages.".to_string(),
+            ));
+        };
+
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active backend"))?;
+        let title_backend: Arc<dyn LlmBackend> = context
+            .config
+            .as_ref()
+            .and_then(|config| {
+                crate::backends::backend_factory::create_title_backend(
+                    backend.backend_name(),
+                    config,
+                )
+            })
+            .map(Arc::from)
+            .unwrap_or_else(|| Arc::clone(backend));
+
+        let (new_title, error) =
+            title::title_or_fallback(title_backend.as_ref(), &first_message).await;
+        conversation.lock().await.set_title(new_title.clone());
+
+        Ok(CommandResult::Success(match error {
+            None => format!("Title: {}", new_title),
+            Some(e) => format!(
+                "Title: {}\n(title generation failed, used the first message: {})",
+                new_title, e
+            ),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use crate::backends::MockBackend;
+
+    #[tokio::test]
+    async fn explicit_title_is_set() {
+        let conversation = Arc::new(tokio::sync::Mutex::new(Conversation::new()));
+        let mut ctx = CommandContext::new().with_conversation(Arc::clone(&conversation));
+
+        TitleCommand
+            .execute(vec!["Release".into(), "notes".into()], &mut ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(conversation.lock().await.title(), "Release notes");
+    }
+
+    #[tokio::test]
+    async fn regenerates_from_first_message() {
+        let mut conv = Conversation::new();
+        conv.add_user_message("Explain lifetimes".to_string());
+        let conversation = Arc::new(tokio::sync::Mutex::new(conv));
+        let mut ctx = CommandContext::new()
+            .with_conversation(Arc::clone(&conversation))
+            .with_backend(Arc::new(MockBackend::new()));
+
+        TitleCommand.execute(vec![], &mut ctx).await.unwrap();
+
+        assert!(!conversation.lock().await.title().is_empty());
+    }
+}
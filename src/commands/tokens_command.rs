@@ -0,0 +1,240 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::registry::{Command, CommandContext, CommandResult};
+use crate::agent::Conversation;
+use crate::context_management::{LARGE_TOOL_RESULT_TOKENS, TokenAccountant};
+
+/// Tool results listed by `/tokens tools`.
+const TOP_TOOL_RESULTS: usize = 15;
+
+pub struct TokensCommand;
+
+#[async_trait]
+impl Command for TokensCommand {
+    fn name(&self) -> &str {
+        "tokens"
+    }
+
+    fn description(&self) -> &str {
+        "Show context usage against the model's context window"
+    }
+
+    fn usage(&self) -> &str {
+        "/tokens [tools]\n\n\
+         Shows the estimated size of the conversation, the model's context window,\n\
+         and the budget context management compacts against.\n\
+         Set `context_window` in the backend's config to correct the window.\n\n\
+         /tokens tools ranks the tool results in the conversation by their size."
+    }
+
+    async fn execute(
+        &self,
+        args: Vec<String>,
+        context: &mut CommandContext,
+    ) -> Result<CommandResult> {
+        let conversation = context
+            .conversation
+            .as_ref()
+            .ok_or_else(|| anyhow!("Conversation not available"))?;
+
+        match args.first().map(String::as_str) {
+            None => {}
+            Some("tools") => {
+                let conversation = conversation.lock().await;
+                return Ok(CommandResult::Success(tool_report(&conversation)));
+            }
+            Some(other) => {
+                return Ok(CommandResult::Success(format!(
+                    "Unknown argument '{}'. Usage: /tokens [tools]",
+                    other
+                )));
+            }
+        }
+        let backend = context
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("Backend not available"))?;
+
+        let used = {
+            let conversation = conversation.lock().await;
+            match &context.context_manager {
+                Some(context_manager) => context_manager
+                    .token_accountant
+                    .context_tokens(&conversation),
+                None => conversation.estimate_token(),
+            }
+        };
+        let window = context
+            .config
+            .clone()
+            .unwrap_or_default()
+            .context_window(backend.as_ref());
+
+        let mut report = String::from("Token Usage\n\n");
+        report.push_str(&format!("Conversation:   ~{} tokens\n", used));
+        report.push_str(&format!(
+            "Context window: {} tokens ({})\n",
+            window,
+            backend.model_name()
+        ));
+
+        if let Some(context_manager) = &context.context_manager {
+            let budget = context_manager.config.max_tokens;
+            let percent = used as f64 / budget.max(1) as f64 * 100.0;
+            report.push_str(&format!(
+                "Budget:         {} tokens, {:.0}% used (warning at {:.0}%)\n",
+                budget,
+                percent,
+                context_manager.config.warning_threshold * 100.0
+            ));
+        }
+
+        Ok(CommandResult::Success(report))
+    }
+}
+
+fn tool_report(conversation: &Conversation) -> String {
+    let mut results = TokenAccountant::tool_result_tokens(conversation);
+    if results.is_empty() {
+        return "No tool results in this conversation yet.".to_string();
+    }
+    results.sort_by_key(|r| std::cmp::Reverse(r.tokens));
+
+    let total = conversation.estimate_token().max(1);
+    let tool_total: usize = results.iter().map(|r| r.tokens).sum();
+    let mut report = format!(
+        "Tool Results by Size\n\n{} results, ~{} tokens ({:.0}% of the conversation)\n\n",
+        results.len(),
+        tool_total,
+        tool_total as f64 / total as f64 * 100.0
+    );
+    for result in results.iter().take(TOP_TOOL_RESULTS) {
+        report.push_str(&format!(
+            "~{:>7} tokens {:>4.0}%  {} (message {}){}\n",
+            result.tokens,
+            result.tokens as f64 / total as f64 * 100.0,
+            result.tool_name,
+            result.message_index,
+            if result.tokens >= LARGE_TOOL_RESULT_TOKENS {
+                "  <- consider truncating"
+            } else {
+                ""
+            }
+        ));
+    }
+    if results.len() > TOP_TOOL_RESULTS {
+        report.push_str(&format!(
+            "... and {} smaller\n",
+            results.len() - TOP_TOOL_RESULTS
+        ));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use crate::backends::context_window::FALLBACK_CONTEXT_WINDOW;
+    use crate::backends::mock::MockBackend;
+    use crate::config::{AppConfig, BackendConfig};
+    use std::sync::Arc;
+
+    fn context(config: AppConfig) -> CommandContext {
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("x".repeat(400));
+        CommandContext::new()
+            .with_conversation(Arc::new(tokio::sync::Mutex::new(conversation)))
+            .with_backend(Arc::new(MockBackend::new()))
+            .with_config(config)
+    }
+
+    async fn report(config: AppConfig) -> String {
+        match TokensCommand
+            .execute(vec![], &mut context(config))
+            .await
+            .unwrap()
+        {
+            CommandResult::Success(msg) => msg,
+            _ => panic!("expected success message"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_model_uses_fallback_window() {
+        let msg = report(AppConfig::default()).await;
+
+        assert!(msg.contains("Conversation:   ~"));
+        assert!(msg.contains(&format!(
+            "Context window: {} tokens (mock-model)",
+            FALLBACK_CONTEXT_WINDOW
+        )));
+    }
+
+    #[tokio::test]
+    async fn configured_window_overrides_fallback() {
+        let mut config = AppConfig::default();
+        config.backends.insert(
+            "mock".into(),
+            BackendConfig {
+                api_key: None,
+                model: None,
+                base_url: None,
+                chat_api: None,
+                temperature: None,
+                top_p: None,
+                pricing_endpoint: None,
+                thinking_budget: None,
+                reasoning_effort: None,
+                reasoning_display: None,
+                streaming: None,
+                context_window: Some(64_000),
+                title_model: None,
+                max_concurrent_requests: None,
+                requests_per_minute: None,
+                retry: None,
+                tool_api: None,
+                stop: None,
+            },
+        );
+
+        let msg = report(config).await;
+        assert!(msg.contains("Context window: 64000 tokens (mock-model)"));
+    }
+
+    #[tokio::test]
+    async fn tools_view_ranks_results_by_size() {
+        use crate::agent::ToolCallResponse;
+
+        let mut conversation = Conversation::new();
+        for (id, name, output) in [
+            ("c1", "list", "a.rs".to_string()),
+            ("c2", "read_file", "x".repeat(40_000)),
+            ("c3", "grep", "y".repeat(400)),
+        ] {
+            conversation.add_tool_result(ToolCallResponse::success(
+                id.to_string(),
+                name.to_string(),
+                name.to_string(),
+                output,
+            ));
+        }
+        let mut context = CommandContext::new()
+            .with_conversation(Arc::new(tokio::sync::Mutex::new(conversation)));
+
+        let CommandResult::Success(msg) = TokensCommand
+            .execute(vec!["tools".to_string()], &mut context)
+            .await
+            .unwrap()
+        else {
+            panic!("expected success message");
+        };
+
+        let read = msg.find("read_file").unwrap();
+        let grep = msg.find("grep").unwrap();
+        let list = msg.find("list (message 0)").unwrap();
+        assert!(read < grep && grep < list, "got: {msg}");
+        assert!(msg.contains("consider truncating"));
+    }
+}
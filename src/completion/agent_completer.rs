@@ -0,0 +1,164 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{Completer, token_end};
+use crate::agent_definition::AgentDefinition;
+
+const SWITCH_AGENT_COMMAND: &str = "/switch-agent";
+
+/// Completes agent names after `/switch-agent `. It triggers on the space
+/// that ends the command word, so it never fires anywhere else in a prompt.
+pub struct AgentCompleter {
+    agents: Vec<(String, String)>,
+}
+
+impl AgentCompleter {
+    pub fn new(agents: Vec<AgentDefinition>) -> Self {
+        let mut agents: Vec<(String, String)> = agents
+            .into_iter()
+            .map(|agent| {
+                let description = agent
+                    .description
+                    .filter(|d| !d.is_empty())
+                    .unwrap_or_else(|| "(no description)".to_string());
+                (agent.name, description)
+            })
+            .collect();
+        agents.sort();
+        Self { agents }
+    }
+}
+
+#[async_trait]
+impl Completer for AgentCompleter {
+    fn trigger_key(&self) -> char {
+        ' '
+    }
+
+    fn should_trigger(&self, input_before_trigger: &str) -> bool {
+        input_before_trigger == SWITCH_AGENT_COMMAND
+    }
+
+    async fn get_completions(&self, query: &str) -> Result<Vec<String>> {
+        let query = query.to_lowercase();
+        let mut matches: Vec<&(String, String)> = self
+            .agents
+            .iter()
+            .filter(|(name, _)| name.to_lowercase().contains(&query))
+            .collect();
+        // Prefix matches first; `agents` is already sorted by name.
+        matches.sort_by_key(|(name, _)| !name.to_lowercase().starts_with(&query));
+
+        Ok(matches
+            .into_iter()
+            .map(|(name, description)| format!("{} - {}", name, description))
+            .collect())
+    }
+
+    fn find_trigger_position(&self, input: &str, cursor: usize) -> Option<usize> {
+        let trigger = SWITCH_AGENT_COMMAND.len();
+        let name = input.get(trigger + 1..cursor)?;
+        (input.get(..trigger) == Some(SWITCH_AGENT_COMMAND)
+            && input[trigger..].starts_with(' ')
+            && !name.contains(char::is_whitespace))
+        .then_some(trigger)
+    }
+
+    fn apply_completion(
+        &self,
+        input: &str,
+        trigger_pos: usize,
+        completion: &str,
+    ) -> (String, usize) {
+        let name = completion.split(" - ").next().unwrap_or(completion);
+        let head = &input[..=trigger_pos];
+        let tail = &input[token_end(input, trigger_pos + 1)..];
+        (format!("{}{}{}", head, name, tail), head.len() + name.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::completion::completion_query;
+
+    fn agent(name: &str, description: Option<&str>) -> AgentDefinition {
+        AgentDefinition {
+            name: name.to_string(),
+            content: String::new(),
+            file: format!("{}.txt", name),
+            description: description.map(str::to_string),
+            tags: Vec::new(),
+            core_instructions: String::new(),
+            backend: None,
+            model: None,
+        }
+    }
+
+    fn completer() -> AgentCompleter {
+        AgentCompleter::new(vec![
+            agent("reviewer", Some("Reviews diffs")),
+            agent("coder", Some("Writes code")),
+            agent("planner", None),
+        ])
+    }
+
+    #[test]
+    fn triggers_only_after_the_switch_agent_command() {
+        let completer = completer();
+        assert!(completer.should_trigger("/switch-agent"));
+        assert!(!completer.should_trigger("/switch"));
+        assert!(!completer.should_trigger("please /switch-agent"));
+        assert!(!completer.should_trigger("hello"));
+    }
+
+    #[tokio::test]
+    async fn lists_agents_with_descriptions() {
+        let completions = completer().get_completions("").await.unwrap();
+        assert_eq!(
+            completions,
+            vec![
+                "coder - Writes code",
+                "planner - (no description)",
+                "reviewer - Reviews diffs",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn prefix_matches_come_first() {
+        let completions = completer().get_completions("r").await.unwrap();
+        assert_eq!(
+            completions,
+            vec![
+                "reviewer - Reviews diffs",
+                "coder - Writes code",
+                "planner - (no description)",
+            ]
+        );
+    }
+
+    #[test]
+    fn query_is_the_name_typed_after_the_command() {
+        let completer = completer();
+        let input = "/switch-agent rev";
+        let trigger = completer.find_trigger_position(input, input.len()).unwrap();
+        assert_eq!(completion_query(input, trigger, input.len()), "rev");
+
+        assert_eq!(
+            completer.find_trigger_position("/switch-agent a b", 17),
+            None
+        );
+        assert_eq!(completer.find_trigger_position("/agents rev", 11), None);
+    }
+
+    #[test]
+    fn applying_replaces_the_partial_name() {
+        let completer = completer();
+        let input = "/switch-agent rev";
+        let trigger = completer.find_trigger_position(input, input.len()).unwrap();
+        let (text, cursor) = completer.apply_completion(input, trigger, "reviewer - Reviews diffs");
+        assert_eq!(text, "/switch-agent reviewer");
+        assert_eq!(cursor, text.len());
+    }
+}
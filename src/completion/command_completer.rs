@@ -36,6 +36,47 @@ impl CommandCompleter {
         current_pattern_char.is_none()
     }
 
+    /// Tolerates a small typo (one edit for short queries, two for longer ones)
+    /// against the start of the target, so `hepl` still finds `help`.
+    fn typo_match(pattern: &str, target: &str) -> bool {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        if pattern.len() < 3 {
+            return false;
+        }
+
+        let target: Vec<char> = target.to_lowercase().chars().collect();
+        let prefix = &target[..pattern.len().min(target.len())];
+        let allowed = if pattern.len() <= 4 { 1 } else { 2 };
+
+        Self::edit_distance(&pattern, prefix) <= allowed
+    }
+
+    /// Optimal string alignment distance: insertions, deletions, substitutions
+    /// and adjacent transpositions each cost one.
+    fn edit_distance(a: &[char], b: &[char]) -> usize {
+        let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in d[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                d[i][j] = (d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1)
+                    .min(d[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+
+        d[a.len()][b.len()]
+    }
+
     fn score_match(pattern: &str, target: &str) -> i32 {
         let pattern_lower = pattern.to_lowercase();
         let target_lower = target.to_lowercase();
@@ -57,8 +98,11 @@ impl CommandCompleter {
             // Contains match gets lower score
             score += 300;
         } else if Self::fuzzy_match(pattern, target) {
-            // Fuzzy match gets lowest score
+            // Fuzzy match gets lower score
             score += 100;
+        } else if Self::typo_match(pattern, target) {
+            // Typo match gets lowest score
+            score += 50;
         }
 
         // Penalize longer targets (prefer shorter command names)
@@ -86,7 +130,7 @@ impl Completer for CommandCompleter {
         let mut matches: Vec<(String, i32)> = commands
             .iter()
             .filter_map(|(name, desc)| {
-                if Self::fuzzy_match(query, name) {
+                if Self::fuzzy_match(query, name) || Self::typo_match(query, name) {
                     let score = Self::score_match(query, name);
                     Some((format!("{} - {}", name, desc), score))
                 } else {
@@ -95,7 +139,9 @@ impl Completer for CommandCompleter {
             })
             .collect();
 
-        matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+        // Registry order is unspecified; break score ties by name so the
+        // popup is stable between keystrokes.
+        matches.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
         Ok(matches.into_iter().map(|(text, _)| text).collect())
     }
@@ -104,16 +150,35 @@ impl Completer for CommandCompleter {
         item.to_string()
     }
 
-    fn apply_completion(&self, input: &str, trigger_pos: usize, completion: &str) -> String {
+    fn apply_completion(
+        &self,
+        input: &str,
+        trigger_pos: usize,
+        completion: &str,
+    ) -> (String, usize) {
         // Extract just the command name before " - " from the completion
         let command_name = completion.split(" - ").next().unwrap_or(completion);
-        format!("{}{}", &input[..=trigger_pos], command_name)
+        super::replace_token(input, trigger_pos, command_name)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::register_default_commands;
+
+    fn default_completer() -> CommandCompleter {
+        let mut registry = CommandRegistry::new();
+        register_default_commands(&mut registry).unwrap();
+        CommandCompleter::new(Arc::new(registry))
+    }
+
+    fn names(completions: &[String]) -> Vec<&str> {
+        completions
+            .iter()
+            .map(|c| c.split(" - ").next().unwrap())
+            .collect()
+    }
 
     #[test]
     fn test_fuzzy_match() {
@@ -152,4 +217,57 @@ mod tests {
                 >= CommandCompleter::score_match("ex", "export")
         );
     }
+
+    #[test]
+    fn test_typo_match() {
+        assert!(CommandCompleter::typo_match("hepl", "help"));
+        assert!(CommandCompleter::typo_match("stauts", "status"));
+        assert!(CommandCompleter::typo_match("exot", "exit"));
+        assert!(!CommandCompleter::typo_match("he", "ha"));
+        assert!(!CommandCompleter::typo_match("zzzz", "help"));
+    }
+
+    #[tokio::test]
+    async fn empty_query_lists_all_commands() {
+        let completer = default_completer();
+        let completions = completer.get_completions("").await.unwrap();
+        assert_eq!(completions.len(), completer.registry.list_commands().len());
+    }
+
+    #[tokio::test]
+    async fn subsequence_query_finds_command() {
+        let completer = default_completer();
+        let completions = completer.get_completions("prms").await.unwrap();
+        assert_eq!(names(&completions).first(), Some(&"permissions"));
+    }
+
+    #[tokio::test]
+    async fn typo_query_finds_command() {
+        let completer = default_completer();
+        let completions = completer.get_completions("hepl").await.unwrap();
+        assert!(names(&completions).contains(&"help"));
+    }
+
+    #[tokio::test]
+    async fn prefix_matches_rank_above_fuzzy_matches() {
+        let completer = default_completer();
+        let completions = completer.get_completions("t").await.unwrap();
+        let names = names(&completions);
+
+        // "tasks" and "tools" start with "t"; "status", "untrust" and "agents" only contain it.
+        assert!(names.first().unwrap().starts_with('t'));
+        let tools_pos = names.iter().position(|n| *n == "tools").unwrap();
+        let tasks_pos = names.iter().position(|n| *n == "tasks").unwrap();
+        let status_pos = names.iter().position(|n| *n == "status").unwrap();
+        assert!(tools_pos < status_pos);
+        assert!(tasks_pos < status_pos);
+    }
+
+    #[tokio::test]
+    async fn ranking_is_stable_for_ties() {
+        let completer = default_completer();
+        let first = completer.get_completions("").await.unwrap();
+        let second = completer.get_completions("").await.unwrap();
+        assert_eq!(first, second);
+    }
 }
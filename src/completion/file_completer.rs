@@ -1,10 +1,30 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tokio::fs;
 
 use super::Completer;
 
+/// How deep below the working directory the scan descends.
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Stop scanning after this many entries so huge trees stay responsive.
+const MAX_SCANNED_ENTRIES: usize = 20_000;
+
+/// Most candidates returned for one query.
+const MAX_COMPLETIONS: usize = 50;
+
+/// Directory names never descended into or offered.
+const IGNORED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// A file or directory found by the scan. Directories carry a trailing `/`
+/// so picking one continues completion inside it.
+struct Candidate {
+    path: String,
+    modified: Option<SystemTime>,
+}
+
 pub struct FileCompleter {
     working_directory: PathBuf,
 }
@@ -14,10 +34,10 @@ impl FileCompleter {
         Self { working_directory }
     }
 
-    async fn scan_directory(&self, dir: &Path, max_depth: usize) -> Result<Vec<PathBuf>> {
-        let mut files = Vec::new();
-        Self::scan_directory_recursive(dir, dir, max_depth, 0, &mut files).await?;
-        Ok(files)
+    async fn scan_directory(&self, dir: &Path, max_depth: usize) -> Result<Vec<Candidate>> {
+        let mut candidates = Vec::new();
+        Self::scan_directory_recursive(dir, dir, max_depth, 0, &mut candidates).await?;
+        Ok(candidates)
     }
 
     fn scan_directory_recursive<'a>(
@@ -25,7 +45,7 @@ impl FileCompleter {
         current_dir: &'a Path,
         max_depth: usize,
         current_depth: usize,
-        files: &'a mut Vec<PathBuf>,
+        candidates: &'a mut Vec<Candidate>,
     ) -> std::pin::Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(async move {
             if current_depth > max_depth {
@@ -35,31 +55,47 @@ impl FileCompleter {
             let mut entries = fs::read_dir(current_dir).await?;
 
             while let Some(entry) = entries.next_entry().await? {
+                if candidates.len() >= MAX_SCANNED_ENTRIES {
+                    return Ok(());
+                }
+
                 let path = entry.path();
                 let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-                // Skip hidden files and common ignore patterns
-                if file_name.starts_with('.')
-                    || file_name == "target"
-                    || file_name == "node_modules"
-                {
+                // Skip hidden files (including .git) and common ignore patterns
+                if file_name.starts_with('.') || IGNORED_DIRS.contains(&file_name) {
                     continue;
                 }
 
-                let metadata = entry.metadata().await?;
+                let Ok(metadata) = entry.metadata().await else {
+                    continue;
+                };
+                // Store paths relative to the working directory, `/`-separated
+                let Ok(relative) = path.strip_prefix(base_dir) else {
+                    continue;
+                };
+                let relative = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
 
                 if metadata.is_file() {
-                    // Store relative path from working directory
-                    if let Ok(relative) = path.strip_prefix(base_dir) {
-                        files.push(relative.to_path_buf());
-                    }
+                    candidates.push(Candidate {
+                        path: relative,
+                        modified: metadata.modified().ok(),
+                    });
                 } else if metadata.is_dir() {
+                    candidates.push(Candidate {
+                        path: format!("{}/", relative),
+                        modified: metadata.modified().ok(),
+                    });
                     Self::scan_directory_recursive(
                         base_dir,
                         &path,
                         max_depth,
                         current_depth + 1,
-                        files,
+                        candidates,
                     )
                     .await?;
                 }
@@ -95,12 +131,17 @@ impl FileCompleter {
     fn score_match(pattern: &str, target: &str) -> i32 {
         let pattern_lower = pattern.to_lowercase();
         let target_lower = target.to_lowercase();
+        let file_name = target_lower
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
 
         let mut score = 0;
 
         // Exact match gets highest score
         if target_lower == pattern_lower {
-            return 1000;
+            return 10_000;
         }
 
         // Starts with pattern gets high score
@@ -108,14 +149,16 @@ impl FileCompleter {
             score += 500;
         }
 
-        // Contains pattern as substring
-        if target_lower.contains(&pattern_lower) {
+        // Contains pattern as substring, better still within the file name
+        if file_name.contains(&pattern_lower) {
+            score += 400;
+        } else if target_lower.contains(&pattern_lower) {
             score += 300;
         }
 
-        // Fuzzy match gets base score
+        // Fuzzy match gets base score plus bonuses for how tightly it fits
         if Self::fuzzy_match(pattern, target) {
-            score += 100;
+            score += 100 + Self::subsequence_bonus(&pattern_lower, &target_lower);
         }
 
         // Shorter paths are preferred
@@ -123,6 +166,94 @@ impl FileCompleter {
 
         score
     }
+
+    /// Bonus for a greedy subsequence match: runs of consecutive characters
+    /// and characters that start a path segment or word (`src/main.rs` for
+    /// `smr`) score higher than scattered ones.
+    fn subsequence_bonus(pattern: &str, target: &str) -> i32 {
+        let mut bonus = 0;
+        let mut pattern_chars = pattern.chars().peekable();
+        let mut prev: Option<char> = None;
+        let mut prev_matched = false;
+
+        for c in target.chars() {
+            let Some(&pc) = pattern_chars.peek() else {
+                break;
+            };
+            if pc == c {
+                if prev_matched {
+                    bonus += 10;
+                }
+                if prev.is_none_or(|p| matches!(p, '/' | '_' | '-' | '.')) {
+                    bonus += 15;
+                }
+                pattern_chars.next();
+                prev_matched = true;
+            } else {
+                prev_matched = false;
+            }
+            prev = Some(c);
+        }
+
+        bonus
+    }
+
+    /// Recently modified files are more likely to be the one being mentioned.
+    fn recency_bonus(modified: Option<SystemTime>, now: SystemTime) -> i32 {
+        let Some(age) = modified.and_then(|m| now.duration_since(m).ok()) else {
+            return 0;
+        };
+        match age.as_secs() {
+            0..=3_600 => 40,
+            3_601..=86_400 => 25,
+            86_401..=604_800 => 10,
+            _ => 0,
+        }
+    }
+
+    /// Rank `candidates` against `query`. A query ending in `/` lists what is
+    /// inside that directory, nearest entries first.
+    fn rank(candidates: Vec<Candidate>, query: &str, now: SystemTime) -> Vec<String> {
+        let query_lower = query.to_lowercase();
+        let (dir, pattern) = if query.ends_with('/') {
+            (query_lower.as_str(), "")
+        } else {
+            ("", query)
+        };
+
+        let mut matches: Vec<(String, i32)> = candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                if !dir.is_empty() {
+                    let rest = candidate.path.to_lowercase();
+                    let rest = rest.strip_prefix(dir)?;
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    // Nearest entries first: penalize each level below `dir`.
+                    let depth = rest.trim_end_matches('/').matches('/').count() as i32;
+                    let score = -depth * 100 - candidate.path.len() as i32
+                        + Self::recency_bonus(candidate.modified, now);
+                    return Some((candidate.path, score));
+                }
+                if !Self::fuzzy_match(pattern, &candidate.path) {
+                    return None;
+                }
+                let score = Self::score_match(pattern, &candidate.path)
+                    + Self::recency_bonus(candidate.modified, now);
+                Some((candidate.path, score))
+            })
+            .collect();
+
+        // Sort by score (descending), then alphabetically for stable output
+        matches.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        matches
+            .into_iter()
+            .take(MAX_COMPLETIONS)
+            .map(|(path, _)| path)
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -132,26 +263,10 @@ impl Completer for FileCompleter {
     }
 
     async fn get_completions(&self, query: &str) -> Result<Vec<String>> {
-        let files = self.scan_directory(&self.working_directory, 5).await?;
-
-        let mut matches: Vec<(String, i32)> = files
-            .iter()
-            .filter_map(|path| {
-                let path_str = path.to_string_lossy().to_string();
-                if Self::fuzzy_match(query, &path_str) {
-                    let score = Self::score_match(query, &path_str);
-                    Some((path_str, score))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Sort by score (descending)
-        matches.sort_by_key(|m| std::cmp::Reverse(m.1));
-
-        // Return top 50 matches
-        Ok(matches.into_iter().take(50).map(|(path, _)| path).collect())
+        let candidates = self
+            .scan_directory(&self.working_directory, MAX_SCAN_DEPTH)
+            .await?;
+        Ok(Self::rank(candidates, query, SystemTime::now()))
     }
 
     fn format_completion(&self, item: &str) -> String {
@@ -191,4 +306,85 @@ mod tests {
                 > FileCompleter::score_match("test", "long/path/to/test.rs")
         );
     }
+
+    fn candidate(path: &str, modified: Option<SystemTime>) -> Candidate {
+        Candidate {
+            path: path.to_string(),
+            modified,
+        }
+    }
+
+    #[test]
+    fn fuzzy_query_finds_file_in_subdirectory() {
+        let now = SystemTime::now();
+        let ranked = FileCompleter::rank(
+            vec![
+                candidate("docs/maintenance/readme.md", None),
+                candidate("src/main.rs", None),
+                candidate("src/tui/main_loop/renderers.rs", None),
+            ],
+            "mainrs",
+            now,
+        );
+        assert_eq!(ranked.first().map(String::as_str), Some("src/main.rs"));
+        assert!(!ranked.contains(&"docs/maintenance/readme.md".to_string()));
+    }
+
+    #[test]
+    fn recently_modified_file_breaks_ties() {
+        let now = SystemTime::now();
+        let old = now - std::time::Duration::from_secs(30 * 86_400);
+        let ranked = FileCompleter::rank(
+            vec![
+                candidate("src/a/mod.rs", Some(old)),
+                candidate("src/b/mod.rs", Some(now)),
+            ],
+            "mod",
+            now,
+        );
+        assert_eq!(ranked, vec!["src/b/mod.rs", "src/a/mod.rs"]);
+    }
+
+    #[test]
+    fn trailing_slash_lists_directory_contents() {
+        let now = SystemTime::now();
+        let ranked = FileCompleter::rank(
+            vec![
+                candidate("src/", None),
+                candidate("src/tui/", None),
+                candidate("src/tui/app.rs", None),
+                candidate("src/main.rs", None),
+                candidate("tests/main.rs", None),
+            ],
+            "src/",
+            now,
+        );
+        assert_eq!(ranked, vec!["src/tui/", "src/main.rs", "src/tui/app.rs"]);
+    }
+
+    #[test]
+    fn results_are_capped() {
+        let candidates = (0..200)
+            .map(|i| candidate(&format!("file{i}.rs"), None))
+            .collect();
+        let ranked = FileCompleter::rank(candidates, "file", SystemTime::now());
+        assert_eq!(ranked.len(), MAX_COMPLETIONS);
+    }
+
+    #[tokio::test]
+    async fn ignored_directories_are_excluded() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["src", "target/debug", ".git/objects", "node_modules/pkg"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join("target/debug/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join(".git/objects/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join("node_modules/pkg/main.rs"), "").unwrap();
+
+        let completer = FileCompleter::new(dir.path().to_path_buf());
+        let completions = completer.get_completions("main").await.unwrap();
+
+        assert_eq!(completions, vec!["src/main.rs"]);
+    }
 }
@@ -1,6 +1,8 @@
+mod agent_completer;
 mod command_completer;
 mod file_completer;
 
+pub use agent_completer::AgentCompleter;
 pub use command_completer::CommandCompleter;
 pub use file_completer::FileCompleter;
 
@@ -24,15 +26,123 @@ pub trait Completer: Send + Sync {
         item.to_string()
     }
 
-    /// Find the position of the trigger character in the input text
-    /// Returns the byte position of the trigger character, or None if not found
-    fn find_trigger_position(&self, input: &str) -> Option<usize> {
-        input.rfind(self.trigger_key())
+    /// Byte position of the trigger that starts the whitespace-delimited
+    /// token holding `cursor`, or None when that token isn't triggered. With
+    /// several `@` references this picks the one being edited, not the last.
+    fn find_trigger_position(&self, input: &str, cursor: usize) -> Option<usize> {
+        let start = token_start(input, cursor);
+        input[start..]
+            .starts_with(self.trigger_key())
+            .then_some(start)
     }
 
-    /// Apply the selected completion to the input text
-    /// Returns the new input text with the completion applied
-    fn apply_completion(&self, input: &str, trigger_pos: usize, completion: &str) -> String {
-        format!("{}{}", &input[..=trigger_pos], completion)
+    /// Replace the token starting at `trigger_pos` with the trigger and
+    /// `completion`, leaving the rest of the input alone. Returns the new
+    /// text and the cursor position just past the completion.
+    fn apply_completion(
+        &self,
+        input: &str,
+        trigger_pos: usize,
+        completion: &str,
+    ) -> (String, usize) {
+        replace_token(input, trigger_pos, completion)
+    }
+}
+
+/// Start of the whitespace-delimited token that ends at `cursor`.
+fn token_start(input: &str, cursor: usize) -> usize {
+    input[..cursor]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0)
+}
+
+/// End of the whitespace-delimited token starting at `start`.
+fn token_end(input: &str, start: usize) -> usize {
+    input[start..]
+        .find(char::is_whitespace)
+        .map(|i| start + i)
+        .unwrap_or(input.len())
+}
+
+/// The query typed so far for the token triggered at `trigger_pos`: from
+/// just after the trigger up to the cursor.
+pub fn completion_query(input: &str, trigger_pos: usize, cursor: usize) -> &str {
+    let start = trigger_pos + 1;
+    &input[start..cursor.max(start)]
+}
+
+pub(crate) fn replace_token(input: &str, trigger_pos: usize, completion: &str) -> (String, usize) {
+    let head = &input[..=trigger_pos];
+    let tail = &input[token_end(input, trigger_pos)..];
+    (
+        format!("{}{}{}", head, completion, tail),
+        head.len() + completion.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AtCompleter;
+
+    #[async_trait]
+    impl Completer for AtCompleter {
+        fn trigger_key(&self) -> char {
+            '@'
+        }
+
+        async fn get_completions(&self, _query: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn trigger_is_the_reference_under_the_cursor() {
+        let input = "review @a.rs and @sr";
+        assert_eq!(
+            AtCompleter.find_trigger_position(input, input.len()),
+            Some(17)
+        );
+        assert_eq!(AtCompleter.find_trigger_position(input, 10), Some(7));
+        // Between references the cursor sits in a plain word.
+        assert_eq!(AtCompleter.find_trigger_position(input, 15), None);
+        assert_eq!(AtCompleter.find_trigger_position(input, 0), None);
+    }
+
+    #[test]
+    fn trigger_inside_a_word_does_not_count() {
+        let input = "mail me@example.com";
+        assert_eq!(AtCompleter.find_trigger_position(input, input.len()), None);
+    }
+
+    #[test]
+    fn completing_the_last_reference_keeps_the_first() {
+        let input = "review @a.rs and @sr";
+        let (text, cursor) = AtCompleter.apply_completion(input, 17, "src/main.rs");
+        assert_eq!(text, "review @a.rs and @src/main.rs");
+        assert_eq!(cursor, text.len());
+    }
+
+    #[test]
+    fn completing_mid_line_replaces_only_that_token() {
+        let input = "review @sr and @b.rs";
+        let cursor = "review @sr".len();
+        let trigger = AtCompleter.find_trigger_position(input, cursor).unwrap();
+        assert_eq!(completion_query(input, trigger, cursor), "sr");
+
+        let (text, cursor) = AtCompleter.apply_completion(input, trigger, "src/main.rs");
+        assert_eq!(text, "review @src/main.rs and @b.rs");
+        assert_eq!(&text[..cursor], "review @src/main.rs");
+    }
+
+    #[test]
+    fn query_stops_at_the_cursor() {
+        let input = "see @src/ma later";
+        let cursor = "see @src".len();
+        assert_eq!(completion_query(input, 4, cursor), "src");
     }
 }
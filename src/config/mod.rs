@@ -1,14 +1,27 @@
+use crate::backends::context_window::FALLBACK_CONTEXT_WINDOW;
+use crate::backends::{BackendKind, LlmBackend, RetryPolicy};
 use crate::console::{VerbosityLevel, console};
 use crate::context_management::ContextManagerConfig;
 use crate::daemon::config::DaemonConfig;
+use crate::history::HistoryDedupMode;
 use crate::memory_mode::MemoryMode;
+use crate::notification::NotificationMode;
+use crate::permissions::{ToolPolicies, ToolPolicy};
+use crate::prompt_injection::{InjectionScanner, PromptInjectionConfig};
+use crate::secret_redaction::{PromptSecretCheck, SecretRedactionConfig, SecretRedactor};
 use crate::storage::{
     ConversationStorageMode, SkillStorageMode, deserialize_conversation_storage,
     resolve_skill_roots, resolve_storage_root,
 };
+use crate::system_reminders::SystemReminderConfig;
 use crate::terminal_mode::TerminalMode;
+use crate::tools::bash::{BashConfig, CommandBlocklist, CommandBlocklistConfig};
+use crate::tools::file_ops::ReadLimits;
+use crate::tools::recall::RecallConfig;
+use crate::tui::editor::EditorConfig;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::OnceLock;
 use std::{collections::HashMap, fs, path::PathBuf};
 
@@ -23,6 +36,140 @@ pub fn set_data_dir_override(path: PathBuf) {
     let _ = DATA_DIR_OVERRIDE.set(path);
 }
 
+/// Finds the project's `.hoosh/config.toml`, the way `.git` and
+/// `.editorconfig` are found. Inside a git work tree the search walks from
+/// `start` up to the repository root, so a repository never picks up a
+/// config from outside it. Outside one only `start` itself is checked: a
+/// `.hoosh/` left in `$HOME` by an earlier run must not claim every folder
+/// below it.
+pub fn discover_project_config(start: &Path) -> Option<PathBuf> {
+    let config_in =
+        |dir: &Path| Some(dir.join(".hoosh").join("config.toml")).filter(|p| p.is_file());
+    let Some(repo_root) = start.ancestors().find(|dir| dir.join(".git").exists()) else {
+        return config_in(start);
+    };
+    for dir in start.ancestors() {
+        if let Some(config) = config_in(dir) {
+            return Some(config);
+        }
+        if dir == repo_root {
+            break;
+        }
+    }
+    None
+}
+
+/// Write a config file via a temp file in the same directory and rename it over
+/// `path`, so a crash mid-write never leaves a truncated config behind. The
+/// final file is 0600 on Unix. If `write` fails the temp file is discarded and
+/// `path` is untouched.
+fn write_config_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut fs::File) -> std::io::Result<()>,
+) -> ConfigResult<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(ConfigError::IoError)?;
+
+    write(tmp.as_file_mut()).map_err(ConfigError::IoError)?;
+    tmp.as_file().sync_all().map_err(ConfigError::IoError)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let permissions = std::fs::Permissions::from_mode(0o600);
+        fs::set_permissions(tmp.path(), permissions).map_err(ConfigError::IoError)?;
+    }
+
+    tmp.persist(path)
+        .map_err(|e| ConfigError::IoError(e.error))?;
+    Ok(())
+}
+
+/// Environment variable that relocates the global config directory
+/// (config.toml, agents/, commands/, permissions.json).
+pub const CONFIG_DIR_ENV: &str = "HOOSH_CONFIG_DIR";
+
+/// Resolve the global config directory from environment values, in order of
+/// precedence: `HOOSH_CONFIG_DIR`, `$XDG_CONFIG_HOME/hoosh`, `$HOME/.config/hoosh`.
+/// Empty values are ignored, as is a relative `XDG_CONFIG_HOME` (per the XDG spec).
+fn resolve_config_dir(
+    hoosh_config_dir: Option<std::ffi::OsString>,
+    xdg_config_home: Option<std::ffi::OsString>,
+    home: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    let non_empty = |v: Option<std::ffi::OsString>| v.filter(|v| !v.is_empty()).map(PathBuf::from);
+
+    if let Some(dir) = non_empty(hoosh_config_dir) {
+        return Some(dir);
+    }
+    if let Some(xdg) = non_empty(xdg_config_home).filter(|p| p.is_absolute()) {
+        return Some(xdg.join("hoosh"));
+    }
+    non_empty(home).map(|home| home.join(".config").join("hoosh"))
+}
+
+/// Environment variable that relocates the data directory (user-scoped
+/// conversations, memory, skills).
+pub const DATA_DIR_ENV: &str = "HOOSH_DATA_DIR";
+
+/// Resolve the data directory the same way as [`resolve_config_dir`]:
+/// `HOOSH_DATA_DIR`, `$XDG_DATA_HOME/hoosh`, `$HOME/.local/share/hoosh`.
+/// Data written to `$HOME/.local/share/hoosh` before `XDG_DATA_HOME` was
+/// honoured keeps being used until the XDG directory exists.
+fn resolve_data_dir(
+    hoosh_data_dir: Option<std::ffi::OsString>,
+    xdg_data_home: Option<std::ffi::OsString>,
+    home: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    let non_empty = |v: Option<std::ffi::OsString>| v.filter(|v| !v.is_empty()).map(PathBuf::from);
+
+    if let Some(dir) = non_empty(hoosh_data_dir) {
+        return Some(dir);
+    }
+    let legacy = non_empty(home).map(|home| home.join(".local").join("share").join("hoosh"));
+    if let Some(xdg) = non_empty(xdg_data_home).filter(|p| p.is_absolute()) {
+        let dir = xdg.join("hoosh");
+        if !dir.exists()
+            && let Some(legacy) = legacy.filter(|legacy| legacy.is_dir())
+        {
+            return Some(legacy);
+        }
+        return Some(dir);
+    }
+    legacy
+}
+
+/// Most stop sequences accepted per backend. OpenAI caps requests at four,
+/// and the other providers accept at least as many.
+pub const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Reject stop lists the providers would refuse: too many entries, or an
+/// empty sequence.
+pub fn validate_stop_sequences(stop: &[String]) -> ConfigResult<()> {
+    if stop.len() > MAX_STOP_SEQUENCES || stop.iter().any(String::is_empty) {
+        return Err(ConfigError::InvalidValue {
+            field: "stop".to_string(),
+            value: format!("{stop:?} (at most {MAX_STOP_SEQUENCES} non-empty sequences)"),
+        });
+    }
+    Ok(())
+}
+
+/// Parse a sampling parameter, rejecting values outside `range`.
+fn parse_in_range(
+    field: &str,
+    value: String,
+    range: std::ops::RangeInclusive<f32>,
+) -> ConfigResult<f32> {
+    match value.parse::<f32>() {
+        Ok(v) if range.contains(&v) => Ok(v),
+        _ => Err(ConfigError::InvalidValue {
+            field: field.to_string(),
+            value,
+        }),
+    }
+}
+
 pub mod error;
 pub mod interpolation;
 pub use error::{ConfigError, ConfigResult};
@@ -129,6 +276,10 @@ pub struct BackendConfig {
     pub base_url: Option<String>,
     pub chat_api: Option<String>,
     pub temperature: Option<f32>,
+    /// Nucleus sampling cutoff. Left out of requests when unset so the
+    /// provider's default applies.
+    #[serde(default)]
+    pub top_p: Option<f32>,
     pub pricing_endpoint: Option<String>,
     /// Anthropic extended thinking budget in tokens. When set, the backend
     /// enables `thinking` in the request and forces temperature to 1.0 (an
@@ -147,6 +298,47 @@ pub struct BackendConfig {
     /// Stream responses token-by-token. Defaults to enabled when unset.
     #[serde(default)]
     pub streaming: Option<bool>,
+    /// Context window of the model in tokens, for models the built-in table
+    /// doesn't know or gets wrong.
+    #[serde(default)]
+    pub context_window: Option<usize>,
+    /// Model used to title new conversations, typically a cheaper one than
+    /// `model`. Titles come from `model` when unset.
+    #[serde(default)]
+    pub title_model: Option<String>,
+    /// Most requests to this backend in flight at once, across the main
+    /// loop and all sub-agent tasks. Further requests wait their turn.
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+    /// Most requests to this backend started in any 60-second window.
+    #[serde(default)]
+    pub requests_per_minute: Option<usize>,
+    /// Extra failures to retry, for gateways with their own transient
+    /// statuses or messages.
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    /// Tool-calling fields an OpenAI-compatible gateway accepts. Defaults
+    /// to `auto`.
+    #[serde(default)]
+    pub tool_api: Option<ToolApi>,
+    /// Sequences that end generation when the model emits them. At most
+    /// [`MAX_STOP_SEQUENCES`]; ignored by backends without stop support.
+    #[serde(default)]
+    pub stop: Option<Vec<String>>,
+}
+
+/// Which tool-calling fields an OpenAI-compatible backend speaks.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolApi {
+    /// Send `tools`, and switch to `functions` for the rest of the session
+    /// if the gateway rejects them.
+    #[default]
+    Auto,
+    /// `tools` / `tool_choice`, answered with `tool_calls`.
+    Tools,
+    /// The legacy `functions` / `function_call` fields.
+    Functions,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
@@ -166,7 +358,95 @@ pub enum ReasoningEffort {
     Max,
 }
 
+/// Anthropic rejects extended-thinking budgets below this many tokens.
+pub const MIN_THINKING_BUDGET: u32 = 1024;
+
+impl ReasoningEffort {
+    pub const ALL: [ReasoningEffort; 5] = [
+        ReasoningEffort::Low,
+        ReasoningEffort::Medium,
+        ReasoningEffort::High,
+        ReasoningEffort::Xhigh,
+        ReasoningEffort::Max,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+            ReasoningEffort::Xhigh => "xhigh",
+            ReasoningEffort::Max => "max",
+        }
+    }
+
+    /// The extended-thinking budget used for this level on backends that take
+    /// a token budget rather than an effort level (Anthropic).
+    pub fn thinking_budget(&self) -> u32 {
+        match self {
+            ReasoningEffort::Low => 4_000,
+            ReasoningEffort::Medium => 16_000,
+            ReasoningEffort::High => 32_000,
+            ReasoningEffort::Xhigh => 48_000,
+            ReasoningEffort::Max => 64_000,
+        }
+    }
+}
+
+impl std::str::FromStr for ReasoningEffort {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str() == s)
+            .ok_or(())
+    }
+}
+
 impl BackendConfig {
+    /// Checks `thinking_budget`/`reasoning_effort` against what the backend
+    /// named `name` accepts. Out-of-range values are an error; settings the
+    /// backend doesn't support are ignored by it, which is reported as a
+    /// warning.
+    pub fn validate_reasoning(&self, name: &str) -> ConfigResult<Option<String>> {
+        let kind = BackendKind::from_str(name).ok();
+
+        if let Some(budget) = self.thinking_budget
+            && budget > 0
+            && budget < MIN_THINKING_BUDGET
+            && kind == Some(BackendKind::Anthropic)
+        {
+            return Err(ConfigError::InvalidValue {
+                field: format!("backends.{name}.thinking_budget"),
+                value: format!("{budget} (must be 0 or at least {MIN_THINKING_BUDGET})"),
+            });
+        }
+
+        let unsupported = match kind {
+            Some(BackendKind::OpenAi | BackendKind::Groq) | None => vec![],
+            Some(BackendKind::Anthropic) => {
+                vec![("reasoning_effort", self.reasoning_effort.is_some())]
+            }
+            Some(BackendKind::Ollama | BackendKind::TogetherAi | BackendKind::Mock) => vec![
+                ("thinking_budget", self.thinking_budget.is_some()),
+                ("reasoning_effort", self.reasoning_effort.is_some()),
+            ],
+        };
+        let ignored: Vec<&str> = unsupported
+            .into_iter()
+            .filter_map(|(field, set)| set.then_some(field))
+            .collect();
+
+        Ok((!ignored.is_empty()).then(|| {
+            format!(
+                "Backend '{}' does not support {}; ignoring it",
+                name,
+                ignored.join(" or ")
+            )
+        }))
+    }
+
     fn merge_from(&mut self, other: &BackendConfig) {
         if other.api_key.is_some() {
             self.api_key = other.api_key.clone();
@@ -183,6 +463,9 @@ impl BackendConfig {
         if other.temperature.is_some() {
             self.temperature = other.temperature;
         }
+        if other.top_p.is_some() {
+            self.top_p = other.top_p;
+        }
         if other.pricing_endpoint.is_some() {
             self.pricing_endpoint = other.pricing_endpoint.clone();
         }
@@ -198,6 +481,27 @@ impl BackendConfig {
         if other.streaming.is_some() {
             self.streaming = other.streaming;
         }
+        if other.context_window.is_some() {
+            self.context_window = other.context_window;
+        }
+        if other.title_model.is_some() {
+            self.title_model = other.title_model.clone();
+        }
+        if other.max_concurrent_requests.is_some() {
+            self.max_concurrent_requests = other.max_concurrent_requests;
+        }
+        if other.requests_per_minute.is_some() {
+            self.requests_per_minute = other.requests_per_minute;
+        }
+        if other.retry.is_some() {
+            self.retry = other.retry.clone();
+        }
+        if other.tool_api.is_some() {
+            self.tool_api = other.tool_api;
+        }
+        if other.stop.is_some() {
+            self.stop = other.stop.clone();
+        }
     }
 }
 
@@ -210,6 +514,12 @@ pub struct AgentConfig {
     pub tags: Vec<String>,
     #[serde(default)]
     pub core_instructions_file: Option<String>,
+    /// Backend selected while this agent is active; the global default if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Model selected while this agent is active; the backend's model if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -227,6 +537,24 @@ pub struct AppConfig {
     pub context_manager: Option<ContextManagerConfig>,
     #[serde(default)]
     pub core_reminder_token_threshold: Option<usize>,
+    #[serde(default)]
+    pub core_reminder_step_interval: Option<usize>,
+    #[serde(default)]
+    pub git_status_reminder_interval: Option<usize>,
+    #[serde(default)]
+    pub system_reminders: Option<SystemReminderConfig>,
+    /// Extra attempts for a tool call that fails transiently.
+    #[serde(default)]
+    pub tool_retries: Option<u32>,
+    /// Retries, of backend requests and tool calls together, allowed per turn.
+    #[serde(default)]
+    pub turn_retry_budget: Option<u32>,
+    /// Model round-trips the main agent may take in one turn.
+    #[serde(default)]
+    pub agent_max_steps: Option<usize>,
+    /// Check tool-call arguments against the tool's schema before running it.
+    #[serde(default)]
+    pub validate_tool_arguments: Option<bool>,
     #[serde(default, deserialize_with = "deserialize_conversation_storage")]
     pub conversation_storage: Option<ConversationStorageMode>,
     #[serde(default, deserialize_with = "deserialize_conversation_storage")]
@@ -239,8 +567,61 @@ pub struct AppConfig {
     pub memory_mode: Option<MemoryMode>,
     #[serde(default)]
     pub display_compact: Option<bool>,
+    /// Show assistant replies as plain text rather than rendered markdown.
+    #[serde(default)]
+    pub raw_output: Option<bool>,
+    /// Widest a rendered markdown table may get, in columns. Tables use the
+    /// full terminal width when unset.
+    #[serde(default)]
+    pub markdown_table_max_width: Option<usize>,
+    /// Draw images inline on kitty and iTerm2-compatible terminals. Needs a
+    /// build with the `inline-images` feature; off by default.
+    #[serde(default)]
+    pub inline_images: Option<bool>,
+    /// How ctrl+g opens `path:line` references from the transcript.
+    #[serde(default)]
+    pub editor: Option<EditorConfig>,
+    /// Pastes longer than this many characters collapse into one
+    /// `[pasted N lines]` element in the input. Defaults to 200.
+    #[serde(default)]
+    pub paste_collapse_chars: Option<usize>,
+    /// Pastes with more lines than this collapse too. Defaults to 10.
+    #[serde(default)]
+    pub paste_collapse_lines: Option<usize>,
     #[serde(default)]
     pub skill_mode: Option<SkillStorageMode>,
+    #[serde(default)]
+    pub history_dedup: Option<HistoryDedupMode>,
+    /// Maximum number of prompts kept in the history file.
+    #[serde(default)]
+    pub history_size: Option<usize>,
+    #[serde(default)]
+    pub secret_redaction: Option<SecretRedactionConfig>,
+    #[serde(default)]
+    pub prompt_injection: Option<PromptInjectionConfig>,
+    #[serde(default)]
+    pub bash_blocklist: Option<CommandBlocklistConfig>,
+    #[serde(default)]
+    pub bash: Option<BashConfig>,
+    #[serde(default)]
+    pub read_limits: Option<ReadLimits>,
+    #[serde(default)]
+    pub github_token: Option<String>,
+    #[serde(default)]
+    pub notifications: Option<NotificationMode>,
+    #[serde(default)]
+    pub tool_policies: Option<ToolPolicies>,
+    #[serde(default)]
+    pub default_permission: Option<ToolPolicy>,
+    #[serde(default)]
+    pub prompt_secret_check: Option<PromptSecretCheck>,
+    #[serde(default)]
+    pub recall: Option<RecallConfig>,
+    /// Set by `--dry-run` for one invocation: tools that would change
+    /// something are simulated instead of run. Never read from or written
+    /// to the config file.
+    #[serde(skip)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -260,6 +641,24 @@ pub struct ProjectConfig {
     #[serde(default)]
     pub core_reminder_token_threshold: Option<usize>,
     #[serde(default)]
+    pub core_reminder_step_interval: Option<usize>,
+    #[serde(default)]
+    pub git_status_reminder_interval: Option<usize>,
+    #[serde(default)]
+    pub system_reminders: Option<SystemReminderConfig>,
+    /// Extra attempts for a tool call that fails transiently.
+    #[serde(default)]
+    pub tool_retries: Option<u32>,
+    /// Retries, of backend requests and tool calls together, allowed per turn.
+    #[serde(default)]
+    pub turn_retry_budget: Option<u32>,
+    /// Model round-trips the main agent may take in one turn.
+    #[serde(default)]
+    pub agent_max_steps: Option<usize>,
+    /// Check tool-call arguments against the tool's schema before running it.
+    #[serde(default)]
+    pub validate_tool_arguments: Option<bool>,
+    #[serde(default)]
     pub core_instructions_file: Option<String>,
     #[serde(default, deserialize_with = "deserialize_conversation_storage")]
     pub conversation_storage: Option<ConversationStorageMode>,
@@ -291,6 +690,8 @@ impl Default for AppConfig {
                     description: None,
                     tags: vec![],
                     core_instructions_file,
+                    backend: None,
+                    model: None,
                 },
             );
         }
@@ -303,13 +704,40 @@ impl Default for AppConfig {
             agents,
             context_manager: None,
             core_reminder_token_threshold: None,
+            core_reminder_step_interval: None,
+            git_status_reminder_interval: None,
+            system_reminders: None,
+            tool_retries: None,
+            turn_retry_budget: None,
+            validate_tool_arguments: None,
+            agent_max_steps: None,
             conversation_storage: None,
             memory_storage: None,
             terminal_mode: None,
             daemon: None,
             memory_mode: None,
             display_compact: None,
+            raw_output: None,
+            inline_images: None,
+            editor: None,
+            markdown_table_max_width: None,
+            paste_collapse_chars: None,
+            paste_collapse_lines: None,
             skill_mode: None,
+            history_dedup: None,
+            history_size: None,
+            secret_redaction: None,
+            prompt_injection: None,
+            bash_blocklist: None,
+            bash: None,
+            read_limits: None,
+            github_token: None,
+            notifications: None,
+            tool_policies: None,
+            default_permission: None,
+            prompt_secret_check: None,
+            recall: None,
+            dry_run: false,
         }
     }
 }
@@ -356,6 +784,21 @@ impl AppConfig {
     fn validate(&self) -> ConfigResult<()> {
         let console = console();
 
+        for (name, backend) in &self.backends {
+            if let Some(warning) = backend.validate_reasoning(name)? {
+                console.warning(&warning);
+            }
+            if let Some(stop) = &backend.stop {
+                validate_stop_sequences(stop).map_err(|e| match e {
+                    ConfigError::InvalidValue { value, .. } => ConfigError::InvalidValue {
+                        field: format!("backends.{name}.stop"),
+                        value,
+                    },
+                    e => e,
+                })?;
+            }
+        }
+
         if let Some(default_agent) = &self.default_agent
             && !self.agents.contains_key(default_agent)
         {
@@ -372,6 +815,27 @@ impl AppConfig {
             }
         }
 
+        if let Some(bash) = &self.bash {
+            bash.validate_shell()
+                .map_err(|value| ConfigError::InvalidValue {
+                    field: "bash.shell".to_string(),
+                    value,
+                })?;
+        }
+
+        if let Some(truncation) = self
+            .context_manager
+            .as_ref()
+            .and_then(|cm| cm.tool_output_truncation.as_ref())
+        {
+            truncation
+                .validate()
+                .map_err(|(field, value)| ConfigError::InvalidValue {
+                    field: format!("context_manager.tool_output_truncation.{field}"),
+                    value,
+                })?;
+        }
+
         let agents_dir = Self::agents_dir()?;
 
         for (name, agent_config) in &self.agents {
@@ -433,17 +897,9 @@ impl AppConfig {
         }
         let content = toml::to_string_pretty(self)
             .map_err(|e| ConfigError::SerializationError(e.to_string()))?;
-        fs::write(&config_path, content).map_err(ConfigError::IoError)?;
-
-        // Set secure permissions on Unix systems (0600)
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let permissions = std::fs::Permissions::from_mode(0o600);
-            fs::set_permissions(&config_path, permissions).map_err(ConfigError::IoError)?;
-        }
-
-        Ok(())
+        write_config_atomically(&config_path, |file| {
+            std::io::Write::write_all(file, content.as_bytes())
+        })
     }
 
     pub fn get_backend_config(&self, backend_name: &str) -> Option<&BackendConfig> {
@@ -469,11 +925,19 @@ impl AppConfig {
                 base_url: None,
                 chat_api: None,
                 temperature: None,
+                top_p: None,
                 pricing_endpoint: None,
                 thinking_budget: None,
                 reasoning_effort: None,
                 reasoning_display: None,
                 streaming: None,
+                context_window: None,
+                title_model: None,
+                max_concurrent_requests: None,
+                requests_per_minute: None,
+                retry: None,
+                tool_api: None,
+                stop: None,
             });
 
         match key {
@@ -482,13 +946,39 @@ impl AppConfig {
             "base_url" => config.base_url = Some(value),
             "chat_api" => config.chat_api = Some(value),
             "temperature" => {
-                let temp: f32 = value.parse().map_err(|_| ConfigError::InvalidValue {
-                    field: "temperature".to_string(),
+                config.temperature = Some(parse_in_range("temperature", value, 0.0..=2.0)?);
+            }
+            "top_p" => config.top_p = Some(parse_in_range("top_p", value, 0.0..=1.0)?),
+            "pricing_endpoint" => config.pricing_endpoint = Some(value),
+            // A JSON array of strings; an empty array clears the list.
+            "stop" => {
+                let stop: Vec<String> =
+                    serde_json::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+                        field: "stop".to_string(),
+                        value: value.clone(),
+                    })?;
+                validate_stop_sequences(&stop)?;
+                config.stop = (!stop.is_empty()).then_some(stop);
+            }
+            "title_model" => config.title_model = Some(value),
+            "context_window" => {
+                let window: usize = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    field: "context_window".to_string(),
                     value,
                 })?;
-                config.temperature = Some(temp);
+                config.context_window = Some(window);
+            }
+            "max_concurrent_requests" | "requests_per_minute" => {
+                let limit: usize = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    field: key.to_string(),
+                    value,
+                })?;
+                if key == "max_concurrent_requests" {
+                    config.max_concurrent_requests = Some(limit);
+                } else {
+                    config.requests_per_minute = Some(limit);
+                }
             }
-            "pricing_endpoint" => config.pricing_endpoint = Some(value),
             _ => {
                 return Err(ConfigError::UnknownConfigKey {
                     key: key.to_string(),
@@ -510,6 +1000,28 @@ impl AppConfig {
         self.verbosity = Some(verbosity.to_string());
     }
 
+    /// A copy of this config with `agent_name`'s `backend` and `model`
+    /// applied, so `default_backend` and that backend's `model` are the ones
+    /// the agent runs on. Unknown agents and unset fields leave the global
+    /// selection in place.
+    pub fn with_agent_overrides(&self, agent_name: &str) -> AppConfig {
+        let mut config = self.clone();
+        let Some(agent) = self.agents.get(agent_name) else {
+            return config;
+        };
+
+        if let Some(ref backend) = agent.backend {
+            config.default_backend = backend.clone();
+        }
+        if let Some(ref model) = agent.model
+            && let Some(backend) = config.backends.get_mut(&config.default_backend)
+        {
+            backend.model = Some(model.clone());
+        }
+
+        config
+    }
+
     pub fn set_default_agent(&mut self, agent_name: String) {
         self.default_agent = Some(agent_name);
     }
@@ -518,6 +1030,29 @@ impl AppConfig {
         self.context_manager.clone().unwrap_or_default()
     }
 
+    /// Context window for `backend`'s model: the backend's `context_window`
+    /// setting, then the backend's own knowledge of the model, then
+    /// [`FALLBACK_CONTEXT_WINDOW`].
+    pub fn context_window(&self, backend: &dyn LlmBackend) -> usize {
+        let configured = self
+            .get_backend_config(backend.backend_name())
+            .and_then(|c| c.context_window);
+
+        let (window, source) = match (configured, backend.context_window()) {
+            (Some(window), _) => (window, "backend config"),
+            (None, Some(window)) => (window, "built-in model table"),
+            (None, None) => (FALLBACK_CONTEXT_WINDOW, "conservative fallback"),
+        };
+        tracing::info!(
+            "Context window for {} ({}): {} tokens from {}",
+            backend.model_name(),
+            backend.backend_name(),
+            window,
+            source
+        );
+        window
+    }
+
     pub fn load_core_instructions(&self, agent_name: Option<&str>) -> ConfigResult<String> {
         // First, try agent-specific core instructions file
         if let Some(agent) = agent_name
@@ -545,15 +1080,12 @@ impl AppConfig {
                 .to_path_buf());
         }
 
-        let home = std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
-            .map_err(|_| ConfigError::NoHomeDirectory)?;
-
-        let mut path = PathBuf::from(home);
-        path.push(".config");
-        path.push("hoosh");
-
-        Ok(path)
+        resolve_config_dir(
+            std::env::var_os(CONFIG_DIR_ENV),
+            std::env::var_os("XDG_CONFIG_HOME"),
+            std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")),
+        )
+        .ok_or(ConfigError::NoHomeDirectory)
     }
 
     pub fn hoosh_data_dir() -> ConfigResult<PathBuf> {
@@ -561,14 +1093,12 @@ impl AppConfig {
             return Ok(override_path.clone());
         }
 
-        let home = std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
-            .map_err(|_| ConfigError::NoHomeDirectory)?;
-
-        Ok(PathBuf::from(home)
-            .join(".local")
-            .join("share")
-            .join("hoosh"))
+        resolve_data_dir(
+            std::env::var_os(DATA_DIR_ENV),
+            std::env::var_os("XDG_DATA_HOME"),
+            std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")),
+        )
+        .ok_or(ConfigError::NoHomeDirectory)
     }
 
     pub fn config_path() -> ConfigResult<PathBuf> {
@@ -612,6 +1142,47 @@ impl AppConfig {
         self.core_reminder_token_threshold.unwrap_or(20000)
     }
 
+    /// Agent steps between core instruction reminders; 0 (the default) leaves
+    /// only the token threshold in effect.
+    pub fn get_core_reminder_step_interval(&self) -> usize {
+        self.core_reminder_step_interval.unwrap_or(0)
+    }
+
+    /// Agent steps between git status reminders; 0 disables them.
+    pub fn get_git_status_reminder_interval(&self) -> usize {
+        self.git_status_reminder_interval.unwrap_or(10)
+    }
+
+    /// Which built-in reminder strategies run, and in what order.
+    pub fn system_reminders(&self) -> SystemReminderConfig {
+        self.system_reminders.clone().unwrap_or_default()
+    }
+
+    /// Times a tool call whose error is transient (a locked or busy file) is
+    /// retried before its error is recorded; 0 disables retries.
+    pub fn get_tool_retries(&self) -> u32 {
+        self.tool_retries.unwrap_or(2)
+    }
+
+    /// Retries a single agent turn may spend across its backend requests and
+    /// tool calls before failures are returned as they are (default: 10).
+    pub fn get_turn_retry_budget(&self) -> u32 {
+        self.turn_retry_budget.unwrap_or(10)
+    }
+
+    /// Model round-trips (each usually ending in tool calls) the main agent
+    /// may take in one turn before it stops and hands control back
+    /// (default: 1000).
+    pub fn get_agent_max_steps(&self) -> usize {
+        self.agent_max_steps.unwrap_or(1000)
+    }
+
+    /// Whether tool-call arguments are validated against the tool's schema
+    /// before execution, so mismatches go back to the model (default: true).
+    pub fn get_validate_tool_arguments(&self) -> bool {
+        self.validate_tool_arguments.unwrap_or(true)
+    }
+
     pub fn conversation_storage_mode(&self) -> ConversationStorageMode {
         self.conversation_storage.unwrap_or_default()
     }
@@ -635,6 +1206,99 @@ impl AppConfig {
         Ok(crate::storage::resolve_memory_root(mode, cwd, &data_dir))
     }
 
+    pub fn history_dedup_mode(&self) -> HistoryDedupMode {
+        self.history_dedup.unwrap_or_default()
+    }
+
+    /// Prompt history capacity, at least one entry (default: 1000).
+    pub fn history_size(&self) -> usize {
+        self.history_size
+            .unwrap_or(crate::history::DEFAULT_HISTORY_SIZE)
+            .max(1)
+    }
+
+    /// Redactor for tool output, seeded with every configured backend API key.
+    /// Returns `None` when redaction is disabled.
+    pub fn secret_redactor(&self) -> Option<SecretRedactor> {
+        SecretRedactor::from_config(
+            &self.secret_redaction.clone().unwrap_or_default(),
+            self.known_secrets(),
+        )
+    }
+
+    /// Scanner that fences off tool output which looks like it is giving the
+    /// agent instructions. Returns `None` when the check is disabled.
+    pub fn injection_scanner(&self) -> Option<InjectionScanner> {
+        InjectionScanner::from_config(&self.prompt_injection.clone().unwrap_or_default())
+    }
+
+    pub fn prompt_secret_check(&self) -> PromptSecretCheck {
+        self.prompt_secret_check.unwrap_or_default()
+    }
+
+    /// Scanner for secrets in the user's own prompts, or `None` when
+    /// `prompt_secret_check = "off"`. Uses the `[secret_redaction]` patterns
+    /// even when redaction of tool output is disabled.
+    pub fn prompt_secret_scanner(&self) -> Option<SecretRedactor> {
+        if self.prompt_secret_check() == PromptSecretCheck::Off {
+            return None;
+        }
+        let config = SecretRedactionConfig {
+            enabled: true,
+            ..self.secret_redaction.clone().unwrap_or_default()
+        };
+        SecretRedactor::from_config(&config, self.known_secrets())
+    }
+
+    fn known_secrets(&self) -> Vec<String> {
+        self.backends
+            .values()
+            .filter_map(|backend| backend.api_key.clone())
+            .chain(self.github_token())
+            .collect()
+    }
+
+    pub fn command_blocklist(&self) -> CommandBlocklist {
+        CommandBlocklist::from_config(&self.bash_blocklist.clone().unwrap_or_default())
+    }
+
+    pub fn bash_config(&self) -> BashConfig {
+        self.bash.clone().unwrap_or_default()
+    }
+
+    pub fn read_limits(&self) -> ReadLimits {
+        self.read_limits.unwrap_or_default()
+    }
+
+    /// Settings for the recall tool; the tool is only offered when set.
+    pub fn recall_config(&self) -> Option<&RecallConfig> {
+        self.recall.as_ref()
+    }
+
+    pub fn notification_mode(&self) -> NotificationMode {
+        self.notifications.unwrap_or_default()
+    }
+
+    /// Token for the GitHub tool: `github_token`, then `GH_TOKEN`, then
+    /// `GITHUB_TOKEN`.
+    pub fn github_token(&self) -> Option<String> {
+        self.github_token
+            .clone()
+            .or_else(|| std::env::var("GH_TOKEN").ok())
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+            .filter(|token| !token.trim().is_empty())
+    }
+
+    pub fn tool_policies(&self) -> ToolPolicies {
+        self.tool_policies.clone().unwrap_or_default()
+    }
+
+    /// Decision for tools no saved rule or `[tool_policies]` entry covers
+    /// (default: prompt).
+    pub fn default_permission(&self) -> ToolPolicy {
+        self.default_permission.unwrap_or(ToolPolicy::Prompt)
+    }
+
     pub fn skill_mode(&self) -> SkillStorageMode {
         self.skill_mode.unwrap_or_default()
     }
@@ -644,11 +1308,20 @@ impl AppConfig {
         Ok(resolve_skill_roots(self.skill_mode(), cwd, &data_dir))
     }
 
+    /// The nearest `.hoosh/config.toml` at or above the current directory, or
+    /// the one the current directory would hold when none exists yet.
     pub fn project_config_path() -> ConfigResult<PathBuf> {
-        let mut path = std::env::current_dir().map_err(ConfigError::IoError)?;
-        path.push(".hoosh");
-        path.push("config.toml");
-        Ok(path)
+        let cwd = std::env::current_dir().map_err(ConfigError::IoError)?;
+        Ok(discover_project_config(&cwd).unwrap_or_else(|| cwd.join(".hoosh").join("config.toml")))
+    }
+
+    /// The directory holding the discovered `.hoosh/`, falling back to the
+    /// current directory.
+    pub fn project_root() -> ConfigResult<PathBuf> {
+        let cwd = std::env::current_dir().map_err(ConfigError::IoError)?;
+        Ok(discover_project_config(&cwd)
+            .and_then(|path| path.parent()?.parent().map(Path::to_path_buf))
+            .unwrap_or(cwd))
     }
 
     pub fn merge(&mut self, other: ProjectConfig) {
@@ -685,6 +1358,34 @@ impl AppConfig {
             self.core_reminder_token_threshold = other.core_reminder_token_threshold;
         }
 
+        if other.core_reminder_step_interval.is_some() {
+            self.core_reminder_step_interval = other.core_reminder_step_interval;
+        }
+
+        if other.git_status_reminder_interval.is_some() {
+            self.git_status_reminder_interval = other.git_status_reminder_interval;
+        }
+
+        if other.system_reminders.is_some() {
+            self.system_reminders = other.system_reminders;
+        }
+
+        if other.tool_retries.is_some() {
+            self.tool_retries = other.tool_retries;
+        }
+
+        if other.turn_retry_budget.is_some() {
+            self.turn_retry_budget = other.turn_retry_budget;
+        }
+
+        if other.agent_max_steps.is_some() {
+            self.agent_max_steps = other.agent_max_steps;
+        }
+
+        if other.validate_tool_arguments.is_some() {
+            self.validate_tool_arguments = other.validate_tool_arguments;
+        }
+
         if other.conversation_storage.is_some() {
             self.conversation_storage = other.conversation_storage;
         }
@@ -719,14 +1420,9 @@ impl AppConfig {
             let empty_config = "# Project-specific configuration\n\
                 # This file overrides settings from ~/.config/hoosh/config.toml\n\
                 # Only specify settings you want to override here\n\n";
-            fs::write(&project_path, empty_config).map_err(ConfigError::IoError)?;
-
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let permissions = std::fs::Permissions::from_mode(0o600);
-                fs::set_permissions(&project_path, permissions).map_err(ConfigError::IoError)?;
-            }
+            write_config_atomically(&project_path, |file| {
+                std::io::Write::write_all(file, empty_config.as_bytes())
+            })?;
         }
 
         Ok(())
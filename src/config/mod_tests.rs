@@ -58,11 +58,19 @@ fn backend_config_fields_are_optional() {
         base_url: None,
         chat_api: None,
         temperature: None,
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     assert!(backend.api_key.is_none());
@@ -80,11 +88,19 @@ fn backend_config_can_be_fully_populated() {
         base_url: Some("https://api.example.com".to_string()),
         chat_api: Some("chat".to_string()),
         temperature: Some(0.7),
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     assert_eq!(backend.api_key, Some("test-key".to_string()));
@@ -104,6 +120,8 @@ fn agent_config_has_file_and_optional_fields() {
         description: Some("Test agent".to_string()),
         tags: vec!["coding".to_string(), "debug".to_string()],
         core_instructions_file: None,
+        backend: None,
+        model: None,
     };
 
     assert_eq!(agent.file, "test.txt");
@@ -111,6 +129,65 @@ fn agent_config_has_file_and_optional_fields() {
     assert_eq!(agent.tags.len(), 2);
 }
 
+#[test]
+fn agent_overrides_switch_effective_backend_and_model() {
+    let config: AppConfig = toml::from_str(
+        r#"
+default_backend = "openai"
+
+[backends.openai]
+model = "gpt-4o"
+
+[backends.anthropic]
+model = "claude-sonnet-4-5"
+
+[agents.reviewer]
+file = "reviewer.txt"
+backend = "anthropic"
+model = "claude-opus-4-1"
+
+[agents.assistant]
+file = "assistant.txt"
+model = "gpt-4o-mini"
+
+[agents.coder]
+file = "coder.txt"
+"#,
+    )
+    .unwrap();
+
+    let effective_model = |agent: &str| {
+        let config = config.with_agent_overrides(agent);
+        let model = config
+            .get_backend_config(&config.default_backend)
+            .and_then(|b| b.model.clone());
+        (config.default_backend, model)
+    };
+
+    assert_eq!(
+        effective_model("reviewer"),
+        ("anthropic".to_string(), Some("claude-opus-4-1".to_string()))
+    );
+    assert_eq!(
+        effective_model("assistant"),
+        ("openai".to_string(), Some("gpt-4o-mini".to_string()))
+    );
+    assert_eq!(
+        effective_model("coder"),
+        ("openai".to_string(), Some("gpt-4o".to_string()))
+    );
+    // The source config keeps the global selection
+    assert_eq!(config.default_backend, "openai");
+    assert_eq!(
+        config
+            .get_backend_config("openai")
+            .unwrap()
+            .model
+            .as_deref(),
+        Some("gpt-4o")
+    );
+}
+
 #[test]
 fn agent_config_tags_default_to_empty() {
     let agent = AgentConfig {
@@ -118,6 +195,8 @@ fn agent_config_tags_default_to_empty() {
         description: None,
         tags: vec![],
         core_instructions_file: None,
+        backend: None,
+        model: None,
     };
 
     assert!(agent.tags.is_empty());
@@ -151,11 +230,19 @@ fn get_backend_config_returns_config_when_exists() {
         base_url: None,
         chat_api: None,
         temperature: None,
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     config.set_backend_config("test".to_string(), backend);
@@ -174,11 +261,19 @@ fn set_backend_config_adds_new_backend() {
         base_url: None,
         chat_api: None,
         temperature: None,
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     config.set_backend_config("new_backend".to_string(), backend);
@@ -205,6 +300,56 @@ fn update_backend_setting_creates_backend_if_not_exists() {
     );
 }
 
+#[test]
+fn update_backend_setting_parses_context_window() {
+    let mut config = AppConfig::default();
+
+    config
+        .update_backend_setting("mock", "context_window", "64000".to_string())
+        .unwrap();
+    assert_eq!(
+        config.context_window(&crate::backends::MockBackend::new()),
+        64_000
+    );
+
+    let result = config.update_backend_setting("mock", "context_window", "lots".to_string());
+    assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+}
+
+#[test]
+fn update_backend_setting_parses_stop_sequences() {
+    let mut config = AppConfig::default();
+
+    config
+        .update_backend_setting("mock", "stop", r#"["</answer>", "\nUser:"]"#.to_string())
+        .unwrap();
+    assert_eq!(
+        config.backends["mock"].stop,
+        Some(vec!["</answer>".to_string(), "\nUser:".to_string()])
+    );
+
+    let too_many = r#"["a", "b", "c", "d", "e"]"#.to_string();
+    let result = config.update_backend_setting("mock", "stop", too_many);
+    assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+    let result = config.update_backend_setting("mock", "stop", "</answer>".to_string());
+    assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+
+    config
+        .update_backend_setting("mock", "stop", "[]".to_string())
+        .unwrap();
+    assert_eq!(config.backends["mock"].stop, None);
+}
+
+#[test]
+fn context_window_falls_back_when_model_unknown() {
+    let config = AppConfig::default();
+
+    assert_eq!(
+        config.context_window(&crate::backends::MockBackend::new()),
+        crate::backends::context_window::FALLBACK_CONTEXT_WINDOW
+    );
+}
+
 #[test]
 fn update_backend_setting_updates_api_key() {
     let mut config = AppConfig::default();
@@ -288,6 +433,40 @@ fn update_backend_setting_rejects_invalid_temperature() {
     ));
 }
 
+#[test]
+fn update_backend_setting_rejects_out_of_range_sampling() {
+    let mut config = AppConfig::default();
+
+    for (key, value) in [
+        ("temperature", "2.5"),
+        ("temperature", "-0.1"),
+        ("top_p", "1.1"),
+        ("top_p", "-1"),
+    ] {
+        let result = config.update_backend_setting("test", key, value.to_string());
+        assert!(
+            matches!(result, Err(ConfigError::InvalidValue { .. })),
+            "{key}={value}"
+        );
+    }
+}
+
+#[test]
+fn update_backend_setting_updates_top_p() {
+    let mut config = AppConfig::default();
+
+    config
+        .update_backend_setting("test", "top_p", "0.9".to_string())
+        .unwrap();
+    config
+        .update_backend_setting("test", "temperature", "2".to_string())
+        .unwrap();
+
+    let backend = config.get_backend_config("test").unwrap();
+    assert_eq!(backend.top_p, Some(0.9));
+    assert_eq!(backend.temperature, Some(2.0));
+}
+
 #[test]
 fn update_backend_setting_rejects_unknown_key() {
     let mut config = AppConfig::default();
@@ -397,11 +576,19 @@ fn merge_overwrites_backends() {
             base_url: None,
             chat_api: None,
             temperature: None,
+            top_p: None,
             pricing_endpoint: None,
             thinking_budget: None,
             reasoning_effort: None,
             reasoning_display: None,
             streaming: None,
+            context_window: None,
+            title_model: None,
+            max_concurrent_requests: None,
+            requests_per_minute: None,
+            retry: None,
+            tool_api: None,
+            stop: None,
         },
     );
 
@@ -414,11 +601,19 @@ fn merge_overwrites_backends() {
             base_url: None,
             chat_api: None,
             temperature: None,
+            top_p: None,
             pricing_endpoint: None,
             thinking_budget: None,
             reasoning_effort: None,
             reasoning_display: None,
             streaming: None,
+            context_window: None,
+            title_model: None,
+            max_concurrent_requests: None,
+            requests_per_minute: None,
+            retry: None,
+            tool_api: None,
+            stop: None,
         },
     );
 
@@ -441,11 +636,19 @@ fn merge_preserves_unspecified_backend_fields() {
             base_url: Some("https://global.example".to_string()),
             chat_api: None,
             temperature: None,
+            top_p: None,
             pricing_endpoint: None,
             thinking_budget: None,
             reasoning_effort: None,
             reasoning_display: None,
             streaming: None,
+            context_window: None,
+            title_model: None,
+            max_concurrent_requests: None,
+            requests_per_minute: None,
+            retry: None,
+            tool_api: None,
+            stop: None,
         },
     );
 
@@ -458,11 +661,19 @@ fn merge_preserves_unspecified_backend_fields() {
             base_url: None,
             chat_api: None,
             temperature: None,
+            top_p: None,
             pricing_endpoint: None,
             thinking_budget: Some(20000),
             reasoning_effort: None,
             reasoning_display: None,
             streaming: None,
+            context_window: None,
+            title_model: None,
+            max_concurrent_requests: None,
+            requests_per_minute: None,
+            retry: None,
+            tool_api: None,
+            stop: None,
         },
     );
 
@@ -485,6 +696,8 @@ fn merge_overwrites_agents() {
             description: None,
             tags: vec![],
             core_instructions_file: None,
+            backend: None,
+            model: None,
         },
     );
 
@@ -496,6 +709,8 @@ fn merge_overwrites_agents() {
             description: Some("Updated".to_string()),
             tags: vec![],
             core_instructions_file: None,
+            backend: None,
+            model: None,
         },
     );
 
@@ -591,6 +806,310 @@ fn merge_updates_context_manager() {
     assert_eq!(config.context_manager, Some(new_ctx));
 }
 
+#[test]
+fn merge_updates_core_reminder_step_interval() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.get_core_reminder_step_interval(), 0);
+    let project_config = ProjectConfig {
+        core_reminder_step_interval: Some(5),
+        ..Default::default()
+    };
+
+    config.merge(project_config);
+
+    assert_eq!(config.get_core_reminder_step_interval(), 5);
+}
+
+#[test]
+fn git_status_reminder_interval_defaults_to_ten() {
+    assert_eq!(AppConfig::default().get_git_status_reminder_interval(), 10);
+}
+
+#[test]
+fn merge_updates_git_status_reminder_interval() {
+    let mut config = AppConfig::default();
+    let project_config = ProjectConfig {
+        git_status_reminder_interval: Some(0),
+        ..Default::default()
+    };
+
+    config.merge(project_config);
+
+    assert_eq!(config.get_git_status_reminder_interval(), 0);
+}
+
+#[test]
+fn tool_retries_default_and_merge() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.get_tool_retries(), 2);
+
+    config.merge(ProjectConfig {
+        tool_retries: Some(0),
+        ..Default::default()
+    });
+
+    assert_eq!(config.get_tool_retries(), 0);
+}
+
+#[test]
+fn turn_retry_budget_default_and_merge() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.get_turn_retry_budget(), 10);
+
+    config.merge(ProjectConfig {
+        turn_retry_budget: Some(3),
+        ..Default::default()
+    });
+
+    assert_eq!(config.get_turn_retry_budget(), 3);
+}
+
+#[test]
+fn agent_max_steps_default_and_merge() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.get_agent_max_steps(), 1000);
+
+    config.merge(ProjectConfig {
+        agent_max_steps: Some(50),
+        ..Default::default()
+    });
+
+    assert_eq!(config.get_agent_max_steps(), 50);
+}
+
+#[test]
+fn validate_tool_arguments_default_and_merge() {
+    let mut config = AppConfig::default();
+    assert!(config.get_validate_tool_arguments());
+
+    config.merge(ProjectConfig {
+        validate_tool_arguments: Some(false),
+        ..Default::default()
+    });
+
+    assert!(!config.get_validate_tool_arguments());
+}
+
+#[test]
+fn history_dedup_defaults_to_consecutive() {
+    assert_eq!(
+        AppConfig::default().history_dedup_mode(),
+        crate::history::HistoryDedupMode::Consecutive
+    );
+}
+
+#[test]
+fn deserialize_history_dedup_mode() {
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\nhistory_dedup = \"global\"").unwrap();
+    assert_eq!(
+        config.history_dedup_mode(),
+        crate::history::HistoryDedupMode::Global
+    );
+}
+
+#[test]
+fn history_size_defaults_and_overrides() {
+    assert_eq!(
+        AppConfig::default().history_size(),
+        crate::history::DEFAULT_HISTORY_SIZE
+    );
+
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\nhistory_size = 250").unwrap();
+    assert_eq!(config.history_size(), 250);
+
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"\nhistory_size = 0").unwrap();
+    assert_eq!(config.history_size(), 1);
+}
+
+#[test]
+fn secret_redactor_masks_configured_api_keys() {
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"openai\"\n[backends.openai]\napi_key = \"custom-key-0123456789\"",
+    )
+    .unwrap();
+    let redactor = config.secret_redactor().unwrap();
+
+    assert_eq!(
+        redactor.redact("key is custom-key-0123456789"),
+        "key is [REDACTED]"
+    );
+}
+
+#[test]
+fn secret_redactor_can_be_disabled() {
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\n[secret_redaction]\nenabled = false").unwrap();
+    assert!(config.secret_redactor().is_none());
+}
+
+#[test]
+fn command_blocklist_uses_defaults_and_custom_patterns() {
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"mock\"\n[bash_blocklist]\npatterns = ['^terraform destroy']",
+    )
+    .unwrap();
+    let blocklist = config.command_blocklist();
+
+    assert!(blocklist.check("rm -rf /").is_some());
+    assert!(blocklist.check("terraform destroy -auto-approve").is_some());
+    assert!(blocklist.check("terraform plan").is_none());
+}
+
+#[test]
+fn bash_config_parses_env_table() {
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"mock\"\n[bash]\nclear_env = true\n[bash.env]\nRUST_LOG = \"debug\"",
+    )
+    .unwrap();
+    let bash = config.bash_config();
+
+    assert!(bash.clear_env);
+    assert_eq!(bash.env.get("RUST_LOG").map(String::as_str), Some("debug"));
+}
+
+#[test]
+fn bash_config_defaults_to_inherited_env() {
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"").unwrap();
+    assert_eq!(
+        config.bash_config(),
+        crate::tools::bash::BashConfig::default()
+    );
+}
+
+#[test]
+fn read_limits_fill_unset_fields_with_defaults() {
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\n[read_limits]\nmax_bytes = 1000").unwrap();
+    let limits = config.read_limits();
+    let defaults = crate::tools::file_ops::ReadLimits::default();
+
+    assert_eq!(limits.max_bytes, 1000);
+    assert_eq!(limits.warn_bytes, defaults.warn_bytes);
+}
+
+#[test]
+fn github_token_from_config_is_redacted() {
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\ngithub_token = \"ghp_configuredtoken123\"")
+            .unwrap();
+
+    assert_eq!(
+        config.github_token().as_deref(),
+        Some("ghp_configuredtoken123")
+    );
+    let redactor = config.secret_redactor().unwrap();
+    assert!(
+        !redactor
+            .redact("token ghp_configuredtoken123")
+            .contains("ghp_configuredtoken123")
+    );
+}
+
+#[test]
+fn notification_mode_defaults_to_off() {
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"").unwrap();
+    assert_eq!(config.notification_mode(), NotificationMode::Off);
+
+    let config: AppConfig =
+        toml::from_str("default_backend = \"mock\"\nnotifications = \"bell\"").unwrap();
+    assert_eq!(config.notification_mode(), NotificationMode::Bell);
+}
+
+#[test]
+fn tool_policies_parse_from_table() {
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"mock\"\n[tool_policies]\nread_file = \"allow\"\nbash = \"prompt\"\ndestructive = \"deny\"",
+    )
+    .unwrap();
+
+    let policies = config.tool_policies();
+    let expected = crate::permissions::ToolPolicies::new(HashMap::from([
+        (
+            "read_file".to_string(),
+            crate::permissions::ToolPolicy::Allow,
+        ),
+        ("bash".to_string(), crate::permissions::ToolPolicy::Prompt),
+        (
+            "destructive".to_string(),
+            crate::permissions::ToolPolicy::Deny,
+        ),
+    ]));
+    assert_eq!(policies, expected);
+}
+
+#[test]
+fn tool_policies_default_to_empty() {
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"").unwrap();
+    assert!(config.tool_policies().is_empty());
+}
+
+#[test]
+fn default_permission_parses_each_value() {
+    use crate::permissions::ToolPolicy;
+
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"").unwrap();
+    assert_eq!(config.default_permission(), ToolPolicy::Prompt);
+
+    for (value, expected) in [
+        ("prompt", ToolPolicy::Prompt),
+        ("allow", ToolPolicy::Allow),
+        ("deny", ToolPolicy::Deny),
+    ] {
+        let config: AppConfig = toml::from_str(&format!(
+            "default_backend = \"mock\"\ndefault_permission = \"{value}\""
+        ))
+        .unwrap();
+        assert_eq!(config.default_permission(), expected);
+    }
+}
+
+#[test]
+fn injection_scanner_on_by_default_and_toggleable() {
+    let mut config = AppConfig::default();
+    assert!(config.injection_scanner().is_some());
+
+    config.prompt_injection = Some(crate::prompt_injection::PromptInjectionConfig {
+        enabled: false,
+        ..Default::default()
+    });
+    assert!(config.injection_scanner().is_none());
+}
+
+#[test]
+fn prompt_secret_scanner_knows_configured_keys_unless_off() {
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"mock\"\n[secret_redaction]\nenabled = false\n[backends.openai]\napi_key = \"my-openai-key-1234\"",
+    )
+    .unwrap();
+    assert_eq!(
+        config.prompt_secret_check(),
+        crate::secret_redaction::PromptSecretCheck::Confirm
+    );
+    let scanner = config.prompt_secret_scanner().unwrap();
+    assert!(scanner.contains_secret("oops my-openai-key-1234"));
+
+    let off: AppConfig =
+        toml::from_str("default_backend = \"mock\"\nprompt_secret_check = \"off\"").unwrap();
+    assert!(off.prompt_secret_scanner().is_none());
+}
+
+#[test]
+fn recall_is_unset_by_default_and_top_k_defaults_to_five() {
+    let config: AppConfig = toml::from_str("default_backend = \"mock\"").unwrap();
+    assert!(config.recall_config().is_none());
+
+    let config: AppConfig = toml::from_str(
+        "default_backend = \"mock\"\n[recall]\nbackend = \"ollama\"\nmodel = \"nomic-embed-text\"",
+    )
+    .unwrap();
+    let recall = config.recall_config().unwrap();
+    assert_eq!(recall.backend, "ollama");
+    assert_eq!(recall.model, "nomic-embed-text");
+    assert_eq!(recall.top_k(), 5);
+}
+
 #[test]
 fn config_path_uses_home_directory() {
     let path = AppConfig::config_path();
@@ -602,6 +1121,127 @@ fn config_path_uses_home_directory() {
     assert!(path.to_str().unwrap().ends_with("config.toml"));
 }
 
+#[test]
+fn config_dir_env_override_takes_precedence() {
+    let dir = resolve_config_dir(
+        Some("/tmp/hoosh-test".into()),
+        Some("/xdg".into()),
+        Some("/home/u".into()),
+    );
+
+    assert_eq!(dir, Some(PathBuf::from("/tmp/hoosh-test")));
+}
+
+#[test]
+fn config_dir_respects_xdg_config_home() {
+    let dir = resolve_config_dir(None, Some("/xdg".into()), Some("/home/u".into()));
+    assert_eq!(dir, Some(PathBuf::from("/xdg/hoosh")));
+
+    // Empty or relative XDG_CONFIG_HOME falls back to ~/.config
+    for xdg in ["", "relative/xdg"] {
+        let dir = resolve_config_dir(Some("".into()), Some(xdg.into()), Some("/home/u".into()));
+        assert_eq!(dir, Some(PathBuf::from("/home/u/.config/hoosh")));
+    }
+
+    assert_eq!(resolve_config_dir(None, None, None), None);
+}
+
+#[test]
+fn data_dir_follows_env_then_xdg_then_home() {
+    assert_eq!(
+        resolve_data_dir(
+            Some("/tmp/hoosh-data".into()),
+            Some("/xdg".into()),
+            Some("/home/u".into())
+        ),
+        Some(PathBuf::from("/tmp/hoosh-data"))
+    );
+    assert_eq!(
+        resolve_data_dir(None, Some("/xdg".into()), Some("/home/u".into())),
+        Some(PathBuf::from("/xdg/hoosh"))
+    );
+    assert_eq!(
+        resolve_data_dir(Some("".into()), Some("rel".into()), Some("/home/u".into())),
+        Some(PathBuf::from("/home/u/.local/share/hoosh"))
+    );
+}
+
+#[test]
+fn data_dir_keeps_existing_home_data_until_the_xdg_dir_exists() {
+    let home = tempfile::tempdir().unwrap();
+    let xdg = tempfile::tempdir().unwrap();
+    let legacy = home.path().join(".local").join("share").join("hoosh");
+    std::fs::create_dir_all(&legacy).unwrap();
+
+    let resolve = || resolve_data_dir(None, Some(xdg.path().into()), Some(home.path().into()));
+    assert_eq!(resolve(), Some(legacy));
+
+    std::fs::create_dir(xdg.path().join("hoosh")).unwrap();
+    assert_eq!(resolve(), Some(xdg.path().join("hoosh")));
+}
+
+#[test]
+fn project_scoped_storage_writes_under_project_dir() {
+    let project = tempfile::tempdir().unwrap();
+    let config = AppConfig {
+        conversation_storage: Some(crate::storage::ConversationStorageMode::Local),
+        ..AppConfig::default()
+    };
+
+    let root = config
+        .conversation_storage_root(project.path())
+        .unwrap()
+        .expect("storage enabled");
+    let storage = crate::ConversationStorage::with_root(&root);
+    storage.create_conversation("conv_project").unwrap();
+
+    let dir = project.path().join(".hoosh").join("conversations");
+    assert!(dir.join("conv_project").join("meta.json").is_file());
+    assert_eq!(storage.list_conversations().unwrap().len(), 1);
+}
+
+#[test]
+fn atomic_config_write_replaces_file_with_0600() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "old = true\n").unwrap();
+
+    write_config_atomically(&path, |file| {
+        std::io::Write::write_all(file, b"default_backend = \"mock\"\n")
+    })
+    .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "default_backend = \"mock\"\n"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+}
+
+#[test]
+fn interrupted_config_write_leaves_previous_config_intact() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "default_backend = \"mock\"\n").unwrap();
+
+    let result = write_config_atomically(&path, |file| {
+        std::io::Write::write_all(file, b"default_backend = \"mo")?;
+        Err(std::io::Error::other("simulated crash"))
+    });
+
+    assert!(result.is_err());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "default_backend = \"mock\"\n"
+    );
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
 #[test]
 fn project_config_path_uses_current_directory() {
     let path = AppConfig::project_config_path();
@@ -612,6 +1252,59 @@ fn project_config_path_uses_current_directory() {
     assert!(path.to_str().unwrap().ends_with("config.toml"));
 }
 
+#[test]
+fn discover_project_config_walks_up_to_nearest_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let nested = root.join("crates").join("core").join("src");
+    fs::create_dir_all(&nested).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::create_dir_all(root.join(".hoosh")).unwrap();
+    fs::write(root.join(".hoosh/config.toml"), "").unwrap();
+
+    assert_eq!(
+        discover_project_config(&nested),
+        Some(root.join(".hoosh").join("config.toml"))
+    );
+
+    fs::create_dir_all(root.join("crates/.hoosh")).unwrap();
+    fs::write(root.join("crates/.hoosh/config.toml"), "").unwrap();
+    assert_eq!(
+        discover_project_config(&nested),
+        Some(root.join("crates").join(".hoosh").join("config.toml"))
+    );
+}
+
+#[test]
+fn discover_project_config_stops_at_git_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let outer = dir.path();
+    let repo = outer.join("repo");
+    let nested = repo.join("src");
+    fs::create_dir_all(&nested).unwrap();
+    fs::create_dir_all(repo.join(".git")).unwrap();
+    fs::create_dir_all(outer.join(".hoosh")).unwrap();
+    fs::write(outer.join(".hoosh/config.toml"), "").unwrap();
+
+    assert_eq!(discover_project_config(&nested), None);
+}
+
+#[test]
+fn discover_project_config_ignores_home_config_outside_git() {
+    let dir = tempfile::tempdir().unwrap();
+    let home = dir.path();
+    let scratch = home.join("Downloads").join("notes");
+    fs::create_dir_all(&scratch).unwrap();
+    fs::create_dir_all(home.join(".hoosh")).unwrap();
+    fs::write(home.join(".hoosh/config.toml"), "").unwrap();
+
+    assert_eq!(discover_project_config(&scratch), None);
+    assert_eq!(
+        discover_project_config(home),
+        Some(home.join(".hoosh").join("config.toml"))
+    );
+}
+
 #[test]
 fn serialize_backend_config_to_toml() {
     let backend = BackendConfig {
@@ -620,11 +1313,19 @@ fn serialize_backend_config_to_toml() {
         base_url: None,
         chat_api: None,
         temperature: Some(0.7),
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     let toml = toml::to_string(&backend).unwrap();
@@ -658,6 +1359,8 @@ fn serialize_agent_config_to_toml() {
         description: Some("Coding assistant".to_string()),
         tags: vec!["coding".to_string(), "rust".to_string()],
         core_instructions_file: None,
+        backend: None,
+        model: None,
     };
 
     let toml = toml::to_string(&agent).unwrap();
@@ -778,11 +1481,19 @@ fn clone_backend_config() {
         base_url: None,
         chat_api: None,
         temperature: Some(0.5),
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     let cloned = backend.clone();
@@ -799,6 +1510,8 @@ fn clone_agent_config() {
         description: Some("Test".to_string()),
         tags: vec!["tag1".to_string()],
         core_instructions_file: None,
+        backend: None,
+        model: None,
     };
 
     let cloned = agent.clone();
@@ -827,11 +1540,19 @@ fn debug_format_backend_config() {
         base_url: None,
         chat_api: None,
         temperature: None,
+        top_p: None,
         pricing_endpoint: None,
         thinking_budget: None,
         reasoning_effort: None,
         reasoning_display: None,
         streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
     };
 
     let debug_str = format!("{:?}", backend);
@@ -847,6 +1568,8 @@ fn debug_format_agent_config() {
         description: None,
         tags: vec![],
         core_instructions_file: None,
+        backend: None,
+        model: None,
     };
 
     let debug_str = format!("{:?}", agent);
@@ -973,3 +1696,66 @@ fn project_config_memory_storage_overrides_app_config() {
     app.merge(project);
     assert_eq!(app.memory_storage, Some(ConversationStorageMode::Central));
 }
+
+fn reasoning_backend(
+    thinking_budget: Option<u32>,
+    reasoning_effort: Option<ReasoningEffort>,
+) -> BackendConfig {
+    BackendConfig {
+        api_key: None,
+        model: None,
+        base_url: None,
+        chat_api: None,
+        temperature: None,
+        top_p: None,
+        pricing_endpoint: None,
+        thinking_budget,
+        reasoning_effort,
+        reasoning_display: None,
+        streaming: None,
+        context_window: None,
+        title_model: None,
+        max_concurrent_requests: None,
+        requests_per_minute: None,
+        retry: None,
+        tool_api: None,
+        stop: None,
+    }
+}
+
+#[test]
+fn validate_reasoning_rejects_small_anthropic_budget() {
+    let result = reasoning_backend(Some(500), None).validate_reasoning("anthropic");
+    assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+
+    let ok = reasoning_backend(Some(MIN_THINKING_BUDGET), None).validate_reasoning("anthropic");
+    assert!(matches!(ok, Ok(None)));
+}
+
+#[test]
+fn validate_reasoning_warns_about_unsupported_settings() {
+    let effort_on_anthropic = reasoning_backend(None, Some(ReasoningEffort::High))
+        .validate_reasoning("anthropic")
+        .unwrap();
+    assert!(effort_on_anthropic.unwrap().contains("reasoning_effort"));
+
+    let both_on_ollama = reasoning_backend(Some(8000), Some(ReasoningEffort::Low))
+        .validate_reasoning("ollama")
+        .unwrap()
+        .unwrap();
+    assert!(both_on_ollama.contains("thinking_budget or reasoning_effort"));
+
+    let effort_on_openai = reasoning_backend(None, Some(ReasoningEffort::High))
+        .validate_reasoning("openai")
+        .unwrap();
+    assert!(effort_on_openai.is_none());
+}
+
+#[test]
+fn reasoning_effort_parses_names() {
+    assert_eq!(
+        "xhigh".parse::<ReasoningEffort>(),
+        Ok(ReasoningEffort::Xhigh)
+    );
+    assert!("extreme".parse::<ReasoningEffort>().is_err());
+}
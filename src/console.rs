@@ -1,4 +1,6 @@
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
+use std::ffi::OsString;
+use std::io::{IsTerminal, Write};
 use std::sync::{Arc, OnceLock};
 
 use crate::terminal_markdown::TerminalMarkdownRenderer;
@@ -56,16 +58,45 @@ impl std::str::FromStr for VerbosityLevel {
     }
 }
 
+/// Whether ANSI styling may be written to a stream: only when it is a
+/// terminal, and never when `NO_COLOR` is set to a non-empty value
+/// (<https://no-color.org>).
+pub fn styling_enabled(is_terminal: bool) -> bool {
+    is_terminal && !no_color_requested(std::env::var_os("NO_COLOR"))
+}
+
+fn no_color_requested(value: Option<OsString>) -> bool {
+    value.is_some_and(|v| !v.is_empty())
+}
+
+/// `prefix message` on its own line, with `prefix` styled only if `styled`.
+fn write_marked(out: &mut impl Write, styled: bool, prefix: ColoredString, message: &str) {
+    let _ = if styled {
+        writeln!(out, "{} {}", prefix, message)
+    } else {
+        writeln!(out, "{} {}", &*prefix, message)
+    };
+}
+
 /// Simple output utilities for non-interactive CLI commands.
 /// Interactive chat uses the TUI system.
+///
+/// Styling is decided per stream when the console is created, so redirecting
+/// stdout to a file keeps it plain while errors on a terminal stay colored.
 #[derive(Debug, Clone)]
 pub struct Console {
     verbosity: VerbosityLevel,
+    style_stdout: bool,
+    style_stderr: bool,
 }
 
 impl Console {
     pub fn new(verbosity: VerbosityLevel) -> Self {
-        Self { verbosity }
+        Self {
+            verbosity,
+            style_stdout: styling_enabled(std::io::stdout().is_terminal()),
+            style_stderr: styling_enabled(std::io::stderr().is_terminal()),
+        }
     }
 
     pub fn set_verbosity(&mut self, verbosity: VerbosityLevel) {
@@ -82,25 +113,41 @@ impl Console {
 
     pub fn error(&self, message: &str) {
         if self.verbosity > VerbosityLevel::Quiet {
-            eprintln!("{} {}", "⡱⢎".red().bold(), message);
+            let prefix = "⡱⢎".red().bold();
+            write_marked(&mut std::io::stderr(), self.style_stderr, prefix, message);
         }
     }
 
     pub fn warning(&self, message: &str) {
         if self.should_show(VerbosityLevel::Normal) {
-            println!("{} {}", "⣴⣦".yellow(), message);
+            write_marked(
+                &mut std::io::stdout(),
+                self.style_stdout,
+                "⣴⣦".yellow(),
+                message,
+            );
         }
     }
 
     pub fn info(&self, message: &str) {
         if self.should_show(VerbosityLevel::Normal) {
-            println!("{} {}", "⢨".cyan(), message);
+            write_marked(
+                &mut std::io::stdout(),
+                self.style_stdout,
+                "⢨".cyan(),
+                message,
+            );
         }
     }
 
     pub fn success(&self, message: &str) {
         if self.should_show(VerbosityLevel::Normal) {
-            println!("{} {}", "⠢⠊".green(), message);
+            write_marked(
+                &mut std::io::stdout(),
+                self.style_stdout,
+                "⠢⠊".green(),
+                message,
+            );
         }
     }
 
@@ -112,7 +159,12 @@ impl Console {
 
     pub fn debug(&self, message: &str) {
         if self.should_show(VerbosityLevel::Debug) {
-            println!("{} {}", "⠪⢕".dimmed(), message);
+            write_marked(
+                &mut std::io::stdout(),
+                self.style_stdout,
+                "⠪⢕".dimmed(),
+                message,
+            );
         }
     }
 
@@ -122,11 +174,18 @@ impl Console {
         }
     }
 
+    /// Rendered with ANSI styling on a terminal; the markdown source itself
+    /// is the plain-text form, so it is printed as is otherwise.
     pub fn markdown(&self, markdown: &str) {
         if self.should_show(VerbosityLevel::Normal) {
-            let renderer = TerminalMarkdownRenderer::new();
-            let rendered = renderer.render(markdown);
-            print!("{}", rendered);
+            if self.style_stdout {
+                let renderer = TerminalMarkdownRenderer::new();
+                print!("{}", renderer.render(markdown));
+            } else if markdown.ends_with('\n') {
+                print!("{}", markdown);
+            } else {
+                println!("{}", markdown);
+            }
         }
     }
 
@@ -145,7 +204,6 @@ impl Console {
     /// Print an interactive prompt to stdout and flush immediately
     /// Use this for user input prompts like "(y/n):" to ensure it appears before reading stdin
     pub fn prompt(&self, message: &str) {
-        use std::io::Write;
         if self.should_show(VerbosityLevel::Normal) {
             print!("{}", message);
             let _ = std::io::stdout().flush();
@@ -154,7 +212,9 @@ impl Console {
 
     /// Clear the current line on stderr (for cleaning up spinner/progress indicators)
     pub fn clear_line(&self) {
-        use std::io::Write;
+        if !std::io::stderr().is_terminal() {
+            return;
+        }
         eprint!("\r\x1b[2K");
         let _ = std::io::stderr().flush();
     }
@@ -174,9 +234,7 @@ pub fn console() -> Arc<Console> {
 
 impl Default for Console {
     fn default() -> Self {
-        Self {
-            verbosity: VerbosityLevel::Normal,
-        }
+        Self::new(VerbosityLevel::Normal)
     }
 }
 
@@ -208,6 +266,33 @@ mod tests {
         assert_eq!(VerbosityLevel::Debug.to_string(), "debug");
     }
 
+    #[test]
+    fn test_no_color_requires_non_empty_value() {
+        assert!(!no_color_requested(None));
+        assert!(!no_color_requested(Some(OsString::new())));
+        assert!(no_color_requested(Some(OsString::from("1"))));
+    }
+
+    #[test]
+    fn test_non_tty_writer_gets_no_ansi_styling() {
+        colored::control::set_override(true);
+        let mut out = Vec::new();
+
+        write_marked(&mut out, styling_enabled(false), "⢨".cyan(), "hello");
+
+        assert_eq!(String::from_utf8(out).unwrap(), "⢨ hello\n");
+    }
+
+    #[test]
+    fn test_styled_writer_keeps_ansi_styling() {
+        colored::control::set_override(true);
+        let mut out = Vec::new();
+
+        write_marked(&mut out, true, "⢨".cyan(), "hello");
+
+        assert!(String::from_utf8(out).unwrap().contains("\x1b["));
+    }
+
     #[test]
     fn test_console_methods() {
         let console = Console::new(VerbosityLevel::Normal);
@@ -11,6 +11,63 @@ use crate::context_management::{
 #[async_trait]
 pub trait ContextManagementStrategy: Send + Sync {
     async fn apply(&self, conversation: &mut Conversation) -> Result<StrategyResult>;
+
+    /// Which configurable strategy this is, so `strategy_order` can place it.
+    /// Strategies without a kind run after the ordered ones, in the order
+    /// they were added.
+    fn kind(&self) -> Option<StrategyKind> {
+        None
+    }
+}
+
+/// The built-in strategies, as named in `strategy_order`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    SlidingWindow,
+    LogCompression,
+    ToolOutputTruncation,
+}
+
+fn default_strategy_order() -> Vec<StrategyKind> {
+    vec![
+        StrategyKind::SlidingWindow,
+        StrategyKind::LogCompression,
+        StrategyKind::ToolOutputTruncation,
+    ]
+}
+
+/// When the context manager runs its strategies on its own.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionTrigger {
+    /// Before every turn.
+    #[default]
+    EveryTurn,
+    /// Before a turn once token pressure reaches `compression_threshold`.
+    Threshold,
+    /// Never automatically; only via `/compact --strategies`.
+    Manual,
+}
+
+fn default_compression_threshold() -> f32 {
+    0.80
+}
+
+/// How an oversized tool output is cut down.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationMode {
+    /// Character-based: keep the first `max_length` characters, or
+    /// `head_length` + `tail_length` characters when `smart_truncate` is set.
+    #[default]
+    Chars,
+    /// Keep the first `head_lines` lines.
+    Head,
+    /// Keep the last `tail_lines` lines.
+    Tail,
+    /// Keep the first `head_lines` and last `tail_lines` lines.
+    HeadTail,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,12 +79,36 @@ pub struct ToolOutputTruncationConfig {
     pub tail_length: usize,
     #[serde(default = "default_preserve_last_tool_result")]
     pub preserve_last_tool_result: bool,
+    #[serde(default)]
+    pub mode: TruncationMode,
+    #[serde(default = "default_head_lines")]
+    pub head_lines: usize,
+    #[serde(default = "default_tail_lines")]
+    pub tail_lines: usize,
+    /// Replaces the omission notice; `{lines}` and `{chars}` are filled in
+    /// with how much was cut.
+    #[serde(default)]
+    pub truncation_marker: Option<String>,
+    /// Whole lines kept from the start and end of every truncated output,
+    /// whatever the mode and character budgets.
+    #[serde(default)]
+    pub min_head_lines: usize,
+    #[serde(default)]
+    pub min_tail_lines: usize,
 }
 
 fn default_preserve_last_tool_result() -> bool {
     true
 }
 
+fn default_head_lines() -> usize {
+    40
+}
+
+fn default_tail_lines() -> usize {
+    60
+}
+
 impl Default for ToolOutputTruncationConfig {
     fn default() -> Self {
         Self {
@@ -37,7 +118,39 @@ impl Default for ToolOutputTruncationConfig {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            mode: TruncationMode::default(),
+            head_lines: default_head_lines(),
+            tail_lines: default_tail_lines(),
+            truncation_marker: None,
+            min_head_lines: 0,
+            min_tail_lines: 0,
+        }
+    }
+}
+
+impl ToolOutputTruncationConfig {
+    /// The preserved minimums may not exceed the `head_lines`/`tail_lines`
+    /// they guarantee part of. Returns the offending field and why.
+    pub fn validate(&self) -> Result<(), (&'static str, String)> {
+        if self.min_head_lines > self.head_lines {
+            return Err((
+                "min_head_lines",
+                format!(
+                    "{} exceeds head_lines ({})",
+                    self.min_head_lines, self.head_lines
+                ),
+            ));
         }
+        if self.min_tail_lines > self.tail_lines {
+            return Err((
+                "min_tail_lines",
+                format!(
+                    "{} exceeds tail_lines ({})",
+                    self.min_tail_lines, self.tail_lines
+                ),
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -102,6 +215,15 @@ pub struct ContextManagerConfig {
     pub tool_output_truncation: Option<ToolOutputTruncationConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sliding_window: Option<SlidingWindowConfig>,
+    #[serde(default)]
+    pub trigger: CompactionTrigger,
+    /// Token pressure at which the `threshold` trigger fires.
+    #[serde(default = "default_compression_threshold")]
+    pub compression_threshold: f32,
+    /// Order the strategies run in. A configured strategy left out of the
+    /// list never runs.
+    #[serde(default = "default_strategy_order")]
+    pub strategy_order: Vec<StrategyKind>,
 }
 
 impl Default for ContextManagerConfig {
@@ -112,6 +234,9 @@ impl Default for ContextManagerConfig {
             log_compression: Some(LogCompressionConfig::default()),
             tool_output_truncation: Some(ToolOutputTruncationConfig::default()),
             sliding_window: Some(SlidingWindowConfig::default()),
+            trigger: CompactionTrigger::default(),
+            compression_threshold: default_compression_threshold(),
+            strategy_order: default_strategy_order(),
         }
     }
 }
@@ -126,6 +251,28 @@ impl ContextManagerConfig {
         self.warning_threshold = threshold.clamp(0.0, 1.0);
         self
     }
+
+    pub fn with_trigger(mut self, trigger: CompactionTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    pub fn with_strategy_order(mut self, order: Vec<StrategyKind>) -> Self {
+        self.strategy_order = order;
+        self
+    }
+
+    /// Sizes the budgets for a model with a `context_window`-token window:
+    /// pressure is measured against the whole window, and the sliding window
+    /// keeps the conversation within three quarters of it, leaving room for
+    /// the response.
+    pub fn with_context_window(mut self, context_window: usize) -> Self {
+        self.max_tokens = context_window;
+        if let Some(sliding_window) = self.sliding_window.as_mut() {
+            sliding_window.max_tokens = context_window / 4 * 3;
+        }
+        self
+    }
 }
 
 pub struct ContextManager {
@@ -153,7 +300,7 @@ impl ContextManager {
     }
 
     pub fn get_token_pressure(&self, conversation: &Conversation) -> f32 {
-        let current = conversation.estimate_token();
+        let current = self.token_accountant.context_tokens(conversation);
         (current as f32 / self.config.max_tokens as f32).min(1.0)
     }
 
@@ -169,13 +316,51 @@ impl ContextManager {
         self.token_accountant.statistics()
     }
 
-    pub fn record_token_usage(&self, input_tokens: usize, output_tokens: usize) {
+    pub fn record_token_usage(&self, record: TokenUsageRecord, conversation: &Conversation) {
         self.token_accountant
-            .record_usage(TokenUsageRecord::from_backend(input_tokens, output_tokens));
+            .record_turn_usage(record, conversation);
     }
 
+    /// Whether the configured trigger calls for running the strategies before
+    /// the next turn.
+    pub fn should_apply(&self, conversation: &Conversation) -> bool {
+        match self.config.trigger {
+            CompactionTrigger::EveryTurn => true,
+            CompactionTrigger::Threshold => {
+                self.get_token_pressure(conversation) >= self.config.compression_threshold
+            }
+            CompactionTrigger::Manual => false,
+        }
+    }
+
+    /// Strategies in the order they should run: those named in
+    /// `strategy_order` first, in that order, then any without a kind.
+    fn ordered_strategies(&self) -> Vec<&dyn ContextManagementStrategy> {
+        let mut ordered: Vec<&dyn ContextManagementStrategy> = Vec::new();
+        for (position, kind) in self.config.strategy_order.iter().enumerate() {
+            if self.config.strategy_order[..position].contains(kind) {
+                continue;
+            }
+            ordered.extend(
+                self.strategies
+                    .iter()
+                    .filter(|s| s.kind() == Some(*kind))
+                    .map(|s| s.as_ref()),
+            );
+        }
+        ordered.extend(
+            self.strategies
+                .iter()
+                .filter(|s| s.kind().is_none())
+                .map(|s| s.as_ref()),
+        );
+        ordered
+    }
+
+    /// Run the strategies regardless of the trigger; callers decide when
+    /// with `should_apply`.
     pub async fn apply_strategies(&self, conversation: &mut Conversation) -> Result<()> {
-        for strategy in &self.strategies {
+        for strategy in self.ordered_strategies() {
             let result = strategy.apply(conversation).await?;
 
             // If strategy reports target reached, stop processing further strategies
@@ -201,6 +386,14 @@ mod tests {
         assert_eq!(config.warning_threshold, 0.65);
     }
 
+    #[test]
+    fn test_with_context_window_scales_budgets() {
+        let config = ContextManagerConfig::default().with_context_window(200_000);
+
+        assert_eq!(config.max_tokens, 200_000);
+        assert_eq!(config.sliding_window.unwrap().max_tokens, 150_000);
+    }
+
     #[test]
     fn test_token_pressure_without_data() {
         let accountant = Arc::new(TokenAccountant::new());
@@ -286,6 +479,144 @@ mod tests {
         assert_eq!(stats.record_count, 2);
     }
 
+    fn big_tool_round() -> Conversation {
+        use crate::agent::{ConversationMessage, Role, ToolCall, ToolFunction};
+
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("Run the build".to_string());
+        conversation.add_assistant_message(
+            None,
+            Some(vec![ToolCall {
+                id: "call_1".to_string(),
+                r#type: "function".to_string(),
+                function: ToolFunction {
+                    name: "bash".to_string(),
+                    arguments: "{}".to_string(),
+                },
+            }]),
+        );
+        conversation.messages.push(ConversationMessage {
+            role: Role::Tool,
+            content: Some("x".repeat(8_000)),
+            tool_calls: None,
+            tool_call_id: Some("call_1".to_string()),
+            name: Some("bash".to_string()),
+            attachments: Vec::new(),
+        });
+        conversation.add_assistant_message(Some("Build done".to_string()), None);
+        conversation
+    }
+
+    fn window_and_truncation(order: Vec<StrategyKind>) -> ContextManager {
+        use crate::context_management::{SlidingWindowStrategy, ToolOutputTruncationStrategy};
+
+        let config = ContextManagerConfig::default().with_strategy_order(order);
+        ContextManager::new(config, Arc::new(TokenAccountant::new()))
+            .add_strategy(Box::new(SlidingWindowStrategy::new(SlidingWindowConfig {
+                preserve_system: false,
+                preserve_initial_task: false,
+                max_tokens: 500,
+            })))
+            .add_strategy(Box::new(ToolOutputTruncationStrategy::new(
+                ToolOutputTruncationConfig {
+                    max_length: 400,
+                    preserve_last_tool_result: false,
+                    ..Default::default()
+                },
+            )))
+    }
+
+    #[tokio::test]
+    async fn test_strategy_order_changes_the_result() {
+        let mut truncate_first = big_tool_round();
+        window_and_truncation(vec![
+            StrategyKind::ToolOutputTruncation,
+            StrategyKind::SlidingWindow,
+        ])
+        .apply_strategies(&mut truncate_first)
+        .await
+        .unwrap();
+        // Truncating first brings the round under budget, so nothing is dropped.
+        assert_eq!(truncate_first.messages.len(), 4);
+        assert!(truncate_first.messages[2].content.as_ref().unwrap().len() < 8_000);
+
+        let mut window_first = big_tool_round();
+        window_and_truncation(vec![
+            StrategyKind::SlidingWindow,
+            StrategyKind::ToolOutputTruncation,
+        ])
+        .apply_strategies(&mut window_first)
+        .await
+        .unwrap();
+        // The window sees the full output and drops the whole round.
+        assert_eq!(window_first.messages.len(), 1);
+        assert_eq!(
+            window_first.messages[0].content.as_deref(),
+            Some("Build done")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strategies_left_out_of_the_order_do_not_run() {
+        let mut conversation = big_tool_round();
+        window_and_truncation(vec![StrategyKind::ToolOutputTruncation])
+            .apply_strategies(&mut conversation)
+            .await
+            .unwrap();
+
+        assert_eq!(conversation.messages.len(), 4);
+        assert!(conversation.messages[2].content.as_ref().unwrap().len() < 8_000);
+    }
+
+    #[test]
+    fn test_trigger_decides_when_strategies_apply() {
+        let accountant = Arc::new(TokenAccountant::new());
+        let small = big_tool_round();
+        let mut large = big_tool_round();
+        large.add_user_message("y".repeat(500_000));
+
+        let every_turn = ContextManager::new(ContextManagerConfig::default(), accountant.clone());
+        assert!(every_turn.should_apply(&small));
+
+        let threshold = ContextManager::new(
+            ContextManagerConfig::default().with_trigger(CompactionTrigger::Threshold),
+            accountant.clone(),
+        );
+        assert!(!threshold.should_apply(&small));
+        assert!(threshold.should_apply(&large));
+
+        let manual = ContextManager::new(
+            ContextManagerConfig::default().with_trigger(CompactionTrigger::Manual),
+            accountant,
+        );
+        assert!(!manual.should_apply(&large));
+    }
+
+    #[test]
+    fn test_trigger_and_order_parse_from_toml() {
+        let config: ContextManagerConfig = toml::from_str(
+            "max_tokens = 1000\nwarning_threshold = 0.7\ntrigger = \"threshold\"\n\
+             compression_threshold = 0.9\n\
+             strategy_order = [\"tool_output_truncation\", \"sliding_window\"]",
+        )
+        .unwrap();
+
+        assert_eq!(config.trigger, CompactionTrigger::Threshold);
+        assert_eq!(config.compression_threshold, 0.9);
+        assert_eq!(
+            config.strategy_order,
+            vec![
+                StrategyKind::ToolOutputTruncation,
+                StrategyKind::SlidingWindow
+            ]
+        );
+
+        let defaults: ContextManagerConfig =
+            toml::from_str("max_tokens = 1000\nwarning_threshold = 0.7").unwrap();
+        assert_eq!(defaults.trigger, CompactionTrigger::EveryTurn);
+        assert_eq!(defaults.strategy_order, default_strategy_order());
+    }
+
     #[tokio::test]
     async fn test_strategy_coordination_stops_at_target_reached() {
         use crate::agent::{ConversationMessage, Role};
@@ -7,7 +7,9 @@ use async_trait::async_trait;
 use regex::Regex;
 
 use crate::agent::{Conversation, ConversationMessage, Role};
-use crate::context_management::{ContextManagementStrategy, LogCompressionConfig, StrategyResult};
+use crate::context_management::{
+    ContextManagementStrategy, LogCompressionConfig, StrategyKind, StrategyResult,
+};
 use crate::tools::ToolRegistry;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -677,6 +679,10 @@ impl ContextManagementStrategy for LogCompressionStrategy {
             Ok(StrategyResult::NoChange)
         }
     }
+
+    fn kind(&self) -> Option<StrategyKind> {
+        Some(StrategyKind::LogCompression)
+    }
 }
 
 #[cfg(test)]
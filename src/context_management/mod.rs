@@ -20,10 +20,14 @@ pub enum StrategyResult {
 }
 
 pub use context_manager::{
-    ContextManagementStrategy, ContextManager, ContextManagerConfig, LogCompressionConfig,
-    SlidingWindowConfig, ToolOutputTruncationConfig,
+    CompactionTrigger, ContextManagementStrategy, ContextManager, ContextManagerConfig,
+    LogCompressionConfig, SlidingWindowConfig, StrategyKind, ToolOutputTruncationConfig,
+    TruncationMode,
 };
 pub use log_compression_strategy::LogCompressionStrategy;
 pub use sliding_window_strategy::SlidingWindowStrategy;
-pub use token_accountant::{TokenAccountant, TokenAccountantStats, TokenUsageRecord};
+pub use token_accountant::{
+    LARGE_TOOL_RESULT_TOKENS, TokenAccountant, TokenAccountantStats, TokenUsageRecord,
+    ToolResultTokens,
+};
 pub use tool_output_truncation_strategy::ToolOutputTruncationStrategy;
@@ -2,7 +2,9 @@ use anyhow::Result;
 use async_trait::async_trait;
 
 use crate::agent::{Conversation, ConversationMessage, Role};
-use crate::context_management::{ContextManagementStrategy, SlidingWindowConfig, StrategyResult};
+use crate::context_management::{
+    ContextManagementStrategy, SlidingWindowConfig, StrategyKind, StrategyResult,
+};
 
 pub struct SlidingWindowStrategy {
     config: SlidingWindowConfig,
@@ -22,6 +24,10 @@ impl SlidingWindowStrategy {
     }
 
     fn should_preserve(&self, message: &ConversationMessage, is_first_user_message: bool) -> bool {
+        if message.is_pinned_summary() {
+            return true;
+        }
+
         if self.config.preserve_system && self.is_system_message(message) {
             return true;
         }
@@ -150,6 +156,10 @@ impl ContextManagementStrategy for SlidingWindowStrategy {
     async fn apply(&self, conversation: &mut Conversation) -> Result<StrategyResult> {
         self.apply_token_budget(conversation, self.config.max_tokens)
     }
+
+    fn kind(&self) -> Option<StrategyKind> {
+        Some(StrategyKind::SlidingWindow)
+    }
 }
 
 #[cfg(test)]
@@ -290,4 +300,19 @@ mod tests {
 
         verify_tool_balance(&conversation.messages);
     }
+
+    #[tokio::test]
+    async fn test_preserves_pinned_summary() {
+        let strategy = SlidingWindowStrategy::new(config(30));
+        let mut conversation = Conversation::new();
+        conversation.replace_with_summary("we decided to keep the auth module as is");
+        for i in 0..30 {
+            conversation.add_user_message(format!("filler message {}", i));
+        }
+
+        strategy.apply(&mut conversation).await.unwrap();
+
+        assert!(conversation.messages[0].is_pinned_summary());
+        assert!(conversation.messages.len() < 31);
+    }
 }
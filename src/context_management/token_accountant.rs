@@ -1,15 +1,20 @@
+use crate::agent::{Conversation, ConversationMessage, Role};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenUsageRecord {
-    /// Actual input tokens used by backend
+    /// Input tokens, as reported by the backend or estimated
     pub input_tokens: usize,
-    /// Actual output tokens generated by backend
+    /// Output tokens, as reported by the backend or estimated
     pub output_tokens: usize,
-    /// Total actual tokens (input + output)
+    /// Total tokens (input + output)
     pub total_tokens: usize,
+    /// True when the backend reported no usage and the counts were
+    /// estimated from the conversation instead.
+    #[serde(default)]
+    pub estimated: bool,
 }
 
 impl TokenUsageRecord {
@@ -18,10 +23,42 @@ impl TokenUsageRecord {
             input_tokens,
             output_tokens,
             total_tokens: input_tokens + output_tokens,
+            estimated: false,
+        }
+    }
+
+    pub fn from_estimate(input_tokens: usize, output_tokens: usize) -> Self {
+        Self {
+            estimated: true,
+            ..Self::from_backend(input_tokens, output_tokens)
         }
     }
 }
 
+/// The conversation prefix whose size the backend last reported, so later
+/// turns only need to estimate what was appended since.
+#[derive(Debug, Clone, Copy)]
+struct ContextBaseline {
+    message_count: usize,
+    /// Estimate of those messages when the usage was recorded; a mismatch
+    /// means they were compacted or rewound and the baseline no longer holds.
+    estimated_tokens: usize,
+    reported_tokens: usize,
+}
+
+/// Tool results at least this large get a hint to truncate them.
+pub const LARGE_TOOL_RESULT_TOKENS: usize = 5_000;
+
+/// Estimated context taken up by one tool result in the conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolResultTokens {
+    /// Position of the tool message in `Conversation::messages`.
+    pub message_index: usize,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    pub tokens: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenAccountant {
     current_input_tokens: Arc<AtomicUsize>,
@@ -29,6 +66,8 @@ pub struct TokenAccountant {
     total_input_consumed: Arc<AtomicUsize>,
     total_output_consumed: Arc<AtomicUsize>,
     call_count: Arc<AtomicUsize>,
+    estimated_count: Arc<AtomicUsize>,
+    baseline: Arc<Mutex<Option<ContextBaseline>>>,
 }
 
 impl TokenAccountant {
@@ -39,6 +78,8 @@ impl TokenAccountant {
             total_input_consumed: Arc::new(AtomicUsize::new(0)),
             total_output_consumed: Arc::new(AtomicUsize::new(0)),
             call_count: Arc::new(AtomicUsize::new(0)),
+            estimated_count: Arc::new(AtomicUsize::new(0)),
+            baseline: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -52,6 +93,41 @@ impl TokenAccountant {
         self.total_output_consumed
             .fetch_add(record.output_tokens, Ordering::Relaxed);
         self.call_count.fetch_add(1, Ordering::Relaxed);
+        if record.estimated {
+            self.estimated_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the usage of a turn sent with `conversation`. Reported input
+    /// tokens become the baseline for [`Self::context_tokens`]; an estimated
+    /// record drops it, so sizing falls back to estimating everything.
+    pub fn record_turn_usage(&self, record: TokenUsageRecord, conversation: &Conversation) {
+        let baseline = (!record.estimated).then(|| ContextBaseline {
+            message_count: conversation.messages.len(),
+            estimated_tokens: estimate_messages(&conversation.messages),
+            reported_tokens: record.input_tokens,
+        });
+        self.record_usage(record);
+        *self.baseline.lock().unwrap_or_else(|e| e.into_inner()) = baseline;
+    }
+
+    /// Tokens `conversation` takes up in the context: the last reported input
+    /// plus an estimate of the messages added since, or a full estimate when
+    /// the backend hasn't reported usage for this conversation.
+    pub fn context_tokens(&self, conversation: &Conversation) -> usize {
+        let baseline = *self.baseline.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(baseline) = baseline else {
+            return conversation.estimate_token();
+        };
+        if baseline.message_count > conversation.messages.len() {
+            return conversation.estimate_token();
+        }
+
+        let (seen, added) = conversation.messages.split_at(baseline.message_count);
+        if estimate_messages(seen) != baseline.estimated_tokens {
+            return conversation.estimate_token();
+        }
+        baseline.reported_tokens + estimate_messages(added)
     }
 
     pub fn current_context_tokens(&self) -> usize {
@@ -97,18 +173,44 @@ impl TokenAccountant {
             total_consumed: self.total_consumed_tokens(),
             average_tokens_per_call: self.average_tokens_per_call(),
             record_count: self.call_count.load(Ordering::Relaxed),
+            estimated_record_count: self.estimated_count.load(Ordering::Relaxed),
         }
     }
 
+    /// Estimated size of each tool result in `conversation`, in order.
+    pub fn tool_result_tokens(conversation: &Conversation) -> Vec<ToolResultTokens> {
+        conversation
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| msg.role == Role::Tool)
+            .map(|(message_index, msg)| ToolResultTokens {
+                message_index,
+                tool_call_id: msg.tool_call_id.clone().unwrap_or_default(),
+                tool_name: msg.name.clone().unwrap_or_default(),
+                tokens: Conversation::estimate_message_tokens(msg),
+            })
+            .collect()
+    }
+
     pub fn reset(&self) {
         self.current_input_tokens.store(0, Ordering::Relaxed);
         self.current_output_tokens.store(0, Ordering::Relaxed);
         self.total_input_consumed.store(0, Ordering::Relaxed);
         self.total_output_consumed.store(0, Ordering::Relaxed);
         self.call_count.store(0, Ordering::Relaxed);
+        self.estimated_count.store(0, Ordering::Relaxed);
+        *self.baseline.lock().unwrap_or_else(|e| e.into_inner()) = None;
     }
 }
 
+fn estimate_messages(messages: &[ConversationMessage]) -> usize {
+    messages
+        .iter()
+        .map(Conversation::estimate_message_tokens)
+        .sum()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenAccountantStats {
     pub current_input_tokens: usize,
@@ -119,11 +221,14 @@ pub struct TokenAccountantStats {
     pub total_consumed: usize,
     pub average_tokens_per_call: usize,
     pub record_count: usize,
+    /// Calls whose usage was estimated because the backend didn't report it.
+    #[serde(default)]
+    pub estimated_record_count: usize,
 }
 
 impl TokenAccountantStats {
     pub fn summary_string(&self) -> String {
-        format!(
+        let summary = format!(
             "Context: {} ({} in, {} out) | Consumed: {} ({} in, {} out) | Avg: {}/call | Calls: {}",
             self.current_context_size,
             self.current_input_tokens,
@@ -133,7 +238,12 @@ impl TokenAccountantStats {
             self.total_output_consumed,
             self.average_tokens_per_call,
             self.record_count
-        )
+        );
+        if self.estimated_record_count == 0 {
+            summary
+        } else {
+            format!("{summary} ({} estimated)", self.estimated_record_count)
+        }
     }
 }
 
@@ -169,6 +279,54 @@ mod tests {
         assert_eq!(accountant.total_output_consumed(), 90);
     }
 
+    #[test]
+    fn tool_result_tokens_are_attributed_to_their_messages() {
+        use crate::agent::{ToolCall, ToolCallResponse, ToolFunction};
+
+        let call = |id: &str, name: &str| ToolCall {
+            id: id.to_string(),
+            r#type: "function".to_string(),
+            function: ToolFunction {
+                name: name.to_string(),
+                arguments: "{}".to_string(),
+            },
+        };
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("look around".to_string());
+        conversation.add_assistant_message(
+            None,
+            Some(vec![
+                call("call_small", "list"),
+                call("call_big", "read_file"),
+            ]),
+        );
+        conversation.add_tool_result(ToolCallResponse::success(
+            "call_small".to_string(),
+            "list".to_string(),
+            "list".to_string(),
+            "a.rs".to_string(),
+        ));
+        conversation.add_tool_result(ToolCallResponse::success(
+            "call_big".to_string(),
+            "read_file".to_string(),
+            "read".to_string(),
+            "x".repeat(4000),
+        ));
+
+        let sizes = TokenAccountant::tool_result_tokens(&conversation);
+
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0].message_index, 2);
+        assert_eq!(sizes[0].tool_call_id, "call_small");
+        assert_eq!(sizes[1].message_index, 3);
+        assert_eq!(sizes[1].tool_name, "read_file");
+        assert_eq!(
+            sizes[1].tokens,
+            Conversation::estimate_message_tokens(&conversation.messages[3])
+        );
+        assert!(sizes[1].tokens > 1000 && sizes[0].tokens < 10);
+    }
+
     #[test]
     fn test_average_tokens_per_call() {
         let accountant = TokenAccountant::new();
@@ -208,6 +366,58 @@ mod tests {
         assert_eq!(accountant.statistics().record_count, 0);
     }
 
+    fn conversation_with(content: &str) -> Conversation {
+        let mut conversation = Conversation::new();
+        conversation.add_user_message(content.to_string());
+        conversation
+    }
+
+    #[test]
+    fn reported_usage_overrides_the_estimate() {
+        let accountant = TokenAccountant::new();
+        let mut conversation = conversation_with(&"x".repeat(20_000));
+        assert_eq!(
+            accountant.context_tokens(&conversation),
+            conversation.estimate_token()
+        );
+
+        accountant.record_turn_usage(TokenUsageRecord::from_backend(1_200, 30), &conversation);
+        assert_eq!(accountant.context_tokens(&conversation), 1_200);
+
+        conversation.add_user_message("y".repeat(400));
+        let added = Conversation::estimate_message_tokens(&conversation.messages[1]);
+        assert_eq!(accountant.context_tokens(&conversation), 1_200 + added);
+    }
+
+    #[test]
+    fn estimated_usage_keeps_estimating_the_conversation() {
+        let accountant = TokenAccountant::new();
+        let conversation = conversation_with(&"x".repeat(20_000));
+        accountant.record_turn_usage(TokenUsageRecord::from_backend(1_200, 30), &conversation);
+
+        accountant.record_turn_usage(TokenUsageRecord::from_estimate(5_000, 10), &conversation);
+
+        assert_eq!(
+            accountant.context_tokens(&conversation),
+            conversation.estimate_token()
+        );
+        assert_eq!(accountant.statistics().estimated_record_count, 1);
+    }
+
+    #[test]
+    fn rewritten_history_invalidates_the_reported_baseline() {
+        let accountant = TokenAccountant::new();
+        let mut conversation = conversation_with(&"x".repeat(20_000));
+        accountant.record_turn_usage(TokenUsageRecord::from_backend(1_200, 30), &conversation);
+
+        conversation.messages[0].content = Some("x".repeat(400));
+
+        assert_eq!(
+            accountant.context_tokens(&conversation),
+            conversation.estimate_token()
+        );
+    }
+
     #[test]
     fn test_summary_string() {
         let stats = TokenAccountantStats {
@@ -219,6 +429,7 @@ mod tests {
             total_consumed: 950,
             average_tokens_per_call: 95,
             record_count: 10,
+            estimated_record_count: 0,
         };
 
         let summary = stats.summary_string();
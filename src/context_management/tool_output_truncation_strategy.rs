@@ -3,7 +3,8 @@ use async_trait::async_trait;
 
 use crate::agent::{Conversation, ConversationMessage, Role};
 use crate::context_management::{
-    ContextManagementStrategy, StrategyResult, ToolOutputTruncationConfig,
+    ContextManagementStrategy, StrategyKind, StrategyResult, ToolOutputTruncationConfig,
+    TruncationMode,
 };
 
 pub struct ToolOutputTruncationStrategy {
@@ -20,31 +21,61 @@ impl ToolOutputTruncationStrategy {
             return content.to_string();
         }
 
-        if self.config.smart_truncate {
-            self.smart_truncate(content)
-        } else {
-            self.simple_truncate(content)
+        match self.config.mode {
+            TruncationMode::Chars if self.config.smart_truncate => self.smart_truncate(content),
+            TruncationMode::Chars => self.simple_truncate(content),
+            TruncationMode::Head => self.line_truncate(content, self.config.head_lines, 0),
+            TruncationMode::Tail => self.line_truncate(content, 0, self.config.tail_lines),
+            TruncationMode::HeadTail => {
+                self.line_truncate(content, self.config.head_lines, self.config.tail_lines)
+            }
         }
     }
 
-    fn simple_truncate(&self, content: &str) -> String {
-        // Find a valid UTF-8 boundary
-        let mut truncate_at = self.config.max_length.min(content.len());
-        while truncate_at > 0 && !content.is_char_boundary(truncate_at) {
-            truncate_at -= 1;
-        }
+    /// Keep the first `head_lines` and last `tail_lines` lines. Kept lines are
+    /// still capped by characters (`max_length` for one side, or
+    /// `head_length`/`tail_length` for both) so a few huge lines can't slip
+    /// through.
+    fn line_truncate(&self, content: &str, head_lines: usize, tail_lines: usize) -> String {
+        let (head_budget, tail_budget) = match (head_lines, tail_lines) {
+            (_, 0) => (self.config.max_length, 0),
+            (0, _) => (0, self.config.max_length),
+            _ => (self.config.head_length, self.config.tail_length),
+        };
+
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let head_count = head_lines.max(self.config.min_head_lines).min(lines.len());
+        let tail_count = tail_lines
+            .max(self.config.min_tail_lines)
+            .min(lines.len() - head_count);
+
+        let head_lines_text = lines[..head_count].concat();
+        let tail_lines_text = lines[lines.len() - tail_count..].concat();
+        let head = char_prefix(&head_lines_text, head_budget);
+        let tail = char_suffix(&tail_lines_text, tail_budget);
+        let Some((head, tail)) = self.preserve_minimum(content, head, tail) else {
+            return content.to_string();
+        };
 
-        let truncated = &content[..truncate_at];
+        let omitted_lines = lines.len() - head_count - tail_count;
+        let omitted_chars = content.len() - head.len() - tail.len();
 
-        if self.config.show_truncation_notice {
-            format!(
-                "{}\n\n[... truncated {} characters ...]",
-                truncated,
-                content.len() - truncated.len()
-            )
-        } else {
-            truncated.to_string()
+        if !self.config.show_truncation_notice {
+            return format!("{}{}", head, tail);
         }
+
+        let notice = self.marker(omitted_lines, omitted_chars, true);
+        let head = head.trim_end_matches('\n');
+        match (head.is_empty(), tail.is_empty()) {
+            (true, _) => format!("{}\n{}", notice, tail),
+            (_, true) => format!("{}\n{}", head, notice),
+            _ => format!("{}\n{}\n{}", head, notice, tail),
+        }
+    }
+
+    fn simple_truncate(&self, content: &str) -> String {
+        let head = char_prefix(content, self.config.max_length);
+        self.chars_truncate(content, head, "")
     }
 
     fn smart_truncate(&self, content: &str) -> String {
@@ -54,30 +85,67 @@ impl ToolOutputTruncationStrategy {
             return content.to_string();
         }
 
-        // Find valid UTF-8 boundary for head
-        let mut head_len = self.config.head_length.min(content.len());
-        while head_len > 0 && !content.is_char_boundary(head_len) {
-            head_len -= 1;
+        let head = char_prefix(content, self.config.head_length);
+        let tail = char_suffix(content, self.config.tail_length);
+        self.chars_truncate(content, head, tail)
+    }
+
+    /// Join a character-cut `head` and `tail` around the omission marker.
+    fn chars_truncate(&self, content: &str, head: &str, tail: &str) -> String {
+        let Some((head, tail)) = self.preserve_minimum(content, head, tail) else {
+            return content.to_string();
+        };
+        if !self.config.show_truncation_notice {
+            return format!("{}{}", head, tail);
         }
 
-        // Find valid UTF-8 boundary for tail
-        let mut tail_start = content.len().saturating_sub(self.config.tail_length);
-        while tail_start < content.len() && !content.is_char_boundary(tail_start) {
-            tail_start += 1;
+        let omitted = &content[head.len()..content.len() - tail.len()];
+        let notice = self.marker(omitted.matches('\n').count(), omitted.len(), false);
+        if tail.is_empty() {
+            format!("{}\n\n{}", head, notice)
+        } else {
+            format!("{}\n\n{}\n\n{}", head, notice, tail)
         }
+    }
 
-        let head = &content[..head_len];
-        let tail = &content[tail_start..];
+    /// Widen `head` (a prefix of `content`) and `tail` (a suffix) to at least
+    /// `min_head_lines` and `min_tail_lines` whole lines. Returns None when
+    /// they would then cover all of `content`.
+    fn preserve_minimum<'a>(
+        &self,
+        content: &'a str,
+        head: &str,
+        tail: &str,
+    ) -> Option<(&'a str, &'a str)> {
+        let lines = || content.split_inclusive('\n');
+        let min_head: usize = lines().take(self.config.min_head_lines).map(str::len).sum();
+        let min_tail: usize = lines()
+            .rev()
+            .take(self.config.min_tail_lines)
+            .map(str::len)
+            .sum();
+
+        let head_len = head.len().max(min_head);
+        let tail_len = tail.len().max(min_tail);
+        if head_len + tail_len >= content.len() {
+            return None;
+        }
+        Some((&content[..head_len], &content[content.len() - tail_len..]))
+    }
 
-        if self.config.show_truncation_notice {
-            format!(
-                "{}\n\n[... truncated {} characters ...]\n\n{}",
-                head,
-                content.len() - head_len - (content.len() - tail_start),
-                tail
-            )
-        } else {
-            format!("{}{}", head, tail)
+    /// The omission marker: `truncation_marker` with `{lines}` and `{chars}`
+    /// filled in, or the built-in wording, which counts lines only for the
+    /// line-based modes.
+    fn marker(&self, omitted_lines: usize, omitted_chars: usize, by_line: bool) -> String {
+        match &self.config.truncation_marker {
+            Some(template) => template
+                .replace("{lines}", &omitted_lines.to_string())
+                .replace("{chars}", &omitted_chars.to_string()),
+            None if by_line && omitted_lines > 0 => format!(
+                "[... truncated {} lines ({} characters) ...]",
+                omitted_lines, omitted_chars
+            ),
+            None => format!("[... truncated {} characters ...]", omitted_chars),
         }
     }
 
@@ -124,6 +192,24 @@ impl ToolOutputTruncationStrategy {
     }
 }
 
+/// Longest prefix of `s` that fits in `max` bytes on a char boundary.
+fn char_prefix(s: &str, max: usize) -> &str {
+    let mut end = max.min(s.len());
+    while end > 0 && !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Longest suffix of `s` that fits in `max` bytes on a char boundary.
+fn char_suffix(s: &str, max: usize) -> &str {
+    let mut start = s.len().saturating_sub(max);
+    while start < s.len() && !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
 #[async_trait]
 impl ContextManagementStrategy for ToolOutputTruncationStrategy {
     async fn apply(&self, conversation: &mut Conversation) -> Result<StrategyResult> {
@@ -190,6 +276,10 @@ impl ContextManagementStrategy for ToolOutputTruncationStrategy {
             Ok(StrategyResult::NoChange)
         }
     }
+
+    fn kind(&self) -> Option<StrategyKind> {
+        Some(StrategyKind::ToolOutputTruncation)
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +296,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -262,6 +353,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -308,6 +400,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -334,6 +427,7 @@ mod tests {
             head_length: 30,
             tail_length: 20,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -401,6 +495,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let max_length = config.max_length;
         let strategy = ToolOutputTruncationStrategy::new(config);
@@ -445,6 +540,7 @@ mod tests {
             head_length: 8,
             tail_length: 5,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -490,6 +586,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let max_length = config.max_length;
         let strategy = ToolOutputTruncationStrategy::new(config);
@@ -535,6 +632,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -581,6 +679,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -645,6 +744,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -706,6 +806,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -776,6 +877,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true,
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -819,6 +921,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: true, // NEW: enabled
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -868,6 +971,7 @@ mod tests {
             head_length: 3000,
             tail_length: 1000,
             preserve_last_tool_result: false, // NEW: disabled
+            ..Default::default()
         };
         let strategy = ToolOutputTruncationStrategy::new(config);
 
@@ -911,4 +1015,160 @@ mod tests {
         );
         assert!(conversation.messages[1].content.as_ref().unwrap().len() < 100);
     }
+
+    fn numbered_lines(count: usize) -> String {
+        (1..=count).map(|i| format!("line {}\n", i)).collect()
+    }
+
+    fn line_mode_strategy(mode: TruncationMode) -> ToolOutputTruncationStrategy {
+        ToolOutputTruncationStrategy::new(ToolOutputTruncationConfig {
+            max_length: 100,
+            head_length: 1000,
+            tail_length: 1000,
+            mode,
+            head_lines: 3,
+            tail_lines: 2,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_head_mode_keeps_first_lines() {
+        let strategy = line_mode_strategy(TruncationMode::Head);
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.starts_with("line 1\nline 2\nline 3\n[... truncated 47 lines"));
+        assert!(!truncated.contains("line 4\n"));
+        assert!(!truncated.contains("line 50"));
+    }
+
+    #[test]
+    fn test_tail_mode_keeps_last_lines() {
+        let strategy = line_mode_strategy(TruncationMode::Tail);
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.starts_with("[... truncated 48 lines"));
+        assert!(truncated.ends_with("]\nline 49\nline 50\n"));
+        assert!(!truncated.contains("line 1\n"));
+    }
+
+    #[test]
+    fn test_head_tail_mode_keeps_both_ends() {
+        let strategy = line_mode_strategy(TruncationMode::HeadTail);
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.starts_with("line 1\nline 2\nline 3\n[... truncated 45 lines"));
+        assert!(truncated.ends_with("]\nline 49\nline 50\n"));
+        assert!(!truncated.contains("line 25\n"));
+    }
+
+    #[test]
+    fn test_line_modes_still_cap_long_lines() {
+        let strategy = line_mode_strategy(TruncationMode::Head);
+        let content = format!("{}\nshort\n", "x".repeat(500));
+
+        let truncated = strategy.truncate_content(&content);
+
+        assert!(truncated.starts_with(&"x".repeat(100)));
+        assert!(!truncated.contains(&"x".repeat(101)));
+        assert!(truncated.contains("truncated"));
+    }
+
+    #[tokio::test]
+    async fn test_head_tail_mode_applies_to_tool_results() {
+        let strategy = line_mode_strategy(TruncationMode::HeadTail);
+        let mut conversation = Conversation::new();
+        conversation.add_tool_result(ToolCallResponse::success(
+            "tool_1".to_string(),
+            "bash".to_string(),
+            "Bash(cargo test)".to_string(),
+            numbered_lines(50),
+        ));
+        conversation.add_tool_result(ToolCallResponse::success(
+            "tool_2".to_string(),
+            "bash".to_string(),
+            "Bash(ls)".to_string(),
+            "Short content".to_string(),
+        ));
+
+        strategy.apply(&mut conversation).await.unwrap();
+
+        let content = conversation.messages[0].content.as_ref().unwrap();
+        assert!(content.starts_with("line 1\n"));
+        assert!(content.ends_with("line 50\n"));
+        assert!(content.contains("[... truncated 45 lines"));
+    }
+
+    #[test]
+    fn test_custom_marker_fills_in_counts() {
+        let strategy = ToolOutputTruncationStrategy::new(ToolOutputTruncationConfig {
+            truncation_marker: Some("<omitted {lines} lines, {chars} chars>".to_string()),
+            ..line_mode_strategy(TruncationMode::HeadTail).config
+        });
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.contains("\n<omitted 45 lines, "));
+        assert!(!truncated.contains("[... truncated"));
+    }
+
+    #[test]
+    fn test_custom_marker_in_chars_mode() {
+        let strategy = ToolOutputTruncationStrategy::new(ToolOutputTruncationConfig {
+            max_length: 10,
+            truncation_marker: Some("[cut {chars}]".to_string()),
+            ..Default::default()
+        });
+
+        let truncated = strategy.truncate_content(&"a".repeat(30));
+
+        assert_eq!(truncated, format!("{}\n\n[cut 20]", "a".repeat(10)));
+    }
+
+    #[test]
+    fn test_minimum_lines_survive_chars_mode() {
+        let strategy = ToolOutputTruncationStrategy::new(ToolOutputTruncationConfig {
+            max_length: 20,
+            min_tail_lines: 2,
+            ..Default::default()
+        });
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.starts_with("line 1\nline 2\nline 3\n\n[... truncated"));
+        assert!(truncated.ends_with("]\n\nline 49\nline 50\n"));
+    }
+
+    #[test]
+    fn test_minimum_lines_survive_line_modes() {
+        let strategy = ToolOutputTruncationStrategy::new(ToolOutputTruncationConfig {
+            min_head_lines: 1,
+            ..line_mode_strategy(TruncationMode::Tail).config
+        });
+
+        let truncated = strategy.truncate_content(&numbered_lines(50));
+
+        assert!(truncated.starts_with("line 1\n[... truncated 47 lines"));
+        assert!(truncated.ends_with("]\nline 49\nline 50\n"));
+    }
+
+    #[test]
+    fn test_minimum_lines_may_not_exceed_targets() {
+        let config = ToolOutputTruncationConfig {
+            head_lines: 3,
+            min_head_lines: 4,
+            ..Default::default()
+        };
+        assert_eq!(config.validate().unwrap_err().0, "min_head_lines");
+
+        let config = ToolOutputTruncationConfig {
+            min_head_lines: 3,
+            min_tail_lines: 3,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
 }
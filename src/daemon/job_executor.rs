@@ -8,6 +8,7 @@ use tokio::sync::mpsc;
 
 use crate::agent::{Agent, AgentEvent, Conversation};
 use crate::backends::LlmBackend;
+use crate::config::AppConfig;
 use crate::daemon::config::DaemonConfig;
 use crate::daemon::job::{Job, JobStatus};
 use crate::daemon::job_store::JobStore;
@@ -19,6 +20,8 @@ use crate::system_reminders::{
     PeriodicCoreReminderStrategy, SystemReminder, TokenBudgetReminderStrategy,
 };
 use crate::tool_executor::ToolExecutor;
+use crate::tools::bash::{BashConfig, CommandBlocklist};
+use crate::tools::file_ops::ReadLimits;
 use crate::tools::{BuiltinToolProvider, ToolRegistry};
 
 pub struct JobExecutor {
@@ -27,6 +30,9 @@ pub struct JobExecutor {
     pub backend: Arc<dyn LlmBackend>,
     pub agent_prompt: String,
     pub core_instructions: String,
+    command_blocklist: Arc<CommandBlocklist>,
+    bash_config: Arc<BashConfig>,
+    read_limits: ReadLimits,
 }
 
 impl JobExecutor {
@@ -43,9 +49,21 @@ impl JobExecutor {
             backend,
             agent_prompt,
             core_instructions,
+            command_blocklist: Arc::new(CommandBlocklist::default()),
+            bash_config: Arc::new(BashConfig::default()),
+            read_limits: ReadLimits::default(),
         }
     }
 
+    /// Give job agents the bash blocklist, bash settings and read limits
+    /// from `config` instead of the defaults.
+    pub fn with_tool_config(mut self, config: &AppConfig) -> Self {
+        self.command_blocklist = Arc::new(config.command_blocklist());
+        self.bash_config = Arc::new(config.bash_config());
+        self.read_limits = config.read_limits();
+        self
+    }
+
     pub async fn run(self: Arc<Self>, job_id: String, cancel: Arc<AtomicBool>) {
         if let Err(e) = self.execute(&job_id, Arc::clone(&cancel)).await
             && let Ok(Some(mut job)) = self.store.get(&job_id)
@@ -241,8 +259,12 @@ impl JobExecutor {
                     .with_sandbox_root(repo_dir.to_path_buf()),
             );
             let tool_registry = Arc::new(
-                ToolRegistry::new()
-                    .with_provider(Arc::new(BuiltinToolProvider::new(repo_dir.to_path_buf()))),
+                ToolRegistry::new().with_provider(Arc::new(
+                    BuiltinToolProvider::new(repo_dir.to_path_buf())
+                        .with_command_blocklist(Arc::clone(&self.command_blocklist))
+                        .with_bash_config(Arc::clone(&self.bash_config))
+                        .with_read_limits(self.read_limits),
+                )),
             );
             let tool_executor = Arc::new(
                 ToolExecutor::new(Arc::clone(&tool_registry), perm_manager)
@@ -1,3 +1,3 @@
 mod prompt_history;
 
-pub use prompt_history::PromptHistory;
+pub use prompt_history::{DEFAULT_HISTORY_SIZE, HistoryDedupMode, PromptHistory};
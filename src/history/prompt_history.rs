@@ -1,8 +1,26 @@
 use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 
+/// Number of prompts kept when `history_size` is not configured
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+/// How repeated prompts are collapsed in history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryDedupMode {
+    /// Keep every prompt, including immediate repeats
+    Off,
+    /// Skip a prompt identical to the most recent entry
+    #[default]
+    Consecutive,
+    /// Keep only the most recent occurrence of each prompt
+    Global,
+}
+
 /// Manages prompt history for command-line navigation
 pub struct PromptHistory {
     entries: Vec<String>,
@@ -10,6 +28,7 @@ pub struct PromptHistory {
     temp_input: Option<String>,
     max_size: usize,
     history_file: Option<PathBuf>,
+    dedup_mode: HistoryDedupMode,
 }
 
 impl PromptHistory {
@@ -21,9 +40,15 @@ impl PromptHistory {
             temp_input: None,
             max_size,
             history_file: None,
+            dedup_mode: HistoryDedupMode::default(),
         }
     }
 
+    pub fn with_dedup_mode(mut self, dedup_mode: HistoryDedupMode) -> Self {
+        self.dedup_mode = dedup_mode;
+        self
+    }
+
     /// Creates a new PromptHistory with persistence enabled
     pub fn with_file<P: AsRef<Path>>(max_size: usize, history_file: P) -> std::io::Result<Self> {
         let history_file = history_file.as_ref().to_path_buf();
@@ -39,6 +64,7 @@ impl PromptHistory {
             temp_input: None,
             max_size,
             history_file: Some(history_file),
+            dedup_mode: HistoryDedupMode::default(),
         };
 
         // Load existing history
@@ -53,6 +79,10 @@ impl PromptHistory {
     }
 
     /// Loads history from the file
+    ///
+    /// Lines that are not valid UTF-8 are dropped and only the newest
+    /// `max_size` entries are kept. If either happens the file is rewritten,
+    /// so a corrupt or oversized history heals itself instead of failing.
     fn load(&mut self) -> std::io::Result<()> {
         let Some(ref path) = self.history_file else {
             return Ok(());
@@ -62,67 +92,132 @@ impl PromptHistory {
             return Ok(());
         }
 
-        let file = fs::File::open(path)?;
-        let reader = BufReader::new(file);
+        let bytes = fs::read(path)?;
 
         self.entries.clear();
-        for line in reader.lines() {
-            let line = line?;
+        let mut dropped = false;
+        for line in bytes.split(|&b| b == b'\n') {
+            let Ok(line) = std::str::from_utf8(line) else {
+                dropped = true;
+                continue;
+            };
             let trimmed = line.trim();
             if !trimmed.is_empty() {
                 self.entries.push(trimmed.to_string());
             }
         }
 
-        // Trim to max_size if needed
         if self.entries.len() > self.max_size {
             let start = self.entries.len() - self.max_size;
-            self.entries = self.entries[start..].to_vec();
+            self.entries.drain(..start);
+            dropped = true;
+        }
+
+        if dropped {
+            let entries: Vec<&str> = self.entries.iter().map(|e| e.as_str()).collect();
+            Self::write_entries(path, &entries)?;
         }
 
         Ok(())
     }
 
-    /// Saves history to the file
+    /// Saves history to the file, keeping at most the newest `max_size` entries
     pub fn save(&self) -> std::io::Result<()> {
         let Some(ref path) = self.history_file else {
             return Ok(());
         };
 
-        let mut file = fs::File::create(path)?;
-        for entry in &self.entries {
+        let entries = self.deduplicated_entries();
+        let start = entries.len().saturating_sub(self.max_size);
+        Self::write_entries(path, &entries[start..])
+    }
+
+    fn write_entries(path: &Path, entries: &[&str]) -> std::io::Result<()> {
+        let mut file = BufWriter::new(fs::File::create(path)?);
+        for entry in entries {
             writeln!(file, "{}", entry)?;
         }
+        file.flush()
+    }
 
-        Ok(())
+    /// Entries as they should be written to disk under the current dedup mode.
+    /// The file may have been written under a different mode, so this collapses
+    /// duplicates that were loaded from it as well as ones added this session.
+    fn deduplicated_entries(&self) -> Vec<&str> {
+        match self.dedup_mode {
+            HistoryDedupMode::Off => self.entries.iter().map(|e| e.as_str()).collect(),
+            HistoryDedupMode::Consecutive => {
+                let mut kept: Vec<&str> = Vec::with_capacity(self.entries.len());
+                for entry in &self.entries {
+                    if kept.last() != Some(&entry.as_str()) {
+                        kept.push(entry);
+                    }
+                }
+                kept
+            }
+            HistoryDedupMode::Global => {
+                let mut seen = HashSet::new();
+                let mut kept: Vec<&str> = self
+                    .entries
+                    .iter()
+                    .rev()
+                    .filter(|e| seen.insert(e.as_str()))
+                    .map(|e| e.as_str())
+                    .collect();
+                kept.reverse();
+                kept
+            }
+        }
     }
 
     /// Adds a new prompt to the history
-    /// Skips empty prompts and consecutive duplicates
+    /// Skips empty prompts; repeats are handled according to the dedup mode
     pub fn add(&mut self, prompt: String) {
         let trimmed = prompt.trim();
         if trimmed.is_empty() {
             return;
         }
 
-        // Skip if it's the same as the last entry
-        if let Some(last) = self.entries.last()
-            && last == trimmed
-        {
-            return;
+        match self.dedup_mode {
+            HistoryDedupMode::Off => {}
+            HistoryDedupMode::Consecutive => {
+                if self.entries.last().is_some_and(|last| last == trimmed) {
+                    return;
+                }
+            }
+            HistoryDedupMode::Global => {
+                self.entries.retain(|entry| entry != trimmed);
+            }
         }
 
         self.entries.push(trimmed.to_string());
 
         // Trim history if it exceeds max size
         if self.entries.len() > self.max_size {
-            self.entries.remove(0);
+            let excess = self.entries.len() - self.max_size;
+            self.entries.drain(..excess);
+        }
+
+        // Written through so a crash or kill can't lose it; `save` compacts
+        // the file on the way out.
+        if let Some(ref path) = self.history_file
+            && let Err(e) = Self::append_entry(path, trimmed)
+        {
+            tracing::warn!("Failed to append to prompt history: {}", e);
         }
 
         // Reset navigation state
         self.reset();
     }
 
+    fn append_entry(path: &Path, entry: &str) -> std::io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", entry)
+    }
+
     /// Navigate to the previous prompt in history (up arrow)
     /// Returns the previous prompt, or None if at the beginning
     pub fn prev(&mut self, current_input: &str) -> Option<String> {
@@ -178,6 +273,28 @@ impl PromptHistory {
     pub fn is_navigating(&self) -> bool {
         self.current_index.is_some()
     }
+
+    /// Finds the newest entry older than `before` that contains `query`
+    /// (case-insensitive). Pass `entries.len()` to search from the newest.
+    pub fn search_backward(&self, query: &str, before: usize) -> Option<usize> {
+        let query = query.to_lowercase();
+        let end = before.min(self.entries.len());
+        self.entries[..end]
+            .iter()
+            .rposition(|entry| entry.to_lowercase().contains(&query))
+    }
+
+    pub fn entry(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|s| s.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -214,6 +331,86 @@ mod tests {
         assert_eq!(history.entries.len(), 2);
     }
 
+    #[test]
+    fn test_dedup_off_keeps_repeats() {
+        let mut history = PromptHistory::new(100).with_dedup_mode(HistoryDedupMode::Off);
+
+        history.add("first".to_string());
+        history.add("first".to_string());
+
+        assert_eq!(history.entries, vec!["first", "first"]);
+    }
+
+    #[test]
+    fn test_dedup_consecutive_keeps_non_adjacent_repeats() {
+        let mut history = PromptHistory::new(100).with_dedup_mode(HistoryDedupMode::Consecutive);
+
+        history.add("first".to_string());
+        history.add("second".to_string());
+        history.add("first".to_string());
+        history.add("first".to_string());
+
+        assert_eq!(history.entries, vec!["first", "second", "first"]);
+    }
+
+    #[test]
+    fn test_dedup_global_moves_repeat_to_end() {
+        let mut history = PromptHistory::new(100).with_dedup_mode(HistoryDedupMode::Global);
+
+        history.add("first".to_string());
+        history.add("second".to_string());
+        history.add("first".to_string());
+
+        assert_eq!(history.entries, vec!["second", "first"]);
+        assert_eq!(history.prev(""), Some("first".to_string()));
+        assert_eq!(history.prev(""), Some("second".to_string()));
+    }
+
+    fn saved_lines(mode: HistoryDedupMode) -> Vec<String> {
+        use tempfile::NamedTempFile;
+
+        let temp_file = NamedTempFile::new().expect("Failed to create temporary file");
+        let path = temp_file.path();
+        {
+            let mut file = fs::File::create(path).expect("Failed to create file");
+            for line in ["a", "a", "b", "a", "c", "b"] {
+                writeln!(file, "{}", line).expect("Failed to write to file");
+            }
+        }
+
+        let history = PromptHistory::with_file(100, path)
+            .expect("Failed to create history with file")
+            .with_dedup_mode(mode);
+        history.save().expect("Failed to save history");
+
+        fs::read_to_string(path)
+            .expect("Failed to read history file")
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_save_with_dedup_off_writes_everything() {
+        assert_eq!(
+            saved_lines(HistoryDedupMode::Off),
+            vec!["a", "a", "b", "a", "c", "b"]
+        );
+    }
+
+    #[test]
+    fn test_save_with_dedup_consecutive_collapses_runs() {
+        assert_eq!(
+            saved_lines(HistoryDedupMode::Consecutive),
+            vec!["a", "b", "a", "c", "b"]
+        );
+    }
+
+    #[test]
+    fn test_save_with_dedup_global_keeps_latest_occurrence() {
+        assert_eq!(saved_lines(HistoryDedupMode::Global), vec!["a", "c", "b"]);
+    }
+
     #[test]
     fn test_skip_empty() {
         let mut history = PromptHistory::new(100);
@@ -250,6 +447,28 @@ mod tests {
         assert_eq!(history.next_entry(), Some(current.to_string()));
     }
 
+    #[test]
+    fn test_search_backward_finds_newest_match_first() {
+        let mut history = PromptHistory::new(100);
+        history.add("cargo build".to_string());
+        history.add("git status".to_string());
+        history.add("cargo test".to_string());
+
+        let newest = history.search_backward("cargo", history.len());
+        assert_eq!(newest, Some(2));
+        assert_eq!(history.search_backward("cargo", 2), Some(0));
+        assert_eq!(history.search_backward("cargo", 0), None);
+    }
+
+    #[test]
+    fn test_search_backward_is_case_insensitive() {
+        let mut history = PromptHistory::new(100);
+        history.add("Fix the README".to_string());
+
+        assert_eq!(history.search_backward("readme", history.len()), Some(0));
+        assert_eq!(history.search_backward("missing", history.len()), None);
+    }
+
     #[test]
     fn test_persistence() {
         use tempfile::NamedTempFile;
@@ -277,6 +496,24 @@ mod tests {
         assert_eq!(history.prev(""), Some("command 1".to_string()));
     }
 
+    #[test]
+    fn test_added_entries_survive_without_save() {
+        use tempfile::NamedTempFile;
+
+        let temp_file = NamedTempFile::new().expect("Failed to create temporary file");
+        let path = temp_file.path();
+
+        let mut history =
+            PromptHistory::with_file(100, path).expect("Failed to create history with file");
+        history.add("before the crash".to_string());
+        // Never saved: the process dies here.
+        std::mem::forget(history);
+
+        let history =
+            PromptHistory::with_file(100, path).expect("Failed to create history with file");
+        assert_eq!(history.entries, vec!["before the crash"]);
+    }
+
     #[test]
     fn test_persistence_with_max_size() {
         use tempfile::NamedTempFile;
@@ -298,5 +535,49 @@ mod tests {
         assert_eq!(history.entries.len(), 5);
         assert_eq!(history.entries[0], "command 6");
         assert_eq!(history.entries[4], "command 10");
+
+        // The oversized file is truncated on load
+        let lines = fs::read_to_string(path).expect("Failed to read history file");
+        assert_eq!(lines.lines().count(), 5);
+        assert_eq!(lines.lines().next(), Some("command 6"));
+    }
+
+    #[test]
+    fn test_save_beyond_capacity_keeps_most_recent() {
+        use tempfile::NamedTempFile;
+
+        let temp_file = NamedTempFile::new().expect("Failed to create temporary file");
+        let path = temp_file.path();
+
+        let mut history =
+            PromptHistory::with_file(3, path).expect("Failed to create history with file");
+        for i in 1..=5 {
+            history.add(format!("command {}", i));
+            history.save().expect("Failed to save history");
+        }
+
+        let saved: Vec<String> = fs::read_to_string(path)
+            .expect("Failed to read history file")
+            .lines()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(saved, vec!["command 3", "command 4", "command 5"]);
+    }
+
+    #[test]
+    fn test_corrupt_history_file_is_truncated() {
+        use tempfile::NamedTempFile;
+
+        let temp_file = NamedTempFile::new().expect("Failed to create temporary file");
+        let path = temp_file.path();
+        fs::write(path, b"good one\n\xff\xfe garbage\ngood two\n").expect("Failed to write file");
+
+        let history =
+            PromptHistory::with_file(100, path).expect("Corrupt history should still load");
+        assert_eq!(history.entries, vec!["good one", "good two"]);
+        assert_eq!(
+            fs::read_to_string(path).expect("Failed to read history file"),
+            "good one\ngood two\n"
+        );
     }
 }
@@ -12,9 +12,13 @@ pub mod history;
 pub mod logging;
 pub mod memory;
 pub mod memory_mode;
+pub mod notification;
 pub mod output_format;
 pub mod parser;
 pub mod permissions;
+pub mod project_instructions;
+pub mod prompt_injection;
+pub mod secret_redaction;
 pub mod session;
 pub mod session_files;
 pub mod skill_management;
@@ -22,6 +26,7 @@ pub mod storage;
 pub mod system_reminders;
 pub mod tagged_mode;
 pub mod task_management;
+pub mod telemetry;
 pub mod terminal_capabilities;
 pub mod terminal_markdown;
 pub mod terminal_mode;
@@ -48,11 +48,14 @@ pub fn init_logging() -> Result<LogGuard> {
     Ok(LogGuard(guard))
 }
 
-/// `~/.config/hoosh/logs` (or `$XDG_CONFIG_HOME/hoosh/logs`). Falls back to
-/// the current directory if a home directory can't be resolved — better to
-/// produce *some* log than to silently drop diagnostics.
+/// `~/.config/hoosh/logs` (or `$XDG_CONFIG_HOME/hoosh/logs`, or
+/// `$HOOSH_CONFIG_DIR/logs` when set). Falls back to the current directory if
+/// a home directory can't be resolved — better to produce *some* log than to
+/// silently drop diagnostics.
 fn log_dir() -> Result<PathBuf> {
-    if let Some(base) = dirs::config_dir() {
+    if let Some(dir) = std::env::var_os(crate::config::CONFIG_DIR_ENV).filter(|v| !v.is_empty()) {
+        Ok(PathBuf::from(dir).join("logs"))
+    } else if let Some(base) = dirs::config_dir() {
         Ok(base.join("hoosh").join("logs"))
     } else {
         Ok(PathBuf::from(".hoosh-logs"))
@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::Parser;
 use hoosh::cli::{
     handle_agent, handle_agents, handle_alias_install, handle_commands, handle_config,
-    handle_conversations, handle_daemon, handle_setup,
+    handle_conversations, handle_daemon, handle_setup, handle_skills,
 };
 use hoosh::session_files::cleanup_stale_sessions;
 use hoosh::{
@@ -10,6 +10,7 @@ use hoosh::{
     config::{AppConfig, ConfigError, set_config_path_override, set_data_dir_override},
     console::{VerbosityLevel, init_console},
     logging::init_logging,
+    telemetry,
 };
 
 #[tokio::main]
@@ -24,6 +25,7 @@ async fn main() -> Result<()> {
         }
     };
     tracing::info!("hoosh starting (version {})", env!("CARGO_PKG_VERSION"));
+    let _telemetry_guard = telemetry::init();
 
     // Cleanup stale session files on startup (>7 days old)
     // This runs silently in the background - failures are non-fatal
@@ -46,6 +48,7 @@ async fn main() -> Result<()> {
             | Some(Commands::Agent { .. })
             | Some(Commands::Command { .. })
             | Some(Commands::Alias { .. })
+            | Some(Commands::Skills { .. })
             | Some(Commands::Daemon { .. })
     ) {
         init_console(cli.get_effective_verbosity(VerbosityLevel::Normal));
@@ -77,6 +80,10 @@ async fn main() -> Result<()> {
                 AliasAction::Install => handle_alias_install()?,
             }
         }
+        Some(Commands::Skills { action }) => {
+            let config = AppConfig::load().unwrap_or_default();
+            handle_skills(action, &config)?;
+        }
         Some(Commands::Setup) => {
             handle_setup().await?;
         }
@@ -128,10 +135,13 @@ async fn main() -> Result<()> {
                 cli.backend,
                 cli.add_dir,
                 cli.skip_permissions,
+                cli.yolo,
+                cli.dry_run,
                 cli.continue_last,
                 cli.resume,
                 cli.name,
                 cli.no_session_persistence,
+                cli.no_preflight,
                 cli.mode,
                 cli.memory_mode,
                 cli.output_format,
@@ -0,0 +1,161 @@
+//! Completion notifications for long-running turns and sub-agent tasks.
+//!
+//! `bell` rings the terminal bell; `desktop` shows a system notification when
+//! built with the `desktop-notifications` feature and falls back to the bell
+//! otherwise. When the terminal reports focus changes, notifications are
+//! suppressed while it has focus; terminals that don't report focus always
+//! get them.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const SUMMARY_MAX_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationMode {
+    #[default]
+    Off,
+    Bell,
+    Desktop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Unknown = 0,
+    Focused = 1,
+    Unfocused = 2,
+}
+
+static MODE: OnceLock<NotificationMode> = OnceLock::new();
+static FOCUS: AtomicU8 = AtomicU8::new(Focus::Unknown as u8);
+
+/// Sets the mode for this process. Later calls are ignored.
+pub fn init(mode: NotificationMode) {
+    let _ = MODE.set(mode);
+}
+
+/// Fed from terminal focus events so notifications can skip a focused window.
+pub fn set_terminal_focused(focused: bool) {
+    let focus = if focused {
+        Focus::Focused
+    } else {
+        Focus::Unfocused
+    };
+    FOCUS.store(focus as u8, Ordering::Relaxed);
+}
+
+fn current_focus() -> Focus {
+    match FOCUS.load(Ordering::Relaxed) {
+        1 => Focus::Focused,
+        2 => Focus::Unfocused,
+        _ => Focus::Unknown,
+    }
+}
+
+fn should_fire(mode: NotificationMode, focus: Focus) -> bool {
+    mode != NotificationMode::Off && focus != Focus::Focused
+}
+
+/// Announces that something finished. A no-op unless [`init`] enabled
+/// notifications.
+pub fn notify(title: &str, body: &str) {
+    let mode = MODE.get().copied().unwrap_or_default();
+    if !should_fire(mode, current_focus()) {
+        return;
+    }
+
+    match mode {
+        NotificationMode::Off => {}
+        NotificationMode::Bell => ring_bell(),
+        NotificationMode::Desktop => show_desktop(title, &summarize(body)),
+    }
+}
+
+/// First non-empty line of `text`, shortened for a notification body.
+pub fn summarize(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    if line.chars().count() > SUMMARY_MAX_CHARS {
+        let truncated: String = line.chars().take(SUMMARY_MAX_CHARS - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Written to stderr as a single byte so it can't split an escape sequence
+/// the TUI is writing to stdout.
+fn ring_bell() {
+    let mut stderr = std::io::stderr();
+    let _ = stderr.write_all(b"\x07");
+    let _ = stderr.flush();
+}
+
+#[cfg(feature = "desktop-notifications")]
+fn show_desktop(title: &str, body: &str) {
+    let title = title.to_string();
+    let body = body.to_string();
+    // Showing a notification can block on the session bus; keep it off the
+    // async runtime.
+    std::thread::spawn(move || {
+        let shown = notify_rust::Notification::new()
+            .appname("hoosh")
+            .summary(&title)
+            .body(&body)
+            .show();
+        if let Err(e) = shown {
+            tracing::warn!("Desktop notification failed, ringing the bell instead: {e}");
+            ring_bell();
+        }
+    });
+}
+
+#[cfg(not(feature = "desktop-notifications"))]
+fn show_desktop(_title: &str, _body: &str) {
+    ring_bell();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_unless_off_or_focused() {
+        assert!(!should_fire(NotificationMode::Off, Focus::Unfocused));
+        assert!(should_fire(NotificationMode::Bell, Focus::Unknown));
+        assert!(should_fire(NotificationMode::Desktop, Focus::Unfocused));
+        assert!(!should_fire(NotificationMode::Bell, Focus::Focused));
+    }
+
+    #[test]
+    fn summary_uses_first_non_empty_line() {
+        assert_eq!(
+            summarize("\n  Done: tests pass\nmore detail"),
+            "Done: tests pass"
+        );
+        assert_eq!(summarize(""), "");
+    }
+
+    #[test]
+    fn summary_is_shortened() {
+        let summary = summarize(&"x".repeat(500));
+        assert_eq!(summary.chars().count(), SUMMARY_MAX_CHARS);
+        assert!(summary.ends_with('…'));
+    }
+
+    #[test]
+    fn mode_parses_lowercase() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            mode: NotificationMode,
+        }
+        let parsed: Wrapper = toml::from_str("mode = \"desktop\"").unwrap();
+        assert_eq!(parsed.mode, NotificationMode::Desktop);
+    }
+}
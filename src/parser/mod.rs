@@ -1,5 +1,6 @@
-use crate::agent::{Attachment, AttachmentKind, FileMention};
-use crate::tools::{Tool, file_ops::ListDirectoryTool, file_ops::ReadFileTool};
+use crate::agent::{AgentEvent, Attachment, AttachmentKind, FileMention};
+use crate::tools::Tool;
+use crate::tools::file_ops::{ListDirectoryTool, ReadFileTool, ReadLimits};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::path::{Path, PathBuf};
@@ -26,6 +27,8 @@ pub struct ExpandedMessage {
     pub text: String,
     pub attachments: Vec<Attachment>,
     pub mentions: Vec<FileMention>,
+    /// Events raised while reading mentioned files, e.g. large-read warnings.
+    pub events: Vec<AgentEvent>,
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +58,12 @@ impl MessageParser {
         }
     }
 
+    pub fn with_read_limits(mut self, limits: ReadLimits) -> Self {
+        self.read_file_tool = ReadFileTool::with_working_directory(self.working_directory.clone())
+            .with_limits(limits);
+        self
+    }
+
     pub fn find_file_references(&self, message: &str) -> Result<Vec<FileReference>> {
         // Regex to match @filename patterns with optional line ranges
         // Supports: @file.txt, @src/main.rs, @file.txt:10-20, @file.txt:15
@@ -134,7 +143,11 @@ impl MessageParser {
         }
     }
 
-    pub async fn read_file_reference(&self, file_ref: &FileReference) -> Result<String> {
+    pub async fn read_file_reference(
+        &self,
+        file_ref: &FileReference,
+        events: &mut Vec<AgentEvent>,
+    ) -> Result<String> {
         let mut args = serde_json::json!({
             "path": file_ref.file_path
         });
@@ -144,15 +157,17 @@ impl MessageParser {
             args["end_line"] = serde_json::Value::Number(serde_json::Number::from(end));
         }
 
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
         let context = crate::tools::ToolExecutionContext {
             tool_call_id: "parser".to_string(),
-            event_tx: None,
+            event_tx: Some(event_tx),
             parent_conversation_id: None,
         };
-        self.read_file_tool
-            .execute(&args, &context)
-            .await
-            .map_err(Into::into)
+        let result = self.read_file_tool.execute(&args, &context).await;
+        while let Ok(event) = event_rx.try_recv() {
+            events.push(event);
+        }
+        result.map_err(Into::into)
     }
 
     async fn list_directory_reference(&self, path: &str) -> Result<String> {
@@ -183,14 +198,14 @@ impl MessageParser {
         if file_references.is_empty() {
             return Ok(ExpandedMessage {
                 text: message.to_string(),
-                attachments: Vec::new(),
-                mentions: Vec::new(),
+                ..Default::default()
             });
         }
 
         let mut text = message.to_string();
         let mut attachments: Vec<Attachment> = Vec::new();
         let mut mentions: Vec<FileMention> = Vec::new();
+        let mut events: Vec<AgentEvent> = Vec::new();
 
         for file_ref in &file_references {
             if let Some(media_type) = image_media_type(&file_ref.file_path) {
@@ -228,7 +243,7 @@ impl MessageParser {
             }
 
             let result = self
-                .read_file_reference(file_ref)
+                .read_file_reference(file_ref, &mut events)
                 .await
                 .map_err(|e| e.to_string());
             mentions.push(FileMention::File {
@@ -242,6 +257,7 @@ impl MessageParser {
             text,
             attachments,
             mentions,
+            events,
         })
     }
 
@@ -420,6 +436,48 @@ mod tests {
         assert!(expanded.mentions[0].result().is_err());
     }
 
+    #[tokio::test]
+    async fn non_utf8_file_ref_is_guarded() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("latin1.txt"), b"caf\xe9")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("blob.bin"), [0u8; 32])
+            .await
+            .unwrap();
+
+        let parser = MessageParser::with_working_directory(temp_dir.path().to_path_buf());
+        let expanded = parser.expand("@latin1.txt and @blob.bin").await.unwrap();
+
+        let lossy = expanded.mentions[0].result().as_ref().unwrap();
+        assert!(lossy.starts_with(crate::tools::file_ops::LOSSY_TEXT_HEADER));
+        assert!(lossy.ends_with("caf\u{FFFD}"));
+        assert!(expanded.mentions[1].result().is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_mention_is_truncated_and_reported() {
+        let temp_dir = tempdir().unwrap();
+        let content = "0123456789\n".repeat(10);
+        fs::write(temp_dir.path().join("big.txt"), &content)
+            .await
+            .unwrap();
+
+        let parser = MessageParser::with_working_directory(temp_dir.path().to_path_buf())
+            .with_read_limits(ReadLimits {
+                warn_bytes: 20,
+                max_bytes: 40,
+            });
+        let expanded = parser.expand("see @big.txt").await.unwrap();
+
+        let read = expanded.mentions[0].result().as_ref().unwrap();
+        assert!(read.starts_with("0123456789\n0123456789\n0123456789\n\n[Truncated"));
+        assert!(matches!(
+            expanded.events.as_slice(),
+            [AgentEvent::LargeFileRead { path, truncated: true, .. }] if path == "big.txt"
+        ));
+    }
+
     #[tokio::test]
     async fn image_ref_produces_attachment_and_marker() {
         let temp_dir = tempdir().unwrap();
@@ -1,4 +1,5 @@
 pub mod pattern_matcher;
+mod policy;
 pub mod storage;
 mod tool_permission;
 
@@ -12,6 +13,7 @@ use tokio::sync::mpsc;
 pub use crate::permissions::pattern_matcher::{
     BashPatternMatcher, FilePatternMatcher, PatternMatcher,
 };
+pub use crate::permissions::policy::{ToolPolicies, ToolPolicy};
 pub use crate::permissions::tool_permission::{ToolPermissionBuilder, ToolPermissionDescriptor};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,6 +31,8 @@ pub struct PermissionsInfo {
 #[derive(Clone)]
 pub struct PermissionManager {
     skip_permissions: bool,
+    /// With `skip_permissions`, also run dangerous commands unconfirmed.
+    yolo: bool,
     deny_unknown: bool,
     sandbox_root: Option<PathBuf>,
     event_sender: mpsc::UnboundedSender<crate::agent::AgentEvent>,
@@ -36,6 +40,8 @@ pub struct PermissionManager {
     request_counter: Arc<AtomicU64>,
     project_root: Arc<Mutex<Option<PathBuf>>>,
     permissions_file: Arc<Mutex<storage::PermissionsFile>>,
+    default_policies: Arc<ToolPolicies>,
+    default_permission: ToolPolicy,
 }
 
 impl PermissionManager {
@@ -45,6 +51,7 @@ impl PermissionManager {
     ) -> Self {
         Self {
             skip_permissions: false,
+            yolo: false,
             deny_unknown: false,
             sandbox_root: None,
             event_sender,
@@ -52,6 +59,8 @@ impl PermissionManager {
             request_counter: Arc::new(AtomicU64::new(0)),
             project_root: Arc::new(Mutex::new(None)),
             permissions_file: Arc::new(Mutex::new(storage::PermissionsFile::default())),
+            default_policies: Arc::new(ToolPolicies::default()),
+            default_permission: ToolPolicy::Prompt,
         }
     }
 
@@ -63,6 +72,7 @@ impl PermissionManager {
         let (_, response_rx) = mpsc::unbounded_channel();
         Self {
             skip_permissions: false,
+            yolo: false,
             deny_unknown: true,
             sandbox_root: None,
             event_sender: event_tx,
@@ -70,6 +80,8 @@ impl PermissionManager {
             request_counter: Arc::new(AtomicU64::new(0)),
             project_root: Arc::new(Mutex::new(None)),
             permissions_file: Arc::new(Mutex::new(permissions_file)),
+            default_policies: Arc::new(ToolPolicies::default()),
+            default_permission: ToolPolicy::Prompt,
         }
     }
 
@@ -78,6 +90,28 @@ impl PermissionManager {
         self
     }
 
+    /// Per-tool defaults consulted after persisted rules and before prompting.
+    pub fn with_default_policies(mut self, policies: ToolPolicies) -> Self {
+        self.default_policies = Arc::new(policies);
+        self
+    }
+
+    pub fn default_policy(&self, descriptor: &ToolPermissionDescriptor) -> Option<ToolPolicy> {
+        self.default_policies.for_descriptor(descriptor)
+    }
+
+    /// Decision for tools that match neither a persisted rule nor a per-tool
+    /// policy. `Prompt` (the default) asks the user, `Allow` and `Deny`
+    /// answer without asking.
+    pub fn with_default_permission(mut self, permission: ToolPolicy) -> Self {
+        self.default_permission = permission;
+        self
+    }
+
+    pub fn default_permission(&self) -> ToolPolicy {
+        self.default_permission
+    }
+
     pub fn with_deny_unknown(mut self, deny_unknown: bool) -> Self {
         self.deny_unknown = deny_unknown;
         self
@@ -156,6 +190,18 @@ impl PermissionManager {
         self.skip_permissions
     }
 
+    pub fn with_yolo(mut self, yolo: bool) -> Self {
+        self.yolo = yolo;
+        self
+    }
+
+    /// True if `descriptor` must be confirmed even when permission checks are
+    /// skipped or a rule, policy or default allows it: it is flagged as
+    /// dangerous and `--yolo` is not set.
+    pub fn requires_confirmation(&self, descriptor: &ToolPermissionDescriptor) -> bool {
+        !self.yolo && descriptor.danger().is_some()
+    }
+
     pub fn get_permissions_info(&self) -> PermissionsInfo {
         let permissions_file = self.permissions_file.try_lock().ok();
         match permissions_file {
@@ -170,6 +216,45 @@ impl PermissionManager {
         }
     }
 
+    /// Persisted allow and deny rules, in the order they are checked.
+    pub fn rules(&self) -> (Vec<storage::PermissionRule>, Vec<storage::PermissionRule>) {
+        match self.permissions_file.try_lock() {
+            Ok(perms) => (perms.allow.clone(), perms.deny.clone()),
+            Err(_) => (Vec::new(), Vec::new()),
+        }
+    }
+
+    /// Remove rule number `index` as listed by [`Self::rules`] (deny rules
+    /// first, 1-based) and persist the change.
+    pub fn remove_rule(&self, index: usize) -> Result<(storage::PermissionRule, bool)> {
+        self.edit_rule(index, storage::PermissionsFile::remove_rule)
+    }
+
+    /// Flip rule number `index` between allow and deny and persist the change.
+    pub fn toggle_rule(&self, index: usize) -> Result<(storage::PermissionRule, bool)> {
+        self.edit_rule(index, storage::PermissionsFile::toggle_rule)
+    }
+
+    fn edit_rule(
+        &self,
+        index: usize,
+        edit: impl FnOnce(
+            &mut storage::PermissionsFile,
+            usize,
+        ) -> Option<(storage::PermissionRule, bool)>,
+    ) -> Result<(storage::PermissionRule, bool)> {
+        let mut permissions_file = self
+            .permissions_file
+            .try_lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock permissions file: {}", e))?;
+        let edited = edit(&mut permissions_file, index)
+            .ok_or_else(|| anyhow::anyhow!("No permission rule #{}", index))?;
+
+        drop(permissions_file);
+        self.save_permissions()?;
+        Ok(edited)
+    }
+
     pub fn clear_all_permissions(&self) -> Result<()> {
         let mut permissions_file = self
             .permissions_file
@@ -188,43 +273,68 @@ impl PermissionManager {
         descriptor: &ToolPermissionDescriptor,
     ) -> Result<bool> {
         if self.skip_permissions {
-            return Ok(true);
+            return self.allow_unless_dangerous(descriptor).await;
         }
 
         if let Some(ref root) = self.sandbox_root {
             const FILE_KINDS: &[&str] = &[
                 "read_file",
                 "write_file",
+                "write_files",
+                "append_file",
+                "move_file",
                 "edit_file",
                 "list_directory",
                 "glob",
                 "grep",
             ];
+            // Multi-file tools list one path per line; each must stay inside.
             if FILE_KINDS.contains(&descriptor.kind()) {
-                let target = std::path::Path::new(descriptor.target());
-                let resolved = if target.is_absolute() {
-                    target.to_path_buf()
-                } else {
-                    root.join(target)
-                };
-                let canonical_root = std::fs::canonicalize(root).unwrap_or(root.clone());
-                let canonical_target = std::fs::canonicalize(&resolved).unwrap_or_else(|_| {
-                    resolved
-                        .parent()
-                        .and_then(|p| std::fs::canonicalize(p).ok())
-                        .map(|p| p.join(resolved.file_name().unwrap_or_default()))
-                        .unwrap_or_else(|| {
-                            canonical_root.join(resolved.file_name().unwrap_or_default())
-                        })
-                });
-                if !canonical_target.starts_with(&canonical_root) {
-                    return Ok(false);
+                for target in descriptor.target().lines() {
+                    let target = std::path::Path::new(target);
+                    let resolved = if target.is_absolute() {
+                        target.to_path_buf()
+                    } else {
+                        root.join(target)
+                    };
+                    let canonical_root = std::fs::canonicalize(root).unwrap_or(root.clone());
+                    let canonical_target = std::fs::canonicalize(&resolved).unwrap_or_else(|_| {
+                        resolved
+                            .parent()
+                            .and_then(|p| std::fs::canonicalize(p).ok())
+                            .map(|p| p.join(resolved.file_name().unwrap_or_default()))
+                            .unwrap_or_else(|| {
+                                canonical_root.join(resolved.file_name().unwrap_or_default())
+                            })
+                    });
+                    if !canonical_target.starts_with(&canonical_root) {
+                        return Ok(false);
+                    }
                 }
             }
         }
 
-        if let Some(persistent_decision) = self.check_persistent_tool_permission(descriptor) {
-            return Ok(persistent_decision);
+        if let Some((rule, allowed)) = self.check_persistent_tool_permission(descriptor) {
+            let _ = self
+                .event_sender
+                .send(crate::agent::AgentEvent::PermissionRuleMatched {
+                    tool: descriptor.display_name().to_string(),
+                    rule: rule.to_string(),
+                    allowed,
+                });
+            if !allowed {
+                return Ok(false);
+            }
+            return self.allow_unless_dangerous(descriptor).await;
+        }
+
+        match self
+            .default_policy(descriptor)
+            .unwrap_or(self.default_permission)
+        {
+            ToolPolicy::Allow => return self.allow_unless_dangerous(descriptor).await,
+            ToolPolicy::Deny => return Ok(false),
+            ToolPolicy::Prompt => {}
         }
 
         if self.deny_unknown {
@@ -240,12 +350,30 @@ impl PermissionManager {
         Ok(allowed)
     }
 
+    /// Allow `descriptor` unless it [requires confirmation](Self::requires_confirmation),
+    /// in which case ask once: stored rules don't apply and the chosen scope
+    /// isn't saved. Headless managers have nobody to ask and refuse.
+    async fn allow_unless_dangerous(&self, descriptor: &ToolPermissionDescriptor) -> Result<bool> {
+        if !self.requires_confirmation(descriptor) {
+            return Ok(true);
+        }
+        if self.deny_unknown {
+            return Ok(false);
+        }
+        let (allowed, _) = self.ask_user_tool_permission(descriptor).await?;
+        Ok(allowed)
+    }
+
+    /// The persisted rule that decides `descriptor`, if any, and whether it
+    /// allows the call.
     fn check_persistent_tool_permission(
         &self,
         descriptor: &ToolPermissionDescriptor,
-    ) -> Option<bool> {
+    ) -> Option<(storage::PermissionRule, bool)> {
         let permissions_file = self.permissions_file.try_lock().ok()?;
-        permissions_file.check_tool_permission(descriptor)
+        permissions_file
+            .matching_rule(descriptor)
+            .map(|(rule, allowed)| (rule.clone(), allowed))
     }
 
     async fn ask_user_tool_permission(
@@ -266,7 +394,7 @@ impl PermissionManager {
         let mut receiver = self.response_receiver.lock().await;
 
         let event = crate::agent::AgentEvent::ToolPermissionRequest {
-            descriptor: descriptor.clone(),
+            descriptor: Box::new(descriptor.clone()),
             request_id: request_id.clone(),
         };
         self.event_sender
@@ -301,7 +429,7 @@ impl Default for PermissionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tools::ReadFileTool;
+    use crate::tools::{ReadFileTool, Tool};
     use std::sync::Arc;
     use tempfile::TempDir;
 
@@ -337,6 +465,53 @@ mod tests {
         assert!(result.unwrap());
     }
 
+    fn dangerous_bash_descriptor() -> ToolPermissionDescriptor {
+        let tool = crate::tools::BashTool::new();
+        tool.describe_permission(Some("rm -rf target"))
+    }
+
+    #[tokio::test]
+    async fn test_dangerous_command_prompts_under_skip() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx).with_skip_permissions(true);
+        let descriptor = dangerous_bash_descriptor();
+        assert!(descriptor.danger().is_some());
+
+        let check = tokio::spawn(async move { manager.check_tool_permission(&descriptor).await });
+
+        let request_id = match event_rx.recv().await {
+            Some(crate::agent::AgentEvent::ToolPermissionRequest { request_id, .. }) => request_id,
+            other => panic!("expected a permission request, got {other:?}"),
+        };
+        response_tx
+            .send(crate::agent::PermissionResponse {
+                request_id,
+                allowed: false,
+                scope: None,
+            })
+            .unwrap();
+
+        assert!(!check.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_yolo_skips_dangerous_command_confirmation() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_skip_permissions(true)
+            .with_yolo(true);
+
+        let allowed = manager
+            .check_tool_permission(&dangerous_bash_descriptor())
+            .await
+            .unwrap();
+
+        assert!(allowed);
+        assert!(event_rx.try_recv().is_err());
+    }
+
     #[test]
     fn test_get_permissions_info_empty() {
         let manager = create_test_manager();
@@ -459,9 +634,74 @@ mod tests {
         );
 
         // Now should have persistent permission
-        let result = manager.check_persistent_tool_permission(&descriptor);
-        assert!(result.is_some());
-        assert!(result.unwrap());
+        let (rule, allowed) = manager
+            .check_persistent_tool_permission(&descriptor)
+            .unwrap();
+        assert!(allowed);
+        assert_eq!(rule.to_string(), "read_file(test.txt)");
+    }
+
+    #[test]
+    fn test_rule_edits_are_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        let manager = create_test_manager()
+            .with_project_root(project_root.clone())
+            .unwrap();
+        let descriptor = create_test_descriptor();
+        for target in ["a.txt", "b.txt"] {
+            manager
+                .add_tool_permission_rule(
+                    &descriptor,
+                    &PermissionScope::Specific(target.into()),
+                    true,
+                )
+                .unwrap();
+        }
+
+        let (rule, allowed) = manager.toggle_rule(2).unwrap();
+        assert_eq!(rule.to_string(), "read_file(b.txt)");
+        assert!(!allowed);
+        let (rule, _) = manager.remove_rule(2).unwrap();
+        assert_eq!(rule.to_string(), "read_file(a.txt)");
+        assert!(manager.remove_rule(5).is_err());
+
+        let saved = storage::PermissionsFile::load_permissions(&project_root).unwrap();
+        assert!(saved.allow.is_empty());
+        assert_eq!(
+            saved.deny,
+            vec![storage::PermissionRule::ops_rule("read_file", "b.txt")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_matching_rule_is_reported_as_an_event() {
+        let temp_dir = TempDir::new().unwrap();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_project_root(temp_dir.path().to_path_buf())
+            .unwrap();
+        let descriptor = create_test_descriptor();
+        manager
+            .add_tool_permission_rule(&descriptor, &PermissionScope::Specific("*".into()), true)
+            .unwrap();
+        manager
+            .add_tool_permission_rule(
+                &descriptor,
+                &PermissionScope::Specific("test.txt".into()),
+                false,
+            )
+            .unwrap();
+
+        assert!(!manager.check_tool_permission(&descriptor).await.unwrap());
+        match event_rx.try_recv().unwrap() {
+            crate::agent::AgentEvent::PermissionRuleMatched { rule, allowed, .. } => {
+                assert_eq!(rule, "read_file(test.txt)");
+                assert!(!allowed);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
     }
 
     #[test]
@@ -675,4 +915,236 @@ mod tests {
 
         assert!(manager.check_tool_permission(&descriptor).await.unwrap());
     }
+
+    fn policies(entries: &[(&str, ToolPolicy)]) -> ToolPolicies {
+        ToolPolicies::new(entries.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+    }
+
+    #[tokio::test]
+    async fn allow_policy_skips_the_prompt() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_default_policies(policies(&[("read_file", ToolPolicy::Allow)]));
+
+        let allowed = manager
+            .check_tool_permission(&create_test_descriptor())
+            .await
+            .unwrap();
+
+        assert!(allowed);
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn prompt_policy_asks_the_user() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_default_policies(policies(&[("bash", ToolPolicy::Prompt)]));
+        let descriptor = crate::tools::BashTool::new().describe_permission(Some("cargo build"));
+
+        let check = tokio::spawn(async move { manager.check_tool_permission(&descriptor).await });
+
+        let Some(crate::agent::AgentEvent::ToolPermissionRequest { request_id, .. }) =
+            event_rx.recv().await
+        else {
+            panic!("expected a permission prompt");
+        };
+        response_tx
+            .send(crate::agent::PermissionResponse {
+                request_id,
+                allowed: false,
+                scope: None,
+            })
+            .unwrap();
+
+        assert!(!check.await.unwrap().unwrap());
+    }
+
+    /// Answer the next permission prompt with `allowed`, returning whether
+    /// one was shown.
+    async fn answer_prompt(
+        event_rx: &mut mpsc::UnboundedReceiver<crate::agent::AgentEvent>,
+        response_tx: &mpsc::UnboundedSender<crate::agent::PermissionResponse>,
+        allowed: bool,
+    ) -> bool {
+        let Some(crate::agent::AgentEvent::ToolPermissionRequest { request_id, .. }) =
+            event_rx.recv().await
+        else {
+            return false;
+        };
+        response_tx
+            .send(crate::agent::PermissionResponse {
+                request_id,
+                allowed,
+                scope: None,
+            })
+            .unwrap();
+        true
+    }
+
+    #[tokio::test]
+    async fn dangerous_command_prompts_under_allow_policy_and_default() {
+        for manager_for in [
+            |tx, rx| {
+                PermissionManager::new(tx, rx)
+                    .with_default_policies(policies(&[("bash", ToolPolicy::Allow)]))
+            },
+            |tx, rx| PermissionManager::new(tx, rx).with_default_permission(ToolPolicy::Allow),
+        ] {
+            let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+            let (response_tx, response_rx) = mpsc::unbounded_channel();
+            let manager: PermissionManager = manager_for(event_tx, response_rx);
+            let descriptor = dangerous_bash_descriptor();
+
+            let check =
+                tokio::spawn(async move { manager.check_tool_permission(&descriptor).await });
+
+            assert!(answer_prompt(&mut event_rx, &response_tx, false).await);
+            assert!(!check.await.unwrap().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn dangerous_command_prompts_despite_an_allow_rule() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx);
+        *manager.permissions_file.try_lock().unwrap() = storage::PermissionsFile {
+            version: 1,
+            allow: vec![storage::PermissionRule::ops_rule("bash", "*")],
+            deny: vec![],
+        };
+        let descriptor = dangerous_bash_descriptor();
+
+        let check = tokio::spawn(async move { manager.check_tool_permission(&descriptor).await });
+
+        // The rule match is reported first, then the confirmation is asked.
+        assert!(matches!(
+            event_rx.recv().await,
+            Some(crate::agent::AgentEvent::PermissionRuleMatched { allowed: true, .. })
+        ));
+        assert!(answer_prompt(&mut event_rx, &response_tx, true).await);
+        assert!(check.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn headless_manager_refuses_dangerous_commands_it_cannot_confirm() {
+        let manager = PermissionManager::non_interactive(storage::PermissionsFile {
+            version: 1,
+            allow: vec![storage::PermissionRule::ops_rule("bash", "*")],
+            deny: vec![],
+        });
+
+        assert!(
+            !manager
+                .check_tool_permission(&dangerous_bash_descriptor())
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn persisted_rules_override_default_policy() {
+        let manager = PermissionManager::non_interactive(storage::PermissionsFile {
+            version: 1,
+            allow: vec![storage::PermissionRule::ops_rule("read_file", "*")],
+            deny: vec![],
+        })
+        .with_default_policies(policies(&[("read_file", ToolPolicy::Deny)]));
+
+        assert!(
+            manager
+                .check_tool_permission(&create_test_descriptor())
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn deny_policy_refuses_without_prompting() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_default_policies(policies(&[("destructive", ToolPolicy::Deny)]));
+        let tool = crate::tools::WriteFileTool::new();
+        let descriptor = tool.describe_permission(Some("a.txt"));
+
+        assert!(!manager.check_tool_permission(&descriptor).await.unwrap());
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn default_permission_allow_skips_the_prompt_for_unmatched_tools() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_default_permission(ToolPolicy::Allow);
+        let descriptor = crate::tools::BashTool::new().describe_permission(Some("cargo build"));
+
+        assert!(manager.check_tool_permission(&descriptor).await.unwrap());
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn default_permission_deny_refuses_unmatched_tools() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (_response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager =
+            PermissionManager::new(event_tx, response_rx).with_default_permission(ToolPolicy::Deny);
+        let descriptor = crate::tools::BashTool::new().describe_permission(Some("cargo build"));
+
+        assert!(!manager.check_tool_permission(&descriptor).await.unwrap());
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn default_permission_prompt_asks_the_user() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (response_tx, response_rx) = mpsc::unbounded_channel();
+        let manager = PermissionManager::new(event_tx, response_rx)
+            .with_default_permission(ToolPolicy::Prompt);
+        assert_eq!(manager.default_permission(), ToolPolicy::Prompt);
+        let descriptor = crate::tools::BashTool::new().describe_permission(Some("cargo build"));
+
+        let check = tokio::spawn(async move { manager.check_tool_permission(&descriptor).await });
+
+        let Some(crate::agent::AgentEvent::ToolPermissionRequest { request_id, .. }) =
+            event_rx.recv().await
+        else {
+            panic!("expected a permission prompt");
+        };
+        response_tx
+            .send(crate::agent::PermissionResponse {
+                request_id,
+                allowed: true,
+                scope: None,
+            })
+            .unwrap();
+
+        assert!(check.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn per_tool_policy_and_saved_rules_beat_default_permission() {
+        let manager = PermissionManager::non_interactive(storage::PermissionsFile {
+            version: 1,
+            allow: vec![storage::PermissionRule::ops_rule("read_file", "*")],
+            deny: vec![],
+        })
+        .with_default_policies(policies(&[("destructive", ToolPolicy::Deny)]))
+        .with_default_permission(ToolPolicy::Allow);
+        let destructive = crate::tools::WriteFileTool::new().describe_permission(Some("a.txt"));
+
+        assert!(!manager.check_tool_permission(&destructive).await.unwrap());
+
+        let manager = manager.with_default_permission(ToolPolicy::Deny);
+        assert!(
+            manager
+                .check_tool_permission(&create_test_descriptor())
+                .await
+                .unwrap()
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use glob::Pattern;
 use std::path::PathBuf;
 
-use crate::tools::bash::{BashCommandParser, BashCommandPatternRegistry};
+use crate::tools::bash::{BashCommandParser, BashCommandPatternRegistry, SubcommandRule};
 
 /// Trait for pattern matching logic specific to each tool type
 pub trait PatternMatcher: Send + Sync {
@@ -71,6 +71,11 @@ impl PatternMatcher for BashPatternMatcher {
             return self.cd_leaves_working_dir(target);
         }
 
+        // `cargo:*!{publish}`-style rules judge the parsed subcommands
+        if let Some(rule) = SubcommandRule::parse(pattern) {
+            return rule.matches(target);
+        }
+
         // Delegate to registry
         if self.registry.matches_pattern(pattern, target) {
             return true;
@@ -241,6 +246,30 @@ mod tests {
         assert!(matcher.matches("*", ""));
     }
 
+    #[test]
+    fn test_subcommand_allow_with_deny_override() {
+        let matcher = BashPatternMatcher::new();
+        let pattern = "cargo:*!{publish}";
+
+        assert!(matcher.matches(pattern, "cargo build"));
+        assert!(matcher.matches(pattern, "cargo test --release"));
+        assert!(!matcher.matches(pattern, "cargo publish"));
+        assert!(!matcher.matches(pattern, "cargo build && cargo publish --dry-run"));
+
+        // Existing prefix rules are unaffected
+        assert!(matcher.matches("cargo:*", "cargo publish"));
+    }
+
+    #[test]
+    fn test_subcommand_allowed_set() {
+        let matcher = BashPatternMatcher::new();
+        let pattern = "git:{status,diff}";
+
+        assert!(matcher.matches(pattern, "git status"));
+        assert!(matcher.matches(pattern, "git diff HEAD~1 | head -20"));
+        assert!(!matcher.matches(pattern, "git push origin main"));
+    }
+
     #[test]
     fn test_file_pattern_matcher_glob() {
         let matcher = FilePatternMatcher;
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::permissions::ToolPermissionDescriptor;
+
+/// Default decision for a tool when no persisted rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolPolicy {
+    Allow,
+    Prompt,
+    Deny,
+}
+
+/// `[tool_policies]` config table. Keys are tool names (`read_file`, `bash`)
+/// or operation types (`read_only`, `write_safe`, `destructive`); a tool-name
+/// entry wins over an operation-type entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ToolPolicies(HashMap<String, ToolPolicy>);
+
+impl ToolPolicies {
+    pub fn new(policies: HashMap<String, ToolPolicy>) -> Self {
+        Self(policies)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn for_descriptor(&self, descriptor: &ToolPermissionDescriptor) -> Option<ToolPolicy> {
+        if let Some(policy) = self.0.get(descriptor.kind()) {
+            return Some(*policy);
+        }
+
+        let operation = if descriptor.is_destructive() {
+            "destructive"
+        } else if descriptor.is_write_safe() {
+            "write_safe"
+        } else if descriptor.is_read_only() {
+            "read_only"
+        } else {
+            return None;
+        };
+        self.0.get(operation).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permissions::ToolPermissionBuilder;
+    use crate::tools::{ReadFileTool, WriteFileTool};
+
+    fn policies(toml_str: &str) -> ToolPolicies {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn tool_name_beats_operation_type() {
+        let policies = policies("read_only = \"deny\"\nread_file = \"allow\"");
+        let tool = ReadFileTool::new();
+        let descriptor = ToolPermissionBuilder::new(&tool, "a.txt")
+            .into_read_only()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            policies.for_descriptor(&descriptor),
+            Some(ToolPolicy::Allow)
+        );
+    }
+
+    #[test]
+    fn operation_type_applies_when_tool_is_unlisted() {
+        let policies = policies("destructive = \"prompt\"");
+        let tool = WriteFileTool::new();
+        let destructive = ToolPermissionBuilder::new(&tool, "a.txt")
+            .into_destructive()
+            .build()
+            .unwrap();
+        let plain = ToolPermissionBuilder::new(&tool, "a.txt").build().unwrap();
+
+        assert_eq!(
+            policies.for_descriptor(&destructive),
+            Some(ToolPolicy::Prompt)
+        );
+        assert_eq!(policies.for_descriptor(&plain), None);
+    }
+}
@@ -111,21 +111,24 @@ impl PermissionsFile {
     }
 
     pub fn check_tool_permission(&self, descriptor: &ToolPermissionDescriptor) -> Option<bool> {
-        let operation_str = descriptor.kind();
+        self.matching_rule(descriptor).map(|(_, allowed)| allowed)
+    }
 
-        for rule in self.deny.iter().filter(|r| r.operation == operation_str) {
-            if rule.matches_pattern(descriptor) {
-                return Some(false);
-            }
-        }
+    /// The rule that decides `descriptor`, and whether it allows it. Deny
+    /// rules are checked first, so they win over an overlapping allow.
+    pub fn matching_rule(
+        &self,
+        descriptor: &ToolPermissionDescriptor,
+    ) -> Option<(&PermissionRule, bool)> {
+        let operation_str = descriptor.kind();
+        let matching = |rule: &&PermissionRule| {
+            rule.operation == operation_str && rule.matches_pattern(descriptor)
+        };
 
-        for rule in self.allow.iter().filter(|r| r.operation == operation_str) {
-            if rule.matches_pattern(descriptor) {
-                return Some(true);
-            }
+        if let Some(rule) = self.deny.iter().find(matching) {
+            return Some((rule, false));
         }
-
-        None
+        self.allow.iter().find(matching).map(|rule| (rule, true))
     }
 
     pub fn add_permission(&mut self, rule: PermissionRule, allow: bool) {
@@ -136,6 +139,25 @@ impl PermissionsFile {
         }
     }
 
+    /// Remove rule number `index` (1-based) in listing order: deny rules
+    /// first, then allow rules. Returns the rule and whether it allowed.
+    pub fn remove_rule(&mut self, index: usize) -> Option<(PermissionRule, bool)> {
+        let index = index.checked_sub(1)?;
+        if index < self.deny.len() {
+            return Some((self.deny.remove(index), false));
+        }
+        let index = index - self.deny.len();
+        (index < self.allow.len()).then(|| (self.allow.remove(index), true))
+    }
+
+    /// Move rule number `index` from allow to deny or back. Returns the rule
+    /// and whether it now allows.
+    pub fn toggle_rule(&mut self, index: usize) -> Option<(PermissionRule, bool)> {
+        let (rule, allowed) = self.remove_rule(index)?;
+        self.add_permission(rule.clone(), !allowed);
+        Some((rule, !allowed))
+    }
+
     pub fn remove_permission(&mut self, operation: &str, pattern: Option<&str>) {
         self.allow.retain(|rule| {
             !(rule.operation == operation
@@ -149,6 +171,17 @@ impl PermissionsFile {
     }
 }
 
+/// `operation(pattern)`, e.g. `bash(git status)`, or just the operation for
+/// a rule without a pattern.
+impl std::fmt::Display for PermissionRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.pattern {
+            Some(pattern) => write!(f, "{}({})", self.operation, pattern),
+            None => f.write_str(&self.operation),
+        }
+    }
+}
+
 impl PermissionRule {
     pub fn ops_rule(operation: impl Into<String>, pattern: impl Into<String>) -> Self {
         Self {
@@ -261,6 +294,78 @@ mod tests {
         assert!(!rule.matches_pattern(&desc4));
     }
 
+    fn bash_descriptor(command: &str) -> ToolPermissionDescriptor {
+        let tool = BashTool::new();
+        ToolPermissionBuilder::new(&tool, command)
+            .with_pattern_matcher(Arc::new(BashPatternMatcher::new()))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matching_rule_reports_the_deciding_rule() {
+        let mut file = PermissionsFile::default();
+        file.add_permission(PermissionRule::ops_rule("bash", "git:*"), true);
+        file.add_permission(PermissionRule::ops_rule("bash", "git push:*"), false);
+        file.add_permission(PermissionRule::ops_rule("bash", "git status"), true);
+
+        let (rule, allowed) = file
+            .matching_rule(&bash_descriptor("git push origin"))
+            .unwrap();
+        assert!(!allowed);
+        assert_eq!(rule.to_string(), "bash(git push:*)");
+
+        // The first matching allow rule is the one reported.
+        let (rule, allowed) = file.matching_rule(&bash_descriptor("git status")).unwrap();
+        assert!(allowed);
+        assert_eq!(rule.to_string(), "bash(git:*)");
+
+        assert!(file.matching_rule(&bash_descriptor("cargo test")).is_none());
+    }
+
+    fn numbered_file() -> PermissionsFile {
+        let mut file = PermissionsFile::default();
+        file.add_permission(PermissionRule::ops_rule("bash", "git:*"), true);
+        file.add_permission(PermissionRule::ops_rule("write_file", "*"), true);
+        file.add_permission(PermissionRule::ops_rule("bash", "rm:*"), false);
+        file
+    }
+
+    #[test]
+    fn test_remove_rule_counts_deny_rules_first() {
+        let mut file = numbered_file();
+
+        let (rule, allowed) = file.remove_rule(3).unwrap();
+        assert_eq!(rule.to_string(), "write_file(*)");
+        assert!(allowed);
+
+        let (rule, allowed) = file.remove_rule(1).unwrap();
+        assert_eq!(rule.to_string(), "bash(rm:*)");
+        assert!(!allowed);
+
+        assert!(file.remove_rule(0).is_none());
+        assert!(file.remove_rule(2).is_none());
+        assert_eq!(file.allow, vec![PermissionRule::ops_rule("bash", "git:*")]);
+        assert!(file.deny.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_rule_moves_between_allow_and_deny() {
+        let mut file = numbered_file();
+
+        let (rule, allowed) = file.toggle_rule(3).unwrap();
+        assert_eq!(rule.to_string(), "write_file(*)");
+        assert!(!allowed);
+        assert_eq!(file.allow.len(), 1);
+        assert_eq!(file.deny.len(), 2);
+
+        // The toggled rule is now the last deny rule, number 2.
+        let (rule, allowed) = file.toggle_rule(2).unwrap();
+        assert_eq!(rule.to_string(), "write_file(*)");
+        assert!(allowed);
+        assert!(file.toggle_rule(9).is_none());
+    }
+
     #[test]
     fn test_exact_file_match() {
         let rule = PermissionRule::ops_rule("write_file", "/config.toml");
@@ -20,11 +20,16 @@ pub struct ToolPermissionDescriptor {
     command_preview: Option<String>,
     persistent_approval: String,
     suggested_pattern: Option<String>,
+    /// Parsed breakdown of a shell command, one line per subcommand.
+    command_breakdown: Vec<String>,
     /// When false, the permission dialog must NOT offer "trust project" for
     /// this descriptor. Used for inherently dynamic commands (subshells)
     /// where a blanket project-wide rule would silently auto-approve
     /// arbitrary future code.
     allow_project_wide_trust: bool,
+    /// Why the action is irreversible enough to confirm even when
+    /// permission checks are skipped.
+    danger: Option<String>,
     pattern_matcher: Arc<dyn PatternMatcher>,
 }
 
@@ -45,6 +50,8 @@ impl std::fmt::Debug for ToolPermissionDescriptor {
             .field("command_preview", &self.command_preview)
             .field("persistent_approval", &self.persistent_approval)
             .field("suggested_pattern", &self.suggested_pattern)
+            .field("command_breakdown", &self.command_breakdown)
+            .field("danger", &self.danger)
             .field("pattern_matcher", &"<PatternMatcher>")
             .finish()
     }
@@ -66,6 +73,8 @@ impl PartialEq for ToolPermissionDescriptor {
             && self.command_preview == other.command_preview
             && self.persistent_approval == other.persistent_approval
             && self.suggested_pattern == other.suggested_pattern
+            && self.command_breakdown == other.command_breakdown
+            && self.danger == other.danger
     }
 }
 
@@ -128,6 +137,35 @@ impl ToolPermissionDescriptor {
         self.allow_project_wide_trust
     }
 
+    pub fn command_breakdown(&self) -> &[String] {
+        &self.command_breakdown
+    }
+
+    pub fn danger(&self) -> Option<&str> {
+        self.danger.as_deref()
+    }
+
+    /// Human-readable breakdown rendered above the approval choices: the
+    /// parsed command breakdown plus the rule that trusting it would save.
+    /// Empty for tools that don't provide a breakdown.
+    pub fn summary(&self) -> Vec<String> {
+        if self.command_breakdown.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines: Vec<String> = self
+            .command_breakdown
+            .iter()
+            .map(|line| format!("• {}", line))
+            .collect();
+        if self.allow_project_wide_trust
+            && let Some(pattern) = &self.suggested_pattern
+        {
+            lines.push(format!("Trusting saves the rule: {}", pattern));
+        }
+        lines
+    }
+
     /// Check if a pattern matches this descriptor's target
     /// Delegates to the tool-specific pattern matcher
     pub fn matches_pattern(&self, pattern: &str) -> bool {
@@ -149,7 +187,9 @@ pub struct ToolPermissionBuilder<'a> {
     command_preview: Option<String>,
     persistent_approval: Option<String>,
     suggested_pattern: Option<String>,
+    command_breakdown: Vec<String>,
     allow_project_wide_trust: bool,
+    danger: Option<String>,
     pattern_matcher: Option<Arc<dyn PatternMatcher>>,
 }
 
@@ -169,7 +209,9 @@ impl<'a> ToolPermissionBuilder<'a> {
             command_preview: None,
             persistent_approval: None,
             suggested_pattern: None,
+            command_breakdown: Vec::new(),
             allow_project_wide_trust: true,
+            danger: None,
             pattern_matcher: None,
         }
     }
@@ -252,6 +294,16 @@ impl<'a> ToolPermissionBuilder<'a> {
         self
     }
 
+    pub fn with_command_breakdown(mut self, breakdown: Vec<String>) -> Self {
+        self.command_breakdown = breakdown;
+        self
+    }
+
+    pub fn with_danger(mut self, reason: impl Into<String>) -> Self {
+        self.danger = Some(reason.into());
+        self
+    }
+
     pub fn with_pattern_matcher(mut self, matcher: Arc<dyn PatternMatcher>) -> Self {
         self.pattern_matcher = Some(matcher);
         self
@@ -309,7 +361,9 @@ impl<'a> ToolPermissionBuilder<'a> {
             command_preview: self.command_preview,
             persistent_approval,
             suggested_pattern: self.suggested_pattern,
+            command_breakdown: self.command_breakdown,
             allow_project_wide_trust: self.allow_project_wide_trust,
+            danger: self.danger,
             pattern_matcher,
         })
     }
@@ -0,0 +1,131 @@
+//! Per-project instructions appended to the active agent's system prompt.
+//!
+//! The first of [`CANDIDATES`] found in the project root is read at session
+//! start and re-read when a stored conversation is resumed, so edits made
+//! between sessions reach the model without starting over.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agent::Conversation;
+
+/// Checked in order; the first non-empty file wins.
+pub const CANDIDATES: [&str; 2] = [".hoosh/instructions.md", "AGENTS.md"];
+
+/// Starts the appended section. Everything from here on in the system prompt
+/// belongs to the project file and is replaced wholesale on refresh.
+const SECTION_HEADER: &str = "\n\n# Project Instructions\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectInstructions {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+pub fn load(project_root: &Path) -> Option<ProjectInstructions> {
+    CANDIDATES.iter().find_map(|candidate| {
+        let path = project_root.join(candidate);
+        let content = fs::read_to_string(&path).ok()?;
+        let content = content.trim();
+        (!content.is_empty()).then(|| ProjectInstructions {
+            path,
+            content: content.to_string(),
+        })
+    })
+}
+
+/// `system_prompt` with any previously appended project section swapped for
+/// `instructions`, or dropped when there are none.
+pub fn apply(system_prompt: &str, instructions: Option<&ProjectInstructions>) -> String {
+    let base = system_prompt
+        .split_once(SECTION_HEADER)
+        .map_or(system_prompt, |(base, _)| base);
+    match instructions {
+        Some(instructions) => format!(
+            "{}{}The project at hand asks you to follow these instructions from `{}`:\n\n{}",
+            base,
+            SECTION_HEADER,
+            instructions.path.display(),
+            instructions.content
+        ),
+        None => base.to_string(),
+    }
+}
+
+/// Bring the project section of a resumed conversation's agent prompt in line
+/// with the file on disk. Returns true when the prompt changed.
+pub fn refresh(conversation: &mut Conversation, project_root: &Path) -> bool {
+    let Some(current) = conversation.system_prompt() else {
+        return false;
+    };
+    let updated = apply(current, load(project_root).as_ref());
+    if updated == current {
+        return false;
+    }
+    conversation.set_system_prompt(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn hoosh_instructions_take_precedence_over_agents_md() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(load(dir.path()), None);
+
+        write(dir.path(), "AGENTS.md", "use tabs\n");
+        assert_eq!(load(dir.path()).unwrap().content, "use tabs");
+
+        write(dir.path(), ".hoosh/instructions.md", "run make check\n");
+        let loaded = load(dir.path()).unwrap();
+        assert_eq!(loaded.content, "run make check");
+        assert!(loaded.path.ends_with(".hoosh/instructions.md"));
+    }
+
+    #[test]
+    fn apply_appends_once_and_replaces_on_reapply() {
+        let first = ProjectInstructions {
+            path: PathBuf::from("AGENTS.md"),
+            content: "use tabs".to_string(),
+        };
+        let second = ProjectInstructions {
+            content: "use spaces".to_string(),
+            ..first.clone()
+        };
+
+        let prompt = apply("You are an agent.", Some(&first));
+        assert!(prompt.starts_with("You are an agent."));
+        assert!(prompt.ends_with("use tabs"));
+
+        let prompt = apply(&prompt, Some(&second));
+        assert!(prompt.ends_with("use spaces"));
+        assert!(!prompt.contains("use tabs"));
+        assert_eq!(apply(&prompt, None), "You are an agent.");
+    }
+
+    #[test]
+    fn refresh_picks_up_edits_to_the_file() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "AGENTS.md", "old rule");
+        let mut conversation = Conversation::new();
+        conversation.add_system_message(apply("agent", load(dir.path()).as_ref()));
+        conversation.add_system_message("env".to_string());
+
+        assert!(!refresh(&mut conversation, dir.path()));
+
+        write(dir.path(), "AGENTS.md", "new rule");
+        assert!(refresh(&mut conversation, dir.path()));
+        let prompt = conversation.system_prompt().unwrap();
+        assert!(prompt.starts_with("agent"));
+        assert!(prompt.ends_with("new rule"));
+        assert_eq!(conversation.messages[1].content.as_deref(), Some("env"));
+    }
+}
@@ -8,20 +8,23 @@ use tokio::sync::mpsc;
 use crate::TaskToolProvider;
 use crate::agent::Conversation;
 use crate::agent_definition::AgentDefinitionManager;
-use crate::backends::LlmBackend;
+use crate::backends::{LlmBackend, RequestLimiters};
 use crate::commands::{CommandRegistry, register_custom_commands, register_default_commands};
-use crate::completion::{CommandCompleter, FileCompleter};
+use crate::completion::{AgentCompleter, CommandCompleter, FileCompleter};
 use crate::config::AppConfig;
 use crate::context_management::{
-    ContextManager, LogCompressionStrategy, SlidingWindowStrategy, ToolOutputTruncationStrategy,
+    ContextManager, ContextManagerConfig, LogCompressionStrategy, SlidingWindowStrategy,
+    ToolOutputTruncationStrategy,
 };
-use crate::history::PromptHistory;
+use crate::history::{HistoryDedupMode, PromptHistory};
 use crate::memory_mode::{MemoryMode, MemoryModeManager};
 use crate::parser::MessageParser;
-use crate::permissions::PermissionManager;
+use crate::permissions::{PermissionManager, ToolPolicies, ToolPolicy};
+use crate::project_instructions;
 use crate::storage::ConversationStorage;
 use crate::system_reminders::{
-    PeriodicCoreReminderStrategy, SkillReminderStrategy, SystemReminder, TodoReminderStrategy,
+    GitStatusReminderStrategy, PeriodicCoreReminderStrategy, ReminderKind, ReminderStrategy,
+    SkillReminderStrategy, SystemReminder, TodoReminderStrategy,
 };
 use crate::terminal_mode::TerminalMode;
 use crate::tool_executor::ToolExecutor;
@@ -32,6 +35,7 @@ use crate::tui::app_loop::{
 };
 use crate::tui::handlers;
 use crate::tui::header;
+use crate::tui::input::{PasteDetector, paste_detector};
 use crate::tui::input_handler::InputHandler;
 use crate::tui::state::AppState;
 
@@ -47,6 +51,9 @@ pub struct SessionConfig {
     pub backend: Arc<dyn LlmBackend>,
     pub parser: MessageParser,
     pub skip_permissions: bool,
+    /// Skip the confirmation that destructive commands still get under
+    /// `skip_permissions`.
+    pub yolo: bool,
     pub tool_registry: ToolRegistry,
     pub config: AppConfig,
     pub continue_conversation_id: Option<String>,
@@ -56,6 +63,10 @@ pub struct SessionConfig {
     pub memory_mode: MemoryMode,
     /// Optional human-readable name to set on the (new or resumed) conversation.
     pub conversation_name: Option<String>,
+    /// Storage root to use instead of the one derived from `working_dir`, so
+    /// a resumed conversation is still found after switching to the
+    /// directory it was started in.
+    pub conversation_root: Option<PathBuf>,
 }
 
 impl SessionConfig {
@@ -73,6 +84,7 @@ impl SessionConfig {
             backend,
             parser,
             skip_permissions,
+            yolo: false,
             tool_registry,
             config,
             continue_conversation_id,
@@ -81,6 +93,7 @@ impl SessionConfig {
             terminal_mode: None,
             memory_mode: MemoryMode::default(),
             conversation_name: None,
+            conversation_root: None,
         }
     }
 
@@ -103,6 +116,16 @@ impl SessionConfig {
         self.conversation_name = name;
         self
     }
+
+    pub fn with_conversation_root(mut self, root: Option<PathBuf>) -> Self {
+        self.conversation_root = root;
+        self
+    }
+
+    pub fn with_yolo(mut self, yolo: bool) -> Self {
+        self.yolo = yolo;
+        self
+    }
 }
 
 /// Initialize a complete agent session with all required resources
@@ -111,14 +134,16 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         backend,
         parser,
         skip_permissions,
+        yolo,
         mut tool_registry,
-        config,
+        mut config,
         continue_conversation_id,
         working_dir,
         todo_state,
         terminal_mode,
         memory_mode,
         conversation_name,
+        conversation_root,
     } = session_config;
 
     let detected_terminal_mode = detect_terminal_mode(terminal_mode, config.terminal_mode);
@@ -126,7 +151,32 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     // Initialize app state with history
     let mut app_state = AppState::new();
     app_state.display_compact = config.display_compact.unwrap_or(false);
-    load_history(&mut app_state);
+    app_state.verbose_subagents =
+        crate::console::console().verbosity() >= crate::console::VerbosityLevel::Verbose;
+    app_state.raw_output = config.raw_output.unwrap_or(false);
+    app_state.table_max_width = config.markdown_table_max_width;
+    if config.inline_images.unwrap_or(false) {
+        app_state.inline_images = crate::tui::inline_image::GraphicsProtocol::detect();
+    }
+    app_state.editor = config.editor.clone().unwrap_or_default();
+    app_state.paste_detector = PasteDetector::with_threshold(
+        config
+            .paste_collapse_chars
+            .unwrap_or(paste_detector::DEFAULT_THRESHOLD_CHARS),
+        paste_detector::DEFAULT_MAX_SIZE_BYTES,
+    )
+    .with_line_threshold(
+        config
+            .paste_collapse_lines
+            .unwrap_or(paste_detector::DEFAULT_THRESHOLD_LINES),
+    );
+    app_state.prompt_secret_check = config.prompt_secret_check();
+    app_state.prompt_secret_scanner = config.prompt_secret_scanner().map(Arc::new);
+    load_history(
+        &mut app_state,
+        config.history_size(),
+        config.history_dedup_mode(),
+    );
 
     if detected_terminal_mode == TerminalMode::Fullview {
         let (_, height) = crossterm::terminal::size()?;
@@ -144,6 +194,27 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     let agent_manager = Arc::new(AgentDefinitionManager::new()?);
     let default_agent = agent_manager.get_default_agent();
 
+    // Run on the default agent's own backend/model when it names one, and
+    // keep the config in step so later rebuilds (`/temp`, `/effort`) stay on it.
+    let agent_base_config = config.clone();
+    let backend = match default_agent.as_ref().filter(|a| a.overrides_backend()) {
+        Some(agent) => match backend_for_agent(&config, &agent.name) {
+            Ok(agent_backend) => {
+                config = config.with_agent_overrides(&agent.name);
+                agent_backend
+            }
+            Err(e) => {
+                use crate::console::console;
+                console().warning(&format!(
+                    "Agent '{}' backend unavailable, using the default: {}",
+                    agent.name, e
+                ));
+                backend
+            }
+        },
+        None => backend,
+    };
+
     // Display header
     let working_dir_display = working_dir
         .to_str()
@@ -171,21 +242,34 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         event_tx.clone(),
         permission_response_rx,
         skip_permissions,
+        yolo,
+        config.tool_policies(),
+        config.default_permission(),
         &working_dir,
         &mut app_state,
     )?;
 
-    tool_registry.add_provider(Arc::new(TaskToolProvider::new(
+    let request_limiters = Arc::new(RequestLimiters::from_config(&config));
+    let task_tool_provider = TaskToolProvider::new(
         Arc::clone(&backend),
         working_dir.clone(),
         Arc::clone(&permission_manager),
-    )));
+    )
+    .with_config(config.clone())
+    .with_request_limiters(Arc::clone(&request_limiters));
+    let subagent_backend = task_tool_provider.backend_handle();
+    tool_registry.add_provider(Arc::new(task_tool_provider));
+    for warning in tool_registry.take_warnings() {
+        app_state.add_status_message(&warning);
+    }
 
     let tool_registry = Arc::new(tool_registry);
 
     // Setup conversation storage and load conversation
     // Resolve storage root from config mode (privacy-first: defaults to Off).
-    let storage_root = config.conversation_storage_root(&working_dir)?;
+    let storage_root = config
+        .conversation_storage_root(&working_dir)?
+        .map(|default_root| conversation_root.unwrap_or(default_root));
     let storage_enabled = storage_root.is_some();
 
     let memory_root = config.memory_storage_root(&working_dir)?;
@@ -199,6 +283,7 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         let conversation_id = setup_conversation(
             &conversation_storage,
             continue_conversation_id,
+            &working_dir,
             &mut app_state,
         )?;
 
@@ -245,6 +330,23 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         (conversation_storage, conversation_id, conversation)
     };
 
+    // A resumed conversation runs on the backend and model it last used.
+    let backend = match restore_conversation_backend(&mut config, &conversation, backend.as_ref()) {
+        Ok(Some(restored)) => {
+            app_state.add_info_line(format!(
+                "Resumed on backend '{}' (model: {})",
+                restored.backend_name(),
+                restored.model_name()
+            ));
+            restored
+        }
+        Ok(None) => backend,
+        Err(warning) => {
+            app_state.add_info_line(warning);
+            backend
+        }
+    };
+
     let conversation = Arc::new(tokio::sync::Mutex::new(conversation));
 
     // Construct MemoryModeManager once per session when in summary mode
@@ -267,6 +369,10 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     if !storage_enabled {
         app_state.add_info_line("Conversation storage disabled".to_string());
     }
+    if config.dry_run {
+        app_state
+            .add_info_line("Dry run: tools that would change something are simulated".to_string());
+    }
     if custom_command_count > 0 {
         app_state.add_info_line(format!("Loaded {} custom command(s)", custom_command_count));
     }
@@ -274,11 +380,21 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     app_state.add_message("\n".to_string());
 
     // Setup tool execution
-    let tool_executor =
+    let mut tool_executor =
         ToolExecutor::new(Arc::clone(&tool_registry), Arc::clone(&permission_manager))
             .with_event_sender(event_tx.clone())
             .with_autopilot_state(Arc::clone(&app_state.autopilot_enabled))
-            .with_approval_receiver(approval_response_rx);
+            .with_approval_receiver(approval_response_rx)
+            .with_tool_retries(config.get_tool_retries())
+            .with_turn_retry_budget(config.get_turn_retry_budget())
+            .with_argument_validation(config.get_validate_tool_arguments())
+            .with_dry_run(config.dry_run);
+    if let Some(redactor) = config.secret_redactor() {
+        tool_executor = tool_executor.with_secret_redactor(Arc::new(redactor));
+    }
+    if let Some(scanner) = config.injection_scanner() {
+        tool_executor = tool_executor.with_injection_scanner(Arc::new(scanner));
+    }
 
     // Setup input handlers (clone channels for input handlers)
     let input_handlers = create_input_handlers(
@@ -288,31 +404,42 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     );
 
     // Setup context management
-    let context_manager = setup_context_manager(&config, &tool_registry);
+    let context_manager = setup_context_manager(&config, backend.as_ref(), &tool_registry);
 
     // Register command completer after session is initialized
     let command_completer = CommandCompleter::new(Arc::clone(&command_registry));
     app_state.register_completer(Box::new(command_completer));
+    app_state.register_completer(Box::new(AgentCompleter::new(agent_manager.list_agents())));
 
     // Build system reminder
     let core_instructions = default_agent
         .as_ref()
         .map(|a| a.core_instructions.clone())
         .unwrap_or_else(|| "Focus on completing the task efficiently.".to_string());
-    let token_threshold = config.get_core_reminder_token_threshold();
-    let periodic_strategy = Box::new(PeriodicCoreReminderStrategy::new(
-        token_threshold,
-        core_instructions,
+    let system_reminder = Arc::new(SystemReminder::from_config(
+        &config.system_reminders(),
+        |kind| -> Option<Box<dyn ReminderStrategy>> {
+            match kind {
+                ReminderKind::PeriodicCore => Some(Box::new(
+                    PeriodicCoreReminderStrategy::new(
+                        config.get_core_reminder_token_threshold(),
+                        core_instructions.clone(),
+                    )
+                    .with_step_interval(config.get_core_reminder_step_interval()),
+                )),
+                ReminderKind::Todo => Some(Box::new(TodoReminderStrategy::new(todo_state.clone()))),
+                ReminderKind::Skill => Some(Box::new(SkillReminderStrategy::new(
+                    config.skill_roots(&working_dir).unwrap_or_default(),
+                ))),
+                ReminderKind::GitStatus => Some(Box::new(GitStatusReminderStrategy::new(
+                    working_dir.clone(),
+                    config.get_git_status_reminder_interval(),
+                ))),
+                // Sub-agents add their own budget reminder.
+                ReminderKind::Budget => None,
+            }
+        },
     ));
-    let todo_strategy = Box::new(TodoReminderStrategy::new(todo_state.clone()));
-    let skill_roots = config.skill_roots(&working_dir).unwrap_or_default();
-    let skill_strategy = Box::new(SkillReminderStrategy::new(skill_roots));
-    let system_reminder = Arc::new(
-        SystemReminder::new()
-            .add_strategy(periodic_strategy)
-            .add_strategy(todo_strategy)
-            .add_strategy(skill_strategy),
-    );
 
     // Build system resources
     let system_resources = SystemResources {
@@ -323,6 +450,8 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         agent_manager,
         command_registry,
         system_reminder,
+        request_limiters,
+        subagent_backend,
     };
 
     // Build conversation state
@@ -335,6 +464,7 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
             .unwrap_or_else(|| "assistant".to_string()),
         conversation_storage,
         conversation_id,
+        turn_interrupt: Arc::clone(&app_state.turn_interrupt),
     };
 
     // Build event channels
@@ -352,8 +482,10 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
         input_handlers,
         working_dir: working_dir_display,
         config,
+        agent_base_config,
         todo_state,
         memory_mode_manager,
+        prompt_secret_scanner: app_state.prompt_secret_scanner.clone(),
     };
 
     let event_loop_context = EventLoopContext {
@@ -371,11 +503,57 @@ pub async fn initialize_session(session_config: SessionConfig) -> Result<AgentSe
     })
 }
 
-fn load_history(app_state: &mut AppState) {
+/// Build the backend `agent_name` is configured to run on.
+pub(crate) fn backend_for_agent(
+    config: &AppConfig,
+    agent_name: &str,
+) -> Result<Arc<dyn LlmBackend>> {
+    let config = config.with_agent_overrides(agent_name);
+    let backend =
+        crate::backends::backend_factory::create_backend(&config.default_backend, &config)?;
+    Ok(Arc::from(backend))
+}
+
+/// The backend `conversation` last ran with, when it differs from `current`.
+/// `config` is switched to that backend and model too, so later rebuilds
+/// (`/temp`, `/effort`) keep them. Errs with a warning when the stored
+/// backend is no longer configured or can't be built.
+fn restore_conversation_backend(
+    config: &mut AppConfig,
+    conversation: &Conversation,
+    current: &dyn LlmBackend,
+) -> std::result::Result<Option<Arc<dyn LlmBackend>>, String> {
+    let (Some(name), Some(model)) = (
+        conversation.metadata.backend.as_deref(),
+        conversation.metadata.model.as_deref(),
+    ) else {
+        return Ok(None);
+    };
+    if name == current.backend_name() && model == current.model_name() {
+        return Ok(None);
+    }
+
+    let mut staged = config.clone();
+    let Some(backend_config) = staged.backends.get_mut(name) else {
+        return Err(format!(
+            "Conversation last ran on backend '{name}', which is no longer configured; using '{}'",
+            current.backend_name()
+        ));
+    };
+    backend_config.model = Some(model.to_string());
+    staged.default_backend = name.to_string();
+
+    let restored = crate::backends::backend_factory::create_backend(name, &staged)
+        .map_err(|e| format!("Couldn't restore backend '{name}' ({model}): {e}"))?;
+    *config = staged;
+    Ok(Some(Arc::from(restored)))
+}
+
+fn load_history(app_state: &mut AppState, max_size: usize, dedup_mode: HistoryDedupMode) {
     if let Some(history_path) = PromptHistory::default_history_path()
-        && let Ok(history) = PromptHistory::with_file(1000, &history_path)
+        && let Ok(history) = PromptHistory::with_file(max_size, &history_path)
     {
-        app_state.prompt_history = history;
+        app_state.prompt_history = history.with_dedup_mode(dedup_mode);
     }
 }
 
@@ -401,23 +579,37 @@ fn setup_command_registry() -> Result<(Arc<CommandRegistry>, usize)> {
     Ok((command_registry, custom_count))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn setup_permission_manager(
     event_tx: mpsc::UnboundedSender<crate::agent::AgentEvent>,
     permission_response_rx: mpsc::UnboundedReceiver<crate::agent::PermissionResponse>,
     skip_permissions: bool,
+    yolo: bool,
+    tool_policies: ToolPolicies,
+    default_permission: ToolPolicy,
     working_dir: &Path,
     app_state: &mut AppState,
 ) -> Result<Arc<PermissionManager>> {
     let permission_manager = PermissionManager::new(event_tx, permission_response_rx)
         .with_skip_permissions(skip_permissions)
+        .with_yolo(yolo)
+        .with_default_policies(tool_policies)
+        .with_default_permission(default_permission)
         .with_project_root(working_dir.to_path_buf())
         .inspect_err(|e| {
             use crate::console::console;
             console().error(&e.to_string());
         })?;
 
-    if !permission_manager.is_enforcing() {
-        app_state.add_message("⚠️ Permission checks disabled (--skip-permissions)".to_string());
+    if yolo {
+        app_state.add_message(
+            "⚠️ Permission checks disabled, destructive commands included (--yolo)".to_string(),
+        );
+    } else if !permission_manager.is_enforcing() {
+        app_state.add_message(
+            "⚠️ Permission checks disabled (--skip-permissions); destructive commands still ask"
+                .to_string(),
+        );
     }
 
     Ok(Arc::new(permission_manager))
@@ -426,6 +618,7 @@ fn setup_permission_manager(
 fn setup_conversation(
     conversation_storage: &ConversationStorage,
     continue_conversation_id: Option<String>,
+    working_dir: &Path,
     app_state: &mut AppState,
 ) -> Result<String> {
     if let Some(ref conv_id) = continue_conversation_id {
@@ -445,8 +638,34 @@ fn setup_conversation(
             app_state.add_message(format!("Continuing: {}", metadata.title));
         }
 
+        let usage = metadata.usage_totals();
+        if usage.calls > 0 {
+            app_state.add_info_line(format!(
+                "Previous usage: {} tokens (${:.4})",
+                usage.total_tokens(),
+                usage.cost
+            ));
+            app_state.metrics.total_cost = usage.cost;
+        }
+
+        if let Some(original) = metadata.moved_from(working_dir) {
+            app_state.add_info_line(format!(
+                "Started in {}, file references may not resolve here",
+                original.display()
+            ));
+        }
+
         Ok(conv_id.clone())
     } else {
+        if let Ok(Some(recoverable)) = crate::session_files::take_recoverable_session()
+            && conversation_storage.conversation_exists(&recoverable)
+        {
+            app_state.add_info_line(format!(
+                "The last session here ended unexpectedly; resume it with `hoosh --resume {}`",
+                recoverable
+            ));
+        }
+
         // Just generate ID - conversation will be created in load_or_create_conversation
         // with the system message included
         let conv_id = ConversationStorage::generate_conversation_id();
@@ -529,7 +748,10 @@ fn load_or_create_conversation(
         && storage.conversation_exists(conversation_id)
     {
         match Conversation::load(conversation_id, Arc::clone(storage)) {
-            Ok(conv) => return Ok(conv),
+            Ok(mut conv) => {
+                project_instructions::refresh(&mut conv, working_dir);
+                return Ok(conv);
+            }
             Err(e) => {
                 use crate::console::console;
                 console().error(&format!("Failed to load conversation: {}", e));
@@ -549,9 +771,21 @@ fn load_or_create_conversation(
             Conversation::new()
         }
     };
-
-    if let Some(agent) = default_agent {
-        conv.add_system_message(agent.content.clone());
+    conv.set_origin(working_dir, backend.backend_name(), backend.model_name());
+
+    let instructions = project_instructions::load(working_dir);
+    match default_agent {
+        Some(agent) => {
+            conv.add_system_message(project_instructions::apply(
+                &agent.content,
+                instructions.as_ref(),
+            ));
+        }
+        None => {
+            if let Some(instructions) = &instructions {
+                conv.add_system_message(project_instructions::apply("", Some(instructions)));
+            }
+        }
     }
 
     // Add environment context system prompt for new conversations
@@ -598,6 +832,9 @@ fn create_input_handlers(
     let mut handlers: Vec<Box<dyn InputHandler + Send>> = vec![
         Box::new(handlers::PermissionHandler::new(permission_response_tx)),
         Box::new(handlers::ApprovalHandler::new(approval_response_tx)),
+        Box::new(handlers::AbortHandler::new()),
+        Box::new(handlers::TaskListHandler::new()),
+        Box::new(handlers::HistorySearchHandler::new()),
         Box::new(handlers::CompletionHandler::new()),
         Box::new(handlers::QuitHandler::new()),
     ];
@@ -607,6 +844,8 @@ fn create_input_handlers(
     }
 
     handlers.push(Box::new(handlers::ToolExpandHandler::new()));
+    handlers.push(Box::new(handlers::VariantHandler::new()));
+    handlers.push(Box::new(handlers::EditorHandler::new()));
     handlers.push(Box::new(handlers::SubmitHandler::new()));
     handlers.push(Box::new(handlers::PasteHandler::new()));
     handlers.push(Box::new(handlers::TextInputHandler::new()));
@@ -616,9 +855,15 @@ fn create_input_handlers(
 
 fn setup_context_manager(
     config: &AppConfig,
+    backend: &dyn LlmBackend,
     tool_registry: &Arc<ToolRegistry>,
 ) -> Arc<ContextManager> {
-    let context_manager_config = config.get_context_manager_config();
+    // An explicit [context_manager] table wins; otherwise size the budgets
+    // from the model's context window.
+    let context_manager_config = match &config.context_manager {
+        Some(explicit) => explicit.clone(),
+        None => ContextManagerConfig::default().with_context_window(config.context_window(backend)),
+    };
     let token_accountant = Arc::new(crate::context_management::TokenAccountant::new());
 
     let mut context_manager_builder = ContextManager::new(
@@ -626,15 +871,15 @@ fn setup_context_manager(
         Arc::clone(&token_accountant),
     );
 
-    // Apply sliding window FIRST to remove old messages
+    // The order strategies run in comes from `strategy_order`; by default the
+    // sliding window removes old messages first, log compression then
+    // shrinks build/test output semantically, and truncation is the backstop.
     if let Some(sliding_window_config) = context_manager_config.sliding_window {
         let sliding_window_strategy = SlidingWindowStrategy::new(sliding_window_config);
         context_manager_builder =
             context_manager_builder.add_strategy(Box::new(sliding_window_strategy));
     }
 
-    // Apply log compression SECOND to semantically shrink build/test output
-    // before the dumb truncation backstop runs.
     if let Some(log_compression_config) = context_manager_config.log_compression {
         let log_compression_strategy =
             LogCompressionStrategy::new(log_compression_config, Arc::clone(tool_registry));
@@ -642,7 +887,6 @@ fn setup_context_manager(
             context_manager_builder.add_strategy(Box::new(log_compression_strategy));
     }
 
-    // Apply truncation LAST to reduce size of remaining messages
     if let Some(truncation_config) = context_manager_config.tool_output_truncation {
         let truncation_strategy = ToolOutputTruncationStrategy::new(truncation_config);
         context_manager_builder =
@@ -651,3 +895,143 @@ fn setup_context_manager(
 
     Arc::new(context_manager_builder)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_definition::AgentDefinition;
+    use crate::backends::{LlmError, LlmResponse};
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    struct NamedBackend;
+
+    #[async_trait]
+    impl LlmBackend for NamedBackend {
+        async fn send_message(&self, _message: &str) -> Result<String> {
+            unimplemented!()
+        }
+
+        async fn send_message_with_tools(
+            &self,
+            _conversation: &Conversation,
+            _tools: &ToolRegistry,
+        ) -> Result<LlmResponse, LlmError> {
+            unimplemented!()
+        }
+
+        fn backend_name(&self) -> &str {
+            "test"
+        }
+
+        fn model_name(&self) -> &str {
+            "test-model"
+        }
+    }
+
+    #[test]
+    fn new_conversation_appends_project_instructions_to_the_agent_prompt() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("AGENTS.md"), "Always run cargo fmt.\n").unwrap();
+        let agent = AgentDefinition {
+            name: "hoosh_coder".to_string(),
+            content: "You are the coder agent.".to_string(),
+            file: "hoosh_coder.txt".to_string(),
+            description: None,
+            tags: Vec::new(),
+            core_instructions: String::new(),
+            backend: None,
+            model: None,
+        };
+        let backend: Arc<dyn LlmBackend> = Arc::new(NamedBackend);
+
+        let conv =
+            load_or_create_conversation(None, "conv", Some(&agent), &backend, dir.path(), None)
+                .unwrap();
+
+        let prompt = conv.system_prompt().unwrap();
+        assert!(prompt.starts_with("You are the coder agent."));
+        assert!(prompt.ends_with("Always run cargo fmt."));
+        assert!(
+            conv.messages[1]
+                .content
+                .as_deref()
+                .unwrap()
+                .contains("Environment Context")
+        );
+    }
+
+    fn config_with_backends(names: &[&str]) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.default_backend = "mock".to_string();
+        for name in names {
+            config
+                .update_backend_setting(name, "model", "configured-model".to_string())
+                .unwrap();
+        }
+        config
+    }
+
+    #[cfg(feature = "ollama")]
+    #[test]
+    fn resume_restores_the_overridden_model() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(ConversationStorage::with_root(dir.path()));
+        let current: Arc<dyn LlmBackend> = Arc::new(crate::backends::MockBackend::new());
+
+        let mut conv = load_or_create_conversation(
+            Some(Arc::clone(&storage)),
+            "conv_model",
+            None,
+            &current,
+            dir.path(),
+            None,
+        )
+        .unwrap();
+        // `/model` mid-session
+        conv.set_backend("ollama", "qwen3-coder");
+
+        let resumed = load_or_create_conversation(
+            Some(storage),
+            "conv_model",
+            None,
+            &current,
+            dir.path(),
+            None,
+        )
+        .unwrap();
+        let mut config = config_with_backends(&["mock", "ollama"]);
+        let restored = restore_conversation_backend(&mut config, &resumed, current.as_ref())
+            .unwrap()
+            .expect("switched back to the conversation's backend");
+
+        assert_eq!(restored.backend_name(), "ollama");
+        assert_eq!(restored.model_name(), "qwen3-coder");
+        assert_eq!(config.default_backend, "ollama");
+        assert_eq!(
+            config.backends["ollama"].model.as_deref(),
+            Some("qwen3-coder")
+        );
+    }
+
+    #[test]
+    fn resume_keeps_the_current_backend_when_the_stored_one_is_gone() {
+        let current = crate::backends::MockBackend::new();
+        let mut conv = Conversation::new();
+        conv.set_backend("retired", "old-model");
+        let mut config = config_with_backends(&["mock"]);
+
+        let warning = restore_conversation_backend(&mut config, &conv, &current)
+            .err()
+            .expect("warns about the missing backend");
+
+        assert!(warning.contains("'retired'"), "{warning}");
+        assert_eq!(config.default_backend, "mock");
+
+        conv.set_backend("mock", "mock-model");
+        assert!(matches!(
+            restore_conversation_backend(&mut config, &conv, &current),
+            Ok(None)
+        ));
+    }
+}
@@ -2,8 +2,9 @@
 
 use anyhow::Result;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use super::store::{SessionFile, get_sessions_dir};
+use super::store::{SessionFile, get_sessions_dir, get_terminal_pid};
 
 const DEFAULT_STALE_THRESHOLD_DAYS: i64 = 7;
 
@@ -42,6 +43,106 @@ pub fn cleanup_stale_sessions_with_threshold(threshold_days: i64) -> Result<()>
     Ok(())
 }
 
+/// Record in this terminal's session file that `conversation_id` ended
+/// abruptly. Run on the way out of a panic or signal, after flushing.
+pub fn mark_session_recoverable(conversation_id: &str) -> Result<()> {
+    let terminal_pid = get_terminal_pid()?;
+    let mut session =
+        SessionFile::load(terminal_pid)?.unwrap_or_else(|| SessionFile::new(terminal_pid));
+    session.mark_recoverable(conversation_id);
+    session.save()
+}
+
+/// The conversation the last session in this terminal left behind when it
+/// crashed, if any. Clears the record so it is offered once.
+pub fn take_recoverable_session() -> Result<Option<String>> {
+    let terminal_pid = get_terminal_pid()?;
+    let Some(mut session) = SessionFile::load(terminal_pid)? else {
+        return Ok(None);
+    };
+    let recoverable = session.take_recoverable();
+    if recoverable.is_some() {
+        session.save()?;
+    }
+    Ok(recoverable)
+}
+
+/// What became of the terminal behind a session file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionState {
+    /// The terminal is still open
+    Live,
+    /// The terminal is gone and left a crashed conversation behind
+    Recoverable(String),
+    /// The terminal is gone and nothing in the file is worth keeping
+    Orphaned,
+}
+
+/// Classify a session file by whether its terminal process is still alive
+pub fn classify_session(session: &SessionFile) -> SessionState {
+    if check_pid_exists(session.terminal_pid) {
+        return SessionState::Live;
+    }
+    match session.recoverable() {
+        Some(conversation_id) => SessionState::Recoverable(conversation_id.to_string()),
+        None => SessionState::Orphaned,
+    }
+}
+
+/// A crashed conversation left in the session file of a terminal that no
+/// longer exists
+#[derive(Debug, Clone)]
+pub struct StaleSession {
+    pub conversation_id: String,
+    path: PathBuf,
+}
+
+impl StaleSession {
+    /// Forget the session, whether it was resumed or declined. The
+    /// conversation itself stays in storage.
+    pub fn discard(self) -> Result<()> {
+        fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+/// Find crashed conversations whose terminals are gone, removing session
+/// files of dead terminals that hold nothing to recover
+pub fn find_stale_sessions() -> Result<Vec<StaleSession>> {
+    find_stale_sessions_in(&get_sessions_dir()?)
+}
+
+fn find_stale_sessions_in(sessions_dir: &Path) -> Result<Vec<StaleSession>> {
+    let mut stale = Vec::new();
+
+    for entry in fs::read_dir(sessions_dir)? {
+        let path = entry?.path();
+
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(session) = serde_json::from_str::<SessionFile>(&content) else {
+            continue;
+        };
+        match classify_session(&session) {
+            SessionState::Live => {}
+            SessionState::Recoverable(conversation_id) => stale.push(StaleSession {
+                conversation_id,
+                path,
+            }),
+            SessionState::Orphaned => {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+
+    Ok(stale)
+}
+
 /// Check if a PID exists (process validation)
 #[cfg(unix)]
 pub fn check_pid_exists(pid: u32) -> bool {
@@ -60,3 +161,57 @@ pub fn check_pid_exists(_pid: u32) -> bool {
     // On non-Unix systems, assume PID exists (graceful degradation)
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The PID of a process that has already exited
+    fn dead_pid() -> u32 {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        pid
+    }
+
+    fn write_session(dir: &Path, session: &SessionFile) -> PathBuf {
+        let path = dir.join(format!("session_{}.json", session.terminal_pid));
+        fs::write(&path, serde_json::to_string(session).unwrap()).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dead_terminal_with_crashed_conversation_is_recoverable() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut crashed = SessionFile::new(dead_pid());
+        crashed.mark_recoverable("conv_crashed");
+        let crashed_path = write_session(dir.path(), &crashed);
+
+        assert_eq!(
+            classify_session(&crashed),
+            SessionState::Recoverable("conv_crashed".to_string())
+        );
+
+        let stale = find_stale_sessions_in(dir.path()).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].conversation_id, "conv_crashed");
+
+        stale.into_iter().next().unwrap().discard().unwrap();
+        assert!(!crashed_path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn orphaned_and_live_sessions_are_not_offered() {
+        let dir = tempfile::tempdir().unwrap();
+        let orphan_path = write_session(dir.path(), &SessionFile::new(dead_pid()));
+        let mut live = SessionFile::new(std::process::id());
+        live.mark_recoverable("conv_live");
+        let live_path = write_session(dir.path(), &live);
+
+        assert!(find_stale_sessions_in(dir.path()).unwrap().is_empty());
+        assert!(!orphan_path.exists());
+        assert!(live_path.exists());
+    }
+}
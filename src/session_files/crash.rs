@@ -0,0 +1,297 @@
+// Last-chance persistence when hoosh panics or is told to stop
+
+use super::cleanup::mark_session_recoverable;
+use crate::agent::Conversation;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableFocusChange, DisableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode, is_raw_mode_enabled};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Once};
+use std::thread::ThreadId;
+
+type Flusher = Box<dyn Fn() + Send + Sync>;
+
+struct Registered {
+    id: u64,
+    flush: Flusher,
+    /// Conversation to offer for resuming once flushed.
+    conversation_id: String,
+}
+
+static REGISTRY: Mutex<Vec<Registered>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+
+thread_local! {
+    static FLUSHING: Cell<bool> = const { Cell::new(false) };
+    /// How many [`recoverable`] sections this thread is inside.
+    static RECOVERABLE: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Marks the current thread as inside a [`recoverable`] section until dropped,
+/// which also happens while a panic unwinds out of it.
+struct RecoverableSection;
+
+impl RecoverableSection {
+    fn enter() -> Self {
+        RECOVERABLE.set(RECOVERABLE.get() + 1);
+        Self
+    }
+}
+
+impl Drop for RecoverableSection {
+    fn drop(&mut self) {
+        RECOVERABLE.set(RECOVERABLE.get() - 1);
+    }
+}
+
+/// Run `f`, whose caller catches any panic in it, so the crash handler must
+/// not treat that panic as fatal.
+pub fn recoverable<R>(f: impl FnOnce() -> R) -> R {
+    let _section = RecoverableSection::enter();
+    f()
+}
+
+fn in_recoverable_section() -> bool {
+    RECOVERABLE.get() > 0
+}
+
+/// Whether a panic on the current thread takes hoosh down. Tokio catches
+/// panics in spawned tasks, so only the thread running the session counts.
+fn panic_is_fatal(main_thread: ThreadId) -> bool {
+    std::thread::current().id() == main_thread && !in_recoverable_section()
+}
+
+/// Keeps a conversation flushed on crash; dropping it (at clean exit)
+/// unregisters the conversation.
+pub struct FlushRegistration {
+    id: u64,
+}
+
+impl Drop for FlushRegistration {
+    fn drop(&mut self) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry.retain(|entry| entry.id != self.id);
+    }
+}
+
+/// Flush `conversation` to storage if hoosh panics or receives a terminating
+/// signal while the registration is alive. Only register persistent
+/// conversations; ephemeral ones have nothing to resume.
+pub fn guard_conversation(
+    conversation: Arc<tokio::sync::Mutex<Conversation>>,
+    conversation_id: &str,
+) -> FlushRegistration {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let flush: Flusher = Box::new(move || {
+        // A turn in progress holds the lock. Its messages are appended to
+        // storage as they arrive, so there is nothing more to write.
+        let Ok(mut conversation) = conversation.try_lock() else {
+            return;
+        };
+        if let Err(e) = conversation.flush() {
+            eprintln!("Warning: failed to save conversation: {}", e);
+        }
+    });
+
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.push(Registered {
+        id,
+        flush,
+        conversation_id: conversation_id.to_string(),
+    });
+    FlushRegistration { id }
+}
+
+/// Run every registered flusher and return the ids of the conversations
+/// flushed. Re-entrant calls, e.g. from a panic inside a flusher, return
+/// nothing.
+pub fn flush_all() -> Vec<String> {
+    if FLUSHING.replace(true) {
+        return Vec::new();
+    }
+    let flushed = {
+        let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry
+            .iter()
+            .map(|entry| {
+                (entry.flush)();
+                entry.conversation_id.clone()
+            })
+            .collect()
+    };
+    FLUSHING.set(false);
+    flushed
+}
+
+/// Tell the crash handler whether the TUI is on the alternate screen, so it
+/// knows to leave it.
+pub fn set_alternate_screen(active: bool) {
+    ALTERNATE_SCREEN.store(active, Ordering::Relaxed);
+}
+
+/// Install the panic hook and signal handlers. SIGTERM and SIGHUP are always
+/// handled; SIGINT only when `handle_interrupt` is set, as tagged mode
+/// handles it itself. Call it from the thread running the session: panics on
+/// other threads, such as spawned tasks, and inside [`recoverable`] sections
+/// are left to their caller. Only the first call has any effect.
+pub fn install(handle_interrupt: bool) {
+    INSTALL.call_once(|| {
+        let main_thread = std::thread::current().id();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if panic_is_fatal(main_thread) {
+                handle_crash();
+            }
+            previous(info);
+        }));
+
+        tokio::spawn(watch_signals(handle_interrupt));
+    });
+}
+
+fn handle_crash() {
+    restore_terminal();
+    for conversation_id in flush_all() {
+        if mark_session_recoverable(&conversation_id).is_ok() {
+            eprintln!(
+                "Conversation saved. Resume it with `hoosh --resume {}`",
+                conversation_id
+            );
+        }
+    }
+}
+
+/// Undo raw mode and the terminal features the TUI turns on, so the shell is
+/// usable after a crash. A no-op outside raw mode.
+fn restore_terminal() {
+    if !is_raw_mode_enabled().unwrap_or(false) {
+        return;
+    }
+    let mut stdout = std::io::stdout();
+    if ALTERNATE_SCREEN.load(Ordering::Relaxed) {
+        let _ = execute!(stdout, DisableMouseCapture, LeaveAlternateScreen);
+    }
+    let _ = execute!(stdout, DisableFocusChange, DisableBracketedPaste, Show);
+    let _ = disable_raw_mode();
+}
+
+#[cfg(unix)]
+async fn watch_signals(handle_interrupt: bool) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let (Ok(mut terminate), Ok(mut hangup)) = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::hangup()),
+    ) else {
+        return;
+    };
+    let interrupt = async {
+        if handle_interrupt {
+            let _ = tokio::signal::ctrl_c().await;
+        } else {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    // Exit codes follow the shell's 128 + signal number convention.
+    let code = tokio::select! {
+        _ = terminate.recv() => 143,
+        _ = hangup.recv() => 129,
+        _ = interrupt => 130,
+    };
+    handle_crash();
+    std::process::exit(code);
+}
+
+#[cfg(not(unix))]
+async fn watch_signals(handle_interrupt: bool) {
+    if handle_interrupt && tokio::signal::ctrl_c().await.is_ok() {
+        handle_crash();
+        std::process::exit(130);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ConversationStorage;
+
+    #[tokio::test]
+    async fn abrupt_drop_still_persists_latest_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(ConversationStorage::with_root(dir.path()));
+        let mut conversation =
+            Conversation::with_storage("conv_crash".to_string(), Arc::clone(&storage)).unwrap();
+        conversation.add_user_message("first".to_string());
+
+        // Make the next append fail, as a full disk would, then let the disk
+        // recover before the crash.
+        let log = dir.path().join("conv_crash").join("messages.jsonl");
+        std::fs::remove_file(&log).unwrap();
+        std::fs::create_dir(&log).unwrap();
+        conversation.add_assistant_message(Some("latest".to_string()), None);
+        std::fs::remove_dir(&log).unwrap();
+
+        let conversation = Arc::new(tokio::sync::Mutex::new(conversation));
+        let _registration = guard_conversation(Arc::clone(&conversation), "conv_crash");
+        // No clean shutdown: the conversation is never dropped or saved.
+        std::mem::forget(conversation);
+
+        assert!(flush_all().contains(&"conv_crash".to_string()));
+
+        let reloaded = Conversation::load("conv_crash", storage).unwrap();
+        let contents: Vec<Option<&str>> = reloaded
+            .messages
+            .iter()
+            .map(|m| m.content.as_deref())
+            .collect();
+        assert_eq!(contents, vec![Some("first"), Some("latest")]);
+    }
+
+    #[test]
+    fn dropping_the_registration_stops_flushing() {
+        let conversation = Arc::new(tokio::sync::Mutex::new(Conversation::new()));
+        let registration = guard_conversation(conversation, "conv_dropped");
+        drop(registration);
+
+        assert!(!flush_all().contains(&"conv_dropped".to_string()));
+    }
+
+    #[test]
+    fn recoverable_section_covers_a_caught_panic_and_then_ends() {
+        assert!(!in_recoverable_section());
+        let caught = std::panic::catch_unwind(|| {
+            recoverable(|| {
+                assert!(in_recoverable_section());
+                panic!("tool bug");
+            })
+        });
+        assert!(caught.is_err());
+        assert!(!in_recoverable_section());
+    }
+
+    #[test]
+    fn only_panics_on_the_session_thread_are_fatal() {
+        let main_thread = std::thread::current().id();
+        assert!(panic_is_fatal(main_thread));
+        assert!(!recoverable(|| panic_is_fatal(main_thread)));
+
+        let on_other_thread = std::thread::spawn(move || panic_is_fatal(main_thread))
+            .join()
+            .unwrap();
+        assert!(!on_other_thread);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn panics_in_spawned_tasks_are_not_fatal() {
+        let main_thread = std::thread::current().id();
+        let in_task = tokio::spawn(async move { panic_is_fatal(main_thread) })
+            .await
+            .unwrap();
+        assert!(!in_task);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod cleanup;
+pub mod crash;
 pub mod store;
 
-pub use cleanup::cleanup_stale_sessions;
+pub use cleanup::{
+    SessionState, StaleSession, classify_session, cleanup_stale_sessions, find_stale_sessions,
+    mark_session_recoverable, take_recoverable_session,
+};
 pub use store::{SessionFile, get_terminal_pid};
@@ -10,6 +10,9 @@ use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::path::PathBuf;
 
+/// `context` key holding the id of a conversation interrupted by a crash.
+const RECOVERABLE_KEY: &str = "recoverable_conversation";
+
 /// Session file structure for tagged mode context persistence
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionFile {
@@ -45,6 +48,28 @@ impl SessionFile {
         }
     }
 
+    /// Note that `conversation_id` was cut short by a crash or signal, so the
+    /// next session in this terminal can offer to resume it.
+    pub fn mark_recoverable(&mut self, conversation_id: &str) {
+        self.context.insert(
+            RECOVERABLE_KEY.to_string(),
+            serde_json::Value::String(conversation_id.to_string()),
+        );
+    }
+
+    /// The conversation left by a crash, if any.
+    pub fn recoverable(&self) -> Option<&str> {
+        self.context.get(RECOVERABLE_KEY)?.as_str()
+    }
+
+    /// The conversation left by a crash, clearing the note.
+    pub fn take_recoverable(&mut self) -> Option<String> {
+        match self.context.remove(RECOVERABLE_KEY)? {
+            serde_json::Value::String(id) => Some(id),
+            _ => None,
+        }
+    }
+
     /// Update last_accessed timestamp
     pub fn touch(&mut self) {
         self.last_accessed = Utc::now();
@@ -1,4 +1,6 @@
 mod skill_manager;
+mod skill_tool;
 
 pub use crate::storage::SkillStorageMode;
 pub use skill_manager::{Skill, SkillManager};
+pub use skill_tool::UseSkillTool;
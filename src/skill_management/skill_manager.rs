@@ -1,8 +1,9 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use gray_matter::Matter;
 use gray_matter::engine::YAML;
 use indexmap::IndexMap;
 use serde::Deserialize;
+use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -14,6 +15,8 @@ struct SkillFrontmatter {
     compatibility: Option<String>,
     #[serde(rename = "allowed-tools", default)]
     allowed_tools: Option<String>,
+    #[serde(default)]
+    parameters: Option<Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +27,8 @@ pub struct Skill {
     pub instructions: Option<String>,
     pub compatibility: Option<String>,
     pub allowed_tools: Option<String>,
+    /// JSON Schema describing the arguments the skill expects, if declared.
+    pub parameters: Option<Value>,
 }
 
 impl Skill {
@@ -44,6 +49,7 @@ impl Skill {
             instructions: Some(parsed.content.trim().to_string()),
             compatibility: parsed.data.compatibility,
             allowed_tools: parsed.data.allowed_tools,
+            parameters: parsed.data.parameters,
         })
     }
 
@@ -66,6 +72,7 @@ impl Skill {
             instructions: None,
             compatibility: None,
             allowed_tools: None,
+            parameters: None,
         })
     }
 
@@ -76,6 +83,15 @@ impl Skill {
             self.path.clone()
         }
     }
+
+    pub fn required_parameters(&self) -> Vec<&str> {
+        self.parameters
+            .as_ref()
+            .and_then(|schema| schema.get("required"))
+            .and_then(|required| required.as_array())
+            .map(|required| required.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
 }
 
 fn extract_description(content: &str) -> String {
@@ -169,13 +185,45 @@ impl SkillManager {
         }
     }
 
+    /// Check `args` against the skill's declared parameter schema. Skills
+    /// without a schema accept any arguments.
+    pub fn validate_arguments(&self, skill: &Skill, args: &Value) -> Result<()> {
+        let Some(schema) = &skill.parameters else {
+            return Ok(());
+        };
+
+        let compiled = jsonschema::JSONSchema::compile(schema)
+            .map_err(|e| anyhow!("Invalid parameter schema for skill '{}': {}", skill.name, e))?;
+
+        compiled.validate(args).map_err(|errors| {
+            let errors: Vec<String> = errors.map(|err| err.to_string()).collect();
+            anyhow!(
+                "Invalid arguments for skill '{}': {}",
+                skill.name,
+                errors.join("; ")
+            )
+        })
+    }
+
+    /// Find the skill called `name` and validate `args` against it, so an
+    /// invocation with missing or mistyped inputs fails before it runs.
+    pub fn resolve_invocation(&self, name: &str, args: &Value) -> Result<Skill> {
+        let skill = self
+            .discover_skills()?
+            .into_iter()
+            .find(|skill| skill.name == name)
+            .ok_or_else(|| anyhow!("Unknown skill '{}'", name))?;
+        self.validate_arguments(&skill, args)?;
+        Ok(skill)
+    }
+
     pub fn get_skills_summary(&self, skills: &[Skill]) -> String {
         if skills.is_empty() {
             return String::new();
         }
 
         let mut summary = "<available_skills>\n".to_string();
-        summary.push_str("Skills available for this project. Check relevant skills before falling back to raw bash commands. For folder-based skills, read SKILL.md at the listed path for full instructions.\n\n");
+        summary.push_str("Skills available for this project. Check relevant skills before falling back to raw bash commands. Invoke a skill with the use_skill tool, passing its required parameters as arguments.\n\n");
 
         for skill in skills {
             let entry = skill.entry_point();
@@ -189,6 +237,10 @@ impl SkillManager {
             } else {
                 summary.push_str("  (no description)\n");
             }
+            let required = skill.required_parameters();
+            if !required.is_empty() {
+                summary.push_str(&format!("  required parameters: {}\n", required.join(", ")));
+            }
         }
 
         summary.push_str("</available_skills>");
@@ -429,6 +481,124 @@ mod tests {
         Ok(())
     }
 
+    fn skill_with_params(tmp: &TempDir) -> Result<Skill> {
+        let skills_dir = make_skills_dir(tmp);
+        let skill_dir = skills_dir.join("release");
+        fs::create_dir_all(&skill_dir)?;
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: release\ndescription: Cut a release.\nparameters:\n  type: object\n  properties:\n    version:\n      type: string\n    dry_run:\n      type: boolean\n  required:\n    - version\n---\nTag and publish.",
+        )?;
+
+        let manager = SkillManager::with_roots(vec![skills_dir]);
+        Ok(manager.discover_skills()?.remove(0))
+    }
+
+    #[test]
+    fn skill_md_parameters_are_parsed() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+
+        assert!(skill.parameters.is_some());
+        assert_eq!(skill.required_parameters(), vec!["version"]);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_arguments_accepts_required_and_optional() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+        let manager = SkillManager::new();
+
+        manager.validate_arguments(&skill, &serde_json::json!({"version": "1.2.0"}))?;
+        manager.validate_arguments(
+            &skill,
+            &serde_json::json!({"version": "1.2.0", "dry_run": true}),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn validate_arguments_reports_missing_required() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+        let manager = SkillManager::new();
+
+        let err = manager
+            .validate_arguments(&skill, &serde_json::json!({"dry_run": true}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("release"));
+        assert!(err.contains("version"));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_arguments_reports_wrong_type() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+        let manager = SkillManager::new();
+
+        let err = manager
+            .validate_arguments(
+                &skill,
+                &serde_json::json!({"version": "1.2.0", "dry_run": "yes"}),
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("boolean"));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_arguments_without_schema_accepts_anything() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skills_dir = make_skills_dir(&tmp);
+        let script = skills_dir.join("deploy.sh");
+        fs::write(&script, "#!/bin/bash\n# Deploy\necho ok")?;
+        make_executable(&script);
+
+        let manager = SkillManager::with_roots(vec![skills_dir]);
+        let skills = manager.discover_skills()?;
+        manager.validate_arguments(&skills[0], &serde_json::json!({"anything": 1}))?;
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_invocation_validates_before_returning_the_skill() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+        let manager = SkillManager::with_roots(vec![skill.path.parent().unwrap().to_path_buf()]);
+
+        let resolved =
+            manager.resolve_invocation("release", &serde_json::json!({"version": "1.2.0"}))?;
+        assert_eq!(resolved.name, "release");
+
+        let err = manager
+            .resolve_invocation("release", &serde_json::json!({}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("version"));
+
+        let err = manager
+            .resolve_invocation("missing", &serde_json::json!({}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown skill 'missing'"));
+        Ok(())
+    }
+
+    #[test]
+    fn get_skills_summary_lists_required_parameters() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let skill = skill_with_params(&tmp)?;
+        let manager = SkillManager::new();
+
+        let summary = manager.get_skills_summary(&[skill]);
+        assert!(summary.contains("required parameters: version"));
+        Ok(())
+    }
+
     #[test]
     fn extract_description_skips_shebang() {
         let content = "#!/bin/bash\n# Deploy app\necho ok";
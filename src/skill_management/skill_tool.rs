@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::skill_management::SkillManager;
+use crate::tools::{Tool, ToolError, ToolExecutionContext, ToolResult};
+
+/// Invokes a skill by name. Arguments are checked against the skill's
+/// declared parameters before its instructions are handed to the model.
+pub struct UseSkillTool {
+    manager: SkillManager,
+}
+
+impl UseSkillTool {
+    pub fn new(manager: SkillManager) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl Tool for UseSkillTool {
+    fn name(&self) -> &'static str {
+        "use_skill"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "UseSkill"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invoke one of the available skills by name. Pass the inputs the skill \
+        declares as `arguments`; they are validated against its required \
+        parameters before the skill's instructions are returned for you to \
+        follow."
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the skill, as listed in available_skills."
+                },
+                "arguments": {
+                    "type": "object",
+                    "description": "Inputs for the skill, matching its declared parameters."
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        let name = args.get("name").and_then(Value::as_str).ok_or_else(|| {
+            ToolError::InvalidArguments {
+                tool: "use_skill".to_string(),
+                message: "missing required field: name".to_string(),
+            }
+        })?;
+        let arguments = args.get("arguments").cloned().unwrap_or_else(|| json!({}));
+
+        let skill = self
+            .manager
+            .resolve_invocation(name, &arguments)
+            .map_err(|e| ToolError::InvalidArguments {
+                tool: "use_skill".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let entry = skill.entry_point();
+        let mut output = match &skill.instructions {
+            Some(instructions) => format!(
+                "Skill '{}' ({}):\n\n{}",
+                skill.name,
+                entry.display(),
+                instructions
+            ),
+            None => format!(
+                "Skill '{}' is a script. Run it with bash: {}",
+                skill.name,
+                entry.display()
+            ),
+        };
+        if arguments.as_object().is_some_and(|map| !map.is_empty()) {
+            output.push_str(&format!("\n\nArguments: {}", arguments));
+        }
+        Ok(output)
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_read_only()
+            .build()
+            .expect("Failed to build use_skill permission descriptor")
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        let name = args.get("name").and_then(Value::as_str).unwrap_or("?");
+        format!("UseSkill({})", name)
+    }
+
+    fn result_summary(&self, result: &str) -> String {
+        result.lines().next().unwrap_or(result).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    fn release_skill_tool(tmp: &TempDir) -> UseSkillTool {
+        let skill_dir = tmp.path().join("release");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: release\ndescription: Cut a release.\nparameters:\n  type: object\n  properties:\n    version:\n      type: string\n  required:\n    - version\n---\nTag and publish.",
+        )
+        .unwrap();
+        UseSkillTool::new(SkillManager::with_roots(vec![tmp.path().to_path_buf()]))
+    }
+
+    #[tokio::test]
+    async fn returns_instructions_for_valid_arguments() {
+        let tmp = TempDir::new().unwrap();
+        let tool = release_skill_tool(&tmp);
+
+        let output = tool
+            .execute(
+                &json!({"name": "release", "arguments": {"version": "1.2.0"}}),
+                &make_context(),
+            )
+            .await
+            .unwrap();
+        assert!(output.contains("Tag and publish."));
+        assert!(output.contains("\"version\":\"1.2.0\""));
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_required_arguments_before_running() {
+        let tmp = TempDir::new().unwrap();
+        let tool = release_skill_tool(&tmp);
+
+        let result = tool
+            .execute(&json!({"name": "release"}), &make_context())
+            .await;
+        let Err(ToolError::InvalidArguments { message, .. }) = result else {
+            panic!("expected invalid arguments, got {:?}", result);
+        };
+        assert!(message.contains("version"));
+    }
+}
@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use super::IndexStorage;
-use crate::agent::ConversationMessage;
+use crate::agent::{ConversationMessage, Role};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationMetadata {
@@ -16,6 +17,48 @@ pub struct ConversationMetadata {
     pub message_count: usize,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// One record per backend call, including calls made by sub-agents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub usage: Vec<UsageRecord>,
+    /// Directory the conversation was started in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<PathBuf>,
+    /// Backend and model the conversation last ran with. Resuming the
+    /// conversation switches back to them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Tokens and cost of a single backend call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// `None` when the backend has no pricing for its model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Tool call id of the sub-agent task that made the call, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+    pub calls: usize,
+    pub subagent_calls: usize,
+    /// True if any call had no known cost, so `cost` is a lower bound.
+    pub cost_incomplete: bool,
+}
+
+impl UsageTotals {
+    pub fn total_tokens(&self) -> usize {
+        self.input_tokens + self.output_tokens
+    }
 }
 
 impl ConversationMetadata {
@@ -32,14 +75,53 @@ impl ConversationMetadata {
             updated_at: now,
             message_count: 0,
             name: None,
+            usage: Vec::new(),
+            working_dir: None,
+            backend: None,
+            model: None,
         }
     }
 
+    pub fn usage_totals(&self) -> UsageTotals {
+        self.usage
+            .iter()
+            .fold(UsageTotals::default(), |mut totals, record| {
+                totals.input_tokens += record.input_tokens;
+                totals.output_tokens += record.output_tokens;
+                match record.cost {
+                    Some(cost) => totals.cost += cost,
+                    None => totals.cost_incomplete = true,
+                }
+                totals.calls += 1;
+                if record.subagent.is_some() {
+                    totals.subagent_calls += 1;
+                }
+                totals
+            })
+    }
+
     pub fn with_title(mut self, title: String) -> Self {
         self.title = title;
         self
     }
 
+    pub fn with_origin(mut self, working_dir: &Path, backend: &str, model: &str) -> Self {
+        self.working_dir = Some(working_dir.to_path_buf());
+        self.with_backend(backend, model)
+    }
+
+    pub fn with_backend(mut self, backend: &str, model: &str) -> Self {
+        self.backend = Some(backend.to_string());
+        self.model = Some(model.to_string());
+        self
+    }
+
+    /// The directory the conversation was started in, when it differs from
+    /// `cwd`. File references in the transcript are relative to that one.
+    pub fn moved_from(&self, cwd: &Path) -> Option<&Path> {
+        self.working_dir.as_deref().filter(|dir| *dir != cwd)
+    }
+
     pub fn update(&mut self) {
         self.updated_at = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -101,6 +183,19 @@ impl ConversationStorage {
         self.conversation_dir(conversation_id).join("meta.json")
     }
 
+    /// Take the conversation's metadata lock, held until the returned file is
+    /// dropped. Every read-modify-write of meta.json holds it, so writers in
+    /// other handles, tasks or processes (sub-agents billing their usage)
+    /// don't overwrite each other's changes.
+    fn lock_metadata(&self, conversation_id: &str) -> Result<fs::File> {
+        let lock_path = self.conversation_dir(conversation_id).join("meta.lock");
+        let lock_file = fs::File::create(&lock_path).context("Failed to open metadata lock")?;
+        lock_file
+            .lock_exclusive()
+            .context("Failed to lock metadata")?;
+        Ok(lock_file)
+    }
+
     pub fn generate_conversation_id() -> String {
         let now = chrono::Local::now();
         format!("conv_{}", now.format("%Y%m%d_%H%M%S"))
@@ -122,7 +217,16 @@ impl ConversationStorage {
         let json =
             serde_json::to_string_pretty(metadata).context("Failed to serialize metadata")?;
 
-        fs::write(&metadata_path, json).context("Failed to write metadata file")?;
+        // Write beside the file and rename over it, so readers never see a
+        // half-written meta.json.
+        let dir = metadata_path
+            .parent()
+            .context("metadata file has no parent dir")?;
+        let tmp = tempfile::NamedTempFile::new_in(dir)
+            .context("Failed to create temp file for metadata")?;
+        fs::write(tmp.path(), json).context("Failed to write metadata file")?;
+        tmp.persist(&metadata_path)
+            .context("Failed to write metadata file")?;
 
         self.index.update_conversation(metadata)?;
 
@@ -156,6 +260,7 @@ impl ConversationStorage {
 
         writeln!(file, "{}", json).context("Failed to write message")?;
 
+        let _lock = self.lock_metadata(conversation_id)?;
         let mut metadata = self.load_metadata(conversation_id)?;
         metadata.message_count += 1;
         metadata.update();
@@ -191,6 +296,7 @@ impl ConversationStorage {
         }
         fs::rename(&tmp_path, &messages_path).context("Failed to swap rewritten messages file")?;
 
+        let _lock = self.lock_metadata(conversation_id)?;
         let mut metadata = self.load_metadata(conversation_id)?;
         metadata.message_count = messages.len();
         metadata.update();
@@ -224,6 +330,7 @@ impl ConversationStorage {
     }
 
     pub fn update_title(&self, conversation_id: &str, title: String) -> Result<()> {
+        let _lock = self.lock_metadata(conversation_id)?;
         let mut metadata = self.load_metadata(conversation_id)?;
         metadata.title = title;
         metadata.update();
@@ -232,6 +339,7 @@ impl ConversationStorage {
     }
 
     pub fn update_name(&self, conversation_id: &str, name: Option<String>) -> Result<()> {
+        let _lock = self.lock_metadata(conversation_id)?;
         let mut metadata = self.load_metadata(conversation_id)?;
         metadata.name = name.filter(|s| !s.is_empty());
         metadata.update();
@@ -239,6 +347,47 @@ impl ConversationStorage {
         Ok(())
     }
 
+    /// Record where and with which backend/model the conversation was started.
+    pub fn update_origin(
+        &self,
+        conversation_id: &str,
+        working_dir: &Path,
+        backend: &str,
+        model: &str,
+    ) -> Result<()> {
+        let _lock = self.lock_metadata(conversation_id)?;
+        let metadata =
+            self.load_metadata(conversation_id)?
+                .with_origin(working_dir, backend, model);
+        self.save_metadata(&metadata)?;
+        Ok(())
+    }
+
+    pub fn update_backend(&self, conversation_id: &str, backend: &str, model: &str) -> Result<()> {
+        let _lock = self.lock_metadata(conversation_id)?;
+        let metadata = self
+            .load_metadata(conversation_id)?
+            .with_backend(backend, model);
+        self.save_metadata(&metadata)?;
+        Ok(())
+    }
+
+    /// Append a usage record to the conversation's metadata on disk and return
+    /// the updated metadata. Reads under the metadata lock so records added
+    /// concurrently by sub-agents through a separate handle are kept.
+    pub fn record_usage(
+        &self,
+        conversation_id: &str,
+        record: UsageRecord,
+    ) -> Result<ConversationMetadata> {
+        let _lock = self.lock_metadata(conversation_id)?;
+        let mut metadata = self.load_metadata(conversation_id)?;
+        metadata.usage.push(record);
+        metadata.update();
+        self.save_metadata(&metadata)?;
+        Ok(metadata)
+    }
+
     /// Find a conversation by name. Returns the metadata when exactly one match exists.
     /// Errors when ambiguous; returns Ok(None) when no match.
     pub fn find_by_name(&self, name: &str) -> Result<Option<ConversationMetadata>> {
@@ -269,6 +418,29 @@ impl ConversationStorage {
     pub fn list_conversations(&self) -> Result<Vec<ConversationMetadata>> {
         self.index.list_conversations()
     }
+
+    /// Conversations whose title, name or user/assistant messages contain
+    /// `query`, ignoring case.
+    pub fn search(&self, query: &str) -> Result<Vec<ConversationMetadata>> {
+        let query = query.to_lowercase();
+        let contains = |text: &str| text.to_lowercase().contains(&query);
+
+        let mut hits = Vec::new();
+        for conv in self.list_conversations()? {
+            let matched = contains(&conv.title)
+                || conv.name.as_deref().is_some_and(contains)
+                || self
+                    .load_messages(&conv.id)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|m| matches!(m.role, Role::User | Role::Assistant))
+                    .any(|m| m.content.as_deref().is_some_and(contains));
+            if matched {
+                hits.push(conv);
+            }
+        }
+        Ok(hits)
+    }
 }
 
 #[cfg(test)]
@@ -368,6 +540,31 @@ mod tests {
         assert_eq!(conversations.len(), 3);
     }
 
+    #[test]
+    fn test_search_matches_titles_and_messages() {
+        let (storage, _temp) = create_test_storage();
+        storage.create_conversation("conv_a").unwrap();
+        storage
+            .update_title("conv_a", "Parser rewrite".to_string())
+            .unwrap();
+        storage.create_conversation("conv_b").unwrap();
+        storage
+            .append_message("conv_b", &create_test_message(Role::User, "Fix the LEXER"))
+            .unwrap();
+
+        let ids = |q: &str| -> Vec<String> {
+            storage
+                .search(q)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.id)
+                .collect()
+        };
+        assert_eq!(ids("parser"), vec!["conv_a"]);
+        assert_eq!(ids("lexer"), vec!["conv_b"]);
+        assert!(ids("nothing like this").is_empty());
+    }
+
     #[test]
     fn test_generate_conversation_id() {
         let id = ConversationStorage::generate_conversation_id();
@@ -429,4 +626,129 @@ mod tests {
         assert_eq!(updated_metadata.created_at, created_at);
         assert!(updated_metadata.updated_at >= created_at);
     }
+
+    #[test]
+    fn test_concurrent_usage_records_from_separate_handles_are_all_kept() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        ConversationStorage::with_root(&root)
+            .create_conversation("conv_parallel")
+            .unwrap();
+
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let root = root.clone();
+                std::thread::spawn(move || {
+                    let storage = ConversationStorage::with_root(&root);
+                    for _ in 0..10 {
+                        storage
+                            .record_usage(
+                                "conv_parallel",
+                                UsageRecord {
+                                    input_tokens: 1,
+                                    output_tokens: 1,
+                                    cost: None,
+                                    subagent: Some(format!("call_{}", writer)),
+                                },
+                            )
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let metadata = ConversationStorage::with_root(&root)
+            .load_metadata("conv_parallel")
+            .unwrap();
+        assert_eq!(metadata.usage.len(), 40);
+    }
+
+    #[test]
+    fn test_usage_round_trips_through_storage() {
+        let (storage, _temp) = create_test_storage();
+        storage.create_conversation("conv_usage").unwrap();
+
+        storage
+            .record_usage(
+                "conv_usage",
+                UsageRecord {
+                    input_tokens: 1000,
+                    output_tokens: 200,
+                    cost: Some(0.01),
+                    subagent: None,
+                },
+            )
+            .unwrap();
+        storage
+            .record_usage(
+                "conv_usage",
+                UsageRecord {
+                    input_tokens: 500,
+                    output_tokens: 100,
+                    cost: Some(0.005),
+                    subagent: Some("call_task".to_string()),
+                },
+            )
+            .unwrap();
+
+        let metadata = storage.load_metadata("conv_usage").unwrap();
+        assert_eq!(metadata.usage.len(), 2);
+        assert_eq!(metadata.usage[1].subagent.as_deref(), Some("call_task"));
+
+        let totals = metadata.usage_totals();
+        assert_eq!(totals.total_tokens(), 1800);
+        assert_eq!(totals.calls, 2);
+        assert_eq!(totals.subagent_calls, 1);
+        assert!((totals.cost - 0.015).abs() < 1e-9);
+        assert!(!totals.cost_incomplete);
+
+        let listed = storage.list_conversations().unwrap();
+        assert_eq!(listed[0].usage_totals(), totals);
+    }
+
+    #[test]
+    fn test_metadata_without_usage_still_parses() {
+        let json = r#"{"id":"c","title":"","created_at":1,"updated_at":1,"message_count":0}"#;
+        let metadata: ConversationMetadata = serde_json::from_str(json).unwrap();
+        assert!(metadata.usage.is_empty());
+        assert_eq!(metadata.usage_totals(), UsageTotals::default());
+        assert!(metadata.working_dir.is_none());
+        assert!(metadata.backend.is_none());
+        assert!(metadata.model.is_none());
+    }
+
+    #[test]
+    fn test_origin_round_trips_through_storage() {
+        let (storage, _temp) = create_test_storage();
+        storage.create_conversation("conv_origin").unwrap();
+
+        storage
+            .update_origin(
+                "conv_origin",
+                Path::new("/work/project"),
+                "anthropic",
+                "claude-sonnet",
+            )
+            .unwrap();
+
+        let metadata = storage.load_metadata("conv_origin").unwrap();
+        assert_eq!(
+            metadata.working_dir.as_deref(),
+            Some(Path::new("/work/project"))
+        );
+        assert_eq!(metadata.backend.as_deref(), Some("anthropic"));
+        assert_eq!(metadata.model.as_deref(), Some("claude-sonnet"));
+
+        let listed = storage.list_conversations().unwrap();
+        assert_eq!(listed[0].working_dir, metadata.working_dir);
+
+        assert!(metadata.moved_from(Path::new("/work/project")).is_none());
+        assert_eq!(
+            metadata.moved_from(Path::new("/elsewhere")),
+            Some(Path::new("/work/project"))
+        );
+    }
 }
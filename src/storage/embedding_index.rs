@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// One embedded message from a stored conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedSnippet {
+    pub conversation_id: String,
+    pub message_index: usize,
+    pub role: String,
+    pub text: String,
+    pub vector: Vec<f32>,
+}
+
+/// Embeddings of past conversation messages, searched by the recall tool.
+/// Vectors are only comparable within one embedding model, so an index built
+/// with a different model is discarded on load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingIndex {
+    pub model: String,
+    /// Messages already embedded per conversation; indexing resumes there.
+    #[serde(default)]
+    pub indexed: HashMap<String, usize>,
+    #[serde(default)]
+    pub snippets: Vec<EmbeddedSnippet>,
+}
+
+impl EmbeddingIndex {
+    pub const FILE_NAME: &'static str = "embeddings.json";
+
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Load the index at `path`, or start an empty one when the file is
+    /// missing or was built with another model.
+    pub fn load(path: &Path, model: &str) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new(model));
+        }
+        let content = fs::read_to_string(path).context("Failed to read embedding index")?;
+        let index: Self =
+            serde_json::from_str(&content).context("Failed to parse embedding index")?;
+        if index.model != model {
+            return Ok(Self::new(model));
+        }
+        Ok(index)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).context("Failed to serialize embedding index")?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json).context("Failed to write embedding index")?;
+        fs::rename(&tmp, path).context("Failed to replace embedding index")?;
+        Ok(())
+    }
+
+    /// Drop everything indexed for `conversation_id`, e.g. after its message
+    /// log was rewritten and indices no longer line up.
+    pub fn forget(&mut self, conversation_id: &str) {
+        self.indexed.remove(conversation_id);
+        self.snippets
+            .retain(|snippet| snippet.conversation_id != conversation_id);
+    }
+
+    /// The `k` snippets most similar to `query` by cosine similarity, best
+    /// first, skipping any from `exclude`.
+    pub fn nearest(
+        &self,
+        query: &[f32],
+        k: usize,
+        exclude: Option<&str>,
+    ) -> Vec<(f32, &EmbeddedSnippet)> {
+        let mut scored: Vec<(f32, &EmbeddedSnippet)> = self
+            .snippets
+            .iter()
+            .filter(|snippet| Some(snippet.conversation_id.as_str()) != exclude)
+            .filter_map(|snippet| {
+                cosine_similarity(query, &snippet.vector).map(|score| (score, snippet))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(k);
+        scored
+    }
+}
+
+/// `None` when the vectors differ in length or either is all zeros.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snippet(conversation_id: &str, text: &str, vector: &[f32]) -> EmbeddedSnippet {
+        EmbeddedSnippet {
+            conversation_id: conversation_id.to_string(),
+            message_index: 0,
+            role: "user".to_string(),
+            text: text.to_string(),
+            vector: vector.to_vec(),
+        }
+    }
+
+    fn tiny_index() -> EmbeddingIndex {
+        let mut index = EmbeddingIndex::new("test-model");
+        index.snippets = vec![
+            snippet("conv_a", "database migrations", &[1.0, 0.0, 0.0]),
+            snippet("conv_b", "css layout", &[0.0, 1.0, 0.0]),
+            snippet("conv_c", "schema changes", &[0.9, 0.1, 0.0]),
+            snippet("conv_d", "unrelated", &[0.0, 0.0, 1.0]),
+        ];
+        index
+    }
+
+    #[test]
+    fn test_nearest_orders_by_similarity() {
+        let index = tiny_index();
+        let hits = index.nearest(&[1.0, 0.05, 0.0], 2, None);
+
+        let texts: Vec<&str> = hits.iter().map(|(_, s)| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["database migrations", "schema changes"]);
+        assert!(hits[0].0 > hits[1].0);
+    }
+
+    #[test]
+    fn test_nearest_skips_excluded_conversation_and_mismatched_vectors() {
+        let mut index = tiny_index();
+        index
+            .snippets
+            .push(snippet("conv_e", "wrong dims", &[1.0, 0.0]));
+
+        let hits = index.nearest(&[1.0, 0.0, 0.0], 10, Some("conv_a"));
+
+        assert_eq!(hits[0].1.conversation_id, "conv_c");
+        assert!(hits.iter().all(|(_, s)| s.conversation_id != "conv_a"));
+        assert!(hits.iter().all(|(_, s)| s.conversation_id != "conv_e"));
+    }
+
+    #[test]
+    fn test_round_trip_and_model_change_discards() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(EmbeddingIndex::FILE_NAME);
+        let mut index = tiny_index();
+        index.indexed.insert("conv_a".to_string(), 3);
+        index.save(&path).unwrap();
+
+        assert_eq!(EmbeddingIndex::load(&path, "test-model").unwrap(), index);
+        let other = EmbeddingIndex::load(&path, "other-model").unwrap();
+        assert!(other.snippets.is_empty());
+        assert_eq!(other.model, "other-model");
+    }
+
+    #[test]
+    fn test_forget_drops_a_conversation() {
+        let mut index = tiny_index();
+        index.indexed.insert("conv_a".to_string(), 1);
+        index.forget("conv_a");
+
+        assert!(!index.indexed.contains_key("conv_a"));
+        assert!(index.snippets.iter().all(|s| s.conversation_id != "conv_a"));
+        assert_eq!(index.snippets.len(), 3);
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -99,12 +100,31 @@ impl IndexStorage {
 
         let json = serde_json::to_string_pretty(index).context("Failed to serialize index")?;
 
-        fs::write(&self.index_path, json).context("Failed to write index file")?;
+        // Rename into place so a concurrent reader never parses a partial file.
+        let dir = self.index_path.parent().unwrap_or(Path::new("."));
+        let tmp =
+            tempfile::NamedTempFile::new_in(dir).context("Failed to create temp file for index")?;
+        fs::write(tmp.path(), json).context("Failed to write index file")?;
+        tmp.persist(&self.index_path)
+            .context("Failed to write index file")?;
 
         Ok(())
     }
 
+    /// Take the index lock, held until the returned file is dropped, so
+    /// concurrent read-modify-writes don't drop each other's entries.
+    fn lock(&self) -> Result<fs::File> {
+        if let Some(parent) = self.index_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create index directory")?;
+        }
+        let lock_file = fs::File::create(self.index_path.with_extension("lock"))
+            .context("Failed to open index lock")?;
+        lock_file.lock_exclusive().context("Failed to lock index")?;
+        Ok(lock_file)
+    }
+
     pub fn add_conversation(&self, metadata: &ConversationMetadata) -> Result<()> {
+        let _lock = self.lock()?;
         let mut index = self.load()?;
         index.add(metadata.clone());
         self.save(&index)?;
@@ -112,6 +132,7 @@ impl IndexStorage {
     }
 
     pub fn update_conversation(&self, metadata: &ConversationMetadata) -> Result<()> {
+        let _lock = self.lock()?;
         let mut index = self.load()?;
         index.update(metadata.clone());
         self.save(&index)?;
@@ -119,6 +140,7 @@ impl IndexStorage {
     }
 
     pub fn remove_conversation(&self, conversation_id: &str) -> Result<()> {
+        let _lock = self.lock()?;
         let mut index = self.load()?;
         index.remove(conversation_id);
         self.save(&index)?;
@@ -144,6 +166,10 @@ mod tests {
             updated_at: 1234567890,
             message_count: 0,
             name: None,
+            usage: Vec::new(),
+            working_dir: None,
+            backend: None,
+            model: None,
         }
     }
 
@@ -1,11 +1,15 @@
 mod conversation;
+mod embedding_index;
 mod index;
 mod mode;
+mod transcript;
 
-pub use conversation::{ConversationMetadata, ConversationStorage};
+pub use conversation::{ConversationMetadata, ConversationStorage, UsageRecord, UsageTotals};
+pub use embedding_index::{EmbeddedSnippet, EmbeddingIndex, cosine_similarity};
 pub use index::{ConversationIndex, IndexStorage};
 pub use mode::{
     ConversationStorageMode, SkillStorageMode, deserialize_conversation_storage, encode_cwd,
     ensure_local_storage_gitignored, resolve_memory_root, resolve_skill_roots,
     resolve_storage_root,
 };
+pub use transcript::{ExportFormat, TRANSCRIPT_FORMAT, TRANSCRIPT_VERSION, Transcript};
@@ -39,11 +39,11 @@ where
         Some(Raw::Bool(false)) => Some(ConversationStorageMode::Off),
         Some(Raw::Mode(s)) => match s.as_str() {
             "off" => Some(ConversationStorageMode::Off),
-            "local" => Some(ConversationStorageMode::Local),
-            "central" => Some(ConversationStorageMode::Central),
+            "local" | "project" => Some(ConversationStorageMode::Local),
+            "central" | "user" => Some(ConversationStorageMode::Central),
             other => {
                 return Err(Error::custom(format!(
-                    "invalid conversation_storage value: {} (expected off|local|central|true|false)",
+                    "invalid conversation_storage value: {} (expected off|project|user|local|central|true|false)",
                     other
                 )));
             }
@@ -60,8 +60,8 @@ pub fn encode_cwd(cwd: &Path) -> String {
 ///
 /// Returns:
 /// - `Off`     → `None` (no on-disk storage)
-/// - `Local`   → `<cwd>/.hoosh/conversations`
-/// - `Central` → `<data_dir>/projects/<encoded-cwd>/conversations`
+/// - `Local`   → `<cwd>/.hoosh/conversations` (project scope)
+/// - `Central` → `<data_dir>/projects/<encoded-cwd>/conversations` (user scope)
 pub fn resolve_storage_root(
     mode: ConversationStorageMode,
     cwd: &Path,
@@ -328,6 +328,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_accepts_scope_names() {
+        #[derive(Deserialize)]
+        struct T {
+            #[serde(default, deserialize_with = "deserialize_conversation_storage")]
+            #[allow(dead_code)]
+            conversation_storage: Option<ConversationStorageMode>,
+        }
+        let project: T = toml::from_str(r#"conversation_storage = "project""#).unwrap();
+        assert_eq!(
+            project.conversation_storage,
+            Some(ConversationStorageMode::Local)
+        );
+        let user: T = toml::from_str(r#"conversation_storage = "user""#).unwrap();
+        assert_eq!(
+            user.conversation_storage,
+            Some(ConversationStorageMode::Central)
+        );
+    }
+
     #[test]
     fn deserialize_rejects_unknown_string() {
         let toml = r#"conversation_storage = "wat""#;
@@ -0,0 +1,371 @@
+//! Portable conversation transcripts for evals, replay and sharing.
+//!
+//! The JSON form is a single object:
+//!
+//! ```json
+//! {
+//!   "format": "hoosh-transcript",
+//!   "version": 1,
+//!   "metadata": { "id": "conv_…", "title": "…", "created_at": 1700000000, … },
+//!   "messages": [
+//!     { "role": "system" | "user" | "assistant" | "tool", "content": "…" | null,
+//!       "tool_calls": [{ "id": "…", "type": "function",
+//!                        "function": { "name": "…", "arguments": "<JSON string>" } }],
+//!       "tool_call_id": "…", "name": "…",
+//!       "attachments": [{ "kind": "image", "media_type": "image/png", "data": "<base64>" }] }
+//!   ]
+//! }
+//! ```
+//!
+//! `metadata` and each message are exactly what the conversation store keeps
+//! on disk, so optional fields are omitted rather than null. Within a
+//! version, fields are only ever added; anything that would change the
+//! meaning of an existing field bumps `version`, and import refuses versions
+//! it doesn't know.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::{ConversationMetadata, ConversationStorage};
+use crate::agent::{ConversationMessage, Role};
+
+pub const TRANSCRIPT_FORMAT: &str = "hoosh-transcript";
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const VARIANTS: &'static [&'static str] = &["json", "markdown"];
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => bail!("Invalid export format: {}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub format: String,
+    pub version: u32,
+    pub metadata: ConversationMetadata,
+    pub messages: Vec<ConversationMessage>,
+}
+
+impl Transcript {
+    pub fn new(metadata: ConversationMetadata, messages: Vec<ConversationMessage>) -> Self {
+        Self {
+            format: TRANSCRIPT_FORMAT.to_string(),
+            version: TRANSCRIPT_VERSION,
+            metadata,
+            messages,
+        }
+    }
+
+    pub fn load(storage: &ConversationStorage, conversation_id: &str) -> Result<Self> {
+        if !storage.conversation_exists(conversation_id) {
+            bail!("Conversation '{}' not found", conversation_id);
+        }
+        Ok(Self::new(
+            storage.load_metadata(conversation_id)?,
+            storage.load_messages(conversation_id)?,
+        ))
+    }
+
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Json => self.to_json(),
+            ExportFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize transcript")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let transcript: Self = serde_json::from_str(json).context("Failed to parse transcript")?;
+        if transcript.format != TRANSCRIPT_FORMAT {
+            bail!(
+                "Not a hoosh transcript (format is '{}', expected '{}')",
+                transcript.format,
+                TRANSCRIPT_FORMAT
+            );
+        }
+        if transcript.version > TRANSCRIPT_VERSION {
+            bail!(
+                "Transcript version {} is newer than this hoosh supports ({})",
+                transcript.version,
+                TRANSCRIPT_VERSION
+            );
+        }
+        Ok(transcript)
+    }
+
+    /// A readable rendering for sharing. System prompts are left out; tool
+    /// calls and results are kept so the reader can follow what happened.
+    pub fn to_markdown(&self) -> String {
+        let title = if self.metadata.title.is_empty() {
+            self.metadata.id.as_str()
+        } else {
+            self.metadata.title.as_str()
+        };
+        let mut out = format!("# {}\n", title);
+
+        for message in &self.messages {
+            let content = message.content.as_deref().unwrap_or("").trim();
+            match message.role {
+                Role::System => continue,
+                Role::User => out.push_str(&format!("\n## User\n\n{}\n", content)),
+                Role::Assistant => {
+                    out.push_str("\n## Assistant\n");
+                    if !content.is_empty() {
+                        out.push_str(&format!("\n{}\n", content));
+                    }
+                    for call in message.tool_calls.iter().flatten() {
+                        out.push_str(&format!(
+                            "\n**Tool call** `{}`\n\n```json\n{}\n```\n",
+                            call.function.name, call.function.arguments
+                        ));
+                    }
+                }
+                Role::Tool => {
+                    let name = message.name.as_deref().unwrap_or("tool");
+                    out.push_str(&format!(
+                        "\n**Tool result** `{}`\n\n```\n{}\n```\n",
+                        name, content
+                    ));
+                }
+            }
+        }
+        out
+    }
+
+    /// Store the transcript as a new conversation and return its id. The
+    /// original id and name are kept unless already taken in `storage`; an id
+    /// that isn't safe to use as a directory name is replaced.
+    pub fn import(&self, storage: &ConversationStorage) -> Result<String> {
+        let id = if !is_safe_id(&self.metadata.id) || storage.conversation_exists(&self.metadata.id)
+        {
+            unused_id(storage, &ConversationStorage::generate_conversation_id())
+        } else {
+            self.metadata.id.clone()
+        };
+        storage.create_conversation(&id)?;
+
+        let mut metadata = self.metadata.clone();
+        metadata.id = id.clone();
+        if let Some(name) = &metadata.name
+            && storage.find_by_name(name).ok().flatten().is_some()
+        {
+            metadata.name = None;
+        }
+        storage.save_metadata(&metadata)?;
+        storage.rewrite_messages(&id, &self.messages)?;
+        Ok(id)
+    }
+}
+
+/// Ids become directory names under the storage root, so an imported one may
+/// only use the characters generated ids do; this rules out separators and
+/// `..`.
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn unused_id(storage: &ConversationStorage, base: &str) -> String {
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                base.to_string()
+            } else {
+                format!("{}_{}", base, n)
+            }
+        })
+        .find(|candidate| !storage.conversation_exists(candidate))
+        .expect("unbounded candidate ids")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{Attachment, AttachmentKind, ToolCall, ToolFunction};
+    use crate::storage::{IndexStorage, UsageRecord};
+    use tempfile::TempDir;
+
+    fn storage(dir: &TempDir) -> ConversationStorage {
+        let index = IndexStorage::new(dir.path().join("index.json"));
+        ConversationStorage::new_with_index(dir.path(), index)
+    }
+
+    fn message(role: Role, content: Option<&str>) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: content.map(str::to_string),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    fn sample(storage: &ConversationStorage) -> String {
+        let id = "conv_20240101_120000";
+        storage.create_conversation(id).unwrap();
+        let mut metadata = storage
+            .load_metadata(id)
+            .unwrap()
+            .with_title("Fix the parser".to_string())
+            .with_origin(std::path::Path::new("/work/app"), "openai", "gpt-4o");
+        metadata.name = Some("parser".to_string());
+        metadata.usage.push(UsageRecord {
+            input_tokens: 120,
+            output_tokens: 30,
+            cost: Some(0.001),
+            subagent: None,
+        });
+        storage.save_metadata(&metadata).unwrap();
+
+        let mut user = message(Role::User, Some("Why does parse() panic?"));
+        user.attachments.push(Attachment {
+            kind: AttachmentKind::Image,
+            media_type: "image/png".to_string(),
+            data: vec![0x89, b'P', b'N', b'G'],
+        });
+        let mut assistant = message(Role::Assistant, None);
+        assistant.tool_calls = Some(vec![ToolCall {
+            id: "call_1".to_string(),
+            r#type: "function".to_string(),
+            function: ToolFunction {
+                name: "read_file".to_string(),
+                arguments: r#"{"path":"src/parser.rs"}"#.to_string(),
+            },
+        }]);
+        let mut tool = message(Role::Tool, Some("fn parse() { unwrap() }"));
+        tool.tool_call_id = Some("call_1".to_string());
+        tool.name = Some("read_file".to_string());
+
+        let messages = vec![
+            message(Role::System, Some("You are hoosh.")),
+            user,
+            assistant,
+            tool,
+            message(Role::Assistant, Some("It unwraps a None.")),
+        ];
+        storage.rewrite_messages(id, &messages).unwrap();
+        id.to_string()
+    }
+
+    #[test]
+    fn export_then_import_preserves_everything() {
+        let source_dir = TempDir::new().unwrap();
+        let source = storage(&source_dir);
+        let id = sample(&source);
+        let json = Transcript::load(&source, &id).unwrap().to_json().unwrap();
+
+        let target_dir = TempDir::new().unwrap();
+        let target = storage(&target_dir);
+        let imported_id = Transcript::from_json(&json)
+            .unwrap()
+            .import(&target)
+            .unwrap();
+        assert_eq!(imported_id, id);
+
+        let reexported = Transcript::load(&target, &imported_id).unwrap();
+        let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let roundtrip = serde_json::to_value(&reexported).unwrap();
+        assert_eq!(roundtrip["messages"], original["messages"]);
+        for field in [
+            "title",
+            "name",
+            "usage",
+            "working_dir",
+            "backend",
+            "model",
+            "created_at",
+        ] {
+            assert_eq!(
+                roundtrip["metadata"][field], original["metadata"][field],
+                "{field}"
+            );
+        }
+        assert_eq!(reexported.metadata.message_count, 5);
+    }
+
+    #[test]
+    fn importing_twice_gets_a_fresh_id_and_drops_the_taken_name() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(&dir);
+        let id = sample(&storage);
+        let transcript = Transcript::load(&storage, &id).unwrap();
+
+        let copy = transcript.import(&storage).unwrap();
+        assert_ne!(copy, id);
+        let metadata = storage.load_metadata(&copy).unwrap();
+        assert_eq!(metadata.name, None);
+        assert_eq!(storage.load_messages(&copy).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn import_replaces_ids_that_would_escape_the_storage_root() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("a").join("b");
+        let storage =
+            ConversationStorage::new_with_index(&root, IndexStorage::new(root.join("index.json")));
+        let id = sample(&storage);
+        let mut transcript = Transcript::load(&storage, &id).unwrap();
+
+        for unsafe_id in ["../../escaped", "a/b", "..", ""] {
+            transcript.metadata.id = unsafe_id.to_string();
+            let imported = transcript.import(&storage).unwrap();
+            assert!(is_safe_id(&imported), "{imported}");
+        }
+        assert!(!dir.path().join("escaped").exists());
+    }
+
+    #[test]
+    fn rejects_foreign_or_newer_documents() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(&dir);
+        let id = sample(&storage);
+        let mut value = serde_json::to_value(Transcript::load(&storage, &id).unwrap()).unwrap();
+
+        value["version"] = serde_json::json!(TRANSCRIPT_VERSION + 1);
+        assert!(Transcript::from_json(&value.to_string()).is_err());
+        value["version"] = serde_json::json!(TRANSCRIPT_VERSION);
+        value["format"] = serde_json::json!("something-else");
+        assert!(Transcript::from_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn markdown_shows_the_exchange_without_system_prompts() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(&dir);
+        let id = sample(&storage);
+        let markdown = Transcript::load(&storage, &id)
+            .unwrap()
+            .render(ExportFormat::Markdown)
+            .unwrap();
+
+        assert!(markdown.starts_with("# Fix the parser\n"));
+        assert!(markdown.contains("## User\n\nWhy does parse() panic?"));
+        assert!(markdown.contains("**Tool call** `read_file`"));
+        assert!(markdown.contains("fn parse() { unwrap() }"));
+        assert!(!markdown.contains("You are hoosh."));
+    }
+}
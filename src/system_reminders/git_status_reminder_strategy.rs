@@ -0,0 +1,237 @@
+use crate::Conversation;
+use crate::system_reminders::{ReminderContext, ReminderStrategy, SideEffectResult};
+use anyhow::Result;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+pub struct GitStatusReminderStrategy {
+    working_dir: PathBuf,
+    step_interval: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct GitStatusSummary {
+    branch: String,
+    staged: usize,
+    unstaged: usize,
+    untracked: usize,
+}
+
+impl GitStatusSummary {
+    fn is_dirty(&self) -> bool {
+        self.staged + self.unstaged + self.untracked > 0
+    }
+
+    fn to_reminder(&self) -> String {
+        let tree = if self.is_dirty() { "dirty" } else { "clean" };
+        format!(
+            "Git status: on branch `{}`, {} staged, {} unstaged, {} untracked (working tree {}).",
+            self.branch, self.staged, self.unstaged, self.untracked, tree
+        )
+    }
+}
+
+impl GitStatusReminderStrategy {
+    /// `step_interval` of 0 disables the reminder.
+    pub fn new(working_dir: PathBuf, step_interval: usize) -> Self {
+        Self {
+            working_dir,
+            step_interval,
+        }
+    }
+
+    fn is_due(&self, step: usize) -> bool {
+        self.step_interval > 0 && step > 0 && step.is_multiple_of(self.step_interval)
+    }
+
+    async fn read_status(&self) -> Option<GitStatusSummary> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain", "--branch"])
+            .current_dir(&self.working_dir)
+            .output()
+            .await
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+fn parse_porcelain(output: &str) -> GitStatusSummary {
+    let mut summary = GitStatusSummary::default();
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("## ") {
+            summary.branch = parse_branch(header);
+            continue;
+        }
+
+        let mut codes = line.chars();
+        let (Some(index), Some(worktree)) = (codes.next(), codes.next()) else {
+            continue;
+        };
+
+        if index == '?' {
+            summary.untracked += 1;
+            continue;
+        }
+        if index != ' ' {
+            summary.staged += 1;
+        }
+        if worktree != ' ' {
+            summary.unstaged += 1;
+        }
+    }
+
+    summary
+}
+
+fn parse_branch(header: &str) -> String {
+    if let Some(branch) = header.strip_prefix("No commits yet on ") {
+        return branch.trim().to_string();
+    }
+    if header.starts_with("HEAD (no branch)") {
+        return "detached HEAD".to_string();
+    }
+    header
+        .split("...")
+        .next()
+        .and_then(|b| b.split_whitespace().next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[async_trait::async_trait]
+impl ReminderStrategy for GitStatusReminderStrategy {
+    async fn apply(
+        &self,
+        conversation: &mut Conversation,
+        context: &ReminderContext,
+    ) -> Result<SideEffectResult> {
+        if !self.is_due(context.agent_step) {
+            return Ok(SideEffectResult::Continue);
+        }
+
+        if let Some(summary) = self.read_status().await {
+            conversation.add_system_message(summary.to_reminder());
+        }
+
+        Ok(SideEffectResult::Continue)
+    }
+
+    fn name(&self) -> &'static str {
+        "git_status_reminder"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Role;
+    use tempfile::TempDir;
+
+    fn create_context(step: usize) -> ReminderContext {
+        ReminderContext { agent_step: step }
+    }
+
+    fn init_repo(tmp: &TempDir) {
+        git2::Repository::init(tmp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_strategy_name() {
+        let strategy = GitStatusReminderStrategy::new(PathBuf::from("."), 5);
+        assert_eq!(strategy.name(), "git_status_reminder");
+    }
+
+    #[test]
+    fn parse_porcelain_counts_staged_unstaged_and_untracked() {
+        let output = "## main...origin/main [ahead 1]\nM  staged.rs\n M unstaged.rs\nMM both.rs\n?? new.rs\n";
+        let summary = parse_porcelain(output);
+
+        assert_eq!(
+            summary,
+            GitStatusSummary {
+                branch: "main".to_string(),
+                staged: 2,
+                unstaged: 2,
+                untracked: 1,
+            }
+        );
+        assert!(summary.is_dirty());
+    }
+
+    #[test]
+    fn parse_porcelain_clean_tree() {
+        let summary = parse_porcelain("## feature/x\n");
+        assert_eq!(summary.branch, "feature/x");
+        assert!(!summary.is_dirty());
+        assert!(summary.to_reminder().contains("working tree clean"));
+    }
+
+    #[test]
+    fn parse_branch_handles_unborn_and_detached() {
+        assert_eq!(parse_branch("No commits yet on master"), "master");
+        assert_eq!(parse_branch("HEAD (no branch)"), "detached HEAD");
+    }
+
+    #[tokio::test]
+    async fn injects_reminder_only_at_interval() {
+        let tmp = TempDir::new().unwrap();
+        init_repo(&tmp);
+        std::fs::write(tmp.path().join("file.txt"), "hello").unwrap();
+        let strategy = GitStatusReminderStrategy::new(tmp.path().to_path_buf(), 3);
+        let mut conversation = Conversation::new();
+
+        for step in 0..3 {
+            strategy
+                .apply(&mut conversation, &create_context(step))
+                .await
+                .unwrap();
+        }
+        assert!(conversation.messages.is_empty());
+
+        strategy
+            .apply(&mut conversation, &create_context(3))
+            .await
+            .unwrap();
+        let last = conversation.messages.last().unwrap();
+        assert_eq!(last.role, Role::System);
+        let content = last.content.as_deref().unwrap();
+        assert!(content.contains("1 untracked"));
+        assert!(content.contains("working tree dirty"));
+    }
+
+    #[tokio::test]
+    async fn noop_outside_git_repo() {
+        let tmp = TempDir::new().unwrap();
+        let strategy = GitStatusReminderStrategy::new(tmp.path().join("missing"), 1);
+        let mut conversation = Conversation::new();
+
+        let result = strategy
+            .apply(&mut conversation, &create_context(1))
+            .await
+            .unwrap();
+
+        assert!(matches!(result, SideEffectResult::Continue));
+        assert!(conversation.messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn zero_interval_disables_reminder() {
+        let tmp = TempDir::new().unwrap();
+        init_repo(&tmp);
+        let strategy = GitStatusReminderStrategy::new(tmp.path().to_path_buf(), 0);
+        let mut conversation = Conversation::new();
+
+        strategy
+            .apply(&mut conversation, &create_context(10))
+            .await
+            .unwrap();
+
+        assert!(conversation.messages.is_empty());
+    }
+}
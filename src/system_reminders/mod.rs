@@ -1,19 +1,81 @@
 pub mod budget_reminder_strategy;
+pub mod git_status_reminder_strategy;
 pub mod periodic_core_reminder_strategy;
 pub mod skill_reminder_strategy;
 pub mod todo_reminder_strategy;
 pub mod token_budget_reminder_strategy;
 
 pub use budget_reminder_strategy::BudgetReminderStrategy;
+pub use git_status_reminder_strategy::GitStatusReminderStrategy;
 pub use periodic_core_reminder_strategy::PeriodicCoreReminderStrategy;
 pub use skill_reminder_strategy::SkillReminderStrategy;
 pub use todo_reminder_strategy::TodoReminderStrategy;
 pub use token_budget_reminder_strategy::TokenBudgetReminderStrategy;
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 
 use crate::agent::Conversation;
 
+/// The built-in reminder strategies, as named in `[system_reminders]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    PeriodicCore,
+    Todo,
+    Skill,
+    GitStatus,
+    /// Step and time budget reminders for sub-agents.
+    Budget,
+}
+
+fn default_reminder_order() -> Vec<ReminderKind> {
+    vec![
+        ReminderKind::PeriodicCore,
+        ReminderKind::Todo,
+        ReminderKind::Skill,
+        ReminderKind::GitStatus,
+        ReminderKind::Budget,
+    ]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SystemReminderConfig {
+    /// Order the strategies run in. A strategy left out of the list is
+    /// never added.
+    #[serde(default = "default_reminder_order")]
+    pub order: Vec<ReminderKind>,
+    /// Strategies that are never added, wherever they appear in `order`.
+    #[serde(default)]
+    pub disabled: Vec<ReminderKind>,
+}
+
+impl Default for SystemReminderConfig {
+    fn default() -> Self {
+        Self {
+            order: default_reminder_order(),
+            disabled: Vec::new(),
+        }
+    }
+}
+
+impl SystemReminderConfig {
+    pub fn is_enabled(&self, kind: ReminderKind) -> bool {
+        self.order.contains(&kind) && !self.disabled.contains(&kind)
+    }
+
+    /// The enabled strategies in the order they run, each listed once.
+    pub fn enabled(&self) -> Vec<ReminderKind> {
+        let mut enabled = Vec::new();
+        for kind in &self.order {
+            if !enabled.contains(kind) && !self.disabled.contains(kind) {
+                enabled.push(*kind);
+            }
+        }
+        enabled
+    }
+}
+
 #[derive(Debug)]
 pub enum SideEffectResult {
     Continue,
@@ -55,11 +117,29 @@ impl SystemReminder {
         }
     }
 
+    /// Add the strategies `config` enables, in its order. `build` returns
+    /// `None` for a kind that doesn't apply in this context; disabled kinds
+    /// are never built.
+    pub fn from_config(
+        config: &SystemReminderConfig,
+        mut build: impl FnMut(ReminderKind) -> Option<Box<dyn ReminderStrategy>>,
+    ) -> Self {
+        config
+            .enabled()
+            .into_iter()
+            .filter_map(&mut build)
+            .fold(Self::new(), Self::add_strategy)
+    }
+
     pub fn add_strategy(mut self, strategy: Box<dyn ReminderStrategy>) -> Self {
         self.strategies.push(strategy);
         self
     }
 
+    pub fn strategy_names(&self) -> Vec<&'static str> {
+        self.strategies.iter().map(|s| s.name()).collect()
+    }
+
     pub async fn apply(
         &self,
         conversation: &mut Conversation,
@@ -76,3 +156,65 @@ impl SystemReminder {
         Ok(SideEffectResult::Continue)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(kind: ReminderKind) -> Option<Box<dyn ReminderStrategy>> {
+        match kind {
+            ReminderKind::PeriodicCore => Some(Box::new(PeriodicCoreReminderStrategy::new(
+                0,
+                "Stay on task.".to_string(),
+            ))),
+            ReminderKind::GitStatus => Some(Box::new(GitStatusReminderStrategy::new(
+                std::env::temp_dir(),
+                0,
+            ))),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn enabled_follows_order_without_disabled_or_duplicates() {
+        let config = SystemReminderConfig {
+            order: vec![
+                ReminderKind::GitStatus,
+                ReminderKind::Todo,
+                ReminderKind::PeriodicCore,
+                ReminderKind::GitStatus,
+            ],
+            disabled: vec![ReminderKind::Todo],
+        };
+
+        assert_eq!(
+            config.enabled(),
+            vec![ReminderKind::GitStatus, ReminderKind::PeriodicCore]
+        );
+        assert!(!config.is_enabled(ReminderKind::Todo));
+        assert!(!config.is_enabled(ReminderKind::Skill));
+    }
+
+    #[tokio::test]
+    async fn disabled_strategy_injects_nothing() {
+        let context = ReminderContext { agent_step: 1 };
+
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("x".repeat(400));
+        let enabled = SystemReminder::from_config(&SystemReminderConfig::default(), build);
+        enabled.apply(&mut conversation, &context).await.unwrap();
+        assert_eq!(conversation.messages.len(), 2);
+
+        let config = SystemReminderConfig {
+            disabled: vec![ReminderKind::PeriodicCore],
+            ..Default::default()
+        };
+        let disabled = SystemReminder::from_config(&config, build);
+        assert!(!disabled.strategy_names().contains(&"periodic_core"));
+
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("x".repeat(400));
+        disabled.apply(&mut conversation, &context).await.unwrap();
+        assert_eq!(conversation.messages.len(), 1);
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct PeriodicCoreReminderStrategy {
     token_interval: usize,
+    step_interval: usize,
     core_instructions: String,
     last_reminder_token_count: Arc<AtomicUsize>,
 }
@@ -14,10 +15,21 @@ impl PeriodicCoreReminderStrategy {
     pub fn new(token_interval: usize, core_instructions: String) -> Self {
         Self {
             token_interval,
+            step_interval: 0,
             core_instructions,
             last_reminder_token_count: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Also re-inject every `step_interval` agent steps; 0 disables the step trigger.
+    pub fn with_step_interval(mut self, step_interval: usize) -> Self {
+        self.step_interval = step_interval;
+        self
+    }
+
+    fn step_due(&self, step: usize) -> bool {
+        self.step_interval > 0 && step > 0 && step.is_multiple_of(self.step_interval)
+    }
 }
 
 #[async_trait::async_trait]
@@ -25,12 +37,13 @@ impl ReminderStrategy for PeriodicCoreReminderStrategy {
     async fn apply(
         &self,
         conversation: &mut Conversation,
-        _context: &ReminderContext,
+        context: &ReminderContext,
     ) -> Result<SideEffectResult> {
         let current_tokens = conversation.estimate_token();
         let last_tokens = self.last_reminder_token_count.load(Ordering::SeqCst);
+        let tokens_due = current_tokens.saturating_sub(last_tokens) > self.token_interval;
 
-        if current_tokens.saturating_sub(last_tokens) > self.token_interval {
+        if tokens_due || self.step_due(context.agent_step) {
             conversation.add_system_message(self.core_instructions.clone());
             self.last_reminder_token_count
                 .store(current_tokens, Ordering::SeqCst);
@@ -157,6 +170,72 @@ mod tests {
         assert!(matches!(result.unwrap(), SideEffectResult::Continue));
     }
 
+    fn system_message_count(conversation: &Conversation) -> usize {
+        conversation
+            .messages
+            .iter()
+            .filter(|m| m.role == Role::System)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_step_interval_injects_exactly_at_interval() {
+        let strategy =
+            PeriodicCoreReminderStrategy::new(usize::MAX, "Core".to_string()).with_step_interval(3);
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("hello".to_string());
+
+        let mut injected_at = Vec::new();
+        for step in 0..10 {
+            let before = system_message_count(&conversation);
+            strategy
+                .apply(&mut conversation, &ReminderContext { agent_step: step })
+                .await
+                .unwrap();
+            if system_message_count(&conversation) > before {
+                injected_at.push(step);
+            }
+        }
+
+        assert_eq!(injected_at, vec![3, 6, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_step_interval_zero_disables_step_trigger() {
+        let strategy =
+            PeriodicCoreReminderStrategy::new(usize::MAX, "Core".to_string()).with_step_interval(0);
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("hello".to_string());
+
+        for step in 0..10 {
+            strategy
+                .apply(&mut conversation, &ReminderContext { agent_step: step })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(system_message_count(&conversation), 0);
+    }
+
+    #[tokio::test]
+    async fn test_step_trigger_resets_token_baseline() {
+        let strategy =
+            PeriodicCoreReminderStrategy::new(100, "Core".to_string()).with_step_interval(2);
+        let mut conversation = Conversation::new();
+        conversation.add_user_message("x".repeat(300));
+
+        strategy
+            .apply(&mut conversation, &ReminderContext { agent_step: 2 })
+            .await
+            .unwrap();
+        strategy
+            .apply(&mut conversation, &ReminderContext { agent_step: 3 })
+            .await
+            .unwrap();
+
+        assert_eq!(system_message_count(&conversation), 1);
+    }
+
     #[test]
     fn test_token_estimation() {
         let mut conversation = Conversation::new();
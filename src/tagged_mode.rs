@@ -130,7 +130,7 @@ pub async fn run_tagged_mode(
         return Ok(());
     }
 
-    let expanded = event_loop_context
+    let mut expanded = event_loop_context
         .system_resources
         .parser
         .expand(&input)
@@ -140,6 +140,30 @@ pub async fn run_tagged_mode(
             ..Default::default()
         });
 
+    for event in &expanded.events {
+        if let AgentEvent::LargeFileRead {
+            path,
+            bytes,
+            truncated,
+        } = event
+        {
+            console().warning(&crate::tools::file_ops::large_read_message(
+                path, *bytes, *truncated,
+            ));
+        }
+    }
+
+    // There is no one to confirm with here, so secrets are redacted instead.
+    if let Some(scanner) = &event_loop_context.runtime.prompt_secret_scanner {
+        if let std::borrow::Cow::Owned(text) = scanner.redact(&expanded.text) {
+            console().warning("Redacted what looks like a secret from the message");
+            expanded.text = text;
+        }
+        for mention in &mut expanded.mentions {
+            mention.redact_secrets(scanner);
+        }
+    }
+
     let memory_manager = event_loop_context
         .runtime
         .memory_mode_manager
@@ -190,7 +214,14 @@ pub async fn run_tagged_mode(
             .context_manager
             .clone(),
     )
-    .with_system_reminder(event_loop_context.system_resources.system_reminder.clone());
+    .with_system_reminder(event_loop_context.system_resources.system_reminder.clone())
+    .with_request_limiter(
+        event_loop_context
+            .system_resources
+            .request_limiters
+            .for_backend(event_loop_context.system_resources.backend.backend_name()),
+    )
+    .with_max_steps(event_loop_context.runtime.config.get_agent_max_steps());
 
     // Start spinner (text mode only)
     let mut spinner = TerminalSpinner::new("Processing");
@@ -269,7 +300,9 @@ pub async fn run_tagged_mode(
                         if !json_mode {
                             spinner.stop();
                             console().newline();
-                            console().plain(&preview);
+                            console().plain(&crate::tools::file_ops::diff_preview::colorize_diff(
+                                &preview,
+                            ));
                             console().newline();
                             spinner.start();
                         }
@@ -295,6 +328,15 @@ pub async fn run_tagged_mode(
                             spinner.start();
                         }
                     }
+                    AgentEvent::LargeFileRead { path, bytes, truncated } => {
+                        if !json_mode {
+                            spinner.stop();
+                            console().warning(&crate::tools::file_ops::large_read_message(
+                                &path, bytes, truncated,
+                            ));
+                            spinner.start();
+                        }
+                    }
                     AgentEvent::TokenUsage { input_tokens, output_tokens, .. } => {
                         total_input_tokens += input_tokens;
                         total_output_tokens += output_tokens;
@@ -314,6 +356,28 @@ pub async fn run_tagged_mode(
                         error_message = Some(err);
                         break;
                     }
+                    AgentEvent::BackendError { message, hint } => {
+                        if !json_mode {
+                            spinner.stop();
+                            console().newline();
+                            console().error(&message);
+                            if let Some(hint) = hint {
+                                console().info(&hint);
+                            }
+                            return Ok(());
+                        }
+                        error_message = Some(message);
+                        break;
+                    }
+                    AgentEvent::MaxStepsReached(max_steps) => {
+                        if !json_mode {
+                            spinner.stop();
+                            console().warning(&format!(
+                                "Step limit ({}) reached, stopping. Raise agent_max_steps to allow longer turns.",
+                                max_steps
+                            ));
+                        }
+                    }
                     AgentEvent::Exit => {
                         if !json_mode { spinner.stop(); }
                         break;
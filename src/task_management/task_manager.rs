@@ -3,11 +3,15 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 
 use crate::agent::{Agent, AgentEvent, Conversation, Role};
-use crate::backends::LlmBackend;
+use crate::backends::{LlmBackend, RequestLimiters};
+use crate::config::AppConfig;
 use crate::permissions::PermissionManager;
-use crate::storage::ConversationStorage;
-use crate::system_reminders::{BudgetReminderStrategy, SystemReminder};
+use crate::storage::{ConversationStorage, UsageRecord};
+use crate::system_reminders::{
+    BudgetReminderStrategy, ReminderKind, ReminderStrategy, SystemReminder,
+};
 use crate::task_management::{ExecutionBudget, TaskDefinition, TaskEvent, TaskResult};
+use crate::telemetry;
 use crate::tool_executor::ToolExecutor;
 use crate::tools::ToolRegistry;
 
@@ -18,6 +22,9 @@ pub struct TaskManager {
     event_tx: Option<mpsc::UnboundedSender<AgentEvent>>,
     tool_call_id: Option<String>,
     parent_conversation_id: Option<String>,
+    config: Option<AppConfig>,
+    request_limiters: Arc<RequestLimiters>,
+    conversation_storage: Option<Arc<ConversationStorage>>,
 }
 
 impl TaskManager {
@@ -33,9 +40,61 @@ impl TaskManager {
             event_tx: None,
             tool_call_id: None,
             parent_conversation_id: None,
+            config: None,
+            request_limiters: Arc::new(RequestLimiters::default()),
+            conversation_storage: None,
         }
     }
 
+    /// Limits shared with the main loop and other tasks, looked up by the
+    /// backend each task runs on.
+    pub fn with_request_limiters(mut self, limiters: Arc<RequestLimiters>) -> Self {
+        self.request_limiters = limiters;
+        self
+    }
+
+    /// Config used to build a backend for tasks that ask for their own model.
+    /// Without it every task runs on the manager's backend.
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// The manager's backend, or the same backend running `task_def.model`
+    /// when the task names a different one.
+    fn backend_for_task(&self, task_def: &TaskDefinition) -> Arc<dyn LlmBackend> {
+        let (Some(model), Some(config)) = (&task_def.model, &self.config) else {
+            return Arc::clone(&self.backend);
+        };
+        if model == self.backend.model_name() {
+            return Arc::clone(&self.backend);
+        }
+
+        match crate::backends::backend_factory::create_backend_with_model(
+            self.backend.backend_name(),
+            config,
+            model,
+        ) {
+            Ok(backend) => Arc::from(backend),
+            Err(e) => {
+                tracing::warn!(
+                    "Model '{}' unavailable for task, using {}: {}",
+                    model,
+                    self.backend.model_name(),
+                    e
+                );
+                Arc::clone(&self.backend)
+            }
+        }
+    }
+
+    /// Storage holding the parent conversation. Without it the storage is
+    /// resolved from the config and the current directory.
+    pub fn with_conversation_storage(mut self, storage: Arc<ConversationStorage>) -> Self {
+        self.conversation_storage = Some(storage);
+        self
+    }
+
     pub fn with_event_sender(mut self, tx: mpsc::UnboundedSender<AgentEvent>) -> Self {
         self.event_tx = Some(tx);
         self
@@ -63,34 +122,75 @@ impl TaskManager {
 
         // The tool_registry passed to TaskManager is already the subagent registry
         // (without task tool) to prevent infinite recursion
-        let tool_executor = Arc::new(
-            ToolExecutor::new(
-                Arc::clone(&self.tool_registry),
-                Arc::clone(&self.permission_manager),
-            )
-            .with_event_sender(event_tx.clone()),
-        );
+        let mut tool_executor = ToolExecutor::new(
+            Arc::clone(&self.tool_registry),
+            Arc::clone(&self.permission_manager),
+        )
+        .with_event_sender(event_tx.clone());
+        if let Some(config) = &self.config {
+            tool_executor = tool_executor
+                .with_tool_retries(config.get_tool_retries())
+                .with_turn_retry_budget(config.get_turn_retry_budget())
+                .with_argument_validation(config.get_validate_tool_arguments())
+                .with_dry_run(config.dry_run);
+            if let Some(redactor) = config.secret_redactor() {
+                tool_executor = tool_executor.with_secret_redactor(Arc::new(redactor));
+            }
+            if let Some(scanner) = config.injection_scanner() {
+                tool_executor = tool_executor.with_injection_scanner(Arc::new(scanner));
+            }
+        }
+        let tool_executor = Arc::new(tool_executor);
 
         let max_steps = task_def.agent_type.max_steps();
-        let budget_strategy = Box::new(BudgetReminderStrategy::new(budget_arc.clone(), max_steps));
-        let system_reminder = Arc::new(SystemReminder::new().add_strategy(budget_strategy));
-
+        let reminders = self
+            .config
+            .as_ref()
+            .map(AppConfig::system_reminders)
+            .unwrap_or_default();
+        let system_reminder = Arc::new(SystemReminder::from_config(&reminders, |kind| {
+            (kind == ReminderKind::Budget).then(|| {
+                Box::new(BudgetReminderStrategy::new(budget_arc.clone(), max_steps))
+                    as Box<dyn ReminderStrategy>
+            })
+        }));
+
+        let backend = self.backend_for_task(&task_def);
         let agent = Agent::new(
-            self.backend.clone(),
+            Arc::clone(&backend),
             self.tool_registry.clone(),
             tool_executor,
         )
         .with_max_steps(max_steps)
         .with_event_sender(event_tx)
         .with_system_reminder(system_reminder)
-        .with_thinking_budget(task_def.thinking_budget);
-
-        let conversation_storage = Arc::new(ConversationStorage::with_default_path()?);
+        .with_thinking_budget(task_def.thinking_budget)
+        .with_request_limiter(self.request_limiters.for_backend(backend.backend_name()));
+
+        let conversation_storage = match &self.conversation_storage {
+            Some(storage) => Arc::clone(storage),
+            None => {
+                let storage_root = match &self.config {
+                    Some(config) => std::env::current_dir()
+                        .ok()
+                        .and_then(|cwd| config.conversation_storage_root(&cwd).ok().flatten()),
+                    None => None,
+                };
+                Arc::new(match storage_root {
+                    Some(root) => ConversationStorage::with_root(root),
+                    None => ConversationStorage::with_default_path()?,
+                })
+            }
+        };
 
         let mut conversation = if let (Some(parent_id), Some(tool_call_id)) =
             (&self.parent_conversation_id, &self.tool_call_id)
         {
-            Conversation::with_subagent_storage(parent_id, tool_call_id, conversation_storage)?
+            Conversation::with_subagent_storage(
+                parent_id,
+                tool_call_id,
+                Arc::clone(&conversation_storage),
+            )?
         } else {
             Conversation::new()
         };
@@ -142,6 +242,11 @@ impl TaskManager {
                     timestamp: std::time::SystemTime::now(),
                 });
 
+                // Queueing affects the whole session, so it is passed up as is.
+                if let (Some(tx), AgentEvent::RequestQueued { .. }) = (&parent_event_tx, &event) {
+                    let _ = tx.send(event.clone());
+                }
+
                 if let (Some(tx), Some(tcid)) = (&parent_event_tx, &tool_call_id)
                     && should_emit_to_parent(&event)
                     && let Ok(progress_event) =
@@ -178,6 +283,24 @@ impl TaskManager {
 
         let total_steps = final_step + 1;
 
+        // Bill the sub-agent's spend to the conversation that spawned it.
+        if let (Some(parent_id), Some(tcid)) = (&self.parent_conversation_id, &self.tool_call_id)
+            && total_input_tokens + total_output_tokens > 0
+            && conversation_storage.conversation_exists(parent_id)
+        {
+            let record = UsageRecord {
+                input_tokens: total_input_tokens,
+                output_tokens: total_output_tokens,
+                cost: backend
+                    .pricing()
+                    .map(|p| p.calculate_cost(total_input_tokens, total_output_tokens)),
+                subagent: Some(tcid.clone()),
+            };
+            if let Err(e) = conversation_storage.record_usage(parent_id, record) {
+                tracing::warn!("Failed to record sub-agent token usage: {}", e);
+            }
+        }
+
         if let (Some(tx), Some(tcid)) = (&self.event_tx, &self.tool_call_id) {
             let _ = tx.send(AgentEvent::SubagentTaskComplete {
                 tool_call_id: tcid.clone(),
@@ -198,6 +321,25 @@ impl TaskManager {
                 max_steps: task_def.agent_type.max_steps(),
             });
 
+        let outcome = match &execute_result {
+            Ok(Ok(())) => "finished",
+            Ok(Err(_)) => "failed",
+            Err(_) => "timed out",
+        };
+        crate::notification::notify(
+            &format!("hoosh: {} agent {}", task_def.agent_type.as_str(), outcome),
+            &task_def.description,
+        );
+
+        telemetry::record_task_outcome(
+            task_def.agent_type.as_str(),
+            match &execute_result {
+                Ok(Ok(())) => "success",
+                Ok(Err(_)) => "failure",
+                Err(_) => "timeout",
+            },
+        );
+
         match execute_result {
             Ok(Ok(())) => {
                 let final_response = conversation
@@ -238,6 +380,7 @@ fn should_emit_to_parent(event: &AgentEvent) -> bool {
     matches!(
         event,
         AgentEvent::AssistantThought(_)
+            | AgentEvent::StepStarted { .. }
             | AgentEvent::ToolExecutionStarted { .. }
             | AgentEvent::ToolExecutionCompleted { .. }
             | AgentEvent::ToolResult { .. }
@@ -251,6 +394,7 @@ fn transform_to_subagent_event(
     budget: Arc<ExecutionBudget>,
 ) -> Result<AgentEvent, String> {
     let (action_type, description) = match event {
+        AgentEvent::StepStarted { step } => ("step", format!("Step {}", step + 1)),
         AgentEvent::AssistantThought(content) => {
             let preview = if content.len() > 50 {
                 format!("{}...", &content[..50])
@@ -280,7 +424,9 @@ fn transform_to_subagent_event(
 
     Ok(AgentEvent::SubagentStepProgress {
         tool_call_id: tool_call_id.to_string(),
-        step_number,
+        // Agent steps count from 0; the user sees them from 1, as in
+        // `SubagentTaskComplete::total_steps`.
+        step_number: step_number + 1,
         action_type: action_type.to_string(),
         description,
         timestamp: std::time::SystemTime::now(),
@@ -310,3 +310,178 @@ async fn test_task_manager_bridges_subagent_events() {
         "Should receive subagent events"
     );
 }
+
+/// Records the most requests it ever had in flight at once.
+#[derive(Default)]
+struct OverlapTrackingBackend {
+    in_flight: std::sync::atomic::AtomicUsize,
+    max_in_flight: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl LlmBackend for OverlapTrackingBackend {
+    async fn send_message(&self, message: &str) -> Result<String> {
+        Ok(message.to_string())
+    }
+
+    async fn send_message_with_tools(
+        &self,
+        _conversation: &Conversation,
+        _tools: &ToolRegistry,
+    ) -> Result<LlmResponse, crate::backends::LlmError> {
+        use std::sync::atomic::Ordering;
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(LlmResponse::content_only("done".to_string()))
+    }
+
+    fn backend_name(&self) -> &str {
+        "overlap"
+    }
+
+    fn model_name(&self) -> &str {
+        "overlap-model"
+    }
+}
+
+#[tokio::test]
+async fn test_request_limit_of_one_serializes_tasks() {
+    crate::console::init_console(crate::console::VerbosityLevel::Quiet);
+
+    let backend = Arc::new(OverlapTrackingBackend::default());
+    let mut config = crate::config::AppConfig::default();
+    config.backends.insert(
+        "overlap".to_string(),
+        toml::from_str("max_concurrent_requests = 1").unwrap(),
+    );
+    let limiters = Arc::new(crate::backends::RequestLimiters::from_config(&config));
+    let (parent_tx, mut parent_rx) = mpsc::unbounded_channel();
+
+    let task_manager = || {
+        let (event_tx, _) = mpsc::unbounded_channel();
+        let (_, response_rx) = mpsc::unbounded_channel();
+        let permission_manager =
+            Arc::new(PermissionManager::new(event_tx, response_rx).with_skip_permissions(true));
+        TaskManager::new(
+            Arc::clone(&backend) as Arc<dyn LlmBackend>,
+            Arc::new(ToolRegistry::new()),
+            permission_manager,
+        )
+        .with_request_limiters(Arc::clone(&limiters))
+        .with_event_sender(parent_tx.clone())
+    };
+    let task = || {
+        TaskDefinition::new(
+            crate::task_management::AgentType::Explore,
+            "look around".to_string(),
+            "explore".to_string(),
+        )
+    };
+
+    let first_manager = task_manager();
+    let second_manager = task_manager();
+    let (first, second) = tokio::join!(
+        first_manager.execute_task(task()),
+        second_manager.execute_task(task())
+    );
+    assert!(first.unwrap().success);
+    assert!(second.unwrap().success);
+    assert_eq!(
+        backend
+            .max_in_flight
+            .load(std::sync::atomic::Ordering::SeqCst),
+        1
+    );
+
+    let mut queued = false;
+    while let Ok(event) = parent_rx.try_recv() {
+        queued |= matches!(event, crate::agent::AgentEvent::RequestQueued { .. });
+    }
+    assert!(queued, "the waiting task should report that it was queued");
+}
+
+/// Answers every request with a fixed token count, after a short delay so
+/// parallel tasks finish together.
+struct MeteredBackend;
+
+#[async_trait]
+impl LlmBackend for MeteredBackend {
+    async fn send_message(&self, message: &str) -> Result<String> {
+        Ok(message.to_string())
+    }
+
+    async fn send_message_with_tools(
+        &self,
+        _conversation: &Conversation,
+        _tools: &ToolRegistry,
+    ) -> Result<LlmResponse, crate::backends::LlmError> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        Ok(LlmResponse::content_only("done".to_string()).with_tokens(100, 10))
+    }
+
+    fn backend_name(&self) -> &str {
+        "metered"
+    }
+
+    fn model_name(&self) -> &str {
+        "metered-model"
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_parallel_subagents_both_record_usage_on_the_parent() {
+    crate::console::init_console(crate::console::VerbosityLevel::Quiet);
+
+    let dir = tempfile::TempDir::new().unwrap();
+    crate::storage::ConversationStorage::with_root(dir.path())
+        .create_conversation("conv_parent")
+        .unwrap();
+
+    // Each task opens its own storage handle, as sub-agents spawned by
+    // separate task tool calls do.
+    let task_manager = |tool_call_id: &str| {
+        let (event_tx, _) = mpsc::unbounded_channel();
+        let (_, response_rx) = mpsc::unbounded_channel();
+        let permission_manager =
+            Arc::new(PermissionManager::new(event_tx, response_rx).with_skip_permissions(true));
+        TaskManager::new(
+            Arc::new(MeteredBackend) as Arc<dyn LlmBackend>,
+            Arc::new(ToolRegistry::new()),
+            permission_manager,
+        )
+        .with_conversation_storage(Arc::new(crate::storage::ConversationStorage::with_root(
+            dir.path(),
+        )))
+        .with_parent_conversation_id("conv_parent".to_string())
+        .with_tool_call_id(tool_call_id.to_string())
+    };
+    let task = || {
+        TaskDefinition::new(
+            crate::task_management::AgentType::Explore,
+            "look around".to_string(),
+            "explore".to_string(),
+        )
+    };
+
+    let first_manager = task_manager("call_a");
+    let second_manager = task_manager("call_b");
+    let (first, second) = tokio::join!(
+        first_manager.execute_task(task()),
+        second_manager.execute_task(task())
+    );
+    assert!(first.unwrap().success);
+    assert!(second.unwrap().success);
+
+    let metadata = crate::storage::ConversationStorage::with_root(dir.path())
+        .load_metadata("conv_parent")
+        .unwrap();
+    let mut billed: Vec<_> = metadata
+        .usage
+        .iter()
+        .filter_map(|record| record.subagent.as_deref())
+        .collect();
+    billed.sort();
+    assert_eq!(billed, vec!["call_a", "call_b"]);
+}
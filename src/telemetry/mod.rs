@@ -0,0 +1,97 @@
+//! Optional OpenTelemetry export.
+//!
+//! Built with the `otel` feature, hoosh sends traces and metrics for agent
+//! turns, tool calls, backend requests and sub-agent tasks to an OTLP
+//! endpoint. The endpoint, headers and protocol come from the standard
+//! `OTEL_EXPORTER_OTLP_*` environment variables. Without the feature every
+//! function here compiles down to nothing.
+//!
+//! Span names and attributes carry tool, backend and agent names only —
+//! never tool arguments, prompts or responses, which may contain secrets.
+
+#[cfg(feature = "otel")]
+mod otel;
+
+use std::future::Future;
+
+/// Flushes and shuts down the exporters when dropped. Keep it alive for the
+/// lifetime of the program, like the log guard.
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    providers: Option<otel::Providers>,
+}
+
+#[cfg(feature = "otel")]
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(providers) = &self.providers {
+            providers.shutdown();
+        }
+    }
+}
+
+pub fn init() -> TelemetryGuard {
+    TelemetryGuard {
+        #[cfg(feature = "otel")]
+        providers: otel::init(),
+    }
+}
+
+/// Runs one agent turn inside a span and counts it.
+pub async fn agent_turn<T, E>(
+    backend: &str,
+    turn: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    #[cfg(feature = "otel")]
+    return otel::agent_turn(backend, turn).await;
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = backend;
+        turn.await
+    }
+}
+
+/// Runs a tool inside a span named after the tool and records its outcome
+/// and duration.
+pub async fn tool_call<T, E>(
+    tool_name: &str,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    #[cfg(feature = "otel")]
+    return otel::tool_call(tool_name, call).await;
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = tool_name;
+        call.await
+    }
+}
+
+/// Runs a backend request, retries included, inside a span named after the
+/// backend and records its latency.
+pub async fn backend_request<T, E>(
+    backend: &str,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    #[cfg(feature = "otel")]
+    return otel::backend_request(backend, request).await;
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = backend;
+        request.await
+    }
+}
+
+pub fn record_usage(backend: &str, input_tokens: usize, output_tokens: usize, cost: Option<f64>) {
+    #[cfg(feature = "otel")]
+    otel::record_usage(backend, input_tokens, output_tokens, cost);
+    #[cfg(not(feature = "otel"))]
+    let _ = (backend, input_tokens, output_tokens, cost);
+}
+
+/// `outcome` is one of `success`, `failure` or `timeout`.
+pub fn record_task_outcome(agent_type: &str, outcome: &'static str) {
+    #[cfg(feature = "otel")]
+    otel::record_task_outcome(agent_type, outcome);
+    #[cfg(not(feature = "otel"))]
+    let _ = (agent_type, outcome);
+}
@@ -0,0 +1,231 @@
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::trace::{FutureExt, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue, global};
+use opentelemetry_otlp::{MetricExporter, SpanExporter};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+
+const SCOPE: &str = "hoosh";
+
+pub(super) struct Providers {
+    tracer: SdkTracerProvider,
+    meter: SdkMeterProvider,
+}
+
+impl Providers {
+    pub(super) fn shutdown(&self) {
+        if let Err(e) = self.tracer.shutdown() {
+            tracing::warn!("Failed to flush OpenTelemetry traces: {e}");
+        }
+        if let Err(e) = self.meter.shutdown() {
+            tracing::warn!("Failed to flush OpenTelemetry metrics: {e}");
+        }
+    }
+}
+
+/// Installs OTLP exporters as the global providers. A broken exporter setup
+/// only disables export; it never stops hoosh from starting.
+pub(super) fn init() -> Option<Providers> {
+    match build_providers() {
+        Ok(providers) => Some(providers),
+        Err(e) => {
+            tracing::warn!("OpenTelemetry export disabled: {e}");
+            None
+        }
+    }
+}
+
+fn build_providers() -> anyhow::Result<Providers> {
+    let resource = Resource::builder()
+        .with_service_name(SCOPE)
+        .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    let tracer = SdkTracerProvider::builder()
+        .with_batch_exporter(SpanExporter::builder().with_http().build()?)
+        .with_resource(resource.clone())
+        .build();
+    let meter = SdkMeterProvider::builder()
+        .with_periodic_exporter(MetricExporter::builder().with_http().build()?)
+        .with_resource(resource)
+        .build();
+
+    global::set_tracer_provider(tracer.clone());
+    global::set_meter_provider(meter.clone());
+
+    Ok(Providers { tracer, meter })
+}
+
+struct Instruments {
+    turns: Counter<u64>,
+    tokens: Counter<u64>,
+    cost: Counter<f64>,
+    tool_calls: Counter<u64>,
+    tool_duration: Histogram<f64>,
+    backend_duration: Histogram<f64>,
+    tasks: Counter<u64>,
+}
+
+fn instruments() -> &'static Instruments {
+    static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+    INSTRUMENTS.get_or_init(|| {
+        let meter = global::meter(SCOPE);
+        Instruments {
+            turns: meter
+                .u64_counter("hoosh.agent.turns")
+                .with_description("Agent turns handled")
+                .build(),
+            tokens: meter
+                .u64_counter("hoosh.tokens")
+                .with_description("Tokens reported by the backend")
+                .build(),
+            cost: meter
+                .f64_counter("hoosh.cost")
+                .with_description("Estimated cost of backend calls")
+                .with_unit("USD")
+                .build(),
+            tool_calls: meter
+                .u64_counter("hoosh.tool.calls")
+                .with_description("Tool invocations")
+                .build(),
+            tool_duration: meter
+                .f64_histogram("hoosh.tool.duration")
+                .with_description("Tool execution time")
+                .with_unit("s")
+                .build(),
+            backend_duration: meter
+                .f64_histogram("hoosh.backend.duration")
+                .with_description("Backend request latency, retries included")
+                .with_unit("s")
+                .build(),
+            tasks: meter
+                .u64_counter("hoosh.tasks")
+                .with_description("Sub-agent task outcomes")
+                .build(),
+        }
+    })
+}
+
+pub(super) async fn agent_turn<T, E>(
+    backend: &str,
+    turn: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    instruments()
+        .turns
+        .add(1, &[KeyValue::new("backend", backend.to_string())]);
+    traced(
+        "agent.turn".to_string(),
+        vec![KeyValue::new("hoosh.backend", backend.to_string())],
+        turn,
+    )
+    .await
+}
+
+pub(super) async fn tool_call<T, E>(
+    tool_name: &str,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = traced(
+        format!("tool {tool_name}"),
+        vec![KeyValue::new("hoosh.tool", tool_name.to_string())],
+        call,
+    )
+    .await;
+
+    let attributes = [
+        KeyValue::new("tool", tool_name.to_string()),
+        KeyValue::new("outcome", outcome(&result)),
+    ];
+    instruments().tool_calls.add(1, &attributes);
+    instruments()
+        .tool_duration
+        .record(started.elapsed().as_secs_f64(), &attributes);
+    result
+}
+
+pub(super) async fn backend_request<T, E>(
+    backend: &str,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = traced(
+        format!("backend {backend}"),
+        vec![KeyValue::new("hoosh.backend", backend.to_string())],
+        request,
+    )
+    .await;
+
+    instruments().backend_duration.record(
+        started.elapsed().as_secs_f64(),
+        &[
+            KeyValue::new("backend", backend.to_string()),
+            KeyValue::new("outcome", outcome(&result)),
+        ],
+    );
+    result
+}
+
+pub(super) fn record_usage(
+    backend: &str,
+    input_tokens: usize,
+    output_tokens: usize,
+    cost: Option<f64>,
+) {
+    let instruments = instruments();
+    for (direction, count) in [("input", input_tokens), ("output", output_tokens)] {
+        instruments.tokens.add(
+            count as u64,
+            &[
+                KeyValue::new("backend", backend.to_string()),
+                KeyValue::new("direction", direction),
+            ],
+        );
+    }
+    if let Some(cost) = cost {
+        instruments
+            .cost
+            .add(cost, &[KeyValue::new("backend", backend.to_string())]);
+    }
+}
+
+pub(super) fn record_task_outcome(agent_type: &str, outcome: &'static str) {
+    instruments().tasks.add(
+        1,
+        &[
+            KeyValue::new("agent_type", agent_type.to_string()),
+            KeyValue::new("outcome", outcome),
+        ],
+    );
+}
+
+fn outcome<T, E>(result: &Result<T, E>) -> &'static str {
+    if result.is_ok() { "success" } else { "error" }
+}
+
+/// Runs `fut` as the current span. Only the fixed status text is recorded on
+/// failure; error messages can echo arguments and are left to the log file.
+async fn traced<T, E>(
+    name: String,
+    attributes: Vec<KeyValue>,
+    fut: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let tracer = global::tracer(SCOPE);
+    let span = tracer
+        .span_builder(name)
+        .with_attributes(attributes)
+        .start(&tracer);
+    let cx = Context::current_with_span(span);
+
+    let result = fut.with_context(cx.clone()).await;
+    if result.is_err() {
+        cx.span().set_status(Status::error("failed"));
+    }
+    cx.span().end();
+    result
+}
@@ -1,6 +1,13 @@
 use colored::*;
 use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 
+/// Text shown in place of an image, which neither renderer can draw inline.
+pub(crate) fn image_placeholder(alt: &str, url: &str) -> String {
+    let alt = alt.trim();
+    let alt = if alt.is_empty() { "image" } else { alt };
+    format!("🖼 {} ({})", alt, url)
+}
+
 /// Renders markdown to terminal output using ANSI escape codes
 pub struct TerminalMarkdownRenderer {}
 
@@ -24,6 +31,8 @@ impl TerminalMarkdownRenderer {
         let mut in_strong = false;
         let mut in_strikethrough = false;
         let mut current_line = String::new();
+        // (url, alt text) while inside an image; alt text arrives as Text events
+        let mut current_image: Option<(String, String)> = None;
 
         for event in parser {
             match event {
@@ -108,6 +117,9 @@ impl TerminalMarkdownRenderer {
                         }
                         current_line.push_str(&format!("{} ", "│".bright_black()));
                     }
+                    Tag::Image { dest_url, .. } => {
+                        current_image = Some((dest_url.to_string(), String::new()));
+                    }
                     Tag::Table(_) if !current_line.is_empty() => {
                         output.push_str(&current_line);
                         output.push('\n');
@@ -165,6 +177,12 @@ impl TerminalMarkdownRenderer {
                             current_line.clear();
                         }
                     }
+                    TagEnd::Image => {
+                        if let Some((url, alt)) = current_image.take() {
+                            current_line
+                                .push_str(&image_placeholder(&alt, &url).blue().to_string());
+                        }
+                    }
                     TagEnd::BlockQuote if !current_line.is_empty() => {
                         output.push_str(&current_line);
                         output.push('\n');
@@ -175,6 +193,8 @@ impl TerminalMarkdownRenderer {
                 Event::Text(text) => {
                     if in_code_block {
                         code_buffer.push_str(&text);
+                    } else if let Some((_, ref mut alt)) = current_image {
+                        alt.push_str(&text);
                     } else {
                         let styled =
                             self.style_text(&text, in_emphasis, in_strong, in_strikethrough);
@@ -320,4 +340,20 @@ mod tests {
         let output = renderer.render("- Item 1\n- Item 2");
         assert!(output.contains("•"));
     }
+
+    #[test]
+    fn test_render_image_placeholder() {
+        let renderer = TerminalMarkdownRenderer::new();
+        let output = renderer.render("See ![build graph](https://example.com/graph.png) here");
+        assert!(output.contains("🖼 build graph (https://example.com/graph.png)"));
+        assert!(output.contains("here"));
+    }
+
+    #[test]
+    fn test_image_placeholder_without_alt_text() {
+        assert_eq!(
+            image_placeholder("  ", "diagram.svg"),
+            "🖼 image (diagram.svg)"
+        );
+    }
 }
@@ -0,0 +1,102 @@
+//! Removing terminal escape sequences from captured command output.
+//!
+//! Colors, cursor movement and window-title updates mean nothing to the model
+//! and cost tokens, so the bash tool strips them before returning a result.
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+/// Single-character (8-bit) form of `ESC [`.
+const C1_CSI: char = '\u{9b}';
+
+/// Remove ANSI escape sequences: CSI (SGR colors, cursor movement, erase),
+/// OSC (titles, hyperlinks), DCS/PM/APC strings, and the short two- and
+/// three-character escapes such as charset selection. Text in hyperlinks
+/// is kept; only the escape around it goes.
+pub fn strip_ansi_escapes(text: &str) -> String {
+    if !text.contains([ESC, C1_CSI]) {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            C1_CSI => skip_csi(&mut chars),
+            ESC => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                // OSC, DCS, SOS, PM and APC run until BEL or `ESC \`.
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_string(&mut chars),
+                // Charset designation takes one more character: `ESC ( B`.
+                Some('(' | ')' | '*' | '+' | '-' | '.' | '/' | '#' | '%') => {
+                    chars.next();
+                }
+                // Everything else is a two-character escape (`ESC 7`, `ESC M`).
+                Some(_) | None => {}
+            },
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Skip CSI parameters and intermediates through the final byte
+/// (`@` to `~`).
+fn skip_csi(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    for c in chars.by_ref() {
+        if ('@'..='~').contains(&c) {
+            break;
+        }
+    }
+}
+
+/// Skip a control string through its terminator, BEL or `ESC \`.
+fn skip_string(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while let Some(c) = chars.next() {
+        if c == BEL {
+            break;
+        }
+        if c == ESC {
+            if chars.peek() == Some(&'\\') {
+                chars.next();
+            }
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_sgr_colors() {
+        assert_eq!(
+            strip_ansi_escapes("\x1b[1;31merror\x1b[0m: \x1b[38;5;208mbad\x1b[m"),
+            "error: bad"
+        );
+    }
+
+    #[test]
+    fn strips_cursor_movement_and_erase() {
+        assert_eq!(
+            strip_ansi_escapes("\x1b[2K\x1b[1G 50%\x1b[?25l\x1b[3A\x1b7done\x1b8"),
+            " 50%done"
+        );
+    }
+
+    #[test]
+    fn strips_osc_titles_and_hyperlinks() {
+        let text = "\x1b]0;cargo build\x07\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+        assert_eq!(strip_ansi_escapes(text), "link");
+    }
+
+    #[test]
+    fn strips_charset_selection_and_c1_csi() {
+        assert_eq!(strip_ansi_escapes("\x1b(Bplain\u{9b}0m text"), "plain text");
+    }
+
+    #[test]
+    fn leaves_plain_text_alone() {
+        assert_eq!(strip_ansi_escapes("a [b] ]c\n"), "a [b] ]c\n");
+    }
+}
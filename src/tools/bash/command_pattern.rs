@@ -543,7 +543,7 @@ impl BashCommandPattern for NetworkReadPattern {
 pub struct SingleCommandPattern;
 
 impl SingleCommandPattern {
-    fn is_whitelisted(cmd: &str, full_command: &str) -> bool {
+    pub(super) fn is_whitelisted(cmd: &str, full_command: &str) -> bool {
         match cmd {
             // Always safe (information only)
             "ls" | "pwd" | "whoami" | "date" | "echo" | "which" | "type" | "hostname" => {
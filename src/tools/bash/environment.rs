@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Variables kept from hoosh's own environment when `clear_env` is set, so
+/// that ordinary tools can still be found and behave sensibly.
+pub(super) const HERMETIC_BASE_VARS: &[&str] = &[
+    "PATH", "HOME", "USER", "LOGNAME", "SHELL", "TERM", "LANG", "LC_ALL", "TMPDIR",
+];
+
+/// The `[bash]` table: which shell runs commands and how their environment
+/// is built.
+///
+/// Precedence, lowest to highest: the inherited environment (or only the
+/// minimal base when `clear_env` is set), then `env`. Values may refer to
+/// the inherited environment as `$NAME` or `${NAME}`, which is how to extend
+/// rather than replace a variable such as `PATH`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct BashConfig {
+    /// Extra variables set for every command, overriding inherited ones.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Start from an empty environment plus a minimal base instead of
+    /// inheriting everything hoosh was started with.
+    #[serde(default)]
+    pub clear_env: bool,
+    /// Program and leading arguments the command string is appended to,
+    /// e.g. `["zsh", "-c"]`. Defaults to [`default_shell`].
+    #[serde(default)]
+    pub shell: Option<Vec<String>>,
+    /// Remove ANSI escape sequences (colors, cursor movement, titles) from
+    /// the output returned to the model. Defaults to on; the live output in
+    /// the TUI keeps them either way.
+    #[serde(default)]
+    pub strip_ansi: Option<bool>,
+}
+
+/// `bash -c` (the tool's namesake) on Unix, `cmd /C` on Windows.
+pub fn default_shell() -> Vec<String> {
+    let shell: &[&str] = if cfg!(windows) {
+        &["cmd", "/C"]
+    } else {
+        &["bash", "-c"]
+    };
+    shell.iter().map(|s| s.to_string()).collect()
+}
+
+impl BashConfig {
+    pub fn shell(&self) -> Vec<String> {
+        self.shell.clone().unwrap_or_else(default_shell)
+    }
+
+    pub fn strip_ansi(&self) -> bool {
+        self.strip_ansi.unwrap_or(true)
+    }
+
+    /// A command that runs `script` in the configured shell with the
+    /// configured environment.
+    pub fn command(&self, script: &str) -> Command {
+        let shell = self.shell();
+        let mut cmd = Command::new(&shell[0]);
+        cmd.args(&shell[1..]).arg(script);
+        self.apply(&mut cmd);
+        cmd
+    }
+
+    /// Checked at startup so a typo in `shell` fails loudly rather than on
+    /// the first command the agent runs.
+    pub fn validate_shell(&self) -> Result<(), String> {
+        let Some(shell) = &self.shell else {
+            return Ok(());
+        };
+        let Some(program) = shell.first().filter(|p| !p.is_empty()) else {
+            return Err("shell must name a program".to_string());
+        };
+        if find_program(program) {
+            Ok(())
+        } else {
+            Err(format!("{program} not found"))
+        }
+    }
+
+    pub fn apply(&self, cmd: &mut Command) {
+        if self.clear_env {
+            cmd.env_clear();
+            for name in HERMETIC_BASE_VARS {
+                if let Some(value) = std::env::var_os(name) {
+                    cmd.env(name, value);
+                }
+            }
+        }
+
+        for (name, value) in &self.env {
+            cmd.env(name, expand_vars(value, |var| std::env::var(var).ok()));
+        }
+    }
+}
+
+/// Expands `$NAME` and `${NAME}` using `lookup`; unknown variables expand to
+/// nothing, like in the shell. A `$` not followed by a name is kept as is.
+fn expand_vars(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) if is_var_name(&braced[..end]) => (&braced[..end], end + 2),
+                _ => ("", 0),
+            }
+        } else {
+            // Names are ASCII, so the character count is also the byte length.
+            let len = after
+                .char_indices()
+                .take_while(|&(i, c)| {
+                    c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+                })
+                .count();
+            (&after[..len], len)
+        };
+
+        if name.is_empty() {
+            out.push('$');
+            rest = after;
+        } else {
+            out.push_str(&lookup(name).unwrap_or_default());
+            rest = &after[consumed..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// A program given as a path must exist there; a bare name is looked up on
+/// `PATH` the way the OS would when spawning it.
+fn find_program(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    let Some(search) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&search).any(|dir| {
+        let candidate = dir.join(program);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "PATH" => Some("/usr/bin:/bin".to_string()),
+            "HOME" => Some("/home/me".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_plain_and_braced_references() {
+        assert_eq!(
+            expand_vars("/opt/tools/bin:$PATH", lookup),
+            "/opt/tools/bin:/usr/bin:/bin"
+        );
+        assert_eq!(expand_vars("${HOME}/.cache", lookup), "/home/me/.cache");
+    }
+
+    #[test]
+    fn unknown_variables_expand_to_nothing() {
+        assert_eq!(expand_vars("a${MISSING}b", lookup), "ab");
+        assert_eq!(expand_vars("$MISSING/x", lookup), "/x");
+    }
+
+    #[test]
+    fn lone_dollar_is_kept() {
+        assert_eq!(expand_vars("cost: $5 or $", lookup), "cost: $5 or $");
+        assert_eq!(expand_vars("${not closed", lookup), "${not closed");
+    }
+
+    #[test]
+    fn validates_the_shell_program() {
+        assert!(BashConfig::default().validate_shell().is_ok());
+
+        let shell = |args: &[&str]| BashConfig {
+            shell: Some(args.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        };
+        assert!(shell(&["sh", "-c"]).validate_shell().is_ok());
+        assert!(shell(&["/bin/sh", "-c"]).validate_shell().is_ok());
+        assert_eq!(
+            shell(&["/no/such/shell", "-c"]).validate_shell(),
+            Err("/no/such/shell not found".to_string())
+        );
+        assert!(shell(&[]).validate_shell().is_err());
+    }
+
+    #[test]
+    fn parses_from_toml() {
+        let config: BashConfig = toml::from_str(
+            "clear_env = true\nshell = [\"zsh\", \"-c\"]\n[env]\nRUST_LOG = \"debug\"",
+        )
+        .unwrap();
+
+        assert!(config.clear_env);
+        assert_eq!(config.shell(), vec!["zsh", "-c"]);
+        assert_eq!(
+            config.env.get("RUST_LOG").map(String::as_str),
+            Some("debug")
+        );
+    }
+}
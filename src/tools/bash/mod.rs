@@ -1,9 +1,17 @@
+mod ansi;
+mod blocklist;
 mod command_pattern;
+mod environment;
 mod parser;
 mod pattern_registry;
+mod subcommand_rule;
 mod tool;
 
+pub use ansi::strip_ansi_escapes;
+pub use blocklist::{CommandBlocklist, CommandBlocklistConfig, danger_reason};
 pub use command_pattern::{BashCommandPattern, CommandPatternResult};
+pub use environment::BashConfig;
 pub use parser::BashCommandParser;
 pub use pattern_registry::BashCommandPatternRegistry;
+pub use subcommand_rule::SubcommandRule;
 pub use tool::BashTool;
@@ -2,6 +2,10 @@ use indexmap::IndexSet;
 
 pub struct BashCommandParser;
 
+/// Subcommands listed by [`BashCommandParser::explain`] before the rest are
+/// collapsed into a "more" line.
+const MAX_EXPLAINED_SEGMENTS: usize = 6;
+
 impl BashCommandParser {
     /// Extract base commands using proper tokenization.
     /// Handles:
@@ -138,6 +142,64 @@ impl BashCommandParser {
         segments
     }
 
+    /// One human-readable line per top-level subcommand: the base command,
+    /// its flags and how many other arguments it takes, e.g.
+    /// `git · flags: --force · 3 args`. Shown in the approval dialog so an
+    /// unfamiliar command can be checked at a glance.
+    pub fn explain(input: &str) -> Vec<String> {
+        let segments = Self::split_subcommands(input);
+        let mut lines: Vec<String> = segments
+            .iter()
+            .take(MAX_EXPLAINED_SEGMENTS)
+            .map(|segment| Self::explain_segment(segment))
+            .collect();
+
+        if segments.len() > MAX_EXPLAINED_SEGMENTS {
+            lines.push(format!(
+                "… {} more subcommand(s)",
+                segments.len() - MAX_EXPLAINED_SEGMENTS
+            ));
+        }
+        lines
+    }
+
+    fn explain_segment(segment: &str) -> String {
+        let Some(tokens) = shlex::split(segment) else {
+            return format!("{} (could not parse)", segment);
+        };
+
+        let mut tokens = tokens
+            .into_iter()
+            .skip_while(|t| t.contains('=') && !t.starts_with('-'));
+        let Some(base) = tokens.next() else {
+            return format!("{} (could not parse)", segment);
+        };
+
+        let mut flags: IndexSet<String> = IndexSet::new();
+        let mut arg_count = 0;
+        for token in tokens {
+            if token.starts_with('-') && token.len() > 1 {
+                flags.insert(token);
+            } else {
+                arg_count += 1;
+            }
+        }
+
+        let mut parts = vec![base];
+        if !flags.is_empty() {
+            parts.push(format!(
+                "flags: {}",
+                flags.into_iter().collect::<Vec<_>>().join(" ")
+            ));
+        }
+        match arg_count {
+            0 => {}
+            1 => parts.push("1 arg".to_string()),
+            n => parts.push(format!("{} args", n)),
+        }
+        parts.join(" · ")
+    }
+
     fn push_segment(segments: &mut Vec<String>, current: &mut String) {
         let trimmed = current.trim();
         if !trimmed.is_empty() {
@@ -245,6 +307,16 @@ impl BashCommandParser {
         targets
     }
 
+    /// The command of a single subcommand segment and all of its arguments,
+    /// skipping `VAR=val` env-var prefixes.
+    pub fn extract_command_and_args(segment: &str) -> Option<(String, Vec<String>)> {
+        let mut tokens = shlex::split(segment)?
+            .into_iter()
+            .skip_while(|t| t.contains('=') && !t.starts_with('-'));
+        let command = tokens.next()?;
+        Some((command, tokens.collect()))
+    }
+
     pub fn extract_first_command_and_arg(input: &str) -> Option<(String, Option<String>)> {
         let input_to_parse = if Self::contains_heredoc(input) {
             input.lines().next().unwrap_or("").to_string()
@@ -438,4 +510,27 @@ mod tests {
             vec!["cat <<EOF"]
         );
     }
+
+    #[test]
+    fn explain_lists_base_command_flags_and_args() {
+        assert_eq!(
+            BashCommandParser::explain("RUST_LOG=debug git push --force -u origin main"),
+            vec!["git · flags: --force -u · 3 args"]
+        );
+    }
+
+    #[test]
+    fn explain_covers_each_subcommand() {
+        assert_eq!(
+            BashCommandParser::explain("cargo build && ls | grep -i 'a | b'"),
+            vec!["cargo · 1 arg", "ls", "grep · flags: -i · 1 arg"]
+        );
+    }
+
+    #[test]
+    fn explain_collapses_long_chains() {
+        let lines = BashCommandParser::explain("a; b; c; d; e; f; g; h");
+        assert_eq!(lines.len(), MAX_EXPLAINED_SEGMENTS + 1);
+        assert_eq!(lines.last().unwrap(), "… 2 more subcommand(s)");
+    }
 }
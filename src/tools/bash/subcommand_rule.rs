@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+
+use super::BashCommandParser;
+use super::command_pattern::SingleCommandPattern;
+
+/// A bash permission rule keyed on a base command and the subcommands it may
+/// run, evaluated against the parsed command rather than its raw text.
+///
+/// Written as `<base>:<allowed>[!<denied>]`, where `<allowed>` is `*` or a
+/// `{a,b}` set and `<denied>` is a `{c,d}` set:
+///
+/// - `cargo:{build,test}` — only `cargo build` and `cargo test`
+/// - `cargo:*!{publish,yank}` — any `cargo` subcommand except those two
+///
+/// Patterns without a `{…}` set are not subcommand rules, so existing `*`,
+/// `cmd:*` and `cmd arg:*` rules keep their meaning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubcommandRule {
+    base: String,
+    /// `None` allows every subcommand not in `denied`.
+    allowed: Option<BTreeSet<String>>,
+    denied: BTreeSet<String>,
+}
+
+impl SubcommandRule {
+    pub fn parse(pattern: &str) -> Option<Self> {
+        if !pattern.contains('{') {
+            return None;
+        }
+        let (base, spec) = pattern.split_once(':')?;
+        let base = base.trim();
+        if base.is_empty() || base.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let (allowed, denied) = match spec.split_once('!') {
+            Some((allowed, denied)) => (allowed, Some(denied)),
+            None => (spec, None),
+        };
+        let allowed = match allowed.trim() {
+            "*" => None,
+            set => Some(Self::parse_set(set)?),
+        };
+        let denied = match denied {
+            Some(set) => Self::parse_set(set.trim())?,
+            None => BTreeSet::new(),
+        };
+
+        Some(Self {
+            base: base.to_string(),
+            allowed,
+            denied,
+        })
+    }
+
+    fn parse_set(set: &str) -> Option<BTreeSet<String>> {
+        let items: BTreeSet<String> = set
+            .strip_prefix('{')?
+            .strip_suffix('}')?
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        (!items.is_empty()).then_some(items)
+    }
+
+    /// Whether every command `command` runs is a permitted invocation of the
+    /// base command. Read-only helpers and `cd` around it (`cd web && cargo
+    /// test | grep FAIL`) are ignored, as compound rules ignore them.
+    /// Subshells, heredocs and redirections never match: what they run or
+    /// write can't be read off the parsed structure.
+    pub fn matches(&self, command: &str) -> bool {
+        if BashCommandParser::contains_subshell(command)
+            || BashCommandParser::contains_heredoc(command)
+            || command.contains('>')
+            || command.contains('<')
+        {
+            return false;
+        }
+
+        let mut invoked = false;
+        for segment in BashCommandParser::split_subcommands(command) {
+            let Some((cmd, args)) = BashCommandParser::extract_command_and_args(&segment) else {
+                return false;
+            };
+            if cmd == self.base {
+                if !self.permits(&args) {
+                    return false;
+                }
+                invoked = true;
+            } else if cmd != "cd" && !SingleCommandPattern::is_whitelisted(&cmd, &segment) {
+                return false;
+            }
+        }
+        invoked
+    }
+
+    /// The subcommand is the first argument that isn't a flag. A denied
+    /// subcommand anywhere among the positional arguments rejects the call,
+    /// so flags with values (`git -C repo push`) can't hide it.
+    fn permits(&self, args: &[String]) -> bool {
+        let mut positional = args.iter().filter(|a| !a.starts_with(['-', '+']));
+        if positional.clone().any(|a| self.denied.contains(a)) {
+            return false;
+        }
+        match &self.allowed {
+            None => true,
+            Some(allowed) => positional.next().is_some_and(|sub| allowed.contains(sub)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_allowed_and_denied_sets() {
+        let rule = SubcommandRule::parse("cargo:{build, test}!{publish}").unwrap();
+        assert_eq!(rule.base, "cargo");
+        assert_eq!(
+            rule.allowed,
+            Some(["build", "test"].map(String::from).into_iter().collect())
+        );
+        assert_eq!(rule.denied, ["publish"].map(String::from).into());
+
+        let rule = SubcommandRule::parse("cargo:*!{publish}").unwrap();
+        assert_eq!(rule.allowed, None);
+    }
+
+    #[test]
+    fn plain_patterns_are_not_subcommand_rules() {
+        assert_eq!(SubcommandRule::parse("*"), None);
+        assert_eq!(SubcommandRule::parse("cargo:*"), None);
+        assert_eq!(SubcommandRule::parse("cargo build:*"), None);
+        assert_eq!(SubcommandRule::parse("cargo:{}"), None);
+        assert_eq!(SubcommandRule::parse("cargo:*!publish"), None);
+    }
+
+    #[test]
+    fn allowed_set_limits_subcommands() {
+        let rule = SubcommandRule::parse("git:{status,log,diff}").unwrap();
+        assert!(rule.matches("git status"));
+        assert!(rule.matches("git log --oneline -5"));
+        assert!(!rule.matches("git push"));
+        assert!(!rule.matches("git"));
+        assert!(!rule.matches("gitk status"));
+    }
+
+    #[test]
+    fn deny_set_overrides_wildcard() {
+        let rule = SubcommandRule::parse("cargo:*!{publish}").unwrap();
+        assert!(rule.matches("cargo build --release"));
+        assert!(rule.matches("RUST_LOG=debug cargo test"));
+        assert!(!rule.matches("cargo publish"));
+        assert!(!rule.matches("cargo --locked publish"));
+        assert!(!rule.matches("npm publish"));
+    }
+
+    #[test]
+    fn every_invocation_in_a_compound_must_be_permitted() {
+        let rule = SubcommandRule::parse("cargo:*!{publish}").unwrap();
+        assert!(rule.matches("cargo fmt && cargo clippy"));
+        assert!(rule.matches("cd crate && cargo test | grep FAIL"));
+        assert!(!rule.matches("cargo build && cargo publish"));
+        assert!(!rule.matches("cargo build && rm -rf target"));
+        assert!(!rule.matches("cargo build > out.txt"));
+        assert!(!rule.matches("cargo build $(echo publish)"));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::agent::AgentEvent;
 use crate::permissions::BashPatternMatcher;
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
-use crate::tools::bash::BashCommandPatternRegistry;
+use crate::tools::bash::{
+    BashCommandParser, BashCommandPatternRegistry, BashConfig, CommandBlocklist, danger_reason,
+    strip_ansi_escapes,
+};
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -11,13 +14,14 @@ use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
 use tokio::time::timeout;
 
 /// Tool for executing bash commands safely
 pub struct BashTool {
     working_directory: PathBuf,
     timeout_seconds: u64,
+    blocklist: Arc<CommandBlocklist>,
+    config: Arc<BashConfig>,
 }
 
 impl BashTool {
@@ -25,6 +29,8 @@ impl BashTool {
         Self {
             working_directory: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
             timeout_seconds: 240, // Default 30 second timeout
+            blocklist: Arc::new(CommandBlocklist::default()),
+            config: Arc::new(BashConfig::default()),
         }
     }
 
@@ -38,6 +44,26 @@ impl BashTool {
         self
     }
 
+    pub fn with_blocklist(mut self, blocklist: Arc<CommandBlocklist>) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    pub fn with_config(mut self, config: Arc<BashConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The result handed back to the model, without escape sequences unless
+    /// the config keeps them. Streamed lines are sent to the TUI untouched.
+    fn model_output(&self, result: String) -> String {
+        if self.config.strip_ansi() {
+            strip_ansi_escapes(&result)
+        } else {
+            result
+        }
+    }
+
     /// Sanitize command to prevent some basic injection attempts
     /// Note: This is NOT sufficient for security - dangerous commands should be blocked entirely
     fn sanitize_command(&self, command: &str) -> String {
@@ -62,6 +88,17 @@ impl BashTool {
 
         let command = self.sanitize_command(&args.command);
 
+        // Checked here rather than in the permission layer so that no grant,
+        // autopilot or --skip-permissions can let these through.
+        if let Some(reason) = self.blocklist.check(&command) {
+            return Err(ToolError::InvalidCommand {
+                message: format!(
+                    "refused to run '{}': {}. This command is on the bash blocklist",
+                    command, reason
+                ),
+            });
+        }
+
         let timeout_duration =
             Duration::from_secs(args.timeout_override.unwrap_or(self.timeout_seconds));
 
@@ -83,10 +120,8 @@ impl BashTool {
         timeout_duration: Duration,
     ) -> ToolResult<String> {
         // Execute the command
-        let mut cmd = Command::new("bash");
-        cmd.arg("-c")
-            .arg(&command)
-            .current_dir(&self.working_directory)
+        let mut cmd = self.config.command(&command);
+        cmd.current_dir(&self.working_directory)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .stdin(Stdio::null());
@@ -131,7 +166,7 @@ impl BashTool {
                 result.push_str("Command failed with non-zero exit code\n");
             }
 
-            Ok::<String, ToolError>(result)
+            Ok::<String, ToolError>(self.model_output(result))
         };
 
         // Apply timeout
@@ -150,10 +185,8 @@ impl BashTool {
         timeout_duration: Duration,
         context: ToolExecutionContext,
     ) -> ToolResult<String> {
-        let mut cmd = Command::new("bash");
-        cmd.arg("-c")
-            .arg(&command)
-            .current_dir(&self.working_directory)
+        let mut cmd = self.config.command(&command);
+        cmd.current_dir(&self.working_directory)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .stdin(Stdio::null());
@@ -283,7 +316,7 @@ impl BashTool {
                 result.push_str("Command failed with non-zero exit code\n");
             }
 
-            Ok::<String, ToolError>(result)
+            Ok::<String, ToolError>(self.model_output(result))
         };
 
         // Apply timeout
@@ -471,12 +504,14 @@ impl BashTool {
             .with_command_preview(target_str.to_string())
             .with_persistent_approval(pattern_result.persistent_message)
             .with_suggested_pattern(pattern_result.pattern)
+            .with_command_breakdown(BashCommandParser::explain(target_str))
             .with_pattern_matcher(Arc::new(
                 BashPatternMatcher::new().with_working_dir(self.working_directory.clone()),
             ));
 
         // Mark as read-only if safe (for ToolExecutor to auto-approve)
-        if pattern_result.safe {
+        let danger = danger_reason(target_str);
+        if pattern_result.safe && danger.is_none() {
             builder = builder.into_read_only();
         }
 
@@ -484,6 +519,12 @@ impl BashTool {
             builder = builder.disallow_project_wide_trust();
         }
 
+        if let Some(reason) = danger {
+            builder = builder.with_danger(reason).with_approval_prompt(format!(
+                "This command looks destructive ({reason}). Run it?"
+            ));
+        }
+
         builder
             .build()
             .expect("Failed to build BashTool permission descriptor")
@@ -643,6 +684,22 @@ mod tests {
         assert!(!desc.is_read_only());
     }
 
+    #[test]
+    fn descriptor_summary_explains_command_and_rule() {
+        let tool = BashTool::new();
+        let desc = tool.describe_permission(Some("git push --force origin main"));
+        assert_eq!(
+            desc.summary(),
+            vec![
+                "• git · flags: --force · 3 args".to_string(),
+                format!(
+                    "Trusting saves the rule: {}",
+                    desc.suggested_pattern().unwrap()
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn blank_description_falls_back_to_pattern_summary() {
         let tool = BashTool::new();
@@ -688,6 +745,119 @@ mod tests {
         assert!(result.contains("Command failed"));
     }
 
+    #[tokio::test]
+    async fn test_bash_tool_refuses_blocklisted_command() {
+        let tool = BashTool::new();
+        let args = json!({ "command": ":(){ :|:& };:" });
+
+        let context = ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+
+        let result = tool.execute(&args, &context).await;
+        assert!(matches!(result, Err(ToolError::InvalidCommand { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_bash_tool_sets_configured_env() {
+        let config = BashConfig {
+            env: [("HOOSH_TEST_VAR".to_string(), "configured".to_string())].into(),
+            ..Default::default()
+        };
+        let tool = BashTool::new().with_config(Arc::new(config));
+        let args = json!({ "command": "echo \"value=$HOOSH_TEST_VAR\"" });
+
+        let result = tool.execute_impl(&args, None).await.unwrap();
+        assert!(result.contains("value=configured"));
+    }
+
+    #[tokio::test]
+    async fn test_bash_tool_clear_env_keeps_only_base_and_configured() {
+        use crate::tools::bash::environment::HERMETIC_BASE_VARS;
+
+        let config = BashConfig {
+            env: [("HOOSH_TEST_VAR".to_string(), "configured".to_string())].into(),
+            clear_env: true,
+            ..Default::default()
+        };
+        let tool = BashTool::new().with_config(Arc::new(config));
+        let args = json!({ "command": "compgen -e" });
+
+        let result = tool.execute_impl(&args, None).await.unwrap();
+        let names: Vec<&str> = result
+            .lines()
+            .skip_while(|line| *line != "STDOUT:")
+            .skip(1)
+            .take_while(|line| !line.starts_with("Exit code:"))
+            .collect();
+
+        assert!(names.contains(&"HOOSH_TEST_VAR"));
+        // bash exports a few variables of its own on startup.
+        let allowed = ["HOOSH_TEST_VAR", "PWD", "OLDPWD", "SHLVL", "_"];
+        for name in names {
+            assert!(
+                allowed.contains(&name) || HERMETIC_BASE_VARS.contains(&name),
+                "unexpected inherited variable {name}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bash_tool_runs_the_configured_shell() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let shell = dir.path().join("fake-shell");
+        std::fs::write(&shell, "#!/bin/sh\necho \"fake-shell got: $*\"\n").unwrap();
+        std::fs::set_permissions(&shell, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = BashConfig {
+            shell: Some(vec![
+                shell.to_string_lossy().into_owned(),
+                "--flag".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let tool = BashTool::new().with_config(Arc::new(config));
+        let args = json!({ "command": "echo hi" });
+
+        let result = tool.execute_impl(&args, None).await.unwrap();
+        assert!(
+            result.contains("fake-shell got: --flag echo hi"),
+            "{result}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bash_tool_custom_blocklist() {
+        use crate::tools::bash::CommandBlocklistConfig;
+
+        let config = CommandBlocklistConfig {
+            patterns: vec![r"^echo\b".to_string()],
+            ..Default::default()
+        };
+        let tool = BashTool::new().with_blocklist(Arc::new(CommandBlocklist::from_config(&config)));
+
+        let context = ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+
+        let blocked = tool
+            .execute(&json!({ "command": "echo hi" }), &context)
+            .await;
+        assert!(blocked.is_err());
+
+        let allowed = tool
+            .execute(&json!({ "command": "printf hi" }), &context)
+            .await;
+        assert!(allowed.unwrap().contains("hi"));
+    }
+
     #[tokio::test]
     async fn test_bash_tool_timeout() {
         let tool = BashTool::new().with_timeout(1); // 1 second timeout
@@ -706,6 +876,49 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Timeout"));
     }
 
+    #[tokio::test]
+    async fn test_bash_tool_strips_ansi_from_colored_output() {
+        let args = json!({
+            "command": r"printf '\033[1;32mok\033[0m \033]0;title\007done\033[2K\n'; printf '\033[31mfail\033[m\n' >&2"
+        });
+
+        let result = BashTool::new().execute_impl(&args, None).await.unwrap();
+        assert!(!result.contains('\x1b'), "{result:?}");
+        assert!(result.contains("STDOUT:\nok done\n"), "{result:?}");
+        assert!(result.contains("STDERR:\nfail\n"), "{result:?}");
+
+        let config = BashConfig {
+            strip_ansi: Some(false),
+            ..Default::default()
+        };
+        let tool = BashTool::new().with_config(Arc::new(config));
+        let result = tool.execute_impl(&args, None).await.unwrap();
+        assert!(result.contains("\x1b[1;32mok"), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_bash_tool_streams_colors_but_returns_plain_text() {
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let context = ToolExecutionContext {
+            tool_call_id: "colored".to_string(),
+            event_tx: Some(event_tx),
+            parent_conversation_id: None,
+        };
+        let args = json!({ "command": r"printf '\033[33mwarn\033[0m\n'" });
+
+        let result = BashTool::new().execute(&args, &context).await.unwrap();
+        assert!(result.contains("STDOUT:\nwarn\n"), "{result:?}");
+        drop(context);
+
+        let mut streamed = Vec::new();
+        while let Some(event) = event_rx.recv().await {
+            if let AgentEvent::BashOutputChunk { output_line, .. } = event {
+                streamed.push(output_line);
+            }
+        }
+        assert_eq!(streamed, vec!["\x1b[33mwarn\x1b[0m".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_bash_tool_streaming_with_context() {
         use tokio::sync::mpsc;
@@ -78,6 +78,22 @@ impl ToolError {
         matches!(self, ToolError::PermissionDenied { .. })
     }
 
+    /// Whether the failure may clear up on its own, such as a file another
+    /// process holds locked. Bad arguments, denials and rejections never are.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            ToolError::ReadFailed { .. } | ToolError::WriteFailed { .. } => true,
+            ToolError::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ResourceBusy
+            ),
+            _ => false,
+        }
+    }
+
     pub fn llm_message(&self) -> String {
         match self {
             ToolError::PermissionDenied { tool } => {
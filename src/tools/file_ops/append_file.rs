@@ -0,0 +1,270 @@
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::file_ops::diff_preview::file_diff;
+use crate::tools::file_ops::edit_summary::EditSummary;
+use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+pub struct AppendFileTool {
+    working_directory: PathBuf,
+}
+
+impl AppendFileTool {
+    pub fn new() -> Self {
+        let working_directory = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self { working_directory }
+    }
+
+    pub fn with_working_directory(working_dir: PathBuf) -> Self {
+        Self {
+            working_directory: working_dir,
+        }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        let p = Path::new(path);
+        if p.is_absolute() {
+            p.to_path_buf()
+        } else {
+            self.working_directory.join(p)
+        }
+    }
+
+    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+        let args: AppendFileArgs =
+            serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
+                tool: "append_file".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let file_path = self.resolve(&args.path);
+
+        if args.create_dirs
+            && let Some(parent) = file_path.parent()
+        {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|_| ToolError::WriteFailed {
+                    path: file_path.clone(),
+                })?;
+        }
+
+        let existed = fs::metadata(&file_path).await.is_ok();
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file_path)
+            .await
+            .map_err(|_| ToolError::WriteFailed {
+                path: file_path.clone(),
+            })?;
+
+        file.write_all(args.content.as_bytes())
+            .await
+            .map_err(|_| ToolError::WriteFailed {
+                path: file_path.clone(),
+            })?;
+        file.flush().await.map_err(|_| ToolError::WriteFailed {
+            path: file_path.clone(),
+        })?;
+
+        let new_len = file
+            .metadata()
+            .await
+            .map_err(|_| ToolError::WriteFailed {
+                path: file_path.clone(),
+            })?
+            .len();
+
+        // Only the appended text is diffed, so the existing contents never
+        // have to be read back.
+        let summary = EditSummary::from_contents(&args.path, existed.then_some(""), &args.content);
+        Ok(summary.into_result(format!(
+            "Successfully appended {} bytes to {} (now {} bytes)",
+            args.content.len(),
+            file_path.display(),
+            new_len
+        )))
+    }
+}
+
+#[derive(Deserialize)]
+struct AppendFileArgs {
+    path: String,
+    content: String,
+    #[serde(default)]
+    create_dirs: bool,
+}
+
+#[async_trait]
+impl Tool for AppendFileTool {
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args).await
+    }
+
+    fn name(&self) -> &'static str {
+        "append_file"
+    }
+
+    fn phrasing(&self) -> CategoryPhrasing {
+        phrasing::EDIT
+    }
+
+    fn display_name(&self) -> &'static str {
+        "append"
+    }
+
+    fn description(&self) -> &'static str {
+        "Append content to the end of a file, creating it if it doesn't exist.\n\n\
+        Usage:\n\
+        - Existing content is never modified; no need to read the file first\n\
+        - Content is appended verbatim - include a leading or trailing newline yourself if needed\n\
+        - Use create_dirs=true to automatically create parent directories\n\n\
+        When to use:\n\
+        - Adding entries to logs, notes or changelogs\n\
+        - Accumulating output across several steps\n\n\
+        When NOT to use:\n\
+        - Inserting or changing text in the middle of a file - use edit_file instead\n\
+        - Replacing a file's contents - use write_file instead"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The path to the file to append to. Examples: \"notes.md\", \"logs/run.log\""
+                },
+                "content": {
+                    "type": "string",
+                    "description": "The text to add to the end of the file."
+                },
+                "create_dirs": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, create parent directories if they don't exist."
+                }
+            },
+            "required": ["path", "content"]
+        })
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        if let Ok(parsed_args) = serde_json::from_value::<AppendFileArgs>(args.clone()) {
+            format!("Append({})", parsed_args.path)
+        } else {
+            "Append(?)".to_string()
+        }
+    }
+
+    fn result_summary(&self, result: &str) -> String {
+        if let Some(summary) = EditSummary::parse(result) {
+            return summary.short();
+        }
+        // Extract byte count from result like "Successfully appended 12 bytes to ..."
+        if let Some(bytes_str) = result.split("appended ").nth(1)
+            && let Some(bytes) = bytes_str.split(" bytes").next()
+        {
+            return format!("Appended {} bytes", bytes);
+        }
+        "Content appended successfully".to_string()
+    }
+
+    async fn generate_preview(&self, args: &Value) -> Option<String> {
+        let args: AppendFileArgs = serde_json::from_value(args.clone()).ok()?;
+        let file_path = self.resolve(&args.path);
+
+        let existing = fs::read_to_string(&file_path).await.ok();
+        let mut appended = existing.clone().unwrap_or_default();
+        appended.push_str(&args.content);
+
+        Some(file_diff(&args.path, existing.as_deref(), &appended))
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        use crate::permissions::FilePatternMatcher;
+        use std::sync::Arc;
+
+        // Appending never discards existing content, so it is write-safe
+        // rather than destructive like write_file.
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_write_safe()
+            .with_pattern_matcher(Arc::new(FilePatternMatcher))
+            .with_display_name("Append")
+            .build()
+            .expect("Failed to build AppendFileTool permission descriptor")
+    }
+}
+
+impl Default for AppendFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_to_existing_file() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("notes.md");
+        fs::write(&file, "first\n").await.unwrap();
+        let tool = AppendFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "path": "notes.md", "content": "second\n" });
+        let result = tool.execute(&args, &context()).await.unwrap();
+
+        assert!(result.contains("appended 7 bytes"));
+        assert!(result.contains("now 13 bytes"));
+        assert_eq!(fs::read_to_string(&file).await.unwrap(), "first\nsecond\n");
+    }
+
+    #[tokio::test]
+    async fn test_append_creates_missing_file() {
+        let temp_dir = tempdir().unwrap();
+        let tool = AppendFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "path": "logs/run.log", "content": "start", "create_dirs": true });
+        tool.execute(&args, &context()).await.unwrap();
+
+        let written = fs::read_to_string(temp_dir.path().join("logs/run.log"))
+            .await
+            .unwrap();
+        assert_eq!(written, "start");
+    }
+
+    #[test]
+    fn test_append_is_write_safe_not_destructive() {
+        let tool = AppendFileTool::new();
+        let descriptor = tool.describe_permission(Some("notes.md"));
+
+        assert!(descriptor.is_write_safe());
+        assert!(!descriptor.is_destructive());
+        assert_eq!(descriptor.kind(), "append_file");
+    }
+
+    #[test]
+    fn test_format_call_display() {
+        let tool = AppendFileTool::new();
+        let display = tool.format_call_display(&json!({ "path": "notes.md", "content": "x" }));
+        assert_eq!(display, "Append(notes.md)");
+    }
+}
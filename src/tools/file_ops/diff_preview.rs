@@ -0,0 +1,155 @@
+//! Unified-diff previews for file-mutating tools.
+//!
+//! Previews are plain text so each frontend can style them: the TUI renders
+//! them with its `DiffRenderer`, the CLI colors them with [`colorize_diff`].
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use similar::TextDiff;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Lines of unchanged context kept around each hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Unified diff from `old` to `new` for `path`. `None` for `old` means the
+/// file is being created, so every line shows as an addition.
+pub fn file_diff(path: &str, old: Option<&str>, new: &str) -> String {
+    let old_header = match old {
+        Some(_) => format!("a/{}", path),
+        None => "/dev/null".to_string(),
+    };
+    let new_header = format!("b/{}", path);
+
+    let diff = TextDiff::from_lines(old.unwrap_or(""), new);
+    let mut output = diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .missing_newline_hint(false)
+        .header(&old_header, &new_header)
+        .to_string();
+
+    if output.is_empty() {
+        output = format!("No changes to {}\n", path);
+    }
+    output
+}
+
+/// ANSI-color a unified diff for terminal output.
+pub fn colorize_diff(diff: &str) -> String {
+    let mut output = String::with_capacity(diff.len());
+    for line in diff.lines() {
+        let styled = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold().to_string()
+        } else if line.starts_with("@@") {
+            line.cyan().to_string()
+        } else if line.starts_with('+') {
+            line.green().to_string()
+        } else if line.starts_with('-') {
+            line.bright_red().to_string()
+        } else {
+            line.dimmed().to_string()
+        };
+        output.push_str(&styled);
+        output.push('\n');
+    }
+    output
+}
+
+/// Run git in `working_dir` and return its stdout, or an error carrying its
+/// stderr when it exits unsuccessfully.
+pub async fn run_git(working_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(working_dir)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Changes to tracked files under `pathspecs` (the whole tree when empty),
+/// staged or not: `git diff HEAD` with rename detection. Before the first
+/// commit there is no HEAD, so the diff is against the index instead.
+pub async fn tracked_diff(working_dir: &Path, pathspecs: &[&str]) -> Result<String> {
+    let mut args = vec!["diff", "--no-color", "--no-ext-diff", "--find-renames"];
+    if run_git(working_dir, &["rev-parse", "--verify", "--quiet", "HEAD"])
+        .await
+        .is_ok()
+    {
+        args.push("HEAD");
+    }
+    args.push("--");
+    args.extend_from_slice(pathspecs);
+    run_git(working_dir, &args).await
+}
+
+/// Every uncommitted change under `pathspecs`: [`tracked_diff`] followed by
+/// untracked files as new-file diffs.
+pub async fn working_tree_diff(working_dir: &Path, pathspecs: &[&str]) -> Result<String> {
+    let mut untracked_args = vec!["ls-files", "--others", "--exclude-standard", "-z", "--"];
+    untracked_args.extend_from_slice(pathspecs);
+
+    let mut diff = tracked_diff(working_dir, pathspecs).await?;
+    let untracked = run_git(working_dir, &untracked_args).await?;
+    for path in untracked.split('\0').filter(|p| !p.is_empty()) {
+        diff.push_str(&untracked_file_diff(working_dir, path).await);
+    }
+    Ok(diff)
+}
+
+/// `git diff` leaves untracked files out, but those are often exactly what
+/// the agent just wrote, so show each as a new file the way git would.
+async fn untracked_file_diff(working_dir: &Path, path: &str) -> String {
+    let Ok(bytes) = tokio::fs::read(working_dir.join(path)).await else {
+        return String::new();
+    };
+    let mut diff = format!("diff --git a/{path} b/{path}\n");
+    match String::from_utf8(bytes) {
+        Ok(text) if text.contains('\0') => {
+            diff.push_str(&format!("Binary files /dev/null and b/{path} differ\n"))
+        }
+        Ok(text) if !text.is_empty() => diff.push_str(&file_diff(path, None, &text)),
+        Ok(_) => {}
+        Err(_) => diff.push_str(&format!("Binary files /dev/null and b/{path} differ\n")),
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_shows_changed_lines_with_context() {
+        let diff = file_diff("src/a.rs", Some("one\ntwo\nthree\n"), "one\n2\nthree\n");
+
+        assert!(diff.starts_with("--- a/src/a.rs\n+++ b/src/a.rs\n"));
+        assert!(diff.contains("@@ -1,3 +1,3 @@"));
+        assert!(diff.contains("\n-two\n+2\n"));
+        assert!(diff.contains("\n one\n"));
+    }
+
+    #[test]
+    fn new_file_is_all_additions() {
+        let diff = file_diff("new.txt", None, "a\nb\n");
+
+        assert!(diff.starts_with("--- /dev/null\n+++ b/new.txt\n"));
+        assert!(diff.ends_with("+a\n+b\n"));
+    }
+
+    #[test]
+    fn identical_content_reports_no_changes() {
+        assert_eq!(
+            file_diff("x", Some("same\n"), "same\n"),
+            "No changes to x\n"
+        );
+    }
+}
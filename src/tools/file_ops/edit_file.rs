@@ -1,10 +1,10 @@
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::file_ops::diff_preview::file_diff;
+use crate::tools::file_ops::edit_summary::EditSummary;
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
-use colored::Colorize;
 use serde::Deserialize;
 use serde_json::{Value, json};
-use similar::{ChangeTag, TextDiff};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
@@ -112,12 +112,14 @@ impl EditFileTool {
                 path: file_path.clone(),
             })?;
 
-        Ok(format!(
+        let summary = EditSummary::from_contents(&args.path, Some(&content), &new_content.0)
+            .with_matches(new_content.1);
+        Ok(summary.into_result(format!(
             "Successfully edited {} (replaced {} occurrence{})",
             file_path.display(),
             new_content.1,
             if new_content.1 == 1 { "" } else { "s" }
-        ))
+        )))
     }
 }
 
@@ -208,6 +210,12 @@ impl Tool for EditFileTool {
     }
 
     fn result_summary(&self, result: &str) -> String {
+        if let Some(summary) = EditSummary::parse(result) {
+            return match summary.matches {
+                Some(n) if n > 1 => format!("{} ({} occurrences)", summary.short(), n),
+                _ => summary.short(),
+            };
+        }
         // Extract occurrence count from result like "Successfully edited ... (replaced N occurrence(s))"
         if let Some(replaced_part) = result.split("replaced ").nth(1)
             && let Some(count_str) = replaced_part.split(" occurrence").next()
@@ -227,8 +235,8 @@ impl Tool for EditFileTool {
         let file_path = self.resolve(&args.path);
         let content = fs::read_to_string(&file_path).await.ok()?;
 
-        // Generate unified diff
         let preview = self.generate_diff(
+            &args.path,
             &content,
             &args.old_string,
             &args.new_string,
@@ -251,140 +259,35 @@ impl Tool for EditFileTool {
 }
 
 impl EditFileTool {
-    /// Generate a unified diff showing what will change using the similar crate
+    /// Unified diff of the edit, or a note when `old_string` doesn't match
+    /// exactly once (and `replace_all` isn't set).
     fn generate_diff(
         &self,
+        path: &str,
         content: &str,
         old_string: &str,
         new_string: &str,
         replace_all: bool,
     ) -> String {
-        // Find all matches
-        let matches: Vec<_> = content.match_indices(old_string).collect();
+        let matches = content.matches(old_string).count();
 
-        if matches.is_empty() {
+        if matches == 0 {
             return format!("No matches found for:\n{}", old_string);
         }
+        if matches > 1 && !replace_all {
+            return format!(
+                "Found {} matches (use replace_all=true to replace all)",
+                matches
+            );
+        }
 
-        // Determine which matches will be replaced
-        let replacements = if replace_all {
-            matches.len()
-        } else {
-            if matches.len() > 1 {
-                return format!(
-                    "Found {} matches (use replace_all=true to replace all)",
-                    matches.len()
-                );
-            }
-            1
-        };
-
-        // Perform the replacement to get the new content
         let new_content = if replace_all {
             content.replace(old_string, new_string)
         } else {
             content.replacen(old_string, new_string, 1)
         };
 
-        // Use similar crate to generate unified diff
-        let diff = TextDiff::from_lines(content, &new_content);
-
-        let mut output = String::new();
-        output.push_str(&format!(
-            "{}\n\n",
-            format!(
-                "Will replace {} occurrence{}:",
-                replacements,
-                if replacements == 1 { "" } else { "s" }
-            )
-            .bold()
-            .cyan()
-        ));
-
-        // Collect all changes first to determine context windows
-        let all_changes: Vec<_> = diff.iter_all_changes().collect();
-
-        // Find indices of lines that have actual changes (not Equal)
-        let mut changed_indices = Vec::new();
-        for (idx, change) in all_changes.iter().enumerate() {
-            if !matches!(change.tag(), ChangeTag::Equal) {
-                changed_indices.push(idx);
-            }
-        }
-
-        if changed_indices.is_empty() {
-            return output;
-        }
-
-        // Determine which lines to show (changed lines + 5 lines context on each side)
-        const CONTEXT_LINES: usize = 5;
-        let mut lines_to_show = std::collections::HashSet::new();
-
-        for &changed_idx in &changed_indices {
-            let start = changed_idx.saturating_sub(CONTEXT_LINES);
-            let end = (changed_idx + CONTEXT_LINES + 1).min(all_changes.len());
-            for i in start..end {
-                lines_to_show.insert(i);
-            }
-        }
-
-        // Convert to sorted vec for sequential processing
-        let mut lines_to_show: Vec<_> = lines_to_show.into_iter().collect();
-        lines_to_show.sort_unstable();
-
-        // Track line numbers for old and new files
-        let mut old_line = 1;
-        let mut new_line = 1;
-        let mut last_shown_idx = None;
-
-        // Show diff with context, with ellipsis for skipped sections
-        for (actual_idx, change) in all_changes.iter().enumerate() {
-            // Update line counters
-            match change.tag() {
-                ChangeTag::Delete => old_line += 1,
-                ChangeTag::Insert => new_line += 1,
-                ChangeTag::Equal => {
-                    old_line += 1;
-                    new_line += 1;
-                }
-            }
-
-            // Check if we should show this line
-            if !lines_to_show.contains(&actual_idx) {
-                continue;
-            }
-
-            // Show ellipsis if we skipped lines
-            if let Some(last_idx) = last_shown_idx
-                && actual_idx > last_idx + 1
-            {
-                output.push_str(&format!("  {}\n", "...".dimmed()));
-            }
-            last_shown_idx = Some(actual_idx);
-
-            let line_content = change.to_string();
-            let line_content = line_content.trim_end();
-
-            let formatted_line = match change.tag() {
-                ChangeTag::Delete => {
-                    let line_str = format!("  {:4} {:4} - {}", old_line - 1, " ", line_content);
-                    line_str.bright_red().to_string()
-                }
-                ChangeTag::Insert => {
-                    let line_str = format!("  {:4} {:4} + {}", " ", new_line - 1, line_content);
-                    line_str.green().to_string()
-                }
-                ChangeTag::Equal => {
-                    let line_str =
-                        format!("  {:4} {:4}   {}", old_line - 1, new_line - 1, line_content);
-                    line_str.dimmed().to_string()
-                }
-            };
-            output.push_str(&formatted_line);
-            output.push('\n');
-        }
-
-        output
+        file_diff(path, Some(content), &new_content)
     }
 }
 
@@ -586,4 +489,56 @@ mod tests {
             "fn main() {\n    println!(\"Goodbye\");\n}"
         );
     }
+
+    #[tokio::test]
+    async fn test_edit_file_reports_line_delta() {
+        let temp_dir = tempdir().expect("Failed to create temporary directory");
+        fs::write(
+            temp_dir.path().join("main.rs"),
+            "fn main() {\n    old();\n}\n",
+        )
+        .await
+        .expect("Failed to write test file");
+
+        let tool = EditFileTool::with_working_directory(temp_dir.path().to_path_buf());
+        let args = serde_json::json!({
+            "path": "main.rs",
+            "old_string": "    old();\n",
+            "new_string": "    one();\n    two();\n    three();\n"
+        });
+        let context = ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+
+        let result = tool.execute(&args, &context).await.unwrap();
+        let summary = EditSummary::parse(&result).expect("result carries a summary");
+
+        assert_eq!(summary.path, "main.rs");
+        assert_eq!(summary.matches, Some(1));
+        assert_eq!(summary.lines_added, 3);
+        assert_eq!(summary.lines_removed, 1);
+        assert!(!summary.new_file);
+        assert_eq!(tool.result_summary(&result), "+3 -1 in main.rs");
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_preview_is_unified_diff() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.rs"), "fn a() {}\nfn b() {}\n")
+            .await
+            .unwrap();
+        let tool = EditFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = serde_json::json!({
+            "path": "a.rs",
+            "old_string": "fn b() {}",
+            "new_string": "fn c() {}"
+        });
+        let preview = tool.generate_preview(&args).await.unwrap();
+
+        assert!(preview.starts_with("--- a/a.rs\n+++ b/a.rs\n"));
+        assert!(preview.contains("\n-fn b() {}\n+fn c() {}\n"));
+    }
 }
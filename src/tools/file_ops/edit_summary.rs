@@ -0,0 +1,107 @@
+//! Machine-readable outcome of a file-mutating tool call.
+//!
+//! The tool trait returns a `String`, so the summary travels as the first line
+//! of the result (`edit_result: {...}`), followed by the usual human-readable
+//! message. Frontends and automation recover it with [`EditSummary::parse`].
+
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+const HEADER_PREFIX: &str = "edit_result: ";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditSummary {
+    pub path: String,
+    /// Occurrences replaced; only meaningful for `edit_file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<usize>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub new_file: bool,
+}
+
+impl EditSummary {
+    /// Line delta between `old` and `new`; `None` for `old` means the file was
+    /// created.
+    pub fn from_contents(path: &str, old: Option<&str>, new: &str) -> Self {
+        let diff = TextDiff::from_lines(old.unwrap_or(""), new);
+        let (mut lines_added, mut lines_removed) = (0, 0);
+        for change in diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => lines_added += 1,
+                ChangeTag::Delete => lines_removed += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+
+        Self {
+            path: path.to_string(),
+            matches: None,
+            lines_added,
+            lines_removed,
+            new_file: old.is_none(),
+        }
+    }
+
+    pub fn with_matches(mut self, matches: usize) -> Self {
+        self.matches = Some(matches);
+        self
+    }
+
+    /// The result string: the summary header, then `message`.
+    pub fn into_result(self, message: impl AsRef<str>) -> String {
+        let header = serde_json::to_string(&self).unwrap_or_default();
+        format!("{}{}\n{}", HEADER_PREFIX, header, message.as_ref())
+    }
+
+    /// Recovers the summary from a tool result, if it carries one.
+    pub fn parse(result: &str) -> Option<Self> {
+        let header = result.lines().next()?.strip_prefix(HEADER_PREFIX)?;
+        serde_json::from_str(header).ok()
+    }
+
+    /// `+3 -1 in src/main.rs`, or `created src/main.rs (+12)` for new files.
+    pub fn short(&self) -> String {
+        if self.new_file {
+            format!("created {} (+{})", self.path, self.lines_added)
+        } else {
+            format!(
+                "+{} -{} in {}",
+                self.lines_added, self.lines_removed, self.path
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_changed_lines() {
+        let summary =
+            EditSummary::from_contents("src/main.rs", Some("a\nb\nc\n"), "a\nB\nB2\nB3\nc\n");
+        assert_eq!(summary.lines_added, 3);
+        assert_eq!(summary.lines_removed, 1);
+        assert!(!summary.new_file);
+        assert_eq!(summary.short(), "+3 -1 in src/main.rs");
+    }
+
+    #[test]
+    fn new_file_counts_every_line_as_added() {
+        let summary = EditSummary::from_contents("notes.md", None, "one\ntwo\n");
+        assert!(summary.new_file);
+        assert_eq!(summary.lines_added, 2);
+        assert_eq!(summary.short(), "created notes.md (+2)");
+    }
+
+    #[test]
+    fn round_trips_through_result_string() {
+        let summary = EditSummary::from_contents("a b.txt", Some("x\n"), "y\n").with_matches(1);
+        let result = summary.clone().into_result("Successfully edited a b.txt");
+
+        assert!(result.ends_with("\nSuccessfully edited a b.txt"));
+        assert_eq!(EditSummary::parse(&result), Some(summary));
+        assert_eq!(EditSummary::parse("Successfully edited a b.txt"), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::ignore_rules::walk_builder;
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -45,23 +46,26 @@ impl ListDirectoryTool {
 
         let dir_path = self.resolve_path(&args.path);
 
-        let mut entries =
-            fs::read_dir(&dir_path)
-                .await
-                .map_err(|_| ToolError::ExecutionFailed {
-                    message: format!("Failed to read directory: {}", dir_path.display()),
-                })?;
+        let is_dir = fs::metadata(&dir_path)
+            .await
+            .map(|m| m.is_dir())
+            .unwrap_or(false);
+        if !is_dir {
+            return Err(ToolError::ExecutionFailed {
+                message: format!("Failed to read directory: {}", dir_path.display()),
+            });
+        }
+
+        let mut walker = walk_builder(&dir_path, args.include_ignored);
+        walker.max_depth(Some(1));
 
         let mut directory_entries = Vec::new();
 
-        while let Some(entry) =
-            entries
-                .next_entry()
-                .await
-                .map_err(|_| ToolError::ExecutionFailed {
-                    message: "Failed to read directory entry".to_string(),
-                })?
-        {
+        for entry in walker.build().filter_map(|e| e.ok()) {
+            if entry.depth() == 0 {
+                continue;
+            }
+
             let file_name = entry.file_name().to_string_lossy().to_string();
 
             // Skip hidden files unless explicitly requested
@@ -69,12 +73,9 @@ impl ListDirectoryTool {
                 continue;
             }
 
-            let metadata = entry
-                .metadata()
-                .await
-                .map_err(|_| ToolError::ExecutionFailed {
-                    message: "Failed to read file metadata".to_string(),
-                })?;
+            let metadata = entry.metadata().map_err(|_| ToolError::ExecutionFailed {
+                message: "Failed to read file metadata".to_string(),
+            })?;
             let is_file = metadata.is_file();
             let is_dir = metadata.is_dir();
             let size = if is_file { Some(metadata.len()) } else { None };
@@ -140,6 +141,8 @@ struct ListDirectoryArgs {
     path: String,
     #[serde(default)]
     show_hidden: bool,
+    #[serde(default)]
+    include_ignored: bool,
 }
 
 #[derive(Serialize)]
@@ -168,6 +171,10 @@ impl Tool for ListDirectoryTool {
         phrasing::LIST
     }
 
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
     fn display_name(&self) -> &'static str {
         "list"
     }
@@ -178,6 +185,7 @@ impl Tool for ListDirectoryTool {
         - Returns directories first (each with a trailing /), then files\n\
         - File sizes are shown in bytes\n\
         - Hidden files (starting with .) are excluded by default\n\
+        - Paths ignored by .gitignore (e.g. target/, node_modules/) are excluded by default\n\
         - Use this instead of bash ls command\n\n\
         When to use:\n\
         - Understanding project structure\n\
@@ -202,6 +210,11 @@ impl Tool for ListDirectoryTool {
                     "type": "boolean",
                     "default": false,
                     "description": "If true, include hidden files/directories (those starting with '.'). Examples: .gitignore, .env, .github/"
+                },
+                "include_ignored": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, include entries excluded by .gitignore (or by the built-in set such as target/ and node_modules/ outside a git repo)."
                 }
             },
             "required": []
@@ -339,4 +352,133 @@ mod tests {
             "Empty directory"
         );
     }
+
+    fn test_context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    async fn make_repo_with_gitignore() -> tempfile::TempDir {
+        let temp_dir = tempdir().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "target/\n*.log\n")
+            .await
+            .unwrap();
+        fs::create_dir(temp_dir.path().join("target"))
+            .await
+            .unwrap();
+        fs::create_dir(temp_dir.path().join("src")).await.unwrap();
+        fs::write(temp_dir.path().join("debug.log"), "x")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}")
+            .await
+            .unwrap();
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn gitignored_entries_are_excluded_by_default() {
+        let temp_dir = make_repo_with_gitignore().await;
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(&serde_json::json!({ "path": "" }), &test_context())
+            .await
+            .unwrap();
+
+        assert!(result.contains("src/"));
+        assert!(result.contains("main.rs"));
+        assert!(!result.contains("target/"));
+        assert!(!result.contains("debug.log"));
+    }
+
+    #[tokio::test]
+    async fn include_ignored_lists_gitignored_entries() {
+        let temp_dir = make_repo_with_gitignore().await;
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(
+                &serde_json::json!({ "path": "", "include_ignored": true }),
+                &test_context(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.contains("target/"));
+        assert!(result.contains("debug.log"));
+    }
+
+    #[tokio::test]
+    async fn git_dir_is_excluded_even_with_show_hidden() {
+        let temp_dir = make_repo_with_gitignore().await;
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(
+                &serde_json::json!({ "path": "", "show_hidden": true }),
+                &test_context(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.contains(".gitignore"));
+        assert!(!result.contains(".git/"));
+    }
+
+    #[tokio::test]
+    async fn nested_gitignore_applies_in_subdirectory() {
+        let temp_dir = make_repo_with_gitignore().await;
+        let src = temp_dir.path().join("src");
+        fs::write(src.join(".gitignore"), "generated.rs\n")
+            .await
+            .unwrap();
+        fs::write(src.join("generated.rs"), "").await.unwrap();
+        fs::write(src.join("lib.rs"), "").await.unwrap();
+        fs::write(src.join("trace.log"), "").await.unwrap();
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(&serde_json::json!({ "path": "src" }), &test_context())
+            .await
+            .unwrap();
+
+        assert!(result.contains("lib.rs"));
+        assert!(!result.contains("generated.rs"));
+        assert!(!result.contains("trace.log"));
+    }
+
+    #[tokio::test]
+    async fn builtin_ignore_set_applies_outside_git_repo() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("node_modules"))
+            .await
+            .unwrap();
+        fs::create_dir(temp_dir.path().join("lib")).await.unwrap();
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(&serde_json::json!({ "path": "" }), &test_context())
+            .await
+            .unwrap();
+
+        assert!(result.contains("lib/"));
+        assert!(!result.contains("node_modules"));
+    }
+
+    #[tokio::test]
+    async fn missing_directory_is_an_error() {
+        let temp_dir = tempdir().unwrap();
+        let tool = ListDirectoryTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(&serde_json::json!({ "path": "nope" }), &test_context())
+            .await;
+
+        assert!(result.is_err());
+    }
 }
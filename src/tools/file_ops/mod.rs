@@ -1,9 +1,18 @@
+mod append_file;
+pub mod diff_preview;
 mod edit_file;
+pub mod edit_summary;
 mod list_directory;
+mod move_file;
 mod read_file;
 mod write_file;
+mod write_files;
 
+pub use append_file::AppendFileTool;
 pub use edit_file::EditFileTool;
+pub use edit_summary::EditSummary;
 pub use list_directory::ListDirectoryTool;
-pub use read_file::ReadFileTool;
+pub use move_file::MoveFileTool;
+pub use read_file::{LOSSY_TEXT_HEADER, ReadFileTool, ReadLimits, large_read_message};
 pub use write_file::WriteFileTool;
+pub use write_files::WriteFilesTool;
@@ -0,0 +1,312 @@
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+pub struct MoveFileTool {
+    working_directory: PathBuf,
+}
+
+impl MoveFileTool {
+    pub fn new() -> Self {
+        let working_directory = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self { working_directory }
+    }
+
+    pub fn with_working_directory(working_dir: PathBuf) -> Self {
+        Self {
+            working_directory: working_dir,
+        }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        let p = Path::new(path);
+        if p.is_absolute() {
+            p.to_path_buf()
+        } else {
+            self.working_directory.join(p)
+        }
+    }
+
+    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+        let args: MoveFileArgs =
+            serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
+                tool: "move_file".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let source = self.resolve(&args.source);
+        let destination = self.resolve(&args.destination);
+
+        if fs::symlink_metadata(&source).await.is_err() {
+            return Err(ToolError::FileNotFound { path: source });
+        }
+
+        if fs::symlink_metadata(&destination).await.is_ok() {
+            if !args.overwrite {
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "Destination {} already exists. Set overwrite=true to replace it",
+                        destination.display()
+                    ),
+                });
+            }
+            if fs::metadata(&destination).await.is_ok_and(|m| m.is_dir()) {
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "Destination {} is a directory and will not be replaced",
+                        destination.display()
+                    ),
+                });
+            }
+        }
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|_| ToolError::WriteFailed {
+                    path: destination.clone(),
+                })?;
+        }
+
+        if fs::rename(&source, &destination).await.is_err() {
+            // rename fails across filesystems; fall back to copy + delete for files.
+            if !fs::metadata(&source).await.is_ok_and(|m| m.is_file()) {
+                return Err(ToolError::WriteFailed { path: destination });
+            }
+            fs::copy(&source, &destination)
+                .await
+                .map_err(|_| ToolError::WriteFailed {
+                    path: destination.clone(),
+                })?;
+            fs::remove_file(&source)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed {
+                    message: format!(
+                        "Copied to {} but failed to remove {}: {}",
+                        destination.display(),
+                        source.display(),
+                        e
+                    ),
+                })?;
+        }
+
+        Ok(format!(
+            "Successfully moved {} to {}",
+            source.display(),
+            destination.display()
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct MoveFileArgs {
+    source: String,
+    destination: String,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+#[async_trait]
+impl Tool for MoveFileTool {
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args).await
+    }
+
+    fn name(&self) -> &'static str {
+        "move_file"
+    }
+
+    fn phrasing(&self) -> CategoryPhrasing {
+        phrasing::EDIT
+    }
+
+    fn display_name(&self) -> &'static str {
+        "move"
+    }
+
+    fn description(&self) -> &'static str {
+        "Move or rename a file or directory.\n\n\
+        Usage:\n\
+        - Missing parent directories of the destination are created automatically\n\
+        - Fails if the destination exists, unless overwrite=true (directories are never replaced)\n\
+        - Prefer this over read_file + write_file + bash rm when relocating files\n\n\
+        When to use:\n\
+        - Renaming a module or test file\n\
+        - Moving files between directories during a refactor"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "source": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The existing path to move. Example: \"src/utils.rs\""
+                },
+                "destination": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The new path, including the file name. Example: \"src/utils/mod.rs\""
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, replace an existing file at the destination."
+                }
+            },
+            "required": ["source", "destination"]
+        })
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        if let Ok(parsed_args) = serde_json::from_value::<MoveFileArgs>(args.clone()) {
+            format!("Move({} → {})", parsed_args.source, parsed_args.destination)
+        } else {
+            "Move(?)".to_string()
+        }
+    }
+
+    fn result_summary(&self, _result: &str) -> String {
+        "Moved".to_string()
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        use crate::permissions::FilePatternMatcher;
+        use std::sync::Arc;
+
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_destructive()
+            .with_pattern_matcher(Arc::new(FilePatternMatcher))
+            .with_display_name("Move")
+            .build()
+            .expect("Failed to build MoveFileTool permission descriptor")
+    }
+
+    /// Permissions are keyed on the destination, which is where the write lands.
+    fn describe_permission_for_call(
+        &self,
+        _target: Option<&str>,
+        args: &Value,
+    ) -> ToolPermissionDescriptor {
+        let destination = args.get("destination").and_then(|v| v.as_str());
+        self.describe_permission(destination)
+    }
+}
+
+impl Default for MoveFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_in_same_directory() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("old.rs"), "fn a() {}")
+            .await
+            .unwrap();
+        let tool = MoveFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "source": "old.rs", "destination": "new.rs" });
+        tool.execute(&args, &context()).await.unwrap();
+
+        assert!(!temp_dir.path().join("old.rs").exists());
+        let moved = fs::read_to_string(temp_dir.path().join("new.rs"))
+            .await
+            .unwrap();
+        assert_eq!(moved, "fn a() {}");
+    }
+
+    #[tokio::test]
+    async fn test_move_across_directories_creates_parents() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("utils.rs"), "x")
+            .await
+            .unwrap();
+        let tool = MoveFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "source": "utils.rs", "destination": "src/utils/mod.rs" });
+        tool.execute(&args, &context()).await.unwrap();
+
+        assert!(!temp_dir.path().join("utils.rs").exists());
+        assert!(temp_dir.path().join("src/utils/mod.rs").exists());
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_overwrite_without_flag() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a").await.unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "b").await.unwrap();
+        let tool = MoveFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "source": "a.txt", "destination": "b.txt" });
+        let result = tool.execute(&args, &context()).await;
+
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("b.txt"))
+                .await
+                .unwrap(),
+            "b"
+        );
+        assert!(temp_dir.path().join("a.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_flag_replaces_destination() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a").await.unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "b").await.unwrap();
+        let tool = MoveFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "source": "a.txt", "destination": "b.txt", "overwrite": true });
+        tool.execute(&args, &context()).await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("b.txt"))
+                .await
+                .unwrap(),
+            "a"
+        );
+        assert!(!temp_dir.path().join("a.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_missing_source_is_an_error() {
+        let temp_dir = tempdir().unwrap();
+        let tool = MoveFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({ "source": "nope.txt", "destination": "b.txt" });
+        let result = tool.execute(&args, &context()).await;
+
+        assert!(matches!(result, Err(ToolError::FileNotFound { .. })));
+    }
+
+    #[test]
+    fn test_permission_targets_destination() {
+        let tool = MoveFileTool::new();
+        let args = json!({ "source": "a.rs", "destination": "src/b.rs" });
+        let descriptor = tool.describe_permission_for_call(None, &args);
+
+        assert_eq!(descriptor.target(), "src/b.rs");
+        assert!(descriptor.is_destructive());
+    }
+}
@@ -1,13 +1,121 @@
+use crate::agent::AgentEvent;
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+/// Prepended to file contents that were not valid UTF-8.
+pub const LOSSY_TEXT_HEADER: &str = "[binary/invalid UTF-8, showing lossy text]";
+
+/// Bytes sampled from the start of a file when deciding whether it is binary.
+const BINARY_SNIFF_LEN: usize = 8192;
+/// A sample with more than this share of NUL bytes is treated as binary.
+const BINARY_NUL_RATIO: f64 = 0.1;
+
+/// Decode file bytes for the model. Anything that looks binary is refused,
+/// even when the bytes happen to be valid UTF-8 (a run of NULs is); valid
+/// UTF-8 otherwise passes through untouched, and other invalid UTF-8 (e.g.
+/// latin-1) is decoded lossily and flagged with [`LOSSY_TEXT_HEADER`] by the
+/// caller.
+fn decode_text(bytes: Vec<u8>, path: &Path) -> ToolResult<(String, bool)> {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+    let nul_count = sample.iter().filter(|&&b| b == 0).count();
+    if nul_count as f64 > sample.len() as f64 * BINARY_NUL_RATIO {
+        return Err(ToolError::ExecutionFailed {
+            message: format!(
+                "{} looks like a binary file ({} bytes); not showing its contents. \
+                 Use bash (e.g. `file` or `xxd | head`) if you need to inspect it",
+                path.display(),
+                bytes.len()
+            ),
+        });
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok((text, false)),
+        Err(e) => Ok((String::from_utf8_lossy(e.as_bytes()).into_owned(), true)),
+    }
+}
+
+/// Guardrail against single reads that would swamp the context window.
+/// Sizes are measured on what the read returns, so a line range out of a
+/// huge file is fine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReadLimits {
+    /// Reads returning more bytes than this raise a warning.
+    pub warn_bytes: usize,
+    /// Reads returning more bytes than this are cut to the lines that fit,
+    /// with a note telling the model to use line ranges.
+    pub max_bytes: usize,
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        Self {
+            warn_bytes: 64 * 1024,
+            max_bytes: 256 * 1024,
+        }
+    }
+}
+
+impl ReadLimits {
+    /// Cut `text`, which starts at line `first_line` of the file, to the
+    /// whole lines that fit in `max_bytes`. `None` when it already fits.
+    fn head_window(&self, text: &str, first_line: usize) -> Option<String> {
+        if text.len() <= self.max_bytes {
+            return None;
+        }
+
+        let mut end = 0;
+        let mut lines = 0;
+        for line in text.split_inclusive('\n') {
+            if end + line.len() > self.max_bytes {
+                break;
+            }
+            end += line.len();
+            lines += 1;
+        }
+        if lines == 0 {
+            // A single line longer than the cap: keep what fits of it.
+            end = self.max_bytes;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            lines = 1;
+        }
+
+        Some(format!(
+            "{}\n\n[Truncated: this read is {} bytes, over the {} byte limit. \
+             Showing lines {}-{}; use start_line/end_line to read the rest.]",
+            text[..end].trim_end_matches('\n'),
+            text.len(),
+            self.max_bytes,
+            first_line,
+            first_line + lines - 1
+        ))
+    }
+}
+
+/// User-facing text for [`AgentEvent::LargeFileRead`].
+pub fn large_read_message(path: &str, bytes: usize, truncated: bool) -> String {
+    let size = format!("{} KB", bytes.div_ceil(1024));
+    if truncated {
+        format!(
+            "{} is {}, over the read limit; only its first lines were sent",
+            path, size
+        )
+    } else {
+        format!("Read {} from {}; large reads use up context", size, path)
+    }
+}
+
 pub struct ReadFileTool {
     working_directory: PathBuf,
+    limits: ReadLimits,
 }
 
 impl ReadFileTool {
@@ -15,15 +123,21 @@ impl ReadFileTool {
 
     pub fn new() -> Self {
         let working_directory = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        Self { working_directory }
+        Self::with_working_directory(working_directory)
     }
 
     pub fn with_working_directory(working_dir: PathBuf) -> Self {
         Self {
             working_directory: working_dir,
+            limits: ReadLimits::default(),
         }
     }
 
+    pub fn with_limits(mut self, limits: ReadLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     fn resolve(&self, path: &str) -> PathBuf {
         let p = Path::new(path);
         if p.is_absolute() {
@@ -33,7 +147,11 @@ impl ReadFileTool {
         }
     }
 
-    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+    async fn execute_impl(
+        &self,
+        args: &Value,
+        context: &ToolExecutionContext,
+    ) -> ToolResult<String> {
         let args: ReadFileArgs =
             serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
                 tool: "read_file".to_string(),
@@ -42,12 +160,43 @@ impl ReadFileTool {
 
         let file_path = self.resolve(&args.path);
 
-        let content = fs::read_to_string(&file_path)
+        let bytes = fs::read(&file_path)
             .await
             .map_err(|_| ToolError::ReadFailed {
                 path: file_path.clone(),
             })?;
+        let (content, lossy) = decode_text(bytes, &file_path)?;
+        let mut selected = self.select_lines(&content, &args)?;
+
+        let bytes = selected.len();
+        let truncated = match self
+            .limits
+            .head_window(&selected, args.start_line.unwrap_or(1))
+        {
+            Some(head) => {
+                selected = head;
+                true
+            }
+            None => false,
+        };
+        if (truncated || bytes > self.limits.warn_bytes)
+            && let Some(tx) = &context.event_tx
+        {
+            let _ = tx.send(AgentEvent::LargeFileRead {
+                path: args.path.clone(),
+                bytes,
+                truncated,
+            });
+        }
+
+        if lossy {
+            Ok(format!("{}\n{}", LOSSY_TEXT_HEADER, selected))
+        } else {
+            Ok(selected)
+        }
+    }
 
+    fn select_lines(&self, content: &str, args: &ReadFileArgs) -> ToolResult<String> {
         // Handle line-based reading if specified
         if let (Some(start), Some(end)) = (args.start_line, args.end_line) {
             let lines: Vec<&str> = content.lines().collect();
@@ -94,7 +243,7 @@ impl ReadFileTool {
             let selected_lines = &lines[start - 1..];
             Ok(selected_lines.join("\n"))
         } else {
-            Ok(content)
+            Ok(content.to_string())
         }
     }
 }
@@ -110,8 +259,8 @@ struct ReadFileArgs {
 
 #[async_trait]
 impl Tool for ReadFileTool {
-    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
-        self.execute_impl(args).await
+    async fn execute(&self, args: &Value, context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args, context).await
     }
 
     fn name(&self) -> &'static str {
@@ -122,6 +271,10 @@ impl Tool for ReadFileTool {
         phrasing::READ
     }
 
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
     fn display_name(&self) -> &'static str {
         "read"
     }
@@ -132,7 +285,9 @@ impl Tool for ReadFileTool {
         - You MUST read a file before editing it with edit_file or overwriting with write_file\n\
         - By default, reads the entire file. Use start_line/end_line for large files\n\
         - Results include line numbers for easy reference when editing\n\
-        - Use this instead of bash commands like cat, head, or tail\n\n\
+        - Use this instead of bash commands like cat, head, or tail\n\
+        - Binary files are refused; non-UTF-8 text is returned lossily with a header\n\
+        - Very large reads are cut to their first lines; page through with start_line/end_line\n\n\
         When to use:\n\
         - Reading source code before making changes\n\
         - Examining configuration files\n\
@@ -226,4 +381,164 @@ mod tests {
         let result = tool.execute(&args, &context).await.unwrap();
         assert_eq!(result, content);
     }
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_utf8_is_decoded_lossily_with_header() {
+        let temp_dir = tempdir().unwrap();
+        // "café\nline 2" in latin-1: 0xE9 is not valid UTF-8 on its own.
+        fs::write(temp_dir.path().join("latin1.txt"), b"caf\xe9\nline 2")
+            .await
+            .unwrap();
+        let tool = ReadFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let result = tool
+            .execute(&json!({ "path": "latin1.txt" }), &context())
+            .await
+            .unwrap();
+        assert_eq!(result, format!("{LOSSY_TEXT_HEADER}\ncaf\u{FFFD}\nline 2"));
+
+        let ranged = tool
+            .execute(
+                &json!({ "path": "latin1.txt", "start_line": 2, "end_line": 2 }),
+                &context(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(ranged, format!("{LOSSY_TEXT_HEADER}\nline 2"));
+    }
+
+    #[tokio::test]
+    async fn test_nul_only_file_is_refused_even_though_it_is_utf8() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("zeros.bin"), [0u8; 32])
+            .await
+            .unwrap();
+        let tool = ReadFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let err = tool
+            .execute(&json!({ "path": "zeros.bin" }), &context())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("looks like a binary file"));
+    }
+
+    #[tokio::test]
+    async fn test_binary_file_is_refused() {
+        let temp_dir = tempdir().unwrap();
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0xff, 0xfe];
+        bytes.extend(std::iter::repeat_n(0u8, 64));
+        fs::write(temp_dir.path().join("blob.bin"), &bytes)
+            .await
+            .unwrap();
+        let tool = ReadFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let err = tool
+            .execute(&json!({ "path": "blob.bin" }), &context())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("looks like a binary file"));
+    }
+
+    fn limited_tool(dir: &Path, warn_bytes: usize, max_bytes: usize) -> ReadFileTool {
+        ReadFileTool::with_working_directory(dir.to_path_buf()).with_limits(ReadLimits {
+            warn_bytes,
+            max_bytes,
+        })
+    }
+
+    async fn read_with_events(tool: &ReadFileTool, args: Value) -> (String, Vec<AgentEvent>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let context = ToolExecutionContext {
+            event_tx: Some(tx),
+            ..context()
+        };
+        let result = tool.execute(&args, &context).await.unwrap();
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        (result, events)
+    }
+
+    #[tokio::test]
+    async fn test_warns_only_above_the_warning_threshold() {
+        let temp_dir = tempdir().unwrap();
+        // Ten lines of "line N\n" minus the final newline: 69 bytes.
+        let content = (0..10)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(temp_dir.path().join("f.txt"), &content)
+            .await
+            .unwrap();
+
+        let at_limit = limited_tool(temp_dir.path(), content.len(), 1024);
+        let (result, events) = read_with_events(&at_limit, json!({ "path": "f.txt" })).await;
+        assert_eq!(result, content);
+        assert!(events.is_empty());
+
+        let below = limited_tool(temp_dir.path(), content.len() - 1, 1024);
+        let (result, events) = read_with_events(&below, json!({ "path": "f.txt" })).await;
+        assert_eq!(result, content);
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::LargeFileRead { path, bytes, truncated: false }]
+                if path == "f.txt" && *bytes == content.len()
+        ));
+
+        // A narrow range of the same file stays under the threshold.
+        let (_, events) = read_with_events(
+            &below,
+            json!({ "path": "f.txt", "start_line": 2, "end_line": 3 }),
+        )
+        .await;
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reads_over_the_cap_return_a_head_window() {
+        let temp_dir = tempdir().unwrap();
+        let content = (1..=100)
+            .map(|n| format!("line {n:03}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(temp_dir.path().join("big.txt"), &content)
+            .await
+            .unwrap();
+        // Each line is 9 bytes including its newline, so 30 bytes fit three.
+        let tool = limited_tool(temp_dir.path(), 10, 30);
+
+        let (result, events) = read_with_events(&tool, json!({ "path": "big.txt" })).await;
+        assert!(result.starts_with("line 001\nline 002\nline 003\n\n[Truncated:"));
+        assert!(result.contains("Showing lines 1-3"));
+        assert!(result.contains("start_line/end_line"));
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::LargeFileRead { truncated: true, bytes, .. }] if *bytes == content.len()
+        ));
+
+        let (result, _) =
+            read_with_events(&tool, json!({ "path": "big.txt", "start_line": 50 })).await;
+        assert!(result.starts_with("line 050\nline 051\nline 052\n\n"));
+        assert!(result.contains("Showing lines 50-52"));
+    }
+
+    #[test]
+    fn test_single_overlong_line_is_cut_at_a_char_boundary() {
+        let limits = ReadLimits {
+            warn_bytes: 0,
+            max_bytes: 5,
+        };
+        let head = limits.head_window("ééééé", 1).unwrap();
+        assert!(head.starts_with("éé\n\n[Truncated"));
+        assert!(limits.head_window("short", 1).is_none());
+    }
 }
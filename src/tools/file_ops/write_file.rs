@@ -1,7 +1,8 @@
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::file_ops::diff_preview::file_diff;
+use crate::tools::file_ops::edit_summary::EditSummary;
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
-use colored::Colorize;
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::path::{Path, PathBuf};
@@ -53,6 +54,12 @@ impl WriteFileTool {
         }
 
         let content = args.content.as_deref().unwrap_or("");
+        // Unreadable (e.g. binary) files still count as existing, just with
+        // nothing to diff against.
+        let previous = match fs::metadata(&file_path).await {
+            Ok(_) => Some(fs::read_to_string(&file_path).await.unwrap_or_default()),
+            Err(_) => None,
+        };
 
         fs::write(&file_path, content)
             .await
@@ -60,11 +67,12 @@ impl WriteFileTool {
                 path: file_path.clone(),
             })?;
 
-        Ok(format!(
+        let summary = EditSummary::from_contents(&args.path, previous.as_deref(), content);
+        Ok(summary.into_result(format!(
             "Successfully wrote {} bytes to {}",
             content.len(),
             file_path.display()
-        ))
+        )))
     }
 }
 
@@ -91,6 +99,11 @@ impl Tool for WriteFileTool {
         phrasing::EDIT
     }
 
+    /// The file is replaced whole, so a second attempt writes the same result.
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
     fn display_name(&self) -> &'static str {
         "write"
     }
@@ -144,6 +157,9 @@ impl Tool for WriteFileTool {
     }
 
     fn result_summary(&self, result: &str) -> String {
+        if let Some(summary) = EditSummary::parse(result) {
+            return summary.short();
+        }
         // Extract byte count from result like "Successfully wrote 123 bytes to ..."
         if let Some(bytes_str) = result.split("wrote ").nth(1)
             && let Some(bytes) = bytes_str.split(" bytes").next()
@@ -161,14 +177,11 @@ impl Tool for WriteFileTool {
         let file_path = self.resolve(&args.path);
         let content = args.content.as_deref().unwrap_or("");
 
-        // Check if file exists
         if file_path.exists() {
-            // Show diff for overwriting existing file
             let old_content = fs::read_to_string(&file_path).await.ok()?;
-            Some(self.generate_diff(&old_content, content, &args.path))
+            Some(file_diff(&args.path, Some(&old_content), content))
         } else {
-            // Show preview of new file content
-            Some(self.generate_new_file_preview(content, &args.path))
+            Some(file_diff(&args.path, None, content))
         }
     }
 
@@ -185,85 +198,6 @@ impl Tool for WriteFileTool {
     }
 }
 
-impl WriteFileTool {
-    /// Generate a diff showing the old file vs new file content
-    fn generate_diff(&self, old_content: &str, new_content: &str, path: &str) -> String {
-        let mut output = String::new();
-
-        output.push_str(&format!(
-            "{}\n\n",
-            format!("Overwriting file: {}", path).bold().cyan()
-        ));
-
-        let old_lines: Vec<&str> = old_content.lines().collect();
-        let new_lines: Vec<&str> = new_content.lines().collect();
-
-        let total_old = old_lines.len();
-        let total_new = new_lines.len();
-
-        // Show size comparison
-        output.push_str(&format!(
-            "{}\n",
-            format!("Old: {} lines, {} bytes", total_old, old_content.len()).yellow()
-        ));
-        output.push_str(&format!(
-            "{}\n\n",
-            format!("New: {} lines, {} bytes", total_new, new_content.len()).yellow()
-        ));
-
-        // Show all lines of old content
-        if total_old > 0 {
-            output.push_str(&format!("{}:\n", "Old content".bold()));
-            for (i, line) in old_lines.iter().enumerate() {
-                output.push_str(&format!(
-                    "{}\n",
-                    format!("  {:4} - {}", i + 1, line).bright_red()
-                ));
-            }
-            output.push('\n');
-        }
-
-        // Show all lines of new content
-        output.push_str(&format!("{}:\n", "New content".bold()));
-        for (i, line) in new_lines.iter().enumerate() {
-            output.push_str(&format!(
-                "{}\n",
-                format!("  {:4} + {}", i + 1, line).green()
-            ));
-        }
-
-        output
-    }
-
-    /// Generate a preview for creating a new file
-    fn generate_new_file_preview(&self, content: &str, path: &str) -> String {
-        let mut output = String::new();
-
-        output.push_str(&format!(
-            "{}\n\n",
-            format!("Creating new file: {}", path).bold().bright_cyan()
-        ));
-
-        let lines: Vec<&str> = content.lines().collect();
-        let total_lines = lines.len();
-
-        output.push_str(&format!(
-            "{}\n\n",
-            format!("Size: {} lines, {} bytes", total_lines, content.len()).yellow()
-        ));
-        output.push_str(&format!("{}:\n", "Content".bold()));
-
-        for (i, line) in lines.iter().enumerate() {
-            output.push_str(&format!(
-                "{}\n",
-                format!("  {:4} + {}", i + 1, line).green()
-            ));
-        }
-
-        output
-    }
-}
-
 impl Default for WriteFileTool {
     fn default() -> Self {
         Self::new()
@@ -300,4 +234,39 @@ mod tests {
             .unwrap();
         assert_eq!(written_content, content);
     }
+
+    #[tokio::test]
+    async fn test_write_file_preview_shows_new_file_as_additions() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFileTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = serde_json::json!({ "path": "new.txt", "content": "a\nb\n" });
+        let preview = tool.generate_preview(&args).await.unwrap();
+
+        assert_eq!(
+            preview,
+            "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_summary_distinguishes_new_and_existing_files() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFileTool::with_working_directory(temp_dir.path().to_path_buf());
+        let context = ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+
+        let args = serde_json::json!({ "path": "notes.txt", "content": "a\nb\n" });
+        let created = tool.execute(&args, &context).await.unwrap();
+        let summary = EditSummary::parse(&created).unwrap();
+        assert!(summary.new_file);
+        assert_eq!(summary.lines_added, 2);
+
+        let args = serde_json::json!({ "path": "notes.txt", "content": "a\nc\n" });
+        let rewritten = tool.execute(&args, &context).await.unwrap();
+        assert_eq!(tool.result_summary(&rewritten), "+1 -1 in notes.txt");
+    }
 }
@@ -0,0 +1,550 @@
+use crate::permissions::{
+    FilePatternMatcher, PatternMatcher, ToolPermissionBuilder, ToolPermissionDescriptor,
+};
+use crate::tools::file_ops::diff_preview::file_diff;
+use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+
+/// Writes several files in one call, all or nothing.
+pub struct WriteFilesTool {
+    working_directory: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct FileEntry {
+    path: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct WriteFilesArgs {
+    files: Vec<FileEntry>,
+    #[serde(default)]
+    overwrite: bool,
+    #[serde(default)]
+    create_dirs: bool,
+}
+
+/// A validated entry, ready to be staged.
+struct PlannedWrite<'a> {
+    entry: &'a FileEntry,
+    target: PathBuf,
+    /// Contents of the file being replaced, to restore on rollback.
+    previous: Option<Vec<u8>>,
+}
+
+/// Matches a stored rule against every path of a multi-file write: the rule
+/// covers the call only if it covers each file.
+struct AllPathsMatcher;
+
+impl PatternMatcher for AllPathsMatcher {
+    fn matches(&self, pattern: &str, target: &str) -> bool {
+        target
+            .lines()
+            .all(|path| FilePatternMatcher.matches(pattern, path))
+    }
+}
+
+impl WriteFilesTool {
+    pub fn new() -> Self {
+        let working_directory = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self { working_directory }
+    }
+
+    pub fn with_working_directory(working_dir: PathBuf) -> Self {
+        Self {
+            working_directory: working_dir,
+        }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        let p = Path::new(path);
+        if p.is_absolute() {
+            p.to_path_buf()
+        } else {
+            self.working_directory.join(p)
+        }
+    }
+
+    fn parse_args(args: &Value) -> ToolResult<WriteFilesArgs> {
+        let args: WriteFilesArgs = serde_json::from_value(args.clone())
+            .map_err(|e| ToolError::invalid_arguments("write_files", e.to_string()))?;
+        if args.files.is_empty() {
+            return Err(ToolError::invalid_arguments(
+                "write_files",
+                "files must list at least one file",
+            ));
+        }
+        Ok(args)
+    }
+
+    /// Check every entry before anything touches the disk, reporting all
+    /// problems at once so the model can fix them in one retry.
+    async fn plan<'a>(&self, args: &'a WriteFilesArgs) -> ToolResult<Vec<PlannedWrite<'a>>> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+        let mut planned = Vec::new();
+
+        for entry in &args.files {
+            if entry.path.trim().is_empty() {
+                problems.push("an entry has an empty path".to_string());
+                continue;
+            }
+            let target = self.resolve(&entry.path);
+            if !seen.insert(target.clone()) {
+                problems.push(format!("{} is listed more than once", entry.path));
+                continue;
+            }
+
+            let previous = match fs::metadata(&target).await {
+                Ok(meta) if meta.is_dir() => {
+                    problems.push(format!("{} is a directory", entry.path));
+                    continue;
+                }
+                Ok(_) if !args.overwrite => {
+                    problems.push(format!(
+                        "{} already exists (set overwrite=true to replace it)",
+                        entry.path
+                    ));
+                    continue;
+                }
+                Ok(_) => Some(fs::read(&target).await.map_err(|_| ToolError::ReadFailed {
+                    path: target.clone(),
+                })?),
+                Err(_) => None,
+            };
+
+            if !args.create_dirs
+                && let Some(parent) = target.parent()
+                && !parent.as_os_str().is_empty()
+                && !parent.is_dir()
+            {
+                problems.push(format!(
+                    "{} has no parent directory (set create_dirs=true to create it)",
+                    entry.path
+                ));
+                continue;
+            }
+
+            planned.push(PlannedWrite {
+                entry,
+                target,
+                previous,
+            });
+        }
+
+        if !problems.is_empty() {
+            return Err(ToolError::execution_failed(format!(
+                "No files were written:\n- {}",
+                problems.join("\n- ")
+            )));
+        }
+        Ok(planned)
+    }
+
+    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+        let args = Self::parse_args(args)?;
+        let planned = self.plan(&args).await?;
+
+        let mut created_dirs = Vec::new();
+        let mut staged = Vec::new();
+        let stage_result = self.stage(&planned, &mut created_dirs, &mut staged).await;
+        if let Err(e) = stage_result {
+            discard(&staged, &created_dirs).await;
+            return Err(e);
+        }
+
+        // Every file is staged next to its target; renaming is the only step
+        // left, and a failure there restores what was already renamed.
+        for (index, (write, temp)) in planned.iter().zip(&staged).enumerate() {
+            if fs::rename(temp, &write.target).await.is_err() {
+                for done in &planned[..index] {
+                    restore(done).await;
+                }
+                discard(&staged[index..], &created_dirs).await;
+                return Err(ToolError::WriteFailed {
+                    path: write.target.clone(),
+                });
+            }
+        }
+
+        let mut summary = format!("Wrote {} files:\n", planned.len());
+        for write in &planned {
+            let verb = if write.previous.is_some() {
+                "overwrote"
+            } else {
+                "created"
+            };
+            summary.push_str(&format!(
+                "  {} {} ({} lines)\n",
+                verb,
+                write.entry.path,
+                write.entry.content.lines().count()
+            ));
+        }
+        Ok(summary)
+    }
+
+    /// Write each entry's content to a temporary file beside its target,
+    /// creating missing parent directories when allowed.
+    async fn stage(
+        &self,
+        planned: &[PlannedWrite<'_>],
+        created_dirs: &mut Vec<PathBuf>,
+        staged: &mut Vec<PathBuf>,
+    ) -> ToolResult<()> {
+        for write in planned {
+            let parent = write
+                .target
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.working_directory.clone());
+            if !parent.is_dir() {
+                let mut missing = Vec::new();
+                let mut dir = parent.as_path();
+                while !dir.as_os_str().is_empty() && !dir.exists() {
+                    missing.push(dir.to_path_buf());
+                    dir = dir.parent().unwrap_or(Path::new(""));
+                }
+                fs::create_dir_all(&parent)
+                    .await
+                    .map_err(|_| ToolError::WriteFailed {
+                        path: write.target.clone(),
+                    })?;
+                created_dirs.extend(missing);
+            }
+
+            let file_name = write
+                .target
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let temp = parent.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+            fs::write(&temp, &write.entry.content)
+                .await
+                .map_err(|_| ToolError::WriteFailed {
+                    path: write.target.clone(),
+                })?;
+            staged.push(temp);
+        }
+        Ok(())
+    }
+}
+
+/// Put a renamed target back the way it was.
+async fn restore(write: &PlannedWrite<'_>) {
+    match &write.previous {
+        Some(previous) => {
+            let _ = fs::write(&write.target, previous).await;
+        }
+        None => {
+            let _ = fs::remove_file(&write.target).await;
+        }
+    }
+}
+
+/// Remove staged temporary files and the directories created for them,
+/// deepest first; a directory that isn't empty is left alone.
+async fn discard(staged: &[PathBuf], created_dirs: &[PathBuf]) {
+    for temp in staged {
+        let _ = fs::remove_file(temp).await;
+    }
+    let mut dirs = created_dirs.to_vec();
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.components().count()));
+    for dir in dirs {
+        let _ = fs::remove_dir(&dir).await;
+    }
+}
+
+#[async_trait]
+impl Tool for WriteFilesTool {
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args).await
+    }
+
+    fn name(&self) -> &'static str {
+        "write_files"
+    }
+
+    fn phrasing(&self) -> CategoryPhrasing {
+        phrasing::EDIT
+    }
+
+    fn display_name(&self) -> &'static str {
+        "write files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create several files in one call. Either every file is written or none is.\n\n\
+        Usage:\n\
+        - Existing files are refused unless overwrite=true; read them first if you do overwrite\n\
+        - Use create_dirs=true to create missing parent directories\n\
+        - If any path is invalid, nothing is written and every problem is reported\n\n\
+        When to use:\n\
+        - Scaffolding a feature, module or project that needs several new files\n\n\
+        When NOT to use:\n\
+        - Changing existing files - use edit_file instead\n\
+        - Writing a single file - use write_file instead"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "files": {
+                    "type": "array",
+                    "minItems": 1,
+                    "description": "The files to write, each with its complete content.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "path": {
+                                "type": "string",
+                                "minLength": 1,
+                                "description": "The path to the file. Example: \"src/feature/mod.rs\""
+                            },
+                            "content": {
+                                "type": "string",
+                                "description": "The complete content of the file."
+                            }
+                        },
+                        "required": ["path", "content"]
+                    }
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, replace files that already exist. Otherwise an existing file fails the whole call."
+                },
+                "create_dirs": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, create missing parent directories."
+                }
+            },
+            "required": ["files"]
+        })
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        match Self::parse_args(args) {
+            Ok(args) if args.files.len() == 1 => format!("WriteFiles({})", args.files[0].path),
+            Ok(args) => format!("WriteFiles({} files)", args.files.len()),
+            Err(_) => "WriteFiles(?)".to_string(),
+        }
+    }
+
+    fn result_summary(&self, result: &str) -> String {
+        result
+            .lines()
+            .next()
+            .map(|line| line.trim_end_matches(':').to_string())
+            .unwrap_or_else(|| "Files written".to_string())
+    }
+
+    async fn generate_preview(&self, args: &Value) -> Option<String> {
+        let args = Self::parse_args(args).ok()?;
+        let mut preview = String::new();
+        for entry in &args.files {
+            let old = fs::read_to_string(self.resolve(&entry.path)).await.ok();
+            preview.push_str(&file_diff(&entry.path, old.as_deref(), &entry.content));
+        }
+        Some(preview)
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_destructive()
+            .with_pattern_matcher(Arc::new(AllPathsMatcher))
+            .with_display_name("Write files")
+            .build()
+            .expect("Failed to build WriteFilesTool permission descriptor")
+    }
+
+    /// The target lists every path, one per line, so stored rules and the
+    /// sandbox check see each file.
+    fn describe_permission_for_call(
+        &self,
+        _target: Option<&str>,
+        args: &Value,
+    ) -> ToolPermissionDescriptor {
+        let Ok(args) = Self::parse_args(args) else {
+            return self.describe_permission(None);
+        };
+        let paths: Vec<String> = args.files.iter().map(|f| f.path.clone()).collect();
+        ToolPermissionBuilder::new(self, paths.join("\n"))
+            .into_destructive()
+            .with_pattern_matcher(Arc::new(AllPathsMatcher))
+            .with_display_name("Write files")
+            .with_approval_prompt(format!("Can I write these {} files?", paths.len()))
+            .with_command_breakdown(paths)
+            .disallow_project_wide_trust()
+            .build()
+            .expect("Failed to build WriteFilesTool permission descriptor")
+    }
+}
+
+impl Default for WriteFilesTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_every_file_and_lists_them() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFilesTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({
+            "files": [
+                { "path": "feature/mod.rs", "content": "mod api;\n" },
+                { "path": "feature/api.rs", "content": "pub fn a() {}\npub fn b() {}\n" }
+            ],
+            "create_dirs": true
+        });
+        let result = tool.execute(&args, &context()).await.unwrap();
+
+        assert_eq!(
+            result,
+            "Wrote 2 files:\n  created feature/mod.rs (1 lines)\n  created feature/api.rs (2 lines)\n"
+        );
+        assert_eq!(tool.result_summary(&result), "Wrote 2 files");
+        let api = fs::read_to_string(temp_dir.path().join("feature/api.rs"))
+            .await
+            .unwrap();
+        assert_eq!(api, "pub fn a() {}\npub fn b() {}\n");
+    }
+
+    #[tokio::test]
+    async fn one_bad_entry_writes_nothing() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFilesTool::with_working_directory(temp_dir.path().to_path_buf());
+        fs::create_dir(temp_dir.path().join("taken")).await.unwrap();
+
+        let args = json!({
+            "files": [
+                { "path": "new/a.rs", "content": "a" },
+                { "path": "taken", "content": "b" }
+            ],
+            "create_dirs": true
+        });
+        let err = tool.execute(&args, &context()).await.unwrap_err();
+
+        assert!(err.to_string().contains("taken is a directory"));
+        assert!(!temp_dir.path().join("new").exists());
+    }
+
+    #[tokio::test]
+    async fn failure_while_staging_rolls_back_earlier_files() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFilesTool::with_working_directory(temp_dir.path().to_path_buf());
+        // A file where the second entry needs a directory: validation can't
+        // see it coming, so creating the directory fails mid-write.
+        fs::write(temp_dir.path().join("blocker"), "")
+            .await
+            .unwrap();
+
+        let args = json!({
+            "files": [
+                { "path": "src/a.rs", "content": "a" },
+                { "path": "blocker/b.rs", "content": "b" }
+            ],
+            "create_dirs": true
+        });
+        assert!(tool.execute(&args, &context()).await.is_err());
+
+        assert!(!temp_dir.path().join("src").exists());
+        let mut entries = fs::read_dir(temp_dir.path()).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+        assert_eq!(names, vec!["blocker"]);
+    }
+
+    #[tokio::test]
+    async fn existing_files_are_refused_without_overwrite() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFilesTool::with_working_directory(temp_dir.path().to_path_buf());
+        fs::write(temp_dir.path().join("keep.txt"), "original")
+            .await
+            .unwrap();
+
+        let args = json!({
+            "files": [
+                { "path": "fresh.txt", "content": "new" },
+                { "path": "keep.txt", "content": "replaced" }
+            ]
+        });
+        let err = tool.execute(&args, &context()).await.unwrap_err();
+        assert!(err.to_string().contains("keep.txt already exists"));
+        assert!(!temp_dir.path().join("fresh.txt").exists());
+        let kept = fs::read_to_string(temp_dir.path().join("keep.txt"))
+            .await
+            .unwrap();
+        assert_eq!(kept, "original");
+
+        let args = json!({
+            "files": [{ "path": "keep.txt", "content": "replaced" }],
+            "overwrite": true
+        });
+        let result = tool.execute(&args, &context()).await.unwrap();
+        assert!(result.contains("overwrote keep.txt"));
+        let replaced = fs::read_to_string(temp_dir.path().join("keep.txt"))
+            .await
+            .unwrap();
+        assert_eq!(replaced, "replaced");
+    }
+
+    #[tokio::test]
+    async fn preview_combines_every_file() {
+        let temp_dir = tempdir().unwrap();
+        let tool = WriteFilesTool::with_working_directory(temp_dir.path().to_path_buf());
+
+        let args = json!({
+            "files": [
+                { "path": "a.txt", "content": "a\n" },
+                { "path": "b.txt", "content": "b\n" }
+            ]
+        });
+        let preview = tool.generate_preview(&args).await.unwrap();
+
+        assert!(preview.contains("+++ b/a.txt"));
+        assert!(preview.contains("+++ b/b.txt"));
+    }
+
+    #[test]
+    fn stored_rules_must_cover_every_path() {
+        let tool = WriteFilesTool::new();
+        let args = json!({
+            "files": [
+                { "path": "src/a.rs", "content": "" },
+                { "path": "docs/b.md", "content": "" }
+            ]
+        });
+        let descriptor = tool.describe_permission_for_call(None, &args);
+
+        assert!(descriptor.matches_pattern("*"));
+        assert!(!descriptor.matches_pattern("src/*"));
+    }
+}
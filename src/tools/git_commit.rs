@@ -0,0 +1,467 @@
+use crate::backends::LlmBackend;
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::file_ops::diff_preview::{run_git, tracked_diff, working_tree_diff};
+use crate::tools::{Tool, ToolError, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Longest staged diff, in characters, sent to the backend when drafting a
+/// commit message.
+const MAX_DRAFT_DIFF_CHARS: usize = 12_000;
+
+/// Stages changes and commits them. When no message is given, a backend
+/// (usually the cheaper title model) drafts a conventional-commit message
+/// from the staged diff.
+pub struct GitCommitTool {
+    working_directory: PathBuf,
+    message_backend: Option<Arc<dyn LlmBackend>>,
+}
+
+#[derive(Deserialize)]
+struct GitCommitArgs {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+}
+
+impl GitCommitArgs {
+    fn message(&self) -> Option<&str> {
+        self.message
+            .as_deref()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+    }
+
+    fn pathspecs(&self) -> Vec<&str> {
+        self.paths.iter().map(String::as_str).collect()
+    }
+}
+
+impl GitCommitTool {
+    pub fn new(working_directory: PathBuf) -> Self {
+        Self {
+            working_directory,
+            message_backend: None,
+        }
+    }
+
+    /// Backend asked to draft a message when the call doesn't provide one.
+    pub fn with_message_backend(mut self, backend: Arc<dyn LlmBackend>) -> Self {
+        self.message_backend = Some(backend);
+        self
+    }
+
+    fn parse_args(args: &Value) -> ToolResult<GitCommitArgs> {
+        serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
+            tool: "git_commit".to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    async fn git(&self, args: &[&str]) -> ToolResult<String> {
+        run_git(&self.working_directory, args)
+            .await
+            .map_err(|e| ToolError::execution_failed(e.to_string()))
+    }
+
+    async fn in_repository(&self) -> bool {
+        self.git(&["rev-parse", "--is-inside-work-tree"])
+            .await
+            .is_ok()
+    }
+
+    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+        let args = Self::parse_args(args)?;
+        if !self.in_repository().await {
+            return Err(ToolError::execution_failed(
+                "Not inside a git repository; nothing to commit",
+            ));
+        }
+
+        let pathspecs = args.pathspecs();
+        if pathspecs.is_empty() {
+            self.git(&["add", "--update"]).await?;
+        } else {
+            let mut add = vec!["add", "--"];
+            add.extend_from_slice(&pathspecs);
+            self.git(&add).await?;
+        }
+
+        // With paths, `git commit -- <paths>` commits only those, even if
+        // other changes were staged earlier, so that's all the diff covers.
+        let mut staged_args = vec!["diff", "--cached", "--no-color", "--no-ext-diff", "--"];
+        staged_args.extend_from_slice(&pathspecs);
+        let staged = self.git(&staged_args).await?;
+        if staged.trim().is_empty() {
+            return Err(ToolError::execution_failed("Nothing to commit"));
+        }
+        let files = staged
+            .lines()
+            .filter(|line| line.starts_with("diff --git "))
+            .count();
+
+        let message = match args.message() {
+            Some(message) => message.to_string(),
+            None => self.draft_message(&staged).await?,
+        };
+
+        let mut commit = vec!["commit", "--quiet", "--message", message.as_str()];
+        if !pathspecs.is_empty() {
+            commit.push("--");
+            commit.extend_from_slice(&pathspecs);
+        }
+        self.git(&commit).await?;
+
+        let hash = self.git(&["rev-parse", "--short", "HEAD"]).await?;
+        Ok(format!(
+            "Committed {} ({} file{}): {}",
+            hash.trim(),
+            files,
+            if files == 1 { "" } else { "s" },
+            message.lines().next().unwrap_or_default()
+        ))
+    }
+
+    async fn draft_message(&self, staged_diff: &str) -> ToolResult<String> {
+        let backend = self
+            .message_backend
+            .as_ref()
+            .ok_or_else(|| ToolError::InvalidArguments {
+                tool: "git_commit".to_string(),
+                message: "message is required: no backend is configured to draft one".to_string(),
+            })?;
+
+        let diff = match staged_diff.char_indices().nth(MAX_DRAFT_DIFF_CHARS) {
+            Some((cut, _)) => format!("{}\n[diff truncated]", &staged_diff[..cut]),
+            None => staged_diff.to_string(),
+        };
+        let prompt = format!(
+            "Write a conventional commit message (`type(scope): summary`, e.g. \
+             `fix(parser): handle empty input`) for the staged diff below. Keep the \
+             summary under 72 characters and add a short body only if the change needs \
+             explaining.\n\nReply with the commit message only.\n\n{}",
+            diff
+        );
+
+        let reply = backend.send_message(&prompt).await.map_err(|e| {
+            ToolError::execution_failed(format!("Failed to draft a commit message: {}", e))
+        })?;
+        let message = strip_code_fence(&reply);
+        if message.is_empty() {
+            return Err(ToolError::execution_failed(
+                "The backend drafted an empty commit message; pass message explicitly",
+            ));
+        }
+        Ok(message)
+    }
+}
+
+/// Models like to wrap the message in a code block despite being asked not to.
+fn strip_code_fence(reply: &str) -> String {
+    let trimmed = reply.trim();
+    let Some(inner) = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+    else {
+        return trimmed.to_string();
+    };
+    // Drop the info string (e.g. ```text) on the opening line.
+    let inner = inner.split_once('\n').map_or("", |(_, body)| body);
+    inner.trim().to_string()
+}
+
+#[async_trait]
+impl Tool for GitCommitTool {
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args).await
+    }
+
+    fn name(&self) -> &'static str {
+        "git_commit"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Commit"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stage changes and create a git commit.\n\n\
+        Usage:\n\
+        - paths lists files or directories to commit, including new ones. Only\n  \
+          those are committed, even if other changes are already staged\n\
+        - Without paths, every change to tracked files is committed; new files\n  \
+          are left out\n\
+        - message is the commit message. Omit it to have one drafted from the\n  \
+          staged diff in conventional-commit style\n\
+        - Returns the new commit's hash\n\
+        - Never pushes"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Files or directories to commit. Defaults to all tracked changes."
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Commit message. Drafted from the diff when omitted."
+                }
+            }
+        })
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        match Self::parse_args(args) {
+            Ok(parsed) if !parsed.paths.is_empty() => {
+                format!("Commit({})", parsed.paths.join(", "))
+            }
+            _ => "Commit(all tracked changes)".to_string(),
+        }
+    }
+
+    async fn generate_preview(&self, args: &Value) -> Option<String> {
+        let parsed = Self::parse_args(args).ok()?;
+        if !self.in_repository().await {
+            return None;
+        }
+        let pathspecs = parsed.pathspecs();
+        // New files only get committed when named.
+        let diff = if pathspecs.is_empty() {
+            tracked_diff(&self.working_directory, &[]).await
+        } else {
+            working_tree_diff(&self.working_directory, &pathspecs).await
+        };
+        diff.ok().filter(|d| !d.trim().is_empty())
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_destructive()
+            .build()
+            .expect("Failed to build GitCommitTool permission descriptor")
+    }
+
+    fn describe_permission_for_call(
+        &self,
+        _target: Option<&str>,
+        args: &Value,
+    ) -> ToolPermissionDescriptor {
+        let Ok(parsed) = Self::parse_args(args) else {
+            return self.describe_permission(None);
+        };
+        let what = if parsed.paths.is_empty() {
+            "all changes to tracked files".to_string()
+        } else {
+            parsed.paths.join(", ")
+        };
+        let summary = match parsed.message() {
+            Some(message) => message.lines().next().unwrap_or_default().to_string(),
+            None => "(message drafted from the diff)".to_string(),
+        };
+
+        ToolPermissionBuilder::new(self, "*")
+            .into_destructive()
+            .with_approval_title(" Commit ")
+            .with_approval_prompt(format!("Can I commit {}?", what))
+            .with_command_summary(summary)
+            .with_persistent_approval("don't ask me again for commits in this project")
+            .build()
+            .expect("Failed to build GitCommitTool permission descriptor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Conversation;
+    use crate::backends::{LlmError, LlmResponse};
+    use crate::tools::ToolRegistry;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    struct DraftingBackend(&'static str);
+
+    #[async_trait]
+    impl LlmBackend for DraftingBackend {
+        async fn send_message(&self, _message: &str) -> anyhow::Result<String> {
+            Ok(self.0.to_string())
+        }
+
+        async fn send_message_with_tools(
+            &self,
+            _conversation: &Conversation,
+            _tools: &ToolRegistry,
+        ) -> Result<LlmResponse, LlmError> {
+            unimplemented!()
+        }
+
+        fn backend_name(&self) -> &str {
+            "drafting"
+        }
+
+        fn model_name(&self) -> &str {
+            "drafting-model"
+        }
+    }
+
+    fn git(path: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// A repo with one commit and a local identity, so commits made by the
+    /// tool don't depend on the machine's git config.
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-b", "main"]);
+        git(dir.path(), &["config", "user.name", "Test"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "two\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Initial commit"]);
+        dir
+    }
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn commits_named_paths_and_returns_the_hash() {
+        let dir = repo();
+        std::fs::write(dir.path().join("a.txt"), "uno\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "dos\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "fresh\n").unwrap();
+        let tool = GitCommitTool::new(dir.path().to_path_buf());
+
+        let args = json!({ "paths": ["a.txt", "new.txt"], "message": "Update a, add new" });
+        let result = tool.execute(&args, &context()).await.unwrap();
+
+        let hash = git(dir.path(), &["rev-parse", "--short", "HEAD"]);
+        assert_eq!(
+            result,
+            format!("Committed {} (2 files): Update a, add new", hash)
+        );
+        let committed = git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]);
+        assert_eq!(committed, "a.txt\nnew.txt");
+        assert_eq!(git(dir.path(), &["status", "--short"]), "M b.txt");
+    }
+
+    #[tokio::test]
+    async fn commits_all_tracked_changes_without_paths() {
+        let dir = repo();
+        std::fs::write(dir.path().join("a.txt"), "uno\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "dos\n").unwrap();
+        std::fs::write(dir.path().join("untracked.txt"), "x\n").unwrap();
+        let tool = GitCommitTool::new(dir.path().to_path_buf());
+
+        tool.execute(&json!({ "message": "Update both" }), &context())
+            .await
+            .unwrap();
+
+        let committed = git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]);
+        assert_eq!(committed, "a.txt\nb.txt");
+        assert_eq!(git(dir.path(), &["status", "--short"]), "?? untracked.txt");
+    }
+
+    #[tokio::test]
+    async fn drafts_a_message_when_none_is_given() {
+        let dir = repo();
+        std::fs::write(dir.path().join("a.txt"), "uno\n").unwrap();
+        let tool = GitCommitTool::new(dir.path().to_path_buf()).with_message_backend(Arc::new(
+            DraftingBackend("```\nfix(a): translate to Spanish\n```"),
+        ));
+
+        let result = tool.execute(&json!({}), &context()).await.unwrap();
+
+        assert!(result.ends_with(": fix(a): translate to Spanish"));
+        assert_eq!(
+            git(dir.path(), &["log", "-1", "--format=%s"]),
+            "fix(a): translate to Spanish"
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_a_message_without_a_backend() {
+        let dir = repo();
+        std::fs::write(dir.path().join("a.txt"), "uno\n").unwrap();
+        let tool = GitCommitTool::new(dir.path().to_path_buf());
+
+        let err = tool.execute(&json!({}), &context()).await.unwrap_err();
+
+        assert!(matches!(err, ToolError::InvalidArguments { .. }));
+    }
+
+    #[tokio::test]
+    async fn refuses_outside_a_repository_and_with_nothing_to_commit() {
+        let plain = TempDir::new().unwrap();
+        let tool = GitCommitTool::new(plain.path().to_path_buf());
+        let err = tool
+            .execute(&json!({ "message": "x" }), &context())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Not inside a git repository"));
+
+        let dir = repo();
+        let tool = GitCommitTool::new(dir.path().to_path_buf());
+        let err = tool
+            .execute(&json!({ "message": "x" }), &context())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Nothing to commit"));
+    }
+
+    #[tokio::test]
+    async fn preview_shows_what_will_be_committed() {
+        let dir = repo();
+        std::fs::write(dir.path().join("a.txt"), "uno\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "fresh\n").unwrap();
+        let tool = GitCommitTool::new(dir.path().to_path_buf());
+
+        let all = tool.generate_preview(&json!({})).await.unwrap();
+        assert!(all.contains("-one\n+uno\n"));
+        assert!(!all.contains("new.txt"));
+
+        let named = tool
+            .generate_preview(&json!({ "paths": ["new.txt"] }))
+            .await
+            .unwrap();
+        assert!(named.contains("+++ b/new.txt"));
+        assert!(!named.contains("a.txt"));
+    }
+
+    #[test]
+    fn permission_prompt_names_what_is_committed() {
+        let tool = GitCommitTool::new(PathBuf::from("."));
+        let args = json!({ "paths": ["src/lib.rs"], "message": "feat: add lib\n\nbody" });
+        let descriptor = tool.describe_permission_for_call(None, &args);
+
+        assert!(descriptor.is_destructive());
+        assert_eq!(descriptor.approval_prompt(), "Can I commit src/lib.rs?");
+        assert_eq!(descriptor.command_summary(), Some("feat: add lib"));
+    }
+
+    #[test]
+    fn strips_code_fences_from_drafts() {
+        assert_eq!(strip_code_fence("```text\nfeat: x\n```"), "feat: x");
+        assert_eq!(strip_code_fence("  feat: y \n"), "feat: y");
+    }
+}
@@ -0,0 +1,604 @@
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::{Tool, ToolError, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_API_BASE: &str = "https://api.github.com";
+
+/// Creates issues, pull requests and comments through the GitHub REST API.
+/// The repository defaults to the one behind the working directory's git
+/// remote.
+pub struct GitHubTool {
+    working_directory: PathBuf,
+    token: String,
+    api_base: String,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GitHubAction {
+    CreateIssue,
+    CreatePr,
+    AddComment,
+}
+
+impl GitHubAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GitHubAction::CreateIssue => "create_issue",
+            GitHubAction::CreatePr => "create_pr",
+            GitHubAction::AddComment => "add_comment",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitHubArgs {
+    action: GitHubAction,
+    #[serde(default)]
+    repo: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    head: Option<String>,
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    number: Option<u64>,
+}
+
+impl GitHubTool {
+    pub fn new(working_directory: PathBuf, token: String) -> Self {
+        Self {
+            working_directory,
+            token,
+            api_base: DEFAULT_API_BASE.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Points the tool at a GitHub Enterprise (or test) API root.
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn execute_impl(&self, args: &Value) -> ToolResult<String> {
+        let args: GitHubArgs =
+            serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
+                tool: "github".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let repo = match &args.repo {
+            Some(repo) => parse_repo_slug(repo).ok_or_else(|| ToolError::InvalidArguments {
+                tool: "github".to_string(),
+                message: format!("repo must look like owner/name, got '{}'", repo),
+            })?,
+            None => repo_from_remote(&self.working_directory)?,
+        };
+
+        let repo_info = self.check_access(&repo).await?;
+
+        let (path, payload) = match args.action {
+            GitHubAction::CreateIssue => (
+                format!("/repos/{}/issues", repo),
+                json!({
+                    "title": required(args.title, "title", args.action)?,
+                    "body": args.body.unwrap_or_default(),
+                }),
+            ),
+            GitHubAction::CreatePr => {
+                let head = match args.head {
+                    Some(head) => head,
+                    None => current_branch(&self.working_directory)?,
+                };
+                let base = args
+                    .base
+                    .or_else(|| {
+                        repo_info
+                            .get("default_branch")
+                            .and_then(Value::as_str)
+                            .map(str::to_string)
+                    })
+                    .ok_or_else(|| {
+                        ToolError::execution_failed(
+                            "Could not determine the default branch; pass base explicitly",
+                        )
+                    })?;
+                (
+                    format!("/repos/{}/pulls", repo),
+                    json!({
+                        "title": required(args.title, "title", args.action)?,
+                        "body": args.body.unwrap_or_default(),
+                        "head": head,
+                        "base": base,
+                        "draft": args.draft,
+                    }),
+                )
+            }
+            GitHubAction::AddComment => {
+                let number = args.number.ok_or_else(|| ToolError::InvalidArguments {
+                    tool: "github".to_string(),
+                    message: "add_comment requires number".to_string(),
+                })?;
+                (
+                    format!("/repos/{}/issues/{}/comments", repo, number),
+                    json!({ "body": required(args.body, "body", args.action)? }),
+                )
+            }
+        };
+
+        let (_, created) = self.request(Method::POST, &path, Some(payload)).await?;
+        let url = created
+            .get("html_url")
+            .and_then(Value::as_str)
+            .unwrap_or("(GitHub did not return a URL)");
+
+        Ok(format!("{} on {}: {}", args.action.as_str(), repo, url))
+    }
+
+    /// Fetches the repository, failing early with a clear message when the
+    /// token is rejected or a classic token lacks the `repo` scope.
+    /// Fine-grained tokens don't report scopes and are left to the API.
+    async fn check_access(&self, repo: &str) -> ToolResult<Value> {
+        let (scopes, info) = self
+            .request(Method::GET, &format!("/repos/{}", repo), None)
+            .await?;
+
+        if let Some(scopes) = scopes
+            && !has_write_scope(&scopes)
+        {
+            return Err(ToolError::execution_failed(format!(
+                "GitHub token scopes ({}) do not allow writing to {}; it needs 'repo' or 'public_repo'",
+                if scopes.trim().is_empty() {
+                    "none"
+                } else {
+                    scopes.trim()
+                },
+                repo
+            )));
+        }
+
+        Ok(info)
+    }
+
+    /// Sends a request and returns the `X-OAuth-Scopes` header (when present)
+    /// along with the JSON body.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        payload: Option<Value>,
+    ) -> ToolResult<(Option<String>, Value)> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.api_base, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", concat!("hoosh/", env!("CARGO_PKG_VERSION")));
+        if let Some(payload) = payload {
+            request = request.json(&payload);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ToolError::execution_failed(format!("GitHub request failed: {}", e)))?;
+
+        let status = response.status();
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(ToolError::execution_failed(status_error(status, &body)));
+        }
+
+        Ok((scopes, body))
+    }
+}
+
+fn required(value: Option<String>, field: &str, action: GitHubAction) -> ToolResult<String> {
+    value
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| ToolError::InvalidArguments {
+            tool: "github".to_string(),
+            message: format!("{} requires {}", action.as_str(), field),
+        })
+}
+
+fn has_write_scope(scopes: &str) -> bool {
+    scopes
+        .split(',')
+        .map(str::trim)
+        .any(|scope| scope == "repo" || scope == "public_repo")
+}
+
+fn status_error(status: StatusCode, body: &Value) -> String {
+    let message = body
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("no details");
+    match status {
+        StatusCode::UNAUTHORIZED => {
+            "GitHub rejected the token; check github_token or GH_TOKEN".to_string()
+        }
+        StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => format!(
+            "GitHub returned {} ({}); the repository may not exist or the token lacks access",
+            status.as_u16(),
+            message
+        ),
+        _ => format!("GitHub returned {}: {}", status.as_u16(), message),
+    }
+}
+
+fn parse_repo_slug(slug: &str) -> Option<String> {
+    let (owner, name) = slug.trim().split_once('/')?;
+    let name = name.trim_end_matches(".git");
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    (valid(owner) && valid(name)).then(|| format!("{}/{}", owner, name))
+}
+
+/// Extracts `owner/name` from the usual GitHub remote forms:
+/// `git@github.com:owner/name.git`, `ssh://git@github.com/owner/name` and
+/// `https://github.com/owner/name.git`. Remotes on other hosts (GitLab,
+/// Gitea, ...) give `None`, since the tool only talks to github.com.
+fn parse_remote_url(url: &str) -> Option<String> {
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = host.split_once(':').map_or(host, |(host, _)| host);
+    if !host.eq_ignore_ascii_case("github.com") {
+        return None;
+    }
+    parse_repo_slug(path.trim_end_matches('/'))
+}
+
+fn repo_from_remote(working_directory: &Path) -> ToolResult<String> {
+    let repo = git2::Repository::discover(working_directory).map_err(|_| {
+        ToolError::execution_failed("Not inside a git repository; pass repo as owner/name")
+    })?;
+
+    let remote = repo.find_remote("origin").ok().or_else(|| {
+        let names = repo.remotes().ok()?;
+        let first = names.iter().flatten().next()?.to_string();
+        repo.find_remote(&first).ok()
+    });
+
+    remote
+        .as_ref()
+        .and_then(|r| r.url())
+        .and_then(parse_remote_url)
+        .ok_or_else(|| {
+            ToolError::execution_failed(
+                "Could not infer a GitHub repository from the git remote; pass repo as owner/name",
+            )
+        })
+}
+
+fn current_branch(working_directory: &Path) -> ToolResult<String> {
+    git2::Repository::discover(working_directory)
+        .ok()
+        .and_then(|repo| {
+            let head = repo.head().ok()?;
+            head.is_branch()
+                .then(|| head.shorthand().map(str::to_string))
+                .flatten()
+        })
+        .ok_or_else(|| {
+            ToolError::execution_failed(
+                "Could not determine the current branch; pass head explicitly",
+            )
+        })
+}
+
+#[async_trait]
+impl Tool for GitHubTool {
+    async fn execute(&self, args: &Value, _context: &ToolExecutionContext) -> ToolResult<String> {
+        self.execute_impl(args).await
+    }
+
+    fn name(&self) -> &'static str {
+        "github"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create GitHub issues, pull requests and comments.\n\n\
+        Usage:\n\
+        - action is one of create_issue, create_pr, add_comment\n\
+        - repo defaults to the repository behind the git remote (origin)\n\
+        - create_pr pushes nothing: push the branch first. head defaults to the\n  \
+          current branch and base to the repository's default branch\n\
+        - add_comment works on both issues and pull requests\n\
+        - Returns the URL of the created resource"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create_issue", "create_pr", "add_comment"],
+                    "description": "The operation to perform"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name. Defaults to the git remote."
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Title for create_issue and create_pr"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Markdown body. Required for add_comment."
+                },
+                "head": {
+                    "type": "string",
+                    "description": "create_pr: branch with the changes. Defaults to the current branch."
+                },
+                "base": {
+                    "type": "string",
+                    "description": "create_pr: branch to merge into. Defaults to the repository's default branch."
+                },
+                "draft": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "create_pr: open the pull request as a draft"
+                },
+                "number": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "add_comment: issue or pull request number"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        match args.get("action").and_then(Value::as_str) {
+            Some(action) => format!("GitHub({})", action),
+            None => "GitHub(?)".to_string(),
+        }
+    }
+
+    fn result_summary(&self, result: &str) -> String {
+        result
+            .rsplit(": ")
+            .next()
+            .unwrap_or("Completed")
+            .to_string()
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_destructive()
+            .disallow_project_wide_trust()
+            .build()
+            .expect("Failed to build GitHubTool permission descriptor")
+    }
+
+    /// Permissions are keyed on `action:owner/name`, and the prompt spells
+    /// out both, since everything this tool does is visible to others.
+    fn describe_permission_for_call(
+        &self,
+        _target: Option<&str>,
+        args: &Value,
+    ) -> ToolPermissionDescriptor {
+        let Ok(parsed) = serde_json::from_value::<GitHubArgs>(args.clone()) else {
+            return self.describe_permission(None);
+        };
+        let repo = parsed
+            .repo
+            .as_deref()
+            .and_then(parse_repo_slug)
+            .or_else(|| repo_from_remote(&self.working_directory).ok())
+            .unwrap_or_else(|| "unknown repository".to_string());
+
+        let what = match parsed.action {
+            GitHubAction::CreateIssue => "create an issue".to_string(),
+            GitHubAction::CreatePr => "open a pull request".to_string(),
+            GitHubAction::AddComment => match parsed.number {
+                Some(number) => format!("comment on #{}", number),
+                None => "add a comment".to_string(),
+            },
+        };
+        let target = format!("{}:{}", parsed.action.as_str(), repo);
+
+        ToolPermissionBuilder::new(self, target.clone())
+            .into_destructive()
+            .disallow_project_wide_trust()
+            .with_approval_title(" GitHub ")
+            .with_approval_prompt(format!("Can I {} on {}?", what, repo))
+            .with_command_summary(parsed.title.unwrap_or_default())
+            .with_persistent_approval(format!(
+                "don't ask me again for {} on {}",
+                parsed.action.as_str(),
+                repo
+            ))
+            .with_suggested_pattern(target)
+            .build()
+            .expect("Failed to build GitHubTool permission descriptor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn context() -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        }
+    }
+
+    fn tool(server: &MockServer) -> GitHubTool {
+        GitHubTool::new(PathBuf::from("."), "test-token".to_string()).with_api_base(server.uri())
+    }
+
+    async fn mount_repo(server: &MockServer, scopes: &str) {
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/widgets"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-oauth-scopes", scopes)
+                    .set_body_json(json!({ "default_branch": "main" })),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn parses_common_remote_forms() {
+        for url in [
+            "git@github.com:octo/widgets.git",
+            "ssh://git@github.com/octo/widgets",
+            "https://github.com/octo/widgets.git",
+            "https://github.com/octo/widgets/",
+        ] {
+            assert_eq!(
+                parse_remote_url(url).as_deref(),
+                Some("octo/widgets"),
+                "{url}"
+            );
+        }
+        assert_eq!(parse_remote_url("/local/path/repo"), None);
+    }
+
+    #[test]
+    fn ignores_remotes_on_other_hosts() {
+        for url in [
+            "git@gitlab.com:octo/widgets.git",
+            "https://gitea.example.com/octo/widgets.git",
+            "ssh://git@github.com.evil.net/octo/widgets",
+        ] {
+            assert_eq!(parse_remote_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn recognises_write_scopes() {
+        assert!(has_write_scope("gist, repo, workflow"));
+        assert!(has_write_scope("public_repo"));
+        assert!(!has_write_scope("read:org, gist"));
+        assert!(!has_write_scope(""));
+    }
+
+    #[tokio::test]
+    async fn creates_issue_and_returns_url() {
+        let server = MockServer::start().await;
+        mount_repo(&server, "repo").await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octo/widgets/issues"))
+            .and(header("authorization", "Bearer test-token"))
+            .and(body_partial_json(json!({ "title": "Flaky test" })))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(
+                    json!({ "html_url": "https://github.com/octo/widgets/issues/7" }),
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let args =
+            json!({ "action": "create_issue", "repo": "octo/widgets", "title": "Flaky test" });
+        let result = tool(&server).execute(&args, &context()).await.unwrap();
+
+        assert!(result.ends_with("https://github.com/octo/widgets/issues/7"));
+    }
+
+    #[tokio::test]
+    async fn create_pr_defaults_base_to_default_branch() {
+        let server = MockServer::start().await;
+        mount_repo(&server, "repo").await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octo/widgets/pulls"))
+            .and(body_partial_json(json!({ "head": "fix", "base": "main" })))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(json!({ "html_url": "https://github.com/octo/widgets/pull/8" })),
+            )
+            .mount(&server)
+            .await;
+
+        let args = json!({
+            "action": "create_pr",
+            "repo": "octo/widgets",
+            "title": "Fix it",
+            "head": "fix"
+        });
+        let result = tool(&server).execute(&args, &context()).await.unwrap();
+
+        assert!(result.ends_with("https://github.com/octo/widgets/pull/8"));
+    }
+
+    #[tokio::test]
+    async fn refuses_token_without_repo_scope() {
+        let server = MockServer::start().await;
+        mount_repo(&server, "read:org").await;
+
+        let args =
+            json!({ "action": "add_comment", "repo": "octo/widgets", "number": 3, "body": "hi" });
+        let err = tool(&server).execute(&args, &context()).await.unwrap_err();
+
+        assert!(err.to_string().contains("'repo' or 'public_repo'"));
+    }
+
+    #[tokio::test]
+    async fn add_comment_requires_number() {
+        let server = MockServer::start().await;
+        mount_repo(&server, "repo").await;
+
+        let args = json!({ "action": "add_comment", "repo": "octo/widgets", "body": "hi" });
+        let err = tool(&server).execute(&args, &context()).await.unwrap_err();
+
+        assert!(matches!(err, ToolError::InvalidArguments { .. }));
+    }
+
+    #[test]
+    fn permission_prompt_names_action_and_repo() {
+        let tool = GitHubTool::new(PathBuf::from("."), "t".to_string());
+        let args = json!({ "action": "create_pr", "repo": "octo/widgets", "title": "Fix it" });
+        let descriptor = tool.describe_permission_for_call(None, &args);
+
+        assert_eq!(descriptor.target(), "create_pr:octo/widgets");
+        assert_eq!(
+            descriptor.approval_prompt(),
+            "Can I open a pull request on octo/widgets?"
+        );
+        assert!(descriptor.is_destructive());
+        assert!(!descriptor.allow_project_wide_trust());
+    }
+}
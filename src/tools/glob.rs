@@ -1,15 +1,18 @@
 use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::tools::ignore_rules::walk_builder;
 use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
 use async_trait::async_trait;
 use glob::Pattern;
-use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
+use std::path::Path;
 
 #[derive(Debug, Deserialize)]
 struct GlobArgs {
     pattern: String,
     path: Option<String>,
+    #[serde(default)]
+    include_ignored: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -46,14 +49,7 @@ impl GlobTool {
         let search_path = args.path.as_deref().unwrap_or(".");
         let mut matches = Vec::new();
 
-        // Use WalkBuilder which respects .gitignore and other ignore files
-        let walker = WalkBuilder::new(search_path)
-            .follow_links(false)
-            .git_ignore(true) // Respect .gitignore files
-            .git_global(true) // Respect global gitignore
-            .git_exclude(true) // Respect .git/info/exclude
-            .hidden(false) // Don't automatically skip hidden files (let .gitignore handle it)
-            .build();
+        let walker = walk_builder(Path::new(search_path), args.include_ignored).build();
 
         for entry in walker.filter_map(|e| e.ok()) {
             if !entry.file_type().is_some_and(|ft| ft.is_file()) {
@@ -100,6 +96,10 @@ impl Tool for GlobTool {
         phrasing::FIND
     }
 
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
     fn display_name(&self) -> &'static str {
         "Glob"
     }
@@ -109,7 +109,7 @@ impl Tool for GlobTool {
         Usage:\n\
         - Use glob patterns like \"**/*.rs\" or \"src/**/*.ts\" to find files\n\
         - Returns matching file paths sorted by modification time (most recent first)\n\
-        - Automatically respects .gitignore rules\n\n\
+        - Automatically respects .gitignore rules; set include_ignored to search ignored paths too\n\n\
         Pattern syntax:\n\
         - * matches any characters except path separator\n\
         - ** matches any characters including path separators (recursive)\n\
@@ -142,6 +142,11 @@ impl Tool for GlobTool {
                 "path": {
                     "type": "string",
                     "description": "Optional: directory to search in. Defaults to current working directory if omitted. Examples: \"src\", \"tests\", \"src/components\". IMPORTANT: Simply omit this field for default behavior - do not set to null or undefined."
+                },
+                "include_ignored": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "If true, also match files excluded by .gitignore (e.g. target/, node_modules/). .git/ is skipped unless this is set."
                 }
             },
             "required": ["pattern"]
@@ -325,4 +330,39 @@ mod tests {
         let result = tool.execute(&args, &context).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_glob_respects_gitignore_and_skips_git_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+        std::fs::write(temp_dir.path().join("target/out.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+
+        let tool = GlobTool::new();
+        let context = ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+        let path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = tool
+            .execute(&json!({ "pattern": "**/*", "path": path }), &context)
+            .await
+            .unwrap();
+        assert!(result.contains("main.rs"));
+        assert!(!result.contains("out.rs"));
+        assert!(!result.contains("/.git/"));
+
+        let result = tool
+            .execute(
+                &json!({ "pattern": "**/*.rs", "path": path, "include_ignored": true }),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(result.contains("out.rs"));
+    }
 }
@@ -266,6 +266,10 @@ impl Tool for GrepTool {
         phrasing::SEARCH
     }
 
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
     fn display_name(&self) -> &'static str {
         "Grep"
     }
@@ -0,0 +1,50 @@
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+
+/// Directories skipped when there is no git repository to supply ignore rules.
+pub const BUILTIN_IGNORED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "__pycache__",
+    ".venv",
+    "dist",
+    "build",
+];
+
+pub fn is_in_git_repo(path: &Path) -> bool {
+    let path = path.canonicalize().unwrap_or_else(|_| PathBuf::from(path));
+    path.ancestors().any(|dir| dir.join(".git").exists())
+}
+
+/// Build a walker for `root` that honors `.gitignore` (including nested and
+/// parent ignore files, the global excludes file and `.git/info/exclude`) and
+/// never descends into `.git`. Outside a git repository the built-in ignore set
+/// is applied instead. `include_ignored` disables all of this.
+pub fn walk_builder(root: &Path, include_ignored: bool) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .follow_links(false)
+        .hidden(false)
+        .ignore(!include_ignored)
+        .parents(!include_ignored)
+        .git_ignore(!include_ignored)
+        .git_global(!include_ignored)
+        .git_exclude(!include_ignored);
+
+    if !include_ignored {
+        let use_builtin = !is_in_git_repo(root);
+        builder.filter_entry(move |entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            let name = entry.file_name().to_string_lossy();
+            if name == ".git" {
+                return false;
+            }
+            let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+            !(use_builtin && is_dir && BUILTIN_IGNORED_DIRS.contains(&name.as_ref()))
+        });
+    }
+
+    builder
+}
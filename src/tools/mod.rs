@@ -173,6 +173,18 @@ pub trait Tool: Send + Sync {
         false
     }
 
+    /// Run this tool in dry-run mode even though it isn't read-only,
+    /// because it applies dry-run to whatever it does itself.
+    fn runs_in_dry_run(&self) -> bool {
+        false
+    }
+
+    /// Whether running the call again after a failure can't repeat a side
+    /// effect the failed attempt already had. Only such tools are retried.
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+
     /// Get the complete tool schema in OpenAI function calling format
     fn tool_schema(&self) -> Value {
         json!({
@@ -189,10 +201,15 @@ pub trait Tool: Send + Sync {
 pub mod bash;
 pub mod error;
 pub mod file_ops;
+pub mod git_commit;
+#[cfg(feature = "github")]
+pub mod github;
 pub mod glob;
 pub mod grep;
+pub mod ignore_rules;
 pub mod provider;
 pub mod readonly_provider;
+pub mod recall;
 pub mod subagent_registry;
 pub mod task_tool;
 pub mod task_tool_provider;
@@ -201,13 +218,20 @@ pub mod todo_write;
 
 pub use bash::BashTool;
 pub use error::{ToolError, ToolResult};
-pub use file_ops::{EditFileTool, ListDirectoryTool, ReadFileTool, WriteFileTool};
+pub use file_ops::{
+    AppendFileTool, EditFileTool, ListDirectoryTool, MoveFileTool, ReadFileTool, WriteFileTool,
+    WriteFilesTool,
+};
+pub use git_commit::GitCommitTool;
+#[cfg(feature = "github")]
+pub use github::GitHubTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
 pub use provider::{BuiltinToolProvider, ToolProvider};
 pub use readonly_provider::ReadOnlyToolProvider;
+pub use recall::RecallTool;
 pub use subagent_registry::create_subagent_registry;
-pub use task_tool::TaskTool;
+pub use task_tool::{SubagentBackend, TaskTool};
 pub use task_tool_provider::TaskToolProvider;
 pub use todo_state::TodoState;
 pub use todo_write::TodoWriteTool;
@@ -217,6 +241,13 @@ pub use todo_write::TodoWriteTool;
 pub struct ToolRegistry {
     tools: HashMap<&'static str, Arc<dyn Tool>>,
     providers: Vec<Arc<dyn ToolProvider>>,
+    /// Schemas of `tools`, rebuilt whenever the tool set changes so each
+    /// request doesn't recompute them.
+    schemas: Vec<Value>,
+    /// Provider failures not yet shown to the user. Registration can run
+    /// while the TUI owns the terminal, so these are surfaced as status lines
+    /// by the caller rather than printed.
+    warnings: Vec<String>,
 }
 
 impl ToolRegistry {
@@ -224,6 +255,8 @@ impl ToolRegistry {
         Self {
             tools: HashMap::new(),
             providers: Vec::new(),
+            schemas: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -235,20 +268,48 @@ impl ToolRegistry {
 
     /// Add a provider and register its tools
     pub fn add_provider(&mut self, provider: Arc<dyn ToolProvider>) {
-        // Get tools from provider and register them
-        for tool in provider.provide_tools() {
+        self.register_provider_tools(provider.as_ref());
+        self.providers.push(provider);
+        self.rebuild_schemas();
+    }
+
+    /// Register the tools `provider` offers. A provider that panics is
+    /// reported and contributes no tools instead of taking the session down.
+    fn register_provider_tools(&mut self, provider: &dyn ToolProvider) {
+        let tools = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            crate::session_files::crash::recoverable(|| provider.provide_tools())
+        })) {
+            Ok(tools) => tools,
+            Err(_) => {
+                tracing::error!(
+                    provider = provider.provider_name(),
+                    "tool provider panicked, its tools are unavailable"
+                );
+                self.warnings.push(format!(
+                    "Tool provider '{}' panicked, its tools are unavailable",
+                    provider.provider_name()
+                ));
+                return;
+            }
+        };
+
+        for tool in tools {
             let name = tool.name();
             if self.tools.contains_key(name) {
-                eprintln!(
-                    "Warning: Tool '{}' already registered, skipping from provider '{}'",
+                tracing::warn!(
+                    tool = name,
+                    provider = provider.provider_name(),
+                    "tool already registered, skipping"
+                );
+                self.warnings.push(format!(
+                    "Tool '{}' already registered, skipping from provider '{}'",
                     name,
                     provider.provider_name()
-                );
+                ));
                 continue;
             }
             self.tools.insert(name, tool);
         }
-        self.providers.push(provider);
     }
 
     pub fn register_tool(&mut self, tool: Arc<dyn Tool>) -> Result<(), String> {
@@ -257,6 +318,7 @@ impl ToolRegistry {
             return Err(format!("Tool with name '{}' already exists", name));
         }
         self.tools.insert(name, tool);
+        self.rebuild_schemas();
         Ok(())
     }
 
@@ -277,7 +339,16 @@ impl ToolRegistry {
     }
 
     pub fn get_tool_schemas(&self) -> Vec<Value> {
-        self.tools.values().map(|tool| tool.tool_schema()).collect()
+        self.schemas.clone()
+    }
+
+    fn rebuild_schemas(&mut self) {
+        self.schemas = self.tools.values().map(|tool| tool.tool_schema()).collect();
+    }
+
+    /// Drain the provider warnings collected since the last call.
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
     }
 
     /// Refresh tools from all providers (useful for dynamic tools)
@@ -285,20 +356,10 @@ impl ToolRegistry {
         self.tools.clear();
         let providers = std::mem::take(&mut self.providers);
         for provider in providers {
-            for tool in provider.provide_tools() {
-                let name = tool.name();
-                if self.tools.contains_key(name) {
-                    eprintln!(
-                        "Warning: Tool '{}' already registered, skipping from provider '{}'",
-                        name,
-                        provider.provider_name()
-                    );
-                    continue;
-                }
-                self.tools.insert(name, tool);
-            }
+            self.register_provider_tools(provider.as_ref());
             self.providers.push(provider);
         }
+        self.rebuild_schemas();
     }
 }
 
@@ -549,4 +610,84 @@ mod tests {
         registry.refresh();
         assert_eq!(registry.list_tools().len(), 1);
     }
+
+    #[test]
+    fn test_panicking_provider_is_skipped() {
+        struct PanickingProvider;
+
+        impl ToolProvider for PanickingProvider {
+            fn provide_tools(&self) -> Vec<Arc<dyn Tool>> {
+                panic!("provider blew up");
+            }
+
+            fn provider_name(&self) -> &'static str {
+                "panicking"
+            }
+        }
+
+        let mock_tool = Arc::new(MockTool::new("mock_tool", "Mock tool", "Mock response"));
+        let mut registry = ToolRegistry::new()
+            .with_provider(Arc::new(PanickingProvider))
+            .with_provider(Arc::new(MockToolProvider::new(vec![mock_tool])));
+
+        assert_eq!(registry.list_tools().len(), 1);
+        assert!(registry.get_tool("mock_tool").is_some());
+        assert_eq!(
+            registry.take_warnings(),
+            vec!["Tool provider 'panicking' panicked, its tools are unavailable".to_string()]
+        );
+
+        registry.refresh();
+        assert_eq!(registry.list_tools().len(), 1);
+        assert_eq!(registry.take_warnings().len(), 1);
+        assert!(registry.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_schema_cache_rebuilt_on_refresh() {
+        struct DynamicProvider {
+            tools: std::sync::Mutex<Vec<Arc<dyn Tool>>>,
+        }
+
+        impl ToolProvider for DynamicProvider {
+            fn provide_tools(&self) -> Vec<Arc<dyn Tool>> {
+                self.tools.lock().unwrap().clone()
+            }
+
+            fn provider_name(&self) -> &'static str {
+                "dynamic"
+            }
+        }
+
+        let schema_names = |registry: &ToolRegistry| {
+            let mut names: Vec<String> = registry
+                .get_tool_schemas()
+                .iter()
+                .map(|schema| schema["function"]["name"].as_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let provider = Arc::new(DynamicProvider {
+            tools: std::sync::Mutex::new(vec![Arc::new(MockTool::new(
+                "first_tool",
+                "First",
+                "ok",
+            ))]),
+        });
+        let mut registry = ToolRegistry::new().with_provider(provider.clone());
+        assert_eq!(schema_names(&registry), vec!["first_tool"]);
+
+        *provider.tools.lock().unwrap() =
+            vec![Arc::new(MockTool::new("second_tool", "Second", "ok"))];
+        // Cached until the registry is refreshed.
+        assert_eq!(schema_names(&registry), vec!["first_tool"]);
+
+        registry.refresh();
+        assert_eq!(schema_names(&registry), vec!["second_tool"]);
+
+        let _ = registry.register_tool(Arc::new(MockTool::new("third_tool", "Third", "ok")));
+        assert_eq!(schema_names(&registry), vec!["second_tool", "third_tool"]);
+    }
 }
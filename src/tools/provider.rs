@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::tools::bash::{BashConfig, CommandBlocklist};
+use crate::tools::file_ops::ReadLimits;
 use crate::tools::todo_state::TodoState;
 use crate::tools::{
-    BashTool, EditFileTool, GlobTool, GrepTool, ListDirectoryTool, ReadFileTool, TodoWriteTool,
-    Tool, WriteFileTool,
+    AppendFileTool, BashTool, EditFileTool, GlobTool, GrepTool, ListDirectoryTool, MoveFileTool,
+    ReadFileTool, TodoWriteTool, Tool, WriteFileTool, WriteFilesTool,
 };
 
 /// Trait for tool providers that can register tools dynamically
@@ -20,43 +22,73 @@ pub trait ToolProvider: Send + Sync {
 pub struct BuiltinToolProvider {
     working_directory: PathBuf,
     todo_state: TodoState,
+    command_blocklist: Arc<CommandBlocklist>,
+    bash_config: Arc<BashConfig>,
+    read_limits: ReadLimits,
 }
 
 impl BuiltinToolProvider {
     pub fn new(working_directory: PathBuf) -> Self {
-        Self {
-            working_directory,
-            todo_state: TodoState::new(),
-        }
+        Self::with_todo_state(working_directory, TodoState::new())
     }
 
     pub fn with_todo_state(working_directory: PathBuf, todo_state: TodoState) -> Self {
         Self {
             working_directory,
             todo_state,
+            command_blocklist: Arc::new(CommandBlocklist::default()),
+            bash_config: Arc::new(BashConfig::default()),
+            read_limits: ReadLimits::default(),
         }
     }
+
+    pub fn with_command_blocklist(mut self, blocklist: Arc<CommandBlocklist>) -> Self {
+        self.command_blocklist = blocklist;
+        self
+    }
+
+    pub fn with_bash_config(mut self, config: Arc<BashConfig>) -> Self {
+        self.bash_config = config;
+        self
+    }
+
+    pub fn with_read_limits(mut self, limits: ReadLimits) -> Self {
+        self.read_limits = limits;
+        self
+    }
 }
 
 impl ToolProvider for BuiltinToolProvider {
     fn provide_tools(&self) -> Vec<Arc<dyn Tool>> {
         vec![
-            Arc::new(ReadFileTool::with_working_directory(
+            Arc::new(
+                ReadFileTool::with_working_directory(self.working_directory.clone())
+                    .with_limits(self.read_limits),
+            ),
+            Arc::new(WriteFileTool::with_working_directory(
                 self.working_directory.clone(),
             )),
-            Arc::new(WriteFileTool::with_working_directory(
+            Arc::new(WriteFilesTool::with_working_directory(
+                self.working_directory.clone(),
+            )),
+            Arc::new(AppendFileTool::with_working_directory(
                 self.working_directory.clone(),
             )),
             Arc::new(EditFileTool::with_working_directory(
                 self.working_directory.clone(),
             )),
+            Arc::new(MoveFileTool::with_working_directory(
+                self.working_directory.clone(),
+            )),
             Arc::new(ListDirectoryTool::with_working_directory(
                 self.working_directory.clone(),
             )),
             Arc::new(
                 BashTool::new()
                     .with_working_directory(self.working_directory.clone())
-                    .with_timeout(360),
+                    .with_timeout(360)
+                    .with_blocklist(Arc::clone(&self.command_blocklist))
+                    .with_config(Arc::clone(&self.bash_config)),
             ),
             Arc::new(GlobTool::new()),
             Arc::new(GrepTool::with_working_directory(
@@ -80,11 +112,14 @@ mod tests {
         let provider = BuiltinToolProvider::new(PathBuf::from("."));
         let tools = provider.provide_tools();
 
-        assert_eq!(tools.len(), 8);
+        assert_eq!(tools.len(), 11);
 
         let tool_names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(tool_names.contains(&"read_file"));
         assert!(tool_names.contains(&"write_file"));
+        assert!(tool_names.contains(&"write_files"));
+        assert!(tool_names.contains(&"append_file"));
+        assert!(tool_names.contains(&"move_file"));
         assert!(tool_names.contains(&"edit_file"));
         assert!(tool_names.contains(&"list_directory"));
         assert!(tool_names.contains(&"bash"));
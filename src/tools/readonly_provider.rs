@@ -1,15 +1,25 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::tools::file_ops::ReadLimits;
 use crate::tools::{GlobTool, GrepTool, ListDirectoryTool, ReadFileTool, Tool, ToolProvider};
 
 pub struct ReadOnlyToolProvider {
     working_directory: PathBuf,
+    read_limits: ReadLimits,
 }
 
 impl ReadOnlyToolProvider {
     pub fn new(working_directory: PathBuf) -> Self {
-        Self { working_directory }
+        Self {
+            working_directory,
+            read_limits: ReadLimits::default(),
+        }
+    }
+
+    pub fn with_read_limits(mut self, limits: ReadLimits) -> Self {
+        self.read_limits = limits;
+        self
     }
 }
 
@@ -17,9 +27,10 @@ impl ToolProvider for ReadOnlyToolProvider {
     fn provide_tools(&self) -> Vec<Arc<dyn Tool>> {
         // Provide ONLY read-only tools for subagent analysis
         vec![
-            Arc::new(ReadFileTool::with_working_directory(
-                self.working_directory.clone(),
-            )),
+            Arc::new(
+                ReadFileTool::with_working_directory(self.working_directory.clone())
+                    .with_limits(self.read_limits),
+            ),
             Arc::new(ListDirectoryTool::with_working_directory(
                 self.working_directory.clone(),
             )),
@@ -0,0 +1,471 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::agent::Role;
+use crate::backends::embeddings::Embedder;
+use crate::permissions::{ToolPermissionBuilder, ToolPermissionDescriptor};
+use crate::storage::{ConversationStorage, EmbeddedSnippet, EmbeddingIndex};
+use crate::tools::{CategoryPhrasing, Tool, ToolError, ToolExecutionContext, ToolResult, phrasing};
+
+const DEFAULT_TOP_K: usize = 5;
+const MAX_TOP_K: usize = 20;
+/// Longer messages are cut before embedding; the start of a message is
+/// usually what it is about, and most embedding models cap their input.
+const MAX_SNIPPET_CHARS: usize = 2000;
+const EMBED_BATCH_SIZE: usize = 32;
+
+/// `[recall]` in the config. The backend supplies the endpoint and API key
+/// from its `[backends.<name>]` entry; the model is the embedding model, not
+/// the chat model.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct RecallConfig {
+    pub backend: String,
+    pub model: String,
+    /// Snippets returned when the call doesn't ask for a number. Defaults to 5.
+    #[serde(default)]
+    pub top_k: Option<usize>,
+}
+
+impl RecallConfig {
+    pub fn top_k(&self) -> usize {
+        self.top_k.unwrap_or(DEFAULT_TOP_K)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RecallArgs {
+    query: String,
+    limit: Option<usize>,
+}
+
+/// Searches past conversations by meaning. Messages are embedded on first use
+/// and incrementally afterwards, and the vectors are kept next to the
+/// conversations so only new messages cost an embedding call.
+pub struct RecallTool {
+    storage: Arc<ConversationStorage>,
+    index_path: PathBuf,
+    embedder: Box<dyn Embedder>,
+    top_k: usize,
+    index: Mutex<Option<EmbeddingIndex>>,
+}
+
+impl RecallTool {
+    pub fn new(
+        storage: Arc<ConversationStorage>,
+        index_path: PathBuf,
+        embedder: Box<dyn Embedder>,
+        top_k: usize,
+    ) -> Self {
+        Self {
+            storage,
+            index_path,
+            embedder,
+            top_k,
+            index: Mutex::new(None),
+        }
+    }
+
+    /// Embed messages added since the last call. Returns conversation titles
+    /// for formatting results.
+    async fn refresh(&self, index: &mut EmbeddingIndex) -> ToolResult<HashMap<String, String>> {
+        let conversations =
+            self.storage
+                .list_conversations()
+                .map_err(|e| ToolError::ExecutionFailed {
+                    message: format!("recall: failed to list conversations: {}", e),
+                })?;
+
+        let mut titles = HashMap::new();
+        let mut pending: Vec<EmbeddedSnippet> = Vec::new();
+        // Only recorded in the index once every batch is embedded, so a failed
+        // batch leaves its messages to be picked up by the next refresh.
+        let mut indexed: Vec<(String, usize)> = Vec::new();
+        for metadata in conversations {
+            // Sub-agent transcripts duplicate what their parent already saw.
+            if metadata.id.contains("/subagent-") {
+                continue;
+            }
+            titles.insert(metadata.id.clone(), metadata.title.clone());
+            let Ok(messages) = self.storage.load_messages(&metadata.id) else {
+                continue;
+            };
+            let done = index.indexed.get(&metadata.id).copied().unwrap_or(0);
+            let start = if messages.len() < done {
+                // The log was rewritten (e.g. compacted); start over.
+                index.forget(&metadata.id);
+                0
+            } else {
+                done
+            };
+            for (message_index, message) in messages.iter().enumerate().skip(start) {
+                if !matches!(message.role, Role::User | Role::Assistant) {
+                    continue;
+                }
+                let Some(content) = message.content.as_deref().map(str::trim) else {
+                    continue;
+                };
+                if content.is_empty() {
+                    continue;
+                }
+                pending.push(EmbeddedSnippet {
+                    conversation_id: metadata.id.clone(),
+                    message_index,
+                    role: message.role.as_str().to_string(),
+                    text: content.chars().take(MAX_SNIPPET_CHARS).collect(),
+                    vector: Vec::new(),
+                });
+            }
+            indexed.push((metadata.id.clone(), messages.len()));
+        }
+
+        if pending.is_empty() {
+            index.indexed.extend(indexed);
+            return Ok(titles);
+        }
+
+        for batch in pending.chunks_mut(EMBED_BATCH_SIZE) {
+            let inputs: Vec<String> = batch.iter().map(|s| s.text.clone()).collect();
+            let vectors = self.embed(&inputs).await?;
+            for (snippet, vector) in batch.iter_mut().zip(vectors) {
+                snippet.vector = vector;
+            }
+        }
+        index.snippets.extend(pending);
+        index.indexed.extend(indexed);
+        index
+            .save(&self.index_path)
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("recall: failed to save embedding index: {}", e),
+            })?;
+        Ok(titles)
+    }
+
+    async fn embed(&self, inputs: &[String]) -> ToolResult<Vec<Vec<f32>>> {
+        let vectors =
+            self.embedder
+                .embed(inputs)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed {
+                    message: format!("recall: embedding failed: {}", e),
+                })?;
+        if vectors.len() != inputs.len() {
+            return Err(ToolError::ExecutionFailed {
+                message: format!(
+                    "recall: expected {} embeddings, got {}",
+                    inputs.len(),
+                    vectors.len()
+                ),
+            });
+        }
+        Ok(vectors)
+    }
+}
+
+fn format_hits(hits: &[(f32, &EmbeddedSnippet)], titles: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    for (score, snippet) in hits {
+        let title = titles
+            .get(&snippet.conversation_id)
+            .map(String::as_str)
+            .filter(|t| !t.is_empty())
+            .unwrap_or(&snippet.conversation_id);
+        out.push_str(&format!(
+            "[{:.2}] {} ({}, message {}, {})\n{}\n\n",
+            score,
+            title,
+            snippet.conversation_id,
+            snippet.message_index,
+            snippet.role,
+            snippet.text
+        ));
+    }
+    out.trim_end().to_string()
+}
+
+#[async_trait]
+impl Tool for RecallTool {
+    fn name(&self) -> &'static str {
+        "recall"
+    }
+
+    fn phrasing(&self) -> CategoryPhrasing {
+        phrasing::SEARCH
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Recall"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search earlier conversations in this project by meaning.\n\n\
+        Returns the most relevant user and assistant messages from past \
+        sessions, best match first, each with its similarity score and the \
+        conversation it came from. The current conversation is excluded.\n\n\
+        When to use:\n\
+        - The user refers to something discussed in a previous session\n\
+        - Looking for an earlier decision, explanation or approach\n\n\
+        When NOT to use:\n\
+        - Searching code - use grep or glob instead\n\
+        - Facts already in this conversation"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "What to look for, phrased as a description of the content, e.g. \"why we switched the cache to LRU\""
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_TOP_K,
+                    "description": "Optional: number of snippets to return. Defaults to the configured top_k."
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: &Value, context: &ToolExecutionContext) -> ToolResult<String> {
+        let args: RecallArgs =
+            serde_json::from_value(args.clone()).map_err(|e| ToolError::InvalidArguments {
+                tool: "recall".to_string(),
+                message: e.to_string(),
+            })?;
+        if args.query.trim().is_empty() {
+            return Err(ToolError::InvalidArguments {
+                tool: "recall".to_string(),
+                message: "query must not be empty".to_string(),
+            });
+        }
+        let limit = args.limit.unwrap_or(self.top_k).clamp(1, MAX_TOP_K);
+
+        let mut guard = self.index.lock().await;
+        if guard.is_none() {
+            let loaded = EmbeddingIndex::load(&self.index_path, self.embedder.model())
+                .unwrap_or_else(|_| EmbeddingIndex::new(self.embedder.model()));
+            *guard = Some(loaded);
+        }
+        let index = guard.as_mut().expect("index loaded above");
+        let titles = self.refresh(index).await?;
+
+        let query = self.embed(std::slice::from_ref(&args.query)).await?;
+        let hits = index.nearest(&query[0], limit, context.parent_conversation_id.as_deref());
+        if hits.is_empty() {
+            return Ok("No related messages found in past conversations.".to_string());
+        }
+        Ok(format_hits(&hits, &titles))
+    }
+
+    fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
+        ToolPermissionBuilder::new(self, target.unwrap_or("*"))
+            .into_read_only()
+            .build()
+            .expect("Failed to build recall permission descriptor")
+    }
+
+    fn format_call_display(&self, args: &Value) -> String {
+        let query = args.get("query").and_then(Value::as_str).unwrap_or("?");
+        format!("Recall({})", query)
+    }
+
+    fn result_summary(&self, result: &str) -> String {
+        let hits = result.lines().filter(|l| l.starts_with('[')).count();
+        if hits == 0 {
+            "No matches".to_string()
+        } else {
+            format!("Found {} snippet{}", hits, if hits == 1 { "" } else { "s" })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::ConversationMessage;
+    use tempfile::TempDir;
+
+    /// Maps text onto fixed axes by keyword so nearest-neighbour results are
+    /// predictable.
+    struct KeywordEmbedder;
+
+    #[async_trait]
+    impl Embedder for KeywordEmbedder {
+        fn model(&self) -> &str {
+            "keywords"
+        }
+
+        async fn embed(&self, inputs: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(inputs
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    ["database", "css", "deploy"]
+                        .iter()
+                        .map(|k| if text.contains(k) { 1.0 } else { 0.1 })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    /// Fails its first call, then embeds like [`KeywordEmbedder`].
+    struct FlakyEmbedder {
+        failed: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait]
+    impl Embedder for FlakyEmbedder {
+        fn model(&self) -> &str {
+            "keywords"
+        }
+
+        async fn embed(&self, inputs: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            if !self.failed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                anyhow::bail!("service unavailable");
+            }
+            KeywordEmbedder.embed(inputs).await
+        }
+    }
+
+    fn context(conversation_id: Option<&str>) -> ToolExecutionContext {
+        ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: conversation_id.map(str::to_string),
+        }
+    }
+
+    fn seed(storage: &ConversationStorage, id: &str, title: &str, user: &str, reply: &str) {
+        storage.create_conversation(id).unwrap();
+        storage.update_title(id, title.to_string()).unwrap();
+        for message in [
+            ConversationMessage {
+                role: Role::User,
+                content: Some(user.to_string()),
+                tool_calls: None,
+                tool_call_id: None,
+                name: None,
+                attachments: Vec::new(),
+            },
+            ConversationMessage {
+                role: Role::Assistant,
+                content: Some(reply.to_string()),
+                tool_calls: None,
+                tool_call_id: None,
+                name: None,
+                attachments: Vec::new(),
+            },
+        ] {
+            storage.append_message(id, &message).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recall_returns_nearest_snippets_and_persists_index() {
+        let temp = TempDir::new().unwrap();
+        let storage = Arc::new(ConversationStorage::with_root(temp.path()));
+        seed(
+            &storage,
+            "conv_db",
+            "Schema work",
+            "Fix the database migration",
+            "Done",
+        );
+        seed(
+            &storage,
+            "conv_css",
+            "Styling",
+            "Tweak the css grid",
+            "Done",
+        );
+        let index_path = temp.path().join(EmbeddingIndex::FILE_NAME);
+        let tool = RecallTool::new(storage, index_path.clone(), Box::new(KeywordEmbedder), 1);
+
+        let result = tool
+            .execute(&json!({"query": "database schema"}), &context(None))
+            .await
+            .unwrap();
+
+        assert!(result.contains("Schema work"), "got: {result}");
+        assert!(result.contains("Fix the database migration"));
+        assert!(!result.contains("css"));
+        let saved = EmbeddingIndex::load(&index_path, "keywords").unwrap();
+        assert_eq!(saved.snippets.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_recall_excludes_the_current_conversation() {
+        let temp = TempDir::new().unwrap();
+        let storage = Arc::new(ConversationStorage::with_root(temp.path()));
+        seed(
+            &storage,
+            "conv_db",
+            "Schema work",
+            "Fix the database migration",
+            "Done",
+        );
+        seed(
+            &storage,
+            "conv_css",
+            "Styling",
+            "Tweak the css grid",
+            "Done",
+        );
+        let tool = RecallTool::new(
+            storage,
+            temp.path().join(EmbeddingIndex::FILE_NAME),
+            Box::new(KeywordEmbedder),
+            1,
+        );
+
+        let result = tool
+            .execute(&json!({"query": "database"}), &context(Some("conv_db")))
+            .await
+            .unwrap();
+
+        assert!(!result.contains("Schema work"), "got: {result}");
+    }
+
+    #[tokio::test]
+    async fn test_failed_embedding_is_retried_on_the_next_call() {
+        let temp = TempDir::new().unwrap();
+        let storage = Arc::new(ConversationStorage::with_root(temp.path()));
+        seed(
+            &storage,
+            "conv_db",
+            "Schema work",
+            "Fix the database migration",
+            "Done",
+        );
+        let tool = RecallTool::new(
+            storage,
+            temp.path().join(EmbeddingIndex::FILE_NAME),
+            Box::new(FlakyEmbedder {
+                failed: std::sync::atomic::AtomicBool::new(false),
+            }),
+            1,
+        );
+
+        let query = json!({"query": "database"});
+        assert!(tool.execute(&query, &context(None)).await.is_err());
+        let result = tool.execute(&query, &context(None)).await.unwrap();
+
+        assert!(
+            result.contains("Fix the database migration"),
+            "got: {result}"
+        );
+    }
+}
@@ -1,18 +1,34 @@
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::config::AppConfig;
 use crate::task_management::AgentType;
 use crate::tools::{BuiltinToolProvider, ReadOnlyToolProvider, ToolProvider, ToolRegistry};
 
+/// Tools for a sub-agent of `agent_type`. With a `config`, they honour the
+/// same bash blocklist, bash settings and read limits as the session's own
+/// tools; without one they fall back to the defaults.
 pub fn create_subagent_registry(
     agent_type: &AgentType,
     working_directory: &Path,
+    config: Option<&AppConfig>,
 ) -> Arc<ToolRegistry> {
+    let read_limits = config.map(AppConfig::read_limits).unwrap_or_default();
     let provider: Arc<dyn ToolProvider> = match agent_type {
-        AgentType::Plan | AgentType::Explore | AgentType::Review => {
-            Arc::new(ReadOnlyToolProvider::new(working_directory.to_path_buf()))
+        AgentType::Plan | AgentType::Explore | AgentType::Review => Arc::new(
+            ReadOnlyToolProvider::new(working_directory.to_path_buf())
+                .with_read_limits(read_limits),
+        ),
+        AgentType::General => {
+            let mut provider = BuiltinToolProvider::new(working_directory.to_path_buf())
+                .with_read_limits(read_limits);
+            if let Some(config) = config {
+                provider = provider
+                    .with_command_blocklist(Arc::new(config.command_blocklist()))
+                    .with_bash_config(Arc::new(config.bash_config()));
+            }
+            Arc::new(provider)
         }
-        AgentType::General => Arc::new(BuiltinToolProvider::new(working_directory.to_path_buf())),
     };
 
     Arc::new(ToolRegistry::new().with_provider(provider))
@@ -23,7 +39,7 @@ mod tests {
     use super::*;
 
     fn tool_names(agent_type: &AgentType) -> Vec<String> {
-        let registry = create_subagent_registry(agent_type, Path::new("."));
+        let registry = create_subagent_registry(agent_type, Path::new("."), None);
         registry
             .list_tools()
             .iter()
@@ -59,4 +75,31 @@ mod tests {
             "subagents must not spawn further subagents"
         );
     }
+
+    #[tokio::test]
+    async fn general_agent_bash_honours_configured_blocklist() {
+        let config: AppConfig = toml::from_str(
+            "default_backend = \"mock\"\n[bash_blocklist]\npatterns = ['^terraform destroy']",
+        )
+        .unwrap();
+        let registry = create_subagent_registry(&AgentType::General, Path::new("."), Some(&config));
+        let bash = registry.get_tool("bash").unwrap();
+
+        let context = crate::tools::ToolExecutionContext {
+            tool_call_id: "test".to_string(),
+            event_tx: None,
+            parent_conversation_id: None,
+        };
+        let err = bash
+            .execute(
+                &serde_json::json!({
+                    "command": "terraform destroy",
+                    "description": "Tear down"
+                }),
+                &context,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bash blocklist"), "{err}");
+    }
 }
@@ -1,4 +1,5 @@
-use crate::backends::LlmBackend;
+use crate::backends::{LlmBackend, RequestLimiters};
+use crate::config::AppConfig;
 use crate::permissions::{PermissionManager, ToolPermissionBuilder, ToolPermissionDescriptor};
 use crate::task_management::{AgentType, TaskDefinition, TaskManager};
 use crate::tools::{
@@ -10,27 +11,72 @@ use capitalize::Capitalize;
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::path::PathBuf;
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// The backend new sub-agents start on. Clones share it, so the session can
+/// move sub-agents along when the active agent changes backend.
+#[derive(Clone)]
+pub struct SubagentBackend {
+    backend: Arc<RwLock<Arc<dyn LlmBackend>>>,
+}
+
+impl SubagentBackend {
+    pub fn new(backend: Arc<dyn LlmBackend>) -> Self {
+        Self {
+            backend: Arc::new(RwLock::new(backend)),
+        }
+    }
+
+    pub fn get(&self) -> Arc<dyn LlmBackend> {
+        Arc::clone(&self.backend.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    pub fn set(&self, backend: Arc<dyn LlmBackend>) {
+        *self.backend.write().unwrap_or_else(|e| e.into_inner()) = backend;
+    }
+}
+
+impl From<Arc<dyn LlmBackend>> for SubagentBackend {
+    fn from(backend: Arc<dyn LlmBackend>) -> Self {
+        Self::new(backend)
+    }
+}
 
 pub struct TaskTool {
-    backend: Arc<dyn LlmBackend>,
+    backend: SubagentBackend,
     working_directory: PathBuf,
     permission_manager: Arc<PermissionManager>,
+    config: Option<AppConfig>,
+    request_limiters: Arc<RequestLimiters>,
 }
 
 impl TaskTool {
     pub fn new(
-        backend: Arc<dyn LlmBackend>,
+        backend: impl Into<SubagentBackend>,
         working_directory: PathBuf,
         permission_manager: Arc<PermissionManager>,
     ) -> Self {
         Self {
-            backend,
+            backend: backend.into(),
             working_directory,
             permission_manager,
+            config: None,
+            request_limiters: Arc::new(RequestLimiters::default()),
         }
     }
 
+    pub fn with_request_limiters(mut self, limiters: Arc<RequestLimiters>) -> Self {
+        self.request_limiters = limiters;
+        self
+    }
+
+    /// Lets a task's `model` argument take effect; without a config every
+    /// sub-agent runs on `backend`.
+    pub fn with_config(mut self, config: Option<AppConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
     async fn execute_impl(
         &self,
         args: &Value,
@@ -58,10 +104,15 @@ impl TaskTool {
         let subagent_tools = self.get_tool_registry_for_agent(&agent_type);
 
         let mut task_manager = TaskManager::new(
-            self.backend.clone(),
+            self.backend.get(),
             subagent_tools,
             self.permission_manager.clone(),
-        );
+        )
+        .with_request_limiters(Arc::clone(&self.request_limiters));
+
+        if let Some(config) = &self.config {
+            task_manager = task_manager.with_config(config.clone());
+        }
 
         if let Some(ctx) = &context {
             if let Some(tx) = &ctx.event_tx {
@@ -85,7 +136,7 @@ impl TaskTool {
         }
     }
     fn get_tool_registry_for_agent(&self, agent_type: &AgentType) -> Arc<ToolRegistry> {
-        create_subagent_registry(agent_type, &self.working_directory)
+        create_subagent_registry(agent_type, &self.working_directory, self.config.as_ref())
     }
 }
 
@@ -200,6 +251,12 @@ impl Tool for TaskTool {
         }
     }
 
+    /// Sub-agents inherit dry-run through the config, so the task itself
+    /// can run; without a config it would not know to simulate.
+    fn runs_in_dry_run(&self) -> bool {
+        self.config.as_ref().is_some_and(|c| c.dry_run)
+    }
+
     fn describe_permission(&self, target: Option<&str>) -> ToolPermissionDescriptor {
         ToolPermissionBuilder::new(self, target.unwrap_or("*"))
             .into_destructive()
@@ -266,6 +323,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn subagent_backend_clones_see_a_new_backend() {
+        let first: Arc<dyn LlmBackend> = Arc::new(MockBackend::new(Vec::new()));
+        let second: Arc<dyn LlmBackend> = Arc::new(MockBackend::new(Vec::new()));
+        let handle = SubagentBackend::new(Arc::clone(&first));
+        let shared = handle.clone();
+
+        handle.set(Arc::clone(&second));
+        assert!(Arc::ptr_eq(&shared.get(), &second));
+    }
+
     #[tokio::test]
     async fn test_task_tool_execute_plan() {
         crate::console::init_console(crate::console::VerbosityLevel::Quiet);
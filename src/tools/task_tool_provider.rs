@@ -1,14 +1,17 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::backends::LlmBackend;
+use crate::backends::{LlmBackend, RequestLimiters};
+use crate::config::AppConfig;
 use crate::permissions::PermissionManager;
-use crate::tools::{TaskTool, Tool, ToolProvider};
+use crate::tools::{SubagentBackend, TaskTool, Tool, ToolProvider};
 
 pub struct TaskToolProvider {
-    backend: Arc<dyn LlmBackend>,
+    backend: SubagentBackend,
     working_directory: PathBuf,
     permission_manager: Arc<PermissionManager>,
+    config: Option<AppConfig>,
+    request_limiters: Arc<RequestLimiters>,
 }
 
 impl TaskToolProvider {
@@ -18,20 +21,42 @@ impl TaskToolProvider {
         permission_manager: Arc<PermissionManager>,
     ) -> Self {
         Self {
-            backend,
+            backend: SubagentBackend::new(backend),
             working_directory,
             permission_manager,
+            config: None,
+            request_limiters: Arc::new(RequestLimiters::default()),
         }
     }
+
+    pub fn with_request_limiters(mut self, limiters: Arc<RequestLimiters>) -> Self {
+        self.request_limiters = limiters;
+        self
+    }
+
+    /// Handle for changing the backend new sub-agents start on.
+    pub fn backend_handle(&self) -> SubagentBackend {
+        self.backend.clone()
+    }
+
+    /// Config sub-agents use to run on a model other than `backend`'s.
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
 }
 
 impl ToolProvider for TaskToolProvider {
     fn provide_tools(&self) -> Vec<Arc<dyn Tool>> {
-        vec![Arc::new(TaskTool::new(
-            self.backend.clone(),
-            self.working_directory.clone(),
-            self.permission_manager.clone(),
-        ))]
+        vec![Arc::new(
+            TaskTool::new(
+                self.backend.clone(),
+                self.working_directory.clone(),
+                self.permission_manager.clone(),
+            )
+            .with_config(self.config.clone())
+            .with_request_limiters(Arc::clone(&self.request_limiters)),
+        )]
     }
 
     fn provider_name(&self) -> &'static str {
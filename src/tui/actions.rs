@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::SystemTime;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -7,6 +8,7 @@ use crate::agent::{Agent, AgentEvent, Conversation, FileMention, PendingToolCall
 use crate::backends::LlmBackend;
 use crate::commands::{CommandContext, CommandResult};
 use crate::context_management::ContextManager;
+use crate::secret_redaction::REDACTED;
 use crate::tool_executor::ToolExecutor;
 use crate::tools::{ToolRegistry, ToolRender};
 use crate::tui::app_loop::EventLoopContext;
@@ -28,6 +30,8 @@ pub fn execute_command(input: String, event_loop_context: &EventLoopContext) {
     let context_manager = Arc::clone(&event_loop_context.conversation_state.context_manager);
     let tool_executor = Arc::clone(&event_loop_context.system_resources.tool_executor);
     let system_reminder = Arc::clone(&event_loop_context.system_resources.system_reminder);
+    let turn_interrupt = Arc::clone(&event_loop_context.conversation_state.turn_interrupt);
+    let max_steps = config.get_agent_max_steps();
 
     tokio::spawn(async move {
         let mut context = CommandContext::new()
@@ -41,7 +45,8 @@ pub fn execute_command(input: String, event_loop_context: &EventLoopContext) {
             .with_event_sender(event_tx.clone())
             .with_config(config)
             .with_backend(Arc::clone(&backend))
-            .with_context_manager(Arc::clone(&context_manager));
+            .with_context_manager(Arc::clone(&context_manager))
+            .with_tool_executor(Arc::clone(&tool_executor));
 
         match command_registry.execute(&input, &mut context).await {
             Ok(CommandResult::Success(msg)) => {
@@ -56,6 +61,8 @@ pub fn execute_command(input: String, event_loop_context: &EventLoopContext) {
                     Arc::clone(&tool_executor),
                     Arc::clone(&context_manager),
                     Arc::clone(&system_reminder),
+                    turn_interrupt,
+                    max_steps,
                 )
                 .await;
             }
@@ -83,13 +90,31 @@ pub fn answer(
     let tool_registry = Arc::clone(&event_loop_context.system_resources.tool_registry);
     let tool_executor = Arc::clone(&event_loop_context.system_resources.tool_executor);
     let system_reminder = Arc::clone(&event_loop_context.system_resources.system_reminder);
+    let request_limiter = event_loop_context
+        .system_resources
+        .request_limiters
+        .for_backend(backend.backend_name());
     let event_tx = event_loop_context.channels.event_tx.clone();
+    let max_steps = event_loop_context.runtime.config.get_agent_max_steps();
     let context_manager = Arc::clone(&event_loop_context.conversation_state.context_manager);
     let memory_manager = event_loop_context
         .runtime
         .memory_mode_manager
         .as_ref()
         .map(Arc::clone);
+    let agent_name = event_loop_context
+        .conversation_state
+        .current_agent_name
+        .clone();
+    let turn_interrupt = Arc::clone(&event_loop_context.conversation_state.turn_interrupt);
+    turn_interrupt.store(false, Ordering::Relaxed);
+    let secret_scanner = event_loop_context.runtime.prompt_secret_scanner.clone();
+    let title_backend: Option<Arc<dyn LlmBackend>> =
+        crate::backends::backend_factory::create_title_backend(
+            backend.backend_name(),
+            &event_loop_context.runtime.config,
+        )
+        .map(Arc::from);
 
     tokio::spawn(async move {
         let turn_start = SystemTime::now();
@@ -102,8 +127,18 @@ pub fn answer(
                     ..Default::default()
                 });
         expanded.attachments.extend(image_attachments);
+        // Files pulled in with @mentions go to the provider without a chance
+        // to confirm, so secrets in them are always redacted.
+        if let Some(scanner) = secret_scanner {
+            for mention in &mut expanded.mentions {
+                mention.redact_secrets(&scanner);
+            }
+        }
 
         emit_mention_events(&expanded.mentions, &tool_registry, &event_tx);
+        for event in expanded.events.drain(..) {
+            let _ = event_tx.send(event);
+        }
 
         let mut conv = conversation.lock().await;
 
@@ -135,17 +170,39 @@ pub fn answer(
         let agent = Agent::new(backend, tool_registry, tool_executor)
             .with_event_sender(event_tx.clone())
             .with_context_manager(context_manager)
-            .with_system_reminder(system_reminder);
+            .with_system_reminder(system_reminder)
+            .with_interrupt(turn_interrupt)
+            .with_title_backend(title_backend)
+            .with_request_limiter(request_limiter)
+            .with_max_steps(max_steps);
 
-        // Error is already sent as AgentEvent::Error from within handle_turn
-        let _ = agent.handle_turn(&mut conv).await;
+        // Errors are already sent as AgentEvent::BackendError from within handle_turn
+        let result = agent.handle_turn(&mut conv).await;
 
         if let Some(ref manager) = memory_manager {
             manager.record_turn_end(turn_start);
         }
+
+        notify_turn_finished(&agent_name, &conv, result.is_ok());
     })
 }
 
+fn notify_turn_finished(agent_name: &str, conversation: &Conversation, succeeded: bool) {
+    let title = if succeeded {
+        format!("hoosh: {} finished", agent_name)
+    } else {
+        format!("hoosh: {} stopped with an error", agent_name)
+    };
+    let last_reply = conversation
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == crate::agent::Role::Assistant)
+        .and_then(|m| m.content.as_deref())
+        .unwrap_or_default();
+    crate::notification::notify(&title, last_reply);
+}
+
 fn emit_mention_events(
     mentions: &[FileMention],
     tool_registry: &ToolRegistry,
@@ -174,11 +231,22 @@ fn emit_mention_events(
             .map(|t| t.phrasing())
             .unwrap_or(crate::tools::phrasing::GENERIC);
         let summary = match mention.result() {
+            Ok(output) if output.contains(REDACTED) => format!(
+                "{}, secrets redacted",
+                tool.map(|t| t.result_summary(output))
+                    .unwrap_or_else(|| "Done".to_string())
+            ),
             Ok(output) => tool
                 .map(|t| t.result_summary(output))
                 .unwrap_or_else(|| "Done".to_string()),
             Err(err) => format!("Error: {}", err),
         };
+        // Same ~4 bytes per token estimate the conversation uses.
+        let tokens = match mention.result() {
+            Ok(output) => output.len(),
+            Err(err) => err.to_string().len(),
+        }
+        .div_ceil(4);
 
         pending.push(PendingToolCall {
             id: id.clone(),
@@ -186,11 +254,11 @@ fn emit_mention_events(
             render,
             phrasing,
         });
-        results.push((id, tool_name.to_string(), summary));
+        results.push((id, tool_name.to_string(), summary, tokens));
     }
 
     let _ = event_tx.send(AgentEvent::ToolCalls(pending));
-    for (id, tool_name, summary) in results {
+    for (id, tool_name, summary, tokens) in results {
         let _ = event_tx.send(AgentEvent::ToolExecutionStarted {
             tool_call_id: id.clone(),
             tool_name: tool_name.clone(),
@@ -199,6 +267,7 @@ fn emit_mention_events(
             tool_call_id: id.clone(),
             tool_name: tool_name.clone(),
             summary,
+            tokens,
         });
         let _ = event_tx.send(AgentEvent::ToolExecutionCompleted {
             tool_call_id: id,
@@ -208,6 +277,7 @@ fn emit_mention_events(
     let _ = event_tx.send(AgentEvent::AllToolsComplete);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_agent_on_conversation(
     event_tx: mpsc::UnboundedSender<AgentEvent>,
     conversation: Arc<tokio::sync::Mutex<Conversation>>,
@@ -216,11 +286,16 @@ pub async fn run_agent_on_conversation(
     tool_executor: Arc<ToolExecutor>,
     context_manager: Arc<ContextManager>,
     system_reminder: Arc<crate::system_reminders::SystemReminder>,
+    turn_interrupt: Arc<AtomicBool>,
+    max_steps: usize,
 ) {
+    turn_interrupt.store(false, Ordering::Relaxed);
     let agent = Agent::new(backend, tool_registry, tool_executor)
         .with_event_sender(event_tx.clone())
         .with_context_manager(context_manager)
-        .with_system_reminder(system_reminder);
+        .with_system_reminder(system_reminder)
+        .with_interrupt(turn_interrupt)
+        .with_max_steps(max_steps);
 
     let mut conv = conversation.lock().await;
     let _ = agent.handle_turn(&mut conv).await;
@@ -1,4 +1,7 @@
 use crate::tui::app_layout_builder::AppLayoutBuilder;
+use crate::tui::components::approval_dialog::approval_dialog_height;
+use crate::tui::components::attachments_bar::ATTACHMENTS_MAX_VISIBLE;
+use crate::tui::components::task_list_popup::TASK_LIST_MAX_VISIBLE;
 use crate::tui::layout::Layout;
 use crate::tui::layout_builder::LayoutBuilder;
 use crate::tui::state::AppState;
@@ -11,7 +14,8 @@ impl AppLayout for Layout<AppState> {
     fn create(app: &AppState, terminal_width: u16, _terminal_height: u16) -> Self {
         let has_overlay = app.is_showing_tool_permission_dialog()
             || app.is_showing_approval_dialog()
-            || app.is_completing();
+            || app.is_completing()
+            || app.is_showing_task_list();
 
         let pending_exploration_only =
             app.tools.active.is_empty() && !app.pending_exploration.is_empty();
@@ -34,6 +38,16 @@ impl AppLayout for Layout<AppState> {
             })
         };
 
+        // The streaming tail is one block at most; cap it so a long code
+        // fence doesn't push the input off screen while it streams.
+        const STREAMING_TAIL_MAX_HEIGHT: usize = 12;
+        let streaming_tail_height = app
+            .streaming
+            .tail_lines()
+            .len()
+            .min(STREAMING_TAIL_MAX_HEIGHT) as u16;
+        let streaming_tail_visible = streaming_tail_height > 0;
+
         // Calculate todo list visibility and height
         let todo_list_visible = !app.todos.is_empty();
         // No border needed, just the number of todos
@@ -52,6 +66,21 @@ impl AppLayout for Layout<AppState> {
             1 + visible + overflow
         };
 
+        // Attachments bar: header + up to ATTACHMENTS_MAX_VISIBLE rows + optional overflow line.
+        let attachment_count = app.attachments.len();
+        let attachments_visible = attachment_count > 0;
+        let attachments_height = if attachment_count == 0 {
+            0
+        } else {
+            let visible = attachment_count.min(ATTACHMENTS_MAX_VISIBLE) as u16;
+            let overflow = if attachment_count > ATTACHMENTS_MAX_VISIBLE {
+                1
+            } else {
+                0
+            };
+            1 + visible + overflow
+        };
+
         // Calculate input field height based on wrapped lines using actual terminal width
         // The input has TOP and BOTTOM borders (no left/right), so width stays the same
         // The text area width is terminal_width - 2 (only the prompt)
@@ -60,11 +89,13 @@ impl AppLayout for Layout<AppState> {
 
         let mut builder = LayoutBuilder::new()
             .spacer(1)
+            .streaming_tail(streaming_tail_height, streaming_tail_visible)
             .active_tool_calls(active_tool_calls_height, active_tool_calls_visible)
             .spacer_if(1, active_tool_calls_visible)
             .status_bar()
             .todo_list(todo_list_height, todo_list_visible)
             .queued_prompts(queued_prompts_height, queued_prompts_visible)
+            .attachments_bar(attachments_height, attachments_visible)
             .input_field(input_height)
             .mode_indicator(!has_overlay);
 
@@ -89,6 +120,9 @@ impl AppLayout for Layout<AppState> {
                         base += 1;
                     }
 
+                    // Parsed command breakdown, one line each.
+                    base += state.descriptor.summary().len() as u16;
+
                     // Add height for command preview if present
                     if let Some(preview) = state.descriptor.command_preview() {
                         const MAX_PREVIEW_LINES: u16 = 15;
@@ -110,7 +144,13 @@ impl AppLayout for Layout<AppState> {
                 .unwrap_or(10);
             builder = builder.permission_dialog(lines, true);
         } else if app.is_showing_approval_dialog() {
-            builder = builder.approval_dialog(true);
+            let lines = app
+                .dialogs
+                .approval
+                .as_ref()
+                .map(approval_dialog_height)
+                .unwrap_or(6);
+            builder = builder.approval_dialog(lines, true);
         } else if app.is_completing() {
             let lines = app
                 .completion_state
@@ -118,6 +158,9 @@ impl AppLayout for Layout<AppState> {
                 .map(|state| state.candidates.len().min(10) as u16)
                 .unwrap_or(5);
             builder = builder.completion_popup(lines, true);
+        } else if let Some(task_list) = &app.task_list {
+            let lines = task_list.items.len().min(TASK_LIST_MAX_VISIBLE) as u16;
+            builder = builder.task_list_popup(lines, true);
         }
 
         builder.build()
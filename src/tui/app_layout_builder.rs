@@ -1,11 +1,14 @@
 use crate::tui::components::active_tool_calls::ActiveToolCallsComponent;
 use crate::tui::components::approval_dialog::ApprovalDialog;
+use crate::tui::components::attachments_bar::AttachmentsBar;
 use crate::tui::components::completion_popup::CompletionPopup;
 use crate::tui::components::input::Input;
 use crate::tui::components::mode_indicator::ModeIndicator;
 use crate::tui::components::permission_dialog::PermissionDialog;
 use crate::tui::components::queued_prompts::QueuedPromptsComponent;
 use crate::tui::components::status_bar::StatusBar;
+use crate::tui::components::streaming_tail::StreamingTailComponent;
+use crate::tui::components::task_list_popup::TaskListPopup;
 use crate::tui::components::todo_list::TodoListComponent;
 use crate::tui::layout::ComponentDescriptor;
 use crate::tui::layout_builder::LayoutBuilder;
@@ -13,14 +16,17 @@ use crate::tui::state::AppState;
 
 pub trait AppLayoutBuilder {
     fn status_bar(self) -> Self;
+    fn streaming_tail(self, height: u16, visible: bool) -> Self;
     fn active_tool_calls(self, height: u16, visible: bool) -> Self;
     fn todo_list(self, height: u16, visible: bool) -> Self;
     fn queued_prompts(self, height: u16, visible: bool) -> Self;
+    fn attachments_bar(self, height: u16, visible: bool) -> Self;
     fn input_field(self, height: u16) -> Self;
     fn mode_indicator(self, visible: bool) -> Self;
     fn permission_dialog(self, content_lines: u16, visible: bool) -> Self;
-    fn approval_dialog(self, visible: bool) -> Self;
+    fn approval_dialog(self, content_lines: u16, visible: bool) -> Self;
     fn completion_popup(self, content_lines: u16, visible: bool) -> Self;
+    fn task_list_popup(self, content_lines: u16, visible: bool) -> Self;
 }
 
 impl AppLayoutBuilder for LayoutBuilder<AppState> {
@@ -28,6 +34,13 @@ impl AppLayoutBuilder for LayoutBuilder<AppState> {
         self.component(ComponentDescriptor::new(1, Some(Box::new(StatusBar))))
     }
 
+    fn streaming_tail(self, height: u16, visible: bool) -> Self {
+        self.component(
+            ComponentDescriptor::new(height, Some(Box::new(StreamingTailComponent)))
+                .with_visibility(visible),
+        )
+    }
+
     fn active_tool_calls(self, height: u16, visible: bool) -> Self {
         self.component(
             ComponentDescriptor::new(height, Some(Box::new(ActiveToolCallsComponent)))
@@ -49,6 +62,13 @@ impl AppLayoutBuilder for LayoutBuilder<AppState> {
         )
     }
 
+    fn attachments_bar(self, height: u16, visible: bool) -> Self {
+        self.component(
+            ComponentDescriptor::new(height, Some(Box::new(AttachmentsBar)))
+                .with_visibility(visible),
+        )
+    }
+
     fn input_field(self, height: u16) -> Self {
         self.component(ComponentDescriptor::new(height, Some(Box::new(Input))).with_border())
     }
@@ -67,9 +87,9 @@ impl AppLayoutBuilder for LayoutBuilder<AppState> {
         )
     }
 
-    fn approval_dialog(self, visible: bool) -> Self {
+    fn approval_dialog(self, content_lines: u16, visible: bool) -> Self {
         self.component(
-            ComponentDescriptor::new(6, Some(Box::new(ApprovalDialog)))
+            ComponentDescriptor::new(content_lines, Some(Box::new(ApprovalDialog)))
                 .with_border()
                 .with_visibility(visible),
         )
@@ -82,4 +102,12 @@ impl AppLayoutBuilder for LayoutBuilder<AppState> {
                 .with_visibility(visible),
         )
     }
+
+    fn task_list_popup(self, content_lines: u16, visible: bool) -> Self {
+        self.component(
+            ComponentDescriptor::new(content_lines, Some(Box::new(TaskListPopup)))
+                .with_border()
+                .with_visibility(visible),
+        )
+    }
 }
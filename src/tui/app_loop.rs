@@ -1,5 +1,7 @@
 use anyhow::Result;
 use crossterm::event;
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{Mutex, mpsc};
@@ -10,13 +12,14 @@ use super::message_renderer::MessageRenderer;
 use super::state::AppState;
 use crate::agent::{AgentEvent, CancelKind, Conversation};
 use crate::agent_definition::AgentDefinitionManager;
-use crate::backends::LlmBackend;
+use crate::backends::{BackendKind, LlmBackend};
 use crate::commands::CommandRegistry;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ReasoningEffort};
 use crate::console::{VerbosityLevel, console};
 use crate::context_management::ContextManager;
 use crate::memory_mode::MemoryModeManager;
 use crate::parser::MessageParser;
+use crate::project_instructions;
 use crate::storage::ConversationStorage;
 use crate::tool_executor::ToolExecutor;
 use crate::tools::ToolRegistry;
@@ -34,6 +37,10 @@ pub struct SystemResources {
     pub agent_manager: Arc<AgentDefinitionManager>,
     pub command_registry: Arc<CommandRegistry>,
     pub system_reminder: Arc<crate::system_reminders::SystemReminder>,
+    /// Per-backend request limits shared by the main loop and sub-agents.
+    pub request_limiters: Arc<crate::backends::RequestLimiters>,
+    /// The backend the task tool starts sub-agents on.
+    pub subagent_backend: crate::tools::SubagentBackend,
 }
 
 pub struct ConversationState {
@@ -42,6 +49,8 @@ pub struct ConversationState {
     pub current_agent_name: String,
     pub conversation_storage: Arc<ConversationStorage>,
     pub conversation_id: String,
+    /// The same flag as `AppState::turn_interrupt`, handed to each agent turn.
+    pub turn_interrupt: Arc<std::sync::atomic::AtomicBool>,
 }
 
 pub struct EventChannels {
@@ -59,8 +68,14 @@ pub struct RuntimeState {
     pub input_handlers: Vec<Box<dyn InputHandler + Send>>,
     pub working_dir: String,
     pub config: AppConfig,
+    /// `config` before any agent's backend/model overrides; `/agent` applies
+    /// the new agent's overrides to this.
+    pub agent_base_config: AppConfig,
     pub todo_state: TodoState,
     pub memory_mode_manager: Option<Arc<MemoryModeManager>>,
+    /// Redacts secrets from files pulled in with @mentions before they are
+    /// sent. `None` when `prompt_secret_check = "off"`.
+    pub prompt_secret_scanner: Option<Arc<crate::secret_redaction::SecretRedactor>>,
 }
 
 pub struct EventLoopContext {
@@ -95,6 +110,11 @@ pub async fn run_event_loop(
             handle_user_input(&event, app, &mut agent_task, &mut context).await?;
         }
 
+        if let Some(reference) = app.editor_request.take() {
+            let status = super::editor::open(&mut terminal, false, &app.editor, &reference);
+            app.add_status_message(&status);
+        }
+
         if app.should_quit {
             break;
         }
@@ -140,13 +160,14 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             descriptor,
             request_id,
         } => {
-            app.show_tool_permission_dialog(descriptor, request_id);
+            app.show_tool_permission_dialog(*descriptor, request_id);
         }
         AgentEvent::ApprovalRequest {
             tool_call_id,
             tool_name,
+            preview,
         } => {
-            app.show_approval_dialog(tool_call_id, tool_name);
+            app.show_approval_dialog(tool_call_id, tool_name, preview);
         }
         AgentEvent::Exit => {
             app.should_quit = true;
@@ -165,7 +186,16 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             model,
             save,
         } => {
-            apply_backend_switch(app, context, backend, model, save);
+            apply_backend_switch(app, context, backend, model, save).await;
+        }
+        AgentEvent::SetReasoningEffort { effort, save } => {
+            apply_reasoning_effort(app, context, effort, save);
+        }
+        AgentEvent::SetBackendSetting { key, value, save } => {
+            apply_backend_setting(app, context, key, value, save);
+        }
+        AgentEvent::SwitchAgent { name } => {
+            apply_agent_switch(app, context, name).await;
         }
         other_event => {
             app.handle_agent_event(other_event);
@@ -173,7 +203,7 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
     }
 }
 
-pub(crate) fn apply_backend_switch(
+pub(crate) async fn apply_backend_switch(
     app: &mut AppState,
     context: &mut EventLoopContext,
     new_backend: Option<String>,
@@ -211,6 +241,7 @@ pub(crate) fn apply_backend_switch(
 
     context.runtime.config = staged;
     context.system_resources.backend = new_backend_arc;
+    record_conversation_backend(context).await;
 
     let mut summary = format!(
         "Switched to backend '{}' (model: {})",
@@ -239,12 +270,177 @@ pub(crate) fn apply_backend_switch(
     );
 }
 
+/// Remember the active backend and model on the conversation, so resuming
+/// it later runs on them again.
+async fn record_conversation_backend(context: &EventLoopContext) {
+    let backend = &context.system_resources.backend;
+    context
+        .conversation_state
+        .conversation
+        .lock()
+        .await
+        .set_backend(backend.backend_name(), backend.model_name());
+}
+
+/// Make `name` the active agent: its prompt joins the conversation and the
+/// backend moves to the agent's configured backend/model, or back to the
+/// global default when it has none.
+pub(crate) async fn apply_agent_switch(
+    app: &mut AppState,
+    context: &mut EventLoopContext,
+    name: String,
+) {
+    let Some(agent) = context.system_resources.agent_manager.get_agent(&name) else {
+        app.add_status_message(&format!("Switch failed: agent '{name}' not found\n"));
+        return;
+    };
+
+    let backend = match crate::session::backend_for_agent(&context.runtime.agent_base_config, &name)
+    {
+        Ok(b) => b,
+        Err(e) => {
+            app.add_status_message(&format!("Switch failed: {e}\n"));
+            return;
+        }
+    };
+
+    context
+        .conversation_state
+        .conversation
+        .lock()
+        .await
+        .add_system_message(project_instructions::apply(
+            &agent.content,
+            project_instructions::load(Path::new(&context.runtime.working_dir)).as_ref(),
+        ));
+    context.runtime.config = context
+        .runtime
+        .agent_base_config
+        .with_agent_overrides(&name);
+    context
+        .system_resources
+        .subagent_backend
+        .set(Arc::clone(&backend));
+    context.system_resources.backend = backend;
+    context.conversation_state.current_agent_name = name;
+    record_conversation_backend(context).await;
+
+    app.add_status_message(&format!(
+        "Switched to agent '{}' (backend: {}, model: {})\n",
+        context.conversation_state.current_agent_name,
+        context.system_resources.backend.backend_name(),
+        context.system_resources.backend.model_name()
+    ));
+    tracing::info!(
+        target: "hoosh::session",
+        "agent switched to {}",
+        context.conversation_state.current_agent_name
+    );
+}
+
+/// Apply `/effort` to the active backend: an effort level for OpenAI-compatible
+/// backends, the matching thinking budget for Anthropic.
+pub(crate) fn apply_reasoning_effort(
+    app: &mut AppState,
+    context: &mut EventLoopContext,
+    effort: Option<ReasoningEffort>,
+    save: bool,
+) {
+    let backend_name = context.system_resources.backend.backend_name().to_string();
+
+    let mut staged = context.runtime.config.clone();
+    let Some(cfg) = staged.backends.get_mut(&backend_name) else {
+        app.add_status_message(&format!(
+            "Effort unchanged: backend '{backend_name}' not configured\n"
+        ));
+        return;
+    };
+    match BackendKind::from_str(&backend_name) {
+        Ok(BackendKind::Anthropic) => {
+            cfg.thinking_budget = effort.map(|e| e.thinking_budget());
+        }
+        Ok(BackendKind::OpenAi | BackendKind::Groq) => cfg.reasoning_effort = effort,
+        _ => {
+            app.add_status_message(&format!(
+                "Effort unchanged: backend '{backend_name}' has no reasoning controls\n"
+            ));
+            return;
+        }
+    }
+
+    let built = crate::backends::backend_factory::create_backend(&backend_name, &staged);
+    let new_backend_arc: Arc<dyn LlmBackend> = match built {
+        Ok(b) => Arc::from(b),
+        Err(e) => {
+            app.add_status_message(&format!("Effort change failed: {e}\n"));
+            return;
+        }
+    };
+
+    context.runtime.config = staged;
+    context.system_resources.backend = new_backend_arc;
+
+    let mut summary = match effort {
+        Some(e) => format!("Reasoning effort set to {} on '{backend_name}'", e.as_str()),
+        None => format!("Reasoning turned off on '{backend_name}'"),
+    };
+    if save {
+        match context.runtime.config.save() {
+            Ok(()) => summary.push_str(" [saved]"),
+            Err(e) => summary.push_str(&format!(" [save failed: {e}]")),
+        }
+    }
+    summary.push('\n');
+    app.add_status_message(&summary);
+}
+
+/// Apply a single backend setting such as `/temp` or `/top_p` to the active
+/// backend, for the turns that follow.
+pub(crate) fn apply_backend_setting(
+    app: &mut AppState,
+    context: &mut EventLoopContext,
+    key: String,
+    value: String,
+    save: bool,
+) {
+    let backend_name = context.system_resources.backend.backend_name().to_string();
+
+    let mut staged = context.runtime.config.clone();
+    if let Err(e) = staged.update_backend_setting(&backend_name, &key, value.clone()) {
+        app.add_status_message(&format!("{key} unchanged: {e}\n"));
+        return;
+    }
+
+    let built = crate::backends::backend_factory::create_backend(&backend_name, &staged);
+    let new_backend_arc: Arc<dyn LlmBackend> = match built {
+        Ok(b) => Arc::from(b),
+        Err(e) => {
+            app.add_status_message(&format!("{key} change failed: {e}\n"));
+            return;
+        }
+    };
+
+    context.runtime.config = staged;
+    context.system_resources.backend = new_backend_arc;
+
+    let mut summary = format!("{key} set to {value} on '{backend_name}'");
+    if save {
+        match context.runtime.config.save() {
+            Ok(()) => summary.push_str(" [saved]"),
+            Err(e) => summary.push_str(&format!(" [save failed: {e}]")),
+        }
+    }
+    summary.push('\n');
+    app.add_status_message(&summary);
+}
+
 async fn handle_user_input(
     event: &event::Event,
     app: &mut AppState,
     agent_task: &mut Option<JoinHandle<()>>,
     context: &mut EventLoopContext,
 ) -> Result<()> {
+    track_focus(event);
     let agent_task_active = agent_task.is_some();
 
     // Process handlers one at a time, stopping when one handles the event
@@ -261,6 +457,15 @@ async fn handle_user_input(
     Ok(())
 }
 
+/// Focus reports only matter to notifications; no handler needs to see them.
+pub(crate) fn track_focus(event: &event::Event) {
+    match event {
+        event::Event::FocusGained => crate::notification::set_terminal_focused(true),
+        event::Event::FocusLost => crate::notification::set_terminal_focused(false),
+        _ => {}
+    }
+}
+
 async fn process_handler_result(
     result: super::handler_result::KeyHandlerResult,
     app: &mut AppState,
@@ -287,6 +492,18 @@ async fn process_handler_result(
             execute_command(input, context);
             true
         }
+        KeyHandlerResult::ToggleTaskItem {
+            message_index,
+            line,
+            checked,
+        } => {
+            apply_task_toggle(app, context, message_index, line, checked);
+            true
+        }
+        KeyHandlerResult::CycleReplyVariant { forward } => {
+            apply_variant_cycle(app, context, forward);
+            true
+        }
         KeyHandlerResult::StartConversation {
             input,
             image_attachments,
@@ -297,6 +514,53 @@ async fn process_handler_result(
     }
 }
 
+/// Write a `/tasks` checkbox toggle back into the assistant message so the
+/// model sees the updated list next turn. A running turn holds the
+/// conversation lock, so the toggle is undone rather than blocking the UI.
+pub(crate) fn apply_task_toggle(
+    app: &mut AppState,
+    context: &EventLoopContext,
+    message_index: usize,
+    line: usize,
+    checked: bool,
+) {
+    let Ok(mut conv) = context.conversation_state.conversation.try_lock() else {
+        if let Some(task_list) = app.task_list.as_mut() {
+            task_list.toggle_selected();
+        }
+        app.add_status_message("Can't edit the task list while hoosh is working");
+        return;
+    };
+    if !conv.set_task_item_checked(message_index, line, checked) {
+        drop(conv);
+        app.hide_task_list();
+        app.add_status_message("Task list is out of date; run /tasks again");
+    }
+}
+
+/// Swap in another `/regenerate` answer and show it.
+pub(crate) fn apply_variant_cycle(app: &mut AppState, context: &EventLoopContext, forward: bool) {
+    let Ok(mut conv) = context.conversation_state.conversation.try_lock() else {
+        return;
+    };
+    let Some((position, count)) = conv.cycle_reply_variant(forward) else {
+        drop(conv);
+        app.add_status_message("No other answers; use /regenerate to get one");
+        return;
+    };
+    let reply = conv
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == crate::agent::Role::Assistant)
+        .and_then(|m| m.content.clone())
+        .unwrap_or_default();
+    drop(conv);
+
+    app.add_status_message(&format!("Answer {} of {}", position, count));
+    app.add_final_response(&reply);
+}
+
 /// Shared cancel implementation for all three event-loop variants.
 ///
 /// Splits behaviour by whether any tool calls fired this turn:
@@ -75,6 +75,13 @@ pub async fn run_event_loop(
 
                 handle_user_input(&event, app, &mut agent_task, &mut context).await?;
 
+                if let Some(reference) = app.editor_request.take() {
+                    let status = super::editor::open(&mut terminal, true, &app.editor, &reference);
+                    app.add_status_message(&status);
+                    // The editor consumed stdin; start reading events afresh.
+                    event_stream = EventStream::new();
+                }
+
                 if !is_mouse_scroll {
                     render_frame(app, &mut terminal, &message_renderer)?;
                 }
@@ -100,6 +107,9 @@ fn render_frame(
 ) -> Result<()> {
     let has_pending = app.has_pending_messages();
     let _ = app.drain_pending_messages();
+    let streaming_table_width =
+        app.table_width((terminal.size()?.width as usize).saturating_sub(3));
+    app.streaming.set_table_width(streaming_table_width);
 
     terminal.draw(|frame| {
         let area = frame.area();
@@ -130,10 +140,11 @@ fn render_frame(
             message_area.width
         } as usize;
 
+        let table_width = app.table_width(content_width.saturating_sub(3));
         let was_at_bottom = app.scroll.at_bottom();
 
         app.scroll.content_length =
-            calculate_wrapped_line_count(app, content_width).saturating_add(10);
+            calculate_wrapped_line_count(app, content_width, table_width).saturating_add(10);
 
         if has_pending && was_at_bottom {
             app.scroll.scroll_to_bottom();
@@ -141,7 +152,7 @@ fn render_frame(
         app.scroll.clamp();
         app.scroll.sync_bar();
 
-        render_messages_fullview(app, message_area, frame.buffer_mut());
+        render_messages_fullview(app, message_area, table_width, frame.buffer_mut());
         layout.render(app, ui_area, frame.buffer_mut());
 
         // Position cursor in the input field
@@ -177,7 +188,7 @@ fn render_frame(
     Ok(())
 }
 
-fn calculate_wrapped_line_count(app: &AppState, content_width: usize) -> usize {
+fn calculate_wrapped_line_count(app: &AppState, content_width: usize, table_width: usize) -> usize {
     use crate::tui::markdown::MarkdownRenderer;
 
     let markdown_renderer = MarkdownRenderer::new();
@@ -198,8 +209,11 @@ fn calculate_wrapped_line_count(app: &AppState, content_width: usize) -> usize {
                     textwrap::wrap(&line_text, content_width).len()
                 }
             }
+            MessageLine::Markdown(md) if app.raw_output => {
+                calculate_wrapped_lines_for_text(md, content_width)
+            }
             MessageLine::Markdown(md) => {
-                let rendered = markdown_renderer.render(md);
+                let rendered = markdown_renderer.render_with_indent(md, "  ", table_width);
                 calculate_wrapped_lines_for_styled_lines(&rendered, content_width)
             }
             MessageLine::Thinking(text) => {
@@ -214,6 +228,7 @@ fn calculate_wrapped_line_count(app: &AppState, content_width: usize) -> usize {
                 }
                 count
             }
+            MessageLine::Image(_) => 1,
         };
     }
 
@@ -254,6 +269,7 @@ fn calculate_wrapped_lines_for_styled_lines(lines: &[Line], width: usize) -> usi
 fn render_messages_fullview(
     app: &mut AppState,
     area: ratatui::layout::Rect,
+    table_width: usize,
     buf: &mut ratatui::buffer::Buffer,
 ) {
     use crate::tui::markdown::MarkdownRenderer;
@@ -273,8 +289,19 @@ fn render_messages_fullview(
             MessageLine::Styled(line) => {
                 all_lines.push(line.clone());
             }
+            MessageLine::Image(image) => {
+                use crate::tui::colors::palette;
+                use ratatui::style::Style;
+                all_lines.push(Line::from(Span::styled(
+                    image.caption.clone(),
+                    Style::default().fg(palette::MARKDOWN_LINK),
+                )));
+            }
+            MessageLine::Markdown(md) if app.raw_output => {
+                all_lines.extend(md.lines().map(|l| Line::from(Span::raw(l.to_string()))));
+            }
             MessageLine::Markdown(md) => {
-                let rendered = markdown_renderer.render(md);
+                let rendered = markdown_renderer.render_with_indent(md, "  ", table_width);
                 all_lines.extend(rendered);
             }
             MessageLine::Thinking(text) => {
@@ -346,13 +373,14 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             descriptor,
             request_id,
         } => {
-            app.show_tool_permission_dialog(descriptor, request_id);
+            app.show_tool_permission_dialog(*descriptor, request_id);
         }
         AgentEvent::ApprovalRequest {
             tool_call_id,
             tool_name,
+            preview,
         } => {
-            app.show_approval_dialog(tool_call_id, tool_name);
+            app.show_approval_dialog(tool_call_id, tool_name, preview);
         }
         AgentEvent::Exit => {
             app.should_quit = true;
@@ -365,7 +393,16 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             model,
             save,
         } => {
-            super::app_loop::apply_backend_switch(app, context, backend, model, save);
+            super::app_loop::apply_backend_switch(app, context, backend, model, save).await;
+        }
+        AgentEvent::SetReasoningEffort { effort, save } => {
+            super::app_loop::apply_reasoning_effort(app, context, effort, save);
+        }
+        AgentEvent::SetBackendSetting { key, value, save } => {
+            super::app_loop::apply_backend_setting(app, context, key, value, save);
+        }
+        AgentEvent::SwitchAgent { name } => {
+            super::app_loop::apply_agent_switch(app, context, name).await;
         }
         AgentEvent::DebugMessage(msg) => {
             tracing::debug!(target: "hoosh::agent", "{}", msg);
@@ -385,6 +422,7 @@ async fn handle_user_input(
     agent_task: &mut Option<JoinHandle<()>>,
     context: &mut EventLoopContext,
 ) -> Result<()> {
+    super::app_loop::track_focus(event);
     let agent_task_active = agent_task.is_some();
 
     let handler_count = context.runtime.input_handlers.len();
@@ -426,6 +464,18 @@ async fn process_handler_result(
             execute_command(input, context);
             true
         }
+        KeyHandlerResult::ToggleTaskItem {
+            message_index,
+            line,
+            checked,
+        } => {
+            super::app_loop::apply_task_toggle(app, context, message_index, line, checked);
+            true
+        }
+        KeyHandlerResult::CycleReplyVariant { forward } => {
+            super::app_loop::apply_variant_cycle(app, context, forward);
+            true
+        }
         KeyHandlerResult::StartConversation {
             input,
             image_attachments,
@@ -44,6 +44,11 @@ pub async fn run_event_loop(
             handle_user_input(&event, app, &mut agent_task, &mut context).await?;
         }
 
+        if let Some(reference) = app.editor_request.take() {
+            let status = super::editor::open(&mut terminal, false, &app.editor, &reference);
+            app.add_status_message(&status);
+        }
+
         if app.should_quit {
             break;
         }
@@ -138,13 +143,14 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             descriptor,
             request_id,
         } => {
-            app.show_tool_permission_dialog(descriptor, request_id);
+            app.show_tool_permission_dialog(*descriptor, request_id);
         }
         AgentEvent::ApprovalRequest {
             tool_call_id,
             tool_name,
+            preview,
         } => {
-            app.show_approval_dialog(tool_call_id, tool_name);
+            app.show_approval_dialog(tool_call_id, tool_name, preview);
         }
         AgentEvent::Exit => {
             app.should_quit = true;
@@ -163,7 +169,16 @@ async fn handle_agent_event(app: &mut AppState, event: AgentEvent, context: &mut
             model,
             save,
         } => {
-            super::app_loop::apply_backend_switch(app, context, backend, model, save);
+            super::app_loop::apply_backend_switch(app, context, backend, model, save).await;
+        }
+        AgentEvent::SetReasoningEffort { effort, save } => {
+            super::app_loop::apply_reasoning_effort(app, context, effort, save);
+        }
+        AgentEvent::SetBackendSetting { key, value, save } => {
+            super::app_loop::apply_backend_setting(app, context, key, value, save);
+        }
+        AgentEvent::SwitchAgent { name } => {
+            super::app_loop::apply_agent_switch(app, context, name).await;
         }
         other_event => {
             app.handle_agent_event(other_event);
@@ -177,6 +192,7 @@ async fn handle_user_input(
     agent_task: &mut Option<JoinHandle<()>>,
     context: &mut EventLoopContext,
 ) -> Result<()> {
+    super::app_loop::track_focus(event);
     let agent_task_active = agent_task.is_some();
 
     // Process handlers one at a time, stopping when one handles the event
@@ -219,6 +235,18 @@ async fn process_handler_result(
             execute_command(input, context);
             true
         }
+        KeyHandlerResult::ToggleTaskItem {
+            message_index,
+            line,
+            checked,
+        } => {
+            super::app_loop::apply_task_toggle(app, context, message_index, line, checked);
+            true
+        }
+        KeyHandlerResult::CycleReplyVariant { forward } => {
+            super::app_loop::apply_variant_cycle(app, context, forward);
+            true
+        }
         KeyHandlerResult::StartConversation {
             input,
             image_attachments,
@@ -31,6 +31,15 @@ pub mod palette {
     pub const MARKDOWN_TASK_MARKER: Color = Color::Green;
     pub const MARKDOWN_RULE: Color = Color::DarkGray;
 
+    // Diff colors
+    pub const DIFF_ADDED: Color = Color::Green;
+    pub const DIFF_REMOVED: Color = Color::Red;
+    pub const DIFF_ADDED_EMPHASIS_BG: Color = Color::Rgb(25, 75, 40);
+    pub const DIFF_REMOVED_EMPHASIS_BG: Color = Color::Rgb(90, 30, 35);
+    pub const DIFF_HUNK: Color = Color::Cyan;
+    pub const DIFF_CONTEXT: Color = Color::Gray;
+    pub const DIFF_FOLD: Color = Color::DarkGray;
+
     // Header colors (RGB for custom styling)
     pub const HEADER_LOGO: Color = Color::Rgb(142, 240, 204);
     pub const HEADER_TITLE: Color = Color::Rgb(255, 255, 255);
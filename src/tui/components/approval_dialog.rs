@@ -1,6 +1,6 @@
 use crate::tui::component::Component;
 use crate::tui::palette;
-use crate::tui::state::AppState;
+use crate::tui::state::{AppState, ApprovalDialogState};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -9,8 +9,22 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
+/// Diff lines shown before the rest is summarized as "... (N more lines)".
+pub const APPROVAL_MAX_PREVIEW_LINES: usize = 15;
+
 pub struct ApprovalDialog;
 
+/// Content height: tool name, spacer, two options, spacer and help, plus the
+/// preview (capped, with its overflow notice) and a spacer after it.
+pub fn approval_dialog_height(dialog_state: &ApprovalDialogState) -> u16 {
+    let preview = dialog_state.preview.len();
+    if preview == 0 {
+        return 6;
+    }
+    let overflow = (preview > APPROVAL_MAX_PREVIEW_LINES) as usize;
+    (6 + preview.min(APPROVAL_MAX_PREVIEW_LINES) + overflow + 1) as u16
+}
+
 impl Component for ApprovalDialog {
     type State = AppState;
 
@@ -27,6 +41,30 @@ impl Component for ApprovalDialog {
 
             lines.push(Line::from(""));
 
+            // Diff preview
+            if !dialog_state.preview.is_empty() {
+                lines.extend(
+                    dialog_state
+                        .preview
+                        .iter()
+                        .take(APPROVAL_MAX_PREVIEW_LINES)
+                        .cloned(),
+                );
+                let hidden = dialog_state
+                    .preview
+                    .len()
+                    .saturating_sub(APPROVAL_MAX_PREVIEW_LINES);
+                if hidden > 0 {
+                    lines.push(Line::from(Span::styled(
+                        format!("... ({} more lines)", hidden),
+                        Style::default()
+                            .fg(palette::DIMMED_TEXT)
+                            .add_modifier(Modifier::ITALIC),
+                    )));
+                }
+                lines.push(Line::from(""));
+            }
+
             // Options
             let options = [("y", "Approve"), ("n", "Reject")];
 
@@ -0,0 +1,74 @@
+use crate::tui::colors::palette;
+use crate::tui::component::Component;
+use crate::tui::state::AppState;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+pub const ATTACHMENTS_MAX_VISIBLE: usize = 5;
+
+/// Lists the attachments queued on the draft, just above the input, so large
+/// pastes and images can be checked or dropped before the prompt is sent.
+pub struct AttachmentsBar;
+
+impl Component for AttachmentsBar {
+    type State = AppState;
+
+    fn render(&self, state: &Self::State, area: Rect, buf: &mut Buffer) {
+        if state.attachments.is_empty() {
+            return;
+        }
+
+        let summaries = state.attachments.summaries();
+        let total = summaries.len();
+        let focused = state.attachments.focused_index().unwrap_or(0);
+        // Keep the focused row on screen when there are more than fit.
+        let first = focused.saturating_sub(ATTACHMENTS_MAX_VISIBLE - 1);
+        let mut lines: Vec<Line> = Vec::with_capacity(ATTACHMENTS_MAX_VISIBLE + 2);
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Attachments ({total})"),
+                Style::default().fg(palette::SECONDARY_TEXT),
+            ),
+            Span::styled(
+                " · alt+a select · alt+x remove",
+                Style::default().fg(palette::DIMMED_TEXT),
+            ),
+        ]));
+
+        for (idx, summary) in summaries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(ATTACHMENTS_MAX_VISIBLE)
+        {
+            let (prefix, name_color) = if idx == focused {
+                ("› ", palette::PRIMARY_TEXT)
+            } else {
+                ("  ", palette::SUBDUED_TEXT)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(palette::SECONDARY_TEXT)),
+                Span::styled(summary.name.clone(), Style::default().fg(name_color)),
+                Span::styled(
+                    format!("  {}", summary.detail),
+                    Style::default().fg(palette::SUBDUED_TEXT),
+                ),
+            ]));
+        }
+
+        if total > ATTACHMENTS_MAX_VISIBLE {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  …and {} more", total - ATTACHMENTS_MAX_VISIBLE),
+                Style::default().fg(palette::SUBDUED_TEXT),
+            )]));
+        }
+
+        Paragraph::new(lines).render(area, buf);
+    }
+}
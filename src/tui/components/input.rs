@@ -1,8 +1,11 @@
 use crate::tui::component::Component;
+use crate::tui::palette;
 use crate::tui::state::AppState;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Span,
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
@@ -13,7 +16,13 @@ impl Component for Input {
 
     fn render(&self, state: &Self::State, area: Rect, buf: &mut Buffer) {
         let input_widget = state.input.widget();
-        let input_block = Block::default().borders(Borders::BOTTOM | Borders::TOP);
+        let mut input_block = Block::default().borders(Borders::BOTTOM | Borders::TOP);
+        if let Some(search) = &state.history_search {
+            input_block = input_block.title(Span::styled(
+                search.label(),
+                Style::default().fg(palette::WARNING),
+            ));
+        }
 
         let inner_area = input_block.inner(area);
         input_block.render(area, buf);
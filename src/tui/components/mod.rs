@@ -1,18 +1,24 @@
 pub mod active_tool_calls;
 pub mod approval_dialog;
+pub mod attachments_bar;
 pub mod completion_popup;
 pub mod input;
 pub mod mode_indicator;
 pub mod permission_dialog;
 pub mod queued_prompts;
 pub mod status_bar;
+pub mod streaming_tail;
+pub mod task_list_popup;
 pub mod todo_list;
 pub use active_tool_calls::ActiveToolCallsComponent;
 pub use approval_dialog::ApprovalDialog;
+pub use attachments_bar::AttachmentsBar;
 pub use completion_popup::CompletionPopup;
 pub use input::Input;
 pub use mode_indicator::ModeIndicator;
 pub use permission_dialog::PermissionDialog;
 pub use queued_prompts::QueuedPromptsComponent;
 pub use status_bar::StatusBar;
+pub use streaming_tail::StreamingTailComponent;
+pub use task_list_popup::TaskListPopup;
 pub use todo_list::TodoListComponent;
@@ -24,7 +24,9 @@ impl Component for PermissionDialog {
             let options_count = dialog_state.options.len() as u16;
             let summary = descriptor.command_summary();
             let summary_height = summary.is_some() as u16;
-            let fixed_chrome_height = 2 + 2 + summary_height + options_count + 2;
+            let breakdown = descriptor.summary();
+            let breakdown_height = breakdown.len() as u16;
+            let fixed_chrome_height = 2 + 2 + summary_height + breakdown_height + options_count + 2;
 
             let mut lines = vec![];
 
@@ -40,6 +42,13 @@ impl Component for PermissionDialog {
                 )]));
             }
 
+            for line in breakdown {
+                lines.push(Line::from(vec![Span::styled(
+                    line,
+                    Style::default().fg(palette::SECONDARY_TEXT),
+                )]));
+            }
+
             lines.push(Line::from(""));
 
             // 2. Calculate Dynamic Preview Height
@@ -2,6 +2,7 @@ use crate::tui::component::Component;
 use crate::tui::events::AgentState;
 use crate::tui::palette;
 use crate::tui::state::AppState;
+use crate::tui::text_utils::format_elapsed;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
@@ -40,6 +41,24 @@ fn radio_wave(frame: usize) -> String {
     out
 }
 
+/// "Thinking 12s" / "Running tools 3s" while the agent is busy and nothing
+/// more specific (a retry or a pending dialog) owns the status area.
+fn busy_label(state: &AppState) -> Option<String> {
+    if state.current_retry_status.is_some()
+        || state.is_showing_tool_permission_dialog()
+        || state.is_showing_approval_dialog()
+    {
+        return None;
+    }
+    let label = match state.agent_state {
+        AgentState::Idle => return None,
+        AgentState::Thinking => "Thinking",
+        AgentState::ExecutingTools => "Running tools",
+    };
+    let elapsed = state.animation.phase_elapsed(state.agent_state)?;
+    Some(format!("{} {}", label, format_elapsed(elapsed)))
+}
+
 impl Component for StatusBar {
     type State = AppState;
 
@@ -67,6 +86,7 @@ impl Component for StatusBar {
                 ),
             }
         };
+        let phase_text = busy_label(state);
 
         let token_text = if state.metrics.has_usage() {
             format!(
@@ -93,9 +113,14 @@ impl Component for StatusBar {
         .split(area);
 
         if !status_text.is_empty() {
-            let status_line =
-                Line::from(Span::styled(status_text, Style::default().fg(status_color)));
-            let paragraph = Paragraph::new(status_line);
+            let mut spans = vec![Span::styled(status_text, Style::default().fg(status_color))];
+            if let Some(phase_text) = phase_text {
+                spans.push(Span::styled(
+                    format!(" {}", phase_text),
+                    Style::default().fg(palette::SUBDUED_TEXT),
+                ));
+            }
+            let paragraph = Paragraph::new(Line::from(spans));
             paragraph.render(areas[0], buf);
         }
 
@@ -0,0 +1,22 @@
+use crate::tui::component::Component;
+use crate::tui::state::AppState;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Paragraph, Widget},
+};
+
+/// The not-yet-committed end of a streaming response, rendered live above the
+/// status bar. Finished blocks have already moved into the transcript, so this
+/// only ever holds the block currently being written.
+pub struct StreamingTailComponent;
+
+impl Component for StreamingTailComponent {
+    type State = AppState;
+
+    fn render(&self, state: &Self::State, area: Rect, buf: &mut Buffer) {
+        let lines = state.streaming.tail_lines();
+        let skip = lines.len().saturating_sub(area.height as usize);
+        Paragraph::new(lines[skip..].to_vec()).render(area, buf);
+    }
+}
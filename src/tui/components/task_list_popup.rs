@@ -0,0 +1,79 @@
+use crate::tui::component::Component;
+use crate::tui::palette;
+use crate::tui::state::AppState;
+use ratatui::text::{Line, Span};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, Widget},
+};
+
+pub const TASK_LIST_MAX_VISIBLE: usize = 10;
+
+pub struct TaskListPopup;
+
+impl Component for TaskListPopup {
+    type State = AppState;
+
+    fn render(&self, state: &AppState, area: Rect, buf: &mut Buffer) {
+        let Some(task_list) = &state.task_list else {
+            return;
+        };
+
+        let scroll_offset = task_list
+            .selected_index
+            .saturating_sub(TASK_LIST_MAX_VISIBLE - 1);
+        let items: Vec<ListItem> = task_list
+            .items
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(TASK_LIST_MAX_VISIBLE)
+            .map(|(idx, item)| {
+                let is_selected = idx == task_list.selected_index;
+                let (marker, marker_color) = if item.checked {
+                    ("[✓] ", palette::MARKDOWN_TASK_MARKER)
+                } else {
+                    ("[ ] ", palette::SECONDARY_TEXT)
+                };
+                let text_style = if is_selected {
+                    Style::default()
+                        .fg(palette::SELECTED_FG)
+                        .bg(palette::SELECTED_BG)
+                        .add_modifier(Modifier::BOLD)
+                } else if item.checked {
+                    Style::default().fg(palette::SECONDARY_TEXT)
+                } else {
+                    Style::default().fg(palette::PRIMARY_TEXT)
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::raw(if is_selected { "> " } else { "  " }),
+                    Span::styled(marker, Style::default().fg(marker_color)),
+                    Span::styled(item.text.clone(), text_style),
+                ]))
+            })
+            .collect();
+
+        let title = Span::styled(
+            format!(
+                " Tasks ( {} / {} done ) · space toggle · esc close ",
+                task_list.completed_count(),
+                task_list.items.len()
+            ),
+            Style::default()
+                .fg(palette::PRIMARY_BORDER)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::PRIMARY_BORDER));
+
+        List::new(items).block(block).render(area, buf);
+    }
+}
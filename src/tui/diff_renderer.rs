@@ -0,0 +1,412 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use similar::{ChangeTag, DiffTag, TextDiff};
+
+use crate::tui::palette;
+
+/// Unchanged lines kept around each change when folding.
+const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Styled diff lines for the TUI, from old/new text or a unified diff.
+///
+/// Each line gets a `+`/`-`/space gutter. When a run of removed lines is
+/// directly followed by added lines, they are paired up and the words that
+/// actually changed are highlighted within each pair. With folding on, runs
+/// of unchanged lines beyond the context radius collapse into one marker.
+pub struct DiffRenderer {
+    context: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row<'a> {
+    /// `---`/`+++` file headers and `diff --git` lines.
+    Header(&'a str),
+    Hunk(&'a str),
+    Context(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+    Fold(usize),
+    /// Anything that isn't diff syntax, e.g. "No changes to x".
+    Text(&'a str),
+}
+
+impl DiffRenderer {
+    pub fn new() -> Self {
+        Self {
+            context: Some(DEFAULT_CONTEXT_LINES),
+        }
+    }
+
+    /// Unchanged lines kept around each change; `None` shows every line.
+    pub fn with_context(mut self, context: Option<usize>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Diff `old` against `new` line by line.
+    pub fn render(&self, old: &str, new: &str) -> Vec<Line<'static>> {
+        let diff = TextDiff::from_lines(old, new);
+        let old_lines = diff.old_slices().len();
+        let mut rows = Vec::new();
+
+        match self.context {
+            None => {
+                for change in diff.iter_all_changes() {
+                    rows.push(change_row(change.tag(), change.value()));
+                }
+            }
+            Some(radius) => {
+                let mut shown_up_to = 0;
+                for group in diff.grouped_ops(radius) {
+                    let Some(first) = group.first() else {
+                        continue;
+                    };
+                    let skipped = first.old_range().start - shown_up_to;
+                    if skipped > 0 {
+                        rows.push(Row::Fold(skipped));
+                    }
+                    for op in &group {
+                        for change in diff.iter_changes(op) {
+                            rows.push(change_row(change.tag(), change.value()));
+                        }
+                        shown_up_to = op.old_range().end;
+                    }
+                }
+                // `grouped_ops` returns nothing for identical input.
+                let identical = diff.ops().iter().all(|op| op.tag() == DiffTag::Equal);
+                if !identical && old_lines > shown_up_to {
+                    rows.push(Row::Fold(old_lines - shown_up_to));
+                }
+            }
+        }
+
+        emit(&rows)
+    }
+
+    /// Style an existing unified diff, such as a file-tool preview or
+    /// `git diff` output. Folding already happened when the diff was made.
+    pub fn render_unified(&self, diff: &str) -> Vec<Line<'static>> {
+        let mut in_hunk = false;
+        let rows: Vec<Row> = diff
+            .lines()
+            .map(|line| {
+                let row = unified_row(line, in_hunk);
+                match row {
+                    Row::Hunk(_) => in_hunk = true,
+                    Row::Header(_) | Row::Text(_) => in_hunk = false,
+                    _ => {}
+                }
+                row
+            })
+            .collect();
+        emit(&rows)
+    }
+}
+
+impl Default for DiffRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn change_row(tag: ChangeTag, value: &str) -> Row<'_> {
+    let text = value.strip_suffix('\n').unwrap_or(value);
+    let text = text.strip_suffix('\r').unwrap_or(text);
+    match tag {
+        ChangeTag::Equal => Row::Context(text),
+        ChangeTag::Delete => Row::Removed(text),
+        ChangeTag::Insert => Row::Added(text),
+    }
+}
+
+/// Lines `git diff` writes between `diff --git` and the first hunk, plus its
+/// stand-in for binary content. None can start a hunk line, so unlike
+/// `---`/`+++` they are headers wherever they appear.
+const GIT_HEADER_PREFIXES: &[&str] = &[
+    "diff ",
+    "index ",
+    "new file mode ",
+    "deleted file mode ",
+    "old mode ",
+    "new mode ",
+    "similarity index ",
+    "dissimilarity index ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+    "Binary files ",
+];
+
+/// Inside a hunk, `--- x` is a removed line that happens to start with
+/// `--`, not a file header.
+fn unified_row(line: &str, in_hunk: bool) -> Row<'_> {
+    let file_header = line.starts_with("--- ") || line.starts_with("+++ ");
+    if GIT_HEADER_PREFIXES.iter().any(|p| line.starts_with(p)) || (file_header && !in_hunk) {
+        Row::Header(line)
+    } else if line.starts_with("@@") {
+        Row::Hunk(line)
+    } else if let Some(rest) = line.strip_prefix('+') {
+        Row::Added(rest)
+    } else if let Some(rest) = line.strip_prefix('-') {
+        Row::Removed(rest)
+    } else if let Some(rest) = line.strip_prefix(' ') {
+        Row::Context(rest)
+    } else {
+        Row::Text(line)
+    }
+}
+
+fn emit(rows: &[Row]) -> Vec<Line<'static>> {
+    let mut lines = Vec::with_capacity(rows.len());
+    let mut i = 0;
+    while i < rows.len() {
+        let removed = run_len(&rows[i..], |r| matches!(r, Row::Removed(_)));
+        if removed > 0 {
+            let added = run_len(&rows[i + removed..], |r| matches!(r, Row::Added(_)));
+            let old_run = &rows[i..i + removed];
+            let new_run = &rows[i + removed..i + removed + added];
+
+            for (k, row) in old_run.iter().enumerate() {
+                let Row::Removed(old) = row else { continue };
+                lines.push(match new_run.get(k) {
+                    Some(Row::Added(new)) => emphasized(old, new, ChangeTag::Delete),
+                    _ => plain_change(old, ChangeTag::Delete),
+                });
+            }
+            for (k, row) in new_run.iter().enumerate() {
+                let Row::Added(new) = row else { continue };
+                lines.push(match old_run.get(k) {
+                    Some(Row::Removed(old)) => emphasized(old, new, ChangeTag::Insert),
+                    _ => plain_change(new, ChangeTag::Insert),
+                });
+            }
+            i += removed + added;
+            continue;
+        }
+
+        lines.push(match rows[i] {
+            Row::Header(text) => Line::from(Span::styled(
+                text.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Row::Hunk(text) => Line::from(Span::styled(
+                text.to_string(),
+                Style::default().fg(palette::DIFF_HUNK),
+            )),
+            Row::Context(text) => Line::from(vec![
+                Span::raw("  "),
+                Span::styled(text.to_string(), Style::default().fg(palette::DIFF_CONTEXT)),
+            ]),
+            Row::Added(text) => plain_change(text, ChangeTag::Insert),
+            Row::Removed(text) => plain_change(text, ChangeTag::Delete),
+            Row::Fold(count) => Line::from(Span::styled(
+                format!(
+                    "  ⋯ {} unchanged line{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+                Style::default()
+                    .fg(palette::DIFF_FOLD)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+            Row::Text(text) => Line::from(text.to_string()),
+        });
+        i += 1;
+    }
+    lines
+}
+
+fn run_len(rows: &[Row], pred: impl Fn(&Row) -> bool) -> usize {
+    rows.iter().take_while(|r| pred(r)).count()
+}
+
+fn change_style(tag: ChangeTag) -> (&'static str, Style, Style) {
+    match tag {
+        ChangeTag::Insert => (
+            "+ ",
+            Style::default().fg(palette::DIFF_ADDED),
+            Style::default()
+                .fg(palette::DIFF_ADDED)
+                .bg(palette::DIFF_ADDED_EMPHASIS_BG)
+                .add_modifier(Modifier::BOLD),
+        ),
+        ChangeTag::Delete | ChangeTag::Equal => (
+            "- ",
+            Style::default().fg(palette::DIFF_REMOVED),
+            Style::default()
+                .fg(palette::DIFF_REMOVED)
+                .bg(palette::DIFF_REMOVED_EMPHASIS_BG)
+                .add_modifier(Modifier::BOLD),
+        ),
+    }
+}
+
+fn plain_change(text: &str, tag: ChangeTag) -> Line<'static> {
+    let (gutter, style, _) = change_style(tag);
+    Line::from(vec![
+        Span::styled(gutter, style),
+        Span::styled(text.to_string(), style),
+    ])
+}
+
+/// One side of a modified line pair, with the words that differ from the
+/// other side emphasized. `side` is `Delete` for the old line and `Insert`
+/// for the new one.
+fn emphasized(old: &str, new: &str, side: ChangeTag) -> Line<'static> {
+    let (gutter, style, emphasis) = change_style(side);
+    let mut spans = vec![Span::styled(gutter, style)];
+    let diff = TextDiff::from_words(old, new);
+    for change in diff.iter_all_changes() {
+        let span_style = match change.tag() {
+            ChangeTag::Equal => style,
+            tag if tag == side => emphasis,
+            _ => continue,
+        };
+        // Merge neighbours with the same style so each line stays a handful
+        // of spans rather than one per word.
+        let n = spans.len();
+        match spans.last_mut() {
+            Some(last) if n > 1 && last.style == span_style => {
+                last.content.to_mut().push_str(change.value());
+            }
+            _ => spans.push(Span::styled(change.value().to_string(), span_style)),
+        }
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain-text snapshot: one line per row, with emphasized runs wrapped
+    /// in `[...]` so intra-line highlighting is visible in the assertion.
+    fn snapshot(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| {
+                        if span.style.bg.is_some() {
+                            format!("[{}]", span.content)
+                        } else {
+                            span.content.to_string()
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_added_lines() {
+        let lines = DiffRenderer::new().render("a\nb\n", "a\nb\nc\nd\n");
+        assert_eq!(snapshot(&lines), "  a\n  b\n+ c\n+ d");
+        assert_eq!(lines[2].spans[0].style.fg, Some(palette::DIFF_ADDED));
+    }
+
+    #[test]
+    fn test_removed_lines() {
+        let lines = DiffRenderer::new().render("a\nb\nc\n", "a\n");
+        assert_eq!(snapshot(&lines), "  a\n- b\n- c");
+        assert_eq!(lines[1].spans[0].style.fg, Some(palette::DIFF_REMOVED));
+    }
+
+    #[test]
+    fn test_modified_line_highlights_changed_words() {
+        let lines = DiffRenderer::new().render(
+            "fn main() {\n    let x = 1;\n}\n",
+            "fn main() {\n    let y = 1;\n}\n",
+        );
+        assert_eq!(
+            snapshot(&lines),
+            "  fn main() {\n-     let [x] = 1;\n+     let [y] = 1;\n  }"
+        );
+    }
+
+    #[test]
+    fn test_unpaired_lines_in_a_replacement_are_not_emphasized() {
+        let lines = DiffRenderer::new().render("one\n", "uno\ndos\n");
+        assert_eq!(snapshot(&lines), "- [one]\n+ [uno]\n+ dos");
+    }
+
+    #[test]
+    fn test_context_folding() {
+        let old: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+        let new = old.replace("line 10\n", "line ten\n");
+
+        let folded = DiffRenderer::new().with_context(Some(1)).render(&old, &new);
+        assert_eq!(
+            snapshot(&folded),
+            "  ⋯ 8 unchanged lines\n  line 9\n- line [10]\n+ line [ten]\n  line 11\n  ⋯ 9 unchanged lines"
+        );
+
+        let full = DiffRenderer::new().with_context(None).render(&old, &new);
+        assert_eq!(full.len(), 21);
+    }
+
+    #[test]
+    fn test_identical_input_renders_context_only() {
+        assert!(DiffRenderer::new().render("same\n", "same\n").is_empty());
+        assert_eq!(
+            DiffRenderer::new()
+                .with_context(None)
+                .render("same\n", "same\n")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let unified =
+            "--- a/x.rs\n+++ b/x.rs\n@@ -1,2 +1,2 @@\n keep\n-old value\n+new value\nNo newline";
+        let lines = DiffRenderer::new().render_unified(unified);
+        assert_eq!(
+            snapshot(&lines),
+            "--- a/x.rs\n+++ b/x.rs\n@@ -1,2 +1,2 @@\n  keep\n- [old] value\n+ [new] value\nNo newline"
+        );
+        assert!(
+            lines[0].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert_eq!(lines[2].spans[0].style.fg, Some(palette::DIFF_HUNK));
+    }
+
+    #[test]
+    fn test_unified_git_rename_and_binary_headers() {
+        let unified = "diff --git a/old.rs b/new.rs\n\
+                       similarity index 90%\n\
+                       rename from old.rs\n\
+                       rename to new.rs\n\
+                       @@ -1 +1 @@\n\
+                       -a\n\
+                       +b\n\
+                       diff --git a/logo.png b/logo.png\n\
+                       index 1234567..89abcde 100644\n\
+                       Binary files a/logo.png and b/logo.png differ";
+        let lines = DiffRenderer::new().render_unified(unified);
+
+        let bold: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.spans[0].style.add_modifier.contains(Modifier::BOLD))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(bold, vec![0, 1, 2, 3, 7, 8, 9]);
+        assert_eq!(snapshot(&lines[5..7]), "- [a]\n+ [b]");
+    }
+
+    #[test]
+    fn test_unified_removal_that_looks_like_a_header() {
+        let unified = "--- a/q.sql\n+++ b/q.sql\n@@ -1 +0,0 @@\n--- drop this comment";
+        let lines = DiffRenderer::new().render_unified(unified);
+        assert_eq!(snapshot(&lines[3..]), "- -- drop this comment");
+        assert_eq!(lines[3].spans[0].style.fg, Some(palette::DIFF_REMOVED));
+    }
+}
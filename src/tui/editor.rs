@@ -0,0 +1,186 @@
+//! Open `path:line` references from the transcript in the user's editor.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use super::state::{AppState, MessageLine};
+use super::terminal::{HooshTerminal, with_suspended_terminal};
+
+/// How many transcript lines back to look for references.
+const SEARCH_DEPTH: usize = 500;
+
+/// `[editor]` settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorConfig {
+    /// Command to run, with `{file}` and `{line}` placeholders, e.g.
+    /// `code -g {file}:{line}`. Defaults to `$VISUAL` or `$EDITOR` with
+    /// `+{line} {file}`.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Whether the editor runs in the terminal, so hoosh steps aside while
+    /// it is open. Set to false for GUI editors. Defaults to true.
+    #[serde(default)]
+    pub terminal: Option<bool>,
+}
+
+impl EditorConfig {
+    pub fn runs_in_terminal(&self) -> bool {
+        self.terminal.unwrap_or(true)
+    }
+
+    /// Arguments that open `reference`, or None if no editor is configured.
+    pub fn command_for(&self, reference: &FileReference) -> Option<Vec<String>> {
+        let template = match &self.command {
+            Some(command) => command.clone(),
+            None => format!("{} +{{line}} {{file}}", default_editor()?),
+        };
+        let args = substitute(&template, &reference.path, reference.line);
+        (!args.is_empty()).then_some(args)
+    }
+}
+
+fn default_editor() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.trim().is_empty()))
+}
+
+/// Split `template` into arguments and fill in `{file}` and `{line}`. The
+/// file is appended when the template doesn't mention it.
+pub fn substitute(template: &str, file: &str, line: usize) -> Vec<String> {
+    let words = shlex::split(template)
+        .unwrap_or_else(|| template.split_whitespace().map(String::from).collect());
+    let mentions_file = words.iter().any(|word| word.contains("{file}"));
+
+    let line = line.to_string();
+    let mut args: Vec<String> = words
+        .iter()
+        .map(|word| word.replace("{file}", file).replace("{line}", &line))
+        .collect();
+    if !mentions_file && !args.is_empty() {
+        args.push(file.to_string());
+    }
+    args
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReference {
+    pub path: String,
+    pub line: usize,
+}
+
+fn reference_pattern() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| {
+        Regex::new(r#"(?:^|[\s`'"(\[<])(/?(?:[\w.~-]+/)*[\w.-]*\w\.[A-Za-z0-9]+):(\d+)"#)
+            .expect("static regex compiles")
+    })
+}
+
+/// `path:line` references in `text`, in order.
+pub fn file_references(text: &str) -> Vec<FileReference> {
+    reference_pattern()
+        .captures_iter(text)
+        .filter_map(|caps| {
+            Some(FileReference {
+                path: caps[1].to_string(),
+                line: caps[2].parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// References in the transcript to files that exist, newest first.
+pub fn transcript_references(app: &AppState) -> Vec<FileReference> {
+    let mut references = Vec::new();
+    for message in app.messages.iter().rev().take(SEARCH_DEPTH) {
+        let (MessageLine::Plain(text) | MessageLine::Markdown(text)) = message else {
+            continue;
+        };
+        let found: Vec<FileReference> = file_references(text)
+            .into_iter()
+            .rev()
+            .filter(|r| Path::new(&r.path).is_file())
+            .filter(|r| !references.contains(r))
+            .collect();
+        references.extend(found);
+    }
+    references
+}
+
+/// Open `reference` with the configured editor, stepping the TUI aside for
+/// terminal editors. Returns a message for the status line.
+pub fn open(
+    terminal: &mut HooshTerminal,
+    alternate_screen: bool,
+    config: &EditorConfig,
+    reference: &FileReference,
+) -> String {
+    let Some(args) = config.command_for(reference) else {
+        return "No editor configured: set $EDITOR or [editor] command".to_string();
+    };
+    let launch = || Command::new(&args[0]).args(&args[1..]).status();
+
+    let status = if config.runs_in_terminal() {
+        match with_suspended_terminal(terminal, alternate_screen, launch) {
+            Ok(status) => status,
+            Err(e) => return format!("Couldn't hand the terminal to {}: {}", args[0], e),
+        }
+    } else {
+        launch()
+    };
+
+    match status {
+        Ok(status) if status.success() => {
+            format!("Opened {}:{}", reference.path, reference.line)
+        }
+        Ok(status) => format!("{} exited with {}", args[0], status),
+        Err(e) => format!("Couldn't start {}: {}", args[0], e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_filled_in() {
+        assert_eq!(
+            substitute("code -g {file}:{line}", "src/main.rs", 42),
+            vec!["code", "-g", "src/main.rs:42"]
+        );
+        assert_eq!(
+            substitute("vim +{line} {file}", "my dir/a.rs", 7),
+            vec!["vim", "+7", "my dir/a.rs"]
+        );
+    }
+
+    #[test]
+    fn file_is_appended_when_not_mentioned() {
+        assert_eq!(
+            substitute("'my editor' --wait", "a.rs", 1),
+            vec!["my editor", "--wait", "a.rs"]
+        );
+    }
+
+    #[test]
+    fn finds_path_line_references() {
+        let refs = file_references("see `src/main.rs:42` and (lib.rs:7), not v1.2 or host:80");
+        assert_eq!(
+            refs,
+            vec![
+                FileReference {
+                    path: "src/main.rs".to_string(),
+                    line: 42
+                },
+                FileReference {
+                    path: "lib.rs".to_string(),
+                    line: 7
+                },
+            ]
+        );
+    }
+}
@@ -4,6 +4,16 @@ pub enum KeyHandlerResult {
     ShouldQuit,
     ShouldCancelTask,
     StartCommand(String),
+    /// Persist a task-list checkbox toggle into the conversation.
+    ToggleTaskItem {
+        message_index: usize,
+        line: usize,
+        checked: bool,
+    },
+    /// Switch to another `/regenerate` answer to the last prompt.
+    CycleReplyVariant {
+        forward: bool,
+    },
     StartConversation {
         input: String,
         image_attachments: Vec<crate::agent::Attachment>,
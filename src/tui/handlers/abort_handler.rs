@@ -0,0 +1,125 @@
+use crate::tui::handler_result::KeyHandlerResult;
+use crate::tui::input_handler::InputHandler;
+use crate::tui::state::AppState;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use std::sync::atomic::Ordering;
+
+/// Graceful interrupt of a running turn.
+///
+/// Rules:
+/// - First Esc while the agent is running: set the turn's interrupt flag. The
+///   agent finishes the in-flight backend request or tool batch, answers any
+///   pending tool calls, records that the user interrupted, and ends the turn.
+///   The conversation stays intact, so the next prompt carries on from there.
+/// - Esc again before the turn has stopped: not handled here, so
+///   `QuitHandler` cancels the turn outright.
+/// - Esc with a completion popup, history search or task list open: left to
+///   those handlers so Esc still closes them.
+///
+/// Ctrl+C keeps its immediate-cancel meaning and is never handled here.
+pub struct AbortHandler;
+
+impl AbortHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AbortHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputHandler for AbortHandler {
+    async fn handle_event(
+        &mut self,
+        event: &Event,
+        app: &mut AppState,
+        agent_task_active: bool,
+    ) -> KeyHandlerResult {
+        let Event::Key(key) = event else {
+            return KeyHandlerResult::NotHandled;
+        };
+
+        if key.code != KeyCode::Esc || key.modifiers != KeyModifiers::NONE || !agent_task_active {
+            return KeyHandlerResult::NotHandled;
+        }
+
+        if app.completion_state.is_some() || app.history_search.is_some() || app.task_list.is_some()
+        {
+            return KeyHandlerResult::NotHandled;
+        }
+
+        if app.turn_interrupt.swap(true, Ordering::Relaxed) {
+            return KeyHandlerResult::NotHandled;
+        }
+
+        app.add_status_message("Interrupting after the current step, Esc again to stop now");
+        KeyHandlerResult::Handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyEventKind, KeyEventState};
+
+    fn esc() -> Event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    #[tokio::test]
+    async fn first_esc_requests_interrupt() {
+        let mut app = AppState::new();
+        let mut h = AbortHandler::new();
+
+        let result = h.handle_event(&esc(), &mut app, true).await;
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(app.turn_interrupt.load(Ordering::Relaxed));
+        assert!(!app.should_cancel_task);
+    }
+
+    #[tokio::test]
+    async fn second_esc_falls_through_to_hard_cancel() {
+        let mut app = AppState::new();
+        let mut h = AbortHandler::new();
+
+        h.handle_event(&esc(), &mut app, true).await;
+        let result = h.handle_event(&esc(), &mut app, true).await;
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+    }
+
+    #[tokio::test]
+    async fn esc_while_idle_is_not_handled() {
+        let mut app = AppState::new();
+        let mut h = AbortHandler::new();
+
+        let result = h.handle_event(&esc(), &mut app, false).await;
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+        assert!(!app.turn_interrupt.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn ctrl_c_is_not_handled() {
+        let mut app = AppState::new();
+        let mut h = AbortHandler::new();
+        let ctrl_c = Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        });
+
+        let result = h.handle_event(&ctrl_c, &mut app, true).await;
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+        assert!(!app.turn_interrupt.load(Ordering::Relaxed));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::completion::completion_query;
 use crate::tui::handler_result::KeyHandlerResult;
 use crate::tui::input_handler::InputHandler;
 use crate::tui::state::AppState;
@@ -49,24 +50,28 @@ impl InputHandler for CompletionHandler {
                 // Save the completer index before applying completion
                 let completer_idx = app.completion_state.as_ref().map(|s| s.completer_index);
                 let input_text = app.get_input_text();
+                let cursor = app.input.cursor();
 
                 if let Some(selected) = app.apply_completion()
                     && let Some(idx) = completer_idx
                     && let Some(completer) = app.completers.get(idx)
-                    && let Some(trigger_pos) = completer.find_trigger_position(&input_text)
+                    && let Some(trigger_pos) = completer.find_trigger_position(&input_text, cursor)
                 {
-                    let new_text = completer.apply_completion(&input_text, trigger_pos, &selected);
-
-                    // Clear and rebuild the input with the new text
-                    app.clear_input();
-
-                    // Insert each line, adding newlines between them
-                    let lines: Vec<&str> = new_text.lines().collect();
-                    for (i, line) in lines.iter().enumerate() {
-                        app.input.insert_str(line);
-                        // Add newline after each line except the last
-                        if i < lines.len() - 1 {
-                            app.input.insert_newline();
+                    // Only the token under the cursor changes; text after it,
+                    // including other references, stays put.
+                    let (new_text, new_cursor) =
+                        completer.apply_completion(&input_text, trigger_pos, &selected);
+                    app.input.set_text(&new_text);
+                    app.input.set_cursor(new_cursor);
+
+                    // A directory was picked: keep completing inside it.
+                    if selected.ends_with('/') {
+                        app.start_completion(idx);
+                        app.update_completion_query(selected.clone());
+                        if let Some(completer) = app.completers.get(idx)
+                            && let Ok(candidates) = completer.get_completions(&selected).await
+                        {
+                            app.set_completion_candidates(candidates);
                         }
                     }
                 }
@@ -83,13 +88,14 @@ impl InputHandler for CompletionHandler {
                 let completer_idx = app.completion_state.as_ref().map(|s| s.completer_index);
 
                 if let Some(idx) = completer_idx {
-                    let trigger_and_query = app.completers.get(idx).and_then(|completer| {
+                    let cursor = app.input.cursor();
+                    let query = app.completers.get(idx).and_then(|completer| {
                         completer
-                            .find_trigger_position(&input_text)
-                            .map(|pos| (pos, input_text[pos + 1..].to_string()))
+                            .find_trigger_position(&input_text, cursor)
+                            .map(|pos| completion_query(&input_text, pos, cursor).to_string())
                     });
 
-                    if let Some((_, query)) = trigger_and_query {
+                    if let Some(query) = query {
                         app.update_completion_query(query.clone());
                         if let Some(completer) = app.completers.get(idx)
                             && let Ok(candidates) = completer.get_completions(&query).await
@@ -105,25 +111,36 @@ impl InputHandler for CompletionHandler {
                 KeyHandlerResult::Handled
             }
             KeyCode::Char(c) => {
+                // The key may end this completion and start another, such as
+                // the space after `/switch-agent`.
+                let next_completer = app.find_completer_for_key(c);
                 app.input
                     .input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
                 let input_text = app.get_input_text();
                 let completer_idx = app.completion_state.as_ref().map(|s| s.completer_index);
 
                 if let Some(idx) = completer_idx {
-                    let trigger_and_query = app.completers.get(idx).and_then(|completer| {
+                    let cursor = app.input.cursor();
+                    let query = app.completers.get(idx).and_then(|completer| {
                         completer
-                            .find_trigger_position(&input_text)
-                            .map(|pos| (pos, input_text[pos + 1..].to_string()))
+                            .find_trigger_position(&input_text, cursor)
+                            .map(|pos| completion_query(&input_text, pos, cursor).to_string())
                     });
 
-                    if let Some((_, query)) = trigger_and_query {
+                    if let Some(query) = query {
                         app.update_completion_query(query.clone());
                         if let Some(completer) = app.completers.get(idx)
                             && let Ok(candidates) = completer.get_completions(&query).await
                         {
                             app.set_completion_candidates(candidates);
                         }
+                    } else if let Some(next) = next_completer {
+                        app.start_completion(next);
+                        if let Some(completer) = app.completers.get(next)
+                            && let Ok(candidates) = completer.get_completions("").await
+                        {
+                            app.set_completion_candidates(candidates);
+                        }
                     }
                 }
                 KeyHandlerResult::Handled
@@ -135,3 +152,98 @@ impl InputHandler for CompletionHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_definition::AgentDefinition;
+    use crate::completion::{AgentCompleter, Completer};
+
+    struct FixedFileCompleter;
+
+    #[async_trait]
+    impl Completer for FixedFileCompleter {
+        fn trigger_key(&self) -> char {
+            '@'
+        }
+
+        async fn get_completions(&self, _query: &str) -> anyhow::Result<Vec<String>> {
+            Ok(vec!["src/main.rs".to_string()])
+        }
+    }
+
+    fn completing(text: &str, cursor: usize) -> AppState {
+        let mut app = AppState::new();
+        app.register_completer(Box::new(FixedFileCompleter));
+        app.input.set_text(text);
+        app.input.set_cursor(cursor);
+        app.start_completion(0);
+        app.set_completion_candidates(vec!["src/main.rs".to_string()]);
+        app
+    }
+
+    async fn press(app: &mut AppState, code: KeyCode) {
+        let event = Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        CompletionHandler::new()
+            .handle_event(&event, app, false)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn completes_the_second_reference_at_the_cursor() {
+        let text = "review @a.rs and @sr";
+        let mut app = completing(text, text.len());
+
+        press(&mut app, KeyCode::Tab).await;
+
+        assert_eq!(app.get_input_text(), "review @a.rs and @src/main.rs");
+        assert_eq!(app.input.cursor(), app.get_input_text().len());
+    }
+
+    #[tokio::test]
+    async fn completes_mid_line_without_touching_the_rest() {
+        let text = "review @sr and @b.rs";
+        let mut app = completing(text, "review @sr".len());
+
+        press(&mut app, KeyCode::Tab).await;
+
+        assert_eq!(app.get_input_text(), "review @src/main.rs and @b.rs");
+        assert_eq!(app.input.cursor(), "review @src/main.rs".len());
+    }
+
+    #[tokio::test]
+    async fn typing_updates_the_query_of_the_token_under_the_cursor() {
+        let text = "review @s and @b.rs";
+        let mut app = completing(text, "review @s".len());
+
+        press(&mut app, KeyCode::Char('r')).await;
+
+        assert_eq!(app.get_input_text(), "review @sr and @b.rs");
+        assert_eq!(app.completion_state.as_ref().unwrap().query, "sr");
+    }
+
+    #[tokio::test]
+    async fn space_after_switch_agent_moves_on_to_agent_names() {
+        let mut app = AppState::new();
+        app.register_completer(Box::new(FixedFileCompleter));
+        app.register_completer(Box::new(AgentCompleter::new(vec![AgentDefinition {
+            name: "reviewer".to_string(),
+            content: String::new(),
+            file: "reviewer.txt".to_string(),
+            description: Some("Reviews diffs".to_string()),
+            tags: Vec::new(),
+            core_instructions: String::new(),
+            backend: None,
+            model: None,
+        }])));
+        app.input.set_text("/switch-agent");
+        app.input.set_cursor("/switch-agent".len());
+        app.start_completion(0);
+
+        press(&mut app, KeyCode::Char(' ')).await;
+
+        let state = app.completion_state.as_ref().unwrap();
+        assert_eq!(state.completer_index, 1);
+        assert_eq!(state.candidates, vec!["reviewer - Reviews diffs"]);
+    }
+}
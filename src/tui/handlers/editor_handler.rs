@@ -0,0 +1,42 @@
+use crate::tui::handler_result::KeyHandlerResult;
+use crate::tui::input_handler::InputHandler;
+use crate::tui::state::AppState;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+
+/// ctrl+g opens the latest `path:line` reference in the transcript in the
+/// editor; pressing it again steps back to older ones.
+pub struct EditorHandler;
+
+impl EditorHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for EditorHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputHandler for EditorHandler {
+    async fn handle_event(
+        &mut self,
+        event: &Event,
+        app: &mut AppState,
+        _agent_task_active: bool,
+    ) -> KeyHandlerResult {
+        let Event::Key(key) = event else {
+            return KeyHandlerResult::NotHandled;
+        };
+
+        if key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.request_editor();
+            return KeyHandlerResult::Handled;
+        }
+
+        KeyHandlerResult::NotHandled
+    }
+}
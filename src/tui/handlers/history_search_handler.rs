@@ -0,0 +1,182 @@
+use crate::tui::handler_result::KeyHandlerResult;
+use crate::tui::input_handler::InputHandler;
+use crate::tui::state::AppState;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+
+/// Ctrl+R reverse search through prompt history, including entries loaded
+/// from the persisted history file.
+pub struct HistorySearchHandler;
+
+impl HistorySearchHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for HistorySearchHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputHandler for HistorySearchHandler {
+    async fn handle_event(
+        &mut self,
+        event: &Event,
+        app: &mut AppState,
+        _agent_task_active: bool,
+    ) -> KeyHandlerResult {
+        let Event::Key(key_event) = event else {
+            return KeyHandlerResult::NotHandled;
+        };
+
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let is_ctrl_r = ctrl && key_event.code == KeyCode::Char('r');
+
+        if !app.is_searching_history() {
+            if is_ctrl_r && !app.is_completing() {
+                app.start_history_search();
+                return KeyHandlerResult::Handled;
+            }
+            return KeyHandlerResult::NotHandled;
+        }
+
+        match key_event.code {
+            _ if is_ctrl_r => app.search_history_older(),
+            KeyCode::Esc => app.cancel_history_search(),
+            KeyCode::Char('g') if ctrl => app.cancel_history_search(),
+            KeyCode::Enter => app.accept_history_search(),
+            KeyCode::Backspace => app.pop_history_search_char(),
+            KeyCode::Char(c) if !ctrl => app.push_history_search_char(c),
+            _ => {
+                // Any other key accepts the match and is processed normally,
+                // matching readline behavior.
+                app.accept_history_search();
+                return KeyHandlerResult::NotHandled;
+            }
+        }
+
+        KeyHandlerResult::Handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode, mods: KeyModifiers) -> Event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers: mods,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn ctrl_r() -> Event {
+        key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+    }
+
+    async fn type_query(h: &mut HistorySearchHandler, app: &mut AppState, query: &str) {
+        for c in query.chars() {
+            h.handle_event(&key(KeyCode::Char(c), KeyModifiers::NONE), app, false)
+                .await;
+        }
+    }
+
+    fn app_with_history(entries: &[&str]) -> AppState {
+        let mut app = AppState::new();
+        for entry in entries {
+            app.prompt_history.add(entry.to_string());
+        }
+        app
+    }
+
+    #[tokio::test]
+    async fn keys_pass_through_when_not_searching() {
+        let mut app = AppState::new();
+        let mut h = HistorySearchHandler::new();
+        let result = h
+            .handle_event(
+                &key(KeyCode::Char('x'), KeyModifiers::NONE),
+                &mut app,
+                false,
+            )
+            .await;
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+    }
+
+    #[tokio::test]
+    async fn typing_shows_best_match_inline() {
+        let mut app = app_with_history(&["fix the parser", "write docs", "fix the lexer"]);
+        let mut h = HistorySearchHandler::new();
+
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+        type_query(&mut h, &mut app, "fix").await;
+
+        assert!(app.is_searching_history());
+        assert_eq!(app.get_input_text(), "fix the lexer");
+    }
+
+    #[tokio::test]
+    async fn repeated_ctrl_r_cycles_older_matches() {
+        let mut app = app_with_history(&["fix the parser", "write docs", "fix the lexer"]);
+        let mut h = HistorySearchHandler::new();
+
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+        type_query(&mut h, &mut app, "fix").await;
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+
+        assert_eq!(app.get_input_text(), "fix the parser");
+    }
+
+    #[tokio::test]
+    async fn enter_accepts_match_without_submitting() {
+        let mut app = app_with_history(&["write docs"]);
+        let mut h = HistorySearchHandler::new();
+
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+        type_query(&mut h, &mut app, "docs").await;
+        let result = h
+            .handle_event(&key(KeyCode::Enter, KeyModifiers::NONE), &mut app, false)
+            .await;
+
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(!app.is_searching_history());
+        assert_eq!(app.get_input_text(), "write docs");
+    }
+
+    #[tokio::test]
+    async fn esc_restores_original_input() {
+        let mut app = app_with_history(&["write docs"]);
+        app.set_input_text("half typed");
+        let mut h = HistorySearchHandler::new();
+
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+        type_query(&mut h, &mut app, "docs").await;
+        h.handle_event(&key(KeyCode::Esc, KeyModifiers::NONE), &mut app, false)
+            .await;
+
+        assert!(!app.is_searching_history());
+        assert_eq!(app.get_input_text(), "half typed");
+    }
+
+    #[tokio::test]
+    async fn other_keys_accept_and_pass_through() {
+        let mut app = app_with_history(&["write docs"]);
+        let mut h = HistorySearchHandler::new();
+
+        h.handle_event(&ctrl_r(), &mut app, false).await;
+        type_query(&mut h, &mut app, "docs").await;
+        let result = h
+            .handle_event(&key(KeyCode::Left, KeyModifiers::NONE), &mut app, false)
+            .await;
+
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+        assert!(!app.is_searching_history());
+        assert_eq!(app.get_input_text(), "write docs");
+    }
+}
@@ -1,19 +1,29 @@
+pub mod abort_handler;
 pub mod approval_handler;
 pub mod completion_handler;
+pub mod editor_handler;
+pub mod history_search_handler;
 pub mod paste_handler;
 pub mod permission_handler;
 pub mod quit_handler;
 pub mod scroll_handler;
 pub mod submit_handler;
+pub mod task_list_handler;
 pub mod text_input_handler;
 pub mod tool_expand_handler;
+pub mod variant_handler;
 
+pub use abort_handler::AbortHandler;
 pub use approval_handler::ApprovalHandler;
 pub use completion_handler::CompletionHandler;
+pub use editor_handler::EditorHandler;
+pub use history_search_handler::HistorySearchHandler;
 pub use paste_handler::PasteHandler;
 pub use permission_handler::PermissionHandler;
 pub use quit_handler::QuitHandler;
 pub use scroll_handler::ScrollHandler;
 pub use submit_handler::SubmitHandler;
+pub use task_list_handler::TaskListHandler;
 pub use text_input_handler::TextInputHandler;
 pub use tool_expand_handler::ToolExpandHandler;
+pub use variant_handler::VariantHandler;
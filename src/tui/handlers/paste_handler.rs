@@ -45,8 +45,11 @@ impl InputHandler for PasteHandler {
             }
             PasteClassification::Attachment => match app.create_attachment(text.to_string()) {
                 Ok(id) => {
-                    let reference = format!("[pasted text-{}]", id);
-                    app.input.insert_str(&reference);
+                    // Inserted as one element so the marker can't be half-edited
+                    // into something that no longer expands.
+                    if let Some(marker) = app.get_attachment(id).map(|a| a.marker()) {
+                        app.input.insert_element(&marker);
+                    }
                 }
                 Err(e) => {
                     app.add_error(&format!("Failed to create attachment: {}", e));
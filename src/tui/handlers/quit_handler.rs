@@ -7,7 +7,11 @@ use crossterm::event::{Event, KeyCode, KeyModifiers};
 /// Cancel and quit semantics.
 ///
 /// Rules:
-/// - Esc or Ctrl+C while the agent is running: cancel the turn. The handler
+/// - Ctrl+C with text selected in the input copies it to the clipboard and
+///   does nothing else, whether or not the agent is running.
+/// - Esc or Ctrl+C while the agent is running: cancel the turn at once. A
+///   first Esc normally reaches `AbortHandler` instead, which interrupts
+///   gracefully; Esc only gets here when pressed again. The handler
 ///   that processes `ShouldCancelTask` restores the submitted prompt back into
 ///   the input buffer. The restored prompt then behaves like normal typed
 ///   input — Ctrl+C clears it (arming quit), and the next Ctrl+C exits.
@@ -51,6 +55,13 @@ impl InputHandler for QuitHandler {
             return KeyHandlerResult::NotHandled;
         }
 
+        if is_ctrl_c && let Some(selected) = app.input.selected_text() {
+            let selected = selected.to_string();
+            let _ = app.clipboard.set_text(selected);
+            app.input.clear_selection();
+            return KeyHandlerResult::Handled;
+        }
+
         if agent_task_active {
             app.should_cancel_task = true;
             return KeyHandlerResult::ShouldCancelTask;
@@ -159,6 +170,21 @@ mod tests {
         assert!(matches!(result, KeyHandlerResult::ShouldQuit));
     }
 
+    #[tokio::test]
+    async fn ctrl_c_with_selection_copies_instead_of_cancelling_or_clearing() {
+        let mut app = AppState::new();
+        app.set_input_text("keep me");
+        app.input.select_all();
+        let mut h = QuitHandler::new();
+
+        let result = h.handle_event(&ctrl_c(), &mut app, true).await;
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(!app.should_cancel_task);
+        assert!(!app.quit_armed);
+        assert_eq!(app.get_input_text(), "keep me");
+        assert!(!app.input.has_selection());
+    }
+
     #[tokio::test]
     async fn non_cancel_key_is_not_handled() {
         let mut app = AppState::new();
@@ -1,3 +1,4 @@
+use crate::secret_redaction::PromptSecretCheck;
 use crate::tui::handler_result::KeyHandlerResult;
 use crate::tui::input_handler::InputHandler;
 use crate::tui::state::AppState;
@@ -39,7 +40,28 @@ impl InputHandler for SubmitHandler {
             return KeyHandlerResult::Handled;
         }
 
-        let expanded_input = app.expand_attachments(&input_text);
+        let mut expanded_input = app.expand_attachments(&input_text);
+
+        if let Some(scanner) = app.prompt_secret_scanner.clone()
+            && scanner.contains_secret(&expanded_input)
+        {
+            match app.prompt_secret_check {
+                PromptSecretCheck::Redact => {
+                    expanded_input = scanner.redact(&expanded_input).into_owned();
+                    app.add_status_message("Redacted what looks like a secret from your message");
+                }
+                _ if app.secret_send_armed => {}
+                _ => {
+                    app.secret_send_armed = true;
+                    app.add_error(
+                        "Your message looks like it contains an API key or other secret. \
+                         Press Enter again to send it anyway, or edit it",
+                    );
+                    return KeyHandlerResult::Handled;
+                }
+            }
+        }
+        app.secret_send_armed = false;
 
         if agent_task_active {
             // Queue the prompt for delivery after the current turn finishes.
@@ -54,6 +76,7 @@ impl InputHandler for SubmitHandler {
         }
 
         app.add_user_input(&expanded_input);
+        app.show_pasted_images();
         app.prompt_history.add(expanded_input.clone());
 
         // Drain any clipboard-pasted image attachments before clear_attachments
@@ -107,6 +130,65 @@ mod tests {
         assert_eq!(app.last_submitted_input.as_deref(), Some("hello world"));
     }
 
+    fn with_secret_check(app: &mut AppState, mode: PromptSecretCheck) {
+        use crate::secret_redaction::{SecretRedactionConfig, SecretRedactor};
+        app.prompt_secret_check = mode;
+        app.prompt_secret_scanner = SecretRedactor::from_config(
+            &SecretRedactionConfig::default(),
+            vec!["my-openai-key-1234".to_string()],
+        )
+        .map(std::sync::Arc::new);
+    }
+
+    #[tokio::test]
+    async fn known_key_in_message_warns_before_sending() {
+        let mut app = AppState::new();
+        with_secret_check(&mut app, PromptSecretCheck::Confirm);
+        app.set_input_text("why does my-openai-key-1234 fail?");
+        let mut h = SubmitHandler::new();
+
+        let r = h.handle_event(&enter_event(), &mut app, false).await;
+        assert!(matches!(r, KeyHandlerResult::Handled));
+        assert!(app.secret_send_armed);
+        assert_eq!(app.get_input_text(), "why does my-openai-key-1234 fail?");
+        let warning: String = app
+            .drain_pending_messages()
+            .iter()
+            .filter_map(|line| match line {
+                crate::tui::state::MessageLine::Styled(l) => Some(
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.to_string())
+                        .collect::<String>(),
+                ),
+                _ => None,
+            })
+            .collect();
+        assert!(warning.contains("secret"), "got: {warning}");
+
+        let r = h.handle_event(&enter_event(), &mut app, false).await;
+        assert!(matches!(
+            r,
+            KeyHandlerResult::StartConversation { ref input, .. }
+                if input == "why does my-openai-key-1234 fail?"
+        ));
+        assert!(!app.secret_send_armed);
+    }
+
+    #[tokio::test]
+    async fn redact_mode_sends_message_without_the_key() {
+        let mut app = AppState::new();
+        with_secret_check(&mut app, PromptSecretCheck::Redact);
+        app.set_input_text("key is my-openai-key-1234");
+        let mut h = SubmitHandler::new();
+
+        let r = h.handle_event(&enter_event(), &mut app, false).await;
+        assert!(matches!(
+            r,
+            KeyHandlerResult::StartConversation { ref input, .. } if input == "key is [REDACTED]"
+        ));
+    }
+
     #[tokio::test]
     async fn submit_while_busy_queues_and_clears_input() {
         let mut app = AppState::new();
@@ -146,4 +228,68 @@ mod tests {
         let collected: Vec<String> = app.queued_prompts.iter().cloned().collect();
         assert_eq!(collected, vec!["one", "two", "three"]);
     }
+
+    #[tokio::test]
+    async fn large_paste_becomes_an_attachment_that_submit_expands() {
+        use crate::tui::handlers::paste_handler::PasteHandler;
+
+        let mut app = AppState::new();
+        let pasted = "log line\n".repeat(50);
+        app.input.insert_str("explain ");
+        PasteHandler::new()
+            .handle_event(&Event::Paste(pasted.clone()), &mut app, false)
+            .await;
+        assert_eq!(app.get_input_text(), "explain [pasted 50 lines]");
+        assert_eq!(app.attachments.len(), 1);
+
+        let r = SubmitHandler::new()
+            .handle_event(&enter_event(), &mut app, false)
+            .await;
+        assert!(matches!(
+            r,
+            KeyHandlerResult::StartConversation { ref input, .. }
+                if *input == format!("explain {pasted}")
+        ));
+        assert!(app.attachments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn collapsed_paste_survives_cursor_movement_and_expands_verbatim() {
+        use crate::tui::handlers::paste_handler::PasteHandler;
+
+        let mut app = AppState::new();
+        let pasted: String = (1..=512).map(|n| format!("row {n}\n")).collect();
+        app.input.insert_str("before ");
+        PasteHandler::new()
+            .handle_event(&Event::Paste(pasted.clone()), &mut app, false)
+            .await;
+        app.input.insert_str(" after");
+        let marker = "[pasted 512 lines]";
+        assert_eq!(app.get_input_text(), format!("before {marker} after"));
+
+        // Walk the cursor across the element both ways; it never lands inside.
+        app.input.move_cursor_to_beginning_of_line();
+        let start = "before ".len();
+        let end = start + marker.len();
+        for _ in 0..app.get_input_text().len() {
+            app.input.move_cursor_right();
+            let pos = app.input.cursor();
+            assert!(pos <= start || pos >= end, "cursor inside element at {pos}");
+        }
+        for _ in 0..app.get_input_text().len() {
+            app.input.move_cursor_left();
+            let pos = app.input.cursor();
+            assert!(pos <= start || pos >= end, "cursor inside element at {pos}");
+        }
+        assert_eq!(app.input.text(), format!("before {marker} after"));
+
+        let r = SubmitHandler::new()
+            .handle_event(&enter_event(), &mut app, false)
+            .await;
+        assert!(matches!(
+            r,
+            KeyHandlerResult::StartConversation { ref input, .. }
+                if *input == format!("before {pasted} after")
+        ));
+    }
 }
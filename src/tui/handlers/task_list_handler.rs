@@ -0,0 +1,152 @@
+use crate::tui::handler_result::KeyHandlerResult;
+use crate::tui::input_handler::InputHandler;
+use crate::tui::state::AppState;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+
+/// Navigation and toggling for the `/tasks` picker. Captures all keys while
+/// the picker is open except Ctrl+C, which falls through to quit/cancel.
+pub struct TaskListHandler;
+
+impl TaskListHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TaskListHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputHandler for TaskListHandler {
+    async fn handle_event(
+        &mut self,
+        event: &Event,
+        app: &mut AppState,
+        _agent_task_active: bool,
+    ) -> KeyHandlerResult {
+        let Event::Key(key_event) = event else {
+            return KeyHandlerResult::NotHandled;
+        };
+        let Some(task_list) = app.task_list.as_mut() else {
+            return KeyHandlerResult::NotHandled;
+        };
+
+        if key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && key_event.code == KeyCode::Char('c')
+        {
+            app.hide_task_list();
+            return KeyHandlerResult::NotHandled;
+        }
+
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => task_list.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => task_list.select_next(),
+            KeyCode::Char(' ') => {
+                if let Some((line, checked)) = task_list.toggle_selected() {
+                    return KeyHandlerResult::ToggleTaskItem {
+                        message_index: task_list.message_index,
+                        line,
+                        checked,
+                    };
+                }
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.hide_task_list(),
+            _ => {}
+        }
+
+        KeyHandlerResult::Handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::TaskListItem;
+    use crossterm::event::{KeyEvent, KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn app_with_tasks() -> AppState {
+        let mut app = AppState::new();
+        let items = vec![
+            TaskListItem {
+                line: 1,
+                checked: false,
+                text: "write tests".to_string(),
+            },
+            TaskListItem {
+                line: 2,
+                checked: true,
+                text: "fix bug".to_string(),
+            },
+        ];
+        app.show_task_list(3, items);
+        app
+    }
+
+    #[tokio::test]
+    async fn keys_pass_through_when_closed() {
+        let mut app = AppState::new();
+        let mut h = TaskListHandler::new();
+        let result = h
+            .handle_event(&key(KeyCode::Char(' ')), &mut app, false)
+            .await;
+        assert!(matches!(result, KeyHandlerResult::NotHandled));
+    }
+
+    #[tokio::test]
+    async fn space_toggles_selected_item() {
+        let mut app = app_with_tasks();
+        let mut h = TaskListHandler::new();
+
+        h.handle_event(&key(KeyCode::Down), &mut app, false).await;
+        let result = h
+            .handle_event(&key(KeyCode::Char(' ')), &mut app, false)
+            .await;
+
+        assert!(matches!(
+            result,
+            KeyHandlerResult::ToggleTaskItem {
+                message_index: 3,
+                line: 2,
+                checked: false
+            }
+        ));
+        assert!(!app.task_list.as_ref().unwrap().items[1].checked);
+    }
+
+    #[tokio::test]
+    async fn esc_closes_picker() {
+        let mut app = app_with_tasks();
+        let mut h = TaskListHandler::new();
+
+        let result = h.handle_event(&key(KeyCode::Esc), &mut app, false).await;
+
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(!app.is_showing_task_list());
+    }
+
+    #[tokio::test]
+    async fn other_keys_are_swallowed_while_open() {
+        let mut app = app_with_tasks();
+        let mut h = TaskListHandler::new();
+
+        let result = h
+            .handle_event(&key(KeyCode::Char('x')), &mut app, false)
+            .await;
+
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(app.get_input_text().is_empty());
+    }
+}
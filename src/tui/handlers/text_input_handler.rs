@@ -33,6 +33,7 @@ impl InputHandler for TextInputHandler {
         // Any input keypress means the user is back at work — disarm quit so
         // a stray Ctrl+C after a cancel doesn't exit unexpectedly.
         app.quit_armed = false;
+        app.secret_send_armed = false;
 
         match key_event.code {
             KeyCode::BackTab => {
@@ -44,13 +45,20 @@ impl InputHandler for TextInputHandler {
                 let label = if now_compact { "compact" } else { "full" };
                 app.add_status_message(&format!("display mode: {}", label));
             }
+            KeyCode::Char('x') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                let cut = app.input.cut();
+                if !cut.is_empty() {
+                    let _ = app.clipboard.set_text(cut);
+                }
+            }
             KeyCode::Char('v') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
                 // Prefer image on the clipboard. Falls back to text when the
                 // clipboard has no image (the common case).
                 if let Ok((png, media_type)) = app.clipboard.get_image_png() {
-                    let id = app.add_image_attachment(png, media_type.to_string());
-                    let marker = format!("[pasted image-{}]", id);
-                    app.input.insert_str(&marker);
+                    app.add_image_attachment(png, media_type.to_string());
+                    if let Some(marker) = app.attachments.images.last().map(|a| a.marker()) {
+                        app.input.insert_element(&marker);
+                    }
                 } else if let Ok(text) = app.clipboard.get_text() {
                     let lines: Vec<&str> = text.lines().collect();
                     for (i, line) in lines.iter().enumerate() {
@@ -61,7 +69,16 @@ impl InputHandler for TextInputHandler {
                     }
                 }
             }
-            KeyCode::Up => {
+            KeyCode::Char('a') if key_event.modifiers.contains(KeyModifiers::ALT) => {
+                app.attachments.focus_next();
+            }
+            KeyCode::Char('x')
+                if key_event.modifiers.contains(KeyModifiers::ALT)
+                    && !app.attachments.is_empty() =>
+            {
+                app.remove_focused_attachment();
+            }
+            KeyCode::Up if !key_event.modifiers.contains(KeyModifiers::SHIFT) => {
                 // Navigate to previous prompt in history
                 let current_input = app.get_input_text();
                 if let Some(prev_prompt) = app.prompt_history.prev(&current_input) {
@@ -75,7 +92,7 @@ impl InputHandler for TextInputHandler {
                     }
                 }
             }
-            KeyCode::Down => {
+            KeyCode::Down if !key_event.modifiers.contains(KeyModifiers::SHIFT) => {
                 // Navigate to next prompt in history
                 if let Some(next_prompt) = app.prompt_history.next_entry() {
                     app.clear_input();
@@ -33,15 +33,14 @@ impl InputHandler for ToolExpandHandler {
         let is_ctrl_o =
             key.code == KeyCode::Char('o') && key.modifiers.contains(KeyModifiers::CONTROL);
 
-        // ctrl+o toggles either a collapsed 2+ batch or the live bash output of
-        // a single streaming call.
-        let has_expandable_bash = app
-            .tools
-            .active
-            .iter()
-            .any(|tc| tc.bash.as_ref().is_some_and(|b| !b.lines.is_empty()));
-
-        if is_ctrl_o && (app.tools.active.len() >= 2 || has_expandable_bash) {
+        // ctrl+o toggles either a collapsed 2+ batch or the live detail of a
+        // single call: bash output or a sub-agent's event stream.
+        let has_expandable_detail = app.tools.active.iter().any(|tc| {
+            tc.bash.as_ref().is_some_and(|b| !b.lines.is_empty())
+                || tc.subagent.as_ref().is_some_and(|s| s.steps.len() > 1)
+        });
+
+        if is_ctrl_o && (app.tools.active.len() >= 2 || has_expandable_detail) {
             app.tools.expanded = !app.tools.expanded;
             return KeyHandlerResult::Handled;
         }
@@ -121,6 +120,29 @@ mod tests {
         assert!(!app.tools.expanded);
     }
 
+    #[tokio::test]
+    async fn ctrl_o_toggles_a_single_subagent_event_stream() {
+        use crate::tui::state::{SubagentDetail, SubagentStepSummary};
+
+        let mut handler = ToolExpandHandler::new();
+        let mut app = AppState::new();
+        let mut task = active_call();
+        let step = |n: usize| SubagentStepSummary {
+            step_number: n,
+            action_type: "tool_starting".into(),
+            description: format!("Executing grep {n}"),
+        };
+        task.subagent = Some(SubagentDetail {
+            steps: vec![step(1), step(2)],
+            ..Default::default()
+        });
+        app.tools.active = vec![task];
+
+        let result = handler.handle_event(&ctrl_o(), &mut app, true).await;
+        assert!(matches!(result, KeyHandlerResult::Handled));
+        assert!(app.tools.expanded);
+    }
+
     #[tokio::test]
     async fn ctrl_o_ignored_for_single_bash_call_without_output() {
         let mut handler = ToolExpandHandler::new();
@@ -0,0 +1,44 @@
+use crate::tui::handler_result::KeyHandlerResult;
+use crate::tui::input_handler::InputHandler;
+use crate::tui::state::AppState;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+
+/// alt+left / alt+right switch between the answers `/regenerate` produced.
+pub struct VariantHandler;
+
+impl VariantHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for VariantHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputHandler for VariantHandler {
+    async fn handle_event(
+        &mut self,
+        event: &Event,
+        _app: &mut AppState,
+        agent_task_active: bool,
+    ) -> KeyHandlerResult {
+        let Event::Key(key) = event else {
+            return KeyHandlerResult::NotHandled;
+        };
+        // The running turn owns the conversation.
+        if agent_task_active || !key.modifiers.contains(KeyModifiers::ALT) {
+            return KeyHandlerResult::NotHandled;
+        }
+
+        match key.code {
+            KeyCode::Left => KeyHandlerResult::CycleReplyVariant { forward: false },
+            KeyCode::Right => KeyHandlerResult::CycleReplyVariant { forward: true },
+            _ => KeyHandlerResult::NotHandled,
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Inline image display for terminals that speak the kitty or iTerm2
+//! graphics protocol. Only active in builds with the `inline-images` feature
+//! and with `inline_images = true` in the config; everywhere else images show
+//! as their alt-text placeholder.
+
+use crate::terminal_markdown::image_placeholder;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use std::path::Path;
+
+/// Tallest an inline image may get, in terminal rows.
+pub const MAX_IMAGE_ROWS: u16 = 20;
+
+/// Largest image file read from disk for inline display.
+const MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Kitty caps each escape sequence's payload at 4096 bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// Cell size assumed when the terminal doesn't report pixel dimensions.
+const FALLBACK_CELL_PIXELS: (u16, u16) = (8, 16);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+}
+
+impl GraphicsProtocol {
+    /// The protocol the current terminal understands, if any. Always `None`
+    /// without the `inline-images` feature.
+    pub fn detect() -> Option<Self> {
+        if cfg!(feature = "inline-images") {
+            Self::detect_from(|key| std::env::var(key).ok())
+        } else {
+            None
+        }
+    }
+
+    fn detect_from(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let term = env("TERM").unwrap_or_default();
+        // Multiplexers swallow graphics escapes unless passthrough is set up.
+        if env("TMUX").is_some() || term.starts_with("screen") {
+            return None;
+        }
+        if env("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || term == "xterm-ghostty" {
+            return Some(GraphicsProtocol::Kitty);
+        }
+        match env("TERM_PROGRAM").as_deref() {
+            Some("iTerm.app") | Some("WezTerm") => Some(GraphicsProtocol::Iterm2),
+            Some("ghostty") => Some(GraphicsProtocol::Kitty),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+}
+
+/// An image queued for the transcript, with the placeholder shown when it
+/// can't be drawn.
+#[derive(Debug, Clone)]
+pub struct InlineImage {
+    pub caption: String,
+    data: Vec<u8>,
+    format: ImageFormat,
+    width_px: u32,
+    height_px: u32,
+}
+
+impl InlineImage {
+    /// `None` when `data` isn't a PNG, JPEG or GIF whose size can be read.
+    pub fn from_bytes(caption: String, data: Vec<u8>) -> Option<Self> {
+        let (format, width_px, height_px) = image_dimensions(&data)?;
+        Some(Self {
+            caption,
+            data,
+            format,
+            width_px,
+            height_px,
+        })
+    }
+
+    /// Loads a local image referenced from markdown. `url` may be a plain
+    /// path or a `file://` URL; remote URLs yield `None`.
+    pub fn from_markdown_ref(alt: &str, url: &str) -> Option<Self> {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        if path.contains("://") {
+            return None;
+        }
+        let path = Path::new(path);
+        let metadata = std::fs::metadata(path).ok()?;
+        if !metadata.is_file() || metadata.len() > MAX_IMAGE_BYTES {
+            return None;
+        }
+        let data = std::fs::read(path).ok()?;
+        Self::from_bytes(image_placeholder(alt, url), data)
+    }
+
+    /// Cells the image occupies when scaled down to fit `max_cols` by
+    /// `max_rows`, keeping its aspect ratio. Images are never scaled up.
+    pub fn cell_size(&self, cell_pixels: (u16, u16), max_cols: u16, max_rows: u16) -> (u16, u16) {
+        let cell_width = cell_pixels.0.max(1) as f64;
+        let cell_height = cell_pixels.1.max(1) as f64;
+        let natural_cols = (self.width_px as f64 / cell_width).max(f64::MIN_POSITIVE);
+        let natural_rows = (self.height_px as f64 / cell_height).max(f64::MIN_POSITIVE);
+        let scale = (max_cols as f64 / natural_cols)
+            .min(max_rows as f64 / natural_rows)
+            .min(1.0);
+
+        let cols = ((natural_cols * scale).ceil() as u16).clamp(1, max_cols.max(1));
+        let rows = ((natural_rows * scale).ceil() as u16).min(max_rows);
+        (cols, rows)
+    }
+
+    /// Escape sequence drawing the image across `cols` by `rows` cells from
+    /// the cursor, leaving the cursor where it was. `None` when the protocol
+    /// can't carry this format (kitty only takes PNG as-is).
+    pub fn escape_sequence(
+        &self,
+        protocol: GraphicsProtocol,
+        cols: u16,
+        rows: u16,
+    ) -> Option<String> {
+        let encoded = STANDARD.encode(&self.data);
+        match protocol {
+            GraphicsProtocol::Kitty => {
+                if self.format != ImageFormat::Png {
+                    return None;
+                }
+                let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK).collect();
+                let mut out = String::new();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = u8::from(i + 1 < chunks.len());
+                    let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+                    if i == 0 {
+                        out.push_str(&format!(
+                            "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\",
+                            cols, rows, more, chunk
+                        ));
+                    } else {
+                        out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+                    }
+                }
+                Some(out)
+            }
+            GraphicsProtocol::Iterm2 => Some(format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                self.data.len(),
+                cols,
+                rows,
+                encoded
+            )),
+        }
+    }
+}
+
+/// Local images referenced from `markdown`, in order. Remote or unreadable
+/// ones are skipped; they stay as placeholders in the rendered text.
+pub fn markdown_images(markdown: &str) -> Vec<InlineImage> {
+    let mut images = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for event in Parser::new_ext(markdown, Options::all()) {
+        match event {
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                current = Some((dest_url.to_string(), String::new()));
+            }
+            Event::Text(text) => {
+                if let Some((_, alt)) = current.as_mut() {
+                    alt.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some((url, alt)) = current.take()
+                    && let Some(image) = InlineImage::from_markdown_ref(&alt, &url)
+                {
+                    images.push(image);
+                }
+            }
+            _ => {}
+        }
+    }
+    images
+}
+
+/// Pixel size of one terminal cell, from the terminal's reported window size.
+pub fn cell_pixels() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => {
+            (size.width / size.columns, size.height / size.rows)
+        }
+        _ => FALLBACK_CELL_PIXELS,
+    }
+}
+
+fn image_dimensions(data: &[u8]) -> Option<(ImageFormat, u32, u32)> {
+    let be16 = |at: usize| -> Option<u32> {
+        Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32)
+    };
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(data.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(data.get(20..24)?.try_into().ok()?);
+        return Some((ImageFormat::Png, width, height));
+    }
+
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        let width = u16::from_le_bytes(data.get(6..8)?.try_into().ok()?) as u32;
+        let height = u16::from_le_bytes(data.get(8..10)?.try_into().ok()?) as u32;
+        return Some((ImageFormat::Gif, width, height));
+    }
+
+    if data.starts_with(&[0xFF, 0xD8]) {
+        let mut at = 2;
+        while at + 1 < data.len() {
+            if data[at] != 0xFF {
+                return None;
+            }
+            let marker = data[at + 1];
+            match marker {
+                0xFF => at += 1,
+                0x01 | 0xD0..=0xD7 => at += 2,
+                0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                    let height = be16(at + 5)?;
+                    let width = be16(at + 7)?;
+                    return Some((ImageFormat::Jpeg, width, height));
+                }
+                _ => at += 2 + be16(at + 2)? as usize,
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        assert_eq!(
+            GraphicsProtocol::detect_from(env(&[("TERM", "xterm-kitty")])),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect_from(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect_from(env(&[("TERM", "xterm-256color")])),
+            None
+        );
+        assert_eq!(
+            GraphicsProtocol::detect_from(env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/t")])),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_dimensions_of_supported_formats() {
+        assert_eq!(
+            image_dimensions(&png(640, 480)),
+            Some((ImageFormat::Png, 640, 480))
+        );
+
+        let gif = b"GIF89a\x20\x00\x10\x00".to_vec();
+        assert_eq!(image_dimensions(&gif), Some((ImageFormat::Gif, 32, 16)));
+
+        let jpeg = vec![
+            0xFF, 0xD8, // SOI
+            0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0, 2 bytes of payload
+            0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x00, 0x64, 0x00, 0xC8, // SOF0: 100 high, 200 wide
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((ImageFormat::Jpeg, 200, 100)));
+
+        assert_eq!(image_dimensions(b"not an image"), None);
+    }
+
+    #[test]
+    fn cell_size_keeps_small_images_and_shrinks_large_ones() {
+        let small = InlineImage::from_bytes("s".into(), png(80, 64)).unwrap();
+        assert_eq!(small.cell_size((8, 16), 100, 20), (10, 4));
+
+        // 1600x800 px is 200x50 cells; fitting 100 columns halves it.
+        let wide = InlineImage::from_bytes("w".into(), png(1600, 800)).unwrap();
+        assert_eq!(wide.cell_size((8, 16), 100, 40), (100, 25));
+
+        // 160x1600 px is 20x100 cells; the row cap wins for tall images.
+        let tall = InlineImage::from_bytes("t".into(), png(160, 1600)).unwrap();
+        assert_eq!(tall.cell_size((8, 16), 100, 20), (4, 20));
+
+        assert_eq!(tall.cell_size((8, 16), 100, 0).1, 0);
+    }
+
+    #[test]
+    fn kitty_sequence_is_chunked_and_png_only() {
+        let mut data = png(10, 10);
+        data.resize(KITTY_CHUNK * 2, 0);
+        let image = InlineImage::from_bytes("k".into(), data).unwrap();
+
+        let sequence = image
+            .escape_sequence(GraphicsProtocol::Kitty, 4, 2)
+            .unwrap();
+        assert!(sequence.starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=4,r=2,m=1;"));
+        assert!(sequence.ends_with("\x1b\\"));
+        assert!(sequence.contains("\x1b_Gm=0;"));
+
+        let gif = InlineImage::from_bytes("g".into(), b"GIF89a\x01\x00\x01\x00".to_vec()).unwrap();
+        assert!(gif.escape_sequence(GraphicsProtocol::Kitty, 1, 1).is_none());
+        assert!(
+            gif.escape_sequence(GraphicsProtocol::Iterm2, 1, 1)
+                .unwrap()
+                .starts_with("\x1b]1337;File=inline=1;size=10;width=1;height=1;")
+        );
+    }
+
+    #[test]
+    fn remote_markdown_images_are_not_loaded() {
+        assert!(InlineImage::from_markdown_ref("logo", "https://example.com/logo.png").is_none());
+        assert!(InlineImage::from_markdown_ref("missing", "no/such/file.png").is_none());
+    }
+
+    #[test]
+    fn local_markdown_images_are_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chart.png");
+        std::fs::write(&path, png(16, 16)).unwrap();
+        let url = format!("file://{}", path.display());
+
+        let image = InlineImage::from_markdown_ref("chart", &url).unwrap();
+        assert_eq!(image.caption, image_placeholder("chart", &url));
+
+        let markdown = format!(
+            "Here ![the chart]({}) and ![logo](https://example.com/logo.png).",
+            url
+        );
+        let images = markdown_images(&markdown);
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].caption, image_placeholder("the chart", &url));
+    }
+}
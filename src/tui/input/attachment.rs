@@ -6,6 +6,9 @@ pub struct TextAttachment {
     pub content: String,
     pub size_chars: usize,
     pub line_count: usize,
+    /// What the collapsed element shows, e.g. `pasted 512 lines`. Fixed at
+    /// creation since the marker in the input must keep matching it.
+    pub label: String,
     pub created_at: Instant,
 }
 
@@ -19,10 +22,25 @@ impl TextAttachment {
             content,
             size_chars,
             line_count,
+            label: format!(
+                "pasted {} line{}",
+                line_count,
+                if line_count == 1 { "" } else { "s" }
+            ),
             created_at: Instant::now(),
         }
     }
 
+    /// Name shown in the attachments bar.
+    pub fn name(&self) -> String {
+        self.label.clone()
+    }
+
+    /// Placeholder standing in for the content in the input until submit.
+    pub fn marker(&self) -> String {
+        format!("[{}]", self.name())
+    }
+
     pub fn update_content(&mut self, content: String) {
         self.size_chars = content.chars().count();
         self.line_count = content.lines().count().max(1);
@@ -47,4 +65,12 @@ impl ImageAttachment {
             data,
         }
     }
+
+    pub fn name(&self) -> String {
+        format!("pasted image-{}", self.id)
+    }
+
+    pub fn marker(&self) -> String {
+        format!("[{}]", self.name())
+    }
 }
@@ -5,26 +5,42 @@ pub enum PasteClassification {
     Rejected(String),
 }
 
+pub const DEFAULT_THRESHOLD_CHARS: usize = 200;
+pub const DEFAULT_THRESHOLD_LINES: usize = 10;
+pub const DEFAULT_MAX_SIZE_BYTES: usize = 5_000_000; // 5MB
+
+/// Decides whether a paste is typed into the input as-is or collapsed into a
+/// single `[pasted N lines]` element that expands on send.
 pub struct PasteDetector {
     threshold_chars: usize,
+    threshold_lines: usize,
     max_size_bytes: usize,
 }
 
 impl PasteDetector {
     pub fn new() -> Self {
         Self {
-            threshold_chars: 200,
-            max_size_bytes: 5_000_000, // 5MB
+            threshold_chars: DEFAULT_THRESHOLD_CHARS,
+            threshold_lines: DEFAULT_THRESHOLD_LINES,
+            max_size_bytes: DEFAULT_MAX_SIZE_BYTES,
         }
     }
 
     pub fn with_threshold(threshold_chars: usize, max_size_bytes: usize) -> Self {
         Self {
             threshold_chars,
+            threshold_lines: DEFAULT_THRESHOLD_LINES,
             max_size_bytes,
         }
     }
 
+    /// Collapse pastes with more than `threshold_lines` lines even when they
+    /// are under the character threshold.
+    pub fn with_line_threshold(mut self, threshold_lines: usize) -> Self {
+        self.threshold_lines = threshold_lines;
+        self
+    }
+
     pub fn classify_paste(&self, content: &str) -> PasteClassification {
         let size_bytes = content.len();
 
@@ -36,8 +52,9 @@ impl PasteDetector {
         }
 
         let char_count = content.chars().count();
+        let line_count = content.lines().count();
 
-        if char_count > self.threshold_chars {
+        if char_count > self.threshold_chars || line_count > self.threshold_lines {
             PasteClassification::Attachment
         } else {
             PasteClassification::Inline
@@ -89,3 +89,16 @@ fn test_custom_threshold() {
     let result = detector.classify_paste(&small_content);
     assert_eq!(result, PasteClassification::Inline);
 }
+
+#[test]
+fn test_line_threshold_collapses_short_multiline_pastes() {
+    let detector = PasteDetector::new().with_line_threshold(3);
+    assert_eq!(
+        detector.classify_paste("a\nb\nc"),
+        PasteClassification::Inline
+    );
+    assert_eq!(
+        detector.classify_paste("a\nb\nc\nd"),
+        PasteClassification::Attachment
+    );
+}
@@ -1,4 +1,5 @@
 use crate::tui::input::wrap_ranges;
+use crate::tui::palette;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
@@ -41,6 +42,10 @@ pub struct TextArea {
     preferred_col: Option<usize>,
     elements: Vec<TextElement>,
     kill_buffer: String,
+    placeholder: String,
+    placeholder_style: Style,
+    /// The other end of the selection; the cursor is the moving end.
+    selection_anchor: Option<usize>,
 }
 
 impl Default for TextArea {
@@ -58,6 +63,9 @@ impl TextArea {
             preferred_col: None,
             elements: Vec::new(),
             kill_buffer: String::new(),
+            placeholder: String::new(),
+            placeholder_style: Style::default().fg(palette::PLACEHOLDER),
+            selection_anchor: None,
         }
     }
 
@@ -73,6 +81,7 @@ impl TextArea {
         let pos = self.clamp_pos_for_insertion(pos);
         self.text.insert_str(pos, text);
         self.wrap_cache.replace(None);
+        self.selection_anchor = None;
 
         if pos <= self.cursor_pos {
             self.cursor_pos += text.len();
@@ -103,6 +112,7 @@ impl TextArea {
         self.text.replace_range(start..end, text);
         self.wrap_cache.replace(None);
         self.preferred_col = None;
+        self.selection_anchor = None;
         self.update_elements_after_replace(start, end, inserted_len);
 
         self.cursor_pos = if self.cursor_pos < start {
@@ -125,6 +135,7 @@ impl TextArea {
         self.wrap_cache.replace(None);
         self.preferred_col = None;
         self.kill_buffer.clear();
+        self.selection_anchor = None;
     }
 
     pub fn cursor(&self) -> usize {
@@ -673,6 +684,7 @@ impl TextArea {
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         let lines = self.wrapped_lines(area.width);
         self.render_lines(area, buf, &lines, 0..lines.len());
+        self.render_placeholder(area, buf);
     }
 
     pub fn render_with_state(&self, area: Rect, buf: &mut Buffer, state: &mut TextAreaState) {
@@ -683,6 +695,22 @@ impl TextArea {
         let start = scroll as usize;
         let end = (scroll + area.height).min(lines.len() as u16) as usize;
         self.render_lines(area, buf, &lines, start..end);
+        self.render_placeholder(area, buf);
+    }
+
+    /// Drawn over the first row only while the buffer is empty. It is never
+    /// part of `text`, so cursor positions and wrapping are unaffected.
+    fn render_placeholder(&self, area: Rect, buf: &mut Buffer) {
+        if !self.text.is_empty() || self.placeholder.is_empty() || area.is_empty() {
+            return;
+        }
+        buf.set_stringn(
+            area.x,
+            area.y,
+            &self.placeholder,
+            area.width as usize,
+            self.placeholder_style,
+        );
     }
 
     fn render_lines(
@@ -692,6 +720,7 @@ impl TextArea {
         lines: &[Range<usize>],
         range: Range<usize>,
     ) {
+        let selection = self.selection_range();
         for (row, idx) in range.enumerate() {
             let r = &lines[idx];
             let y = area.y + row as u16;
@@ -718,6 +747,19 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            if let Some(sel) = &selection {
+                let overlap_start = sel.start.max(line_range.start);
+                let overlap_end = sel.end.min(line_range.end);
+                if overlap_start < overlap_end {
+                    let selected = &self.text[overlap_start..overlap_end];
+                    let x_off = self.text[line_range.start..overlap_start].width() as u16;
+                    let style = Style::default()
+                        .fg(palette::SELECTED_FG)
+                        .bg(palette::SELECTED_BG);
+                    buf.set_string(area.x + x_off, y, selected, style);
+                }
+            }
         }
     }
 
@@ -751,21 +793,66 @@ impl TextArea {
         // No-op for now - we don't store cursor line style separately
     }
 
-    pub fn set_placeholder_text(&mut self, _text: &str) {
-        // No-op for now - we don't support placeholder text yet
+    pub fn set_placeholder_text(&mut self, text: &str) {
+        self.placeholder = text.to_string();
     }
 
-    pub fn set_placeholder_style(&mut self, _style: Style) {
-        // No-op for now - we don't support placeholder style yet
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
     }
 
     pub fn select_all(&mut self) {
-        // No-op for now - we don't support selection yet
+        self.selection_anchor = Some(0);
+        self.cursor_pos = self.text.len();
+        self.preferred_col = None;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection_anchor = None;
+    }
+
+    /// The selected byte range, widened so an element (attachment) it touches
+    /// is covered whole. `None` when nothing is selected.
+    pub fn selection_range(&self) -> Option<Range<usize>> {
+        let anchor = self.clamp_pos_to_char_boundary(self.selection_anchor?);
+        let range = anchor.min(self.cursor_pos)..anchor.max(self.cursor_pos);
+        let range = self.expand_range_to_element_boundaries(range);
+        (!range.is_empty()).then_some(range)
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.selection_range().is_some()
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection_range().map(|r| &self.text[r])
     }
 
+    /// Remove the selection and return its text. Returns an empty string when
+    /// nothing is selected.
     pub fn cut(&mut self) -> String {
-        // For now, just return empty string - we don't support selection/cut yet
-        String::new()
+        let Some(range) = self.selection_range() else {
+            return String::new();
+        };
+        let removed = self.text[range.clone()].to_string();
+        self.replace_range_raw(range, "");
+        removed
+    }
+
+    fn extend_selection(&mut self, code: KeyCode) {
+        let anchor = *self.selection_anchor.get_or_insert(self.cursor_pos);
+        match code {
+            KeyCode::Left => self.move_cursor_left(),
+            KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Up => self.move_cursor_up(),
+            KeyCode::Down => self.move_cursor_down(),
+            KeyCode::Home => self.move_cursor_to_beginning_of_line(),
+            KeyCode::End => self.move_cursor_to_end_of_line(),
+            _ => {}
+        }
+        if self.cursor_pos == anchor {
+            self.selection_anchor = None;
+        }
     }
 
     pub fn insert_element(&mut self, text: &str) {
@@ -838,6 +925,47 @@ impl TextArea {
     }
 
     pub fn input(&mut self, event: KeyEvent) {
+        if event.modifiers.contains(KeyModifiers::SHIFT)
+            && matches!(
+                event.code,
+                KeyCode::Left
+                    | KeyCode::Right
+                    | KeyCode::Up
+                    | KeyCode::Down
+                    | KeyCode::Home
+                    | KeyCode::End
+            )
+        {
+            self.extend_selection(event.code);
+            return;
+        }
+
+        if self.has_selection() {
+            match event {
+                KeyEvent {
+                    code: KeyCode::Backspace | KeyCode::Delete,
+                    ..
+                } => {
+                    self.cut();
+                    return;
+                }
+                // Typing replaces the selection.
+                KeyEvent {
+                    code: KeyCode::Char(_),
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    ..
+                }
+                | KeyEvent {
+                    code: KeyCode::Enter,
+                    ..
+                } => {
+                    self.cut();
+                }
+                _ => {}
+            }
+        }
+        self.selection_anchor = None;
+
         match event {
             KeyEvent {
                 code: KeyCode::Char(c),
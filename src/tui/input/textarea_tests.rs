@@ -336,3 +336,178 @@ fn delete_backward_on_multibyte_char_removes_correctly() {
     assert_eq!(ta.text(), "hllo");
     assert_eq!(ta.cursor(), 1);
 }
+
+// --- Placeholder ---
+
+fn render_row(ta: &TextArea, width: u16) -> ratatui::buffer::Buffer {
+    let area = ratatui::layout::Rect::new(0, 0, width, 1);
+    let mut buf = ratatui::buffer::Buffer::empty(area);
+    ta.render(area, &mut buf);
+    buf
+}
+
+fn row_text(buf: &ratatui::buffer::Buffer) -> String {
+    buf.content().iter().map(|c| c.symbol()).collect()
+}
+
+#[test]
+fn placeholder_renders_dim_only_when_empty() {
+    let mut ta = TextArea::new();
+    ta.set_placeholder_text("Type here");
+
+    let buf = render_row(&ta, 12);
+    assert_eq!(row_text(&buf), "Type here   ");
+    assert_eq!(buf[(0, 0)].fg, crate::tui::palette::PLACEHOLDER);
+    assert_eq!(ta.text(), "");
+    assert_eq!(ta.cursor(), 0);
+
+    ta.insert_str("a");
+    let buf = render_row(&ta, 12);
+    assert_eq!(row_text(&buf), "a           ");
+}
+
+#[test]
+fn placeholder_is_clipped_to_width() {
+    let mut ta = TextArea::new();
+    ta.set_placeholder_text("Type a message, /command, or @file...");
+    assert_eq!(row_text(&render_row(&ta, 6)), "Type a");
+}
+
+// --- Selection ---
+
+fn key(
+    code: crossterm::event::KeyCode,
+    modifiers: crossterm::event::KeyModifiers,
+) -> crossterm::event::KeyEvent {
+    crossterm::event::KeyEvent::new(code, modifiers)
+}
+
+fn shift(code: crossterm::event::KeyCode) -> crossterm::event::KeyEvent {
+    key(code, crossterm::event::KeyModifiers::SHIFT)
+}
+
+#[test]
+fn shift_arrows_extend_selection_from_anchor() {
+    use crossterm::event::KeyCode;
+    let mut ta = textarea_with("hello world");
+    ta.set_cursor(5);
+
+    ta.input(shift(KeyCode::Right));
+    ta.input(shift(KeyCode::Right));
+    assert_eq!(ta.selection_range(), Some(5..7));
+    assert_eq!(ta.selected_text(), Some(" w"));
+
+    // Moving back past the anchor flips the range.
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+    assert_eq!(ta.selection_range(), Some(4..5));
+}
+
+#[test]
+fn shift_home_end_select_to_line_bounds() {
+    use crossterm::event::KeyCode;
+    let mut ta = textarea_with("first\nsecond");
+    ta.set_cursor(9);
+
+    ta.input(shift(KeyCode::Home));
+    assert_eq!(ta.selected_text(), Some("sec"));
+
+    ta.clear_selection();
+    ta.set_cursor(9);
+    ta.input(shift(KeyCode::End));
+    assert_eq!(ta.selected_text(), Some("ond"));
+}
+
+#[test]
+fn plain_movement_clears_selection() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut ta = textarea_with("hello");
+    ta.input(shift(KeyCode::Left));
+    assert!(ta.has_selection());
+
+    ta.input(key(KeyCode::Left, KeyModifiers::NONE));
+    assert!(!ta.has_selection());
+}
+
+#[test]
+fn selection_touching_element_includes_it_whole() {
+    use crossterm::event::KeyCode;
+    let mut ta = textarea_with("see ");
+    ta.insert_element("[image-1]");
+    ta.insert_str(" now");
+    ta.set_cursor(ta.text().len());
+
+    // Select " now" plus one step into the element.
+    for _ in 0..5 {
+        ta.input(shift(KeyCode::Left));
+    }
+    assert_eq!(ta.selected_text(), Some("[image-1] now"));
+}
+
+#[test]
+fn cut_removes_selection_and_round_trips() {
+    let mut ta = textarea_with("alpha beta gamma");
+    ta.set_cursor(6);
+    for _ in 0..5 {
+        ta.input(shift(crossterm::event::KeyCode::Right));
+    }
+
+    let cut = ta.cut();
+    assert_eq!(cut, "beta ");
+    assert_eq!(ta.text(), "alpha gamma");
+    assert_eq!(ta.cursor(), 6);
+    assert!(!ta.has_selection());
+
+    ta.insert_str(&cut);
+    assert_eq!(ta.text(), "alpha beta gamma");
+}
+
+#[test]
+fn cut_without_selection_is_a_no_op() {
+    let mut ta = textarea_with("text");
+    assert_eq!(ta.cut(), "");
+    assert_eq!(ta.text(), "text");
+}
+
+#[test]
+fn select_all_then_cut_empties_buffer() {
+    let mut ta = textarea_with("everything\ngoes");
+    ta.select_all();
+    assert_eq!(ta.selected_text(), Some("everything\ngoes"));
+    assert_eq!(ta.cut(), "everything\ngoes");
+    assert!(ta.is_empty());
+}
+
+#[test]
+fn typing_and_backspace_replace_selection() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut ta = textarea_with("hello world");
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+    ta.input(shift(KeyCode::Left));
+
+    ta.input(key(KeyCode::Char('X'), KeyModifiers::SHIFT));
+    assert_eq!(ta.text(), "hello X");
+
+    ta.select_all();
+    ta.input(key(KeyCode::Backspace, KeyModifiers::NONE));
+    assert!(ta.is_empty());
+}
+
+#[test]
+fn selection_is_highlighted_when_rendered() {
+    let mut ta = textarea_with("abcdef");
+    ta.set_cursor(1);
+    ta.input(shift(crossterm::event::KeyCode::Right));
+    ta.input(shift(crossterm::event::KeyCode::Right));
+
+    let buf = render_row(&ta, 6);
+    assert_eq!(row_text(&buf), "abcdef");
+    assert_eq!(buf[(0, 0)].bg, ratatui::style::Color::Reset);
+    assert_eq!(buf[(1, 0)].bg, crate::tui::palette::SELECTED_BG);
+    assert_eq!(buf[(2, 0)].bg, crate::tui::palette::SELECTED_BG);
+    assert_eq!(buf[(3, 0)].bg, ratatui::style::Color::Reset);
+}
@@ -1,14 +1,17 @@
 use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use std::sync::LazyLock;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
+use crate::terminal_markdown::image_placeholder;
 use crate::tui::palette;
 
-const DEFAULT_TABLE_WIDTH: usize = 120;
+/// Table width for callers that don't know the viewport, such as [`MarkdownRenderer::render`].
+pub const DEFAULT_TABLE_WIDTH: usize = 120;
 
 fn middle_elide(s: &str, max: usize) -> String {
     let chars: Vec<char> = s.chars().collect();
@@ -107,18 +110,27 @@ impl TableBuilder {
     }
 }
 
+// Loading syntect's bundled syntaxes and themes takes tens of milliseconds, so
+// they are built on first use and shared by every renderer.
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    ThemeSet::load_defaults()
+        .themes
+        .remove("base16-ocean.dark")
+        .expect("syntect ships base16-ocean.dark")
+});
+
 pub struct MarkdownRenderer {
-    syntax_set: SyntaxSet,
-    theme: Theme,
+    syntax_set: &'static SyntaxSet,
+    theme: &'static Theme,
 }
 
 impl MarkdownRenderer {
     pub fn new() -> Self {
-        let syntax_set = SyntaxSet::load_defaults_newlines();
-        let theme_set = ThemeSet::load_defaults();
-        let theme = theme_set.themes["base16-ocean.dark"].clone();
-
-        Self { syntax_set, theme }
+        Self {
+            syntax_set: &SYNTAX_SET,
+            theme: &THEME,
+        }
     }
 
     pub fn render(&self, markdown: &str) -> Vec<Line<'static>> {
@@ -148,6 +160,8 @@ impl MarkdownRenderer {
         let mut in_strong = false;
         let mut in_strikethrough = false;
         let mut current_table: Option<TableBuilder> = None;
+        // (url, alt text) while inside an image; alt text arrives as Text events
+        let mut current_image: Option<(String, String)> = None;
 
         for event in parser {
             match event {
@@ -237,7 +251,9 @@ impl MarkdownRenderer {
                         pending_quote_marker = true;
                     }
                     Tag::Link { .. } => {}
-                    Tag::Image { .. } => {}
+                    Tag::Image { dest_url, .. } => {
+                        current_image = Some((dest_url.to_string(), String::new()));
+                    }
                     Tag::Table(alignments) => {
                         if !current_line_spans.is_empty() {
                             lines.push(Line::from(std::mem::take(&mut current_line_spans)));
@@ -352,11 +368,26 @@ impl MarkdownRenderer {
                             lines.push(Line::from(""));
                         }
                     }
+                    TagEnd::Image => {
+                        if let Some((url, alt)) = current_image.take() {
+                            let span = Span::styled(
+                                image_placeholder(&alt, &url),
+                                Style::default().fg(palette::MARKDOWN_LINK),
+                            );
+                            if let Some(ref mut table) = current_table {
+                                table.current_cell.add_span(span);
+                            } else {
+                                current_line_spans.push(span);
+                            }
+                        }
+                    }
                     _ => {}
                 },
                 Event::Text(text) => {
                     if in_code_block {
                         code_buffer.push_str(&text);
+                    } else if let Some((_, ref mut alt)) = current_image {
+                        alt.push_str(&text);
                     } else if let Some(ref mut table) = current_table {
                         // T017: Add text span to current table cell
                         let style = self.get_inline_style(in_emphasis, in_strong, in_strikethrough);
@@ -367,6 +398,11 @@ impl MarkdownRenderer {
                         current_line_spans.push(Span::styled(text.to_string(), style));
                     }
                 }
+                Event::Code(code) if current_image.is_some() => {
+                    if let Some((_, ref mut alt)) = current_image {
+                        alt.push_str(&code);
+                    }
+                }
                 Event::Code(code) => {
                     let style = Style::default()
                         .fg(palette::MARKDOWN_CODE_FG)
@@ -458,7 +494,7 @@ impl MarkdownRenderer {
                 .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text())
         };
 
-        let mut highlighter = HighlightLines::new(syntax, &self.theme);
+        let mut highlighter = HighlightLines::new(syntax, self.theme);
         let code_bg = palette::MARKDOWN_CODE_BG;
 
         let header = if !language.is_empty() {
@@ -473,7 +509,7 @@ impl MarkdownRenderer {
 
         for (line_num, line) in LinesWithEndings::from(code).enumerate() {
             let highlighted = highlighter
-                .highlight_line(line, &self.syntax_set)
+                .highlight_line(line, self.syntax_set)
                 .unwrap_or_default();
 
             let mut spans = vec![Span::styled(
@@ -739,6 +775,18 @@ impl Default for MarkdownRenderer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_renderers_share_syntax_assets() {
+        let first = MarkdownRenderer::new();
+        let second = MarkdownRenderer::new();
+
+        // Every renderer points at the one process-wide load.
+        assert!(std::ptr::eq(first.syntax_set, second.syntax_set));
+        assert!(std::ptr::eq(first.theme, second.theme));
+        assert!(std::ptr::eq(first.syntax_set, &*SYNTAX_SET));
+        assert!(std::ptr::eq(first.theme, &*THEME));
+    }
+
     #[test]
     fn test_render_plain_text() {
         let renderer = MarkdownRenderer::new();
@@ -945,6 +993,32 @@ mod tests {
         line.spans.iter().map(|s| s.content.as_ref()).collect()
     }
 
+    #[test]
+    fn test_render_image_placeholder() {
+        let renderer = MarkdownRenderer::new();
+        let lines = renderer.render_with_indent(
+            "Chart: ![latency *p99*](https://example.com/p99.png)",
+            "",
+            DEFAULT_TABLE_WIDTH,
+        );
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            line_text(&lines[0]),
+            "Chart: 🖼 latency p99 (https://example.com/p99.png)"
+        );
+        let image_span = lines[0].spans.last().unwrap();
+        assert_eq!(image_span.style.fg, Some(palette::MARKDOWN_LINK));
+    }
+
+    #[test]
+    fn test_image_inside_table_cell() {
+        let renderer = MarkdownRenderer::new();
+        let markdown = "| Icon |\n|------|\n| ![ok](ok.png) |";
+        let lines = renderer.render_with_indent(markdown, "", DEFAULT_TABLE_WIDTH);
+        let text: String = lines.iter().map(line_text).collect::<Vec<_>>().join("\n");
+        assert!(text.contains("🖼 ok (ok.png)"));
+    }
+
     #[test]
     fn test_render_simple_table() {
         let renderer = MarkdownRenderer::new();
@@ -1050,6 +1124,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_table_fits_the_given_width() {
+        let renderer = MarkdownRenderer::new();
+        let markdown = "| Name | Description |\n|------|-------------|\n\
+                        | parser | Turns the token stream into an AST |\n\
+                        | checker | Resolves names and infers types |";
+        let widest = |lines: &[Line<'static>]| {
+            lines
+                .iter()
+                .map(|l| line_text(l).chars().count())
+                .max()
+                .unwrap()
+        };
+
+        let narrow = renderer.render_with_indent(markdown, "", 30);
+        assert!(widest(&narrow) <= 30, "narrow table fits: {narrow:?}");
+        let rows = row_lines(&narrow);
+        assert!(
+            rows.iter().any(|t| t.contains('…')),
+            "cells elide: {rows:?}"
+        );
+        // The short column fits its share, so only the long one shrinks.
+        assert!(rows.iter().any(|t| t.contains("│ checker │")));
+
+        let wide = renderer.render_with_indent(markdown, "", 200);
+        assert!(row_lines(&wide).iter().all(|t| !t.contains('…')));
+        assert!(
+            row_lines(&wide)
+                .iter()
+                .any(|t| t.contains("Turns the token stream into an AST"))
+        );
+    }
+
     #[test]
     fn test_path_cells_middle_elide() {
         let renderer = MarkdownRenderer::new();
@@ -1,11 +1,16 @@
 use super::colors::palette;
+use super::inline_image::{self, GraphicsProtocol, InlineImage, MAX_IMAGE_ROWS};
 use super::markdown::MarkdownRenderer;
 use super::state::{AppState, MessageLine};
 use crate::tui::terminal::HooshTerminal;
 use anyhow::Result;
+use crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use crossterm::queue;
+use crossterm::style::Print;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Paragraph, Widget};
+use std::io::Write;
 
 /// Handles rendering of chat messages in the TUI
 ///
@@ -36,14 +41,26 @@ impl MessageRenderer {
         app: &mut AppState,
         terminal: &mut HooshTerminal,
     ) -> Result<()> {
+        let terminal_width = terminal.size()?.width as usize;
+        let table_width = app.table_width(terminal_width.saturating_sub(3));
+        app.streaming.set_table_width(table_width);
+
         if !app.has_pending_messages() {
             return Ok(());
         }
 
-        let terminal_width = terminal.size()?.width as usize;
+        let raw = app.raw_output;
+        let graphics = app.inline_images;
 
         for message in app.drain_pending_messages() {
-            self.render_single_message(message, terminal_width, terminal)?;
+            self.render_single_message(
+                message,
+                terminal_width,
+                table_width,
+                raw,
+                graphics,
+                terminal,
+            )?;
         }
 
         Ok(())
@@ -53,20 +70,88 @@ impl MessageRenderer {
         &self,
         message: MessageLine,
         terminal_width: usize,
+        table_width: usize,
+        raw: bool,
+        graphics: Option<GraphicsProtocol>,
         terminal: &mut HooshTerminal,
     ) -> Result<()> {
         match message {
             MessageLine::Plain(text) => self.render_plain_message(text, terminal_width, terminal),
             MessageLine::Styled(line) => self.render_styled_message(line, terminal),
+            MessageLine::Markdown(markdown) if raw => {
+                self.render_plain_message(markdown, terminal_width, terminal)
+            }
             MessageLine::Markdown(markdown) => {
-                self.render_markdown_message(markdown, terminal_width, terminal)
+                let images = match graphics {
+                    Some(_) => inline_image::markdown_images(&markdown),
+                    None => Vec::new(),
+                };
+                self.render_markdown_message(markdown, terminal_width, table_width, terminal)?;
+                for image in images {
+                    self.render_image(image, graphics, terminal_width, terminal)?;
+                }
+                Ok(())
             }
             MessageLine::Thinking(text) => {
                 self.render_thinking_message(text, terminal_width, terminal)
             }
+            MessageLine::Image(image) => {
+                self.render_image(image, graphics, terminal_width, terminal)
+            }
         }
     }
 
+    /// Draws `image` on its own rows above the viewport. The rows are
+    /// inserted blank first, so everything after the image is laid out below
+    /// the cells it covers; the caption stands in when it can't be drawn.
+    fn render_image(
+        &self,
+        image: InlineImage,
+        graphics: Option<GraphicsProtocol>,
+        terminal_width: usize,
+        terminal: &mut HooshTerminal,
+    ) -> Result<()> {
+        const INDENT: u16 = 2;
+
+        let screen = terminal.size()?;
+        // Rows above the viewport; an image taller than that would be cut
+        // off as it scrolled into history.
+        let room = screen
+            .height
+            .saturating_sub(terminal.get_viewport_area().height);
+        let max_cols = (terminal_width as u16).saturating_sub(INDENT);
+        let (cols, rows) = image.cell_size(
+            inline_image::cell_pixels(),
+            max_cols,
+            room.min(MAX_IMAGE_ROWS),
+        );
+
+        let sequence = graphics
+            .filter(|_| rows > 0)
+            .and_then(|protocol| image.escape_sequence(protocol, cols, rows));
+        let Some(sequence) = sequence else {
+            let caption = Line::from(Span::styled(
+                image.caption,
+                Style::default().fg(palette::MARKDOWN_LINK),
+            ));
+            return self.render_styled_message(caption, terminal);
+        };
+
+        terminal.insert_before(rows, |_| {})?;
+        let top = terminal.get_viewport_area().top().saturating_sub(rows);
+        let backend = terminal.backend_mut();
+        queue!(
+            backend,
+            SavePosition,
+            MoveTo(INDENT, top),
+            Print(sequence),
+            RestorePosition
+        )?;
+        backend.flush()?;
+
+        Ok(())
+    }
+
     fn render_thinking_message(
         &self,
         text: String,
@@ -142,13 +227,12 @@ impl MessageRenderer {
         &self,
         markdown: String,
         terminal_width: usize,
+        table_width: usize,
         terminal: &mut HooshTerminal,
     ) -> Result<()> {
-        let rendered_lines = self.markdown_renderer.render_with_indent(
-            &markdown,
-            "  ",
-            terminal_width.saturating_sub(3),
-        );
+        let rendered_lines =
+            self.markdown_renderer
+                .render_with_indent(&markdown, "  ", table_width);
         let wrapped_lines = self.wrap_styled_lines(rendered_lines, terminal_width);
         let line_count = wrapped_lines.len() as u16;
 
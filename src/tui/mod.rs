@@ -11,11 +11,14 @@ mod clipboard;
 pub mod colors;
 mod component;
 pub mod components;
+pub mod diff_renderer;
+pub mod editor;
 mod events;
 mod handler_result;
 pub mod handlers;
 pub(crate) mod header;
 pub mod init_permission;
+pub mod inline_image;
 pub mod input;
 pub(crate) mod input_handler;
 mod layout;
@@ -25,6 +28,7 @@ mod message_renderer;
 pub mod modes;
 pub mod setup;
 pub(crate) mod state;
+pub mod streaming_markdown;
 pub mod terminal;
 mod text_utils;
 mod tool_phrase;
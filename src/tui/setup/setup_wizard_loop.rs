@@ -72,11 +72,19 @@ pub fn save_wizard_result(result: &SetupWizardResult) -> Result<()> {
             base_url: result.base_url.clone(),
             chat_api: None,
             temperature: None,
+            top_p: None,
             pricing_endpoint: result.pricing_endpoint.clone(),
             thinking_budget: None,
             reasoning_effort: None,
             reasoning_display: None,
             streaming: None,
+            context_window: None,
+            title_model: None,
+            max_concurrent_requests: None,
+            requests_per_minute: None,
+            retry: None,
+            tool_api: None,
+            stop: None,
         },
     );
 
@@ -11,6 +11,8 @@ pub struct ActiveToolCall {
     pub status: ToolCallStatus,
     pub preview: Option<String>,
     pub result_summary: Option<String>,
+    /// Estimated context the result takes up.
+    pub result_tokens: Option<usize>,
     pub subagent: Option<SubagentDetail>,
     pub bash: Option<BashDetail>,
     pub start_time: Instant,
@@ -41,6 +43,7 @@ impl ActiveToolCall {
             status: ToolCallStatus::Starting,
             preview: None,
             result_summary: None,
+            result_tokens: None,
             subagent: None,
             bash: None,
             start_time: Instant::now(),
@@ -57,15 +60,6 @@ impl ActiveToolCall {
     }
 
     pub fn elapsed_time(&self) -> String {
-        let elapsed = self.start_time.elapsed();
-        let total_secs = elapsed.as_secs();
-
-        if total_secs < 60 {
-            format!("{}s", total_secs)
-        } else {
-            let mins = total_secs / 60;
-            let secs = total_secs % 60;
-            format!("{}m{}s", mins, secs)
-        }
+        crate::tui::text_utils::format_elapsed(self.start_time.elapsed())
     }
 }
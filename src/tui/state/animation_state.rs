@@ -1,10 +1,14 @@
-use std::time::Instant;
+use crate::tui::events::AgentState;
+use std::time::{Duration, Instant};
 
 /// Frame counter and spinner cadence for the TUI's animations, advanced on a
 /// fixed 100ms tick decoupled from the event-loop rate.
 pub struct AnimationState {
     pub frame: usize,
     pub last_tick: Instant,
+    /// The busy state the agent is in and when it entered it, so the status
+    /// bar can show how long the current call or tool batch has been running.
+    pub phase: Option<(AgentState, Instant)>,
 }
 
 impl Default for AnimationState {
@@ -12,6 +16,7 @@ impl Default for AnimationState {
         Self {
             frame: 0,
             last_tick: Instant::now(),
+            phase: None,
         }
     }
 }
@@ -23,4 +28,24 @@ impl AnimationState {
             self.last_tick = Instant::now();
         }
     }
+
+    /// Restarts the phase timer whenever the agent changes state, and stops it
+    /// when the agent goes idle.
+    pub fn track_phase(&mut self, state: AgentState) {
+        match (state, self.phase) {
+            (AgentState::Idle, _) => self.phase = None,
+            (state, Some((current, _))) if current == state => {}
+            (state, _) => self.phase = Some((state, Instant::now())),
+        }
+    }
+
+    /// Time spent in `state`. A state change not yet seen by
+    /// [`Self::track_phase`] reads as just started.
+    pub fn phase_elapsed(&self, state: AgentState) -> Option<Duration> {
+        match (state, self.phase) {
+            (AgentState::Idle, _) => None,
+            (state, Some((current, started))) if current == state => Some(started.elapsed()),
+            _ => Some(Duration::ZERO),
+        }
+    }
 }
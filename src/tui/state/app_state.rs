@@ -1,19 +1,27 @@
 use super::tool_call_view::is_exploration_batch;
 use super::*;
-use crate::agent::AgentEvent;
+use crate::agent::{AgentEvent, TaskListItem};
 use crate::completion::Completer;
+use crate::context_management::LARGE_TOOL_RESULT_TOKENS;
 use crate::history::PromptHistory;
 use crate::permissions::ToolPermissionDescriptor;
+use crate::secret_redaction::{PromptSecretCheck, SecretRedactor};
 use crate::tools::todo_write::{TodoItem, TodoStatus};
 use crate::tools::{CategoryPhrasing, ToolRender};
 use crate::tui::clipboard::ClipboardManager;
+use crate::tui::diff_renderer::DiffRenderer;
+use crate::tui::editor::{EditorConfig, FileReference, transcript_references};
 use crate::tui::events::AgentState;
-use crate::tui::input::{PasteDetector, TextArea, TextAttachment};
+use crate::tui::inline_image::{GraphicsProtocol, InlineImage};
+use crate::tui::input::{PasteClassification, PasteDetector, TextArea, TextAttachment};
+use crate::tui::streaming_markdown::StreamingMarkdown;
+use crate::tui::text_utils::format_token_count;
 use crate::tui::{glyphs, palette};
 use anyhow::Result;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use std::collections::VecDeque;
+use std::sync::Arc;
 
 pub struct AppState {
     pub input: TextArea,
@@ -33,11 +41,24 @@ pub struct AppState {
     /// True after a cancel or an idle Ctrl+C with empty input — next Ctrl+C
     /// exits the app even if input is non-empty. Cleared on any other keypress.
     pub quit_armed: bool,
+    /// How a prompt that looks like it contains a secret is handled, and the
+    /// scanner that decides it (`None` when the check is off).
+    pub prompt_secret_check: PromptSecretCheck,
+    pub prompt_secret_scanner: Option<Arc<SecretRedactor>>,
+    /// True after Enter was held back because the input looks like it holds
+    /// a secret — the next Enter sends it as typed. Cleared on any other
+    /// keypress.
+    pub secret_send_armed: bool,
     pub max_messages: usize,
     pub completion_state: Option<CompletionState>,
+    pub history_search: Option<HistorySearchState>,
+    pub task_list: Option<TaskListState>,
     pub completers: Vec<Box<dyn Completer>>,
     pub dialogs: DialogState,
     pub autopilot_enabled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Shared with the running agent; set by Esc to end the turn after the
+    /// in-flight step. Reset when the next turn starts.
+    pub turn_interrupt: std::sync::Arc<std::sync::atomic::AtomicBool>,
     pub animation: AnimationState,
     pub prompt_history: PromptHistory,
     pub clipboard: ClipboardManager,
@@ -50,7 +71,26 @@ pub struct AppState {
     pub attachments: AttachmentState,
     pub paste_detector: PasteDetector,
     pub display_compact: bool,
+    /// Stream every sub-agent event under its task instead of a one-line
+    /// status. On at verbose verbosity.
+    pub verbose_subagents: bool,
+    /// Show assistant text verbatim instead of rendering it as markdown.
+    /// Read at render time, so fullview re-renders past messages too.
+    pub raw_output: bool,
+    /// Configured cap on markdown table width; see [`AppState::table_width`].
+    pub table_max_width: Option<usize>,
+    /// Graphics protocol images are drawn with, when inline images are
+    /// enabled and the terminal supports one.
+    pub inline_images: Option<GraphicsProtocol>,
+    pub editor: EditorConfig,
+    /// A file reference to open once the event loop has the terminal.
+    pub editor_request: Option<FileReference>,
+    /// Last reference opened this turn; asking again steps to an older one.
+    last_opened_reference: Option<FileReference>,
     pub fullview: bool,
+    /// The response currently streaming in. Completed blocks are committed to
+    /// `messages` as they finish; only the tail is re-rendered per delta.
+    pub streaming: StreamingMarkdown,
 }
 
 /// Normalize a short status/error string: trim trailing punctuation/whitespace
@@ -80,6 +120,7 @@ impl AppState {
         let mut input = TextArea::default();
         input.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
         input.set_cursor_line_style(Style::default());
+        input.set_placeholder_text("Type a message, /command, or @file...");
 
         Self {
             input,
@@ -91,13 +132,19 @@ impl AppState {
             last_submitted_input: None,
             queued_prompts: std::collections::VecDeque::new(),
             quit_armed: false,
+            prompt_secret_check: PromptSecretCheck::default(),
+            prompt_secret_scanner: None,
+            secret_send_armed: false,
             max_messages: 100_000,
             completion_state: None,
+            history_search: None,
+            task_list: None,
             completers: Vec::new(),
             dialogs: DialogState::default(),
             autopilot_enabled: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            turn_interrupt: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             animation: AnimationState::default(),
-            prompt_history: PromptHistory::new(1000),
+            prompt_history: PromptHistory::new(crate::history::DEFAULT_HISTORY_SIZE),
             clipboard: ClipboardManager::new(),
             current_retry_status: None,
             metrics: MetricsState::default(),
@@ -108,17 +155,55 @@ impl AppState {
             attachments: AttachmentState::default(),
             paste_detector: PasteDetector::new(),
             display_compact: false,
+            verbose_subagents: false,
+            raw_output: false,
+            table_max_width: None,
+            inline_images: None,
+            editor: EditorConfig::default(),
+            editor_request: None,
+            last_opened_reference: None,
             fullview: false,
+            streaming: StreamingMarkdown::new(),
         }
     }
 
+    /// Queue the newest `path:line` reference in the transcript for the
+    /// editor, or the next older one if that was opened last.
+    pub fn request_editor(&mut self) {
+        let references = transcript_references(self);
+        let next = self
+            .last_opened_reference
+            .as_ref()
+            .and_then(|last| references.iter().position(|r| r == last))
+            .map_or(0, |i| (i + 1) % references.len());
+        let Some(reference) = references.into_iter().nth(next) else {
+            self.add_status_message("No file:line references to open");
+            return;
+        };
+        self.last_opened_reference = Some(reference.clone());
+        self.editor_request = Some(reference);
+    }
+
     pub fn toggle_display_compact(&mut self) -> bool {
         self.display_compact = !self.display_compact;
         self.display_compact
     }
 
+    /// `None` toggles.
+    pub fn set_raw_output(&mut self, enabled: Option<bool>) {
+        self.raw_output = enabled.unwrap_or(!self.raw_output);
+    }
+
+    /// Width a markdown table may use when `content_width` columns are
+    /// available, after the configured cap.
+    pub fn table_width(&self, content_width: usize) -> usize {
+        self.table_max_width
+            .map_or(content_width, |cap| content_width.min(cap))
+    }
+
     pub fn tick_animation(&mut self) {
         self.animation.tick();
+        self.animation.track_phase(self.agent_state);
     }
 
     pub fn register_completer(&mut self, completer: Box<dyn Completer>) {
@@ -132,6 +217,72 @@ impl AppState {
             .position(|c| c.trigger_key() == key && c.should_trigger(&input_before))
     }
 
+    pub fn is_searching_history(&self) -> bool {
+        self.history_search.is_some()
+    }
+
+    pub fn start_history_search(&mut self) {
+        self.prompt_history.reset();
+        self.history_search = Some(HistorySearchState::new(self.get_input_text()));
+    }
+
+    pub fn push_history_search_char(&mut self, c: char) {
+        if let Some(search) = &mut self.history_search {
+            search.push_char(c, &self.prompt_history);
+        }
+        self.show_history_search_match();
+    }
+
+    pub fn pop_history_search_char(&mut self) {
+        if let Some(search) = &mut self.history_search {
+            search.pop_char(&self.prompt_history);
+        }
+        self.show_history_search_match();
+    }
+
+    pub fn search_history_older(&mut self) {
+        if let Some(search) = &mut self.history_search {
+            search.search_older(&self.prompt_history);
+        }
+        self.show_history_search_match();
+    }
+
+    /// Keep the matched prompt in the input buffer and leave search mode.
+    pub fn accept_history_search(&mut self) {
+        self.history_search = None;
+    }
+
+    /// Leave search mode and restore what was typed before Ctrl+R.
+    pub fn cancel_history_search(&mut self) {
+        if let Some(search) = self.history_search.take() {
+            self.set_input_text(&search.original_input);
+        }
+    }
+
+    fn show_history_search_match(&mut self) {
+        let Some(search) = &self.history_search else {
+            return;
+        };
+        let text = match search.current_match(&self.prompt_history) {
+            Some(entry) => entry.to_string(),
+            None if search.query.is_empty() => search.original_input.clone(),
+            None => return,
+        };
+        self.set_input_text(&text);
+    }
+
+    pub fn is_showing_task_list(&self) -> bool {
+        self.task_list.is_some()
+    }
+
+    pub fn show_task_list(&mut self, message_index: usize, items: Vec<TaskListItem>) {
+        self.task_list = Some(TaskListState::new(message_index, items));
+    }
+
+    pub fn hide_task_list(&mut self) {
+        self.task_list = None;
+    }
+
     pub fn is_completing(&self) -> bool {
         self.completion_state.is_some()
     }
@@ -152,8 +303,17 @@ impl AppState {
             .store(!current, std::sync::atomic::Ordering::Relaxed);
     }
 
-    pub fn show_approval_dialog(&mut self, tool_call_id: String, tool_name: String) {
-        self.dialogs.approval = Some(ApprovalDialogState::new(tool_call_id, tool_name));
+    pub fn show_approval_dialog(
+        &mut self,
+        tool_call_id: String,
+        tool_name: String,
+        preview: Option<String>,
+    ) {
+        let mut dialog = ApprovalDialogState::new(tool_call_id, tool_name);
+        if let Some(preview) = preview {
+            dialog = dialog.with_preview(&preview);
+        }
+        self.dialogs.approval = Some(dialog);
     }
 
     pub fn hide_approval_dialog(&mut self) {
@@ -361,6 +521,12 @@ impl AppState {
         }
     }
 
+    pub fn set_tool_call_tokens(&mut self, tool_call_id: &str, tokens: usize) {
+        if let Some(tool_call) = self.get_active_tool_call_mut(tool_call_id) {
+            tool_call.result_tokens = Some(tokens);
+        }
+    }
+
     pub fn get_active_tool_call_mut(&mut self, tool_call_id: &str) -> Option<&mut ActiveToolCall> {
         self.tools
             .active
@@ -436,16 +602,10 @@ impl AppState {
             if let (Some(tool_uses), Some(tokens)) =
                 (subagent.total_tool_uses, subagent.total_tokens)
             {
-                let tokens_formatted = if tokens >= 1000 {
-                    format!("{:.1}k", tokens as f64 / 1000.0)
-                } else {
-                    tokens.to_string()
-                };
-
                 let completion_text = format!(
                     "Done ({} tool uses · {} tokens · {})",
                     tool_uses,
-                    tokens_formatted,
+                    format_token_count(tokens),
                     tool_call.elapsed_time()
                 );
                 self.add_tool_continuation(&completion_text);
@@ -453,7 +613,24 @@ impl AppState {
         } else if !self.display_compact
             && let Some(summary) = &tool_call.result_summary
         {
-            self.add_tool_continuation(summary);
+            match tool_call.result_tokens {
+                Some(tokens) => self.add_tool_continuation(&format!(
+                    "{} · ~{} tokens",
+                    summary,
+                    format_token_count(tokens)
+                )),
+                None => self.add_tool_continuation(summary),
+            }
+        }
+
+        if tool_call.subagent.is_none()
+            && let Some(tokens) = tool_call.result_tokens
+            && tokens >= LARGE_TOOL_RESULT_TOKENS
+        {
+            self.add_tool_continuation(&format!(
+                "Large result (~{} tokens): narrow the call or lower tool_output_truncation",
+                format_token_count(tokens)
+            ));
         }
 
         if let ToolCallStatus::Error(err) = &tool_call.status {
@@ -485,29 +662,51 @@ impl AppState {
     pub fn handle_agent_event(&mut self, event: AgentEvent) {
         match event {
             AgentEvent::Thinking => self.on_thinking(),
-            AgentEvent::StreamStarted | AgentEvent::TextDelta(_) => {}
+            AgentEvent::StreamStarted => self.streaming.reset(),
+            AgentEvent::TextDelta(delta) => self.on_text_delta(&delta),
             AgentEvent::AssistantThought(content) => self.on_assistant_thought(content),
             AgentEvent::AssistantThinking(content) => self.add_thinking(&content),
             AgentEvent::ToolCalls(calls) => self.on_tool_calls(calls),
             AgentEvent::ToolExecutionStarted { tool_call_id, .. } => {
                 self.update_tool_call_status(&tool_call_id, ToolCallStatus::Executing)
             }
-            AgentEvent::ToolPreview { preview, .. } => self.add_message(format!("\n{}", preview)),
+            AgentEvent::ToolPreview { preview, .. } => self.add_diff(&preview),
             AgentEvent::ToolResult {
                 tool_call_id,
                 summary,
+                tokens,
                 ..
-            } => self.set_tool_call_result(&tool_call_id, summary),
+            } => {
+                self.set_tool_call_result(&tool_call_id, summary);
+                self.set_tool_call_tokens(&tool_call_id, tokens);
+            }
             AgentEvent::ToolExecutionCompleted { tool_call_id, .. } => {
                 self.update_tool_call_status(&tool_call_id, ToolCallStatus::Completed)
             }
             AgentEvent::AllToolsComplete => self.on_all_tools_complete(),
             AgentEvent::FinalResponse(content) => self.on_final_response(content),
             AgentEvent::Error(error) => self.on_error(error),
+            AgentEvent::BackendError { message, hint } => {
+                self.on_error(message);
+                if let Some(hint) = hint {
+                    self.add_styled_line(continuation_line(format!("hint: {hint}")));
+                }
+            }
             AgentEvent::MaxStepsReached(max_steps) => self.on_max_steps_reached(max_steps),
+            AgentEvent::TurnInterrupted => self.on_turn_interrupted(),
             AgentEvent::UserRejection(calls) => {
                 self.on_tool_calls_rejected(&calls, "Rejected, tell me what to do instead")
             }
+            AgentEvent::PermissionRuleMatched {
+                tool,
+                rule,
+                allowed,
+            } => self.add_status_message(&format!(
+                "{} {} by rule: {}",
+                tool,
+                if allowed { "allowed" } else { "denied" },
+                rule
+            )),
             AgentEvent::PermissionDenied(calls) => {
                 self.on_tool_calls_rejected(&calls, "Permission denied, tell me what to do instead")
             }
@@ -522,6 +721,23 @@ impl AppState {
                 current_pressure,
                 threshold,
             } => self.on_token_pressure(current_pressure, threshold),
+            AgentEvent::RequestQueued { reason } => {
+                self.add_status_message(&format!("Request queued: {}", reason))
+            }
+            AgentEvent::ContextAutoCompacted {
+                tokens_before,
+                tokens_after,
+            } => self.add_status_message(&format!(
+                "Context window full, compacted ~{} to ~{} tokens and retrying",
+                tokens_before, tokens_after
+            )),
+            AgentEvent::LargeFileRead {
+                path,
+                bytes,
+                truncated,
+            } => self.add_status_message(&crate::tools::file_ops::large_read_message(
+                &path, bytes, truncated,
+            )),
             AgentEvent::TokenUsage {
                 input_tokens,
                 output_tokens,
@@ -560,6 +776,12 @@ impl AppState {
                 ..
             } => self.on_bash_output(tool_call_id, line_number, output_line, stream_type),
             AgentEvent::TodoUpdate { todos } => self.on_todo_update(todos),
+            AgentEvent::ShowTaskList {
+                message_index,
+                items,
+            } => self.show_task_list(message_index, items),
+            AgentEvent::SetRawOutput(enabled) => self.set_raw_output(enabled),
+            AgentEvent::ShowDiff(diff) => self.add_diff(&diff),
             AgentEvent::ThinkingDelta(_)
             | AgentEvent::ToolPermissionRequest { .. }
             | AgentEvent::ApprovalRequest { .. }
@@ -567,7 +789,10 @@ impl AppState {
             | AgentEvent::ClearConversation
             | AgentEvent::DebugMessage(_)
             | AgentEvent::StepStarted { .. }
-            | AgentEvent::SwitchBackend { .. } => {}
+            | AgentEvent::SwitchBackend { .. }
+            | AgentEvent::SetReasoningEffort { .. }
+            | AgentEvent::SetBackendSetting { .. }
+            | AgentEvent::SwitchAgent { .. } => {}
         }
     }
 
@@ -575,8 +800,35 @@ impl AppState {
         self.agent_state = AgentState::Thinking;
     }
 
+    fn on_text_delta(&mut self, delta: &str) {
+        let first_block = !self.streaming.has_committed();
+        for (i, block) in self.streaming.push(delta).into_iter().enumerate() {
+            if first_block && i == 0 {
+                self.seal_exploration_run();
+                self.add_message("\n".to_string());
+            }
+            self.add_message_line(MessageLine::Markdown(block));
+        }
+    }
+
+    /// Commit whatever part of a streamed message hasn't been committed yet.
+    /// Returns false when nothing was streamed and the caller should add the
+    /// whole message itself.
+    fn finish_streamed_message(&mut self, content: &str) -> bool {
+        let Some(remainder) = self.streaming.finish(content) else {
+            return false;
+        };
+        let remainder = remainder.trim_matches('\n');
+        if !remainder.is_empty() {
+            self.add_message_line(MessageLine::Markdown(remainder.to_string()));
+        }
+        true
+    }
+
     fn on_assistant_thought(&mut self, content: String) {
-        self.add_thought(&content);
+        if !self.finish_streamed_message(&content) {
+            self.add_thought(&content);
+        }
     }
 
     fn on_tool_calls(&mut self, calls: Vec<crate::agent::PendingToolCall>) {
@@ -596,11 +848,14 @@ impl AppState {
 
     fn on_final_response(&mut self, content: String) {
         self.agent_state = AgentState::Idle;
-        self.add_final_response(&content);
+        if !self.finish_streamed_message(&content) {
+            self.add_final_response(&content);
+        }
     }
 
     fn on_error(&mut self, error: String) {
         self.agent_state = AgentState::Idle;
+        self.streaming.reset();
         self.seal_exploration_run();
         self.add_error(&error);
     }
@@ -609,11 +864,19 @@ impl AppState {
         self.agent_state = AgentState::Idle;
         self.seal_exploration_run();
         self.add_message(format!(
-            "   Maximum conversation steps ({}) reached, stopping.",
+            "   Step limit ({}) reached, stopping. Raise agent_max_steps to allow longer turns.",
             max_steps
         ));
     }
 
+    fn on_turn_interrupted(&mut self) {
+        self.agent_state = AgentState::Idle;
+        self.streaming.reset();
+        self.seal_exploration_run();
+        self.clear_active_tool_calls();
+        self.add_status_message("Interrupted, tell me what to do instead");
+    }
+
     fn on_tool_calls_rejected(&mut self, rejected: &[String], reason: &str) {
         self.seal_exploration_run();
         for rtc in rejected {
@@ -659,11 +922,15 @@ impl AppState {
         description: String,
         budget_pct: f32,
     ) {
+        let verbose = self.verbose_subagents;
         if let Some(tool_call) = self.get_active_tool_call_mut(&tool_call_id) {
             tool_call.budget_pct = Some(budget_pct);
             tool_call
                 .subagent
-                .get_or_insert_with(SubagentDetail::default)
+                .get_or_insert_with(|| SubagentDetail {
+                    verbose,
+                    ..Default::default()
+                })
                 .steps
                 .push(SubagentStepSummary {
                     step_number,
@@ -789,12 +1056,18 @@ impl AppState {
     }
 
     pub fn add_user_input(&mut self, input: &str) {
+        self.last_opened_reference = None;
         self.seal_exploration_run();
         self.add_message(format!("\n> {}", input));
     }
 
-    pub fn add_tool_preview(&mut self, preview: &str) {
-        self.add_message(format!("\n{}", preview));
+    /// Show a unified diff, such as a file-tool preview, with +/- gutters and
+    /// the changed words highlighted.
+    pub fn add_diff(&mut self, diff: &str) {
+        self.add_message("\n".to_string());
+        for line in DiffRenderer::new().render_unified(diff) {
+            self.add_styled_line(line);
+        }
     }
 
     pub fn add_retry_failure(&mut self, message: &str) {
@@ -833,17 +1106,13 @@ impl AppState {
     }
 
     pub fn create_attachment(&mut self, content: String) -> Result<usize> {
-        let size_bytes = content.len();
-
-        if size_bytes > 5_000_000 {
-            anyhow::bail!("Paste rejected: exceeds 5MB limit");
-        }
-
-        if content.chars().count() <= 200 {
-            anyhow::bail!("Content too small for attachment (must be >200 chars)");
+        match self.paste_detector.classify_paste(&content) {
+            PasteClassification::Attachment => Ok(self.attachments.add_text(content)),
+            PasteClassification::Rejected(msg) => anyhow::bail!(msg),
+            PasteClassification::Inline => {
+                anyhow::bail!("Content too small for attachment (below the paste thresholds)")
+            }
         }
-
-        Ok(self.attachments.add_text(content))
     }
 
     pub fn delete_attachment(&mut self, id: usize) -> Result<()> {
@@ -858,12 +1127,41 @@ impl AppState {
         self.attachments.clear();
     }
 
+    /// Drop the attachment focused in the attachments bar, along with its
+    /// marker in the input. Returns false when there is nothing to remove.
+    pub fn remove_focused_attachment(&mut self) -> bool {
+        let Some(marker) = self.attachments.remove_focused() else {
+            return false;
+        };
+        if let Some(start) = self.input.text().find(&marker) {
+            self.input.replace_range(start..start + marker.len(), "");
+        }
+        true
+    }
+
     /// Park a pasted image on the draft. Returns the assigned id so the caller
     /// can insert the matching `[pasted image-N]` marker into the input.
     pub fn add_image_attachment(&mut self, data: Vec<u8>, media_type: String) -> usize {
         self.attachments.add_image(media_type, data)
     }
 
+    /// Queue the draft's pasted images for display under the submitted
+    /// prompt. Does nothing unless inline images are enabled.
+    pub fn show_pasted_images(&mut self) {
+        if self.inline_images.is_none() {
+            return;
+        }
+        let images: Vec<InlineImage> = self
+            .attachments
+            .images
+            .iter()
+            .filter_map(|image| InlineImage::from_bytes(image.marker(), image.data.clone()))
+            .collect();
+        for image in images {
+            self.add_message_line(MessageLine::Image(image));
+        }
+    }
+
     /// Drain the image attachments queued by the user, converting them to
     /// [`crate::agent::Attachment`] for the conversation.
     pub fn drain_image_attachments(&mut self) -> Vec<crate::agent::Attachment> {
@@ -881,8 +1179,7 @@ impl AppState {
     pub fn expand_attachments(&self, input: &str) -> String {
         let mut expanded = input.to_string();
         for attachment in &self.attachments.text {
-            let token = format!("[pasted text-{}]", attachment.id);
-            expanded = expanded.replace(&token, &attachment.content);
+            expanded = expanded.replace(&attachment.marker(), &attachment.content);
         }
         expanded
     }
@@ -928,6 +1225,23 @@ mod image_attachment_tests {
         assert_eq!(state.attachments.next_image_id, 1);
     }
 
+    #[test]
+    fn pasted_images_are_shown_only_when_inline_images_are_on() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x10\0\0\0\x10".to_vec();
+        let mut state = AppState::new();
+        state.add_image_attachment(png.clone(), "image/png".to_string());
+        state.show_pasted_images();
+        assert!(!state.has_pending_messages());
+
+        state.inline_images = Some(GraphicsProtocol::Kitty);
+        state.show_pasted_images();
+        let lines = state.drain_pending_messages();
+        assert!(matches!(
+            lines.as_slice(),
+            [MessageLine::Image(image)] if image.caption == "[pasted image-1]"
+        ));
+    }
+
     #[test]
     fn clear_attachments_also_wipes_images() {
         let mut state = AppState::new();
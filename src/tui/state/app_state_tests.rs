@@ -142,6 +142,71 @@ fn final_response_commits_full_text() {
     assert!(rendered_text(&mut state).contains("the complete answer"));
 }
 
+#[test]
+fn streamed_blocks_commit_before_the_final_response() {
+    let mut state = AppState::new();
+    state.handle_agent_event(AgentEvent::StreamStarted);
+    state.handle_agent_event(AgentEvent::TextDelta("First block.\n\nSec".into()));
+    state.handle_agent_event(AgentEvent::TextDelta("ond block.\n".into()));
+
+    assert!(rendered_text(&mut state).contains("First block."));
+    assert!(!state.streaming.tail_lines().is_empty());
+
+    state.handle_agent_event(AgentEvent::FinalResponse(
+        "First block.\n\nSecond block.\n".into(),
+    ));
+    let rendered = rendered_text(&mut state);
+    assert!(!rendered.contains("First block."), "got: {rendered}");
+    assert!(rendered.contains("Second block."), "got: {rendered}");
+    assert!(state.streaming.tail_lines().is_empty());
+}
+
+#[test]
+fn tool_preview_renders_as_styled_diff() {
+    let mut state = AppState::new();
+    state.handle_agent_event(AgentEvent::ToolPreview {
+        preview: "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-```old\n+new\n".into(),
+    });
+
+    let texts: Vec<String> = state
+        .messages
+        .iter()
+        .filter_map(|line| match line {
+            MessageLine::Styled(line) => {
+                Some(line.spans.iter().map(|s| s.content.as_ref()).collect())
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(
+        texts.ends_with(&[
+            "--- a/x".to_string(),
+            "+++ b/x".to_string(),
+            "@@ -1 +1 @@".to_string(),
+            "- ```old".to_string(),
+            "+ new".to_string(),
+        ]),
+        "got: {texts:?}"
+    );
+}
+
+#[test]
+fn approval_dialog_renders_the_preview_diff() {
+    let mut state = AppState::new();
+    state.handle_agent_event(AgentEvent::ShowDiff("@@ -1 +1 @@\n-a\n+b\n".into()));
+    assert!(matches!(
+        state.messages.back(),
+        Some(MessageLine::Styled(_))
+    ));
+
+    state.show_approval_dialog(
+        "call1".to_string(),
+        "edit_file".to_string(),
+        Some("@@ -1 +1 @@\n-a\n+b\n".to_string()),
+    );
+    assert_eq!(state.dialogs.approval.as_ref().unwrap().preview.len(), 3);
+}
+
 #[test]
 fn app_state_tick_animation_increments_after_interval() {
     let mut state = AppState::new();
@@ -158,6 +223,34 @@ fn app_state_tick_animation_increments_after_interval() {
     assert_eq!(state.animation.frame, initial.wrapping_add(1));
 }
 
+#[test]
+fn app_state_tick_tracks_busy_phase() {
+    let mut state = AppState::new();
+    state.tick_animation();
+    assert!(state.animation.phase.is_none());
+
+    state.agent_state = AgentState::Thinking;
+    state.tick_animation();
+    let (phase, thinking_started) = state.animation.phase.unwrap();
+    assert_eq!(phase, AgentState::Thinking);
+
+    state.tick_animation();
+    assert_eq!(state.animation.phase.unwrap().1, thinking_started);
+
+    state.agent_state = AgentState::ExecutingTools;
+    assert_eq!(
+        state.animation.phase_elapsed(AgentState::ExecutingTools),
+        Some(std::time::Duration::ZERO)
+    );
+    state.tick_animation();
+    assert_eq!(state.animation.phase.unwrap().0, AgentState::ExecutingTools);
+
+    state.agent_state = AgentState::Idle;
+    state.tick_animation();
+    assert!(state.animation.phase.is_none());
+    assert!(state.animation.phase_elapsed(AgentState::Idle).is_none());
+}
+
 #[test]
 fn app_state_toggle_autopilot() {
     let mut state = AppState::new();
@@ -259,7 +352,7 @@ fn app_state_apply_completion_without_state() {
 #[test]
 fn app_state_show_approval_dialog() {
     let mut state = AppState::new();
-    state.show_approval_dialog("call1".to_string(), "bash".to_string());
+    state.show_approval_dialog("call1".to_string(), "bash".to_string(), None);
 
     assert!(state.is_showing_approval_dialog());
     let dialog = state.dialogs.approval.as_ref().unwrap();
@@ -270,7 +363,7 @@ fn app_state_show_approval_dialog() {
 #[test]
 fn app_state_hide_approval_dialog() {
     let mut state = AppState::new();
-    state.show_approval_dialog("call1".to_string(), "bash".to_string());
+    state.show_approval_dialog("call1".to_string(), "bash".to_string(), None);
     assert!(state.is_showing_approval_dialog());
 
     state.hide_approval_dialog();
@@ -280,7 +373,7 @@ fn app_state_hide_approval_dialog() {
 #[test]
 fn app_state_select_approval_options() {
     let mut state = AppState::new();
-    state.show_approval_dialog("call1".to_string(), "bash".to_string());
+    state.show_approval_dialog("call1".to_string(), "bash".to_string(), None);
 
     state.select_next_approval_option();
     assert_eq!(state.dialogs.approval.as_ref().unwrap().selected_index, 1);
@@ -457,6 +550,7 @@ fn rendered_text(state: &mut AppState) -> String {
             MessageLine::Plain(s) | MessageLine::Markdown(s) | MessageLine::Thinking(s) => {
                 s.clone()
             }
+            MessageLine::Image(image) => image.caption.clone(),
             MessageLine::Styled(l) => l
                 .spans
                 .iter()
@@ -512,6 +606,27 @@ fn complete_single_tool_call_includes_continuation_in_full_mode() {
     assert!(rendered_text(&mut state).contains("unique-result-marker"));
 }
 
+#[test]
+fn completed_tool_call_shows_result_size_and_flags_large_ones() {
+    let mut state = AppState::new();
+    for (id, tokens) in [("small", 420), ("big", 12_300)] {
+        state.add_active_tool_call(
+            id.to_string(),
+            "bash".to_string(),
+            ToolRender::Standard,
+            phrasing::GENERIC,
+        );
+        state.set_tool_call_result(id, format!("{id}-output"));
+        state.set_tool_call_tokens(id, tokens);
+        state.complete_single_tool_call(id);
+    }
+
+    let text = rendered_text(&mut state);
+    assert!(text.contains("small-output · ~420 tokens"), "got: {text}");
+    assert!(text.contains("big-output · ~12.3k tokens"));
+    assert_eq!(text.matches("Large result").count(), 1);
+}
+
 #[test]
 fn batch_completion_collapses_to_single_summary_in_scrollback() {
     let mut state = AppState::new();
@@ -957,6 +1072,7 @@ fn subagent_completion_preserves_individual_stats() {
             MessageLine::Styled(line) => line.spans.iter().map(|s| s.content.as_ref()).collect(),
             MessageLine::Markdown(t) => t.clone(),
             MessageLine::Thinking(t) => t.clone(),
+            MessageLine::Image(image) => image.caption.clone(),
         })
         .collect::<Vec<_>>()
         .join("\n");
@@ -965,3 +1081,72 @@ fn subagent_completion_preserves_individual_stats() {
         "each subagent should report its own stats, got:\n{rendered}"
     );
 }
+
+#[test]
+fn set_raw_output_toggles_and_sets() {
+    let mut state = AppState::new();
+    assert!(!state.raw_output);
+
+    state.handle_agent_event(AgentEvent::SetRawOutput(None));
+    assert!(state.raw_output);
+    state.handle_agent_event(AgentEvent::SetRawOutput(Some(true)));
+    assert!(state.raw_output);
+    state.handle_agent_event(AgentEvent::SetRawOutput(Some(false)));
+    assert!(!state.raw_output);
+}
+
+#[test]
+fn table_width_follows_the_viewport_up_to_the_cap() {
+    let mut state = AppState::new();
+    assert_eq!(state.table_width(200), 200);
+
+    state.table_max_width = Some(100);
+    assert_eq!(state.table_width(200), 100);
+    assert_eq!(state.table_width(60), 60);
+}
+
+#[test]
+fn attachments_can_be_focused_and_removed_with_their_marker() {
+    let mut state = AppState::new();
+    let first = state.create_attachment("line\n".repeat(60)).unwrap();
+    state.create_attachment("x".repeat(201)).unwrap();
+    state.add_image_attachment(vec![0; 2048], "image/png".to_string());
+    let marker = state.get_attachment(first).unwrap().marker();
+    state.input.insert_str("see ");
+    state.input.insert_element(&marker);
+    state.input.insert_str(" please");
+
+    let summaries = state.attachments.summaries();
+    assert_eq!(summaries.len(), 3);
+    assert_eq!(summaries[0].name, "pasted 60 lines");
+    assert_eq!(summaries[0].detail, "300 B");
+    assert_eq!(summaries[2].detail, "image/png, 2.0 KB");
+    // Newest is focused until the user moves focus; it wraps around.
+    assert_eq!(state.attachments.focused_index(), Some(2));
+    state.attachments.focus_next();
+    assert_eq!(state.attachments.focused_index(), Some(0));
+
+    assert!(state.remove_focused_attachment());
+    assert_eq!(state.get_input_text(), "see  please");
+    assert!(state.get_attachment(first).is_none());
+    assert_eq!(state.attachments.len(), 2);
+
+    assert!(state.remove_focused_attachment());
+    assert!(state.remove_focused_attachment());
+    assert!(state.attachments.is_empty());
+    assert!(!state.remove_focused_attachment());
+}
+
+#[test]
+fn backend_error_shows_its_hint_under_the_message() {
+    let mut state = AppState::new();
+    state.handle_agent_event(AgentEvent::BackendError {
+        message: "Context window exceeded: prompt is too long".to_string(),
+        hint: Some("Try /compact to summarize the conversation".to_string()),
+    });
+
+    let rendered = rendered_text(&mut state);
+    assert!(rendered.contains("context window exceeded: prompt is too long"));
+    assert!(rendered.contains("hint: Try /compact"));
+    assert_eq!(state.agent_state, AgentState::Idle);
+}
@@ -1,12 +1,22 @@
 use crate::tui::input::{ImageAttachment, TextAttachment};
 
-/// Text and image attachments queued on the current draft, each with a
-/// monotonic id used to match `[attachment-N]` / `[pasted image-N]` markers.
+/// Text and image attachments queued on the current draft. Each one stands in
+/// the input as a `[pasted N lines]` / `[pasted image-N]` marker until submit.
 pub struct AttachmentState {
     pub text: Vec<TextAttachment>,
     pub images: Vec<ImageAttachment>,
     pub next_text_id: usize,
     pub next_image_id: usize,
+    /// Index into [`AttachmentState::summaries`] of the attachment the remove
+    /// key acts on. The newest attachment when unset.
+    pub focused: Option<usize>,
+}
+
+/// One row of the attachments bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentSummary {
+    pub name: String,
+    pub detail: String,
 }
 
 impl Default for AttachmentState {
@@ -16,6 +26,7 @@ impl Default for AttachmentState {
             images: Vec::new(),
             next_text_id: 1,
             next_image_id: 1,
+            focused: None,
         }
     }
 }
@@ -24,7 +35,13 @@ impl AttachmentState {
     pub fn add_text(&mut self, content: String) -> usize {
         let id = self.next_text_id;
         self.next_text_id += 1;
-        self.text.push(TextAttachment::new(id, content));
+        let mut attachment = TextAttachment::new(id, content);
+        // Two pastes of the same length would otherwise share a marker and
+        // expand to the same content.
+        if self.text.iter().any(|a| a.label == attachment.label) {
+            attachment.label = format!("{} #{}", attachment.label, id);
+        }
+        self.text.push(attachment);
         id
     }
 
@@ -57,6 +74,53 @@ impl AttachmentState {
         self.images.clear();
         self.next_text_id = 1;
         self.next_image_id = 1;
+        self.focused = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len() + self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Text attachments first, then images, each in the order added.
+    pub fn summaries(&self) -> Vec<AttachmentSummary> {
+        let text = self.text.iter().map(|att| AttachmentSummary {
+            name: att.name(),
+            detail: format_size(att.content.len()),
+        });
+        let images = self.images.iter().map(|att| AttachmentSummary {
+            name: att.name(),
+            detail: format!("{}, {}", att.media_type, format_size(att.data.len())),
+        });
+        text.chain(images).collect()
+    }
+
+    pub fn focused_index(&self) -> Option<usize> {
+        let last = self.len().checked_sub(1)?;
+        Some(self.focused.unwrap_or(last).min(last))
+    }
+
+    /// Move focus to the next attachment, wrapping around.
+    pub fn focus_next(&mut self) {
+        if let Some(index) = self.focused_index() {
+            self.focused = Some((index + 1) % self.len());
+        }
+    }
+
+    /// Remove the focused attachment and return its marker so the caller can
+    /// take it out of the input too. Focus stays on the same row.
+    pub fn remove_focused(&mut self) -> Option<String> {
+        let index = self.focused_index()?;
+        let marker = if index < self.text.len() {
+            self.text.remove(index).marker()
+        } else {
+            self.images.remove(index - self.text.len()).marker()
+        };
+        self.focused = Some(index);
+        Some(marker)
     }
 
     pub fn drain_images(&mut self) -> Vec<crate::agent::Attachment> {
@@ -70,6 +134,19 @@ impl AttachmentState {
             })
             .collect();
         self.next_image_id = 1;
+        self.focused = None;
         out
     }
 }
+
+fn format_size(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f < KB {
+        format!("{} B", bytes)
+    } else if bytes_f < KB * KB {
+        format!("{:.1} KB", bytes_f / KB)
+    } else {
+        format!("{:.1} MB", bytes_f / (KB * KB))
+    }
+}
@@ -1,4 +1,7 @@
+use ratatui::text::Line;
+
 use crate::permissions::ToolPermissionDescriptor;
+use crate::tui::diff_renderer::DiffRenderer;
 
 pub struct ToolPermissionDialogState {
     pub descriptor: ToolPermissionDescriptor,
@@ -11,6 +14,8 @@ pub struct ApprovalDialogState {
     pub tool_call_id: String,
     pub tool_name: String,
     pub selected_index: usize,
+    /// The tool's diff preview, rendered once when the dialog opens.
+    pub preview: Vec<Line<'static>>,
 }
 
 impl ApprovalDialogState {
@@ -19,8 +24,14 @@ impl ApprovalDialogState {
             tool_call_id,
             tool_name,
             selected_index: 0, // 0 = Approve, 1 = Reject
+            preview: Vec::new(),
         }
     }
+
+    pub fn with_preview(mut self, preview: &str) -> Self {
+        self.preview = DiffRenderer::new().render_unified(preview);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,115 @@
+use crate::history::PromptHistory;
+
+/// Incremental reverse search through prompt history (Ctrl+R).
+pub struct HistorySearchState {
+    pub query: String,
+    pub match_index: Option<usize>,
+    /// Input buffer contents when the search started, restored on cancel.
+    pub original_input: String,
+}
+
+impl HistorySearchState {
+    pub fn new(original_input: String) -> Self {
+        Self {
+            query: String::new(),
+            match_index: None,
+            original_input,
+        }
+    }
+
+    pub fn push_char(&mut self, c: char, history: &PromptHistory) {
+        self.query.push(c);
+        self.search_from_newest(history);
+    }
+
+    pub fn pop_char(&mut self, history: &PromptHistory) {
+        self.query.pop();
+        self.search_from_newest(history);
+    }
+
+    /// Move to the next older match, keeping the current one if none remain.
+    pub fn search_older(&mut self, history: &PromptHistory) {
+        if self.query.is_empty() {
+            return;
+        }
+        let before = self.match_index.unwrap_or(history.len());
+        if let Some(idx) = history.search_backward(&self.query, before) {
+            self.match_index = Some(idx);
+        }
+    }
+
+    pub fn current_match<'a>(&self, history: &'a PromptHistory) -> Option<&'a str> {
+        self.match_index.and_then(|idx| history.entry(idx))
+    }
+
+    pub fn is_failing(&self) -> bool {
+        !self.query.is_empty() && self.match_index.is_none()
+    }
+
+    pub fn label(&self) -> String {
+        let prefix = if self.is_failing() {
+            "failing reverse-i-search"
+        } else {
+            "reverse-i-search"
+        };
+        format!("({})`{}'", prefix, self.query)
+    }
+
+    fn search_from_newest(&mut self, history: &PromptHistory) {
+        self.match_index = if self.query.is_empty() {
+            None
+        } else {
+            history.search_backward(&self.query, history.len())
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(entries: &[&str]) -> PromptHistory {
+        let mut history = PromptHistory::new(100);
+        for entry in entries {
+            history.add(entry.to_string());
+        }
+        history
+    }
+
+    #[test]
+    fn typing_narrows_to_newest_match() {
+        let history = history(&["deploy staging", "run tests", "deploy prod"]);
+        let mut state = HistorySearchState::new(String::new());
+
+        state.push_char('d', &history);
+        state.push_char('e', &history);
+
+        assert_eq!(state.current_match(&history), Some("deploy prod"));
+    }
+
+    #[test]
+    fn search_older_cycles_and_stops_at_oldest() {
+        let history = history(&["deploy staging", "run tests", "deploy prod"]);
+        let mut state = HistorySearchState::new(String::new());
+        state.push_char('d', &history);
+
+        state.search_older(&history);
+        assert_eq!(state.current_match(&history), Some("deploy staging"));
+
+        state.search_older(&history);
+        assert_eq!(state.current_match(&history), Some("deploy staging"));
+    }
+
+    #[test]
+    fn no_match_marks_search_failing() {
+        let history = history(&["run tests"]);
+        let mut state = HistorySearchState::new(String::new());
+        state.push_char('z', &history);
+
+        assert!(state.is_failing());
+        assert!(state.label().starts_with("(failing reverse-i-search)"));
+
+        state.pop_char(&history);
+        assert!(!state.is_failing());
+    }
+}
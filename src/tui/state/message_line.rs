@@ -1,3 +1,4 @@
+use crate::tui::inline_image::InlineImage;
 use ratatui::text::Line;
 
 #[derive(Clone)]
@@ -6,4 +7,6 @@ pub enum MessageLine {
     Styled(Line<'static>),
     Markdown(String),
     Thinking(String),
+    /// Drawn inline where the terminal supports it, else as its caption.
+    Image(InlineImage),
 }
@@ -4,9 +4,11 @@ mod app_state;
 mod attachment_state;
 mod completion_state;
 mod dialog_state;
+mod history_search_state;
 mod message_line;
 mod metrics_state;
 mod scroll_state;
+mod task_list_state;
 mod tool_call_view;
 mod tool_detail;
 
@@ -18,8 +20,10 @@ pub use completion_state::CompletionState;
 pub use dialog_state::{
     ApprovalDialogState, DialogState, PermissionOption, ToolPermissionDialogState,
 };
+pub use history_search_state::HistorySearchState;
 pub use message_line::MessageLine;
 pub use metrics_state::MetricsState;
 pub use scroll_state::ScrollState;
+pub use task_list_state::TaskListState;
 pub use tool_call_view::ToolCallView;
 pub use tool_detail::{BashDetail, BashOutputLine, SubagentDetail, SubagentStepSummary};
@@ -0,0 +1,82 @@
+use crate::agent::TaskListItem;
+
+/// Picker over the task-list items of one assistant message.
+pub struct TaskListState {
+    pub message_index: usize,
+    pub items: Vec<TaskListItem>,
+    pub selected_index: usize,
+}
+
+impl TaskListState {
+    pub fn new(message_index: usize, items: Vec<TaskListItem>) -> Self {
+        Self {
+            message_index,
+            items,
+            selected_index: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.items.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.items.is_empty() {
+            self.selected_index = self
+                .selected_index
+                .checked_sub(1)
+                .unwrap_or(self.items.len() - 1);
+        }
+    }
+
+    /// Flip the selected item and return its line and new checked state.
+    pub fn toggle_selected(&mut self) -> Option<(usize, bool)> {
+        let item = self.items.get_mut(self.selected_index)?;
+        item.checked = !item.checked;
+        Some((item.line, item.checked))
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.items.iter().filter(|i| i.checked).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> TaskListState {
+        let items = ["a", "b", "c"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| TaskListItem {
+                line: i * 2,
+                checked: i == 1,
+                text: t.to_string(),
+            })
+            .collect();
+        TaskListState::new(4, items)
+    }
+
+    #[test]
+    fn selection_wraps_both_ways() {
+        let mut state = state();
+        state.select_prev();
+        assert_eq!(state.selected_index, 2);
+        state.select_next();
+        assert_eq!(state.selected_index, 0);
+    }
+
+    #[test]
+    fn toggle_flips_selected_item() {
+        let mut state = state();
+        state.select_next();
+
+        assert_eq!(state.toggle_selected(), Some((2, false)));
+        assert_eq!(state.completed_count(), 0);
+        assert_eq!(state.toggle_selected(), Some((2, true)));
+        assert_eq!(state.completed_count(), 1);
+    }
+}
@@ -31,6 +31,9 @@ pub struct SubagentDetail {
     pub steps: Vec<SubagentStepSummary>,
     pub total_tool_uses: Option<usize>,
     pub total_tokens: Option<usize>,
+    /// Show the event stream rather than a one-line status without waiting
+    /// for ctrl+o; set at verbose verbosity.
+    pub verbose: bool,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -38,29 +41,72 @@ pub struct BashDetail {
     pub lines: Vec<BashOutputLine>,
 }
 
-const MAX_SUBAGENT_STEPS: usize = 5;
+const SUBAGENT_EXPANDED_STEPS: usize = 30;
 const BASH_COLLAPSED_LINES: usize = 5;
 const BASH_EXPANDED_LINES: usize = 30;
 const BASH_LINE_WIDTH: usize = 80;
 
+impl SubagentDetail {
+    /// Current step and the latest thing the task did, e.g.
+    /// `step 4 · Executing grep`.
+    fn status_line(&self) -> Option<String> {
+        let step = self.steps.last()?.step_number;
+        let latest = self
+            .steps
+            .iter()
+            .rev()
+            .find(|s| s.action_type != "step")
+            .map(|s| format!(" · {}", s.description))
+            .unwrap_or_default();
+        Some(format!("step {}{}", step, latest))
+    }
+}
+
 impl ToolDetail for SubagentDetail {
-    fn detail_lines(&self, _expanded: bool) -> Vec<Line<'static>> {
+    fn detail_lines(&self, expanded: bool) -> Vec<Line<'static>> {
         let style = Style::default().fg(palette::SUBDUED_TEXT);
+
+        if !expanded && !self.verbose {
+            let Some(status) = self.status_line() else {
+                return Vec::new();
+            };
+            let hint = if self.steps.len() > 1 {
+                format!(" (+{} events · ctrl+o to expand)", self.steps.len() - 1)
+            } else {
+                String::new()
+            };
+            let mut out = Vec::new();
+            let row = DetailRow {
+                text: format!("{}{}", status, hint),
+                style,
+            };
+            emit(&mut out, row, &mut false);
+            return out;
+        }
+
         let rows = self
             .steps
             .iter()
             .map(|step| DetailRow {
-                text: step.description.clone(),
+                text: format!("[{}] {}", step.step_number, step.description),
                 style,
             })
             .collect();
 
-        trailing_block(rows, MAX_SUBAGENT_STEPS, OverflowPos::Bottom, |_hidden| {
-            DetailRow {
-                text: "...".into(),
-                style,
-            }
-        })
+        let verbose = self.verbose;
+        trailing_block(
+            rows,
+            SUBAGENT_EXPANDED_STEPS,
+            OverflowPos::Top,
+            move |hidden| {
+                let hint = if verbose {
+                    format!("... (+{hidden} earlier events)")
+                } else {
+                    format!("... (+{hidden} earlier events · ctrl+o to collapse)")
+                };
+                DetailRow { text: hint, style }
+            },
+        )
     }
 }
 
@@ -111,7 +157,6 @@ pub struct DetailRow {
 
 pub enum OverflowPos {
     Top,
-    Bottom,
 }
 
 const BOUNDARY_PREFIX: &str = "  ⎿ ";
@@ -139,12 +184,6 @@ fn trailing_block(
                 emit(&mut out, row, &mut boundary_used);
             }
         }
-        (OverflowPos::Bottom, Some(row)) => {
-            for r in rows.into_iter().skip(total - shown) {
-                emit(&mut out, r, &mut boundary_used);
-            }
-            emit(&mut out, row, &mut boundary_used);
-        }
         (_, None) => {
             for row in rows.into_iter().skip(total - shown) {
                 emit(&mut out, row, &mut boundary_used);
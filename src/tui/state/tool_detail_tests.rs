@@ -32,7 +32,7 @@ fn subagent(num_steps: usize) -> SubagentDetail {
             .map(|i| SubagentStepSummary {
                 step_number: i + 1,
                 action_type: "tool_starting".into(),
-                description: format!("step {i}"),
+                description: format!("event {i}"),
             })
             .collect(),
         ..Default::default()
@@ -87,28 +87,43 @@ fn bash_boundary_marks_overflow_hint_when_present() {
 }
 
 #[test]
-fn subagent_shows_last_five_steps_with_bottom_ellipsis() {
-    let out = render(&subagent(8), false);
-    assert!(
-        out.starts_with("  ⎿ "),
-        "first step should carry boundary:\n{out}"
-    );
-    assert!(out.contains("step 7"));
-    assert!(!out.contains("step 0") && !out.contains("step 2"));
-    assert!(
-        out.trim_end().ends_with("..."),
-        "ellipsis goes last:\n{out}"
-    );
+fn subagent_collapsed_is_a_single_status_line() {
+    let mut detail = subagent(8);
+    detail.steps.push(SubagentStepSummary {
+        step_number: 9,
+        action_type: "step".into(),
+        description: "Step 9".into(),
+    });
+    let out = render(&detail, false);
+    assert_eq!(out, "  ⎿ step 9 · event 7 (+8 events · ctrl+o to expand)");
 }
 
 #[test]
-fn subagent_no_ellipsis_when_within_window() {
-    let out = render(&subagent(3), false);
-    assert!(!out.contains("..."), "unexpected ellipsis:\n{out}");
-    assert_eq!(out.lines().count(), 3);
+fn subagent_collapsed_without_events_renders_nothing() {
+    assert!(render(&subagent(0), false).is_empty());
+    assert_eq!(render(&subagent(1), false), "  ⎿ step 1 · event 0");
 }
 
 #[test]
-fn subagent_ignores_expansion() {
-    assert_eq!(render(&subagent(8), false), render(&subagent(8), true));
+fn subagent_expanded_streams_every_event() {
+    let out = render(&subagent(8), true);
+    assert_eq!(out.lines().count(), 8);
+    assert!(out.starts_with("  ⎿ [1] event 0"), "oldest first:\n{out}");
+    assert!(out.contains("[8] event 7"));
+}
+
+#[test]
+fn subagent_verbose_streams_without_ctrl_o_and_keeps_a_window() {
+    let detail = SubagentDetail {
+        verbose: true,
+        ..subagent(35)
+    };
+    let out = render(&detail, false);
+    assert_eq!(out, render(&detail, true));
+    assert!(
+        out.starts_with("  ⎿ ... (+5 earlier events)"),
+        "overflow on top:\n{out}"
+    );
+    assert!(!out.contains("ctrl+o"));
+    assert!(out.contains("[35] event 34"));
 }
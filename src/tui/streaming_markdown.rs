@@ -0,0 +1,352 @@
+use ratatui::text::Line;
+
+use crate::tui::markdown::{DEFAULT_TABLE_WIDTH, MarkdownRenderer};
+
+/// Incremental markdown for a response that is still streaming in.
+///
+/// Re-rendering the whole message on every delta is quadratic in its length,
+/// so text is split into blocks at blank lines instead: once a block is known
+/// to be finished it is handed back to the caller to commit to the transcript,
+/// and only the unfinished tail is re-rendered as new deltas arrive. Blank
+/// lines inside a fenced code block never end a block, so a fence is always
+/// committed whole.
+pub struct StreamingMarkdown {
+    renderer: MarkdownRenderer,
+    /// Text already handed back as completed blocks, verbatim.
+    committed: String,
+    /// Text received since the last committed block.
+    pending: String,
+    /// Offset in `pending` of the first line not yet scanned for boundaries.
+    scanned: usize,
+    /// Offset in `pending` of a blank line that may end the current block.
+    blank_at: Option<usize>,
+    fence: Option<Fence>,
+    tail_lines: Vec<Line<'static>>,
+    table_width: usize,
+}
+
+/// An open code fence: the marker character and how many of them opened it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    fn open(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == marker).count();
+        if len < 3 {
+            return None;
+        }
+        // A backtick fence's info string may not itself contain backticks,
+        // otherwise the line is inline code.
+        if marker == '`' && trimmed[len..].contains('`') {
+            return None;
+        }
+        Some(Self { marker, len })
+    }
+
+    fn closes(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.chars().count() >= self.len && trimmed.chars().all(|c| c == self.marker)
+    }
+}
+
+impl StreamingMarkdown {
+    pub fn new() -> Self {
+        Self {
+            renderer: MarkdownRenderer::new(),
+            committed: String::new(),
+            pending: String::new(),
+            scanned: 0,
+            blank_at: None,
+            fence: None,
+            tail_lines: Vec::new(),
+            table_width: DEFAULT_TABLE_WIDTH,
+        }
+    }
+
+    /// Width tables in the tail are fitted to; takes effect on the next push.
+    pub fn set_table_width(&mut self, width: usize) {
+        self.table_width = width;
+    }
+
+    /// Drop everything from the previous stream.
+    pub fn reset(&mut self) {
+        self.committed.clear();
+        self.pending.clear();
+        self.scanned = 0;
+        self.blank_at = None;
+        self.fence = None;
+        self.tail_lines.clear();
+    }
+
+    pub fn has_committed(&self) -> bool {
+        !self.committed.is_empty()
+    }
+
+    /// Append a delta and return any blocks it completed, in order. The tail
+    /// is re-rendered once per call regardless of how many blocks completed.
+    pub fn push(&mut self, delta: &str) -> Vec<String> {
+        self.pending.push_str(delta);
+        let mut blocks = Vec::new();
+
+        while let Some(newline) = self.pending[self.scanned..].find('\n') {
+            let start = self.scanned;
+            let end = start + newline;
+            self.scanned = end + 1;
+            let line = &self.pending[start..end];
+
+            if let Some(fence) = self.fence {
+                if fence.closes(line) {
+                    self.fence = None;
+                }
+                continue;
+            }
+
+            if line.trim().is_empty() {
+                self.blank_at.get_or_insert(start);
+                continue;
+            }
+
+            // An indented line after a blank one continues the block (a list
+            // item's second paragraph, an indented code block), so only a
+            // line starting in column zero proves the previous block is done.
+            let continues_block = line.starts_with([' ', '\t']);
+            let opens = Fence::open(line);
+            if let Some(blank) = self.blank_at.take()
+                && !continues_block
+            {
+                let block = self.pending[..blank].trim_matches('\n');
+                if !block.is_empty() {
+                    blocks.push(block.to_string());
+                }
+                self.committed.push_str(&self.pending[..start]);
+                self.pending.drain(..start);
+                self.scanned -= start;
+            }
+            self.fence = opens;
+        }
+
+        self.render_tail();
+        blocks
+    }
+
+    /// The uncommitted text as it should be shown right now. A trailing line
+    /// that is still arriving and starts with a fence marker is held back: a
+    /// half-typed "``" renders as stray inline code and "```ru" would pick
+    /// the wrong highlighter until the info string is complete.
+    pub fn tail(&self) -> &str {
+        let visible = &self.pending[..self.scanned];
+        let partial = &self.pending[self.scanned..];
+        if partial.trim_start().starts_with(['`', '~']) {
+            visible
+        } else {
+            &self.pending
+        }
+    }
+
+    /// Rendered lines for the uncommitted tail, refreshed on every `push`.
+    pub fn tail_lines(&self) -> &[Line<'static>] {
+        &self.tail_lines
+    }
+
+    /// End the stream given the final text of the message. Returns the part
+    /// of `full` not yet committed when earlier blocks were committed from
+    /// this same text, or `None` when nothing was committed (or the final text
+    /// doesn't continue what was streamed) and `full` should be shown whole.
+    pub fn finish<'a>(&mut self, full: &'a str) -> Option<&'a str> {
+        let remainder = if self.has_committed() {
+            full.strip_prefix(self.committed.as_str())
+        } else {
+            None
+        };
+        self.reset();
+        remainder
+    }
+
+    fn render_tail(&mut self) {
+        let tail = self.tail().trim_matches('\n');
+        self.tail_lines = if tail.trim().is_empty() {
+            Vec::new()
+        } else {
+            self.renderer
+                .render_with_indent(tail, "  ", self.table_width)
+        };
+    }
+}
+
+impl Default for StreamingMarkdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(chunks: &[&str]) -> (StreamingMarkdown, Vec<String>) {
+        let mut streaming = StreamingMarkdown::new();
+        let mut blocks = Vec::new();
+        for chunk in chunks {
+            blocks.extend(streaming.push(chunk));
+        }
+        (streaming, blocks)
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_blocks_commit_once_the_next_block_starts() {
+        let (streaming, blocks) = stream(&["First para", "graph.\n\nSec", "ond one.\n"]);
+
+        assert_eq!(blocks, vec!["First paragraph.".to_string()]);
+        assert_eq!(streaming.tail(), "Second one.\n");
+    }
+
+    #[test]
+    fn test_blank_lines_inside_a_fence_do_not_split_it() {
+        let (streaming, blocks) = stream(&["```rust\nfn a() {}\n\n", "fn b() {}\n```\n\nAfter.\n"]);
+
+        assert_eq!(
+            blocks,
+            vec!["```rust\nfn a() {}\n\nfn b() {}\n```".to_string()]
+        );
+        assert_eq!(streaming.tail(), "After.\n");
+    }
+
+    #[test]
+    fn test_indented_continuation_stays_in_the_block() {
+        let (_, blocks) = stream(&["- item\n\n  more of the item\n"]);
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn test_partial_fence_opener_is_held_back() {
+        let (streaming, _) = stream(&["Intro.\n\n``"]);
+        assert_eq!(streaming.tail(), "Intro.\n\n");
+
+        let (streaming, _) = stream(&["Intro.\n\n```ru"]);
+        assert_eq!(streaming.tail(), "Intro.\n\n");
+        let rendered: Vec<String> = streaming.tail_lines().iter().map(line_text).collect();
+        assert!(
+            rendered.iter().all(|l| !l.contains("┌─")),
+            "no code block should render yet: {rendered:?}"
+        );
+    }
+
+    #[test]
+    fn test_open_fence_renders_as_code_with_final_language() {
+        let (streaming, blocks) = stream(&["```python\nx = 1\n", "y = 2"]);
+
+        assert!(blocks.is_empty());
+        let rendered: Vec<String> = streaming.tail_lines().iter().map(line_text).collect();
+        assert!(
+            rendered.iter().any(|l| l.contains("┌─ python")),
+            "got: {rendered:?}"
+        );
+        assert!(
+            rendered.iter().any(|l| l.contains("y = 2")),
+            "got: {rendered:?}"
+        );
+    }
+
+    #[test]
+    fn test_finish_returns_only_the_uncommitted_remainder() {
+        let full = "One.\n\nTwo.\n\nThree.\n";
+        let mut streaming = StreamingMarkdown::new();
+        let blocks: Vec<String> = full
+            .as_bytes()
+            .chunks(3)
+            .flat_map(|c| streaming.push(std::str::from_utf8(c).unwrap()))
+            .collect();
+
+        assert_eq!(blocks, vec!["One.".to_string(), "Two.".to_string()]);
+        assert_eq!(streaming.finish(full), Some("Three.\n"));
+        assert!(!streaming.has_committed());
+    }
+
+    #[test]
+    fn test_finish_without_commits_or_with_diverging_text_is_none() {
+        let mut streaming = StreamingMarkdown::new();
+        streaming.push("partial");
+        assert_eq!(streaming.finish("the complete answer"), None);
+
+        streaming.push("One.\n\nTwo.\n");
+        assert_eq!(streaming.finish("Something else entirely"), None);
+    }
+
+    #[test]
+    fn test_committed_blocks_render_like_the_whole_message() {
+        let full = "# Title\n\nSome *text*.\n\n```rust\nfn main() {}\n```\n\n- a\n- b\n";
+        let renderer = MarkdownRenderer::new();
+        let mut streaming = StreamingMarkdown::new();
+        let mut lines = Vec::new();
+        for ch in full.chars() {
+            for block in streaming.push(&ch.to_string()) {
+                lines.extend(renderer.render(&block));
+            }
+        }
+        lines.extend(renderer.render(streaming.finish(full).unwrap()));
+
+        let incremental: Vec<String> = lines.iter().map(line_text).collect();
+        let whole: Vec<String> = renderer.render(full).iter().map(line_text).collect();
+        assert_eq!(
+            incremental
+                .iter()
+                .filter(|l| !l.trim().is_empty())
+                .collect::<Vec<_>>(),
+            whole
+                .iter()
+                .filter(|l| !l.trim().is_empty())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_incremental_rendering_rerenders_far_fewer_bytes() {
+        let mut full = String::new();
+        for i in 0..60 {
+            full.push_str(&format!(
+                "Paragraph {i} with some **bold** text and `code` in it.\n\n"
+            ));
+            if i % 10 == 0 {
+                full.push_str("```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\n");
+            }
+        }
+        let deltas: Vec<&str> = full
+            .as_bytes()
+            .chunks(16)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect();
+
+        // Re-rendering everything on each delta renders every prefix.
+        let mut naive = 0;
+        let mut so_far = 0;
+        for delta in &deltas {
+            so_far += delta.len();
+            naive += so_far;
+        }
+
+        // Streaming renders the tail on each delta and each block once.
+        let mut incremental = 0;
+        let mut longest_tail = 0;
+        let mut streaming = StreamingMarkdown::new();
+        for delta in &deltas {
+            incremental += streaming.push(delta).iter().map(String::len).sum::<usize>();
+            incremental += streaming.tail().len();
+            longest_tail = longest_tail.max(streaming.tail().len());
+        }
+
+        assert!(longest_tail < 200, "tail grew to {longest_tail} bytes");
+        assert!(
+            incremental * 10 < naive,
+            "incremental rendered {incremental} bytes, naive {naive}"
+        );
+    }
+}
@@ -1,8 +1,13 @@
 use crate::tui::terminal::Terminal;
 use anyhow::Result;
 use crossterm::ExecutableCommand;
-use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture,
+};
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
 use ratatui::{TerminalOptions, Viewport};
@@ -17,6 +22,7 @@ pub fn init_terminal() -> Result<HooshTerminal> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnableBracketedPaste)?;
+    stdout.execute(EnableFocusChange)?;
 
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::with_options(
@@ -31,12 +37,44 @@ pub fn init_terminal() -> Result<HooshTerminal> {
 /// Restores the terminal to its normal state.
 pub fn restore_terminal(mut terminal: HooshTerminal) -> Result<()> {
     let mut stdout = io::stdout();
+    stdout.execute(DisableFocusChange)?;
     stdout.execute(DisableBracketedPaste)?;
     disable_raw_mode()?;
     terminal.show_cursor()?;
     Ok(())
 }
 
+/// Hand the terminal to a child process such as an editor for the duration
+/// of `run`, then take it back and force a full redraw. `alternate_screen` is
+/// set in fullview mode, which also captures the mouse.
+pub fn with_suspended_terminal<T>(
+    terminal: &mut HooshTerminal,
+    alternate_screen: bool,
+    run: impl FnOnce() -> T,
+) -> Result<T> {
+    let mut stdout = io::stdout();
+    stdout.execute(DisableFocusChange)?;
+    stdout.execute(DisableBracketedPaste)?;
+    if alternate_screen {
+        stdout.execute(DisableMouseCapture)?;
+        stdout.execute(LeaveAlternateScreen)?;
+    }
+    disable_raw_mode()?;
+    terminal.show_cursor()?;
+
+    let result = run();
+
+    enable_raw_mode()?;
+    if alternate_screen {
+        stdout.execute(EnterAlternateScreen)?;
+        stdout.execute(EnableMouseCapture)?;
+    }
+    stdout.execute(EnableBracketedPaste)?;
+    stdout.execute(EnableFocusChange)?;
+    terminal.clear()?;
+    Ok(result)
+}
+
 /// Dynamically resizes the inline viewport based on UI needs and draws the frame.
 pub fn resize_terminal(terminal: &mut HooshTerminal, height: u16) -> Result<()> {
     // Get the current size of the physical terminal window.
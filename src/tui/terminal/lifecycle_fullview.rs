@@ -2,7 +2,8 @@ use crate::tui::terminal::Terminal;
 use anyhow::Result;
 use crossterm::ExecutableCommand;
 use crossterm::event::{
-    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture,
 };
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
@@ -17,8 +18,10 @@ pub fn init_terminal_fullview() -> Result<HooshTerminal> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnterAlternateScreen)?;
+    crate::session_files::crash::set_alternate_screen(true);
     stdout.execute(EnableBracketedPaste)?;
     stdout.execute(EnableMouseCapture)?;
+    stdout.execute(EnableFocusChange)?;
 
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::with_options(
@@ -32,9 +35,11 @@ pub fn init_terminal_fullview() -> Result<HooshTerminal> {
 
 pub fn restore_terminal_fullview(mut terminal: HooshTerminal) -> Result<()> {
     let mut stdout = io::stdout();
+    stdout.execute(DisableFocusChange)?;
     stdout.execute(DisableMouseCapture)?;
     stdout.execute(DisableBracketedPaste)?;
     stdout.execute(LeaveAlternateScreen)?;
+    crate::session_files::crash::set_alternate_screen(false);
     disable_raw_mode()?;
     terminal.show_cursor()?;
     Ok(())
@@ -1,7 +1,9 @@
 use crate::tui::terminal::Terminal;
 use anyhow::Result;
 use crossterm::ExecutableCommand;
-use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
+};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
@@ -14,6 +16,7 @@ pub fn init_terminal_inline() -> Result<HooshTerminal> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnableBracketedPaste)?;
+    stdout.execute(EnableFocusChange)?;
 
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::with_options(
@@ -27,6 +30,7 @@ pub fn init_terminal_inline() -> Result<HooshTerminal> {
 
 pub fn restore_terminal_inline(mut terminal: HooshTerminal) -> Result<()> {
     let mut stdout = io::stdout();
+    stdout.execute(DisableFocusChange)?;
     stdout.execute(DisableBracketedPaste)?;
     disable_raw_mode()?;
     terminal.show_cursor()?;
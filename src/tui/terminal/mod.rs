@@ -8,3 +8,4 @@ pub use lifecycle::HooshTerminal;
 pub use lifecycle::init_terminal;
 pub use lifecycle::resize_terminal;
 pub use lifecycle::restore_terminal;
+pub use lifecycle::with_suspended_terminal;
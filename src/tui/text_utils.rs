@@ -12,6 +12,25 @@ pub fn strip_ansi_codes(text: &str) -> String {
     regex.replace_all(text, "").to_string()
 }
 
+/// Compact duration for status lines: `42s`, then `3m7s`.
+pub fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let total_secs = elapsed.as_secs();
+    if total_secs < 60 {
+        format!("{}s", total_secs)
+    } else {
+        format!("{}m{}s", total_secs / 60, total_secs % 60)
+    }
+}
+
+/// Compact token count: `950`, then `1.2k`.
+pub fn format_token_count(tokens: usize) -> String {
+    if tokens >= 1000 {
+        format!("{:.1}k", tokens as f64 / 1000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,4 +67,12 @@ mod tests {
     fn test_strip_ansi_codes_empty() {
         assert_eq!(strip_ansi_codes(""), "");
     }
+
+    #[test]
+    fn test_format_elapsed() {
+        use std::time::Duration;
+        assert_eq!(format_elapsed(Duration::from_millis(900)), "0s");
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(187)), "3m7s");
+    }
 }
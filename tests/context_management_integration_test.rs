@@ -71,12 +71,14 @@ async fn test_strategy_execution_order() {
             head_length: 800,
             tail_length: 200,
             preserve_last_tool_result: true,
+            ..Default::default()
         }),
         sliding_window: Some(SlidingWindowConfig {
             preserve_system: false,
             preserve_initial_task: false,
             max_tokens: 4000,
         }),
+        ..Default::default()
     };
 
     let mut manager_builder = ContextManager::new(config.clone(), Arc::clone(&accountant));
@@ -184,6 +186,7 @@ async fn test_pressure_recalculation_after_compression() {
             preserve_initial_task: false,
             max_tokens: 25_000,
         }),
+        ..Default::default()
     };
 
     let mut manager_builder = ContextManager::new(config.clone(), Arc::clone(&accountant));
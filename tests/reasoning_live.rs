@@ -87,11 +87,13 @@ async fn live_bedrock_adaptive_display_surfaces_reasoning() {
             .clone()
             .unwrap_or_else(|| "/chat/completions".to_string()),
         temperature: bc.temperature,
+        top_p: bc.top_p,
         pricing_endpoint: bc.pricing_endpoint.clone(),
         thinking_budget: None,
         reasoning_effort: Some(ReasoningEffort::High),
         reasoning_display: Some(ReasoningDisplay::Summarized),
         streaming: true,
+        ..Default::default()
     })
     .expect("backend");
 